@@ -0,0 +1,83 @@
+//! Whole-file decode timings on the bundled samples, one group per stage.
+
+use std::hint::black_box;
+
+use _core::core::config::ParseConfig;
+use _core::dwg::decoder::Decoder;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+/// At least one sample per container format: R2000 (in place), R2004 (paged),
+/// R2007 (R21 pages), plus the R2010+ object layout.
+const SAMPLES: [&str; 6] = [
+    "line_2000.dwg",
+    "insert_2004.dwg",
+    "polyline2d_line_2007.dwg",
+    "line_2013.dwg",
+    "acadsharp/sample_AC1027.dwg",
+    "acadsharp/sample_AC1032.dwg",
+];
+
+fn samples() -> Vec<(&'static str, Vec<u8>)> {
+    SAMPLES
+        .iter()
+        .map(|name| {
+            let path = format!("{}/../test_dwg/{name}", env!("CARGO_MANIFEST_DIR"));
+            let bytes = std::fs::read(&path).unwrap_or_else(|err| panic!("{path}: {err}"));
+            (*name, bytes)
+        })
+        .collect()
+}
+
+fn load_sections(c: &mut Criterion) {
+    let mut group = c.benchmark_group("load_sections");
+    for (name, bytes) in samples() {
+        group.throughput(Throughput::Bytes(bytes.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &bytes, |b, bytes| {
+            b.iter(|| {
+                let decoder = Decoder::new(bytes, ParseConfig::default()).unwrap();
+                let directory = decoder.section_directory().unwrap();
+                for index in 0..directory.records.len() {
+                    black_box(decoder.load_section_by_index(&directory, index).unwrap());
+                }
+            });
+        });
+    }
+    group.finish();
+}
+
+fn build_object_index(c: &mut Criterion) {
+    let mut group = c.benchmark_group("build_object_index");
+    for (name, bytes) in samples() {
+        group.throughput(Throughput::Bytes(bytes.len() as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &bytes, |b, bytes| {
+            // A fresh decoder each time: the index is cached once built.
+            b.iter(|| {
+                let decoder = Decoder::new(bytes, ParseConfig::default()).unwrap();
+                black_box(decoder.build_object_index().unwrap().objects.len())
+            });
+        });
+    }
+    group.finish();
+}
+
+fn decode_all_entities(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode_all_entities");
+    for (name, bytes) in samples() {
+        let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+        // Leave the object map and objects section out of the measurement.
+        let objects = decoder.build_object_index().unwrap().objects.len();
+        group.throughput(Throughput::Elements(objects as u64));
+        group.bench_function(BenchmarkId::from_parameter(name), |b| {
+            b.iter(|| black_box(decoder.entities().filter(Result::is_ok).count()));
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    load_sections,
+    build_object_index,
+    decode_all_entities
+);
+criterion_main!(benches);
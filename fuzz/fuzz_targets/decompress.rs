@@ -0,0 +1,26 @@
+#![no_main]
+
+use _core::core::config::ParseConfig;
+use _core::dwg::compression::{decode_reed_solomon, decompress_r18, decompress_r21};
+use libfuzzer_sys::fuzz_target;
+
+// The first two bytes pick the declared output size; the rest is the
+// compressed stream. Any input may fail, but none may panic.
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 2 {
+        return;
+    }
+    let dst_size = u16::from_le_bytes([data[0], data[1]]) as usize;
+    let src = &data[2..];
+    let config = ParseConfig::default();
+
+    if let Ok(out) = decompress_r18(src, dst_size, &config) {
+        assert_eq!(out.len(), dst_size);
+    }
+    if let Ok(out) = decompress_r21(src, dst_size, &config) {
+        assert_eq!(out.len(), dst_size);
+    }
+    let k = (data[0] as usize % 255) + 1;
+    let block_count = (data[1] as usize % 8) + 1;
+    let _ = decode_reed_solomon(src, k, block_count, 4);
+});
@@ -1,32 +1,162 @@
 #![allow(clippy::useless_conversion)] // Triggered by PyO3 #[pyfunction] wrapper expansion.
 
-use pyo3::exceptions::{PyIOError, PyNotImplementedError, PyValueError};
+use pyo3::exceptions::{PyException, PyIOError, PyNotImplementedError, PyTypeError, PyValueError};
 use pyo3::prelude::*;
+use pyo3::types::{PyByteArray, PyBytes, PyDict};
+use std::borrow::Cow;
+use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
 
 use crate::bit::{BitReader, Endian};
+use crate::core::color;
+use crate::core::config::{ParseConfig, ParseConfigBuilder, SpaceFilter};
+use crate::core::crc;
 use crate::core::error::{DwgError, ErrorKind};
+use crate::core::geometry::{self, points_equal_3d};
+use crate::core::progress::Progress;
 use crate::dwg::decoder;
+use crate::dwg::entity_dispatch::{
+    decode_3dface_for_version, decode_arc_for_version, decode_attdef_for_version,
+    decode_attrib_for_version, decode_circle_for_version, decode_dim_diameter_for_version,
+    decode_dim_linear_for_version, decode_dim_radius_for_version, decode_ellipse_for_version,
+    decode_hatch_for_version, decode_insert_for_version, decode_leader_for_version,
+    decode_line_for_version, decode_lwpolyline_for_version, decode_mline_for_version,
+    decode_mtext_for_version, decode_point_for_version, decode_polyline_2d_for_version,
+    decode_polyline_3d_for_version, decode_polyline_mesh_for_version,
+    decode_polyline_pface_for_version, decode_shape_for_version, decode_solid_for_version,
+    decode_spline_for_version, decode_text_for_version, decode_tolerance_for_version,
+    decode_trace_for_version, decode_vertex_3d_for_version, decode_vertex_pface_face_for_version,
+    parse_entity_owner_for_version, parse_object_header_for_version,
+    resolve_r2010_object_data_end_bit, resolve_r2010_object_data_end_bit_candidates,
+    skip_object_type_prefix, EntityOwner, ObjectHeader,
+};
 use crate::dwg::file_open;
 use crate::dwg::version;
 use crate::entities;
-use crate::objects;
+use crate::high_level::{
+    self, is_best_effort_compat_version, is_recoverable_decode_error, matches_type_name,
+    AdjacentChild, ChildWalk,
+};
+use crate::objects::{self, decode_layer_color_record, decode_layer_state_record, LayerState};
+
+pyo3::create_exception!(_core, UnsupportedEntityError, PyValueError);
+pyo3::create_exception!(_core, Cancelled, PyException);
+
+/// Input accepted wherever the raw API takes `path`: a filesystem path
+/// (`str` or `os.PathLike`), a bytes-like object, a binary file-like
+/// object, or one of those wrapped by `configure`. File-like objects are
+/// read to the end up front because the DWG container cannot be parsed
+/// incrementally.
+#[derive(Debug, Clone)]
+pub struct DwgSource {
+    input: SourceInput,
+    /// What the file is decoded with; the default unless wrapped by `configure`.
+    config: ParseConfig,
+}
+
+#[derive(Debug, Clone)]
+enum SourceInput {
+    Path(PathBuf),
+    Bytes(Vec<u8>),
+}
+
+impl DwgSource {
+    fn new(input: SourceInput) -> Self {
+        Self {
+            input,
+            config: ParseConfig::default(),
+        }
+    }
+
+    fn read_bytes(&self) -> crate::core::result::Result<Cow<'_, [u8]>> {
+        match &self.input {
+            SourceInput::Path(path) => Ok(Cow::Owned(file_open::read_file(path)?)),
+            SourceInput::Bytes(bytes) => Ok(Cow::Borrowed(bytes.as_slice())),
+        }
+    }
+
+    fn read_version_tag(&self) -> crate::core::result::Result<[u8; 6]> {
+        match &self.input {
+            SourceInput::Path(path) => file_open::read_version_tag(path),
+            SourceInput::Bytes(bytes) => bytes
+                .get(..6)
+                .and_then(|tag| <[u8; 6]>::try_from(tag).ok())
+                .ok_or_else(|| {
+                    DwgError::new(ErrorKind::Io, "unexpected EOF while reading version tag")
+                        .with_offset(bytes.len() as u64)
+                }),
+        }
+    }
+}
+
+impl<'py> FromPyObject<'py> for DwgSource {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(configured) = ob.downcast::<ConfiguredSource>() {
+            return Ok(configured.get().source.clone());
+        }
+        if let Ok(bytes) = ob.downcast::<PyBytes>() {
+            return Ok(Self::new(SourceInput::Bytes(bytes.as_bytes().to_vec())));
+        }
+        if let Ok(bytes) = ob.downcast::<PyByteArray>() {
+            return Ok(Self::new(SourceInput::Bytes(bytes.to_vec())));
+        }
+        if let Ok(path) = ob.extract::<PathBuf>() {
+            return Ok(Self::new(SourceInput::Path(path)));
+        }
+        if ob.hasattr("read")? {
+            let data = ob.call_method0("read")?;
+            return match data.downcast::<PyBytes>() {
+                Ok(bytes) => Ok(Self::new(SourceInput::Bytes(bytes.as_bytes().to_vec()))),
+                Err(_) => Err(PyTypeError::new_err(
+                    "file-like object must be opened in binary mode (read() returned non-bytes)",
+                )),
+            };
+        }
+        Err(PyTypeError::new_err(
+            "expected a path, bytes, or a binary file-like object",
+        ))
+    }
+}
+
+/// A source bound to a `ParseConfig`, built by `configure` and accepted
+/// wherever the raw API takes `path`.
+#[pyclass(frozen, module = "ezdwg._core")]
+pub struct ConfiguredSource {
+    source: DwgSource,
+}
 
 type Point2 = (f64, f64);
 type Point3 = (f64, f64, f64);
 
 type SectionLocatorRow = (String, u32, u32);
+type SectionCrcRow = (String, u16);
+type AuxHeaderRow = (u16, u32, (u16, u32), u32, f64, f64, Option<u32>);
+type SectionDetailRow = (String, u64, u64, u32, u32, u32);
+type AnomalyRow = (String, String, Option<u64>);
 type ObjectMapEntryRow = (u64, u32);
 type ObjectHeaderRow = (u64, u32, u32, u16);
 type ObjectHeaderWithTypeRow = (u64, u32, u32, u16, String, String);
+type ObjectTypeHistogramRow = (u16, String, usize);
 type ObjectRecordBytesRow = (u64, u32, u32, u16, Vec<u8>);
-type EntityStyleRow = (u64, Option<u16>, Option<u32>, u64);
+type EntityStyleRow = (u64, Option<u16>, Option<u32>, Option<u32>, u64);
+type EntityLineStyleRow = (u64, f64, Option<i8>);
 type LayerColorRow = (u64, u16, Option<u32>);
+type LayerStateRow = (u64, bool, bool, bool, bool);
 
 type LineEntityRow = (u64, f64, f64, f64, f64, f64, f64);
-type PointEntityRow = (u64, f64, f64, f64, f64);
+type LineChainRow = (Vec<u64>, bool, Vec<Point3>);
+type EntityBoundsRow = (u64, (f64, f64), (f64, f64));
+type ObjectSummaryRow = (
+    u64,
+    String,
+    Option<String>,
+    Option<((f64, f64), (f64, f64))>,
+);
+type PointEntityRow = (u64, f64, f64, f64, f64, f64);
 type ArcEntityRow = (u64, f64, f64, f64, f64, f64, f64);
-type CircleEntityRow = (u64, f64, f64, f64, f64);
+type ArcSweepRow = (u64, f64, f64, f64, f64, f64, f64, f64, Point3);
+type CircleEntityRow = (u64, f64, f64, f64, f64, f64, Point3);
 type LineArcCircleRows = (Vec<LineEntityRow>, Vec<ArcEntityRow>, Vec<CircleEntityRow>);
 type EllipseEntityRow = (u64, Point3, Point3, Point3, f64, f64, f64);
 type SplineFlagsRow = (u32, u32, bool, bool, bool);
@@ -40,6 +170,14 @@ type SplineEntityRow = (
     Vec<f64>,
     Vec<Point3>,
 );
+type SplinePointsRow = (
+    u64,
+    u32,
+    bool,
+    Vec<Point3>,
+    Option<Vec<Point3>>,
+    Option<Vec<f64>>,
+);
 type TextMetricsRow = (f64, f64, f64, f64, f64);
 type TextAlignmentRow = (u16, u16, u16);
 type TextEntityRow = (
@@ -114,8 +252,10 @@ type DimEntityRow = (
 );
 type DimTypedEntityRow = (String, DimEntityRow);
 type InsertEntityRow = (u64, f64, f64, f64, f64, f64, f64, f64);
+type InsertAttribRow = (u64, Option<String>, String);
+type InsertWithAttribsRow = (u64, f64, f64, f64, f64, f64, f64, f64, Vec<InsertAttribRow>);
 type MInsertEntityRow = (u64, f64, f64, f64, f64, f64, f64, f64, u16, u16, f64, f64);
-type Polyline2dEntityRow = (u64, u16, u16, f64, f64, f64, f64);
+type Polyline2dEntityRow = (u64, u16, u16, f64, f64, f64, f64, Option<Point3>);
 type Polyline2dInterpretedRow = (
     u64,
     u16,
@@ -130,7 +270,32 @@ type Polyline2dInterpretedRow = (
     bool,
     bool,
 );
-type LwPolylineEntityRow = (u64, u16, Vec<Point2>, Vec<f64>, Vec<Point2>, Option<f64>);
+type InsertTransformRow = (u64, Option<u64>, Option<String>, Vec<f64>);
+type InsertWithBlockRow = (u64, Point3, Point3, f64, Option<u64>, Option<String>);
+type LwPolylineEntityRow = (
+    u64,
+    u16,
+    LwPolylineVertices,
+    Vec<f64>,
+    Vec<Point2>,
+    Option<f64>,
+);
+
+/// LWPOLYLINE vertices: OCS `(x, y)` pairs by default, world `(x, y, z)`
+/// points with `wcs=True`.
+pub enum LwPolylineVertices {
+    Ocs(Vec<Point2>),
+    World(Vec<Point3>),
+}
+
+impl IntoPy<PyObject> for LwPolylineVertices {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        match self {
+            Self::Ocs(points) => points.into_py(py),
+            Self::World(points) => points.into_py(py),
+        }
+    }
+}
 type Polyline3dEntityRow = (u64, u8, u8);
 type Vertex3dEntityRow = (u64, u8, f64, f64, f64);
 type Polyline3dVerticesRow = (u64, u8, bool, Vec<Point3>);
@@ -157,6 +322,72 @@ type ShapeEntityRow = (
     Point3,
     Option<u64>,
 );
+type ProxyEntityRow = (
+    u64,
+    u32,
+    Option<String>,
+    bool,
+    usize,
+    Option<(Point3, Point3)>,
+);
+type ImageDefRow = (u64, Option<String>, (f64, f64), (f64, f64), u8);
+type ImageRow = (
+    u64,
+    Point3,
+    Point3,
+    Point3,
+    (f64, f64),
+    u16,
+    Vec<(f64, f64)>,
+    Option<ImageDefRow>,
+);
+type AppIdRow = (u64, Option<String>);
+type BlockRow = (u64, Option<String>, bool, usize, Point3);
+type DrawingStatsRow = (
+    String,
+    usize,
+    usize,
+    usize,
+    usize,
+    Vec<SectionDetailRow>,
+    u64,
+    u64,
+    Vec<(String, usize)>,
+);
+type XrefRow = (u64, Option<String>, Option<String>, &'static str);
+type EntityXDataRow = (u64, String, Vec<(u64, Vec<(u16, PyObject)>)>);
+type VportRow = (
+    u64,
+    Option<String>,
+    (f64, f64),
+    (f64, f64),
+    (f64, f64),
+    f64,
+    f64,
+    Point3,
+    Point3,
+    (bool, (f64, f64)),
+    (bool, (f64, f64), (f64, f64), f64),
+);
+type MLeaderRootRow = (Option<Point3>, Option<Point3>, f64, Vec<Vec<Point3>>);
+type MLeaderRow = (
+    u64,
+    &'static str,
+    Option<String>,
+    Option<Point3>,
+    Vec<MLeaderRootRow>,
+);
+type WipeoutRow = (u64, u16, bool, Vec<Point3>);
+type TextItemRow = (u64, &'static str, Option<String>, String, Point3);
+type TableRow = (
+    u64,
+    Point3,
+    usize,
+    usize,
+    Vec<f64>,
+    Vec<f64>,
+    Vec<Vec<String>>,
+);
 type PolylineVerticesRow = (u64, u16, Vec<Point3>);
 type PolylineInterpolatedRow = (u64, u16, bool, Vec<Point3>);
 type Vertex2dEntityRow = (u64, u16, f64, f64, f64, f64, f64, f64, f64);
@@ -164,16 +395,31 @@ type VertexDataRow = (f64, f64, f64, f64, f64, f64, f64, u16);
 type PolylineVertexDataRow = (u64, u16, Vec<VertexDataRow>);
 
 #[pyfunction]
-pub fn detect_version(path: &str) -> PyResult<String> {
-    let tag = file_open::read_version_tag(path).map_err(to_py_err)?;
+pub fn detect_version(path: DwgSource) -> PyResult<String> {
+    let tag = path.read_version_tag().map_err(to_py_err)?;
     let version = version::detect_version(&tag).map_err(to_py_err)?;
     Ok(version.as_str().to_string())
 }
 
+/// `(kind, version)`: `kind` is one of `dwg`, `dxf`, `dxf_binary`, `dwf` or
+/// `other`, and `version` the DWG release tag for `dwg` files.
+#[pyfunction]
+pub fn sniff(path: DwgSource) -> PyResult<(String, Option<String>)> {
+    let kind = match &path.input {
+        SourceInput::Path(path) => file_open::sniff(path).map_err(to_py_err)?,
+        SourceInput::Bytes(bytes) => version::sniff_bytes(bytes),
+    };
+    let version = match &kind {
+        version::FileKind::Dwg(version) => Some(version.as_str().to_string()),
+        _ => None,
+    };
+    Ok((kind.as_str().to_string(), version))
+}
+
 #[pyfunction]
-pub fn list_section_locators(path: &str) -> PyResult<Vec<SectionLocatorRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+pub fn list_section_locators(path: DwgSource) -> PyResult<Vec<SectionLocatorRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let directory = decoder.section_directory().map_err(to_py_err)?;
     let result = directory
         .records
@@ -187,9 +433,46 @@ pub fn list_section_locators(path: &str) -> PyResult<Vec<SectionLocatorRow>> {
 }
 
 #[pyfunction]
-pub fn read_section_bytes(path: &str, index: usize) -> PyResult<Vec<u8>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+pub fn list_sections_detailed(path: DwgSource) -> PyResult<Vec<SectionDetailRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let sections = decoder.list_sections().map_err(to_py_err)?;
+    Ok(sections
+        .into_iter()
+        .map(|section| {
+            (
+                section.name,
+                section.size,
+                section.compressed_size,
+                section.page_count,
+                section.encrypted,
+                section.compression,
+            )
+        })
+        .collect())
+}
+
+#[pyfunction]
+pub fn analyze(path: DwgSource) -> PyResult<Vec<AnomalyRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    Ok(
+        crate::dwg::analyze::analyze_bytes(&bytes, Default::default())
+            .into_iter()
+            .map(|anomaly| {
+                (
+                    anomaly.kind.as_str().to_string(),
+                    anomaly.message,
+                    anomaly.offset,
+                )
+            })
+            .collect(),
+    )
+}
+
+#[pyfunction]
+pub fn read_section_bytes(path: DwgSource, index: usize) -> PyResult<Vec<u8>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let directory = decoder.section_directory().map_err(to_py_err)?;
     let section = decoder
         .load_section_by_index(&directory, index)
@@ -197,13 +480,73 @@ pub fn read_section_bytes(path: &str, index: usize) -> PyResult<Vec<u8>> {
     Ok(section.data.as_ref().to_vec())
 }
 
+#[pyfunction]
+pub fn read_section_bytes_by_name(path: DwgSource, name: &str) -> PyResult<Vec<u8>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let directory = decoder.section_directory().map_err(to_py_err)?;
+    let section = decoder
+        .load_section_by_name(&directory, name)
+        .map_err(to_py_err)?;
+    Ok(section.data.as_ref().to_vec())
+}
+
+#[pyfunction]
+pub fn section_crcs(path: DwgSource) -> PyResult<Vec<SectionCrcRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    decoder.section_crcs().map_err(to_py_err)
+}
+
+#[pyfunction]
+pub fn aux_header(path: DwgSource) -> PyResult<AuxHeaderRow> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let header = decoder.aux_header().map_err(to_py_err)?;
+    Ok((
+        header.dwg_version,
+        header.maintenance_version,
+        header.created_version,
+        header.save_count,
+        header.created_julian(),
+        header.updated_julian(),
+        header.handle_seed,
+    ))
+}
+
+#[pyfunction]
+pub fn measurement_units(path: DwgSource) -> PyResult<Option<&'static str>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let measurement = decoder.measurement().map_err(to_py_err)?;
+    Ok(measurement.map(|measurement| measurement.as_str()))
+}
+
+#[pyfunction]
+pub fn read_thumbnail(path: DwgSource) -> PyResult<Option<(String, Cow<'static, [u8]>)>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let thumbnail = decoder.thumbnail().map_err(to_py_err)?;
+    Ok(thumbnail.map(|thumbnail| {
+        (
+            thumbnail.format.as_str().to_string(),
+            Cow::Owned(thumbnail.data),
+        )
+    }))
+}
+
+#[pyfunction(signature = (data, seed=0))]
+pub fn compute_crc(data: &[u8], seed: u16) -> u16 {
+    crc::compute_crc(data, seed)
+}
+
 #[pyfunction(signature = (path, limit=None))]
 pub fn list_object_map_entries(
-    path: &str,
+    path: DwgSource,
     limit: Option<usize>,
 ) -> PyResult<Vec<ObjectMapEntryRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let index = decoder.build_object_index().map_err(to_py_err)?;
     let mut entries: Vec<ObjectMapEntryRow> = index
         .objects
@@ -219,9 +562,12 @@ pub fn list_object_map_entries(
 }
 
 #[pyfunction(signature = (path, limit=None))]
-pub fn list_object_headers(path: &str, limit: Option<usize>) -> PyResult<Vec<ObjectHeaderRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+pub fn list_object_headers(
+    path: DwgSource,
+    limit: Option<usize>,
+) -> PyResult<Vec<ObjectHeaderRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let index = decoder.build_object_index().map_err(to_py_err)?;
     let mut result = Vec::new();
     for obj in index.objects.iter() {
@@ -240,11 +586,11 @@ pub fn list_object_headers(path: &str, limit: Option<usize>) -> PyResult<Vec<Obj
 
 #[pyfunction(signature = (path, limit=None))]
 pub fn list_object_headers_with_type(
-    path: &str,
+    path: DwgSource,
     limit: Option<usize>,
 ) -> PyResult<Vec<ObjectHeaderWithTypeRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let dynamic_types = decoder.dynamic_type_map().map_err(to_py_err)?;
     let index = decoder.build_object_index().map_err(to_py_err)?;
     let mut result = Vec::new();
@@ -271,17 +617,47 @@ pub fn list_object_headers_with_type(
     Ok(result)
 }
 
+#[pyfunction]
+pub fn object_type_histogram(path: DwgSource) -> PyResult<Vec<ObjectTypeHistogramRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let best_effort = is_best_effort_compat_version(&decoder);
+    let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
+    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let objects_data = decoder.objects_data().map_err(to_py_err)?;
+    let mut counts: HashMap<u16, usize> = HashMap::new();
+    for item in index.headers(&objects_data, decoder.version()) {
+        match item {
+            Ok((_, header)) => *counts.entry(header.type_code).or_insert(0) += 1,
+            Err(_) if best_effort => {}
+            Err(err) => return Err(to_py_err(err)),
+        }
+    }
+    let mut result: Vec<ObjectTypeHistogramRow> = counts
+        .into_iter()
+        .map(|(type_code, count)| {
+            (
+                type_code,
+                resolved_type_name(type_code, &dynamic_types),
+                count,
+            )
+        })
+        .collect();
+    result.sort_by(|a, b| b.2.cmp(&a.2).then(a.0.cmp(&b.0)));
+    Ok(result)
+}
+
 #[pyfunction(signature = (path, type_codes, limit=None))]
 pub fn list_object_headers_by_type(
-    path: &str,
+    path: DwgSource,
     type_codes: Vec<u16>,
     limit: Option<usize>,
 ) -> PyResult<Vec<ObjectHeaderWithTypeRow>> {
     if type_codes.is_empty() {
         return Ok(Vec::new());
     }
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let dynamic_types = decoder.dynamic_type_map().map_err(to_py_err)?;
     let filter: HashSet<u16> = type_codes.into_iter().collect();
     let index = decoder.build_object_index().map_err(to_py_err)?;
@@ -314,15 +690,15 @@ pub fn list_object_headers_by_type(
 
 #[pyfunction(signature = (path, type_codes, limit=None))]
 pub fn read_object_records_by_type(
-    path: &str,
+    path: DwgSource,
     type_codes: Vec<u16>,
     limit: Option<usize>,
 ) -> PyResult<Vec<ObjectRecordBytesRow>> {
     if type_codes.is_empty() {
         return Ok(Vec::new());
     }
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let dynamic_types = decoder.dynamic_type_map().map_err(to_py_err)?;
     let filter: HashSet<u16> = type_codes.into_iter().collect();
     let index = decoder.build_object_index().map_err(to_py_err)?;
@@ -352,14 +728,63 @@ pub fn read_object_records_by_type(
     Ok(result)
 }
 
+/// Records for `handles`, in the order given; handles the object map does
+/// not list are left out.
+#[pyfunction]
+pub fn read_object_records_by_handles(
+    path: DwgSource,
+    handles: Vec<u64>,
+) -> PyResult<Vec<ObjectRecordBytesRow>> {
+    if handles.is_empty() {
+        return Ok(Vec::new());
+    }
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let mut result = Vec::with_capacity(handles.len());
+    for handle in handles {
+        let Some(obj) = index.get(objects::Handle(handle)) else {
+            continue;
+        };
+        let record = decoder.parse_object_record(obj.offset).map_err(to_py_err)?;
+        let header =
+            parse_object_header_for_version(&record, decoder.version()).map_err(to_py_err)?;
+        result.push((
+            obj.handle.0,
+            obj.offset,
+            header.data_size,
+            header.type_code,
+            record.raw.as_ref().to_vec(),
+        ));
+    }
+    Ok(result)
+}
+
 #[pyfunction(signature = (path, limit=None))]
-pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<EntityStyleRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+pub fn decode_entity_line_styles(
+    path: DwgSource,
+    limit: Option<usize>,
+) -> PyResult<Vec<EntityLineStyleRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let styles = high_level::entity_line_styles(&decoder, limit).map_err(to_py_err)?;
+    Ok(styles
+        .into_iter()
+        .map(|style| (style.handle, style.ltype_scale, style.lineweight))
+        .collect())
+}
+
+#[pyfunction(signature = (path, limit=None))]
+pub fn decode_entity_styles(
+    path: DwgSource,
+    limit: Option<usize>,
+) -> PyResult<Vec<EntityStyleRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let best_effort = is_best_effort_compat_version(&decoder);
     let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
-    let decoded_layer_rows = decode_layer_colors(path, None)?;
+    let index = scoped_object_index(&decoder)?;
+    let decoded_layer_rows = decode_layer_colors(path.clone(), None)?;
     let decoded_layer_handles: Vec<u64> = decoded_layer_rows.iter().map(|(h, _, _)| *h).collect();
     let raw_layer_handles =
         collect_known_layer_handles_in_order(&decoder, &dynamic_types, &index, best_effort)?;
@@ -417,6 +842,7 @@ pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<En
                 entity.handle,
                 entity.color_index,
                 entity.true_color,
+                entity.transparency,
                 layer_handle,
             ));
         } else if matches_type_name(header.type_code, 0x1B, "POINT", &dynamic_types) {
@@ -446,6 +872,7 @@ pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<En
                 entity.handle,
                 entity.color_index,
                 entity.true_color,
+                entity.transparency,
                 layer_handle,
             ));
         } else if matches_type_name(header.type_code, 0x11, "ARC", &dynamic_types) {
@@ -472,6 +899,7 @@ pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<En
                 entity.handle,
                 entity.color_index,
                 entity.true_color,
+                entity.transparency,
                 layer_handle,
             ));
         } else if matches_type_name(header.type_code, 0x12, "CIRCLE", &dynamic_types) {
@@ -501,6 +929,7 @@ pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<En
                 entity.handle,
                 entity.color_index,
                 entity.true_color,
+                entity.transparency,
                 layer_handle,
             ));
         } else if matches_type_name(header.type_code, 0x23, "ELLIPSE", &dynamic_types) {
@@ -530,6 +959,7 @@ pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<En
                 entity.handle,
                 entity.color_index,
                 entity.true_color,
+                entity.transparency,
                 layer_handle,
             ));
         } else if matches_type_name(header.type_code, 0x24, "SPLINE", &dynamic_types) {
@@ -559,6 +989,7 @@ pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<En
                 entity.handle,
                 entity.color_index,
                 entity.true_color,
+                entity.transparency,
                 layer_handle,
             ));
         } else if matches_type_name(header.type_code, 0x01, "TEXT", &dynamic_types) {
@@ -588,6 +1019,7 @@ pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<En
                 entity.handle,
                 entity.color_index,
                 entity.true_color,
+                entity.transparency,
                 layer_handle,
             ));
         } else if matches_type_name(header.type_code, 0x02, "ATTRIB", &dynamic_types) {
@@ -617,6 +1049,7 @@ pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<En
                 entity.handle,
                 entity.color_index,
                 entity.true_color,
+                entity.transparency,
                 layer_handle,
             ));
         } else if matches_type_name(header.type_code, 0x03, "ATTDEF", &dynamic_types) {
@@ -646,6 +1079,7 @@ pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<En
                 entity.handle,
                 entity.color_index,
                 entity.true_color,
+                entity.transparency,
                 layer_handle,
             ));
         } else if matches_type_name(header.type_code, 0x2C, "MTEXT", &dynamic_types) {
@@ -675,6 +1109,7 @@ pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<En
                 entity.handle,
                 entity.color_index,
                 entity.true_color,
+                entity.transparency,
                 layer_handle,
             ));
         } else if matches_type_name(header.type_code, 0x2D, "LEADER", &dynamic_types) {
@@ -704,6 +1139,7 @@ pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<En
                 entity.handle,
                 entity.color_index,
                 entity.true_color,
+                entity.transparency,
                 layer_handle,
             ));
         } else if matches_type_name(header.type_code, 0x4E, "HATCH", &dynamic_types) {
@@ -733,6 +1169,7 @@ pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<En
                 entity.handle,
                 entity.color_index,
                 entity.true_color,
+                entity.transparency,
                 layer_handle,
             ));
         } else if matches_type_name(header.type_code, 0x2E, "TOLERANCE", &dynamic_types) {
@@ -762,6 +1199,7 @@ pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<En
                 entity.handle,
                 entity.color_index,
                 entity.true_color,
+                entity.transparency,
                 layer_handle,
             ));
         } else if matches_type_name(header.type_code, 0x2F, "MLINE", &dynamic_types) {
@@ -791,6 +1229,7 @@ pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<En
                 entity.handle,
                 entity.color_index,
                 entity.true_color,
+                entity.transparency,
                 layer_handle,
             ));
         } else if matches_type_name(header.type_code, 0x4D, "LWPOLYLINE", &dynamic_types) {
@@ -820,6 +1259,7 @@ pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<En
                 entity.handle,
                 entity.color_index,
                 entity.true_color,
+                entity.transparency,
                 layer_handle,
             ));
         } else if matches_type_name(header.type_code, 0x10, "POLYLINE_3D", &dynamic_types) {
@@ -849,6 +1289,7 @@ pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<En
                 entity.handle,
                 entity.color_index,
                 entity.true_color,
+                entity.transparency,
                 layer_handle,
             ));
         } else if matches_type_name(header.type_code, 0x1E, "POLYLINE_MESH", &dynamic_types) {
@@ -878,6 +1319,7 @@ pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<En
                 entity.handle,
                 entity.color_index,
                 entity.true_color,
+                entity.transparency,
                 layer_handle,
             ));
         } else if matches_type_name(header.type_code, 0x1D, "POLYLINE_PFACE", &dynamic_types) {
@@ -907,6 +1349,7 @@ pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<En
                 entity.handle,
                 entity.color_index,
                 entity.true_color,
+                entity.transparency,
                 layer_handle,
             ));
         } else if matches_type_name(header.type_code, 0x1C, "3DFACE", &dynamic_types) {
@@ -936,6 +1379,7 @@ pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<En
                 entity.handle,
                 entity.color_index,
                 entity.true_color,
+                entity.transparency,
                 layer_handle,
             ));
         } else if matches_type_name(header.type_code, 0x1F, "SOLID", &dynamic_types) {
@@ -965,6 +1409,7 @@ pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<En
                 entity.handle,
                 entity.color_index,
                 entity.true_color,
+                entity.transparency,
                 layer_handle,
             ));
         } else if matches_type_name(header.type_code, 0x20, "TRACE", &dynamic_types) {
@@ -994,6 +1439,7 @@ pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<En
                 entity.handle,
                 entity.color_index,
                 entity.true_color,
+                entity.transparency,
                 layer_handle,
             ));
         } else if matches_type_name(header.type_code, 0x21, "SHAPE", &dynamic_types) {
@@ -1023,39 +1469,15 @@ pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<En
                 entity.handle,
                 entity.color_index,
                 entity.true_color,
+                entity.transparency,
                 layer_handle,
             ));
-        } else if matches_type_name(header.type_code, 0x15, "DIM_LINEAR", &dynamic_types) {
-            let entity = match decode_dim_linear_for_version(
-                &mut reader,
-                decoder.version(),
-                &header,
-                obj.handle.0,
-            ) {
-                Ok(entity) => entity,
-                Err(err) if best_effort || is_recoverable_decode_error(&err) => continue,
-                Err(err) => return Err(to_py_err(err)),
-            };
-            let common = &entity.common;
-            let layer_handle = recover_entity_layer_handle_r2010_plus(
-                &record,
-                decoder.version(),
-                &header,
-                obj.handle.0,
-                common.layer_handle,
-                &known_layer_handles,
-            );
-            let layer_handle = layer_handle_remap
-                .get(&layer_handle)
-                .copied()
-                .unwrap_or(layer_handle);
-            result.push((
-                common.handle,
-                common.color_index,
-                common.true_color,
-                layer_handle,
-            ));
-        } else if matches_type_name(header.type_code, 0x14, "DIM_ORDINATE", &dynamic_types) {
+        } else if matches_type_name(header.type_code, 0x15, "DIM_LINEAR", &dynamic_types)
+            || matches_type_name(header.type_code, 0x14, "DIM_ORDINATE", &dynamic_types)
+            || matches_type_name(header.type_code, 0x16, "DIM_ALIGNED", &dynamic_types)
+            || matches_type_name(header.type_code, 0x17, "DIM_ANG3PT", &dynamic_types)
+            || matches_type_name(header.type_code, 0x18, "DIM_ANG2LN", &dynamic_types)
+        {
             let entity = match decode_dim_linear_for_version(
                 &mut reader,
                 decoder.version(),
@@ -1083,10 +1505,11 @@ pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<En
                 common.handle,
                 common.color_index,
                 common.true_color,
+                common.transparency,
                 layer_handle,
             ));
-        } else if matches_type_name(header.type_code, 0x16, "DIM_ALIGNED", &dynamic_types) {
-            let entity = match decode_dim_linear_for_version(
+        } else if matches_type_name(header.type_code, 0x1A, "DIM_DIAMETER", &dynamic_types) {
+            let entity = match decode_dim_diameter_for_version(
                 &mut reader,
                 decoder.version(),
                 &header,
@@ -1113,10 +1536,11 @@ pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<En
                 common.handle,
                 common.color_index,
                 common.true_color,
+                common.transparency,
                 layer_handle,
             ));
-        } else if matches_type_name(header.type_code, 0x17, "DIM_ANG3PT", &dynamic_types) {
-            let entity = match decode_dim_linear_for_version(
+        } else if matches_type_name(header.type_code, 0x19, "DIM_RADIUS", &dynamic_types) {
+            let entity = match decode_dim_radius_for_version(
                 &mut reader,
                 decoder.version(),
                 &header,
@@ -1143,102 +1567,55 @@ pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<En
                 common.handle,
                 common.color_index,
                 common.true_color,
+                common.transparency,
                 layer_handle,
             ));
-        } else if matches_type_name(header.type_code, 0x18, "DIM_ANG2LN", &dynamic_types) {
-            let entity = match decode_dim_linear_for_version(
-                &mut reader,
-                decoder.version(),
-                &header,
-                obj.handle.0,
-            ) {
-                Ok(entity) => entity,
+        } else {
+            continue;
+        }
+
+        if let Some(limit) = limit {
+            if result.len() >= limit {
+                break;
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+#[pyfunction(signature = (path, limit=None))]
+pub fn decode_layer_colors(path: DwgSource, limit: Option<usize>) -> PyResult<Vec<LayerColorRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let best_effort = is_best_effort_compat_version(&decoder);
+    let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
+    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let mut result = Vec::new();
+
+    for obj in index.objects.iter() {
+        let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
+        else {
+            continue;
+        };
+        if !matches_type_name(header.type_code, 0x33, "LAYER", &dynamic_types) {
+            continue;
+        }
+
+        let mut reader = record.bit_reader();
+        if let Err(err) = skip_object_type_prefix(&mut reader, decoder.version()) {
+            if best_effort {
+                continue;
+            }
+            return Err(to_py_err(err));
+        }
+        let (handle, color_index, true_color) =
+            match decode_layer_color_record(&mut reader, decoder.version(), obj.handle.0) {
+                Ok(decoded) => decoded,
                 Err(err) if best_effort || is_recoverable_decode_error(&err) => continue,
                 Err(err) => return Err(to_py_err(err)),
             };
-            let common = &entity.common;
-            let layer_handle = recover_entity_layer_handle_r2010_plus(
-                &record,
-                decoder.version(),
-                &header,
-                obj.handle.0,
-                common.layer_handle,
-                &known_layer_handles,
-            );
-            let layer_handle = layer_handle_remap
-                .get(&layer_handle)
-                .copied()
-                .unwrap_or(layer_handle);
-            result.push((
-                common.handle,
-                common.color_index,
-                common.true_color,
-                layer_handle,
-            ));
-        } else if matches_type_name(header.type_code, 0x1A, "DIM_DIAMETER", &dynamic_types) {
-            let entity = match decode_dim_diameter_for_version(
-                &mut reader,
-                decoder.version(),
-                &header,
-                obj.handle.0,
-            ) {
-                Ok(entity) => entity,
-                Err(err) if best_effort || is_recoverable_decode_error(&err) => continue,
-                Err(err) => return Err(to_py_err(err)),
-            };
-            let common = &entity.common;
-            let layer_handle = recover_entity_layer_handle_r2010_plus(
-                &record,
-                decoder.version(),
-                &header,
-                obj.handle.0,
-                common.layer_handle,
-                &known_layer_handles,
-            );
-            let layer_handle = layer_handle_remap
-                .get(&layer_handle)
-                .copied()
-                .unwrap_or(layer_handle);
-            result.push((
-                common.handle,
-                common.color_index,
-                common.true_color,
-                layer_handle,
-            ));
-        } else if matches_type_name(header.type_code, 0x19, "DIM_RADIUS", &dynamic_types) {
-            let entity = match decode_dim_radius_for_version(
-                &mut reader,
-                decoder.version(),
-                &header,
-                obj.handle.0,
-            ) {
-                Ok(entity) => entity,
-                Err(err) if best_effort || is_recoverable_decode_error(&err) => continue,
-                Err(err) => return Err(to_py_err(err)),
-            };
-            let common = &entity.common;
-            let layer_handle = recover_entity_layer_handle_r2010_plus(
-                &record,
-                decoder.version(),
-                &header,
-                obj.handle.0,
-                common.layer_handle,
-                &known_layer_handles,
-            );
-            let layer_handle = layer_handle_remap
-                .get(&layer_handle)
-                .copied()
-                .unwrap_or(layer_handle);
-            result.push((
-                common.handle,
-                common.color_index,
-                common.true_color,
-                layer_handle,
-            ));
-        } else {
-            continue;
-        }
-
+        result.push((handle, color_index, true_color));
         if let Some(limit) = limit {
             if result.len() >= limit {
                 break;
@@ -1250,261 +1627,245 @@ pub fn decode_entity_styles(path: &str, limit: Option<usize>) -> PyResult<Vec<En
 }
 
 #[pyfunction(signature = (path, limit=None))]
-pub fn decode_layer_colors(path: &str, limit: Option<usize>) -> PyResult<Vec<LayerColorRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
-    let best_effort = is_best_effort_compat_version(&decoder);
-    let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
-    let mut result = Vec::new();
-
-    for obj in index.objects.iter() {
-        let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
-        else {
-            continue;
-        };
-        if !matches_type_name(header.type_code, 0x33, "LAYER", &dynamic_types) {
-            continue;
-        }
-
-        let mut reader = record.bit_reader();
-        if let Err(err) = skip_object_type_prefix(&mut reader, decoder.version()) {
-            if best_effort {
-                continue;
-            }
-            return Err(to_py_err(err));
-        }
-        let (handle, color_index, true_color) =
-            match decode_layer_color_record(&mut reader, decoder.version(), obj.handle.0) {
-                Ok(decoded) => decoded,
-                Err(err) if best_effort || is_recoverable_decode_error(&err) => continue,
-                Err(err) => return Err(to_py_err(err)),
-            };
-        result.push((handle, color_index, true_color));
-        if let Some(limit) = limit {
-            if result.len() >= limit {
-                break;
-            }
-        }
+pub fn decode_layer_states(path: DwgSource, limit: Option<usize>) -> PyResult<Vec<LayerStateRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let mut states = collect_layer_states(&decoder)?;
+    if let Some(limit) = limit {
+        states.truncate(limit);
     }
-
-    Ok(result)
+    Ok(states
+        .into_iter()
+        .map(|(handle, state)| {
+            (
+                handle,
+                state.on,
+                state.frozen,
+                state.locked,
+                state.plottable,
+            )
+        })
+        .collect())
 }
 
-#[pyfunction(signature = (path, limit=None))]
-pub fn decode_line_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<LineEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
-    let best_effort = is_best_effort_compat_version(&decoder);
-    let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
+/// `(handle, state)` of every LAYER record, in object-index order.
+fn collect_layer_states(decoder: &decoder::Decoder<'_>) -> PyResult<Vec<(u64, LayerState)>> {
+    let best_effort = is_best_effort_compat_version(decoder);
+    let dynamic_types = load_dynamic_types(decoder, best_effort)?;
     let index = decoder.build_object_index().map_err(to_py_err)?;
     let mut result = Vec::new();
     for obj in index.objects.iter() {
-        let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
+        let Some((record, header)) = parse_record_and_header(decoder, obj.offset, best_effort)?
         else {
             continue;
         };
-        if !matches_type_name(header.type_code, 0x13, "LINE", &dynamic_types) {
+        if !matches_type_name(header.type_code, 0x33, "LAYER", &dynamic_types) {
             continue;
         }
         let mut reader = record.bit_reader();
-        if let Err(err) = skip_object_type_prefix(&mut reader, decoder.version()) {
-            if best_effort {
-                continue;
-            }
-            return Err(to_py_err(err));
-        }
-        let entity =
-            match decode_line_for_version(&mut reader, decoder.version(), &header, obj.handle.0) {
-                Ok(entity) => entity,
-                Err(err) if best_effort => continue,
-                Err(err) => return Err(to_py_err(err)),
-            };
-        result.push((
-            entity.handle,
-            entity.start.0,
-            entity.start.1,
-            entity.start.2,
-            entity.end.0,
-            entity.end.1,
-            entity.end.2,
-        ));
-        if let Some(limit) = limit {
-            if result.len() >= limit {
-                break;
+        let decoded = skip_object_type_prefix(&mut reader, decoder.version())
+            .and_then(|_| decode_layer_state_record(&mut reader, decoder.version()));
+        match decoded {
+            Ok((handle, state, _)) => {
+                // Same choice of handle as `decode_layer_colors`.
+                let handle = if handle != 0 { handle } else { obj.handle.0 };
+                result.push((handle, state));
             }
+            Err(err) if best_effort || is_recoverable_decode_error(&err) => continue,
+            Err(err) => return Err(to_py_err(err)),
         }
     }
     Ok(result)
 }
 
-#[pyfunction(signature = (path, limit=None))]
-pub fn decode_point_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<PointEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
-    let best_effort = is_best_effort_compat_version(&decoder);
-    let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
-    let mut result = Vec::new();
-    for obj in index.objects.iter() {
-        let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
-        else {
-            continue;
-        };
-        if !matches_type_name(header.type_code, 0x1B, "POINT", &dynamic_types) {
-            continue;
-        }
-        let mut reader = record.bit_reader();
-        if let Err(err) = skip_object_type_prefix(&mut reader, decoder.version()) {
-            if best_effort {
-                continue;
-            }
-            return Err(to_py_err(err));
-        }
-        let entity =
-            match decode_point_for_version(&mut reader, decoder.version(), &header, obj.handle.0) {
-                Ok(entity) => entity,
-                Err(err) if best_effort => {
-                    if std::env::var("EZDWG_DEBUG_POINT_DECODE")
-                        .ok()
-                        .is_some_and(|value| value != "0")
-                    {
-                        eprintln!(
-                            "[point-decode] handle={} type=0x{:X} offset={} error={}",
-                            obj.handle.0, header.type_code, obj.offset, err
-                        );
-                    }
-                    continue;
-                }
-                Err(err) => return Err(to_py_err(err)),
-            };
-        result.push((
-            entity.handle,
-            entity.location.0,
-            entity.location.1,
-            entity.location.2,
-            entity.x_axis_angle,
-        ));
-        if let Some(limit) = limit {
-            if result.len() >= limit {
-                break;
-            }
-        }
-    }
-    Ok(result)
+/// Handles of frozen or switched-off layers.
+fn hidden_layer_handles(decoder: &decoder::Decoder<'_>) -> PyResult<HashSet<u64>> {
+    Ok(collect_layer_states(decoder)?
+        .into_iter()
+        .filter(|(_, state)| state.is_hidden())
+        .map(|(handle, _)| handle)
+        .collect())
 }
 
 #[pyfunction(signature = (path, limit=None))]
-pub fn decode_3dface_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<Face3dEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
-    let best_effort = is_best_effort_compat_version(&decoder);
-    let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
-    let mut result = Vec::new();
-    for obj in index.objects.iter() {
-        let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
-        else {
-            continue;
-        };
-        if !matches_type_name(header.type_code, 0x1C, "3DFACE", &dynamic_types) {
-            continue;
-        }
-        let mut reader = record.bit_reader();
-        if let Err(err) = skip_object_type_prefix(&mut reader, decoder.version()) {
-            if best_effort {
-                continue;
-            }
-            return Err(to_py_err(err));
-        }
-        let entity = match decode_3dface_for_version(
-            &mut reader,
-            decoder.version(),
-            &header,
-            obj.handle.0,
-        ) {
-            Ok(entity) => entity,
-            Err(err) if best_effort => continue,
-            Err(err) => return Err(to_py_err(err)),
-        };
-        result.push((
-            entity.handle,
-            entity.p1,
-            entity.p2,
-            entity.p3,
-            entity.p4,
-            entity.invisible_edge_flags,
-        ));
-        if let Some(limit) = limit {
-            if result.len() >= limit {
-                break;
-            }
-        }
-    }
-    Ok(result)
+pub fn decode_line_entities(path: DwgSource, limit: Option<usize>) -> PyResult<Vec<LineEntityRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let entities = high_level::decode_line_entities(&decoder, limit).map_err(to_py_err)?;
+    Ok(entities
+        .into_iter()
+        .map(|entity| {
+            (
+                entity.handle,
+                entity.start.0,
+                entity.start.1,
+                entity.start.2,
+                entity.end.0,
+                entity.end.1,
+                entity.end.2,
+            )
+        })
+        .collect())
 }
 
-#[pyfunction(signature = (path, limit=None))]
-pub fn decode_arc_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<ArcEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
-    let best_effort = is_best_effort_compat_version(&decoder);
-    let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
-    let mut result = Vec::new();
-    for obj in index.objects.iter() {
-        let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
-        else {
-            continue;
-        };
-        if !matches_type_name(header.type_code, 0x11, "ARC", &dynamic_types) {
-            continue;
-        }
-        let mut reader = record.bit_reader();
-        if let Err(err) = skip_object_type_prefix(&mut reader, decoder.version()) {
-            if best_effort {
-                continue;
-            }
-            return Err(to_py_err(err));
-        }
-        let entity =
-            match decode_arc_for_version(&mut reader, decoder.version(), &header, obj.handle.0) {
-                Ok(entity) => entity,
-                Err(err) if best_effort => continue,
-                Err(err) => return Err(to_py_err(err)),
-            };
-        result.push((
-            entity.handle,
-            entity.center.0,
-            entity.center.1,
-            entity.center.2,
-            entity.radius,
-            entity.angle_start,
-            entity.angle_end,
-        ));
-        if let Some(limit) = limit {
-            if result.len() >= limit {
-                break;
-            }
-        }
+/// RGB of an AutoCAD Color Index; see [`crate::core::color::aci_to_rgb`].
+#[pyfunction]
+pub fn aci_to_rgb(index: u16) -> (u8, u8, u8) {
+    color::aci_to_rgb(index)
+}
+
+/// Chain LINE entities that share endpoints into `(handles, closed, points)`
+/// polylines. Endpoints join when they lie within `tolerance` drawing units of
+/// each other, by default the parser's point epsilon.
+#[pyfunction(signature = (path, tolerance=None))]
+pub fn merge_lines_to_polylines(
+    path: DwgSource,
+    tolerance: Option<f64>,
+) -> PyResult<Vec<LineChainRow>> {
+    let tolerance = tolerance.unwrap_or(path.config.point_epsilon);
+    if !(tolerance.is_finite() && tolerance > 0.0) {
+        return Err(PyValueError::new_err("tolerance must be a positive number"));
+    }
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let lines = high_level::decode_line_entities(&decoder, None).map_err(to_py_err)?;
+    let segments: Vec<(Point3, Point3)> = lines.iter().map(|line| (line.start, line.end)).collect();
+    Ok(geometry::chain_segments(&segments, tolerance)
+        .into_iter()
+        .map(|chain| {
+            let closed = chain.points.first() == chain.points.last();
+            let handles = chain
+                .segments
+                .iter()
+                .map(|&index| lines[index].handle)
+                .collect();
+            (handles, closed, chain.points)
+        })
+        .collect())
+}
+
+#[pyfunction]
+pub fn entity_bounds(path: DwgSource) -> PyResult<Vec<EntityBoundsRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let bounds = high_level::entity_bounds(&decoder).map_err(to_py_err)?;
+    Ok(bounds
+        .into_iter()
+        .map(|(handle, bounds)| (handle, bounds.min, bounds.max))
+        .collect())
+}
+
+#[pyfunction(signature = (path, skip_hidden_layers=false))]
+pub fn object_summaries(
+    path: DwgSource,
+    skip_hidden_layers: bool,
+) -> PyResult<Vec<ObjectSummaryRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let mut summaries = high_level::object_summaries(&decoder).map_err(to_py_err)?;
+    if skip_hidden_layers {
+        let hidden = hidden_layer_handles(&decoder)?;
+        summaries.retain(|summary| {
+            !summary
+                .layer_handle
+                .is_some_and(|layer| hidden.contains(&layer))
+        });
     }
-    Ok(result)
+    Ok(summaries
+        .into_iter()
+        .map(|summary| {
+            (
+                summary.handle,
+                summary.type_name,
+                summary.layer_name,
+                summary.bounds.map(|bounds| (bounds.min, bounds.max)),
+            )
+        })
+        .collect())
+}
+
+#[pyfunction]
+pub fn decode_entities_paginated(
+    path: DwgSource,
+    offset: usize,
+    count: usize,
+) -> PyResult<Vec<(u64, String, String)>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let entities =
+        high_level::decode_entities_paginated(&decoder, offset, count).map_err(to_py_err)?;
+    Ok(entities
+        .into_iter()
+        .map(|(handle, entity)| {
+            (
+                handle.0,
+                entity.type_name().to_string(),
+                entity.geometry_kind().as_str().to_string(),
+            )
+        })
+        .collect())
+}
+
+#[pyfunction]
+pub fn list_inexact_decodes(path: DwgSource) -> PyResult<Vec<(u64, String, String)>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let rows = high_level::inexact_decodes(&decoder).map_err(to_py_err)?;
+    Ok(rows
+        .into_iter()
+        .map(|(handle, type_name, method)| {
+            (handle, type_name.to_string(), method.as_str().to_string())
+        })
+        .collect())
+}
+
+#[pyfunction]
+pub fn spatial_query(path: DwgSource, min: (f64, f64), max: (f64, f64)) -> PyResult<Vec<u64>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    high_level::spatial_query(&decoder, min, max).map_err(to_py_err)
+}
+
+#[pyfunction(signature = (path, limit=None))]
+pub fn decode_point_entities(
+    path: DwgSource,
+    limit: Option<usize>,
+) -> PyResult<Vec<PointEntityRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let entities = high_level::decode_point_entities(&decoder, limit).map_err(to_py_err)?;
+    Ok(entities
+        .into_iter()
+        .map(|entity| {
+            (
+                entity.handle,
+                entity.location.0,
+                entity.location.1,
+                entity.location.2,
+                entity.thickness,
+                entity.x_axis_angle,
+            )
+        })
+        .collect())
 }
 
 #[pyfunction(signature = (path, limit=None))]
-pub fn decode_circle_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<CircleEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+pub fn decode_3dface_entities(
+    path: DwgSource,
+    limit: Option<usize>,
+) -> PyResult<Vec<Face3dEntityRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let best_effort = is_best_effort_compat_version(&decoder);
     let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut result = Vec::new();
     for obj in index.objects.iter() {
         let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
         else {
             continue;
         };
-        if !matches_type_name(header.type_code, 0x12, "CIRCLE", &dynamic_types) {
+        if !matches_type_name(header.type_code, 0x1C, "3DFACE", &dynamic_types) {
             continue;
         }
         let mut reader = record.bit_reader();
@@ -1514,22 +1875,23 @@ pub fn decode_circle_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<
             }
             return Err(to_py_err(err));
         }
-        let entity = match decode_circle_for_version(
+        let entity = match decode_3dface_for_version(
             &mut reader,
             decoder.version(),
             &header,
             obj.handle.0,
         ) {
             Ok(entity) => entity,
-            Err(err) if best_effort => continue,
+            Err(_) if best_effort => continue,
             Err(err) => return Err(to_py_err(err)),
         };
         result.push((
             entity.handle,
-            entity.center.0,
-            entity.center.1,
-            entity.center.2,
-            entity.radius,
+            entity.p1,
+            entity.p2,
+            entity.p3,
+            entity.p4,
+            entity.invisible_edge_flags,
         ));
         if let Some(limit) = limit {
             if result.len() >= limit {
@@ -1540,16 +1902,59 @@ pub fn decode_circle_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<
     Ok(result)
 }
 
-#[pyfunction(signature = (path, limit=None))]
+#[pyfunction(signature = (path, limit=None, wcs=false))]
+pub fn decode_arc_entities(
+    path: DwgSource,
+    limit: Option<usize>,
+    wcs: bool,
+) -> PyResult<Vec<ArcEntityRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let entities = high_level::decode_arc_entities(&decoder, limit).map_err(to_py_err)?;
+    Ok(entities.iter().map(|entity| arc_row(entity, wcs)).collect())
+}
+
+#[pyfunction(signature = (path, limit=None, wcs=false))]
+pub fn decode_arc_sweeps(
+    path: DwgSource,
+    limit: Option<usize>,
+    wcs: bool,
+) -> PyResult<Vec<ArcSweepRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let entities = high_level::decode_arc_entities(&decoder, limit).map_err(to_py_err)?;
+    Ok(entities
+        .iter()
+        .map(|entity| arc_sweep_row(entity, wcs))
+        .collect())
+}
+
+#[pyfunction(signature = (path, limit=None, wcs=false))]
+pub fn decode_circle_entities(
+    path: DwgSource,
+    limit: Option<usize>,
+    wcs: bool,
+) -> PyResult<Vec<CircleEntityRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let entities = high_level::decode_circle_entities(&decoder, limit).map_err(to_py_err)?;
+    Ok(entities
+        .iter()
+        .map(|entity| circle_row(entity, wcs))
+        .collect())
+}
+
+#[pyfunction(signature = (path, limit=None, wcs=false))]
 pub fn decode_line_arc_circle_entities(
-    path: &str,
+    path: DwgSource,
     limit: Option<usize>,
+    wcs: bool,
 ) -> PyResult<LineArcCircleRows> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let best_effort = is_best_effort_compat_version(&decoder);
     let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut lines: Vec<LineEntityRow> = Vec::new();
     let mut arcs: Vec<ArcEntityRow> = Vec::new();
     let mut circles: Vec<CircleEntityRow> = Vec::new();
@@ -1583,7 +1988,7 @@ pub fn decode_line_arc_circle_entities(
                 obj.handle.0,
             ) {
                 Ok(entity) => entity,
-                Err(err) if best_effort => continue,
+                Err(_) if best_effort => continue,
                 Err(err) => return Err(to_py_err(err)),
             };
             lines.push((
@@ -1601,18 +2006,10 @@ pub fn decode_line_arc_circle_entities(
                 match decode_arc_for_version(&mut reader, decoder.version(), &header, obj.handle.0)
                 {
                     Ok(entity) => entity,
-                    Err(err) if best_effort => continue,
+                    Err(_) if best_effort => continue,
                     Err(err) => return Err(to_py_err(err)),
                 };
-            arcs.push((
-                entity.handle,
-                entity.center.0,
-                entity.center.1,
-                entity.center.2,
-                entity.radius,
-                entity.angle_start,
-                entity.angle_end,
-            ));
+            arcs.push(arc_row(&entity, wcs));
             total += 1;
         } else if is_circle {
             let entity = match decode_circle_for_version(
@@ -1622,16 +2019,10 @@ pub fn decode_line_arc_circle_entities(
                 obj.handle.0,
             ) {
                 Ok(entity) => entity,
-                Err(err) if best_effort => continue,
+                Err(_) if best_effort => continue,
                 Err(err) => return Err(to_py_err(err)),
             };
-            circles.push((
-                entity.handle,
-                entity.center.0,
-                entity.center.1,
-                entity.center.2,
-                entity.radius,
-            ));
+            circles.push(circle_row(&entity, wcs));
             total += 1;
         }
 
@@ -1645,180 +2036,233 @@ pub fn decode_line_arc_circle_entities(
     Ok((lines, arcs, circles))
 }
 
+/// With `wcs`, the centre is moved out of the entity's OCS. Angles stay
+/// measured in the OCS plane, except for mirrored (0, 0, -1) extrusions where
+/// the arc is re-expressed counter-clockwise in the world XY plane.
+fn arc_row(entity: &entities::ArcEntity, wcs: bool) -> ArcEntityRow {
+    let (center, angle_start, angle_end) =
+        arc_placement(entity, (entity.angle_start, entity.angle_end), wcs);
+    (
+        entity.handle,
+        center.0,
+        center.1,
+        center.2,
+        entity.radius,
+        angle_start,
+        angle_end,
+    )
+}
+
+/// [`arc_row`] with the angles wrapped into `[0, 2*pi)`, followed by the
+/// sweep and the extrusion.
+fn arc_sweep_row(entity: &entities::ArcEntity, wcs: bool) -> ArcSweepRow {
+    let (center, angle_start, angle_end) = arc_placement(entity, entity.normalized_angles(), wcs);
+    (
+        entity.handle,
+        center.0,
+        center.1,
+        center.2,
+        entity.radius,
+        angle_start,
+        angle_end,
+        entity.sweep_angle(),
+        entity.extrusion,
+    )
+}
+
+fn arc_placement(
+    entity: &entities::ArcEntity,
+    (start, end): (f64, f64),
+    wcs: bool,
+) -> (Point3, f64, f64) {
+    if !wcs {
+        return (entity.center, start, end);
+    }
+    let center = geometry::ocs_to_wcs(entity.extrusion, entity.center);
+    if is_mirrored_extrusion(entity.extrusion) {
+        (
+            center,
+            normalize_angle(std::f64::consts::PI - end),
+            normalize_angle(std::f64::consts::PI - start),
+        )
+    } else {
+        (center, start, end)
+    }
+}
+
+fn circle_row(entity: &entities::CircleEntity, wcs: bool) -> CircleEntityRow {
+    let center = if wcs {
+        geometry::ocs_to_wcs(entity.extrusion, entity.center)
+    } else {
+        entity.center
+    };
+    (
+        entity.handle,
+        center.0,
+        center.1,
+        center.2,
+        entity.radius,
+        entity.thickness,
+        entity.extrusion,
+    )
+}
+
+/// World positions of the OCS vertices, at the polyline's elevation. Bulges
+/// flip sign when the OCS normal points below the world XY plane, so that
+/// positive bulges turn counter-clockwise seen from the side of the plane
+/// facing world +Z.
+fn lwpolyline_to_wcs(entity: &mut entities::LwPolylineEntity) -> Vec<Point3> {
+    let extrusion = entity.extrusion;
+    if geometry::arbitrary_axes(extrusion)[2].2 < 0.0 {
+        for bulge in entity.bulges.iter_mut() {
+            *bulge = -*bulge;
+        }
+    }
+    entity
+        .vertices
+        .iter()
+        .map(|vertex| geometry::ocs_to_wcs(extrusion, (vertex.0, vertex.1, entity.elevation)))
+        .collect()
+}
+
+fn is_mirrored_extrusion(extrusion: Point3) -> bool {
+    let [_, _, z_axis] = geometry::arbitrary_axes(extrusion);
+    z_axis.0.abs() < 1e-9 && z_axis.1.abs() < 1e-9 && z_axis.2 < 0.0
+}
+
+fn normalize_angle(angle: f64) -> f64 {
+    angle.rem_euclid(std::f64::consts::TAU)
+}
+
 #[pyfunction(signature = (path, limit=None))]
 pub fn decode_ellipse_entities(
-    path: &str,
+    path: DwgSource,
     limit: Option<usize>,
 ) -> PyResult<Vec<EllipseEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
-    let best_effort = is_best_effort_compat_version(&decoder);
-    let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
-    let mut result = Vec::new();
-    for obj in index.objects.iter() {
-        let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
-        else {
-            continue;
-        };
-        if !matches_type_name(header.type_code, 0x23, "ELLIPSE", &dynamic_types) {
-            continue;
-        }
-        let mut reader = record.bit_reader();
-        if let Err(err) = skip_object_type_prefix(&mut reader, decoder.version()) {
-            if best_effort {
-                continue;
-            }
-            return Err(to_py_err(err));
-        }
-        let entity =
-            match decode_ellipse_for_version(&mut reader, decoder.version(), &header, obj.handle.0)
-            {
-                Ok(entity) => entity,
-                Err(err) if best_effort => continue,
-                Err(err) => return Err(to_py_err(err)),
-            };
-        result.push((
-            entity.handle,
-            entity.center,
-            entity.major_axis,
-            entity.extrusion,
-            entity.axis_ratio,
-            entity.start_angle,
-            entity.end_angle,
-        ));
-        if let Some(limit) = limit {
-            if result.len() >= limit {
-                break;
-            }
-        }
-    }
-    Ok(result)
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let entities = high_level::decode_ellipse_entities(&decoder, limit).map_err(to_py_err)?;
+    Ok(entities
+        .into_iter()
+        .map(|entity| {
+            (
+                entity.handle,
+                entity.center,
+                entity.major_axis,
+                entity.extrusion,
+                entity.axis_ratio,
+                entity.start_angle,
+                entity.end_angle,
+            )
+        })
+        .collect())
 }
 
 #[pyfunction(signature = (path, limit=None))]
-pub fn decode_spline_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<SplineEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
-    let best_effort = is_best_effort_compat_version(&decoder);
-    let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
-    let mut result = Vec::new();
-    for obj in index.objects.iter() {
-        let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
-        else {
-            continue;
-        };
-        if !matches_type_name(header.type_code, 0x24, "SPLINE", &dynamic_types) {
-            continue;
-        }
-        let mut reader = record.bit_reader();
-        if let Err(err) = skip_object_type_prefix(&mut reader, decoder.version()) {
-            if best_effort {
-                continue;
-            }
-            return Err(to_py_err(err));
-        }
-        let entity = match decode_spline_for_version(
-            &mut reader,
-            decoder.version(),
-            &header,
-            obj.handle.0,
-        ) {
-            Ok(entity) => entity,
-            Err(err) if best_effort => continue,
-            Err(err) => return Err(to_py_err(err)),
+pub fn decode_spline_entities(
+    path: DwgSource,
+    limit: Option<usize>,
+) -> PyResult<Vec<SplineEntityRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let entities = high_level::decode_spline_entities(&decoder, limit).map_err(to_py_err)?;
+    Ok(entities
+        .into_iter()
+        .map(|entity| {
+            (
+                entity.handle,
+                (
+                    entity.scenario,
+                    entity.degree,
+                    entity.rational,
+                    entity.closed,
+                    entity.periodic,
+                ),
+                (
+                    entity.fit_tolerance,
+                    entity.knot_tolerance,
+                    entity.ctrl_tolerance,
+                ),
+                entity.knots,
+                entity.control_points,
+                entity.weights,
+                entity.fit_points,
+            )
+        })
+        .collect())
+}
+
+#[pyfunction(signature = (path, samples_per_segment=16, include_raw=false, limit=None))]
+pub fn decode_spline_points(
+    path: DwgSource,
+    samples_per_segment: usize,
+    include_raw: bool,
+    limit: Option<usize>,
+) -> PyResult<Vec<SplinePointsRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let entities = high_level::decode_spline_entities(&decoder, limit).map_err(to_py_err)?;
+    let mut result = Vec::with_capacity(entities.len());
+    for entity in entities {
+        let points =
+            entities::sample_spline(&entity, samples_per_segment, path.config.point_epsilon)
+                .map_err(to_py_err)?;
+        let (control_points, knots) = if include_raw {
+            (Some(entity.control_points), Some(entity.knots))
+        } else {
+            (None, None)
         };
         result.push((
             entity.handle,
-            (
-                entity.scenario,
-                entity.degree,
-                entity.rational,
-                entity.closed,
-                entity.periodic,
-            ),
-            (
-                entity.fit_tolerance,
-                entity.knot_tolerance,
-                entity.ctrl_tolerance,
-            ),
-            entity.knots,
-            entity.control_points,
-            entity.weights,
-            entity.fit_points,
+            entity.degree,
+            entity.closed,
+            points,
+            control_points,
+            knots,
         ));
-        if let Some(limit) = limit {
-            if result.len() >= limit {
-                break;
-            }
-        }
     }
     Ok(result)
 }
 
 #[pyfunction(signature = (path, limit=None))]
-pub fn decode_text_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<TextEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
-    let best_effort = is_best_effort_compat_version(&decoder);
-    let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
-    let mut result = Vec::new();
-    for obj in index.objects.iter() {
-        let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
-        else {
-            continue;
-        };
-        if !matches_type_name(header.type_code, 0x01, "TEXT", &dynamic_types) {
-            continue;
-        }
-        let mut reader = record.bit_reader();
-        if let Err(err) = skip_object_type_prefix(&mut reader, decoder.version()) {
-            if best_effort {
-                continue;
-            }
-            return Err(to_py_err(err));
-        }
-        let entity =
-            match decode_text_for_version(&mut reader, decoder.version(), &header, obj.handle.0) {
-                Ok(entity) => entity,
-                Err(err) if best_effort => continue,
-                Err(err) => return Err(to_py_err(err)),
-            };
-        result.push((
-            entity.handle,
-            entity.text,
-            entity.insertion,
-            entity.alignment,
-            entity.extrusion,
-            (
-                entity.thickness,
-                entity.oblique_angle,
-                entity.height,
-                entity.rotation,
-                entity.width_factor,
-            ),
+pub fn decode_text_entities(path: DwgSource, limit: Option<usize>) -> PyResult<Vec<TextEntityRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let entities = high_level::decode_text_entities(&decoder, limit).map_err(to_py_err)?;
+    Ok(entities
+        .into_iter()
+        .map(|entity| {
             (
-                entity.generation,
-                entity.horizontal_alignment,
-                entity.vertical_alignment,
-            ),
-            entity.style_handle,
-        ));
-        if let Some(limit) = limit {
-            if result.len() >= limit {
-                break;
-            }
-        }
-    }
-    Ok(result)
+                entity.handle,
+                entity.text,
+                entity.insertion,
+                entity.alignment,
+                entity.extrusion,
+                (
+                    entity.thickness,
+                    entity.oblique_angle,
+                    entity.height,
+                    entity.rotation,
+                    entity.width_factor,
+                ),
+                (
+                    entity.generation,
+                    entity.horizontal_alignment,
+                    entity.vertical_alignment,
+                ),
+                entity.style_handle,
+            )
+        })
+        .collect())
 }
 
 #[pyfunction(signature = (path, limit=None))]
-pub fn decode_attrib_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<AttribEntityRow>> {
+pub fn decode_attrib_entities(
+    path: DwgSource,
+    limit: Option<usize>,
+) -> PyResult<Vec<AttribEntityRow>> {
     decode_attrib_like_entities_by_type(
-        path,
+        &path,
         limit,
         0x02,
         "ATTRIB",
@@ -1829,9 +2273,12 @@ pub fn decode_attrib_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<
 }
 
 #[pyfunction(signature = (path, limit=None))]
-pub fn decode_attdef_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<AttribEntityRow>> {
+pub fn decode_attdef_entities(
+    path: DwgSource,
+    limit: Option<usize>,
+) -> PyResult<Vec<AttribEntityRow>> {
     decode_attrib_like_entities_by_type(
-        path,
+        &path,
         limit,
         0x03,
         "ATTDEF",
@@ -1842,7 +2289,7 @@ pub fn decode_attdef_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<
 }
 
 fn decode_attrib_like_entities_by_type<F>(
-    path: &str,
+    path: &DwgSource,
     limit: Option<usize>,
     type_code: u16,
     type_name: &str,
@@ -1852,15 +2299,15 @@ where
     F: FnMut(
         &mut BitReader<'_>,
         &version::DwgVersion,
-        &ApiObjectHeader,
+        &ObjectHeader,
         u64,
     ) -> crate::core::result::Result<entities::AttribEntity>,
 {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(path, &bytes).map_err(to_py_err)?;
     let best_effort = is_best_effort_compat_version(&decoder);
     let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut result = Vec::new();
     for obj in index.objects.iter() {
         let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
@@ -1879,7 +2326,7 @@ where
         }
         let entity = match decode_entity(&mut reader, decoder.version(), &header, obj.handle.0) {
             Ok(entity) => entity,
-            Err(err) if best_effort => continue,
+            Err(_) if best_effort => continue,
             Err(err) => return Err(to_py_err(err)),
         };
         result.push((
@@ -1903,64 +2350,8 @@ where
                 entity.vertical_alignment,
             ),
             entity.flags,
-            entity.lock_position,
-            entity.style_handle,
-        ));
-        if let Some(limit) = limit {
-            if result.len() >= limit {
-                break;
-            }
-        }
-    }
-    Ok(result)
-}
-
-#[pyfunction(signature = (path, limit=None))]
-pub fn decode_mtext_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<MTextEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
-    let best_effort = is_best_effort_compat_version(&decoder);
-    let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
-    let mut result = Vec::new();
-    for obj in index.objects.iter() {
-        let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
-        else {
-            continue;
-        };
-        if !matches_type_name(header.type_code, 0x2C, "MTEXT", &dynamic_types) {
-            continue;
-        }
-        let mut reader = record.bit_reader();
-        if let Err(err) = skip_object_type_prefix(&mut reader, decoder.version()) {
-            if best_effort {
-                continue;
-            }
-            return Err(to_py_err(err));
-        }
-        let entity =
-            match decode_mtext_for_version(&mut reader, decoder.version(), &header, obj.handle.0) {
-                Ok(entity) => entity,
-                Err(err) if best_effort => continue,
-                Err(err) => return Err(to_py_err(err)),
-            };
-        result.push((
-            entity.handle,
-            entity.text,
-            entity.insertion,
-            entity.extrusion,
-            entity.x_axis_dir,
-            entity.rect_width,
-            entity.text_height,
-            entity.attachment,
-            entity.drawing_dir,
-            (
-                entity.background_flags,
-                entity.background_scale_factor,
-                entity.background_color_index,
-                entity.background_true_color,
-                entity.background_transparency,
-            ),
+            entity.lock_position,
+            entity.style_handle,
         ));
         if let Some(limit) = limit {
             if result.len() >= limit {
@@ -1972,12 +2363,48 @@ pub fn decode_mtext_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<M
 }
 
 #[pyfunction(signature = (path, limit=None))]
-pub fn decode_leader_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<LeaderEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+pub fn decode_mtext_entities(
+    path: DwgSource,
+    limit: Option<usize>,
+) -> PyResult<Vec<MTextEntityRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let entities = high_level::decode_mtext_entities(&decoder, limit).map_err(to_py_err)?;
+    Ok(entities
+        .into_iter()
+        .map(|entity| {
+            (
+                entity.handle,
+                entity.text,
+                entity.insertion,
+                entity.extrusion,
+                entity.x_axis_dir,
+                entity.rect_width,
+                entity.text_height,
+                entity.attachment,
+                entity.drawing_dir,
+                (
+                    entity.background_flags,
+                    entity.background_scale_factor,
+                    entity.background_color_index,
+                    entity.background_true_color,
+                    entity.background_transparency,
+                ),
+            )
+        })
+        .collect())
+}
+
+#[pyfunction(signature = (path, limit=None))]
+pub fn decode_leader_entities(
+    path: DwgSource,
+    limit: Option<usize>,
+) -> PyResult<Vec<LeaderEntityRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let best_effort = is_best_effort_compat_version(&decoder);
     let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut result = Vec::new();
     for obj in index.objects.iter() {
         let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
@@ -2001,7 +2428,7 @@ pub fn decode_leader_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<
             obj.handle.0,
         ) {
             Ok(entity) => entity,
-            Err(err) if best_effort => continue,
+            Err(_) if best_effort => continue,
             Err(err) => return Err(to_py_err(err)),
         };
         result.push((
@@ -2020,12 +2447,15 @@ pub fn decode_leader_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<
 }
 
 #[pyfunction(signature = (path, limit=None))]
-pub fn decode_hatch_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<HatchEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+pub fn decode_hatch_entities(
+    path: DwgSource,
+    limit: Option<usize>,
+) -> PyResult<Vec<HatchEntityRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let best_effort = is_best_effort_compat_version(&decoder);
     let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut result = Vec::new();
     for obj in index.objects.iter() {
         let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
@@ -2042,12 +2472,13 @@ pub fn decode_hatch_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<H
             }
             return Err(to_py_err(err));
         }
-        let entity =
+        let mut entity =
             match decode_hatch_for_version(&mut reader, decoder.version(), &header, obj.handle.0) {
                 Ok(entity) => entity,
-                Err(err) if best_effort => continue,
+                Err(_) if best_effort => continue,
                 Err(err) => return Err(to_py_err(err)),
             };
+        decoder.flatten_hatch(&mut entity);
         let paths: Vec<HatchPathRow> = entity
             .paths
             .into_iter()
@@ -2073,14 +2504,14 @@ pub fn decode_hatch_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<H
 
 #[pyfunction(signature = (path, limit=None))]
 pub fn decode_tolerance_entities(
-    path: &str,
+    path: DwgSource,
     limit: Option<usize>,
 ) -> PyResult<Vec<ToleranceEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let best_effort = is_best_effort_compat_version(&decoder);
     let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut result = Vec::new();
     for obj in index.objects.iter() {
         let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
@@ -2104,7 +2535,7 @@ pub fn decode_tolerance_entities(
             obj.handle.0,
         ) {
             Ok(entity) => entity,
-            Err(err) if best_effort => continue,
+            Err(_) if best_effort => continue,
             Err(err) => return Err(to_py_err(err)),
         };
         result.push((
@@ -2127,12 +2558,15 @@ pub fn decode_tolerance_entities(
 }
 
 #[pyfunction(signature = (path, limit=None))]
-pub fn decode_mline_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<MLineEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+pub fn decode_mline_entities(
+    path: DwgSource,
+    limit: Option<usize>,
+) -> PyResult<Vec<MLineEntityRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let best_effort = is_best_effort_compat_version(&decoder);
     let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut result = Vec::new();
     for obj in index.objects.iter() {
         let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
@@ -2152,7 +2586,7 @@ pub fn decode_mline_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<M
         let entity =
             match decode_mline_for_version(&mut reader, decoder.version(), &header, obj.handle.0) {
                 Ok(entity) => entity,
-                Err(err) if best_effort => continue,
+                Err(_) if best_effort => continue,
                 Err(err) => return Err(to_py_err(err)),
             };
         let vertices: Vec<MLineVertexRow> = entity
@@ -2187,122 +2621,113 @@ pub fn decode_mline_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<M
 }
 
 #[pyfunction(signature = (path, limit=None))]
-pub fn decode_dim_linear_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<DimEntityRow>> {
+pub fn decode_dim_linear_entities(
+    path: DwgSource,
+    limit: Option<usize>,
+) -> PyResult<Vec<DimEntityRow>> {
     decode_dim_entities_by_type(
-        path,
+        &path,
         limit,
         0x15,
         "DIM_LINEAR",
-        |reader, version, header, object_handle| {
-            let entity = decode_dim_linear_for_version(reader, version, header, object_handle)?;
-            Ok(dim_entity_row_from_linear_like(&entity))
-        },
+        decode_dim_linear_for_version,
     )
 }
 
 #[pyfunction(signature = (path, limit=None))]
 pub fn decode_dim_ordinate_entities(
-    path: &str,
+    path: DwgSource,
     limit: Option<usize>,
 ) -> PyResult<Vec<DimEntityRow>> {
     decode_dim_entities_by_type(
-        path,
+        &path,
         limit,
         0x14,
         "DIM_ORDINATE",
-        |reader, version, header, object_handle| {
-            let entity = decode_dim_linear_for_version(reader, version, header, object_handle)?;
-            Ok(dim_entity_row_from_linear_like(&entity))
-        },
+        decode_dim_linear_for_version,
     )
 }
 
 #[pyfunction(signature = (path, limit=None))]
 pub fn decode_dim_diameter_entities(
-    path: &str,
+    path: DwgSource,
     limit: Option<usize>,
 ) -> PyResult<Vec<DimEntityRow>> {
     decode_dim_entities_by_type(
-        path,
+        &path,
         limit,
         0x1A,
         "DIM_DIAMETER",
-        |reader, version, header, object_handle| {
-            let entity = decode_dim_diameter_for_version(reader, version, header, object_handle)?;
-            Ok(dim_entity_row_from_linear_like(&entity))
-        },
+        decode_dim_diameter_for_version,
     )
 }
 
 #[pyfunction(signature = (path, limit=None))]
 pub fn decode_dim_aligned_entities(
-    path: &str,
+    path: DwgSource,
     limit: Option<usize>,
 ) -> PyResult<Vec<DimEntityRow>> {
     decode_dim_entities_by_type(
-        path,
+        &path,
         limit,
         0x16,
         "DIM_ALIGNED",
-        |reader, version, header, object_handle| {
-            let entity = decode_dim_linear_for_version(reader, version, header, object_handle)?;
-            Ok(dim_entity_row_from_linear_like(&entity))
-        },
+        decode_dim_linear_for_version,
     )
 }
 
 #[pyfunction(signature = (path, limit=None))]
-pub fn decode_dim_ang3pt_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<DimEntityRow>> {
+pub fn decode_dim_ang3pt_entities(
+    path: DwgSource,
+    limit: Option<usize>,
+) -> PyResult<Vec<DimEntityRow>> {
     decode_dim_entities_by_type(
-        path,
+        &path,
         limit,
         0x17,
         "DIM_ANG3PT",
-        |reader, version, header, object_handle| {
-            let entity = decode_dim_linear_for_version(reader, version, header, object_handle)?;
-            Ok(dim_entity_row_from_linear_like(&entity))
-        },
+        decode_dim_linear_for_version,
     )
 }
 
 #[pyfunction(signature = (path, limit=None))]
-pub fn decode_dim_ang2ln_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<DimEntityRow>> {
+pub fn decode_dim_ang2ln_entities(
+    path: DwgSource,
+    limit: Option<usize>,
+) -> PyResult<Vec<DimEntityRow>> {
     decode_dim_entities_by_type(
-        path,
+        &path,
         limit,
         0x18,
         "DIM_ANG2LN",
-        |reader, version, header, object_handle| {
-            let entity = decode_dim_linear_for_version(reader, version, header, object_handle)?;
-            Ok(dim_entity_row_from_linear_like(&entity))
-        },
+        decode_dim_linear_for_version,
     )
 }
 
 #[pyfunction(signature = (path, limit=None))]
-pub fn decode_dim_radius_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<DimEntityRow>> {
+pub fn decode_dim_radius_entities(
+    path: DwgSource,
+    limit: Option<usize>,
+) -> PyResult<Vec<DimEntityRow>> {
     decode_dim_entities_by_type(
-        path,
+        &path,
         limit,
         0x19,
         "DIM_RADIUS",
-        |reader, version, header, object_handle| {
-            let entity = decode_dim_radius_for_version(reader, version, header, object_handle)?;
-            Ok(dim_entity_row_from_linear_like(&entity))
-        },
+        decode_dim_radius_for_version,
     )
 }
 
 #[pyfunction(signature = (path, limit=None))]
 pub fn decode_dimension_entities(
-    path: &str,
+    path: DwgSource,
     limit: Option<usize>,
 ) -> PyResult<Vec<DimTypedEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let best_effort = is_best_effort_compat_version(&decoder);
     let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut result: Vec<DimTypedEntityRow> = Vec::new();
 
     for obj in index.objects.iter() {
@@ -2325,9 +2750,11 @@ pub fn decode_dimension_entities(
                 decoder.version(),
                 &header,
                 obj.handle.0,
-            ) {
+            )
+            .and_then(|entity| checked_dimension_layout(&decoder, entity))
+            {
                 Ok(entity) => entity,
-                Err(err) if best_effort => continue,
+                Err(_) if best_effort => continue,
                 Err(err) => return Err(to_py_err(err)),
             };
             Some(("LINEAR", dim_entity_row_from_linear_like(&entity)))
@@ -2337,9 +2764,11 @@ pub fn decode_dimension_entities(
                 decoder.version(),
                 &header,
                 obj.handle.0,
-            ) {
+            )
+            .and_then(|entity| checked_dimension_layout(&decoder, entity))
+            {
                 Ok(entity) => entity,
-                Err(err) if best_effort => continue,
+                Err(_) if best_effort => continue,
                 Err(err) => return Err(to_py_err(err)),
             };
             Some(("ORDINATE", dim_entity_row_from_linear_like(&entity)))
@@ -2349,9 +2778,11 @@ pub fn decode_dimension_entities(
                 decoder.version(),
                 &header,
                 obj.handle.0,
-            ) {
+            )
+            .and_then(|entity| checked_dimension_layout(&decoder, entity))
+            {
                 Ok(entity) => entity,
-                Err(err) if best_effort => continue,
+                Err(_) if best_effort => continue,
                 Err(err) => return Err(to_py_err(err)),
             };
             Some(("ALIGNED", dim_entity_row_from_linear_like(&entity)))
@@ -2361,9 +2792,11 @@ pub fn decode_dimension_entities(
                 decoder.version(),
                 &header,
                 obj.handle.0,
-            ) {
+            )
+            .and_then(|entity| checked_dimension_layout(&decoder, entity))
+            {
                 Ok(entity) => entity,
-                Err(err) if best_effort => continue,
+                Err(_) if best_effort => continue,
                 Err(err) => return Err(to_py_err(err)),
             };
             Some(("ANG3PT", dim_entity_row_from_linear_like(&entity)))
@@ -2373,9 +2806,11 @@ pub fn decode_dimension_entities(
                 decoder.version(),
                 &header,
                 obj.handle.0,
-            ) {
+            )
+            .and_then(|entity| checked_dimension_layout(&decoder, entity))
+            {
                 Ok(entity) => entity,
-                Err(err) if best_effort => continue,
+                Err(_) if best_effort => continue,
                 Err(err) => return Err(to_py_err(err)),
             };
             Some(("ANG2LN", dim_entity_row_from_linear_like(&entity)))
@@ -2385,9 +2820,11 @@ pub fn decode_dimension_entities(
                 decoder.version(),
                 &header,
                 obj.handle.0,
-            ) {
+            )
+            .and_then(|entity| checked_dimension_layout(&decoder, entity))
+            {
                 Ok(entity) => entity,
-                Err(err) if best_effort => continue,
+                Err(_) if best_effort => continue,
                 Err(err) => return Err(to_py_err(err)),
             };
             Some(("DIAMETER", dim_entity_row_from_linear_like(&entity)))
@@ -2397,9 +2834,11 @@ pub fn decode_dimension_entities(
                 decoder.version(),
                 &header,
                 obj.handle.0,
-            ) {
+            )
+            .and_then(|entity| checked_dimension_layout(&decoder, entity))
+            {
                 Ok(entity) => entity,
-                Err(err) if best_effort => continue,
+                Err(_) if best_effort => continue,
                 Err(err) => return Err(to_py_err(err)),
             };
             Some(("RADIUS", dim_entity_row_from_linear_like(&entity)))
@@ -2421,25 +2860,25 @@ pub fn decode_dimension_entities(
 }
 
 fn decode_dim_entities_by_type<F>(
-    path: &str,
+    path: &DwgSource,
     limit: Option<usize>,
     type_code: u16,
     type_name: &str,
-    mut decode_entity_row: F,
+    decode_entity: F,
 ) -> PyResult<Vec<DimEntityRow>>
 where
-    F: FnMut(
+    F: Fn(
         &mut BitReader<'_>,
         &version::DwgVersion,
-        &ApiObjectHeader,
+        &ObjectHeader,
         u64,
-    ) -> crate::core::result::Result<DimEntityRow>,
+    ) -> crate::core::result::Result<entities::DimLinearEntity>,
 {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(path, &bytes).map_err(to_py_err)?;
     let best_effort = is_best_effort_compat_version(&decoder);
     let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut result = Vec::new();
 
     for obj in index.objects.iter() {
@@ -2459,12 +2898,14 @@ where
             return Err(to_py_err(err));
         }
 
-        let row = match decode_entity_row(&mut reader, decoder.version(), &header, obj.handle.0) {
-            Ok(row) => row,
-            Err(err) if best_effort => continue,
+        let entity = match decode_entity(&mut reader, decoder.version(), &header, obj.handle.0)
+            .and_then(|entity| checked_dimension_layout(&decoder, entity))
+        {
+            Ok(entity) => entity,
+            Err(_) if best_effort => continue,
             Err(err) => return Err(to_py_err(err)),
         };
-        result.push(row);
+        result.push(dim_entity_row_from_linear_like(&entity));
 
         if let Some(limit) = limit {
             if result.len() >= limit {
@@ -2476,6 +2917,18 @@ where
     Ok(result)
 }
 
+/// `entity`, or its layout error when the decoder runs with
+/// `strict_dimensions` and no layout variant read it cleanly.
+fn checked_dimension_layout(
+    decoder: &decoder::Decoder<'_>,
+    entity: entities::DimLinearEntity,
+) -> crate::core::result::Result<entities::DimLinearEntity> {
+    if decoder.config().strict_dimensions {
+        entity.common.ensure_layout_decoded()?;
+    }
+    Ok(entity)
+}
+
 fn dim_entity_row_from_linear_like(entity: &entities::DimLinearEntity) -> DimEntityRow {
     let common = &entity.common;
     (
@@ -2506,11 +2959,14 @@ fn dim_entity_row_from_linear_like(entity: &entities::DimLinearEntity) -> DimEnt
 }
 
 #[pyfunction(signature = (path, limit=None))]
-pub fn decode_insert_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<InsertEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+pub fn decode_insert_entities(
+    path: DwgSource,
+    limit: Option<usize>,
+) -> PyResult<Vec<InsertEntityRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let dynamic_types = decoder.dynamic_type_map().map_err(to_py_err)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut result = Vec::new();
     for obj in index.objects.iter() {
         let record = decoder.parse_object_record(obj.offset).map_err(to_py_err)?;
@@ -2522,7 +2978,158 @@ pub fn decode_insert_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<
         let mut reader = record.bit_reader();
         let _type_code =
             skip_object_type_prefix(&mut reader, decoder.version()).map_err(to_py_err)?;
-        let entity = entities::decode_insert(&mut reader).map_err(to_py_err)?;
+        let entity =
+            decode_insert_for_version(&mut reader, decoder.version(), &header, obj.handle.0)
+                .map_err(to_py_err)?;
+        result.push((
+            entity.handle,
+            entity.position.0,
+            entity.position.1,
+            entity.position.2,
+            entity.scale.0,
+            entity.scale.1,
+            entity.scale.2,
+            entity.rotation,
+        ));
+        if let Some(limit) = limit {
+            if result.len() >= limit {
+                break;
+            }
+        }
+    }
+    Ok(result)
+}
+
+#[pyfunction(signature = (path, limit=None))]
+pub fn insert_transforms(
+    path: DwgSource,
+    limit: Option<usize>,
+) -> PyResult<Vec<InsertTransformRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let entities =
+        high_level::decode_insert_entities_with_block(&decoder, limit).map_err(to_py_err)?;
+    let base_points: HashMap<u64, (f64, f64, f64)> = high_level::list_block_headers(&decoder)
+        .map_err(to_py_err)?
+        .into_iter()
+        .map(|header| (header.handle, header.base_point))
+        .collect();
+    Ok(entities
+        .into_iter()
+        .map(|(entity, block_name)| {
+            let base_point = entity
+                .block_header_handle
+                .and_then(|handle| base_points.get(&handle).copied())
+                .unwrap_or((0.0, 0.0, 0.0));
+            (
+                entity.handle,
+                entity.block_header_handle,
+                block_name,
+                entity.transform(base_point).to_vec(),
+            )
+        })
+        .collect())
+}
+
+#[pyfunction(signature = (path, limit=None))]
+pub fn decode_insert_entities_with_block(
+    path: DwgSource,
+    limit: Option<usize>,
+) -> PyResult<Vec<InsertWithBlockRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let entities =
+        high_level::decode_insert_entities_with_block(&decoder, limit).map_err(to_py_err)?;
+    Ok(entities
+        .into_iter()
+        .map(|(entity, block_name)| {
+            (
+                entity.handle,
+                entity.position,
+                entity.scale,
+                entity.rotation,
+                entity.block_header_handle,
+                block_name,
+            )
+        })
+        .collect())
+}
+
+fn collect_table_entry_names(
+    decoder: &decoder::Decoder<'_>,
+    objects: &[objects::ObjectRef],
+    dynamic_types: &HashMap<u16, String>,
+    best_effort: bool,
+    table: (u16, &str),
+) -> PyResult<HashMap<u64, String>> {
+    high_level::collect_table_entry_names(decoder, objects, dynamic_types, best_effort, table)
+        .map_err(to_py_err)
+}
+
+#[pyfunction(signature = (path, limit=None))]
+pub fn decode_inserts_with_attribs(
+    path: DwgSource,
+    limit: Option<usize>,
+) -> PyResult<Vec<InsertWithAttribsRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let best_effort = is_best_effort_compat_version(&decoder);
+    let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
+    let index = scoped_object_index(&decoder)?;
+    let mut sorted = index.objects.clone();
+    sorted.sort_by_key(|obj| obj.offset);
+
+    let attrib_map = build_attrib_map(&decoder, &sorted, &dynamic_types, best_effort)?;
+    let attrib_owners = group_children_by_owner(
+        &decoder,
+        &sorted,
+        |handle| attrib_map.contains_key(&handle),
+        best_effort,
+    )?;
+    let walk = ChildWalk {
+        decoder: &decoder,
+        sorted: &sorted,
+        dynamic_types: &dynamic_types,
+        child_owners: &attrib_owners,
+        best_effort,
+    };
+    let mut result = Vec::new();
+    let mut i = 0usize;
+    while i < sorted.len() {
+        let obj = sorted[i];
+        let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
+        else {
+            i += 1;
+            continue;
+        };
+        if !matches_type_name(header.type_code, 0x07, "INSERT", &dynamic_types) {
+            i += 1;
+            continue;
+        }
+        let mut reader = record.bit_reader();
+        if let Err(err) = skip_object_type_prefix(&mut reader, decoder.version()) {
+            if best_effort {
+                i += 1;
+                continue;
+            }
+            return Err(to_py_err(err));
+        }
+        let entity = match decode_insert_for_version(
+            &mut reader,
+            decoder.version(),
+            &header,
+            obj.handle.0,
+        ) {
+            Ok(entity) => entity,
+            Err(_) if best_effort => {
+                i += 1;
+                continue;
+            }
+            Err(err) => return Err(to_py_err(err)),
+        };
+        let (attribs, next_i) = collect_insert_attribs(&walk, &attrib_map, &entity, i)?;
+        i = next_i;
+
         result.push((
             entity.handle,
             entity.position.0,
@@ -2532,6 +3139,10 @@ pub fn decode_insert_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<
             entity.scale.1,
             entity.scale.2,
             entity.rotation,
+            attribs
+                .into_iter()
+                .map(|attrib| (attrib.handle, attrib.tag, attrib.text))
+                .collect(),
         ));
         if let Some(limit) = limit {
             if result.len() >= limit {
@@ -2542,16 +3153,78 @@ pub fn decode_insert_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<
     Ok(result)
 }
 
+fn build_attrib_map(
+    decoder: &decoder::Decoder<'_>,
+    sorted: &[objects::ObjectRef],
+    dynamic_types: &HashMap<u16, String>,
+    best_effort: bool,
+) -> PyResult<HashMap<u64, entities::AttribEntity>> {
+    let mut attrib_map = HashMap::new();
+    for obj in sorted {
+        let Some((record, header)) = parse_record_and_header(decoder, obj.offset, best_effort)?
+        else {
+            continue;
+        };
+        if !matches_type_name(header.type_code, 0x02, "ATTRIB", dynamic_types) {
+            continue;
+        }
+        let mut reader = record.bit_reader();
+        if let Err(err) = skip_object_type_prefix(&mut reader, decoder.version()) {
+            if best_effort {
+                continue;
+            }
+            return Err(to_py_err(err));
+        }
+        let attrib = match decode_attrib_for_version(
+            &mut reader,
+            decoder.version(),
+            &header,
+            obj.handle.0,
+        ) {
+            Ok(attrib) => attrib,
+            Err(_) if best_effort => continue,
+            Err(err) => return Err(to_py_err(err)),
+        };
+        attrib_map.insert(obj.handle.0, attrib);
+    }
+    Ok(attrib_map)
+}
+
+fn collect_insert_attribs(
+    walk: &ChildWalk<'_, '_>,
+    attrib_map: &HashMap<u64, entities::AttribEntity>,
+    insert: &entities::InsertEntity,
+    start_index: usize,
+) -> PyResult<(Vec<entities::AttribEntity>, usize)> {
+    if !insert.has_attribs {
+        return Ok((Vec::new(), start_index + 1));
+    }
+    walk.owned_children(
+        insert.handle,
+        &insert.attrib_handles,
+        start_index,
+        |handle| attrib_map.get(&handle).cloned(),
+        |type_code| matches_type_name(type_code, 0x02, "ATTRIB", walk.dynamic_types),
+        |_reader, _header, handle| {
+            Ok(attrib_map
+                .get(&handle)
+                .cloned()
+                .map_or(AdjacentChild::Skip, AdjacentChild::Take))
+        },
+    )
+    .map_err(to_py_err)
+}
+
 #[pyfunction(signature = (path, limit=None))]
 pub fn decode_minsert_entities(
-    path: &str,
+    path: DwgSource,
     limit: Option<usize>,
 ) -> PyResult<Vec<MInsertEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let best_effort = is_best_effort_compat_version(&decoder);
     let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut result = Vec::new();
     for obj in index.objects.iter() {
         let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
@@ -2570,7 +3243,7 @@ pub fn decode_minsert_entities(
         }
         let entity = match entities::decode_minsert(&mut reader) {
             Ok(entity) => entity,
-            Err(err) if best_effort => continue,
+            Err(_) if best_effort => continue,
             Err(err) => return Err(to_py_err(err)),
         };
         result.push((
@@ -2596,15 +3269,16 @@ pub fn decode_minsert_entities(
     Ok(result)
 }
 
-#[pyfunction(signature = (path, limit=None))]
+#[pyfunction(signature = (path, limit=None, include_extrusion=false))]
 pub fn decode_polyline_2d_entities(
-    path: &str,
+    path: DwgSource,
     limit: Option<usize>,
+    include_extrusion: bool,
 ) -> PyResult<Vec<Polyline2dEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let dynamic_types = decoder.dynamic_type_map().map_err(to_py_err)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut result = Vec::new();
     for obj in index.objects.iter() {
         let record = decoder.parse_object_record(obj.offset).map_err(to_py_err)?;
@@ -2658,6 +3332,7 @@ pub fn decode_polyline_2d_entities(
             entity.width_end,
             entity.thickness,
             entity.elevation,
+            include_extrusion.then_some(entity.extrusion),
         ));
         if let Some(limit) = limit {
             if result.len() >= limit {
@@ -2670,13 +3345,13 @@ pub fn decode_polyline_2d_entities(
 
 #[pyfunction(signature = (path, limit=None))]
 pub fn decode_polyline_2d_entities_interpreted(
-    path: &str,
+    path: DwgSource,
     limit: Option<usize>,
 ) -> PyResult<Vec<Polyline2dInterpretedRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let dynamic_types = decoder.dynamic_type_map().map_err(to_py_err)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut result = Vec::new();
     for obj in index.objects.iter() {
         let record = decoder.parse_object_record(obj.offset).map_err(to_py_err)?;
@@ -2728,79 +3403,15 @@ pub fn decode_polyline_2d_entities_interpreted(
             entity.handle,
             entity.flags,
             entity.curve_type,
-            curve_label,
-            info.closed,
-            info.curve_fit,
-            info.spline_fit,
-            info.is_3d_polyline,
-            info.is_3d_mesh,
-            info.is_closed_mesh,
-            info.is_polyface_mesh,
-            info.continuous_linetype,
-        ));
-        if let Some(limit) = limit {
-            if result.len() >= limit {
-                break;
-            }
-        }
-    }
-    Ok(result)
-}
-
-#[pyfunction(signature = (path, limit=None))]
-pub fn decode_lwpolyline_entities(
-    path: &str,
-    limit: Option<usize>,
-) -> PyResult<Vec<LwPolylineEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
-    let best_effort = is_best_effort_compat_version(&decoder);
-    let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
-    let mut result = Vec::new();
-    for obj in index.objects.iter() {
-        let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
-        else {
-            continue;
-        };
-        if !matches_type_name(header.type_code, 0x4D, "LWPOLYLINE", &dynamic_types) {
-            continue;
-        }
-        let mut reader = record.bit_reader();
-        if let Err(err) = skip_object_type_prefix(&mut reader, decoder.version()) {
-            if best_effort {
-                continue;
-            }
-            return Err(to_py_err(err));
-        }
-        let entity = match decode_lwpolyline_for_version(
-            &mut reader,
-            decoder.version(),
-            &header,
-            obj.handle.0,
-        ) {
-            Ok(entity) => entity,
-            Err(err) if best_effort => {
-                if std::env::var("EZDWG_DEBUG_LWPOLYLINE")
-                    .ok()
-                    .is_some_and(|value| value != "0")
-                {
-                    eprintln!(
-                        "[lwpolyline] skip handle={} type=0x{:X} err={}",
-                        obj.handle.0, header.type_code, err
-                    );
-                }
-                continue;
-            }
-            Err(err) => return Err(to_py_err(err)),
-        };
-        result.push((
-            entity.handle,
-            entity.flags,
-            entity.vertices,
-            entity.bulges,
-            entity.widths,
-            entity.const_width,
+            curve_label,
+            info.closed,
+            info.curve_fit,
+            info.spline_fit,
+            info.is_3d_polyline,
+            info.is_3d_mesh,
+            info.is_closed_mesh,
+            info.is_polyface_mesh,
+            info.continuous_linetype,
         ));
         if let Some(limit) = limit {
             if result.len() >= limit {
@@ -2811,16 +3422,45 @@ pub fn decode_lwpolyline_entities(
     Ok(result)
 }
 
+#[pyfunction(signature = (path, limit=None, wcs=false))]
+pub fn decode_lwpolyline_entities(
+    path: DwgSource,
+    limit: Option<usize>,
+    wcs: bool,
+) -> PyResult<Vec<LwPolylineEntityRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let entities = high_level::decode_lwpolyline_entities(&decoder, limit).map_err(to_py_err)?;
+    Ok(entities
+        .into_iter()
+        .map(|mut entity| {
+            let vertices = if wcs {
+                LwPolylineVertices::World(lwpolyline_to_wcs(&mut entity))
+            } else {
+                LwPolylineVertices::Ocs(std::mem::take(&mut entity.vertices))
+            };
+            (
+                entity.handle,
+                entity.flags,
+                vertices,
+                entity.bulges,
+                entity.widths,
+                entity.const_width,
+            )
+        })
+        .collect())
+}
+
 #[pyfunction(signature = (path, limit=None))]
 pub fn decode_polyline_3d_entities(
-    path: &str,
+    path: DwgSource,
     limit: Option<usize>,
 ) -> PyResult<Vec<Polyline3dEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let best_effort = is_best_effort_compat_version(&decoder);
     let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut result = Vec::new();
     for obj in index.objects.iter() {
         let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
@@ -2844,7 +3484,7 @@ pub fn decode_polyline_3d_entities(
             obj.handle.0,
         ) {
             Ok(entity) => entity,
-            Err(err) if best_effort => continue,
+            Err(_) if best_effort => continue,
             Err(err) => return Err(to_py_err(err)),
         };
         result.push((entity.handle, entity.flags_75_bits, entity.flags_70_bits));
@@ -2859,14 +3499,14 @@ pub fn decode_polyline_3d_entities(
 
 #[pyfunction(signature = (path, limit=None))]
 pub fn decode_vertex_3d_entities(
-    path: &str,
+    path: DwgSource,
     limit: Option<usize>,
 ) -> PyResult<Vec<Vertex3dEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let best_effort = is_best_effort_compat_version(&decoder);
     let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut result = Vec::new();
     for obj in index.objects.iter() {
         let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
@@ -2890,7 +3530,7 @@ pub fn decode_vertex_3d_entities(
             obj.handle.0,
         ) {
             Ok(entity) => entity,
-            Err(err) if best_effort => continue,
+            Err(_) if best_effort => continue,
             Err(err) => return Err(to_py_err(err)),
         };
         result.push((
@@ -2911,19 +3551,15 @@ pub fn decode_vertex_3d_entities(
 
 #[pyfunction(signature = (path, limit=None))]
 pub fn decode_polyline_3d_with_vertices(
-    path: &str,
+    path: DwgSource,
     limit: Option<usize>,
 ) -> PyResult<Vec<Polyline3dVerticesRow>> {
-    let decoded_rows = decode_polyline_3d_vertex_rows(path, limit)?;
+    let decoded_rows = decode_polyline_3d_vertex_rows(&path, limit)?;
     let mut result = Vec::with_capacity(decoded_rows.len());
     for row in decoded_rows {
         let mut vertices: Vec<Point3> = row.vertices.iter().map(|vertex| vertex.position).collect();
-        if row.closed && vertices.len() > 1 {
-            let first = vertices[0];
-            let last = *vertices.last().unwrap();
-            if !points_equal_3d(first, last) {
-                vertices.push(first);
-            }
+        if row.closed {
+            close_vertex_loop(&mut vertices, |point| *point, path.config.point_epsilon);
         }
         result.push((row.handle, row.flags_70_bits, row.closed, vertices));
     }
@@ -2939,18 +3575,31 @@ struct Polyline3dVertexRow {
 }
 
 fn decode_polyline_3d_vertex_rows(
-    path: &str,
+    path: &DwgSource,
     limit: Option<usize>,
 ) -> PyResult<Vec<Polyline3dVertexRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(path, &bytes).map_err(to_py_err)?;
     let best_effort = is_best_effort_compat_version(&decoder);
     let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut sorted = index.objects.clone();
     sorted.sort_by_key(|obj| obj.offset);
 
     let vertex_map = build_vertex_3d_map(&decoder, &sorted, &dynamic_types, best_effort)?;
+    let vertex_owners = group_children_by_owner(
+        &decoder,
+        &sorted,
+        |handle| vertex_map.contains_key(&handle),
+        best_effort,
+    )?;
+    let walk = ChildWalk {
+        decoder: &decoder,
+        sorted: &sorted,
+        dynamic_types: &dynamic_types,
+        child_owners: &vertex_owners,
+        best_effort,
+    };
     let mut result = Vec::new();
     let mut i = 0usize;
     while i < sorted.len() {
@@ -2980,21 +3629,13 @@ fn decode_polyline_3d_vertex_rows(
             sorted[i].handle.0,
         ) {
             Ok(poly) => poly,
-            Err(err) if best_effort => {
+            Err(_) if best_effort => {
                 i += 1;
                 continue;
             }
             Err(err) => return Err(to_py_err(err)),
         };
-        let (vertices, next_i) = collect_polyline_3d_vertices(
-            &decoder,
-            &sorted,
-            &dynamic_types,
-            &vertex_map,
-            &poly,
-            i,
-            best_effort,
-        )?;
+        let (vertices, next_i) = collect_polyline_3d_vertices(&walk, &vertex_map, &poly, i)?;
         i = next_i;
         result.push(Polyline3dVertexRow {
             handle: poly.handle,
@@ -3041,7 +3682,7 @@ fn build_vertex_3d_map(
             obj.handle.0,
         ) {
             Ok(vertex) => vertex,
-            Err(err) if best_effort => continue,
+            Err(_) if best_effort => continue,
             Err(err) => return Err(to_py_err(err)),
         };
         vertex_map.insert(vertex.handle, vertex);
@@ -3050,90 +3691,35 @@ fn build_vertex_3d_map(
 }
 
 fn collect_polyline_3d_vertices(
-    decoder: &decoder::Decoder<'_>,
-    sorted: &[objects::ObjectRef],
-    dynamic_types: &HashMap<u16, String>,
+    walk: &ChildWalk<'_, '_>,
     vertex_map: &HashMap<u64, entities::Vertex3dEntity>,
     poly: &entities::Polyline3dEntity,
     start_index: usize,
-    best_effort: bool,
 ) -> PyResult<(Vec<entities::Vertex3dEntity>, usize)> {
-    let mut vertices = Vec::new();
-
-    if !poly.owned_handles.is_empty() {
-        for handle in &poly.owned_handles {
-            if let Some(vertex) = vertex_map.get(handle) {
-                vertices.push(vertex.clone());
-            }
-        }
-        return Ok((vertices, start_index + 1));
-    }
-
-    let mut next_i = start_index + 1;
-    while next_i < sorted.len() {
-        let Some((next_record, next_header)) =
-            parse_record_and_header(decoder, sorted[next_i].offset, best_effort)?
-        else {
-            next_i += 1;
-            continue;
-        };
-        let mut next_reader = next_record.bit_reader();
-        if matches_type_name(next_header.type_code, 0x0B, "VERTEX_3D", dynamic_types) {
-            if let Err(err) = skip_object_type_prefix(&mut next_reader, decoder.version()) {
-                if best_effort {
-                    next_i += 1;
-                    continue;
-                }
-                return Err(to_py_err(err));
-            }
-            let vertex = match decode_vertex_3d_for_version(
-                &mut next_reader,
-                decoder.version(),
-                &next_header,
-                sorted[next_i].handle.0,
-            ) {
-                Ok(vertex) => vertex,
-                Err(err) if best_effort => {
-                    next_i += 1;
-                    continue;
-                }
-                Err(err) => return Err(to_py_err(err)),
-            };
-            vertices.push(vertex);
-            next_i += 1;
-            continue;
-        }
-        if matches_type_name(next_header.type_code, 0x06, "SEQEND", dynamic_types) {
-            if let Err(err) = skip_object_type_prefix(&mut next_reader, decoder.version()) {
-                if best_effort {
-                    next_i += 1;
-                    continue;
-                }
-                return Err(to_py_err(err));
-            }
-            if let Err(err) = entities::decode_seqend(&mut next_reader) {
-                if !best_effort {
-                    return Err(to_py_err(err));
-                }
-            }
-            next_i += 1;
-        }
-        break;
-    }
-
-    Ok((vertices, next_i))
+    walk.owned_children(
+        poly.handle,
+        &poly.owned_handles,
+        start_index,
+        |handle| vertex_map.get(&handle).cloned(),
+        |type_code| matches_type_name(type_code, 0x0B, "VERTEX_3D", walk.dynamic_types),
+        |reader, header, handle| {
+            decode_vertex_3d_for_version(reader, walk.decoder.version(), header, handle)
+                .map(AdjacentChild::Take)
+        },
+    )
+    .map_err(to_py_err)
 }
 
 #[pyfunction(signature = (path, limit=None))]
 pub fn decode_polyline_mesh_entities(
-    path: &str,
+    path: DwgSource,
     limit: Option<usize>,
 ) -> PyResult<Vec<PolylineMeshEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let best_effort = is_best_effort_compat_version(&decoder);
     let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut result = Vec::new();
     for obj in index.objects.iter() {
         let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
@@ -3157,7 +3743,7 @@ pub fn decode_polyline_mesh_entities(
             obj.handle.0,
         ) {
             Ok(entity) => entity,
-            Err(err) if best_effort => continue,
+            Err(_) if best_effort => continue,
             Err(err) => return Err(to_py_err(err)),
         };
         result.push((
@@ -3180,14 +3766,14 @@ pub fn decode_polyline_mesh_entities(
 
 #[pyfunction(signature = (path, limit=None))]
 pub fn decode_vertex_mesh_entities(
-    path: &str,
+    path: DwgSource,
     limit: Option<usize>,
 ) -> PyResult<Vec<VertexMeshEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let best_effort = is_best_effort_compat_version(&decoder);
     let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut result = Vec::new();
     for obj in index.objects.iter() {
         let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
@@ -3211,7 +3797,7 @@ pub fn decode_vertex_mesh_entities(
             obj.handle.0,
         ) {
             Ok(entity) => entity,
-            Err(err) if best_effort => continue,
+            Err(_) if best_effort => continue,
             Err(err) => return Err(to_py_err(err)),
         };
         result.push((
@@ -3232,19 +3818,15 @@ pub fn decode_vertex_mesh_entities(
 
 #[pyfunction(signature = (path, limit=None))]
 pub fn decode_polyline_mesh_with_vertices(
-    path: &str,
+    path: DwgSource,
     limit: Option<usize>,
 ) -> PyResult<Vec<PolylineMeshVerticesRow>> {
-    let decoded_rows = decode_polyline_mesh_vertex_rows(path, limit)?;
+    let decoded_rows = decode_polyline_mesh_vertex_rows(&path, limit)?;
     let mut result = Vec::with_capacity(decoded_rows.len());
     for row in decoded_rows {
         let mut vertices: Vec<Point3> = row.vertices.iter().map(|vertex| vertex.position).collect();
-        if row.closed && vertices.len() > 1 {
-            let first = vertices[0];
-            let last = *vertices.last().unwrap();
-            if !points_equal_3d(first, last) {
-                vertices.push(first);
-            }
+        if row.closed {
+            close_vertex_loop(&mut vertices, |point| *point, path.config.point_epsilon);
         }
         result.push((
             row.handle,
@@ -3269,18 +3851,31 @@ struct PolylineMeshVertexRow {
 }
 
 fn decode_polyline_mesh_vertex_rows(
-    path: &str,
+    path: &DwgSource,
     limit: Option<usize>,
 ) -> PyResult<Vec<PolylineMeshVertexRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(path, &bytes).map_err(to_py_err)?;
     let best_effort = is_best_effort_compat_version(&decoder);
     let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut sorted = index.objects.clone();
     sorted.sort_by_key(|obj| obj.offset);
 
     let vertex_map = build_vertex_mesh_map(&decoder, &sorted, &dynamic_types, best_effort)?;
+    let vertex_owners = group_children_by_owner(
+        &decoder,
+        &sorted,
+        |handle| vertex_map.contains_key(&handle),
+        best_effort,
+    )?;
+    let walk = ChildWalk {
+        decoder: &decoder,
+        sorted: &sorted,
+        dynamic_types: &dynamic_types,
+        child_owners: &vertex_owners,
+        best_effort,
+    };
     let mut result = Vec::new();
     let mut i = 0usize;
     while i < sorted.len() {
@@ -3310,21 +3905,13 @@ fn decode_polyline_mesh_vertex_rows(
             sorted[i].handle.0,
         ) {
             Ok(poly) => poly,
-            Err(err) if best_effort => {
+            Err(_) if best_effort => {
                 i += 1;
                 continue;
             }
             Err(err) => return Err(to_py_err(err)),
         };
-        let (vertices, next_i) = collect_polyline_mesh_vertices(
-            &decoder,
-            &sorted,
-            &dynamic_types,
-            &vertex_map,
-            &poly,
-            i,
-            best_effort,
-        )?;
+        let (vertices, next_i) = collect_polyline_mesh_vertices(&walk, &vertex_map, &poly, i)?;
         i = next_i;
         result.push(PolylineMeshVertexRow {
             handle: poly.handle,
@@ -3373,7 +3960,7 @@ fn build_vertex_mesh_map(
             obj.handle.0,
         ) {
             Ok(vertex) => vertex,
-            Err(err) if best_effort => continue,
+            Err(_) if best_effort => continue,
             Err(err) => return Err(to_py_err(err)),
         };
         vertex_map.insert(vertex.handle, vertex);
@@ -3382,90 +3969,35 @@ fn build_vertex_mesh_map(
 }
 
 fn collect_polyline_mesh_vertices(
-    decoder: &decoder::Decoder<'_>,
-    sorted: &[objects::ObjectRef],
-    dynamic_types: &HashMap<u16, String>,
+    walk: &ChildWalk<'_, '_>,
     vertex_map: &HashMap<u64, entities::Vertex3dEntity>,
     poly: &entities::PolylineMeshEntity,
     start_index: usize,
-    best_effort: bool,
 ) -> PyResult<(Vec<entities::Vertex3dEntity>, usize)> {
-    let mut vertices = Vec::new();
-
-    if !poly.owned_handles.is_empty() {
-        for handle in &poly.owned_handles {
-            if let Some(vertex) = vertex_map.get(handle) {
-                vertices.push(vertex.clone());
-            }
-        }
-        return Ok((vertices, start_index + 1));
-    }
-
-    let mut next_i = start_index + 1;
-    while next_i < sorted.len() {
-        let Some((next_record, next_header)) =
-            parse_record_and_header(decoder, sorted[next_i].offset, best_effort)?
-        else {
-            next_i += 1;
-            continue;
-        };
-        let mut next_reader = next_record.bit_reader();
-        if matches_type_name(next_header.type_code, 0x0C, "VERTEX_MESH", dynamic_types) {
-            if let Err(err) = skip_object_type_prefix(&mut next_reader, decoder.version()) {
-                if best_effort {
-                    next_i += 1;
-                    continue;
-                }
-                return Err(to_py_err(err));
-            }
-            let vertex = match decode_vertex_3d_for_version(
-                &mut next_reader,
-                decoder.version(),
-                &next_header,
-                sorted[next_i].handle.0,
-            ) {
-                Ok(vertex) => vertex,
-                Err(err) if best_effort => {
-                    next_i += 1;
-                    continue;
-                }
-                Err(err) => return Err(to_py_err(err)),
-            };
-            vertices.push(vertex);
-            next_i += 1;
-            continue;
-        }
-        if matches_type_name(next_header.type_code, 0x06, "SEQEND", dynamic_types) {
-            if let Err(err) = skip_object_type_prefix(&mut next_reader, decoder.version()) {
-                if best_effort {
-                    next_i += 1;
-                    continue;
-                }
-                return Err(to_py_err(err));
-            }
-            if let Err(err) = entities::decode_seqend(&mut next_reader) {
-                if !best_effort {
-                    return Err(to_py_err(err));
-                }
-            }
-            next_i += 1;
-        }
-        break;
-    }
-
-    Ok((vertices, next_i))
+    walk.owned_children(
+        poly.handle,
+        &poly.owned_handles,
+        start_index,
+        |handle| vertex_map.get(&handle).cloned(),
+        |type_code| matches_type_name(type_code, 0x0C, "VERTEX_MESH", walk.dynamic_types),
+        |reader, header, handle| {
+            decode_vertex_3d_for_version(reader, walk.decoder.version(), header, handle)
+                .map(AdjacentChild::Take)
+        },
+    )
+    .map_err(to_py_err)
 }
 
 #[pyfunction(signature = (path, limit=None))]
 pub fn decode_polyline_pface_entities(
-    path: &str,
+    path: DwgSource,
     limit: Option<usize>,
 ) -> PyResult<Vec<PolylinePFaceEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let best_effort = is_best_effort_compat_version(&decoder);
     let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut result = Vec::new();
     for obj in index.objects.iter() {
         let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
@@ -3489,7 +4021,7 @@ pub fn decode_polyline_pface_entities(
             obj.handle.0,
         ) {
             Ok(entity) => entity,
-            Err(err) if best_effort => continue,
+            Err(_) if best_effort => continue,
             Err(err) => return Err(to_py_err(err)),
         };
         result.push((entity.handle, entity.num_vertices, entity.num_faces));
@@ -3504,14 +4036,14 @@ pub fn decode_polyline_pface_entities(
 
 #[pyfunction(signature = (path, limit=None))]
 pub fn decode_vertex_pface_entities(
-    path: &str,
+    path: DwgSource,
     limit: Option<usize>,
 ) -> PyResult<Vec<VertexPFaceEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let best_effort = is_best_effort_compat_version(&decoder);
     let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut result = Vec::new();
     for obj in index.objects.iter() {
         let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
@@ -3535,7 +4067,7 @@ pub fn decode_vertex_pface_entities(
             obj.handle.0,
         ) {
             Ok(entity) => entity,
-            Err(err) if best_effort => continue,
+            Err(_) if best_effort => continue,
             Err(err) => return Err(to_py_err(err)),
         };
         result.push((
@@ -3556,14 +4088,14 @@ pub fn decode_vertex_pface_entities(
 
 #[pyfunction(signature = (path, limit=None))]
 pub fn decode_vertex_pface_face_entities(
-    path: &str,
+    path: DwgSource,
     limit: Option<usize>,
 ) -> PyResult<Vec<VertexPFaceFaceEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let best_effort = is_best_effort_compat_version(&decoder);
     let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut result = Vec::new();
     for obj in index.objects.iter() {
         let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
@@ -3587,7 +4119,7 @@ pub fn decode_vertex_pface_face_entities(
             obj.handle.0,
         ) {
             Ok(entity) => entity,
-            Err(err) if best_effort => continue,
+            Err(_) if best_effort => continue,
             Err(err) => return Err(to_py_err(err)),
         };
         result.push((
@@ -3608,10 +4140,10 @@ pub fn decode_vertex_pface_face_entities(
 
 #[pyfunction(signature = (path, limit=None))]
 pub fn decode_polyline_pface_with_faces(
-    path: &str,
+    path: DwgSource,
     limit: Option<usize>,
 ) -> PyResult<Vec<PolylinePFaceFacesRow>> {
-    let decoded_rows = decode_polyline_pface_rows(path, limit)?;
+    let decoded_rows = decode_polyline_pface_rows(&path, limit)?;
     let mut result = Vec::with_capacity(decoded_rows.len());
     for row in decoded_rows {
         let vertices: Vec<Point3> = row.vertices.iter().map(|vertex| vertex.position).collect();
@@ -3626,12 +4158,15 @@ pub fn decode_polyline_pface_with_faces(
 }
 
 #[pyfunction(signature = (path, limit=None))]
-pub fn decode_solid_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<SolidEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+pub fn decode_solid_entities(
+    path: DwgSource,
+    limit: Option<usize>,
+) -> PyResult<Vec<SolidEntityRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let best_effort = is_best_effort_compat_version(&decoder);
     let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut result = Vec::new();
     for obj in index.objects.iter() {
         let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
@@ -3651,7 +4186,7 @@ pub fn decode_solid_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<S
         let entity =
             match decode_solid_for_version(&mut reader, decoder.version(), &header, obj.handle.0) {
                 Ok(entity) => entity,
-                Err(err) if best_effort => continue,
+                Err(_) if best_effort => continue,
                 Err(err) => return Err(to_py_err(err)),
             };
         result.push((
@@ -3673,12 +4208,15 @@ pub fn decode_solid_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<S
 }
 
 #[pyfunction(signature = (path, limit=None))]
-pub fn decode_trace_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<TraceEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+pub fn decode_trace_entities(
+    path: DwgSource,
+    limit: Option<usize>,
+) -> PyResult<Vec<TraceEntityRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let best_effort = is_best_effort_compat_version(&decoder);
     let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut result = Vec::new();
     for obj in index.objects.iter() {
         let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
@@ -3698,7 +4236,7 @@ pub fn decode_trace_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<T
         let entity =
             match decode_trace_for_version(&mut reader, decoder.version(), &header, obj.handle.0) {
                 Ok(entity) => entity,
-                Err(err) if best_effort => continue,
+                Err(_) if best_effort => continue,
                 Err(err) => return Err(to_py_err(err)),
             };
         result.push((
@@ -3720,12 +4258,396 @@ pub fn decode_trace_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<T
 }
 
 #[pyfunction(signature = (path, limit=None))]
-pub fn decode_shape_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<ShapeEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+pub fn decode_proxy_entities(
+    path: DwgSource,
+    limit: Option<usize>,
+) -> PyResult<Vec<ProxyEntityRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let entities = high_level::decode_proxy_entities(&decoder, limit).map_err(to_py_err)?;
+    Ok(entities
+        .into_iter()
+        .map(|entity| {
+            (
+                entity.handle,
+                entity.class_id,
+                entity.class_name,
+                entity.from_dxf,
+                entity.graphics_size,
+                entity.graphics_extents,
+            )
+        })
+        .collect())
+}
+
+#[pyfunction(signature = (path, limit=None))]
+pub fn decode_multileaders(path: DwgSource, limit: Option<usize>) -> PyResult<Vec<MLeaderRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let entities = high_level::decode_multileaders(&decoder, limit).map_err(to_py_err)?;
+    Ok(entities
+        .into_iter()
+        .map(|entity| {
+            let (content_type, text, location) = match entity.content {
+                entities::MLeaderContent::Text { text, location, .. } => {
+                    ("mtext", text, Some(location))
+                }
+                entities::MLeaderContent::Block { location, .. } => ("block", None, Some(location)),
+                entities::MLeaderContent::None => ("none", None, None),
+            };
+            let leaders = entity
+                .leaders
+                .into_iter()
+                .map(|root| {
+                    (
+                        root.landing_point,
+                        root.dogleg_vector,
+                        root.dogleg_length,
+                        root.lines,
+                    )
+                })
+                .collect();
+            (entity.handle, content_type, text, location, leaders)
+        })
+        .collect())
+}
+
+#[pyfunction(signature = (path, limit=None))]
+pub fn decode_wipeouts(path: DwgSource, limit: Option<usize>) -> PyResult<Vec<WipeoutRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let entities = high_level::decode_wipeouts(&decoder, limit).map_err(to_py_err)?;
+    Ok(entities
+        .into_iter()
+        .map(|entity| {
+            let boundary = entity.boundary_wcs();
+            (
+                entity.handle,
+                entity.display_flags,
+                entity.clipping,
+                boundary,
+            )
+        })
+        .collect())
+}
+
+#[pyfunction(signature = (path, limit=None))]
+pub fn decode_tables(path: DwgSource, limit: Option<usize>) -> PyResult<Vec<TableRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let entities = high_level::decode_tables(&decoder, limit).map_err(to_py_err)?;
+    Ok(entities
+        .into_iter()
+        .map(|entity| {
+            let cells = entity.text_grid();
+            (
+                entity.handle,
+                entity.insertion,
+                entity.row_count(),
+                entity.column_count(),
+                entity.row_heights,
+                entity.column_widths,
+                cells,
+            )
+        })
+        .collect())
+}
+
+#[pyfunction(signature = (path, strip_formatting=true, progress=None, skip_hidden_layers=false))]
+pub fn extract_all_text(
+    py: Python<'_>,
+    path: DwgSource,
+    strip_formatting: bool,
+    progress: Option<PyObject>,
+    skip_hidden_layers: bool,
+) -> PyResult<Vec<TextItemRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let callback_error = RefCell::new(None);
+    let items = high_level::extract_all_text_with_progress(
+        &decoder,
+        &mut py_progress(py, progress.as_ref(), &callback_error),
+    )
+    .map_err(to_py_err)?;
+    if let Some(err) = callback_error.take() {
+        return Err(err);
+    }
+    let hidden = if skip_hidden_layers {
+        hidden_layer_handles(&decoder)?
+    } else {
+        HashSet::new()
+    };
     let best_effort = is_best_effort_compat_version(&decoder);
     let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
+    let layer_names = collect_table_entry_names(
+        &decoder,
+        &index.objects,
+        &dynamic_types,
+        best_effort,
+        (0x33, "LAYER"),
+    )?;
+    Ok(items
+        .into_iter()
+        .filter(|item| !hidden.contains(&item.layer_handle))
+        .map(|item| {
+            let formatted = item.type_name == "MTEXT" || item.type_name.starts_with("DIM_");
+            let text = if strip_formatting && formatted {
+                entities::mtext_to_plain(&item.text)
+            } else {
+                item.text
+            };
+            (
+                item.handle,
+                item.type_name,
+                layer_names.get(&item.layer_handle).cloned(),
+                text,
+                item.insertion,
+            )
+        })
+        .collect())
+}
+
+#[pyfunction]
+pub fn list_images(path: DwgSource) -> PyResult<Vec<ImageRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let images = high_level::list_images(&decoder).map_err(to_py_err)?;
+    Ok(images
+        .into_iter()
+        .map(|(image, definition)| {
+            let clip = if image.clipping {
+                image.clip_vertices
+            } else {
+                Vec::new()
+            };
+            (
+                image.handle,
+                image.insertion,
+                image.u_vector,
+                image.v_vector,
+                image.size,
+                image.display_flags,
+                clip,
+                definition.map(|definition| {
+                    (
+                        definition.handle,
+                        definition.file_path,
+                        definition.image_size,
+                        definition.pixel_size,
+                        definition.resolution_units,
+                    )
+                }),
+            )
+        })
+        .collect())
+}
+
+#[pyfunction]
+pub fn list_vports(path: DwgSource) -> PyResult<Vec<VportRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let vports = high_level::list_vports(&decoder).map_err(to_py_err)?;
+    Ok(vports
+        .into_iter()
+        .map(|vport| {
+            (
+                vport.handle,
+                vport.name,
+                vport.lower_left,
+                vport.upper_right,
+                vport.view_center,
+                vport.view_height,
+                vport.aspect_ratio,
+                vport.view_target,
+                vport.view_direction,
+                (vport.grid_on, vport.grid_spacing),
+                (
+                    vport.snap_on,
+                    vport.snap_base,
+                    vport.snap_spacing,
+                    vport.snap_rotation,
+                ),
+            )
+        })
+        .collect())
+}
+
+#[pyfunction(signature = (path, limit=None, progress=None))]
+pub fn decode_entity_xdata(
+    py: Python<'_>,
+    path: DwgSource,
+    limit: Option<usize>,
+    progress: Option<PyObject>,
+) -> PyResult<Vec<EntityXDataRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let callback_error = RefCell::new(None);
+    let rows = high_level::decode_entity_xdata_with_progress(
+        &decoder,
+        limit,
+        &mut py_progress(py, progress.as_ref(), &callback_error),
+    )
+    .map_err(to_py_err)?;
+    if let Some(err) = callback_error.take() {
+        return Err(err);
+    }
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let blocks = row
+                .xdata
+                .into_iter()
+                .map(|(app_handle, values)| {
+                    let values = values
+                        .into_iter()
+                        .map(|value| (value.group_code(), xdata_value_to_py(py, value)))
+                        .collect();
+                    (app_handle, values)
+                })
+                .collect();
+            (row.handle, row.type_name, blocks)
+        })
+        .collect())
+}
+
+/// Adapt an optional Python `progress(done, total)` callable. Returning
+/// `False` stops the walk; raising (conventionally `Cancelled`) stops it too
+/// and leaves the exception in `error` for the caller to re-raise.
+fn py_progress<'a>(
+    py: Python<'a>,
+    callback: Option<&'a PyObject>,
+    error: &'a RefCell<Option<PyErr>>,
+) -> Progress<'a> {
+    let Some(callback) = callback else {
+        return Progress::none();
+    };
+    Progress::new(move |done, total| match callback.call1(py, (done, total)) {
+        Ok(result) => !matches!(result.extract::<bool>(py), Ok(false)),
+        Err(err) => {
+            *error.borrow_mut() = Some(err);
+            false
+        }
+    })
+}
+
+/// XDATA value as its DXF-style Python value: `str`, `"{"`/`"}"` for list
+/// markers, `bytes`, `int` handles and numbers, or a 3-tuple point.
+fn xdata_value_to_py(py: Python<'_>, value: entities::XDataValue) -> PyObject {
+    use entities::XDataValue;
+    match value {
+        XDataValue::String(text) => text.into_py(py),
+        XDataValue::ListStart => "{".into_py(py),
+        XDataValue::ListEnd => "}".into_py(py),
+        XDataValue::Binary(data) => PyBytes::new_bound(py, &data).into_py(py),
+        XDataValue::Layer(handle) | XDataValue::Handle(handle) => handle.into_py(py),
+        XDataValue::Point(point)
+        | XDataValue::WorldPosition(point)
+        | XDataValue::WorldDisplacement(point)
+        | XDataValue::WorldDirection(point) => point.into_py(py),
+        XDataValue::Real(value) | XDataValue::Distance(value) | XDataValue::ScaleFactor(value) => {
+            value.into_py(py)
+        }
+        XDataValue::Short(value) => value.into_py(py),
+        XDataValue::Long(value) => value.into_py(py),
+    }
+}
+
+#[pyfunction]
+pub fn list_appids(path: DwgSource) -> PyResult<Vec<AppIdRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let appids = high_level::list_appids(&decoder).map_err(to_py_err)?;
+    Ok(appids
+        .into_iter()
+        .map(|appid| (appid.handle, appid.name))
+        .collect())
+}
+
+#[pyfunction]
+pub fn list_blocks(path: DwgSource) -> PyResult<Vec<BlockRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let blocks = high_level::list_blocks(&decoder).map_err(to_py_err)?;
+    Ok(blocks
+        .into_iter()
+        .map(|block| {
+            (
+                block.header.handle,
+                block.header.name,
+                block.header.is_xref,
+                block.entity_count,
+                block.header.base_point,
+            )
+        })
+        .collect())
+}
+
+#[pyfunction]
+pub fn drawing_stats(path: DwgSource) -> PyResult<DrawingStatsRow> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let stats = high_level::drawing_stats(&decoder).map_err(to_py_err)?;
+    let sections = stats
+        .sections
+        .into_iter()
+        .map(|section| {
+            (
+                section.name,
+                section.size,
+                section.compressed_size,
+                section.page_count,
+                section.encrypted,
+                section.compression,
+            )
+        })
+        .collect();
+    Ok((
+        stats.version.as_str().to_string(),
+        stats.object_count,
+        stats.entity_count,
+        stats.layer_count,
+        stats.block_count,
+        sections,
+        stats.section_bytes,
+        stats.compressed_section_bytes,
+        stats.unsupported_types,
+    ))
+}
+
+#[pyfunction]
+pub fn modelspace_entity_handles(path: DwgSource) -> PyResult<Vec<u64>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    high_level::modelspace_entity_handles(&decoder).map_err(to_py_err)
+}
+
+#[pyfunction]
+pub fn list_xrefs(path: DwgSource) -> PyResult<Vec<XrefRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let xrefs = high_level::list_xrefs(&decoder).map_err(to_py_err)?;
+    Ok(xrefs
+        .into_iter()
+        .map(|xref| {
+            let status = xref
+                .xref_status()
+                .unwrap_or(objects::XrefStatus::Unresolved);
+            (xref.handle, xref.name, xref.xref_path, status.as_str())
+        })
+        .collect())
+}
+
+#[pyfunction(signature = (path, limit=None))]
+pub fn decode_shape_entities(
+    path: DwgSource,
+    limit: Option<usize>,
+) -> PyResult<Vec<ShapeEntityRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
+    let best_effort = is_best_effort_compat_version(&decoder);
+    let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
+    let index = scoped_object_index(&decoder)?;
     let mut result = Vec::new();
     for obj in index.objects.iter() {
         let Some((record, header)) = parse_record_and_header(&decoder, obj.offset, best_effort)?
@@ -3745,7 +4667,7 @@ pub fn decode_shape_entities(path: &str, limit: Option<usize>) -> PyResult<Vec<S
         let entity =
             match decode_shape_for_version(&mut reader, decoder.version(), &header, obj.handle.0) {
                 Ok(entity) => entity,
-                Err(err) if best_effort => continue,
+                Err(_) if best_effort => continue,
                 Err(err) => return Err(to_py_err(err)),
             };
         result.push((
@@ -3778,17 +4700,33 @@ struct PolylinePFaceRow {
     faces: Vec<entities::VertexPFaceFaceEntity>,
 }
 
-fn decode_polyline_pface_rows(path: &str, limit: Option<usize>) -> PyResult<Vec<PolylinePFaceRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+fn decode_polyline_pface_rows(
+    path: &DwgSource,
+    limit: Option<usize>,
+) -> PyResult<Vec<PolylinePFaceRow>> {
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(path, &bytes).map_err(to_py_err)?;
     let best_effort = is_best_effort_compat_version(&decoder);
     let dynamic_types = load_dynamic_types(&decoder, best_effort)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut sorted = index.objects.clone();
     sorted.sort_by_key(|obj| obj.offset);
 
     let vertex_map = build_vertex_pface_map(&decoder, &sorted, &dynamic_types, best_effort)?;
     let face_map = build_vertex_pface_face_map(&decoder, &sorted, &dynamic_types, best_effort)?;
+    let child_owners = group_children_by_owner(
+        &decoder,
+        &sorted,
+        |handle| vertex_map.contains_key(&handle) || face_map.contains_key(&handle),
+        best_effort,
+    )?;
+    let walk = ChildWalk {
+        decoder: &decoder,
+        sorted: &sorted,
+        dynamic_types: &dynamic_types,
+        child_owners: &child_owners,
+        best_effort,
+    };
     let mut result = Vec::new();
     let mut i = 0usize;
     while i < sorted.len() {
@@ -3818,22 +4756,14 @@ fn decode_polyline_pface_rows(path: &str, limit: Option<usize>) -> PyResult<Vec<
             sorted[i].handle.0,
         ) {
             Ok(poly) => poly,
-            Err(err) if best_effort => {
+            Err(_) if best_effort => {
                 i += 1;
                 continue;
             }
             Err(err) => return Err(to_py_err(err)),
         };
-        let (vertices, faces, next_i) = collect_polyline_pface_data(
-            &decoder,
-            &sorted,
-            &dynamic_types,
-            &vertex_map,
-            &face_map,
-            &poly,
-            i,
-            best_effort,
-        )?;
+        let (vertices, faces, next_i) =
+            collect_polyline_pface_data(&walk, &vertex_map, &face_map, &poly, i)?;
         i = next_i;
         result.push(PolylinePFaceRow {
             handle: poly.handle,
@@ -3881,7 +4811,7 @@ fn build_vertex_pface_map(
             obj.handle.0,
         ) {
             Ok(vertex) => vertex,
-            Err(err) if best_effort => continue,
+            Err(_) if best_effort => continue,
             Err(err) => return Err(to_py_err(err)),
         };
         vertex_map.insert(vertex.handle, vertex);
@@ -3918,7 +4848,7 @@ fn build_vertex_pface_face_map(
             obj.handle.0,
         ) {
             Ok(face) => face,
-            Err(err) if best_effort => continue,
+            Err(_) if best_effort => continue,
             Err(err) => return Err(to_py_err(err)),
         };
         face_map.insert(face.handle, face);
@@ -3927,141 +4857,77 @@ fn build_vertex_pface_face_map(
 }
 
 fn collect_polyline_pface_data(
-    decoder: &decoder::Decoder<'_>,
-    sorted: &[objects::ObjectRef],
-    dynamic_types: &HashMap<u16, String>,
+    walk: &ChildWalk<'_, '_>,
     vertex_map: &HashMap<u64, entities::Vertex3dEntity>,
     face_map: &HashMap<u64, entities::VertexPFaceFaceEntity>,
     poly: &entities::PolylinePFaceEntity,
     start_index: usize,
-    best_effort: bool,
 ) -> PyResult<(
     Vec<entities::Vertex3dEntity>,
     Vec<entities::VertexPFaceFaceEntity>,
     usize,
 )> {
-    let mut vertices = Vec::new();
-    let mut faces = Vec::new();
-
-    if !poly.owned_handles.is_empty() {
-        for handle in &poly.owned_handles {
-            if let Some(vertex) = vertex_map.get(handle) {
-                vertices.push(vertex.clone());
-                continue;
-            }
-            if let Some(face) = face_map.get(handle) {
-                faces.push(face.clone());
-            }
-        }
-        return Ok((vertices, faces, start_index + 1));
-    }
-
-    let mut next_i = start_index + 1;
-    while next_i < sorted.len() {
-        let Some((next_record, next_header)) =
-            parse_record_and_header(decoder, sorted[next_i].offset, best_effort)?
-        else {
-            next_i += 1;
-            continue;
-        };
-        let mut next_reader = next_record.bit_reader();
-        if matches_type_name(next_header.type_code, 0x0D, "VERTEX_PFACE", dynamic_types) {
-            if let Err(err) = skip_object_type_prefix(&mut next_reader, decoder.version()) {
-                if best_effort {
-                    next_i += 1;
-                    continue;
-                }
-                return Err(to_py_err(err));
-            }
-            let vertex = match decode_vertex_3d_for_version(
-                &mut next_reader,
-                decoder.version(),
-                &next_header,
-                sorted[next_i].handle.0,
-            ) {
-                Ok(vertex) => vertex,
-                Err(err) if best_effort => {
-                    next_i += 1;
-                    continue;
-                }
-                Err(err) => return Err(to_py_err(err)),
-            };
-            vertices.push(vertex);
-            next_i += 1;
-            continue;
-        }
-        if matches_type_name(
-            next_header.type_code,
-            0x0E,
-            "VERTEX_PFACE_FACE",
-            dynamic_types,
-        ) {
-            if let Err(err) = skip_object_type_prefix(&mut next_reader, decoder.version()) {
-                if best_effort {
-                    next_i += 1;
-                    continue;
-                }
-                return Err(to_py_err(err));
-            }
-            let face = match decode_vertex_pface_face_for_version(
-                &mut next_reader,
-                decoder.version(),
-                &next_header,
-                sorted[next_i].handle.0,
-            ) {
-                Ok(face) => face,
-                Err(err) if best_effort => {
-                    next_i += 1;
-                    continue;
-                }
-                Err(err) => return Err(to_py_err(err)),
-            };
-            faces.push(face);
-            next_i += 1;
-            continue;
-        }
-        if matches_type_name(next_header.type_code, 0x06, "SEQEND", dynamic_types) {
-            if let Err(err) = skip_object_type_prefix(&mut next_reader, decoder.version()) {
-                if best_effort {
-                    next_i += 1;
-                    continue;
-                }
-                return Err(to_py_err(err));
-            }
-            if let Err(err) = entities::decode_seqend(&mut next_reader) {
-                if !best_effort {
-                    return Err(to_py_err(err));
-                }
-            }
-            next_i += 1;
+    enum PFaceChild {
+        Vertex(entities::Vertex3dEntity),
+        Face(entities::VertexPFaceFaceEntity),
+    }
+
+    let is_vertex =
+        |type_code| matches_type_name(type_code, 0x0D, "VERTEX_PFACE", walk.dynamic_types);
+    let is_face =
+        |type_code| matches_type_name(type_code, 0x0E, "VERTEX_PFACE_FACE", walk.dynamic_types);
+    let version = walk.decoder.version();
+    let (children, next_i) = walk
+        .owned_children(
+            poly.handle,
+            &poly.owned_handles,
+            start_index,
+            |handle| {
+                vertex_map
+                    .get(&handle)
+                    .cloned()
+                    .map(PFaceChild::Vertex)
+                    .or_else(|| face_map.get(&handle).cloned().map(PFaceChild::Face))
+            },
+            |type_code| is_vertex(type_code) || is_face(type_code),
+            |reader, header, handle| {
+                let child = if is_vertex(header.type_code) {
+                    PFaceChild::Vertex(decode_vertex_3d_for_version(
+                        reader, version, header, handle,
+                    )?)
+                } else {
+                    PFaceChild::Face(decode_vertex_pface_face_for_version(
+                        reader, version, header, handle,
+                    )?)
+                };
+                Ok(AdjacentChild::Take(child))
+            },
+        )
+        .map_err(to_py_err)?;
+    let mut vertices = Vec::new();
+    let mut faces = Vec::new();
+    for child in children {
+        match child {
+            PFaceChild::Vertex(vertex) => vertices.push(vertex),
+            PFaceChild::Face(face) => faces.push(face),
         }
-        break;
     }
-
     Ok((vertices, faces, next_i))
 }
 
-#[pyfunction(signature = (path, limit=None))]
+#[pyfunction(signature = (path, limit=None, wcs=false))]
 pub fn decode_polyline_2d_with_vertices(
-    path: &str,
+    path: DwgSource,
     limit: Option<usize>,
+    wcs: bool,
 ) -> PyResult<Vec<PolylineVerticesRow>> {
-    let decoded_rows = decode_polyline_2d_vertex_rows(path, limit)?;
+    let decoded_rows = decode_polyline_2d_vertex_rows(&path, limit)?;
     let mut result = Vec::with_capacity(decoded_rows.len());
 
     for row in decoded_rows {
-        let use_vertex_z = polyline_uses_vertex_z(row.flags_info);
-        let mut vertices: Vec<Point3> = row
-            .vertices
-            .iter()
-            .map(|vertex| vertex_position_for_polyline(vertex, row.elevation, use_vertex_z))
-            .collect();
-        if row.flags_info.closed && vertices.len() > 1 {
-            let first = vertices[0];
-            let last = *vertices.last().unwrap();
-            if !points_equal_3d(first, last) {
-                vertices.push(first);
-            }
+        let mut vertices = polyline_vertex_positions(&row, wcs);
+        if row.flags_info.closed {
+            close_vertex_loop(&mut vertices, |point| *point, path.config.point_epsilon);
         }
         result.push((row.handle, row.flags, vertices));
     }
@@ -4069,22 +4935,20 @@ pub fn decode_polyline_2d_with_vertices(
     Ok(result)
 }
 
-#[pyfunction(signature = (path, segments_per_span=8, limit=None))]
+#[pyfunction(signature = (path, segments_per_span=None, limit=None, wcs=false))]
 pub fn decode_polyline_2d_with_vertices_interpolated(
-    path: &str,
-    segments_per_span: usize,
+    path: DwgSource,
+    segments_per_span: Option<usize>,
     limit: Option<usize>,
+    wcs: bool,
 ) -> PyResult<Vec<PolylineInterpolatedRow>> {
-    let decoded_rows = decode_polyline_2d_vertex_rows(path, limit)?;
+    let segments_per_span =
+        segments_per_span.unwrap_or_else(|| default_segments_per_span(&path.config));
+    let decoded_rows = decode_polyline_2d_vertex_rows(&path, limit)?;
     let mut result = Vec::with_capacity(decoded_rows.len());
 
     for row in decoded_rows {
-        let use_vertex_z = polyline_uses_vertex_z(row.flags_info);
-        let mut vertices: Vec<Point3> = row
-            .vertices
-            .iter()
-            .map(|vertex| vertex_position_for_polyline(vertex, row.elevation, use_vertex_z))
-            .collect();
+        let mut vertices = polyline_vertex_positions(&row, wcs);
         let mut applied = false;
         let should_interpolate = row.flags_info.curve_fit
             || row.flags_info.spline_fit
@@ -4096,18 +4960,18 @@ pub fn decode_polyline_2d_with_vertices_interpolated(
             );
 
         if should_interpolate && vertices.len() > 1 {
-            let base = strip_closure(vertices);
-            let interpolated =
-                entities::catmull_rom_spline(&base, row.flags_info.closed, segments_per_span)
-                    .map_err(to_py_err)?;
+            let base = strip_closure(vertices, path.config.point_epsilon);
+            let interpolated = entities::catmull_rom_spline(
+                &base,
+                row.flags_info.closed,
+                segments_per_span,
+                path.config.point_epsilon,
+            )
+            .map_err(to_py_err)?;
             vertices = interpolated;
             applied = true;
-        } else if row.flags_info.closed && vertices.len() > 1 {
-            let first = vertices[0];
-            let last = *vertices.last().unwrap();
-            if !points_equal_3d(first, last) {
-                vertices.push(first);
-            }
+        } else if row.flags_info.closed {
+            close_vertex_loop(&mut vertices, |point| *point, path.config.point_epsilon);
         }
 
         result.push((row.handle, row.flags, applied, vertices));
@@ -4118,13 +4982,13 @@ pub fn decode_polyline_2d_with_vertices_interpolated(
 
 #[pyfunction(signature = (path, limit=None))]
 pub fn decode_vertex_2d_entities(
-    path: &str,
+    path: DwgSource,
     limit: Option<usize>,
 ) -> PyResult<Vec<Vertex2dEntityRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(&path, &bytes).map_err(to_py_err)?;
     let dynamic_types = decoder.dynamic_type_map().map_err(to_py_err)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut result = Vec::new();
     for obj in index.objects.iter() {
         let record = decoder.parse_object_record(obj.offset).map_err(to_py_err)?;
@@ -4159,10 +5023,10 @@ pub fn decode_vertex_2d_entities(
 
 #[pyfunction(signature = (path, limit=None))]
 pub fn decode_polyline_2d_with_vertex_data(
-    path: &str,
+    path: DwgSource,
     limit: Option<usize>,
 ) -> PyResult<Vec<PolylineVertexDataRow>> {
-    let decoded_rows = decode_polyline_2d_vertex_rows(path, limit)?;
+    let decoded_rows = decode_polyline_2d_vertex_rows(&path, limit)?;
     let mut result = Vec::with_capacity(decoded_rows.len());
 
     for row in decoded_rows {
@@ -4172,12 +5036,12 @@ pub fn decode_polyline_2d_with_vertex_data(
             .iter()
             .map(|vertex| vertex_data_for_polyline(vertex, row.elevation, use_vertex_z))
             .collect();
-        if row.flags_info.closed && vertices.len() > 1 {
-            let first = vertices[0];
-            let last = *vertices.last().unwrap();
-            if !points_equal_3d_with_data(first, last) {
-                vertices.push(first);
-            }
+        if row.flags_info.closed {
+            close_vertex_loop(
+                &mut vertices,
+                |vertex| (vertex.0, vertex.1, vertex.2),
+                path.config.point_epsilon,
+            );
         }
         result.push((row.handle, row.flags, vertices));
     }
@@ -4192,21 +5056,34 @@ struct PolylineVertexRow {
     flags_info: entities::PolylineFlagsInfo,
     curve_type_info: entities::PolylineCurveType,
     elevation: f64,
+    extrusion: Point3,
     vertices: Vec<entities::Vertex2dEntity>,
 }
 
 fn decode_polyline_2d_vertex_rows(
-    path: &str,
+    path: &DwgSource,
     limit: Option<usize>,
 ) -> PyResult<Vec<PolylineVertexRow>> {
-    let bytes = file_open::read_file(path).map_err(to_py_err)?;
-    let decoder = build_decoder(&bytes).map_err(to_py_err)?;
+    let bytes = path.read_bytes().map_err(to_py_err)?;
+    let decoder = build_decoder(path, &bytes).map_err(to_py_err)?;
     let dynamic_types = decoder.dynamic_type_map().map_err(to_py_err)?;
-    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let index = scoped_object_index(&decoder)?;
     let mut sorted = index.objects.clone();
     sorted.sort_by_key(|obj| obj.offset);
 
     let vertex_map = build_vertex_2d_map(&decoder, &sorted, &dynamic_types)?;
+    let vertex_owners = if decoder.config().vertex_owner_fallback {
+        group_vertex_2d_by_owner(&sorted, &vertex_map)
+    } else {
+        HashMap::new()
+    };
+    let walk = ChildWalk {
+        decoder: &decoder,
+        sorted: &sorted,
+        dynamic_types: &dynamic_types,
+        child_owners: &vertex_owners,
+        best_effort: false,
+    };
     let mut result = Vec::new();
     let mut i = 0usize;
     while i < sorted.len() {
@@ -4260,8 +5137,7 @@ fn decode_polyline_2d_vertex_rows(
             i += 1;
             continue;
         }
-        let (vertices, next_i) =
-            collect_polyline_vertices(&decoder, &sorted, &dynamic_types, &vertex_map, &poly, i)?;
+        let (vertices, next_i) = collect_polyline_vertices(&walk, &vertex_map, &poly, i)?;
         i = next_i;
 
         result.push(PolylineVertexRow {
@@ -4270,6 +5146,7 @@ fn decode_polyline_2d_vertex_rows(
             flags_info: poly.flags_info,
             curve_type_info: poly.curve_type_info,
             elevation: poly.elevation,
+            extrusion: poly.extrusion,
             vertices,
         });
         if let Some(limit) = limit {
@@ -4304,58 +5181,118 @@ fn build_vertex_2d_map(
     Ok(vertex_map)
 }
 
-fn collect_polyline_vertices(
-    decoder: &decoder::Decoder<'_>,
+/// Groups decoded VERTEX_2D handles by their owner handle, in file offset order.
+fn group_vertex_2d_by_owner(
     sorted: &[objects::ObjectRef],
-    dynamic_types: &HashMap<u16, String>,
     vertex_map: &HashMap<u64, entities::Vertex2dEntity>,
-    poly: &entities::Polyline2dEntity,
-    start_index: usize,
-) -> PyResult<(Vec<entities::Vertex2dEntity>, usize)> {
-    let mut vertices = Vec::new();
+) -> HashMap<u64, Vec<u64>> {
+    group_by_owner(sorted, |handle| {
+        vertex_map
+            .get(&handle)
+            .and_then(|vertex| vertex.owner_handle)
+    })
+}
 
-    if !poly.owned_handles.is_empty() {
-        for handle in &poly.owned_handles {
-            if let Some(vertex) = vertex_map.get(handle) {
-                vertices.push(vertex.clone());
-            }
+/// Groups the handles in `sorted` that `owner_of` resolves by that owner,
+/// in file offset order, whatever else sits between them in the map.
+fn group_by_owner(
+    sorted: &[objects::ObjectRef],
+    owner_of: impl Fn(u64) -> Option<u64>,
+) -> HashMap<u64, Vec<u64>> {
+    let mut owners: HashMap<u64, Vec<u64>> = HashMap::new();
+    for obj in sorted {
+        if let Some(owner) = owner_of(obj.handle.0) {
+            owners.entry(owner).or_default().push(obj.handle.0);
         }
-        return Ok((vertices, start_index + 1));
     }
+    owners
+}
 
-    let mut next_i = start_index + 1;
-    while next_i < sorted.len() {
-        let next = sorted[next_i];
-        let next_record = decoder
-            .parse_object_record(next.offset)
-            .map_err(to_py_err)?;
-        let next_header =
-            parse_object_header_for_version(&next_record, decoder.version()).map_err(to_py_err)?;
-        let mut next_reader = next_record.bit_reader();
-        if matches_type_name(next_header.type_code, 0x0A, "VERTEX_2D", dynamic_types) {
-            let _next_type =
-                skip_object_type_prefix(&mut next_reader, decoder.version()).map_err(to_py_err)?;
-            let vertex = entities::decode_vertex_2d(&mut next_reader).map_err(to_py_err)?;
-            vertices.push(vertex);
-            next_i += 1;
+/// Child entities (`is_child` picks ATTRIBs, VERTEX_3Ds, ...) grouped by
+/// the owner reference in their own records. Children whose owner cannot
+/// be read are left out, so their owners fall back to object-map adjacency.
+/// Empty when `vertex_owner_fallback` is off.
+fn group_children_by_owner(
+    decoder: &decoder::Decoder<'_>,
+    sorted: &[objects::ObjectRef],
+    is_child: impl Fn(u64) -> bool,
+    best_effort: bool,
+) -> PyResult<HashMap<u64, Vec<u64>>> {
+    if !decoder.config().vertex_owner_fallback {
+        return Ok(HashMap::new());
+    }
+    let mut owner_of = HashMap::new();
+    for obj in sorted.iter().filter(|obj| is_child(obj.handle.0)) {
+        let Some((record, header)) = parse_record_and_header(decoder, obj.offset, best_effort)?
+        else {
+            continue;
+        };
+        let mut reader = record.bit_reader();
+        if skip_object_type_prefix(&mut reader, decoder.version()).is_err() {
             continue;
         }
-        if matches_type_name(next_header.type_code, 0x06, "SEQEND", dynamic_types) {
-            let _next_type =
-                skip_object_type_prefix(&mut next_reader, decoder.version()).map_err(to_py_err)?;
-            let _seqend = entities::decode_seqend(&mut next_reader).map_err(to_py_err)?;
-            next_i += 1;
+        if let Ok(EntityOwner::Block(owner)) =
+            parse_entity_owner_for_version(&mut reader, decoder.version(), &header, obj.handle.0)
+        {
+            owner_of.insert(obj.handle.0, owner);
         }
-        break;
     }
+    Ok(group_by_owner(sorted, |handle| {
+        owner_of.get(&handle).copied()
+    }))
+}
 
-    Ok((vertices, next_i))
+fn collect_polyline_vertices(
+    walk: &ChildWalk<'_, '_>,
+    vertex_map: &HashMap<u64, entities::Vertex2dEntity>,
+    poly: &entities::Polyline2dEntity,
+    start_index: usize,
+) -> PyResult<(Vec<entities::Vertex2dEntity>, usize)> {
+    walk.owned_children(
+        poly.handle,
+        &poly.owned_handles,
+        start_index,
+        |handle| vertex_map.get(&handle).cloned(),
+        |type_code| matches_type_name(type_code, 0x0A, "VERTEX_2D", walk.dynamic_types),
+        |reader, _header, _handle| {
+            let vertex = entities::decode_vertex_2d(reader)?;
+            Ok(
+                if vertex
+                    .owner_handle
+                    .is_some_and(|owner| owner != poly.handle)
+                {
+                    AdjacentChild::Stop
+                } else {
+                    AdjacentChild::Take(vertex)
+                },
+            )
+        },
+    )
+    .map_err(to_py_err)
 }
 
 fn polyline_uses_vertex_z(flags_info: entities::PolylineFlagsInfo) -> bool {
     flags_info.is_3d_polyline || flags_info.is_3d_mesh || flags_info.is_polyface_mesh
 }
 
+/// Vertex positions of `row`. With `wcs`, the OCS positions of a plain 2D
+/// polyline are moved into world coordinates; 3D polylines and meshes
+/// already store world positions.
+fn polyline_vertex_positions(row: &PolylineVertexRow, wcs: bool) -> Vec<Point3> {
+    let use_vertex_z = polyline_uses_vertex_z(row.flags_info);
+    row.vertices
+        .iter()
+        .map(|vertex| {
+            let position = vertex_position_for_polyline(vertex, row.elevation, use_vertex_z);
+            if wcs && !use_vertex_z {
+                geometry::ocs_to_wcs(row.extrusion, position)
+            } else {
+                position
+            }
+        })
+        .collect()
+}
+
 fn vertex_position_for_polyline(
     vertex: &entities::Vertex2dEntity,
     polyline_elevation: f64,
@@ -4392,24 +5329,54 @@ fn vertex_data_for_polyline(
 }
 
 pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add(
+        "UnsupportedEntityError",
+        module.py().get_type_bound::<UnsupportedEntityError>(),
+    )?;
+    module.add("Cancelled", module.py().get_type_bound::<Cancelled>())?;
     module.add_function(wrap_pyfunction!(detect_version, module)?)?;
+    module.add_function(wrap_pyfunction!(sniff, module)?)?;
+    module.add_function(wrap_pyfunction!(set_decode_cache_dir, module)?)?;
+    module.add_function(wrap_pyfunction!(configure, module)?)?;
+    module.add_class::<ConfiguredSource>()?;
     module.add_function(wrap_pyfunction!(list_section_locators, module)?)?;
+    module.add_function(wrap_pyfunction!(list_sections_detailed, module)?)?;
     module.add_function(wrap_pyfunction!(read_section_bytes, module)?)?;
+    module.add_function(wrap_pyfunction!(read_section_bytes_by_name, module)?)?;
+    module.add_function(wrap_pyfunction!(section_crcs, module)?)?;
+    module.add_function(wrap_pyfunction!(aux_header, module)?)?;
+    module.add_function(wrap_pyfunction!(measurement_units, module)?)?;
+    module.add_function(wrap_pyfunction!(read_thumbnail, module)?)?;
+    module.add_function(wrap_pyfunction!(analyze, module)?)?;
+    module.add_function(wrap_pyfunction!(compute_crc, module)?)?;
     module.add_function(wrap_pyfunction!(list_object_map_entries, module)?)?;
     module.add_function(wrap_pyfunction!(list_object_headers, module)?)?;
     module.add_function(wrap_pyfunction!(list_object_headers_with_type, module)?)?;
     module.add_function(wrap_pyfunction!(list_object_headers_by_type, module)?)?;
+    module.add_function(wrap_pyfunction!(object_type_histogram, module)?)?;
     module.add_function(wrap_pyfunction!(read_object_records_by_type, module)?)?;
+    module.add_function(wrap_pyfunction!(read_object_records_by_handles, module)?)?;
     module.add_function(wrap_pyfunction!(decode_entity_styles, module)?)?;
+    module.add_function(wrap_pyfunction!(decode_entity_line_styles, module)?)?;
     module.add_function(wrap_pyfunction!(decode_layer_colors, module)?)?;
+    module.add_function(wrap_pyfunction!(decode_layer_states, module)?)?;
     module.add_function(wrap_pyfunction!(decode_line_entities, module)?)?;
+    module.add_function(wrap_pyfunction!(merge_lines_to_polylines, module)?)?;
+    module.add_function(wrap_pyfunction!(aci_to_rgb, module)?)?;
+    module.add_function(wrap_pyfunction!(entity_bounds, module)?)?;
+    module.add_function(wrap_pyfunction!(spatial_query, module)?)?;
+    module.add_function(wrap_pyfunction!(object_summaries, module)?)?;
+    module.add_function(wrap_pyfunction!(list_inexact_decodes, module)?)?;
+    module.add_function(wrap_pyfunction!(decode_entities_paginated, module)?)?;
     module.add_function(wrap_pyfunction!(decode_point_entities, module)?)?;
     module.add_function(wrap_pyfunction!(decode_3dface_entities, module)?)?;
     module.add_function(wrap_pyfunction!(decode_arc_entities, module)?)?;
+    module.add_function(wrap_pyfunction!(decode_arc_sweeps, module)?)?;
     module.add_function(wrap_pyfunction!(decode_circle_entities, module)?)?;
     module.add_function(wrap_pyfunction!(decode_line_arc_circle_entities, module)?)?;
     module.add_function(wrap_pyfunction!(decode_ellipse_entities, module)?)?;
     module.add_function(wrap_pyfunction!(decode_spline_entities, module)?)?;
+    module.add_function(wrap_pyfunction!(decode_spline_points, module)?)?;
     module.add_function(wrap_pyfunction!(decode_text_entities, module)?)?;
     module.add_function(wrap_pyfunction!(decode_attrib_entities, module)?)?;
     module.add_function(wrap_pyfunction!(decode_attdef_entities, module)?)?;
@@ -4427,6 +5394,9 @@ pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
     module.add_function(wrap_pyfunction!(decode_dim_diameter_entities, module)?)?;
     module.add_function(wrap_pyfunction!(decode_dim_radius_entities, module)?)?;
     module.add_function(wrap_pyfunction!(decode_insert_entities, module)?)?;
+    module.add_function(wrap_pyfunction!(decode_insert_entities_with_block, module)?)?;
+    module.add_function(wrap_pyfunction!(decode_inserts_with_attribs, module)?)?;
+    module.add_function(wrap_pyfunction!(insert_transforms, module)?)?;
     module.add_function(wrap_pyfunction!(decode_minsert_entities, module)?)?;
     module.add_function(wrap_pyfunction!(decode_polyline_2d_entities, module)?)?;
     module.add_function(wrap_pyfunction!(
@@ -4446,6 +5416,19 @@ pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
     module.add_function(wrap_pyfunction!(decode_solid_entities, module)?)?;
     module.add_function(wrap_pyfunction!(decode_trace_entities, module)?)?;
     module.add_function(wrap_pyfunction!(decode_shape_entities, module)?)?;
+    module.add_function(wrap_pyfunction!(decode_proxy_entities, module)?)?;
+    module.add_function(wrap_pyfunction!(list_images, module)?)?;
+    module.add_function(wrap_pyfunction!(list_vports, module)?)?;
+    module.add_function(wrap_pyfunction!(list_appids, module)?)?;
+    module.add_function(wrap_pyfunction!(list_blocks, module)?)?;
+    module.add_function(wrap_pyfunction!(drawing_stats, module)?)?;
+    module.add_function(wrap_pyfunction!(modelspace_entity_handles, module)?)?;
+    module.add_function(wrap_pyfunction!(list_xrefs, module)?)?;
+    module.add_function(wrap_pyfunction!(decode_entity_xdata, module)?)?;
+    module.add_function(wrap_pyfunction!(decode_multileaders, module)?)?;
+    module.add_function(wrap_pyfunction!(decode_wipeouts, module)?)?;
+    module.add_function(wrap_pyfunction!(decode_tables, module)?)?;
+    module.add_function(wrap_pyfunction!(extract_all_text, module)?)?;
     module.add_function(wrap_pyfunction!(decode_polyline_2d_with_vertices, module)?)?;
     module.add_function(wrap_pyfunction!(
         decode_polyline_2d_with_vertices_interpolated,
@@ -4458,402 +5441,9 @@ pub fn register(module: &Bound<'_, PyModule>) -> PyResult<()> {
     module.add_function(wrap_pyfunction!(decode_vertex_pface_face_entities, module)?)?;
     module.add_function(wrap_pyfunction!(
         decode_polyline_2d_with_vertex_data,
-        module
-    )?)?;
-    Ok(())
-}
-
-fn is_best_effort_compat_version(decoder: &decoder::Decoder<'_>) -> bool {
-    matches!(
-        decoder.version(),
-        version::DwgVersion::R14
-            | version::DwgVersion::R2000
-            | version::DwgVersion::R2010
-            | version::DwgVersion::R2013
-            | version::DwgVersion::R2018
-    )
-}
-
-fn decode_line_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::LineEntity> {
-    match version {
-        version::DwgVersion::R14 => entities::decode_line_r14(reader, object_handle),
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_line_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_line_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_line_r2007(reader),
-        _ => entities::decode_line(reader),
-    }
-}
-
-fn decode_point_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::PointEntity> {
-    match version {
-        version::DwgVersion::R14 => entities::decode_point_r14(reader, object_handle),
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_point_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_point_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_point_r2007(reader),
-        _ => entities::decode_point(reader),
-    }
-}
-
-fn decode_arc_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::ArcEntity> {
-    match version {
-        version::DwgVersion::R14 => entities::decode_arc_r14(reader, object_handle),
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_arc_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_arc_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_arc_r2007(reader),
-        _ => entities::decode_arc(reader),
-    }
-}
-
-fn decode_circle_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::CircleEntity> {
-    match version {
-        version::DwgVersion::R14 => entities::decode_circle_r14(reader, object_handle),
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_circle_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_circle_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_circle_r2007(reader),
-        _ => entities::decode_circle(reader),
-    }
-}
-
-fn decode_ellipse_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::EllipseEntity> {
-    match version {
-        version::DwgVersion::R14 => entities::decode_ellipse_r14(reader, object_handle),
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_ellipse_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_ellipse_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_ellipse_r2007(reader),
-        _ => entities::decode_ellipse(reader),
-    }
-}
-
-fn decode_spline_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::SplineEntity> {
-    match version {
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_spline_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_spline_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_spline_r2007(reader),
-        _ => entities::decode_spline(reader),
-    }
-}
-
-fn decode_text_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::TextEntity> {
-    match version {
-        version::DwgVersion::R14 => entities::decode_text_r14(reader, object_handle),
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_text_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_text_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_text_r2007(reader),
-        _ => entities::decode_text(reader),
-    }
-}
-
-fn decode_attrib_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::AttribEntity> {
-    match version {
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_attrib_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_attrib_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_attrib_r2007(reader),
-        _ => entities::decode_attrib(reader),
-    }
-}
-
-fn decode_attdef_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::AttribEntity> {
-    match version {
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_attdef_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_attdef_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_attdef_r2007(reader),
-        _ => entities::decode_attdef(reader),
-    }
-}
-
-fn decode_mtext_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::MTextEntity> {
-    match version {
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_mtext_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_mtext_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_mtext_r2007(reader),
-        version::DwgVersion::R2004 => entities::decode_mtext_r2004(reader),
-        _ => entities::decode_mtext(reader),
-    }
-}
-
-fn decode_leader_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::LeaderEntity> {
-    match version {
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_leader_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_leader_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_leader_r2007(reader),
-        _ => entities::decode_leader(reader),
-    }
-}
-
-fn decode_hatch_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::HatchEntity> {
-    match version {
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_hatch_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_hatch_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_hatch_r2007(reader),
-        version::DwgVersion::R2004 => entities::decode_hatch_r2004(reader),
-        _ => entities::decode_hatch(reader),
-    }
-}
-
-fn decode_tolerance_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::ToleranceEntity> {
-    match version {
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_tolerance_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_tolerance_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_tolerance_r2007(reader),
-        _ => entities::decode_tolerance(reader),
-    }
-}
-
-fn decode_mline_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::MLineEntity> {
-    match version {
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_mline_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_mline_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_mline_r2007(reader),
-        _ => entities::decode_mline(reader),
-    }
-}
-
-fn decode_dim_linear_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::DimLinearEntity> {
-    match version {
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_dim_linear_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_dim_linear_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_dim_linear_r2007(reader),
-        _ => entities::decode_dim_linear(reader),
-    }
-}
-
-fn decode_dim_radius_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::DimRadiusEntity> {
-    match version {
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_dim_radius_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_dim_radius_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_dim_radius_r2007(reader),
-        _ => entities::decode_dim_radius(reader),
-    }
-}
-
-fn decode_dim_diameter_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::DimDiameterEntity> {
-    match version {
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_dim_diameter_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_dim_diameter_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_dim_diameter_r2007(reader),
-        _ => entities::decode_dim_diameter(reader),
-    }
-}
-
-fn decode_lwpolyline_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::LwPolylineEntity> {
-    match version {
-        version::DwgVersion::R14 => {
-            entities::decode_lwpolyline_r14(reader, object_handle, header.type_code)
-        }
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_lwpolyline_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_lwpolyline_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_lwpolyline_r2007(reader),
-        _ => entities::decode_lwpolyline(reader),
-    }
-}
-
-fn decode_polyline_2d_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::Polyline2dEntity> {
-    match version {
-        version::DwgVersion::R14 => entities::decode_polyline_2d_r14(reader, object_handle),
-        _ => entities::decode_polyline_2d(reader),
-    }
+        module
+    )?)?;
+    Ok(())
 }
 
 fn is_r14_polyline_2d_speculative_type(version: &version::DwgVersion, type_code: u16) -> bool {
@@ -4888,322 +5478,19 @@ fn is_plausible_polyline_2d_entity(entity: &entities::Polyline2dEntity) -> bool
     owned_len > 0 && owned_len <= 4096
 }
 
-fn decode_polyline_3d_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::Polyline3dEntity> {
-    match version {
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_polyline_3d_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_polyline_3d_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_polyline_3d_r2007(reader),
-        _ => entities::decode_polyline_3d(reader),
-    }
-}
-
-fn decode_vertex_3d_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::Vertex3dEntity> {
-    match version {
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_vertex_3d_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_vertex_3d_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_vertex_3d_r2007(reader),
-        _ => entities::decode_vertex_3d(reader),
-    }
-}
-
-fn decode_polyline_mesh_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::PolylineMeshEntity> {
-    match version {
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_polyline_mesh_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_polyline_mesh_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_polyline_mesh_r2007(reader),
-        _ => entities::decode_polyline_mesh(reader),
-    }
-}
-
-fn decode_polyline_pface_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::PolylinePFaceEntity> {
-    match version {
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_polyline_pface_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_polyline_pface_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_polyline_pface_r2007(reader),
-        _ => entities::decode_polyline_pface(reader),
-    }
-}
-
-fn decode_vertex_pface_face_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::VertexPFaceFaceEntity> {
-    match version {
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_vertex_pface_face_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_vertex_pface_face_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_vertex_pface_face_r2007(reader),
-        _ => entities::decode_vertex_pface_face(reader),
-    }
-}
-
-fn decode_3dface_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::Face3dEntity> {
-    match version {
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_3dface_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_3dface_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_3dface_r2007(reader),
-        _ => entities::decode_3dface(reader),
-    }
-}
-
-fn decode_solid_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::SolidEntity> {
-    match version {
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_solid_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_solid_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_solid_r2007(reader),
-        _ => entities::decode_solid(reader),
-    }
-}
-
-fn decode_trace_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::TraceEntity> {
-    match version {
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_trace_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_trace_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_trace_r2007(reader),
-        _ => entities::decode_trace(reader),
-    }
-}
-
-fn decode_shape_for_version(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    header: &ApiObjectHeader,
-    object_handle: u64,
-) -> crate::core::result::Result<entities::ShapeEntity> {
-    match version {
-        version::DwgVersion::R2010 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_shape_r2010(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
-            entities::decode_shape_r2013(reader, object_data_end_bit, object_handle)
-        }
-        version::DwgVersion::R2007 => entities::decode_shape_r2007(reader),
-        _ => entities::decode_shape(reader),
-    }
-}
-
-fn resolve_r2010_object_data_end_bit(header: &ApiObjectHeader) -> crate::core::result::Result<u32> {
-    let total_bits = header
-        .data_size
-        .checked_mul(8)
-        .ok_or_else(|| DwgError::new(ErrorKind::Format, "object size bits overflow"))?;
-    let handle_bits = header
-        .handle_stream_size_bits
-        .ok_or_else(|| DwgError::new(ErrorKind::Format, "missing R2010 handle stream size"))?;
-    // R2010+/R2013 object header reports handle stream size with an extra
-    // one-byte string-stream marker payload. Exclude it from the handle stream
-    // bit count when seeking to entity handle references.
-    let effective_handle_bits = handle_bits.saturating_sub(8);
-    total_bits
-        .checked_sub(effective_handle_bits)
-        .ok_or_else(|| {
-            DwgError::new(
-                ErrorKind::Format,
-                "R2010 handle stream exceeds object data size",
-            )
-        })
-}
-
-fn resolve_r2010_object_data_end_bit_candidates(header: &ApiObjectHeader) -> Vec<u32> {
-    let total_bits = header.data_size.saturating_mul(8);
-    let Some(handle_bits) = header.handle_stream_size_bits else {
-        return Vec::new();
-    };
-
-    let bases = [
-        total_bits.saturating_sub(handle_bits),
-        total_bits.saturating_sub(handle_bits.saturating_sub(8)),
-    ];
-    let deltas = [-16i32, -8, 0, 8, 16];
-
-    let mut out = Vec::new();
-    for base in bases {
-        for delta in deltas {
-            let candidate_i64 = i64::from(base) + i64::from(delta);
-            if candidate_i64 < 0 {
-                continue;
-            }
-            let Ok(candidate) = u32::try_from(candidate_i64) else {
-                continue;
-            };
-            if candidate > total_bits {
-                continue;
-            }
-            out.push(candidate);
-        }
-    }
-    out.sort_unstable();
-    out.dedup();
-    out
-}
-
-fn skip_object_type_prefix(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-) -> crate::core::result::Result<u16> {
-    match version {
-        version::DwgVersion::R2010 | version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let _handle_stream_size_bits = reader.read_umc()?;
-            let type_code = reader.read_ot_r2010()?;
-            if type_code == 0 {
-                return Err(DwgError::new(ErrorKind::Format, "object type code is zero"));
-            }
-            Ok(type_code)
-        }
-        _ => {
-            let type_code = reader.read_bs()?;
-            if type_code == 0 {
-                return Err(DwgError::new(ErrorKind::Format, "object type code is zero"));
-            }
-            Ok(type_code)
-        }
-    }
-}
-
-#[derive(Debug, Clone, Copy)]
-struct ApiObjectHeader {
-    data_size: u32,
-    type_code: u16,
-    handle_stream_size_bits: Option<u32>,
-}
-
-fn parse_object_header_for_version(
-    record: &objects::ObjectRecord<'_>,
-    version: &version::DwgVersion,
-) -> crate::core::result::Result<ApiObjectHeader> {
-    match version {
-        version::DwgVersion::R2010 | version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
-            let header = objects::object_header_r2010::parse_from_record(record)?;
-            Ok(ApiObjectHeader {
-                data_size: header.data_size,
-                type_code: header.type_code,
-                handle_stream_size_bits: Some(header.handle_stream_size_bits),
-            })
-        }
-        _ => {
-            let header = objects::object_header_r2000::parse_from_record(record)?;
-            Ok(ApiObjectHeader {
-                data_size: header.data_size,
-                type_code: header.type_code,
-                handle_stream_size_bits: None,
-            })
-        }
-    }
-}
-
 fn parse_record_and_header<'a>(
-    decoder: &decoder::Decoder<'a>,
+    decoder: &'a decoder::Decoder<'_>,
     offset: u32,
     best_effort: bool,
-) -> PyResult<Option<(objects::ObjectRecord<'a>, ApiObjectHeader)>> {
-    let record = match decoder.parse_object_record(offset) {
-        Ok(record) => record,
-        Err(err) if best_effort => return Ok(None),
-        Err(err) => return Err(to_py_err(err)),
-    };
-    let header = match parse_object_header_for_version(&record, decoder.version()) {
-        Ok(header) => header,
-        Err(err) if best_effort => return Ok(None),
-        Err(err) => return Err(to_py_err(err)),
-    };
-    Ok(Some((record, header)))
+) -> PyResult<Option<(objects::ObjectRecord<'a>, ObjectHeader)>> {
+    high_level::parse_record_and_header(decoder, offset, best_effort).map_err(to_py_err)
 }
 
 fn load_dynamic_types(
     decoder: &decoder::Decoder<'_>,
     best_effort: bool,
 ) -> PyResult<HashMap<u16, String>> {
-    match decoder.dynamic_type_map() {
-        Ok(map) => Ok(map),
-        Err(_) if best_effort => Ok(HashMap::new()),
-        Err(err) => Err(to_py_err(err)),
-    }
+    high_level::load_dynamic_types(decoder, best_effort).map_err(to_py_err)
 }
 
 fn collect_known_layer_handles_in_order(
@@ -5212,11 +5499,13 @@ fn collect_known_layer_handles_in_order(
     index: &objects::ObjectIndex,
     best_effort: bool,
 ) -> PyResult<Vec<u64>> {
+    let objects_data = decoder.objects_data().map_err(to_py_err)?;
     let mut layer_handles = Vec::new();
-    for obj in index.objects.iter() {
-        let Some((_record, header)) = parse_record_and_header(decoder, obj.offset, best_effort)?
-        else {
-            continue;
+    for item in index.headers(&objects_data, decoder.version()) {
+        let (obj, header) = match item {
+            Ok(item) => item,
+            Err(_) if best_effort => continue,
+            Err(err) => return Err(to_py_err(err)),
         };
         if matches_type_name(header.type_code, 0x33, "LAYER", dynamic_types) {
             layer_handles.push(obj.handle.0);
@@ -5228,7 +5517,7 @@ fn collect_known_layer_handles_in_order(
 fn recover_entity_layer_handle_r2010_plus(
     record: &objects::ObjectRecord<'_>,
     version: &version::DwgVersion,
-    api_header: &ApiObjectHeader,
+    api_header: &ObjectHeader,
     object_handle: u64,
     parsed_layer_handle: u64,
     known_layer_handles: &HashSet<u64>,
@@ -5459,7 +5748,7 @@ fn recover_entity_layer_handle_r2010_plus(
 fn parse_expected_entity_layer_ref_index(
     record: &objects::ObjectRecord<'_>,
     version: &version::DwgVersion,
-    api_header: &ApiObjectHeader,
+    api_header: &ObjectHeader,
     object_handle: u64,
 ) -> Option<usize> {
     let object_data_end_bit = resolve_r2010_object_data_end_bit(api_header).ok()?;
@@ -5517,7 +5806,7 @@ fn parse_expected_entity_layer_ref_index(
 fn parse_common_entity_layer_handle_from_common_header(
     record: &objects::ObjectRecord<'_>,
     version: &version::DwgVersion,
-    api_header: &ApiObjectHeader,
+    api_header: &ObjectHeader,
 ) -> Option<u64> {
     let object_data_end_bit = resolve_r2010_object_data_end_bit(api_header).ok()?;
     let mut reader = record.bit_reader();
@@ -5535,7 +5824,7 @@ fn parse_common_entity_layer_handle_from_common_header(
         }
         _ => return None,
     };
-    reader.set_bit_pos(header.obj_size);
+    reader.set_bit_pos(header.handle_stream_start_bit());
     entities::common::parse_common_entity_layer_handle(&mut reader, &header).ok()
 }
 
@@ -5544,14 +5833,7 @@ fn read_handle_reference_chained(
     prev_handle: &mut u64,
 ) -> crate::core::result::Result<u64> {
     let handle = reader.read_h()?;
-    let absolute = match handle.code {
-        0x06 => prev_handle.saturating_add(1),
-        0x08 => prev_handle.saturating_sub(1),
-        0x0A => prev_handle.saturating_add(handle.value),
-        0x0C => prev_handle.saturating_sub(handle.value),
-        0x02..=0x05 => handle.value,
-        _ => handle.value,
-    };
+    let absolute = entities::common::resolve_handle_reference(handle, *prev_handle)?;
     *prev_handle = absolute;
     Ok(absolute)
 }
@@ -5566,254 +5848,186 @@ fn layer_handle_score(layer_handle: u64, known_layer_handles: &HashSet<u64>) ->
     }
 }
 
-fn decode_layer_color_record(
-    reader: &mut BitReader<'_>,
-    version: &version::DwgVersion,
-    expected_handle: u64,
-) -> crate::core::result::Result<(u64, u16, Option<u32>)> {
-    // R2010+/R2013 objects start with handle directly after OT prefix.
-    // Older versions keep ObjSize (RL) before handle.
-    if !matches!(
-        version,
-        version::DwgVersion::R2010 | version::DwgVersion::R2013 | version::DwgVersion::R2018
-    ) {
-        let _obj_size = reader.read_rl(Endian::Little)?;
-    }
-    let record_handle = reader.read_h()?.value;
-    skip_eed(reader)?;
-
-    let _num_reactors = reader.read_bl()?;
-    let _xdic_missing_flag = reader.read_b()?;
-    if matches!(
-        version,
-        version::DwgVersion::R2013 | version::DwgVersion::R2018
-    ) {
-        let _has_ds_binary_data = reader.read_b()?;
-    }
-    // R2010+ stores entry name in string stream. The data stream directly
-    // continues with layer state flags and color data.
-    if !matches!(
-        version,
-        version::DwgVersion::R2010 | version::DwgVersion::R2013 | version::DwgVersion::R2018
-    ) {
-        let _entry_name = reader.read_tv()?;
-    }
-
-    let style_start = reader.get_pos();
-    let variants = [
-        LayerColorParseVariant {
-            pre_flag_bits: 0,
-            post_flag_bits: 0,
-            pre_values_bits: 0,
-        },
-        LayerColorParseVariant {
-            pre_flag_bits: 2,
-            post_flag_bits: 0,
-            pre_values_bits: 0,
-        },
-        LayerColorParseVariant {
-            pre_flag_bits: 0,
-            post_flag_bits: 2,
-            pre_values_bits: 0,
-        },
-        LayerColorParseVariant {
-            pre_flag_bits: 0,
-            post_flag_bits: 0,
-            pre_values_bits: 2,
-        },
-        LayerColorParseVariant {
-            pre_flag_bits: 2,
-            post_flag_bits: 2,
-            pre_values_bits: 0,
-        },
-        LayerColorParseVariant {
-            pre_flag_bits: 2,
-            post_flag_bits: 0,
-            pre_values_bits: 2,
-        },
-        LayerColorParseVariant {
-            pre_flag_bits: 0,
-            post_flag_bits: 2,
-            pre_values_bits: 2,
-        },
-        LayerColorParseVariant {
-            pre_flag_bits: 2,
-            post_flag_bits: 2,
-            pre_values_bits: 2,
-        },
-    ];
-
-    let mut best: Option<(u64, (u16, Option<u32>))> = None;
-    for variant in variants {
-        reader.set_pos(style_start.0, style_start.1);
-        let Ok((color_index, true_color, color_byte)) = decode_layer_color_cmc(reader, variant)
-        else {
-            continue;
-        };
-        let score = layer_color_candidate_score(color_index, true_color, color_byte);
-        match &best {
-            Some((best_score, _)) if score >= *best_score => {}
-            _ => best = Some((score, (color_index, true_color))),
-        }
-    }
-
-    if let Some((_, (color_index, true_color))) = best {
-        let handle = if record_handle != 0 {
-            record_handle
-        } else {
-            expected_handle
+/// The object index without the entities `ParseConfig::space_filter`
+/// excludes, for the walks below that match entity types themselves.
+fn scoped_object_index(decoder: &decoder::Decoder<'_>) -> PyResult<objects::ObjectIndex> {
+    let index = decoder.build_object_index().map_err(to_py_err)?;
+    let objects = high_level::scope_objects(decoder, index.objects).map_err(to_py_err)?;
+    Ok(objects::ObjectIndex::from_objects(objects))
+}
+
+/// `bytes` must be the contents of `source`. Paths go through the decode
+/// cache when one is configured.
+fn build_decoder<'a>(
+    source: &DwgSource,
+    bytes: &'a [u8],
+) -> crate::core::result::Result<decoder::Decoder<'a>> {
+    #[cfg(feature = "cache")]
+    if let SourceInput::Path(path) = &source.input {
+        let cache = DECODE_CACHE.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(cache) = cache.as_ref() {
+            return cache.open(path, bytes, source.config.clone());
+        }
+    }
+    decoder::Decoder::new(bytes, source.config.clone())
+}
+
+/// Bind `path` to the named `ParseConfig` preset: `"default"`, `"strict"`,
+/// `"tolerant"` or `"fast_preview"`, with the fields named in `config`
+/// overridden. Raw calls given the result decode with that configuration;
+/// other calls keep the default.
+#[pyfunction(signature = (path, preset=None, config=None))]
+pub fn configure(
+    path: DwgSource,
+    preset: Option<&str>,
+    config: Option<&Bound<'_, PyDict>>,
+) -> PyResult<ConfiguredSource> {
+    let base = match preset {
+        Some(name) => ParseConfig::preset(name).ok_or_else(|| {
+            PyValueError::new_err(format!(
+                "unknown parse config preset: {name} \
+                 (expected default, strict, tolerant or fast_preview)"
+            ))
+        })?,
+        None => ParseConfig::default(),
+    };
+    let config = match config {
+        Some(fields) => apply_config_fields(base, fields)?,
+        None => base,
+    };
+    Ok(ConfiguredSource {
+        source: DwgSource { config, ..path },
+    })
+}
+
+/// `config` entries are `ParseConfig` field names; values must convert to
+/// the field's type, with `space_filter` one of `"model"`, `"paper"` or
+/// `"all"` and `arc_max_sagitta` a float or `None`.
+fn apply_config_fields(config: ParseConfig, fields: &Bound<'_, PyDict>) -> PyResult<ParseConfig> {
+    let mut builder = ParseConfigBuilder::from(config);
+    for (key, value) in fields.iter() {
+        let name: String = key.extract()?;
+        builder = match name.as_str() {
+            "strict" => builder.strict(value.extract()?),
+            "max_recursion" => builder.max_recursion(value.extract()?),
+            "max_objects" => builder.max_objects(value.extract()?),
+            "max_section_bytes" => builder.max_section_bytes(value.extract()?),
+            "vertex_owner_fallback" => builder.vertex_owner_fallback(value.extract()?),
+            "retain_objects_buffer" => builder.retain_objects_buffer(value.extract()?),
+            "point_epsilon" => builder.point_epsilon(value.extract()?),
+            "space_filter" => {
+                builder.space_filter(parse_space_filter(&value.extract::<String>()?)?)
+            }
+            "strict_dimensions" => builder.strict_dimensions(value.extract()?),
+            "scan_recovery" => builder.scan_recovery(value.extract()?),
+            "arc_segments" => builder.arc_segments(value.extract()?),
+            "arc_max_sagitta" => builder.arc_max_sagitta(value.extract()?),
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "unknown parse config field: {name}"
+                )))
+            }
         };
-        return Ok((handle, color_index, true_color));
     }
-
-    // Last resort: parse in the simplest form to keep progress.
-    reader.set_pos(style_start.0, style_start.1);
-    let (color_index, true_color, _) = decode_layer_color_cmc(reader, variants[0])?;
-    let handle = if record_handle != 0 {
-        record_handle
-    } else {
-        expected_handle
-    };
-    Ok((handle, color_index, true_color))
-}
-
-#[derive(Clone, Copy)]
-struct LayerColorParseVariant {
-    pre_flag_bits: u8,
-    post_flag_bits: u8,
-    pre_values_bits: u8,
+    Ok(builder.build())
 }
 
-fn decode_layer_color_cmc(
-    reader: &mut BitReader<'_>,
-    variant: LayerColorParseVariant,
-) -> crate::core::result::Result<(u16, Option<u32>, u8)> {
-    if variant.pre_flag_bits > 0 {
-        let _unknown = reader.read_bits_msb(variant.pre_flag_bits)?;
-    }
-    let _flag_64 = reader.read_b()?;
-    if variant.post_flag_bits > 0 {
-        let _unknown = reader.read_bits_msb(variant.post_flag_bits)?;
-    }
-    let _xref_index_plus_one = reader.read_bs()?;
-    let _xdep = reader.read_b()?;
-    let _frozen = reader.read_b()?;
-    let _on = reader.read_b()?;
-    let _frozen_new = reader.read_b()?;
-    let _locked = reader.read_b()?;
-    if variant.pre_values_bits > 0 {
-        let _unknown = reader.read_bits_msb(variant.pre_values_bits)?;
-    }
-    let _values = reader.read_bs()?;
-
-    let color_index = reader.read_bs()?;
-    let color_rgb = reader.read_bl()?;
-    let color_byte = reader.read_rc()?;
-    if (color_byte & 0x01) != 0 {
-        let _color_name = reader.read_tv()?;
-    }
-    if (color_byte & 0x02) != 0 {
-        let _book_name = reader.read_tv()?;
+fn parse_space_filter(name: &str) -> PyResult<SpaceFilter> {
+    match name {
+        "model" => Ok(SpaceFilter::ModelOnly),
+        "paper" => Ok(SpaceFilter::PaperOnly),
+        "all" => Ok(SpaceFilter::All),
+        _ => Err(PyValueError::new_err(format!(
+            "unknown space filter: {name} (expected model, paper or all)"
+        ))),
     }
-
-    let true_color = if color_rgb == 0 || (color_rgb >> 24) == 0 {
-        // Keep only true 24-bit payload with marker byte present.
-        // If high byte is zero, treat as unset to prefer indexed color.
-        None
-    } else {
-        let rgb = color_rgb & 0x00FF_FFFF;
-        if rgb == 0 {
-            None
-        } else {
-            Some(rgb)
-        }
-    };
-    Ok((color_index, true_color, color_byte))
 }
 
-fn layer_color_candidate_score(color_index: u16, true_color: Option<u32>, color_byte: u8) -> u64 {
-    let mut score = 0u64;
-
-    if color_index <= 257 {
-        score += 0;
-    } else if color_index <= 4096 {
-        score += 1_000;
-    } else {
-        score += 100_000;
-    }
-
-    if color_byte <= 3 {
-        score += 0;
-    } else {
-        score += 10_000;
-    }
+#[cfg(feature = "cache")]
+static DECODE_CACHE: std::sync::Mutex<Option<crate::dwg::cache::DecodeCache>> =
+    std::sync::Mutex::new(None);
 
-    if let Some(rgb) = true_color {
-        if rgb == 0 || rgb > 0x00FF_FFFF {
-            score += 10_000;
-        }
+/// Cache the object map and objects section of every file opened by path
+/// under `dir`, keyed by path, modification time and size; `None` turns the
+/// cache off. Needs the `cache` build feature.
+#[pyfunction(signature = (dir=None))]
+pub fn set_decode_cache_dir(dir: Option<PathBuf>) -> PyResult<()> {
+    #[cfg(feature = "cache")]
+    {
+        let mut cache = DECODE_CACHE.lock().unwrap_or_else(|err| err.into_inner());
+        *cache = dir.map(crate::dwg::cache::DecodeCache::new);
+        Ok(())
     }
-
-    score
-}
-
-fn skip_eed(reader: &mut BitReader<'_>) -> crate::core::result::Result<()> {
-    let mut ext_size = reader.read_bs()?;
-    while ext_size > 0 {
-        let _app_handle = reader.read_h()?;
-        for _ in 0..ext_size {
-            let _ = reader.read_rc()?;
-        }
-        ext_size = reader.read_bs()?;
+    #[cfg(not(feature = "cache"))]
+    match dir {
+        Some(_) => Err(PyNotImplementedError::new_err(
+            "ezdwg was built without the `cache` feature",
+        )),
+        None => Ok(()),
     }
-    Ok(())
-}
-
-fn is_recoverable_decode_error(err: &DwgError) -> bool {
-    matches!(
-        err.kind,
-        ErrorKind::NotImplemented | ErrorKind::Decode | ErrorKind::Format
-    )
 }
 
-fn build_decoder(bytes: &[u8]) -> crate::core::result::Result<decoder::Decoder<'_>> {
-    decoder::Decoder::new(bytes, Default::default())
+/// Catmull-Rom spans get an eighth of the chords of a full arc turn.
+fn default_segments_per_span(config: &ParseConfig) -> usize {
+    (config.arc_segments / 8).max(1)
 }
 
 fn to_py_err(err: DwgError) -> PyErr {
     let message = err.to_string();
-    match err.kind {
+    let py_err = match err.kind {
         ErrorKind::Io => PyIOError::new_err(message),
-        ErrorKind::Format | ErrorKind::Decode | ErrorKind::Resolve | ErrorKind::Unsupported => {
-            PyValueError::new_err(message)
-        }
+        ErrorKind::Format
+        | ErrorKind::Decode
+        | ErrorKind::Resolve
+        | ErrorKind::Unsupported
+        | ErrorKind::NotDwg
+        | ErrorKind::SentinelMismatch => PyValueError::new_err(message),
+        ErrorKind::UnsupportedEntity => UnsupportedEntityError::new_err(message),
         ErrorKind::NotImplemented => PyNotImplementedError::new_err(message),
+        ErrorKind::Cancelled => Cancelled::new_err(message),
+    };
+    // Expose the structured fields so callers can branch on them without
+    // parsing the message.
+    Python::with_gil(|py| {
+        let value = py_err.value_bound(py);
+        let _ = value.setattr("kind", err.kind.to_string());
+        let _ = value.setattr("offset", err.offset);
+        let _ = value.setattr("detail", err.message.as_str());
+    });
+    py_err
+}
+
+/// Repeat the first vertex at the end of a closed loop unless the last vertex
+/// already coincides with it.
+fn close_vertex_loop<T: Copy>(
+    vertices: &mut Vec<T>,
+    position: impl Fn(&T) -> Point3,
+    epsilon: f64,
+) {
+    if vertices.len() < 2 {
+        return;
+    }
+    let first = vertices[0];
+    if !points_equal_3d(
+        position(&first),
+        position(vertices.last().unwrap()),
+        epsilon,
+    ) {
+        vertices.push(first);
     }
 }
 
-fn points_equal_3d(a: (f64, f64, f64), b: (f64, f64, f64)) -> bool {
-    const EPS: f64 = 1e-9;
-    (a.0 - b.0).abs() < EPS && (a.1 - b.1).abs() < EPS && (a.2 - b.2).abs() < EPS
-}
-
-fn strip_closure(mut points: Vec<(f64, f64, f64)>) -> Vec<(f64, f64, f64)> {
+fn strip_closure(mut points: Vec<(f64, f64, f64)>, epsilon: f64) -> Vec<(f64, f64, f64)> {
     if points.len() > 1 {
         let first = points[0];
         let last = *points.last().unwrap();
-        if points_equal_3d(first, last) {
+        if points_equal_3d(first, last, epsilon) {
             points.pop();
         }
     }
     points
 }
 
-fn points_equal_3d_with_data(
-    a: (f64, f64, f64, f64, f64, f64, f64, u16),
-    b: (f64, f64, f64, f64, f64, f64, f64, u16),
-) -> bool {
-    points_equal_3d((a.0, a.1, a.2), (b.0, b.1, b.2))
-}
-
 fn resolved_type_name(type_code: u16, dynamic_types: &HashMap<u16, String>) -> String {
     dynamic_types
         .get(&type_code)
@@ -5832,21 +6046,6 @@ fn resolved_type_class(type_code: u16, resolved_name: &str) -> String {
     String::new()
 }
 
-fn matches_type_name(
-    type_code: u16,
-    builtin_code: u16,
-    builtin_name: &str,
-    dynamic_types: &HashMap<u16, String>,
-) -> bool {
-    if type_code == builtin_code {
-        return true;
-    }
-    dynamic_types
-        .get(&type_code)
-        .map(|name| name == builtin_name)
-        .unwrap_or(false)
-}
-
 fn matches_type_filter(filter: &HashSet<u16>, type_code: u16, resolved_name: &str) -> bool {
     if filter.contains(&type_code) {
         return true;
@@ -5875,10 +6074,213 @@ fn builtin_code_from_name(name: &str) -> Option<u16> {
         "DIM_RADIUS" => Some(0x19),
         "DIM_DIAMETER" => Some(0x1A),
         "DIMENSION" => Some(0x15),
+        "ACAD_PROXY_ENTITY" => Some(0x1F2),
         _ => None,
     }
 }
 
 fn is_known_entity_type_name(name: &str) -> bool {
     builtin_code_from_name(name).is_some()
+        || matches!(name, "IMAGE" | "MULTILEADER" | "WIPEOUT" | "ACAD_TABLE")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        arc_row, arc_sweep_row, close_vertex_loop, group_by_owner, group_vertex_2d_by_owner,
+        lwpolyline_to_wcs, polyline_vertex_positions, PolylineVertexRow,
+    };
+    use crate::core::geometry::DEFAULT_POINT_EPSILON;
+    use crate::entities::{
+        ArcEntity, LwPolylineEntity, PolylineCurveType, PolylineFlagsInfo, Vertex2dEntity,
+        Vertex2dFlags,
+    };
+    use crate::objects::{Handle, ObjectRef};
+    use std::collections::HashMap;
+
+    fn vertex(handle: u64, owner: Option<u64>) -> Vertex2dEntity {
+        Vertex2dEntity {
+            handle,
+            flags: 0,
+            flags_info: Vertex2dFlags::from_flags(0),
+            position: (handle as f64, 0.0, 0.0),
+            start_width: 0.0,
+            end_width: 0.0,
+            bulge: 0.0,
+            tangent_dir: 0.0,
+            owner_handle: owner,
+        }
+    }
+
+    #[test]
+    fn groups_scrambled_vertices_by_owner_in_offset_order() {
+        // Two polylines (0x10, 0x20) whose vertices are interleaved in the
+        // object map rather than following their owner.
+        let sorted: Vec<ObjectRef> = [(0x11, 100), (0x21, 200), (0x12, 300), (0x22, 400)]
+            .into_iter()
+            .map(|(handle, offset)| ObjectRef {
+                handle: Handle(handle),
+                offset,
+            })
+            .collect();
+        let vertex_map: HashMap<u64, Vertex2dEntity> = [
+            vertex(0x11, Some(0x10)),
+            vertex(0x12, Some(0x10)),
+            vertex(0x21, Some(0x20)),
+            vertex(0x22, Some(0x20)),
+        ]
+        .into_iter()
+        .map(|v| (v.handle, v))
+        .collect();
+
+        let owners = group_vertex_2d_by_owner(&sorted, &vertex_map);
+
+        assert_eq!(owners.get(&0x10), Some(&vec![0x11, 0x12]));
+        assert_eq!(owners.get(&0x20), Some(&vec![0x21, 0x22]));
+    }
+
+    #[test]
+    fn groups_interleaved_attribs_by_owner_handle() {
+        // ATTRIBs of two INSERTs (0x40, 0x50) written out of order, with the
+        // SEQENDs and an ATTRIB whose owner could not be read in between.
+        let sorted: Vec<ObjectRef> = [0x52, 0x41, 0x5F, 0x51, 0x60, 0x42, 0x4F]
+            .into_iter()
+            .enumerate()
+            .map(|(idx, handle)| ObjectRef {
+                handle: Handle(handle),
+                offset: 100 * (idx as u32 + 1),
+            })
+            .collect();
+        let owner_of: HashMap<u64, u64> = [(0x41, 0x40), (0x42, 0x40), (0x51, 0x50), (0x52, 0x50)]
+            .into_iter()
+            .collect();
+
+        let owners = group_by_owner(&sorted, |handle| owner_of.get(&handle).copied());
+
+        assert_eq!(owners.len(), 2);
+        assert_eq!(owners.get(&0x40), Some(&vec![0x41, 0x42]));
+        assert_eq!(owners.get(&0x50), Some(&vec![0x52, 0x51]));
+    }
+
+    #[test]
+    fn skips_vertices_without_owner() {
+        let sorted = vec![ObjectRef {
+            handle: Handle(0x11),
+            offset: 100,
+        }];
+        let vertex_map: HashMap<u64, Vertex2dEntity> =
+            [(0x11, vertex(0x11, None))].into_iter().collect();
+
+        assert!(group_vertex_2d_by_owner(&sorted, &vertex_map).is_empty());
+    }
+
+    #[test]
+    fn closes_survey_scale_loops_without_duplicate_vertex() {
+        let origin = (1_000_123.456_789, 2_000_987.654_321, 0.0);
+        let corner = (origin.0 + 25.0, origin.1, 0.0);
+        // Last vertex repeats the first with float noise far above 1e-9.
+        let noisy_origin = (origin.0 + 1e-7, origin.1 - 1e-7, 0.0);
+
+        let mut closed = vec![origin, corner, noisy_origin];
+        close_vertex_loop(&mut closed, |point| *point, DEFAULT_POINT_EPSILON);
+        assert_eq!(closed.len(), 3);
+
+        let mut open = vec![origin, corner, (corner.0, corner.1 + 25.0, 0.0)];
+        close_vertex_loop(&mut open, |point| *point, DEFAULT_POINT_EPSILON);
+        assert_eq!(open.len(), 4);
+        assert_eq!(open[3], origin);
+    }
+
+    #[test]
+    fn wcs_rows_mirror_arcs_and_polylines_with_negative_extrusion() {
+        let arc = ArcEntity {
+            handle: 0x40,
+            color_index: None,
+            true_color: None,
+            transparency: None,
+            ltype_scale: 1.0,
+            lineweight: None,
+            layer_handle: 0,
+            center: (2.0, 3.0, 1.0),
+            radius: 1.5,
+            extrusion: (0.0, 0.0, -1.0),
+            angle_start: 0.0,
+            angle_end: std::f64::consts::FRAC_PI_2,
+        };
+        let (_, x, y, z, radius, start, end) = arc_row(&arc, true);
+        assert_eq!((x, y, z, radius), (-2.0, 3.0, -1.0, 1.5));
+        assert!((start - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+        assert!((end - std::f64::consts::PI).abs() < 1e-12);
+        assert_eq!(arc_row(&arc, false).1, 2.0);
+        let (_, _, _, _, _, start, end, sweep, extrusion) = arc_sweep_row(&arc, true);
+        assert!((start - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+        assert!((end - std::f64::consts::PI).abs() < 1e-12);
+        assert!((sweep - std::f64::consts::FRAC_PI_2).abs() < 1e-12);
+        assert_eq!(extrusion, (0.0, 0.0, -1.0));
+
+        let mut polyline = LwPolylineEntity {
+            handle: 0x41,
+            color_index: None,
+            true_color: None,
+            transparency: None,
+            ltype_scale: 1.0,
+            lineweight: None,
+            layer_handle: 0,
+            flags: 0,
+            vertices: vec![(1.0, 0.0), (1.0, 2.0)],
+            const_width: None,
+            bulges: vec![0.5, 0.0],
+            widths: Vec::new(),
+            elevation: 4.0,
+            extrusion: (0.0, 0.0, -1.0),
+        };
+        let world = lwpolyline_to_wcs(&mut polyline);
+        assert_eq!(world, vec![(-1.0, 0.0, -4.0), (-1.0, 2.0, -4.0)]);
+        assert_eq!(polyline.bulges, vec![-0.5, -0.0]);
+
+        // A plane parallel to world YZ: OCS X runs along world Y, OCS Y
+        // along world Z and the elevation along world X.
+        polyline.extrusion = (1.0, 0.0, 0.0);
+        polyline.bulges = vec![0.5, 0.0];
+        let world = lwpolyline_to_wcs(&mut polyline);
+        assert_eq!(world, vec![(4.0, 1.0, 0.0), (4.0, 1.0, 2.0)]);
+        assert_eq!(polyline.bulges, vec![0.5, 0.0]);
+    }
+
+    #[test]
+    fn wcs_polyline_2d_vertices_leave_a_tilted_ocs() {
+        // Extrusion +X: OCS X runs along world Y, OCS Y along world Z and the
+        // elevation along world X.
+        let mut row = PolylineVertexRow {
+            handle: 0x50,
+            flags: 0,
+            flags_info: PolylineFlagsInfo::from_flags(0),
+            curve_type_info: PolylineCurveType::None,
+            elevation: 2.0,
+            extrusion: (1.0, 0.0, 0.0),
+            vertices: [(3.0, 4.0), (5.0, -1.0)]
+                .into_iter()
+                .enumerate()
+                .map(|(idx, (x, y))| Vertex2dEntity {
+                    position: (x, y, 9.0),
+                    ..vertex(0x51 + idx as u64, Some(0x50))
+                })
+                .collect(),
+        };
+        assert_eq!(
+            polyline_vertex_positions(&row, false),
+            vec![(3.0, 4.0, 2.0), (5.0, -1.0, 2.0)]
+        );
+        assert_eq!(
+            polyline_vertex_positions(&row, true),
+            vec![(2.0, 3.0, 4.0), (2.0, 5.0, -1.0)]
+        );
+
+        // 3D polyline vertices are world positions already.
+        row.flags_info = PolylineFlagsInfo::from_flags(0x08);
+        assert_eq!(
+            polyline_vertex_positions(&row, true),
+            vec![(3.0, 4.0, 9.0), (5.0, -1.0, 9.0)]
+        );
+    }
 }
@@ -0,0 +1,968 @@
+//! Command-line inspection of DWG files without Python.
+//!
+//! Built only with the `cli` feature:
+//!
+//! ```bash
+//! cargo run --features cli --bin ezdwg -- entities drawing.dwg --type LINE
+//! ```
+
+use std::collections::HashMap;
+use std::f64::consts::PI;
+use std::fmt::Write as _;
+use std::process::ExitCode;
+
+use _core::core::config::ParseConfig;
+use _core::core::error::DwgError;
+use _core::core::geometry::{bulge_ribbon_2d, ArcTessellation};
+use _core::core::result::Result;
+use _core::dwg::decoder::Decoder;
+use _core::dwg::entity_dispatch::parse_object_header_for_version;
+use _core::entities::{ArcEntity, CircleEntity, LineEntity, LwPolylineEntity};
+use _core::high_level::{self, LayerRecord};
+use _core::objects;
+
+const USAGE: &str = "\
+usage: ezdwg <command> <file> [options]
+
+commands:
+  version <file>                     print the DWG version tag
+  sections <file>                    list container sections
+  objects <file>                     list object map entries with their type
+  entities <file> --type <NAME>      print decoded entities of one type
+  export <file> (--svg|--dxf) <out>  write LINE/ARC/CIRCLE/LWPOLYLINE geometry
+         [--render-widths]           fill wide LWPOLYLINE segments (SVG only)
+         [--data-attributes]         tag SVG elements with data-handle/data-layer
+         [--titles]                  add a <title> tooltip to each SVG element
+
+entity types: LINE POINT ARC CIRCLE ELLIPSE SPLINE TEXT MTEXT LWPOLYLINE INSERT";
+
+const COMMANDS: [&str; 5] = ["version", "sections", "objects", "entities", "export"];
+
+fn main() -> ExitCode {
+    #[cfg(feature = "tracing")]
+    _core::core::diag::init_stderr_logger();
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(CliError::Usage(message)) => {
+            if !message.is_empty() {
+                eprintln!("error: {message}");
+            }
+            eprintln!("{USAGE}");
+            ExitCode::from(2)
+        }
+        Err(CliError::Dwg(err)) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+enum CliError {
+    Usage(String),
+    Dwg(DwgError),
+}
+
+impl From<DwgError> for CliError {
+    fn from(err: DwgError) -> Self {
+        Self::Dwg(err)
+    }
+}
+
+fn usage(message: impl Into<String>) -> CliError {
+    CliError::Usage(message.into())
+}
+
+fn run(args: &[String]) -> std::result::Result<(), CliError> {
+    let Some((command, rest)) = args.split_first() else {
+        return Err(usage(""));
+    };
+    if matches!(command.as_str(), "-h" | "--help" | "help") {
+        println!("{USAGE}");
+        return Ok(());
+    }
+    if !COMMANDS.contains(&command.as_str()) {
+        return Err(usage(format!("unknown command: {command}")));
+    }
+    let Some((path, options)) = rest.split_first() else {
+        return Err(usage(format!("{command}: missing <file>")));
+    };
+    let bytes = std::fs::read(path).map_err(DwgError::from)?;
+    let decoder = Decoder::new(&bytes, ParseConfig::default())?;
+    match command.as_str() {
+        "version" => println!("{}", decoder.version().as_str()),
+        "sections" => print_sections(&decoder)?,
+        "objects" => print_objects(&decoder)?,
+        "entities" => {
+            let type_name = option_value(options, "--type")
+                .ok_or_else(|| usage("entities: missing --type <NAME>"))?;
+            print_entities(&decoder, &type_name.to_ascii_uppercase())?;
+        }
+        "export" => {
+            let flag = |name: &str| options.iter().any(|option| option == name);
+            let svg_options = SvgOptions {
+                render_widths: flag("--render-widths"),
+                data_attributes: flag("--data-attributes"),
+                titles: flag("--titles"),
+            };
+            let svg = option_value(options, "--svg");
+            let with_layers = match svg {
+                Some(_) => svg_options.data_attributes || svg_options.titles,
+                None => true,
+            };
+            let document = collect_drawing(&decoder, with_layers)?;
+            let (out, contents) = if let Some(out) = svg {
+                (out, document.to_svg(svg_options))
+            } else if let Some(out) = option_value(options, "--dxf") {
+                (out, document.to_dxf())
+            } else {
+                return Err(usage("export: expected --svg <out> or --dxf <out>"));
+            };
+            std::fs::write(out, contents).map_err(DwgError::from)?;
+        }
+        _ => unreachable!("checked against COMMANDS"),
+    }
+    Ok(())
+}
+
+fn option_value<'a>(options: &'a [String], name: &str) -> Option<&'a str> {
+    options
+        .iter()
+        .position(|option| option == name)
+        .and_then(|index| options.get(index + 1))
+        .map(String::as_str)
+}
+
+fn print_sections(decoder: &Decoder<'_>) -> Result<()> {
+    println!("name\tsize\tcompressed_size\tpages");
+    for section in decoder.list_sections()? {
+        println!(
+            "{}\t{}\t{}\t{}",
+            section.name, section.size, section.compressed_size, section.page_count
+        );
+    }
+    Ok(())
+}
+
+fn print_objects(decoder: &Decoder<'_>) -> Result<()> {
+    let dynamic_types = decoder.dynamic_type_map().unwrap_or_default();
+    let index = decoder.build_object_index()?;
+    println!("handle\toffset\ttype");
+    for obj in index.objects.iter() {
+        let type_name = decoder
+            .parse_object_record(obj.offset)
+            .and_then(|record| parse_object_header_for_version(&record, decoder.version()))
+            .map(|header| {
+                dynamic_types
+                    .get(&header.type_code)
+                    .cloned()
+                    .unwrap_or_else(|| objects::object_type_name(header.type_code))
+            })
+            .unwrap_or_else(|_| "?".to_string());
+        println!("{:#X}\t{}\t{}", obj.handle.0, obj.offset, type_name);
+    }
+    Ok(())
+}
+
+fn print_entities(decoder: &Decoder<'_>, type_name: &str) -> std::result::Result<(), CliError> {
+    print!("{}", entity_table(decoder, type_name)?);
+    Ok(())
+}
+
+/// Tab-separated rows for `entities`, under a header naming the columns as
+/// `objects` does. Points are written as `x,y,z` and handles in hex.
+fn entity_table(decoder: &Decoder<'_>, type_name: &str) -> std::result::Result<String, CliError> {
+    fn table<T>(header: &str, entities: Vec<T>, row: impl Fn(&T) -> Vec<String>) -> String {
+        let mut out = format!("handle\t{header}\n");
+        for entity in &entities {
+            out.push_str(&row(entity).join("\t"));
+            out.push('\n');
+        }
+        out
+    }
+    fn xyz(point: (f64, f64, f64)) -> String {
+        format!("{},{},{}", point.0, point.1, point.2)
+    }
+    fn hex(handle: u64) -> String {
+        format!("{handle:#X}")
+    }
+    // Keep multi-line and tabbed strings on one row.
+    fn cell(text: &str) -> String {
+        text.replace('\t', "\\t")
+            .replace('\n', "\\n")
+            .replace('\r', "\\r")
+    }
+
+    let out = match type_name {
+        "LINE" => table(
+            "layer\tstart\tend",
+            high_level::decode_line_entities(decoder, None)?,
+            |e| vec![hex(e.handle), hex(e.layer_handle), xyz(e.start), xyz(e.end)],
+        ),
+        "POINT" => table(
+            "layer\tlocation",
+            high_level::decode_point_entities(decoder, None)?,
+            |e| vec![hex(e.handle), hex(e.layer_handle), xyz(e.location)],
+        ),
+        "ARC" => table(
+            "layer\tcenter\tradius\tangle_start\tangle_end",
+            high_level::decode_arc_entities(decoder, None)?,
+            |e| {
+                vec![
+                    hex(e.handle),
+                    hex(e.layer_handle),
+                    xyz(e.center),
+                    e.radius.to_string(),
+                    e.angle_start.to_string(),
+                    e.angle_end.to_string(),
+                ]
+            },
+        ),
+        "CIRCLE" => table(
+            "layer\tcenter\tradius",
+            high_level::decode_circle_entities(decoder, None)?,
+            |e| {
+                vec![
+                    hex(e.handle),
+                    hex(e.layer_handle),
+                    xyz(e.center),
+                    e.radius.to_string(),
+                ]
+            },
+        ),
+        "ELLIPSE" => table(
+            "layer\tcenter\tmajor_axis\taxis_ratio\tstart_angle\tend_angle",
+            high_level::decode_ellipse_entities(decoder, None)?,
+            |e| {
+                vec![
+                    hex(e.handle),
+                    hex(e.layer_handle),
+                    xyz(e.center),
+                    xyz(e.major_axis),
+                    e.axis_ratio.to_string(),
+                    e.start_angle.to_string(),
+                    e.end_angle.to_string(),
+                ]
+            },
+        ),
+        "SPLINE" => table(
+            "layer\tdegree\tclosed\tcontrol_points\tfit_points",
+            high_level::decode_spline_entities(decoder, None)?,
+            |e| {
+                vec![
+                    hex(e.handle),
+                    hex(e.layer_handle),
+                    e.degree.to_string(),
+                    e.closed.to_string(),
+                    e.control_points.len().to_string(),
+                    e.fit_points.len().to_string(),
+                ]
+            },
+        ),
+        "TEXT" => table(
+            "layer\tinsertion\theight\ttext",
+            high_level::decode_text_entities(decoder, None)?,
+            |e| {
+                vec![
+                    hex(e.handle),
+                    hex(e.layer_handle),
+                    xyz(e.insertion),
+                    e.height.to_string(),
+                    cell(&e.text),
+                ]
+            },
+        ),
+        "MTEXT" => table(
+            "layer\tinsertion\ttext_height\ttext",
+            high_level::decode_mtext_entities(decoder, None)?,
+            |e| {
+                vec![
+                    hex(e.handle),
+                    hex(e.layer_handle),
+                    xyz(e.insertion),
+                    e.text_height.to_string(),
+                    cell(&e.text),
+                ]
+            },
+        ),
+        "LWPOLYLINE" => table(
+            "layer\tflags\tvertices",
+            high_level::decode_lwpolyline_entities(decoder, None)?,
+            |e| {
+                vec![
+                    hex(e.handle),
+                    hex(e.layer_handle),
+                    e.flags.to_string(),
+                    e.vertices.len().to_string(),
+                ]
+            },
+        ),
+        // INSERT carries no common entity data, so it has no layer column.
+        "INSERT" => table(
+            "block_header\tposition\tscale\trotation",
+            high_level::decode_insert_entities(decoder, None)?,
+            |e| {
+                vec![
+                    hex(e.handle),
+                    e.block_header_handle.map(hex).unwrap_or_default(),
+                    xyz(e.position),
+                    xyz(e.scale),
+                    e.rotation.to_string(),
+                ]
+            },
+        ),
+        other => return Err(CliError::Dwg(DwgError::unsupported_entity(other))),
+    };
+    Ok(out)
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+struct SvgOptions {
+    /// Draw LWPOLYLINE segments with a width as filled outlines instead of
+    /// hairlines.
+    render_widths: bool,
+    /// Give each element `data-handle` and, where the layer name resolves,
+    /// `data-layer` attributes for viewers to look up on click.
+    data_attributes: bool,
+    /// Give each element a `<title>` naming its type, handle and layer,
+    /// which browsers show as a tooltip.
+    titles: bool,
+}
+
+/// Curve geometry written by `export`, in each entity's own plane (OCS).
+#[derive(Default)]
+struct Drawing {
+    lines: Vec<LineEntity>,
+    arcs: Vec<ArcEntity>,
+    circles: Vec<CircleEntity>,
+    polylines: Vec<LwPolylineEntity>,
+    /// LAYER records, for DXF tables and SVG labels; empty unless requested.
+    layers: Vec<LayerRecord>,
+    /// Names of `layers` by handle, missing where they do not resolve.
+    layer_names: HashMap<u64, String>,
+}
+
+fn collect_drawing(decoder: &Decoder<'_>, with_layers: bool) -> Result<Drawing> {
+    let layers = if with_layers {
+        high_level::layer_records(decoder)?
+    } else {
+        Vec::new()
+    };
+    let layer_names = layers
+        .iter()
+        .filter_map(|layer| Some((layer.handle, layer.name.clone()?)))
+        .collect();
+    Ok(Drawing {
+        lines: high_level::decode_line_entities(decoder, None)?,
+        arcs: high_level::decode_arc_entities(decoder, None)?,
+        circles: high_level::decode_circle_entities(decoder, None)?,
+        polylines: high_level::decode_lwpolyline_entities(decoder, None)?,
+        layers,
+        layer_names,
+    })
+}
+
+/// Identifies an exported entity in SVG labels.
+struct Label<'a> {
+    type_name: &'static str,
+    handle: u64,
+    layer: Option<&'a str>,
+}
+
+impl Label<'_> {
+    fn attributes(&self, options: SvgOptions) -> String {
+        let mut attributes = String::new();
+        if options.data_attributes {
+            let _ = write!(attributes, r#" data-handle="{:#X}""#, self.handle);
+            if let Some(layer) = self.layer {
+                let _ = write!(attributes, r#" data-layer="{}""#, xml_escape(layer));
+            }
+        }
+        attributes
+    }
+
+    fn title(&self) -> String {
+        let mut title = format!("{} {:#X}", self.type_name, self.handle);
+        if let Some(layer) = self.layer {
+            let _ = write!(title, " on layer {layer}");
+        }
+        format!("<title>{}</title>", xml_escape(&title))
+    }
+}
+
+/// Write `<{tag}{body}/>`, with `label`'s attributes and, if requested, a
+/// `<title>` child.
+fn write_element(out: &mut String, tag: &str, body: &str, label: &Label<'_>, options: SvgOptions) {
+    let attributes = label.attributes(options);
+    if options.titles {
+        let _ = writeln!(out, "<{tag}{body}{attributes}>{}</{tag}>", label.title());
+    } else {
+        let _ = writeln!(out, "<{tag}{body}{attributes}/>");
+    }
+}
+
+fn xml_escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+impl Drawing {
+    fn bounds(&self) -> Option<(f64, f64, f64, f64)> {
+        let mut points = Vec::new();
+        for line in &self.lines {
+            points.push((line.start.0, line.start.1));
+            points.push((line.end.0, line.end.1));
+        }
+        let round = self.arcs.iter().map(|arc| (arc.center, arc.radius)).chain(
+            self.circles
+                .iter()
+                .map(|circle| (circle.center, circle.radius)),
+        );
+        for (center, radius) in round {
+            points.push((center.0 - radius, center.1 - radius));
+            points.push((center.0 + radius, center.1 + radius));
+        }
+        for polyline in &self.polylines {
+            points.extend(polyline.vertices.iter().copied());
+        }
+        points
+            .into_iter()
+            .filter(|(x, y)| x.is_finite() && y.is_finite())
+            .fold(None, |bounds, (x, y)| match bounds {
+                None => Some((x, y, x, y)),
+                Some((min_x, min_y, max_x, max_y)) => {
+                    Some((min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y)))
+                }
+            })
+    }
+
+    /// SVG with y pointing up: the content group is mirrored, so SVG's
+    /// positive sweep direction is counter-clockwise like DWG angles.
+    fn to_svg(&self, options: SvgOptions) -> String {
+        let (min_x, min_y, max_x, max_y) = self.bounds().unwrap_or((0.0, 0.0, 1.0, 1.0));
+        let width = (max_x - min_x).max(1e-9);
+        let height = (max_y - min_y).max(1e-9);
+        let stroke = width.max(height) / 1000.0;
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}">"#,
+            min_x, -max_y, width, height
+        );
+        let _ = writeln!(
+            out,
+            r#"<g transform="scale(1,-1)" fill="none" stroke="black" stroke-width="{stroke}">"#
+        );
+        for line in &self.lines {
+            let body = format!(
+                r#" x1="{}" y1="{}" x2="{}" y2="{}""#,
+                line.start.0, line.start.1, line.end.0, line.end.1
+            );
+            let label = self.label("LINE", line.handle, line.layer_handle);
+            write_element(&mut out, "line", &body, &label, options);
+        }
+        for circle in &self.circles {
+            let body = format!(
+                r#" cx="{}" cy="{}" r="{}""#,
+                circle.center.0, circle.center.1, circle.radius
+            );
+            let label = self.label("CIRCLE", circle.handle, circle.layer_handle);
+            write_element(&mut out, "circle", &body, &label, options);
+        }
+        for arc in &self.arcs {
+            let (cx, cy, r) = (arc.center.0, arc.center.1, arc.radius);
+            let body = format!(
+                r#" d="M {} {} A {r} {r} 0 {} 1 {} {}""#,
+                cx + r * arc.angle_start.cos(),
+                cy + r * arc.angle_start.sin(),
+                u8::from(arc.sweep_angle() > PI),
+                cx + r * arc.angle_end.cos(),
+                cy + r * arc.angle_end.sin(),
+            );
+            let label = self.label("ARC", arc.handle, arc.layer_handle);
+            write_element(&mut out, "path", &body, &label, options);
+        }
+        for polyline in &self.polylines {
+            let label = self.label("LWPOLYLINE", polyline.handle, polyline.layer_handle);
+            if options.render_widths && has_width(polyline) {
+                // Wide segments become several paths; label their group.
+                let labeled = options.data_attributes || options.titles;
+                if labeled {
+                    let _ = write!(out, "<g{}>", label.attributes(options));
+                    if options.titles {
+                        out.push_str(&label.title());
+                    }
+                    out.push('\n');
+                }
+                write_wide_polyline(&mut out, polyline);
+                if labeled {
+                    out.push_str("</g>\n");
+                }
+            } else if let Some(path) = polyline_path(polyline) {
+                write_element(
+                    &mut out,
+                    "path",
+                    &format!(r#" d="{path}""#),
+                    &label,
+                    options,
+                );
+            }
+        }
+        out.push_str("</g>\n</svg>\n");
+        out
+    }
+
+    fn label(&self, type_name: &'static str, handle: u64, layer_handle: u64) -> Label<'_> {
+        Label {
+            type_name,
+            handle,
+            layer: self.layer_names.get(&layer_handle).map(String::as_str),
+        }
+    }
+
+    /// Name an entity on `layer_handle` is written with: the LAYER record's
+    /// name, a handle-based stand-in for records whose name does not
+    /// resolve, and the default layer "0" for unknown handles.
+    fn dxf_layer_name(&self, layer_handle: u64) -> String {
+        if let Some(name) = self.layer_names.get(&layer_handle) {
+            return name.clone();
+        }
+        if self.layers.iter().any(|layer| layer.handle == layer_handle) {
+            return format!("LAYER_{layer_handle:X}");
+        }
+        "0".to_string()
+    }
+
+    /// Minimal AutoCAD R12 ASCII DXF: LTYPE and LAYER tables, then the
+    /// entities on their layers with any explicit color, and the extrusion
+    /// written out so OCS geometry keeps its plane.
+    fn to_dxf(&self) -> String {
+        let mut out = String::new();
+        let mut group = |code: i32, value: &dyn std::fmt::Display| {
+            let _ = writeln!(out, "{code:>3}\n{value}");
+        };
+        group(0, &"SECTION");
+        group(2, &"TABLES");
+        group(0, &"TABLE");
+        group(2, &"LTYPE");
+        group(70, &1);
+        group(0, &"LTYPE");
+        group(2, &"CONTINUOUS");
+        group(70, &0);
+        group(3, &"Solid line");
+        group(72, &65);
+        group(73, &0);
+        group(40, &0.0);
+        group(0, &"ENDTAB");
+        let mut layers: Vec<(String, i16, u8)> = self
+            .layers
+            .iter()
+            .map(|layer| {
+                let color = aci(Some(layer.color_index), layer.true_color)
+                    .filter(|color| (1..=255).contains(color))
+                    .unwrap_or(7) as i16;
+                let (color, flags) = match layer.state {
+                    Some(state) => (
+                        if state.on { color } else { -color },
+                        u8::from(state.frozen) | (u8::from(state.locked) << 2),
+                    ),
+                    None => (color, 0),
+                };
+                (self.dxf_layer_name(layer.handle), color, flags)
+            })
+            .collect();
+        // Entities on unknown layers fall back to "0", which must exist.
+        if !layers.iter().any(|(name, _, _)| name == "0") {
+            layers.insert(0, ("0".to_string(), 7, 0));
+        }
+        group(0, &"TABLE");
+        group(2, &"LAYER");
+        group(70, &layers.len());
+        for (name, color, flags) in &layers {
+            group(0, &"LAYER");
+            group(2, name);
+            group(70, flags);
+            group(62, color);
+            group(6, &"CONTINUOUS");
+        }
+        group(0, &"ENDTAB");
+        group(0, &"ENDSEC");
+
+        group(0, &"SECTION");
+        group(2, &"ENTITIES");
+        let properties = |group: &mut dyn FnMut(i32, &dyn std::fmt::Display),
+                          layer_handle: u64,
+                          color_index: Option<u16>,
+                          true_color: Option<u32>| {
+            group(8, &self.dxf_layer_name(layer_handle));
+            // 256 is BYLAYER, the default.
+            if let Some(color) = aci(color_index, true_color).filter(|color| *color <= 255) {
+                group(62, &color);
+            }
+        };
+        for line in &self.lines {
+            group(0, &"LINE");
+            properties(
+                &mut group,
+                line.layer_handle,
+                line.color_index,
+                line.true_color,
+            );
+            write_point(&mut group, 10, line.start);
+            write_point(&mut group, 11, line.end);
+        }
+        for circle in &self.circles {
+            group(0, &"CIRCLE");
+            properties(
+                &mut group,
+                circle.layer_handle,
+                circle.color_index,
+                circle.true_color,
+            );
+            write_point(&mut group, 10, circle.center);
+            group(40, &circle.radius);
+            write_point(&mut group, 210, circle.extrusion);
+        }
+        for arc in &self.arcs {
+            group(0, &"ARC");
+            properties(
+                &mut group,
+                arc.layer_handle,
+                arc.color_index,
+                arc.true_color,
+            );
+            write_point(&mut group, 10, arc.center);
+            group(40, &arc.radius);
+            group(50, &arc.angle_start.to_degrees());
+            group(51, &arc.angle_end.to_degrees());
+            write_point(&mut group, 210, arc.extrusion);
+        }
+        for polyline in &self.polylines {
+            let layer = self.dxf_layer_name(polyline.layer_handle);
+            group(0, &"POLYLINE");
+            properties(
+                &mut group,
+                polyline.layer_handle,
+                polyline.color_index,
+                polyline.true_color,
+            );
+            group(66, &1);
+            write_point(&mut group, 10, (0.0, 0.0, polyline.elevation));
+            group(70, &u8::from(polyline.flags & 0x200 != 0));
+            write_point(&mut group, 210, polyline.extrusion);
+            for (index, vertex) in polyline.vertices.iter().enumerate() {
+                group(0, &"VERTEX");
+                group(8, &layer);
+                write_point(&mut group, 10, (vertex.0, vertex.1, polyline.elevation));
+                if let Some(bulge) = polyline.bulges.get(index).filter(|bulge| **bulge != 0.0) {
+                    group(42, bulge);
+                }
+            }
+            group(0, &"SEQEND");
+            group(8, &layer);
+        }
+        group(0, &"ENDSEC");
+        group(0, &"EOF");
+        out
+    }
+}
+
+/// ACI color index from a decoded color. R2004+ records often store an
+/// indexed color as index 0 (or BYLAYER/BYBLOCK) with the index in the
+/// true-color value.
+fn aci(color_index: Option<u16>, true_color: Option<u32>) -> Option<u16> {
+    match (color_index, true_color) {
+        (None | Some(0 | 256 | 257), Some(index @ 1..=257)) => Some(index as u16),
+        (index, _) => index,
+    }
+}
+
+fn write_point(
+    group: &mut impl FnMut(i32, &dyn std::fmt::Display),
+    code: i32,
+    point: (f64, f64, f64),
+) {
+    group(code, &point.0);
+    group(code + 10, &point.1);
+    group(code + 20, &point.2);
+}
+
+/// SVG path for an LWPOLYLINE; bulge `b` is tan(θ/4) of the segment's arc,
+/// positive for counter-clockwise.
+fn polyline_path(polyline: &LwPolylineEntity) -> Option<String> {
+    let first = polyline.vertices.first()?;
+    let closed = polyline.flags & 0x200 != 0;
+    let mut path = format!("M {} {}", first.0, first.1);
+    for (start, end, bulge) in polyline_segments(polyline) {
+        path.push_str(&segment_command(start, end, bulge));
+    }
+    if closed {
+        path.push_str(" Z");
+    }
+    Some(path)
+}
+
+/// `(start, end, bulge)` of each drawn LWPOLYLINE segment, including the
+/// closing one.
+fn polyline_segments(
+    polyline: &LwPolylineEntity,
+) -> impl Iterator<Item = ((f64, f64), (f64, f64), f64)> + '_ {
+    let vertices = &polyline.vertices;
+    let segment_count = if polyline.flags & 0x200 != 0 {
+        vertices.len()
+    } else {
+        vertices.len().saturating_sub(1)
+    };
+    (0..segment_count).map(move |index| {
+        (
+            vertices[index],
+            vertices[(index + 1) % vertices.len()],
+            polyline.bulges.get(index).copied().unwrap_or(0.0),
+        )
+    })
+}
+
+/// Path command continuing from `start` to `end` along the segment.
+fn segment_command(start: (f64, f64), end: (f64, f64), bulge: f64) -> String {
+    if bulge == 0.0 {
+        return format!(" L {} {}", end.0, end.1);
+    }
+    let chord = (end.0 - start.0).hypot(end.1 - start.1);
+    let radius = chord * (1.0 + bulge * bulge) / (4.0 * bulge.abs());
+    format!(
+        " A {radius} {radius} 0 {} {} {} {}",
+        u8::from(bulge.abs() > 1.0),
+        u8::from(bulge > 0.0),
+        end.0,
+        end.1
+    )
+}
+
+/// `(start, end)` width of segment `index`: its own widths if stored,
+/// otherwise the constant width.
+fn segment_widths(polyline: &LwPolylineEntity, index: usize) -> (f64, f64) {
+    polyline.widths.get(index).copied().unwrap_or_else(|| {
+        let width = polyline.const_width.unwrap_or(0.0);
+        (width, width)
+    })
+}
+
+fn has_width(polyline: &LwPolylineEntity) -> bool {
+    polyline.const_width.is_some_and(|width| width > 0.0)
+        || polyline
+            .widths
+            .iter()
+            .any(|(start, end)| *start > 0.0 || *end > 0.0)
+}
+
+/// One filled outline per wide segment; zero-width segments stay hairlines.
+fn write_wide_polyline(out: &mut String, polyline: &LwPolylineEntity) {
+    for (index, (start, end, bulge)) in polyline_segments(polyline).enumerate() {
+        let (start_width, end_width) = segment_widths(polyline, index);
+        if start_width <= 0.0 && end_width <= 0.0 {
+            let command = segment_command(start, end, bulge);
+            let _ = writeln!(out, r#"<path d="M {} {}{command}"/>"#, start.0, start.1);
+            continue;
+        }
+        let outline = bulge_ribbon_2d(
+            start,
+            end,
+            bulge,
+            start_width,
+            end_width,
+            ArcTessellation::default(),
+        );
+        let Some((first, rest)) = outline.split_first() else {
+            continue;
+        };
+        let mut path = format!("M {} {}", first.0, first.1);
+        for point in rest {
+            let _ = write!(path, " L {} {}", point.0, point.1);
+        }
+        let _ = writeln!(out, r#"<path d="{path} Z" fill="black" stroke="none"/>"#);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use _core::objects::LayerState;
+
+    fn polyline(vertices: Vec<(f64, f64)>, bulges: Vec<f64>, flags: u16) -> LwPolylineEntity {
+        LwPolylineEntity {
+            handle: 1,
+            color_index: None,
+            true_color: None,
+            transparency: None,
+            ltype_scale: 1.0,
+            lineweight: None,
+            layer_handle: 0,
+            flags,
+            vertices,
+            const_width: None,
+            bulges,
+            widths: Vec::new(),
+            elevation: 0.0,
+            extrusion: (0.0, 0.0, 1.0),
+        }
+    }
+
+    #[test]
+    fn entities_rejects_types_it_cannot_list() {
+        let bytes = std::fs::read("test_dwg/line_2000.dwg").expect("sample file");
+        let decoder = Decoder::new(&bytes, ParseConfig::default()).expect("decoder");
+        match print_entities(&decoder, "WIPEOUT") {
+            Err(CliError::Dwg(err)) => {
+                assert_eq!(err.kind, _core::core::error::ErrorKind::UnsupportedEntity);
+            }
+            _ => panic!("expected an unsupported entity error"),
+        }
+    }
+
+    #[test]
+    fn entities_prints_tab_separated_columns() {
+        let bytes = std::fs::read("test_dwg/line_2000.dwg").expect("sample file");
+        let decoder = Decoder::new(&bytes, ParseConfig::default()).expect("decoder");
+        let Ok(table) = entity_table(&decoder, "LINE") else {
+            panic!("expected a LINE table");
+        };
+        let mut rows = table.lines();
+        assert_eq!(rows.next(), Some("handle\tlayer\tstart\tend"));
+        let lines = high_level::decode_line_entities(&decoder, None).expect("lines");
+        assert!(!lines.is_empty());
+        for (row, line) in rows.zip(&lines) {
+            let cells: Vec<&str> = row.split('\t').collect();
+            assert_eq!(cells.len(), 4);
+            assert_eq!(cells[0], format!("{:#X}", line.handle));
+            let start = format!("{},{},{}", line.start.0, line.start.1, line.start.2);
+            assert_eq!(cells[2], start);
+        }
+        assert_eq!(table.lines().count(), lines.len() + 1);
+    }
+
+    #[test]
+    fn semicircle_bulge_becomes_svg_arc() {
+        let path = polyline_path(&polyline(vec![(0.0, 0.0), (2.0, 0.0)], vec![1.0, 0.0], 0));
+        assert_eq!(path.as_deref(), Some("M 0 0 A 1 1 0 0 1 2 0"));
+    }
+
+    #[test]
+    fn render_widths_fills_wide_segments() {
+        let mut wide = polyline(vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)], Vec::new(), 0);
+        wide.widths = vec![(2.0, 0.0), (0.0, 0.0)];
+        let drawing = Drawing {
+            polylines: vec![wide],
+            ..Drawing::default()
+        };
+
+        let plain = drawing.to_svg(SvgOptions::default());
+        assert!(!plain.contains("fill=\"black\""));
+        let svg = drawing.to_svg(SvgOptions {
+            render_widths: true,
+            ..SvgOptions::default()
+        });
+        assert!(
+            svg.contains(r#"<path d="M 0 1 L 10 0 L 10 0 L 0 -1 Z" fill="black" stroke="none"/>"#)
+        );
+        assert!(svg.contains(r#"<path d="M 10 0 L 10 10"/>"#));
+    }
+
+    #[test]
+    fn labels_carry_handle_and_layer() {
+        let mut labeled = polyline(vec![(0.0, 0.0), (1.0, 0.0)], Vec::new(), 0);
+        labeled.handle = 0x2A;
+        labeled.layer_handle = 0x10;
+        let mut drawing = Drawing {
+            polylines: vec![labeled],
+            ..Drawing::default()
+        };
+        drawing
+            .layer_names
+            .insert(0x10, "Walls & <doors>".to_string());
+
+        let plain = drawing.to_svg(SvgOptions::default());
+        assert!(plain.contains(r#"<path d="M 0 0 L 1 0"/>"#));
+        let svg = drawing.to_svg(SvgOptions {
+            data_attributes: true,
+            titles: true,
+            ..SvgOptions::default()
+        });
+        assert!(svg.contains(
+            r#"<path d="M 0 0 L 1 0" data-handle="0x2A" data-layer="Walls &amp; &lt;doors&gt;"><title>LWPOLYLINE 0x2A on layer Walls &amp; &lt;doors&gt;</title></path>"#
+        ));
+
+        // Unresolved layers are left out rather than guessed.
+        drawing.layer_names.clear();
+        let svg = drawing.to_svg(SvgOptions {
+            data_attributes: true,
+            ..SvgOptions::default()
+        });
+        assert!(svg.contains(r#"<path d="M 0 0 L 1 0" data-handle="0x2A"/>"#));
+    }
+
+    #[test]
+    fn closed_polyline_dxf_lists_every_vertex() {
+        let drawing = Drawing {
+            polylines: vec![polyline(
+                vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0)],
+                Vec::new(),
+                0x200,
+            )],
+            ..Drawing::default()
+        };
+        let dxf = drawing.to_dxf();
+        assert_eq!(dxf.matches("\nVERTEX\n").count(), 3);
+        assert!(dxf.contains(" 70\n1\n"));
+        assert!(dxf.ends_with("  0\nEOF\n"));
+    }
+
+    #[test]
+    fn dxf_tables_carry_layers_and_colors() {
+        let mut walls = polyline(vec![(0.0, 0.0), (1.0, 0.0)], Vec::new(), 0);
+        walls.layer_handle = 0x10;
+        let mut red = polyline(vec![(0.0, 0.0), (0.0, 1.0)], Vec::new(), 0);
+        red.layer_handle = 0x11;
+        red.color_index = Some(0);
+        red.true_color = Some(1);
+        let layer = |handle, name: Option<&str>, true_color, on| LayerRecord {
+            handle,
+            name: name.map(str::to_string),
+            color_index: 0,
+            true_color,
+            state: Some(LayerState {
+                on,
+                frozen: false,
+                locked: false,
+                plottable: true,
+            }),
+        };
+        let layers = vec![
+            layer(0x10, Some("WALLS"), Some(5), true),
+            layer(0x11, None, Some(3), false),
+        ];
+        let drawing = Drawing {
+            polylines: vec![walls, red],
+            layer_names: HashMap::from([(0x10, "WALLS".to_string())]),
+            layers,
+            ..Drawing::default()
+        };
+        let dxf = drawing.to_dxf();
+        assert!(dxf.contains("  2\nLAYER\n 70\n3\n"));
+        assert!(dxf.contains("  0\nLAYER\n  2\n0\n 70\n0\n 62\n7\n"));
+        assert!(dxf.contains("  0\nLAYER\n  2\nWALLS\n 70\n0\n 62\n5\n"));
+        // Switched-off layers have a negative color; unnamed ones a stand-in.
+        assert!(dxf.contains("  0\nLAYER\n  2\nLAYER_11\n 70\n0\n 62\n-3\n"));
+        assert!(dxf.contains("  0\nPOLYLINE\n  8\nWALLS\n 66\n"));
+        assert!(dxf.contains("  0\nPOLYLINE\n  8\nLAYER_11\n 62\n1\n 66\n"));
+        assert!(dxf.contains("  0\nSEQEND\n  8\nWALLS\n"));
+    }
+}
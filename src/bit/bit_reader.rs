@@ -19,6 +19,7 @@ pub struct BitReader<'a> {
     data: &'a [u8],
     byte_pos: usize,
     bit_pos: u8,
+    strict: bool,
 }
 
 impl<'a> BitReader<'a> {
@@ -27,13 +28,32 @@ impl<'a> BitReader<'a> {
             data,
             byte_pos: 0,
             bit_pos: 0,
+            strict: false,
         }
     }
 
+    /// Whether decoders should reject layouts they would otherwise recover
+    /// from, as [`ParseConfig::strict`](crate::core::config::ParseConfig::strict) asks.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
     pub fn tell_bits(&self) -> u64 {
         (self.byte_pos as u64) * 8 + self.bit_pos as u64
     }
 
+    pub fn remaining_bits(&self) -> u64 {
+        ((self.data.len() as u64) * 8).saturating_sub(self.tell_bits())
+    }
+
+    pub fn has_bits(&self, n: u64) -> bool {
+        self.remaining_bits() >= n
+    }
+
     pub fn get_pos(&self) -> (usize, u8) {
         (self.byte_pos, self.bit_pos)
     }
@@ -207,6 +227,13 @@ impl<'a> BitReader<'a> {
         Ok(value)
     }
 
+    pub fn read_2dd(&mut self, default_value: (f64, f64)) -> Result<(f64, f64)> {
+        Ok((
+            self.read_dd(default_value.0)?,
+            self.read_dd(default_value.1)?,
+        ))
+    }
+
     pub fn read_bt(&mut self) -> Result<f64> {
         let what_it_is = self.read_b()?;
         if what_it_is == 1 {
@@ -249,15 +276,40 @@ impl<'a> BitReader<'a> {
         Ok(value)
     }
 
+    /// [`read_bs`](Self::read_bs) for fields the spec defines as signed
+    /// shorts, such as the LAYER color index (negative when the layer is
+    /// off). The selector bits are the same; only the full 16-bit form can
+    /// be negative, the one-byte form still covers 0..=255.
+    pub fn read_bss(&mut self) -> Result<i16> {
+        Ok(self.read_bs()? as i16)
+    }
+
+    /// [`read_bl`](Self::read_bl) for signed bit longs; as with
+    /// [`read_bss`](Self::read_bss), only the full 32-bit form can be
+    /// negative.
+    pub fn read_bls(&mut self) -> Result<i32> {
+        Ok(self.read_bl()? as i32)
+    }
+
+    /// Bit long long (BLL): a 3-bit byte count, then that many bytes,
+    /// least significant first.
     pub fn read_bll(&mut self) -> Result<u64> {
-        let length = self.read_3b()? as usize;
+        let length = self.read_3b()?;
         let mut value = 0u64;
-        for _ in 0..length {
-            value = (value << 8) | self.read_rc()? as u64;
+        for byte in 0..length {
+            value |= u64::from(self.read_rc()?) << (8 * byte);
         }
         Ok(value)
     }
 
+    /// Modular short (MS): little-endian 15-bit words, each with a 0x8000
+    /// continuation flag, at most two words. Object records start with one
+    /// giving their size.
+    ///
+    /// `read_ms`, [`read_mc`](Self::read_mc) and [`read_umc`](Self::read_umc)
+    /// are the modular readers for bit streams. The object-map parsers read
+    /// whole bytes through [`ByteReader`](crate::io::ByteReader)'s copies of
+    /// the same layouts; both need to stay in step.
     pub fn read_ms(&mut self) -> Result<u32> {
         let mut value: u32 = 0;
         let mut shift = 0;
@@ -276,6 +328,11 @@ impl<'a> BitReader<'a> {
         Ok(value)
     }
 
+    /// Modular char (MC): little-endian 7-bit groups with a 0x80
+    /// continuation flag; bit 0x40 of the final byte is the sign, so that
+    /// byte holds 6 value bits. At most four bytes are read: a fourth byte
+    /// that still has the continuation flag ends the value, unsigned, and
+    /// leaves the next byte unread.
     pub fn read_mc(&mut self) -> Result<i64> {
         let mut value: i64 = 0;
         let mut shift = 0;
@@ -301,6 +358,9 @@ impl<'a> BitReader<'a> {
         Ok(value)
     }
 
+    /// Unsigned modular char: the MC layout with all 7 bits of the final
+    /// byte as value, as used for the R2010+ handle stream size. Reads up to
+    /// five bytes.
     pub fn read_umc(&mut self) -> Result<u32> {
         let mut value: u32 = 0;
         let mut shift = 0u32;
@@ -370,6 +430,16 @@ impl<'a> BitReader<'a> {
         Ok(String::from_utf8_lossy(&text).to_string())
     }
 
+    /// R2007+ text: BS length in UTF-16 code units, then the units.
+    pub fn read_tu(&mut self) -> Result<String> {
+        let length = self.read_bs()? as usize;
+        let mut units = Vec::with_capacity(length.min((self.remaining_bits() / 16) as usize));
+        for _ in 0..length {
+            units.push(self.read_rs(Endian::Little)?);
+        }
+        Ok(String::from_utf16_lossy(&units))
+    }
+
     pub fn read_crc(&mut self) -> Result<u16> {
         if self.bit_pos > 0 {
             self.set_pos(self.byte_pos + 1, 0);
@@ -383,3 +453,160 @@ impl<'a> BitReader<'a> {
         self.bit_pos = (pos_end % 8) as u8;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEFAULT: f64 = 1234.5678;
+    const VALUE: f64 = -98.765;
+
+    /// Pack each `(selector, raw bytes)` DD field back to back, MSB first,
+    /// as DWG bit streams are laid out.
+    fn dd_stream(fields: &[(u8, &[u8])]) -> Vec<u8> {
+        let mut bits: Vec<u8> = Vec::new();
+        for (selector, bytes) in fields {
+            bits.extend([(selector >> 1) & 1, selector & 1]);
+            for byte in *bytes {
+                bits.extend((0..8).rev().map(|shift| (byte >> shift) & 1));
+            }
+        }
+        bits.chunks(8)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .enumerate()
+                    .fold(0u8, |acc, (idx, bit)| acc | (bit << (7 - idx)))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn modular_chars_cover_four_bytes_and_the_sign_bit() {
+        let data = [
+            0xFF, 0xFF, 0xFF, 0x3F, // largest four-byte value
+            0xFF, 0xFF, 0xFF, 0x7F, // and its negation
+            0x41, // -1
+            0x81, 0x80, 0x80, 0x80, 0x05, // continuation on the fourth byte
+        ];
+        let mut reader = BitReader::new(&data);
+        assert_eq!(reader.read_mc().unwrap(), (1 << 27) - 1);
+        assert_eq!(reader.read_mc().unwrap(), -((1 << 27) - 1));
+        assert_eq!(reader.read_mc().unwrap(), -1);
+        assert_eq!(reader.read_mc().unwrap(), 1);
+        assert_eq!(reader.read_rc().unwrap(), 0x05);
+        assert!(reader.read_mc().is_err());
+
+        let mut reader = BitReader::new(&[0xFF, 0xFF, 0xFF, 0x7F]);
+        assert_eq!(reader.read_umc().unwrap(), (1 << 28) - 1);
+        let mut reader = BitReader::new(&[0xFF, 0xFF, 0x02, 0x00]);
+        assert_eq!(reader.read_ms().unwrap(), 0x7FFF | (2 << 15));
+    }
+
+    #[test]
+    fn remaining_bits_count_down_to_zero() {
+        let mut reader = BitReader::new(&[0xA5, 0x5A]);
+        assert_eq!(reader.remaining_bits(), 16);
+        assert!(reader.has_bits(16));
+        assert!(!reader.has_bits(17));
+
+        reader.read_b().unwrap();
+        reader.read_rc().unwrap();
+        assert_eq!(reader.remaining_bits(), 7);
+        assert!(reader.has_bits(7));
+        assert!(!reader.has_bits(8));
+
+        // Positions past the end report nothing left instead of wrapping.
+        reader.set_bit_pos(40);
+        assert_eq!(reader.remaining_bits(), 0);
+        assert!(reader.has_bits(0));
+        assert!(!reader.has_bits(1));
+    }
+
+    #[test]
+    fn bit_long_longs_are_little_endian() {
+        // 3-bit count 2, then 0x50 0x03: a MULTILEADER proxy graphics size.
+        let data = [0b0100_1010, 0b0000_0000, 0b0110_0000];
+        let mut reader = BitReader::new(&data);
+        assert_eq!(reader.read_bll().unwrap(), 0x0350);
+        assert_eq!(reader.tell_bits(), 19);
+        let mut reader = BitReader::new(&[0x00]);
+        assert_eq!(reader.read_bll().unwrap(), 0);
+    }
+
+    #[test]
+    fn signed_bit_shorts_and_longs_share_the_selectors() {
+        let short = (-5i16).to_le_bytes();
+        let long = (-70_000i32).to_le_bytes();
+        let data = dd_stream(&[
+            (0, &short),
+            (1, &[0xFF]),
+            (2, &[]),
+            (3, &[]),
+            (0, &long),
+            (1, &[0x80]),
+        ]);
+        let mut reader = BitReader::new(&data);
+        assert_eq!(reader.read_bss().unwrap(), -5);
+        assert_eq!(reader.read_bss().unwrap(), 255);
+        assert_eq!(reader.read_bss().unwrap(), 0);
+        assert_eq!(reader.read_bss().unwrap(), 256);
+        assert_eq!(reader.read_bls().unwrap(), -70_000);
+        assert_eq!(reader.read_bls().unwrap(), 128);
+    }
+
+    #[test]
+    fn read_dd_selector_0_keeps_default() {
+        let data = dd_stream(&[(0, &[])]);
+        let mut reader = BitReader::new(&data);
+        assert_eq!(reader.read_dd(DEFAULT).unwrap(), DEFAULT);
+        assert_eq!(reader.tell_bits(), 2);
+    }
+
+    #[test]
+    fn read_dd_selector_1_replaces_low_four_bytes() {
+        let value = VALUE.to_le_bytes();
+        let data = dd_stream(&[(1, &value[0..4])]);
+        let mut reader = BitReader::new(&data);
+
+        let mut expected = DEFAULT.to_le_bytes();
+        expected[0..4].copy_from_slice(&value[0..4]);
+        assert_eq!(
+            reader.read_dd(DEFAULT).unwrap().to_bits(),
+            f64::from_le_bytes(expected).to_bits()
+        );
+        assert_eq!(reader.tell_bits(), 2 + 32);
+    }
+
+    #[test]
+    fn read_dd_selector_2_replaces_bytes_4_5_then_0_to_3() {
+        let value = VALUE.to_le_bytes();
+        let payload = [value[4], value[5], value[0], value[1], value[2], value[3]];
+        let data = dd_stream(&[(2, &payload)]);
+        let mut reader = BitReader::new(&data);
+
+        let mut expected = DEFAULT.to_le_bytes();
+        expected[0..6].copy_from_slice(&value[0..6]);
+        assert_eq!(
+            reader.read_dd(DEFAULT).unwrap().to_bits(),
+            f64::from_le_bytes(expected).to_bits()
+        );
+        assert_eq!(reader.tell_bits(), 2 + 48);
+    }
+
+    #[test]
+    fn read_dd_selector_3_reads_full_double() {
+        let data = dd_stream(&[(3, &VALUE.to_le_bytes())]);
+        let mut reader = BitReader::new(&data);
+        assert_eq!(reader.read_dd(DEFAULT).unwrap(), VALUE);
+        assert_eq!(reader.tell_bits(), 2 + 64);
+    }
+
+    #[test]
+    fn read_2dd_applies_each_default_in_order() {
+        let data = dd_stream(&[(0, &[]), (3, &VALUE.to_le_bytes())]);
+        let mut reader = BitReader::new(&data);
+        assert_eq!(reader.read_2dd((DEFAULT, 0.5)).unwrap(), (DEFAULT, VALUE));
+        assert_eq!(reader.tell_bits(), 2 + 2 + 64);
+    }
+}
@@ -15,6 +15,7 @@ pub enum SectionKind {
     ObjectMap,
     Unknown3,
     Measurement,
+    AuxHeader,
     Unknown(u8),
 }
 
@@ -26,6 +27,7 @@ impl SectionKind {
             2 => Self::ObjectMap,
             3 => Self::Unknown3,
             4 => Self::Measurement,
+            5 => Self::AuxHeader,
             other => Self::Unknown(other),
         }
     }
@@ -37,9 +39,112 @@ impl SectionKind {
             Self::ObjectMap => "ObjectMap".to_string(),
             Self::Unknown3 => "Unknown3".to_string(),
             Self::Measurement => "Measurement".to_string(),
+            Self::AuxHeader => "AuxHeader".to_string(),
             Self::Unknown(value) => format!("Unknown({value})"),
         }
     }
+
+    /// Name of the equivalent R2004+ named section, if there is one.
+    pub fn acdb_name(&self) -> Option<&'static str> {
+        match self {
+            Self::HeaderVariables => Some(NamedSection::Header.as_str()),
+            Self::Classes => Some(NamedSection::Classes.as_str()),
+            Self::ObjectMap => Some(NamedSection::Handles.as_str()),
+            Self::Measurement => Some(NamedSection::Template.as_str()),
+            Self::AuxHeader => Some(NamedSection::AuxHeader.as_str()),
+            _ => None,
+        }
+    }
+}
+
+/// Data sections of the R2004+ section map, by their stored name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NamedSection {
+    Header,
+    AuxHeader,
+    Classes,
+    Handles,
+    Template,
+    ObjFreeSpace,
+    AcDbObjects,
+    RevHistory,
+    SummaryInfo,
+    Preview,
+    AppInfo,
+    AppInfoHistory,
+    FileDepList,
+    Security,
+    VbaProject,
+    Signature,
+    AcDsPrototype,
+}
+
+impl NamedSection {
+    pub const ALL: [Self; 17] = [
+        Self::Header,
+        Self::AuxHeader,
+        Self::Classes,
+        Self::Handles,
+        Self::Template,
+        Self::ObjFreeSpace,
+        Self::AcDbObjects,
+        Self::RevHistory,
+        Self::SummaryInfo,
+        Self::Preview,
+        Self::AppInfo,
+        Self::AppInfoHistory,
+        Self::FileDepList,
+        Self::Security,
+        Self::VbaProject,
+        Self::Signature,
+        Self::AcDsPrototype,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Header => "AcDb:Header",
+            Self::AuxHeader => "AcDb:AuxHeader",
+            Self::Classes => "AcDb:Classes",
+            Self::Handles => "AcDb:Handles",
+            Self::Template => "AcDb:Template",
+            Self::ObjFreeSpace => "AcDb:ObjFreeSpace",
+            Self::AcDbObjects => "AcDb:AcDbObjects",
+            Self::RevHistory => "AcDb:RevHistory",
+            Self::SummaryInfo => "AcDb:SummaryInfo",
+            Self::Preview => "AcDb:Preview",
+            Self::AppInfo => "AcDb:AppInfo",
+            Self::AppInfoHistory => "AcDb:AppInfoHistory",
+            Self::FileDepList => "AcDb:FileDepList",
+            Self::Security => "AcDb:Security",
+            Self::VbaProject => "AcDb:VBAProject",
+            Self::Signature => "AcDb:Signature",
+            Self::AcDsPrototype => "AcDb:AcDsPrototype_1b",
+        }
+    }
+
+    /// Inverse of [`as_str`](Self::as_str). R2007 files may spell the
+    /// header section "AcDb:Headers".
+    pub fn from_name(name: &str) -> Option<Self> {
+        if name == "AcDb:Headers" {
+            return Some(Self::Header);
+        }
+        Self::ALL
+            .into_iter()
+            .find(|section| section.as_str() == name)
+    }
+
+    /// Locator record number the section corresponds to in R13-R15 files;
+    /// 255 for sections that have none.
+    pub fn record_no(&self) -> u8 {
+        match self {
+            Self::Header => 0,
+            Self::Classes => 1,
+            Self::Handles => 2,
+            Self::Template => 4,
+            Self::AuxHeader => 5,
+            _ => 255,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -56,6 +161,25 @@ impl SectionLocatorRecord {
     }
 }
 
+/// Container-level metadata of one section, as stored in the R2004+ section map.
+#[derive(Debug, Clone)]
+pub struct SectionInfo {
+    pub name: String,
+    pub size: u64,
+    pub compressed_size: u64,
+    pub page_count: u32,
+    pub encrypted: u32,
+    /// How the section is stored, in R2004 section-map terms:
+    /// [`SECTION_UNCOMPRESSED`] or [`SECTION_COMPRESSED`]. The R2007 section
+    /// map keeps this per page; a section counts as compressed if any page is.
+    pub compression: u32,
+}
+
+/// [`SectionInfo::compression`] of a section stored as is.
+pub const SECTION_UNCOMPRESSED: u32 = 1;
+/// [`SectionInfo::compression`] of a section stored compressed.
+pub const SECTION_COMPRESSED: u32 = 2;
+
 #[derive(Debug, Clone)]
 pub struct SectionDirectory {
     pub record_count: u32,
@@ -64,6 +188,20 @@ pub struct SectionDirectory {
     pub sentinel_ok: bool,
 }
 
+impl SectionDirectory {
+    /// Index of the record called `name`. Unnamed (R13-R15) records also
+    /// answer to their kind label ("ObjectMap") and AcDb name ("AcDb:Handles").
+    pub fn find_by_name(&self, name: &str) -> Option<usize> {
+        self.records.iter().position(|record| match &record.name {
+            Some(record_name) => record_name == name,
+            None => {
+                let kind = record.kind();
+                kind.label() == name || kind.acdb_name() == Some(name)
+            }
+        })
+    }
+}
+
 pub fn parse(bytes: &[u8]) -> Result<SectionDirectory> {
     parse_with_config(bytes, &ParseConfig::default())
 }
@@ -119,3 +257,23 @@ pub fn parse_with_config(bytes: &[u8], config: &ParseConfig) -> Result<SectionDi
         sentinel_ok,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::NamedSection;
+
+    #[test]
+    fn named_sections_round_trip_through_their_names() {
+        for section in NamedSection::ALL {
+            assert_eq!(NamedSection::from_name(section.as_str()), Some(section));
+        }
+        assert_eq!(
+            NamedSection::from_name("AcDb:Headers"),
+            Some(NamedSection::Header)
+        );
+        assert_eq!(NamedSection::from_name("AcDb:Unknown"), None);
+        assert_eq!(NamedSection::Handles.record_no(), 2);
+        assert_eq!(NamedSection::AuxHeader.record_no(), 5);
+        assert_eq!(NamedSection::AcDbObjects.record_no(), 255);
+    }
+}
@@ -0,0 +1,92 @@
+//! AutoCAD Color Index (ACI) palette.
+
+/// RGB of the standard 256-entry ACI palette.
+///
+/// 1-9 are the named colors (7 is white; viewers swap it for black on light
+/// backgrounds), 10-249 step the hue by 15 degrees with five brightness
+/// levels each in a full and a half-saturated shade, and 250-255 are grays.
+/// 0 (BYBLOCK) maps to black; 256 (BYLAYER) and larger indices have no
+/// palette entry and map to the foreground color 7, so resolve them against
+/// the owning layer or block first.
+pub fn aci_to_rgb(index: u16) -> (u8, u8, u8) {
+    match index {
+        0 => (0, 0, 0),
+        1 => (255, 0, 0),
+        2 => (255, 255, 0),
+        3 => (0, 255, 0),
+        4 => (0, 255, 255),
+        5 => (0, 0, 255),
+        6 => (255, 0, 255),
+        8 => (128, 128, 128),
+        9 => (192, 192, 192),
+        10..=249 => hue_entry(index),
+        250..=255 => {
+            let level = [51, 91, 132, 173, 214, 255][usize::from(index - 250)];
+            (level, level, level)
+        }
+        _ => (255, 255, 255),
+    }
+}
+
+fn hue_entry(index: u16) -> (u8, u8, u8) {
+    const LEVELS: [f64; 5] = [1.0, 0.8, 0.6, 0.5, 0.3];
+    let hue = f64::from((index - 10) / 10) * 15.0;
+    let shade = index % 10;
+    let max = 255.0 * LEVELS[usize::from(shade / 2)];
+    let min = if shade % 2 == 1 { max / 2.0 } else { 0.0 };
+    let ramp = |t: f64| min + (max - min) * t;
+    let sector = hue / 60.0;
+    let t = sector.fract();
+    let (r, g, b) = match sector as u8 {
+        0 => (max, ramp(t), min),
+        1 => (ramp(1.0 - t), max, min),
+        2 => (min, max, ramp(t)),
+        3 => (min, ramp(1.0 - t), max),
+        4 => (ramp(t), min, max),
+        _ => (max, min, ramp(1.0 - t)),
+    };
+    (r as u8, g as u8, b as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_reference_palette_entries() {
+        for (index, rgb) in [
+            (1, 0xFF0000),
+            (7, 0xFFFFFF),
+            (10, 0xFF0000),
+            (11, 0xFF7F7F),
+            (12, 0xCC0000),
+            (15, 0x994C4C),
+            (18, 0x4C0000),
+            (19, 0x4C2626),
+            (20, 0xFF3F00),
+            (21, 0xFF9F7F),
+            (30, 0xFF7F00),
+            (50, 0xFFFF00),
+            (90, 0x00FF00),
+            (130, 0x00FFFF),
+            (140, 0x00BFFF),
+            (170, 0x0000FF),
+            (210, 0xFF00FF),
+            (240, 0xFF003F),
+            (250, 0x333333),
+            (251, 0x5B5B5B),
+            (254, 0xD6D6D6),
+            (255, 0xFFFFFF),
+        ] {
+            let (r, g, b) = aci_to_rgb(index);
+            let packed = (u32::from(r) << 16) | (u32::from(g) << 8) | u32::from(b);
+            assert_eq!(packed, rgb, "ACI {index}: {packed:06X}");
+        }
+    }
+
+    #[test]
+    fn by_layer_and_by_block_have_fallbacks() {
+        assert_eq!(aci_to_rgb(0), (0, 0, 0));
+        assert_eq!(aci_to_rgb(256), aci_to_rgb(7));
+    }
+}
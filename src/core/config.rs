@@ -1,9 +1,118 @@
+use crate::core::geometry::{ArcTessellation, DEFAULT_ARC_SEGMENTS, DEFAULT_POINT_EPSILON};
+
+/// Which layouts whole-file entity queries return entities from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpaceFilter {
+    /// Entities owned by `*Model_Space`.
+    ModelOnly,
+    /// Entities of any paper-space layout. Layouts other than the active one
+    /// are only recognized where block names can be read (R2004 and older).
+    PaperOnly,
+    /// Every entity, including the contents of block definitions.
+    #[default]
+    All,
+}
+
 #[derive(Debug, Clone)]
 pub struct ParseConfig {
     pub strict: bool,
     pub max_recursion: u32,
     pub max_objects: u32,
     pub max_section_bytes: u64,
+    /// Associate VERTEX and ATTRIB records with their POLYLINE or INSERT
+    /// through their owner handle when the owner carries no owned-handle
+    /// list, instead of relying only on object-map adjacency.
+    pub vertex_owner_fallback: bool,
+    /// Keep the decompressed `AcDb:AcDbObjects` section on the decoder so
+    /// object records borrow from it instead of re-decompressing the
+    /// section for every record. R13-R15 files store records uncompressed;
+    /// there the parsed object map is kept instead.
+    pub retain_objects_buffer: bool,
+    /// Tolerance for treating two points as coincident (polyline closure),
+    /// relative to coordinate magnitude above 1.0.
+    pub point_epsilon: f64,
+    /// Restrict the `high_level` entity queries, and the Python `raw`
+    /// decoders, to model or paper space.
+    pub space_filter: SpaceFilter,
+    /// Fail dimension decodes that no layout variant reads cleanly instead
+    /// of returning the most plausible guess; see
+    /// [`DimensionCommonData::layout_score`](crate::entities::DimensionCommonData::layout_score).
+    pub strict_dimensions: bool,
+    /// Chords per full turn when flattening arcs: HATCH boundaries, and the
+    /// Catmull-Rom spans of curve-fit polylines at an eighth of that.
+    pub arc_segments: usize,
+    /// When set, HATCH boundary arcs instead get as many chords as keep each
+    /// within this distance of the arc, and `arc_segments` only sets the
+    /// Catmull-Rom span count.
+    pub arc_max_sagitta: Option<f64>,
+    /// Rebuild the R2004+ object index by scanning `AcDb:AcDbObjects` for
+    /// CRC-valid records when the object map fails to load or lists records
+    /// that do not parse, and the scan finds more readable ones.
+    pub scan_recovery: bool,
+}
+
+/// Chords per full arc turn under [`ParseConfig::fast_preview`].
+const PREVIEW_ARC_SEGMENTS: usize = 16;
+
+impl ParseConfig {
+    /// Fail on structural inconsistencies and on dimensions no layout
+    /// variant reads cleanly, and never guess record ownership or rebuild
+    /// the object index.
+    pub fn strict() -> Self {
+        Self {
+            strict: true,
+            vertex_owner_fallback: false,
+            strict_dimensions: true,
+            scan_recovery: false,
+            ..Self::default()
+        }
+    }
+
+    /// The default, plus the object-index scan for files whose object map
+    /// is damaged.
+    pub fn tolerant() -> Self {
+        Self {
+            strict: false,
+            vertex_owner_fallback: true,
+            strict_dimensions: false,
+            scan_recovery: true,
+            ..Self::default()
+        }
+    }
+
+    /// The default with coarse arc tessellation, for drawing a quick
+    /// overview. Decoding itself does no less work than the default.
+    pub fn fast_preview() -> Self {
+        Self {
+            arc_segments: PREVIEW_ARC_SEGMENTS,
+            ..Self::default()
+        }
+    }
+
+    /// The preset named `name`: `default`, `strict`, `tolerant` or
+    /// `fast_preview`.
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::default()),
+            "strict" => Some(Self::strict()),
+            "tolerant" => Some(Self::tolerant()),
+            "fast_preview" => Some(Self::fast_preview()),
+            _ => None,
+        }
+    }
+
+    /// A builder starting from the default configuration.
+    pub fn builder() -> ParseConfigBuilder {
+        ParseConfigBuilder::default()
+    }
+
+    /// How arcs are flattened under `arc_segments` and `arc_max_sagitta`.
+    pub fn arc_tessellation(&self) -> ArcTessellation {
+        match self.arc_max_sagitta {
+            Some(max_sagitta) => ArcTessellation::MaxSagitta(max_sagitta),
+            None => ArcTessellation::Segments(self.arc_segments),
+        }
+    }
 }
 
 impl Default for ParseConfig {
@@ -13,6 +122,114 @@ impl Default for ParseConfig {
             max_recursion: 64,
             max_objects: 1_000_000,
             max_section_bytes: 256 * 1024 * 1024,
+            vertex_owner_fallback: true,
+            retain_objects_buffer: true,
+            point_epsilon: DEFAULT_POINT_EPSILON,
+            space_filter: SpaceFilter::All,
+            strict_dimensions: false,
+            arc_segments: DEFAULT_ARC_SEGMENTS,
+            arc_max_sagitta: None,
+            scan_recovery: false,
         }
     }
 }
+
+/// Sets [`ParseConfig`] fields one at a time, starting from the default or
+/// from a preset (`ParseConfigBuilder::from(ParseConfig::strict())`).
+#[derive(Debug, Clone, Default)]
+pub struct ParseConfigBuilder {
+    config: ParseConfig,
+}
+
+impl From<ParseConfig> for ParseConfigBuilder {
+    fn from(config: ParseConfig) -> Self {
+        Self { config }
+    }
+}
+
+impl ParseConfigBuilder {
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.config.strict = strict;
+        self
+    }
+
+    pub fn max_recursion(mut self, max_recursion: u32) -> Self {
+        self.config.max_recursion = max_recursion;
+        self
+    }
+
+    pub fn max_objects(mut self, max_objects: u32) -> Self {
+        self.config.max_objects = max_objects;
+        self
+    }
+
+    pub fn max_section_bytes(mut self, max_section_bytes: u64) -> Self {
+        self.config.max_section_bytes = max_section_bytes;
+        self
+    }
+
+    pub fn vertex_owner_fallback(mut self, vertex_owner_fallback: bool) -> Self {
+        self.config.vertex_owner_fallback = vertex_owner_fallback;
+        self
+    }
+
+    pub fn retain_objects_buffer(mut self, retain_objects_buffer: bool) -> Self {
+        self.config.retain_objects_buffer = retain_objects_buffer;
+        self
+    }
+
+    pub fn point_epsilon(mut self, point_epsilon: f64) -> Self {
+        self.config.point_epsilon = point_epsilon;
+        self
+    }
+
+    pub fn space_filter(mut self, space_filter: SpaceFilter) -> Self {
+        self.config.space_filter = space_filter;
+        self
+    }
+
+    pub fn strict_dimensions(mut self, strict_dimensions: bool) -> Self {
+        self.config.strict_dimensions = strict_dimensions;
+        self
+    }
+
+    pub fn arc_segments(mut self, arc_segments: usize) -> Self {
+        self.config.arc_segments = arc_segments;
+        self
+    }
+
+    pub fn arc_max_sagitta(mut self, arc_max_sagitta: Option<f64>) -> Self {
+        self.config.arc_max_sagitta = arc_max_sagitta;
+        self
+    }
+
+    pub fn scan_recovery(mut self, scan_recovery: bool) -> Self {
+        self.config.scan_recovery = scan_recovery;
+        self
+    }
+
+    pub fn build(self) -> ParseConfig {
+        self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builder_overrides_fields_of_a_preset() {
+        let config = ParseConfigBuilder::from(ParseConfig::fast_preview())
+            .max_objects(10)
+            .scan_recovery(true)
+            .build();
+        assert_eq!(config.arc_segments, PREVIEW_ARC_SEGMENTS);
+        assert_eq!(config.max_objects, 10);
+        assert!(config.scan_recovery);
+        assert!(!config.strict);
+
+        assert!(ParseConfig::preset("strict").unwrap().strict);
+        assert!(ParseConfig::preset("tolerant").unwrap().scan_recovery);
+        assert!(ParseConfig::preset("lenient").is_none());
+    }
+}
@@ -0,0 +1,74 @@
+/// Seed used for object records, object-map blocks and most sections.
+pub const CRC_SEED: u16 = 0xC0C1;
+
+const CRC_TABLE: [u16; 256] = build_crc_table();
+
+const fn build_crc_table() -> [u16; 256] {
+    let mut table = [0u16; 256];
+    let mut idx = 0usize;
+    while idx < 256 {
+        let mut crc = idx as u16;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if (crc & 1) != 0 {
+                (crc >> 1) ^ 0xA001
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[idx] = crc;
+        idx += 1;
+    }
+    table
+}
+
+/// DWG CRC-16 (reflected 0x8005 polynomial) over `bytes`, starting from `seed`.
+pub fn compute_crc(bytes: &[u8], seed: u16) -> u16 {
+    let mut crc = seed;
+    for &byte in bytes {
+        let idx = ((crc ^ byte as u16) & 0xFF) as usize;
+        crc = (crc >> 8) ^ CRC_TABLE[idx];
+    }
+    crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compute_crc, CRC_SEED};
+
+    #[test]
+    fn matches_known_vectors() {
+        assert_eq!(compute_crc(b"", 0), 0);
+        assert_eq!(compute_crc(b"", CRC_SEED), CRC_SEED);
+        assert_eq!(compute_crc(b"123456789", 0), 0xBB3D);
+        assert_eq!(compute_crc(&[0x00], 0), 0x0000);
+        assert_eq!(compute_crc(&[0x01], 0), 0xC0C1);
+    }
+
+    #[test]
+    fn chains_across_split_input() {
+        let data = b"AC1015 object map block";
+        let (head, tail) = data.split_at(9);
+        assert_eq!(
+            compute_crc(tail, compute_crc(head, CRC_SEED)),
+            compute_crc(data, CRC_SEED)
+        );
+    }
+
+    #[test]
+    fn verifies_r2000_object_map_block() {
+        let bytes = std::fs::read("test_dwg/line_2000.dwg").expect("sample file");
+        let directory = crate::container::section_directory::parse(&bytes).expect("directory");
+        let record = directory
+            .records
+            .iter()
+            .find(|record| record.record_no == 2)
+            .expect("object map record");
+        let start = record.offset as usize;
+        let block_size = u16::from_be_bytes([bytes[start], bytes[start + 1]]) as usize;
+        let block = &bytes[start..start + block_size];
+        let stored = u16::from_be_bytes([bytes[start + block_size], bytes[start + block_size + 1]]);
+        assert_eq!(compute_crc(block, CRC_SEED), stored);
+    }
+}
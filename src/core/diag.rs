@@ -0,0 +1,123 @@
+//! Parse diagnostics emitted through the `log` crate behind the `tracing`
+//! feature.
+//!
+//! Events are logged under `ezdwg::<stage>` targets (`ezdwg::section`,
+//! `ezdwg::rs_decode`, `ezdwg::object`, ...): progress at `debug`, suspect
+//! but recoverable input at `warn`. Rust callers see them through whichever
+//! logger they install. The Python extension and the CLI install
+//! [`init_stderr_logger`], which reads `RUST_LOG` (e.g. `RUST_LOG=ezdwg=trace`).
+//! Without the feature, the macros expand to dead code and their arguments are
+//! never evaluated.
+
+#[cfg(feature = "tracing")]
+use log::{LevelFilter, Log, Metadata, Record};
+#[cfg(feature = "tracing")]
+use std::sync::OnceLock;
+
+/// Logger writing `ezdwg` records to stderr at the level `RUST_LOG` selects.
+#[cfg(feature = "tracing")]
+struct StderrLogger {
+    level: LevelFilter,
+}
+
+#[cfg(feature = "tracing")]
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        is_ezdwg_target(metadata.target()) && metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if self.enabled(record.metadata()) {
+            eprintln!("[{} {}] {}", record.level(), record.target(), record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Install the stderr logger unless `RUST_LOG` leaves `ezdwg` off or another
+/// logger is already registered.
+#[cfg(feature = "tracing")]
+pub fn init_stderr_logger() {
+    let level = std::env::var("RUST_LOG")
+        .map(|spec| directives_level(&spec))
+        .unwrap_or(LevelFilter::Off);
+    if level == LevelFilter::Off {
+        return;
+    }
+    static LOGGER: OnceLock<StderrLogger> = OnceLock::new();
+    if log::set_logger(LOGGER.get_or_init(|| StderrLogger { level })).is_ok() {
+        log::set_max_level(level);
+    }
+}
+
+#[cfg(feature = "tracing")]
+fn is_ezdwg_target(target: &str) -> bool {
+    target == "ezdwg" || target.starts_with("ezdwg::")
+}
+
+/// Most verbose level that `RUST_LOG`-style directives grant `ezdwg` targets.
+#[cfg(feature = "tracing")]
+fn directives_level(spec: &str) -> LevelFilter {
+    spec.split(',')
+        .map(str::trim)
+        .filter_map(|directive| match directive.split_once('=') {
+            Some((target, level)) if is_ezdwg_target(target) => level.parse().ok(),
+            Some(_) => None,
+            None => match directive.parse() {
+                Ok(level) => Some(level),
+                Err(_) if is_ezdwg_target(directive) => Some(LevelFilter::Trace),
+                Err(_) => None,
+            },
+        })
+        .max()
+        .unwrap_or(LevelFilter::Off)
+}
+
+/// Log a `debug` diagnostic under `ezdwg::$target` (e.g. `"section"`).
+macro_rules! diag_event {
+    ($target:literal, $($arg:tt)+) => {{
+        #[cfg(feature = "tracing")]
+        log::debug!(target: concat!("ezdwg::", $target), $($arg)+);
+        #[cfg(not(feature = "tracing"))]
+        if false {
+            let _ = format_args!($($arg)+);
+        }
+    }};
+}
+
+/// Log a `warn` diagnostic under `ezdwg::$target`, for input that decodes
+/// but looks damaged.
+macro_rules! diag_warn {
+    ($target:literal, $($arg:tt)+) => {{
+        #[cfg(feature = "tracing")]
+        log::warn!(target: concat!("ezdwg::", $target), $($arg)+);
+        #[cfg(not(feature = "tracing"))]
+        if false {
+            let _ = format_args!($($arg)+);
+        }
+    }};
+}
+
+pub(crate) use diag_event;
+pub(crate) use diag_warn;
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use super::directives_level;
+    use log::LevelFilter;
+
+    #[test]
+    fn directives_select_ezdwg_target() {
+        assert_eq!(directives_level("trace"), LevelFilter::Trace);
+        assert_eq!(directives_level("ezdwg=debug"), LevelFilter::Debug);
+        assert_eq!(directives_level("ezdwg"), LevelFilter::Trace);
+        assert_eq!(
+            directives_level("warn,ezdwg::dwg=trace"),
+            LevelFilter::Trace
+        );
+        assert_eq!(directives_level("ezdwg=info"), LevelFilter::Info);
+        assert_eq!(directives_level("other=trace"), LevelFilter::Off);
+        assert_eq!(directives_level("warn"), LevelFilter::Warn);
+    }
+}
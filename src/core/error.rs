@@ -7,7 +7,14 @@ pub enum ErrorKind {
     Decode,
     Resolve,
     Unsupported,
+    UnsupportedEntity,
     NotImplemented,
+    /// A progress callback asked a walk to stop.
+    Cancelled,
+    /// The input is some other file type (DXF, DWF, ...), not a DWG.
+    NotDwg,
+    /// A section's start or end marker does not match the expected bytes.
+    SentinelMismatch,
 }
 
 impl fmt::Display for ErrorKind {
@@ -18,7 +25,11 @@ impl fmt::Display for ErrorKind {
             Self::Decode => "decode",
             Self::Resolve => "resolve",
             Self::Unsupported => "unsupported",
+            Self::UnsupportedEntity => "unsupported_entity",
             Self::NotImplemented => "not_implemented",
+            Self::Cancelled => "cancelled",
+            Self::NotDwg => "not_dwg",
+            Self::SentinelMismatch => "sentinel_mismatch",
         };
         write!(f, "{label}")
     }
@@ -48,6 +59,13 @@ impl DwgError {
     pub fn not_implemented(message: impl Into<String>) -> Self {
         Self::new(ErrorKind::NotImplemented, message)
     }
+
+    pub fn unsupported_entity(type_name: impl AsRef<str>) -> Self {
+        Self::new(
+            ErrorKind::UnsupportedEntity,
+            format!("unsupported entity type: {}", type_name.as_ref()),
+        )
+    }
 }
 
 impl fmt::Display for DwgError {
@@ -0,0 +1,1079 @@
+use std::collections::HashMap;
+
+/// Default tolerance for coincident-point checks (closure, segment joins).
+pub const DEFAULT_POINT_EPSILON: f64 = 1e-9;
+
+/// `a` and `b` agree within `epsilon`, scaled by their magnitude once it
+/// exceeds 1 so float noise in large (e.g. survey) coordinates still matches.
+pub fn approx_eq(a: f64, b: f64, epsilon: f64) -> bool {
+    (a - b).abs() <= epsilon * a.abs().max(b.abs()).max(1.0)
+}
+
+pub fn points_equal_2d(a: (f64, f64), b: (f64, f64), epsilon: f64) -> bool {
+    approx_eq(a.0, b.0, epsilon) && approx_eq(a.1, b.1, epsilon)
+}
+
+pub fn points_equal_3d(a: (f64, f64, f64), b: (f64, f64, f64), epsilon: f64) -> bool {
+    approx_eq(a.0, b.0, epsilon) && approx_eq(a.1, b.1, epsilon) && approx_eq(a.2, b.2, epsilon)
+}
+
+type Point3 = (f64, f64, f64);
+
+/// Below this, an extrusion is "close to" the world Z axis for the
+/// arbitrary-axis algorithm.
+const ARBITRARY_AXIS_LIMIT: f64 = 1.0 / 64.0;
+
+/// OCS X, Y and Z axes (in WCS) for an entity extrusion, per the AutoCAD
+/// arbitrary-axis algorithm. A zero-length extrusion is treated as world Z.
+pub fn arbitrary_axes(extrusion: Point3) -> [Point3; 3] {
+    let Some(z_axis) = normalize(extrusion) else {
+        return [(1.0, 0.0, 0.0), (0.0, 1.0, 0.0), (0.0, 0.0, 1.0)];
+    };
+    let world = if z_axis.0.abs() < ARBITRARY_AXIS_LIMIT && z_axis.1.abs() < ARBITRARY_AXIS_LIMIT {
+        (0.0, 1.0, 0.0)
+    } else {
+        (0.0, 0.0, 1.0)
+    };
+    let x_axis = normalize(cross(world, z_axis)).unwrap_or((1.0, 0.0, 0.0));
+    let y_axis = normalize(cross(z_axis, x_axis)).unwrap_or((0.0, 1.0, 0.0));
+    [x_axis, y_axis, z_axis]
+}
+
+/// Convert a point in the Object Coordinate System of `extrusion` to world
+/// coordinates.
+pub fn ocs_to_wcs(extrusion: Point3, point: Point3) -> Point3 {
+    let [x_axis, y_axis, z_axis] = arbitrary_axes(extrusion);
+    (
+        point.0 * x_axis.0 + point.1 * y_axis.0 + point.2 * z_axis.0,
+        point.0 * x_axis.1 + point.1 * y_axis.1 + point.2 * z_axis.1,
+        point.0 * x_axis.2 + point.1 * y_axis.2 + point.2 * z_axis.2,
+    )
+}
+
+/// Row-major 4x4 matrix taking block coordinates to world coordinates for an
+/// INSERT: `OCS(extrusion) * T(position) * Rz(rotation) * S(scale) *
+/// T(-base_point)`, so the block's base point lands on the insertion point.
+pub fn insert_transform(
+    position: Point3,
+    scale: Point3,
+    rotation: f64,
+    extrusion: Point3,
+    base_point: Point3,
+) -> [f64; 16] {
+    let [x_axis, y_axis, z_axis] = arbitrary_axes(extrusion);
+    let (sin, cos) = rotation.sin_cos();
+    // Columns of the OCS-local part: rotated, scaled X/Y, scaled Z.
+    let local = [
+        (cos * scale.0, sin * scale.0, 0.0),
+        (-sin * scale.1, cos * scale.1, 0.0),
+        (0.0, 0.0, scale.2),
+    ];
+    let to_world = |v: Point3| {
+        (
+            v.0 * x_axis.0 + v.1 * y_axis.0 + v.2 * z_axis.0,
+            v.0 * x_axis.1 + v.1 * y_axis.1 + v.2 * z_axis.1,
+            v.0 * x_axis.2 + v.1 * y_axis.2 + v.2 * z_axis.2,
+        )
+    };
+    let [c0, c1, c2] = local.map(to_world);
+    let origin = ocs_to_wcs(extrusion, position);
+    let t = (
+        origin.0 - (c0.0 * base_point.0 + c1.0 * base_point.1 + c2.0 * base_point.2),
+        origin.1 - (c0.1 * base_point.0 + c1.1 * base_point.1 + c2.1 * base_point.2),
+        origin.2 - (c0.2 * base_point.0 + c1.2 * base_point.1 + c2.2 * base_point.2),
+    );
+    [
+        c0.0, c1.0, c2.0, t.0, //
+        c0.1, c1.1, c2.1, t.1, //
+        c0.2, c1.2, c2.2, t.2, //
+        0.0, 0.0, 0.0, 1.0,
+    ]
+}
+
+/// One polyline produced by [`chain_segments`]: the indices of the input
+/// segments in walk order and the joined vertices. A closed chain repeats its
+/// first vertex at the end.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SegmentChain {
+    pub segments: Vec<usize>,
+    pub points: Vec<Point3>,
+}
+
+/// Join segments whose endpoints lie within `tolerance` of each other
+/// (absolute distance, in drawing units) into ordered chains. A chain ends at
+/// a free endpoint or where three or more segments meet; loops close on
+/// themselves. Zero-length segments are dropped.
+pub fn chain_segments(segments: &[(Point3, Point3)], tolerance: f64) -> Vec<SegmentChain> {
+    // Matching points are at most one cell apart per axis, so they land in
+    // the same or a neighbouring cell.
+    let cell = tolerance.max(f64::MIN_POSITIVE);
+    let cell_of = |p: Point3| {
+        (
+            (p.0 / cell).floor() as i64,
+            (p.1 / cell).floor() as i64,
+            (p.2 / cell).floor() as i64,
+        )
+    };
+    let within = |a: Point3, b: Point3| {
+        let (dx, dy, dz) = (a.0 - b.0, a.1 - b.1, a.2 - b.2);
+        (dx * dx + dy * dy + dz * dz).sqrt() <= tolerance
+    };
+
+    let mut grid: HashMap<(i64, i64, i64), Vec<usize>> = HashMap::new();
+    let mut nodes: Vec<Point3> = Vec::new();
+    let mut node_for = |p: Point3| -> usize {
+        let (cx, cy, cz) = cell_of(p);
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let key = (
+                        cx.saturating_add(dx),
+                        cy.saturating_add(dy),
+                        cz.saturating_add(dz),
+                    );
+                    if let Some(found) = grid.get(&key).and_then(|candidates| {
+                        candidates
+                            .iter()
+                            .copied()
+                            .find(|&node| within(nodes[node], p))
+                    }) {
+                        return found;
+                    }
+                }
+            }
+        }
+        nodes.push(p);
+        grid.entry((cx, cy, cz)).or_default().push(nodes.len() - 1);
+        nodes.len() - 1
+    };
+
+    // Segment index -> (start node, end node); None for zero-length ones.
+    let ends: Vec<Option<(usize, usize)>> = segments
+        .iter()
+        .map(|&(a, b)| {
+            let (start, end) = (node_for(a), node_for(b));
+            (start != end).then_some((start, end))
+        })
+        .collect();
+    let mut incident: Vec<Vec<usize>> = vec![Vec::new(); nodes.len()];
+    for (index, pair) in ends.iter().enumerate() {
+        if let Some((start, end)) = *pair {
+            incident[start].push(index);
+            incident[end].push(index);
+        }
+    }
+
+    let mut used = vec![false; segments.len()];
+    let walk = |start_node: usize, first: usize, used: &mut Vec<bool>| -> SegmentChain {
+        let mut chain = SegmentChain {
+            segments: Vec::new(),
+            points: vec![nodes[start_node]],
+        };
+        let (mut node, mut segment) = (start_node, Some(first));
+        while let Some(index) = segment {
+            used[index] = true;
+            let (a, b) = ends[index].expect("walked segments have distinct ends");
+            node = if a == node { b } else { a };
+            chain.segments.push(index);
+            chain.points.push(nodes[node]);
+            segment = if incident[node].len() == 2 {
+                incident[node].iter().copied().find(|&next| !used[next])
+            } else {
+                None
+            };
+        }
+        chain
+    };
+
+    let mut chains = Vec::new();
+    // Open chains start at free endpoints and junctions...
+    for (node, edges) in incident.iter().enumerate() {
+        if edges.len() == 2 {
+            continue;
+        }
+        for &first in edges {
+            if !used[first] {
+                chains.push(walk(node, first, &mut used));
+            }
+        }
+    }
+    // ...and whatever is left forms closed loops.
+    for index in 0..segments.len() {
+        if let (Some((start, _)), false) = (ends[index], used[index]) {
+            chains.push(walk(start, index, &mut used));
+        }
+    }
+    chains
+}
+
+/// Axis-aligned box in the XY plane; `min` is never greater than `max`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds2 {
+    pub min: (f64, f64),
+    pub max: (f64, f64),
+}
+
+impl Bounds2 {
+    /// Box spanned by two opposite corners given in any order.
+    pub fn new(a: (f64, f64), b: (f64, f64)) -> Self {
+        Self {
+            min: (a.0.min(b.0), a.1.min(b.1)),
+            max: (a.0.max(b.0), a.1.max(b.1)),
+        }
+    }
+
+    /// Smallest box holding every finite point, or `None` if there are none.
+    pub fn from_points(points: impl IntoIterator<Item = (f64, f64)>) -> Option<Self> {
+        let mut points = points
+            .into_iter()
+            .filter(|point| point.0.is_finite() && point.1.is_finite());
+        let first = points.next()?;
+        let mut bounds = Self::new(first, first);
+        for point in points {
+            bounds.include(point);
+        }
+        Some(bounds)
+    }
+
+    pub fn include(&mut self, point: (f64, f64)) {
+        self.min = (self.min.0.min(point.0), self.min.1.min(point.1));
+        self.max = (self.max.0.max(point.0), self.max.1.max(point.1));
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        let mut bounds = self;
+        bounds.include(other.min);
+        bounds.include(other.max);
+        bounds
+    }
+
+    /// Boxes overlap or touch.
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.min.0 <= other.max.0
+            && other.min.0 <= self.max.0
+            && self.min.1 <= other.max.1
+            && other.min.1 <= self.max.1
+    }
+
+    pub fn corners(&self) -> [(f64, f64); 4] {
+        [
+            self.min,
+            (self.max.0, self.min.1),
+            self.max,
+            (self.min.0, self.max.1),
+        ]
+    }
+}
+
+/// Bounds of the arc running counter-clockwise from `start` to `end`
+/// (radians). Equal angles mean a full circle.
+pub fn arc_bounds_2d(center: (f64, f64), radius: f64, start: f64, end: f64) -> Bounds2 {
+    let mut sweep = (end - start).rem_euclid(std::f64::consts::TAU);
+    if sweep == 0.0 {
+        sweep = std::f64::consts::TAU;
+    }
+    let at = |angle: f64| {
+        let (sin, cos) = angle.sin_cos();
+        (center.0 + radius * cos, center.1 + radius * sin)
+    };
+    let mut bounds = Bounds2::new(at(start), at(start + sweep));
+    // Add each axis extreme (0, 90, 180, 270 degrees) the sweep passes.
+    let first_quadrant = (start / std::f64::consts::FRAC_PI_2).ceil();
+    for step in 0..4 {
+        let angle = (first_quadrant + f64::from(step)) * std::f64::consts::FRAC_PI_2;
+        if angle - start > sweep {
+            break;
+        }
+        bounds.include(at(angle));
+    }
+    bounds
+}
+
+/// Bounds of one LWPOLYLINE segment from `a` to `b`; `bulge` is tan(θ/4) of
+/// the arc's included angle, positive for counter-clockwise.
+pub fn bulge_bounds_2d(a: (f64, f64), b: (f64, f64), bulge: f64) -> Bounds2 {
+    let chord = (b.0 - a.0).hypot(b.1 - a.1);
+    if bulge == 0.0 || !bulge.is_finite() || chord == 0.0 {
+        return Bounds2::new(a, b);
+    }
+    let (center, radius) = bulge_arc(a, b, bulge, chord);
+    let angle_of = |point: (f64, f64)| (point.1 - center.1).atan2(point.0 - center.0);
+    let (start, end) = if bulge > 0.0 { (a, b) } else { (b, a) };
+    let mut bounds = arc_bounds_2d(center, radius, angle_of(start), angle_of(end));
+    // Keep the exact vertices; the arc endpoints carry rounding error.
+    bounds.include(a);
+    bounds.include(b);
+    bounds
+}
+
+/// Centre and radius of the arc a non-zero `bulge` draws over a chord of
+/// length `chord` from `a` to `b`.
+fn bulge_arc(a: (f64, f64), b: (f64, f64), bulge: f64, chord: f64) -> ((f64, f64), f64) {
+    let radius = chord * (1.0 + bulge * bulge) / (4.0 * bulge.abs());
+    // Signed distance from the chord midpoint to the centre, along the
+    // chord's left normal.
+    let offset = bulge.signum() * (radius - bulge.abs() * chord / 2.0);
+    let normal = (-(b.1 - a.1) / chord, (b.0 - a.0) / chord);
+    let center = (
+        (a.0 + b.0) / 2.0 + normal.0 * offset,
+        (a.1 + b.1) / 2.0 + normal.1 * offset,
+    );
+    (center, radius)
+}
+
+/// Outline of one LWPOLYLINE segment drawn with a width tapering linearly
+/// from `start_width` at `a` to `end_width` at `b`: the left edge from `a` to
+/// `b`, then the right edge back.
+pub fn bulge_ribbon_2d(
+    a: (f64, f64),
+    b: (f64, f64),
+    bulge: f64,
+    start_width: f64,
+    end_width: f64,
+    tessellation: ArcTessellation,
+) -> Vec<(f64, f64)> {
+    let chord = (b.0 - a.0).hypot(b.1 - a.1);
+    if chord == 0.0 {
+        return Vec::new();
+    }
+    // Centre-line samples with their unit left normals.
+    let samples: Vec<((f64, f64), (f64, f64))> = if bulge == 0.0 || !bulge.is_finite() {
+        let normal = (-(b.1 - a.1) / chord, (b.0 - a.0) / chord);
+        vec![(a, normal), (b, normal)]
+    } else {
+        let (center, radius) = bulge_arc(a, b, bulge, chord);
+        let sweep = 4.0 * bulge.atan();
+        let start = (a.1 - center.1).atan2(a.0 - center.0);
+        let steps = tessellation.segment_count(sweep, radius, 1);
+        (0..=steps)
+            .map(|step| {
+                let (sin, cos) = (start + sweep * step as f64 / steps as f64).sin_cos();
+                let point = (center.0 + radius * cos, center.1 + radius * sin);
+                // Counter-clockwise travel turns left, towards the centre.
+                let normal = if sweep > 0.0 {
+                    (-cos, -sin)
+                } else {
+                    (cos, sin)
+                };
+                (point, normal)
+            })
+            .collect()
+    };
+    let last = (samples.len() - 1) as f64;
+    let half_width =
+        |index: usize| (start_width + (end_width - start_width) * index as f64 / last) / 2.0;
+    let left = samples.iter().enumerate().map(|(index, (point, normal))| {
+        let half = half_width(index);
+        (point.0 + normal.0 * half, point.1 + normal.1 * half)
+    });
+    let right = samples
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(index, (point, normal))| {
+            let half = half_width(index);
+            (point.0 - normal.0 * half, point.1 - normal.1 * half)
+        });
+    left.chain(right).collect()
+}
+
+/// Chords per full turn used to flatten arcs unless
+/// [`ParseConfig::arc_segments`](crate::core::config::ParseConfig::arc_segments)
+/// says otherwise.
+pub const DEFAULT_ARC_SEGMENTS: usize = 64;
+
+/// Upper bound on chords per full turn, so a huge `arc_segments` or a tiny
+/// tolerance on a huge radius cannot ask for millions of points.
+const MAX_ARC_SEGMENTS: usize = 1 << 16;
+
+/// How arcs are split into chords.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArcTessellation {
+    /// A fixed number of chords per full turn, whatever the radius.
+    Segments(usize),
+    /// As many chords as keep each one within this distance (its sagitta)
+    /// of the arc, so large arcs get more chords than small ones.
+    MaxSagitta(f64),
+}
+
+impl Default for ArcTessellation {
+    fn default() -> Self {
+        Self::Segments(DEFAULT_ARC_SEGMENTS)
+    }
+}
+
+impl ArcTessellation {
+    /// Chords for an arc of `radius` sweeping `sweep` radians, never fewer
+    /// than `min`. A tolerance that is not a positive number falls back to
+    /// [`DEFAULT_ARC_SEGMENTS`].
+    pub fn segment_count(self, sweep: f64, radius: f64, min: usize) -> usize {
+        match self {
+            Self::Segments(arc_segments) => arc_segment_count(sweep, arc_segments, min),
+            Self::MaxSagitta(max_sagitta) if max_sagitta > 0.0 && radius.abs() > 0.0 => {
+                // A chord spanning θ deviates r(1 - cos(θ/2)) from its arc.
+                let step = 2.0 * (1.0 - max_sagitta / radius.abs()).max(-1.0).acos();
+                let turns = sweep.abs() / std::f64::consts::TAU;
+                let limit = (turns * MAX_ARC_SEGMENTS as f64).ceil();
+                ((sweep.abs() / step).ceil().min(limit) as usize).max(min)
+            }
+            Self::MaxSagitta(_) => arc_segment_count(sweep, DEFAULT_ARC_SEGMENTS, min),
+        }
+    }
+}
+
+/// Chords for an arc sweeping `sweep` radians at `arc_segments` per full
+/// turn (8 to 65536), and never fewer than `min`.
+pub fn arc_segment_count(sweep: f64, arc_segments: usize, min: usize) -> usize {
+    let turns = sweep.abs() / std::f64::consts::TAU;
+    ((turns * arc_segments.clamp(8, MAX_ARC_SEGMENTS) as f64).ceil() as usize).max(min)
+}
+
+/// Points along the counter-clockwise arc from `start_angle` to `end_angle`
+/// (radians), with no point of a chord further than `max_sagitta` from the
+/// arc.
+pub fn arc_points_by_tolerance(
+    center: (f64, f64),
+    radius: f64,
+    start_angle: f64,
+    end_angle: f64,
+    max_sagitta: f64,
+) -> Vec<(f64, f64)> {
+    circular_arc_points(
+        center,
+        radius,
+        start_angle,
+        end_angle,
+        true,
+        ArcTessellation::MaxSagitta(max_sagitta),
+    )
+}
+
+/// Points along the circular arc from `start_angle` to `end_angle` (radians),
+/// counter-clockwise when `is_ccw`.
+pub fn circular_arc_points(
+    center: (f64, f64),
+    radius: f64,
+    start_angle: f64,
+    end_angle: f64,
+    is_ccw: bool,
+    tessellation: ArcTessellation,
+) -> Vec<(f64, f64)> {
+    if radius.abs() <= 1.0e-12 {
+        return vec![];
+    }
+    let sweep = normalized_sweep(start_angle, end_angle, is_ccw);
+    let segments = tessellation.segment_count(sweep, radius, 2);
+    let mut out = Vec::with_capacity(segments + 1);
+    for i in 0..=segments {
+        let t = (i as f64) / (segments as f64);
+        let angle = start_angle + sweep * t;
+        out.push((
+            center.0 + radius * angle.cos(),
+            center.1 + radius * angle.sin(),
+        ));
+    }
+    out
+}
+
+/// Points along an elliptical arc given by its major axis endpoint (relative
+/// to `center`) and minor-to-major `ratio`. With a fixed count, ellipses get
+/// half as many chords again as circles, as their curvature peaks at the
+/// major axis ends; a tolerance is held against the major-axis circle, whose
+/// chords deviate at least as much as the ellipse's.
+pub fn elliptical_arc_points(
+    center: (f64, f64),
+    major_endpoint: (f64, f64),
+    ratio: f64,
+    start_angle: f64,
+    end_angle: f64,
+    is_ccw: bool,
+    tessellation: ArcTessellation,
+) -> Vec<(f64, f64)> {
+    let mx = major_endpoint.0;
+    let my = major_endpoint.1;
+    if mx.abs() <= 1.0e-12 && my.abs() <= 1.0e-12 {
+        return vec![];
+    }
+    let vx = -my * ratio;
+    let vy = mx * ratio;
+    let sweep = normalized_sweep(start_angle, end_angle, is_ccw);
+    let segments = match tessellation {
+        ArcTessellation::Segments(arc_segments) => {
+            arc_segment_count(sweep, arc_segments.clamp(16, MAX_ARC_SEGMENTS) * 3 / 2, 4)
+        }
+        tolerance => tolerance.segment_count(sweep, mx.hypot(my), 4),
+    };
+    let mut out = Vec::with_capacity(segments + 1);
+    for i in 0..=segments {
+        let t = (i as f64) / (segments as f64);
+        let angle = start_angle + sweep * t;
+        let c = angle.cos();
+        let s = angle.sin();
+        out.push((center.0 + mx * c + vx * s, center.1 + my * c + vy * s));
+    }
+    out
+}
+
+/// Points along a polyline whose segment `i` bulges by `bulges[i]` (missing
+/// bulges are straight), including the closing segment when `closed`.
+/// Segment ends within `point_epsilon` of each other are kept once.
+pub fn polyline_with_bulges_points(
+    points: &[(f64, f64)],
+    bulges: &[f64],
+    closed: bool,
+    tessellation: ArcTessellation,
+    point_epsilon: f64,
+) -> Vec<(f64, f64)> {
+    if points.len() <= 1 {
+        return points.to_vec();
+    }
+    let mut bulge_values = vec![0.0f64; points.len()];
+    for (idx, bulge) in bulges.iter().enumerate().take(points.len()) {
+        bulge_values[idx] = *bulge;
+    }
+
+    let seg_count = if closed {
+        points.len()
+    } else {
+        points.len().saturating_sub(1)
+    };
+    let mut out: Vec<(f64, f64)> = Vec::new();
+    for idx in 0..seg_count {
+        let start = points[idx];
+        let end = points[(idx + 1) % points.len()];
+        let bulge = bulge_values[idx];
+        let segment = bulge_segment_points(start, end, bulge, tessellation);
+        append_segment_points(&mut out, &segment, point_epsilon);
+    }
+    out
+}
+
+/// Points along one bulged segment; `bulge` is tan(θ/4) of the arc's
+/// included angle, positive for counter-clockwise.
+pub fn bulge_segment_points(
+    start: (f64, f64),
+    end: (f64, f64),
+    bulge: f64,
+    tessellation: ArcTessellation,
+) -> Vec<(f64, f64)> {
+    if bulge.abs() <= 1.0e-12 {
+        return vec![start, end];
+    }
+
+    let dx = end.0 - start.0;
+    let dy = end.1 - start.1;
+    let chord = (dx * dx + dy * dy).sqrt();
+    if chord <= 1.0e-12 {
+        return vec![start, end];
+    }
+
+    let theta = 4.0 * bulge.atan();
+    if theta.abs() <= 1.0e-12 {
+        return vec![start, end];
+    }
+
+    let normal = (-dy / chord, dx / chord);
+    let center_offset = chord * (1.0 - bulge * bulge) / (4.0 * bulge);
+    let mid = ((start.0 + end.0) * 0.5, (start.1 + end.1) * 0.5);
+    let center = (
+        mid.0 + normal.0 * center_offset,
+        mid.1 + normal.1 * center_offset,
+    );
+    let radius = ((start.0 - center.0).powi(2) + (start.1 - center.1).powi(2)).sqrt();
+    if radius <= 1.0e-12 {
+        return vec![start, end];
+    }
+
+    let start_angle = (start.1 - center.1).atan2(start.0 - center.0);
+    let segments = tessellation.segment_count(theta, radius, 2);
+    let mut out = Vec::with_capacity(segments + 1);
+    for i in 0..=segments {
+        let t = (i as f64) / (segments as f64);
+        let angle = start_angle + theta * t;
+        out.push((
+            center.0 + radius * angle.cos(),
+            center.1 + radius * angle.sin(),
+        ));
+    }
+    if let Some(first) = out.first_mut() {
+        *first = start;
+    }
+    if let Some(last) = out.last_mut() {
+        *last = end;
+    }
+    out
+}
+
+/// Append `segment` to `points`, dropping its first point when it is within
+/// `point_epsilon` of the last one.
+pub fn append_segment_points(
+    points: &mut Vec<(f64, f64)>,
+    segment: &[(f64, f64)],
+    point_epsilon: f64,
+) {
+    if segment.is_empty() {
+        return;
+    }
+    if points.is_empty() {
+        points.extend_from_slice(segment);
+        return;
+    }
+    let mut start = 0usize;
+    if points_equal_2d(*points.last().unwrap(), segment[0], point_epsilon) {
+        start = 1;
+    }
+    points.extend_from_slice(&segment[start..]);
+}
+
+fn normalized_sweep(start_angle: f64, end_angle: f64, is_ccw: bool) -> f64 {
+    let mut sweep = end_angle - start_angle;
+    if is_ccw {
+        if sweep < 0.0 {
+            sweep += std::f64::consts::TAU;
+        }
+    } else if sweep > 0.0 {
+        sweep -= std::f64::consts::TAU;
+    }
+    sweep
+}
+
+/// World XY bounds of a box drawn in the OCS plane of `extrusion` at height
+/// `elevation`. Exact for plan-view (Z) extrusions, a cover otherwise.
+pub fn ocs_bounds_to_wcs(extrusion: Point3, bounds: Bounds2, elevation: f64) -> Bounds2 {
+    let corners = bounds.corners().map(|(x, y)| {
+        let world = ocs_to_wcs(extrusion, (x, y, elevation));
+        (world.0, world.1)
+    });
+    Bounds2::from_points(corners).unwrap_or(bounds)
+}
+
+/// World XY bounds of the full ellipse with WCS `center` and `major_axis`;
+/// the minor axis is `axis_ratio` times the major, perpendicular to it in the
+/// `extrusion` plane.
+pub fn ellipse_bounds(
+    center: Point3,
+    major_axis: Point3,
+    extrusion: Point3,
+    axis_ratio: f64,
+) -> Bounds2 {
+    let major_length =
+        (major_axis.0 * major_axis.0 + major_axis.1 * major_axis.1 + major_axis.2 * major_axis.2)
+            .sqrt();
+    let minor = normalize(cross(extrusion, major_axis))
+        .map(|direction| {
+            let length = major_length * axis_ratio;
+            (
+                direction.0 * length,
+                direction.1 * length,
+                direction.2 * length,
+            )
+        })
+        .unwrap_or((0.0, 0.0, 0.0));
+    let half_x = major_axis.0.hypot(minor.0);
+    let half_y = major_axis.1.hypot(minor.1);
+    Bounds2::new(
+        (center.0 - half_x, center.1 - half_y),
+        (center.0 + half_x, center.1 + half_y),
+    )
+}
+
+fn cross(a: Point3, b: Point3) -> Point3 {
+    (
+        a.1 * b.2 - a.2 * b.1,
+        a.2 * b.0 - a.0 * b.2,
+        a.0 * b.1 - a.1 * b.0,
+    )
+}
+
+fn normalize(v: Point3) -> Option<Point3> {
+    let len = (v.0 * v.0 + v.1 * v.1 + v.2 * v.2).sqrt();
+    if !len.is_finite() || len < 1e-12 {
+        return None;
+    }
+    Some((v.0 / len, v.1 / len, v.2 / len))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::{PI, TAU};
+
+    #[test]
+    fn small_coordinates_use_absolute_tolerance() {
+        assert!(approx_eq(0.0, 5e-10, DEFAULT_POINT_EPSILON));
+        assert!(!approx_eq(0.0, 1e-8, DEFAULT_POINT_EPSILON));
+        assert!(points_equal_2d(
+            (1.0, 2.0),
+            (1.0, 2.0),
+            DEFAULT_POINT_EPSILON
+        ));
+        assert!(!points_equal_3d(
+            (1.0, 2.0, 3.0),
+            (1.0, 2.0, 3.001),
+            DEFAULT_POINT_EPSILON
+        ));
+    }
+
+    #[test]
+    fn large_coordinates_scale_tolerance_with_magnitude() {
+        let x: f64 = 1_234_567.891_234;
+        let noisy = x + 1e-7;
+        assert!((x - noisy).abs() > DEFAULT_POINT_EPSILON);
+        assert!(approx_eq(x, noisy, DEFAULT_POINT_EPSILON));
+        assert!(!approx_eq(x, x + 0.01, DEFAULT_POINT_EPSILON));
+    }
+
+    #[test]
+    fn straight_ribbon_tapers_between_widths() {
+        let outline = bulge_ribbon_2d(
+            (0.0, 0.0),
+            (10.0, 0.0),
+            0.0,
+            2.0,
+            0.0,
+            ArcTessellation::Segments(32),
+        );
+        assert_eq!(
+            outline,
+            vec![(0.0, 1.0), (10.0, 0.0), (10.0, 0.0), (0.0, -1.0)]
+        );
+        assert!(bulge_ribbon_2d(
+            (1.0, 1.0),
+            (1.0, 1.0),
+            0.0,
+            1.0,
+            1.0,
+            ArcTessellation::Segments(32)
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn arc_ribbon_offsets_radially() {
+        // Counter-clockwise semicircle of radius 1 around the origin.
+        let outline = bulge_ribbon_2d(
+            (1.0, 0.0),
+            (-1.0, 0.0),
+            1.0,
+            0.5,
+            0.5,
+            ArcTessellation::Segments(32),
+        );
+        assert_eq!(outline.len(), 2 * 17);
+        for (index, (x, y)) in outline.iter().enumerate() {
+            let expected = if index < 17 { 0.75 } else { 1.25 };
+            assert!(approx_eq(x.hypot(*y), expected, 1e-12), "{index}: {x} {y}");
+            assert!(*y >= -1e-12);
+        }
+    }
+
+    #[test]
+    fn tolerance_mode_scales_chords_with_radius() {
+        let max_sagitta = 0.01;
+        let small = arc_points_by_tolerance((0.0, 0.0), 1.0, 0.0, PI, max_sagitta);
+        let large = arc_points_by_tolerance((0.0, 0.0), 1000.0, 0.0, PI, max_sagitta);
+        assert!(
+            large.len() > 10 * small.len(),
+            "{} {}",
+            small.len(),
+            large.len()
+        );
+        for (radius, points) in [(1.0, &small), (1000.0, &large)] {
+            for pair in points.windows(2) {
+                let mid = ((pair[0].0 + pair[1].0) / 2.0, (pair[0].1 + pair[1].1) / 2.0);
+                assert!(radius - mid.0.hypot(mid.1) <= max_sagitta + 1e-9);
+            }
+        }
+        // A tolerance beyond the diameter still leaves the minimum count.
+        assert_eq!(
+            arc_points_by_tolerance((0.0, 0.0), 1.0, 0.0, PI, 5.0).len(),
+            3
+        );
+        assert_eq!(
+            ArcTessellation::MaxSagitta(0.0).segment_count(TAU, 1.0, 2),
+            DEFAULT_ARC_SEGMENTS
+        );
+        assert_eq!(
+            ArcTessellation::MaxSagitta(1e-12).segment_count(TAU, 1e9, 2),
+            MAX_ARC_SEGMENTS
+        );
+        assert_eq!(
+            ArcTessellation::Segments(usize::MAX).segment_count(TAU, 1.0, 2),
+            MAX_ARC_SEGMENTS
+        );
+    }
+
+    fn assert_point(actual: Point3, expected: Point3) {
+        assert!(
+            points_equal_3d(actual, expected, DEFAULT_POINT_EPSILON),
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    #[test]
+    fn world_z_extrusion_is_identity() {
+        assert_point(
+            ocs_to_wcs((0.0, 0.0, 1.0), (1.0, 2.0, 3.0)),
+            (1.0, 2.0, 3.0),
+        );
+        assert_point(
+            ocs_to_wcs((0.0, 0.0, 0.0), (1.0, 2.0, 3.0)),
+            (1.0, 2.0, 3.0),
+        );
+    }
+
+    #[test]
+    fn mirrored_extrusion_flips_x_and_z() {
+        assert_point(
+            ocs_to_wcs((0.0, 0.0, -1.0), (1.0, 2.0, 3.0)),
+            (-1.0, 2.0, -3.0),
+        );
+    }
+
+    #[test]
+    fn tilted_extrusions_follow_arbitrary_axis_algorithm() {
+        // World X normal: OCS X = world Y, OCS Y = world Z.
+        assert_point(
+            ocs_to_wcs((1.0, 0.0, 0.0), (1.0, 2.0, 3.0)),
+            (3.0, 1.0, 2.0),
+        );
+
+        // Unnormalised 45 degree tilt about world X.
+        let [x_axis, y_axis, z_axis] = arbitrary_axes((0.0, -2.0, 2.0));
+        let h = std::f64::consts::FRAC_1_SQRT_2;
+        assert_point(x_axis, (1.0, 0.0, 0.0));
+        assert_point(y_axis, (0.0, h, h));
+        assert_point(z_axis, (0.0, -h, h));
+        assert_point(
+            ocs_to_wcs((0.0, -2.0, 2.0), (10.0, 4.0, 0.0)),
+            (10.0, 4.0 * h, 4.0 * h),
+        );
+    }
+
+    fn apply(m: &[f64; 16], p: Point3) -> Point3 {
+        (
+            m[0] * p.0 + m[1] * p.1 + m[2] * p.2 + m[3],
+            m[4] * p.0 + m[5] * p.1 + m[6] * p.2 + m[7],
+            m[8] * p.0 + m[9] * p.1 + m[10] * p.2 + m[11],
+        )
+    }
+
+    #[test]
+    fn insert_transform_scales_then_rotates_then_translates() {
+        let m = insert_transform(
+            (100.0, 50.0, 0.0),
+            (2.0, 3.0, 1.0),
+            std::f64::consts::FRAC_PI_2,
+            (0.0, 0.0, 1.0),
+            (0.0, 0.0, 0.0),
+        );
+        assert_point(apply(&m, (1.0, 0.0, 0.0)), (100.0, 52.0, 0.0));
+        assert_point(apply(&m, (0.0, 1.0, 0.0)), (97.0, 50.0, 0.0));
+        assert_eq!(m[12..], [0.0, 0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn insert_transform_places_mirrored_ocs_inserts() {
+        let m = insert_transform(
+            (10.0, 0.0, 0.0),
+            (1.0, 1.0, 1.0),
+            0.0,
+            (0.0, 0.0, -1.0),
+            (0.0, 0.0, 0.0),
+        );
+        // Insertion point is OCS too: world X is mirrored.
+        assert_point(apply(&m, (0.0, 0.0, 0.0)), (-10.0, 0.0, 0.0));
+        assert_point(apply(&m, (1.0, 2.0, 0.0)), (-11.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn insert_transform_moves_the_base_point_onto_the_insertion_point() {
+        let m = insert_transform(
+            (100.0, 50.0, 0.0),
+            (2.0, 2.0, 1.0),
+            std::f64::consts::FRAC_PI_2,
+            (0.0, 0.0, 1.0),
+            (1.0, 2.0, 0.0),
+        );
+        assert_point(apply(&m, (1.0, 2.0, 0.0)), (100.0, 50.0, 0.0));
+        // One unit along block X from the base: scaled by 2, turned onto +Y.
+        assert_point(apply(&m, (2.0, 2.0, 0.0)), (100.0, 52.0, 0.0));
+    }
+
+    fn chain_points(chain: &SegmentChain) -> Vec<(f64, f64)> {
+        chain.points.iter().map(|p| (p.0, p.1)).collect()
+    }
+
+    #[test]
+    fn chains_scrambled_segments_in_either_direction() {
+        let segments = [
+            ((2.0, 0.0, 0.0), (3.0, 0.0, 0.0)),
+            ((0.0, 0.0, 0.0), (1.0, 0.0, 0.0)),
+            ((2.0, 0.0, 0.0), (1.0, 0.0, 1e-12)),
+            ((10.0, 0.0, 0.0), (11.0, 0.0, 0.0)),
+        ];
+        let chains = chain_segments(&segments, DEFAULT_POINT_EPSILON);
+        assert_eq!(chains.len(), 2);
+        assert_eq!(chains[0].segments, vec![0, 2, 1]);
+        assert_eq!(
+            chain_points(&chains[0]),
+            vec![(3.0, 0.0), (2.0, 0.0), (1.0, 0.0), (0.0, 0.0)]
+        );
+        assert_eq!(chains[1].segments, vec![3]);
+    }
+
+    #[test]
+    fn closes_loops_and_splits_at_junctions() {
+        let square = [
+            ((0.0, 0.0, 0.0), (1.0, 0.0, 0.0)),
+            ((1.0, 1.0, 0.0), (1.0, 0.0, 0.0)),
+            ((1.0, 1.0, 0.0), (0.0, 1.0, 0.0)),
+            ((0.0, 1.0, 0.0), (0.0, 0.0, 0.0)),
+        ];
+        let chains = chain_segments(&square, DEFAULT_POINT_EPSILON);
+        assert_eq!(chains.len(), 1);
+        assert_eq!(chains[0].segments.len(), 4);
+        assert_eq!(chains[0].points.first(), chains[0].points.last());
+
+        // Three spokes meeting at the origin stay separate.
+        let star = [
+            ((0.0, 0.0, 0.0), (1.0, 0.0, 0.0)),
+            ((0.0, 0.0, 0.0), (0.0, 1.0, 0.0)),
+            ((0.0, 0.0, 0.0), (-1.0, 0.0, 0.0)),
+            ((5.0, 5.0, 0.0), (5.0, 5.0, 0.0)),
+        ];
+        let chains = chain_segments(&star, DEFAULT_POINT_EPSILON);
+        assert_eq!(chains.len(), 3);
+        assert!(chains.iter().all(|chain| chain.segments.len() == 1));
+    }
+
+    #[test]
+    fn tolerance_controls_gap_closing() {
+        let segments = [
+            ((0.0, 0.0, 0.0), (1.0, 0.0, 0.0)),
+            ((1.001, 0.0, 0.0), (2.0, 0.0, 0.0)),
+        ];
+        assert_eq!(chain_segments(&segments, DEFAULT_POINT_EPSILON).len(), 2);
+        let merged = chain_segments(&segments, 0.01);
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].segments, vec![0, 1]);
+    }
+
+    #[test]
+    fn tolerance_is_an_absolute_distance() {
+        // A survey-sized outline: each joint is off by a millimetre, which a
+        // relative epsilon would scale up by the 1e6 coordinates.
+        let base = 1.0e6;
+        let corners = [
+            (base, base, 0.0),
+            (base + 10.0, base, 0.0),
+            (base + 10.0, base + 10.0, 0.0),
+            (base, base + 10.0, 0.0),
+        ];
+        let segments: Vec<(Point3, Point3)> = (0..4)
+            .map(|i| {
+                let (a, b) = (corners[i], corners[(i + 1) % 4]);
+                ((a.0 + 0.001, a.1, a.2), b)
+            })
+            .collect();
+        let chains = chain_segments(&segments, 0.01);
+        assert_eq!(chains.len(), 1);
+        assert_eq!(chains[0].segments.len(), 4);
+        assert_eq!(chains[0].points.first(), chains[0].points.last());
+
+        // Gaps wider than the tolerance stay open however large the
+        // coordinates are.
+        let chains = chain_segments(&segments, 0.0005);
+        assert_eq!(chains.len(), 4);
+
+        let spread = [
+            ((base, 0.0, 0.0), (base + 1.0, 0.0, 0.0)),
+            ((base + 1.5, 0.0, 0.0), (base + 2.0, 0.0, 0.0)),
+            ((base + 2.0, 0.0, 0.0), (base + 3.0, 0.0, 0.0)),
+        ];
+        let chains = chain_segments(&spread, 0.1);
+        assert_eq!(chains.len(), 2);
+        assert!(chains
+            .iter()
+            .any(|chain| chain.segments == vec![1, 2] || chain.segments == vec![2, 1]));
+    }
+
+    fn assert_bounds_close(actual: Bounds2, min: (f64, f64), max: (f64, f64)) {
+        let close = |a: (f64, f64), b: (f64, f64)| points_equal_2d(a, b, 1e-12);
+        assert!(
+            close(actual.min, min) && close(actual.max, max),
+            "{actual:?} != {min:?}..{max:?}"
+        );
+    }
+
+    #[test]
+    fn arc_bounds_include_crossed_axis_extremes() {
+        use std::f64::consts::{FRAC_PI_2, PI};
+        // First quadrant only: no extremes crossed.
+        assert_bounds_close(
+            arc_bounds_2d((0.0, 0.0), 1.0, 0.1, FRAC_PI_2 - 0.1),
+            (0.1f64.sin(), 0.1f64.sin()),
+            (0.1f64.cos(), 0.1f64.cos()),
+        );
+        // 45 to 225 degrees passes 90 and 180.
+        let r = std::f64::consts::FRAC_1_SQRT_2;
+        assert_bounds_close(
+            arc_bounds_2d((10.0, 0.0), 1.0, PI / 4.0, 5.0 * PI / 4.0),
+            (9.0, -r),
+            (10.0 + r, 1.0),
+        );
+        // Wrapping through 0 from 270 to 90 degrees.
+        assert_bounds_close(
+            arc_bounds_2d((0.0, 0.0), 2.0, -FRAC_PI_2, FRAC_PI_2),
+            (0.0, -2.0),
+            (2.0, 2.0),
+        );
+        assert_bounds_close(
+            arc_bounds_2d((0.0, 0.0), 1.0, 1.0, 1.0),
+            (-1.0, -1.0),
+            (1.0, 1.0),
+        );
+    }
+
+    #[test]
+    fn bulge_bounds_follow_arc_side() {
+        // Semicircles from (0,0) to (2,0): CCW dips below, CW rises above.
+        assert_bounds_close(
+            bulge_bounds_2d((0.0, 0.0), (2.0, 0.0), 1.0),
+            (0.0, -1.0),
+            (2.0, 0.0),
+        );
+        assert_bounds_close(
+            bulge_bounds_2d((0.0, 0.0), (2.0, 0.0), -1.0),
+            (0.0, 0.0),
+            (2.0, 1.0),
+        );
+        assert_bounds_close(
+            bulge_bounds_2d((0.0, 0.0), (2.0, 3.0), 0.0),
+            (0.0, 0.0),
+            (2.0, 3.0),
+        );
+    }
+
+    #[test]
+    fn ellipse_and_ocs_bounds_cover_rotated_shapes() {
+        // Major axis along the diagonal, ratio 1: a circle of radius sqrt(2).
+        let root2 = 2.0f64.sqrt();
+        assert_bounds_close(
+            ellipse_bounds((0.0, 0.0, 0.0), (1.0, 1.0, 0.0), (0.0, 0.0, 1.0), 1.0),
+            (-root2, -root2),
+            (root2, root2),
+        );
+        assert_bounds_close(
+            ellipse_bounds((5.0, 5.0, 0.0), (2.0, 0.0, 0.0), (0.0, 0.0, 1.0), 0.5),
+            (3.0, 4.0),
+            (7.0, 6.0),
+        );
+        // A -Z extrusion mirrors OCS X.
+        assert_bounds_close(
+            ocs_bounds_to_wcs((0.0, 0.0, -1.0), Bounds2::new((1.0, 0.0), (3.0, 2.0)), 0.0),
+            (-3.0, 0.0),
+            (-1.0, 2.0),
+        );
+    }
+}
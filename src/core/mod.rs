@@ -1,3 +1,9 @@
+pub mod color;
 pub mod config;
+pub mod crc;
+pub mod diag;
 pub mod error;
+pub mod geometry;
+pub mod progress;
 pub mod result;
+pub mod spatial;
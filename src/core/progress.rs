@@ -0,0 +1,124 @@
+//! Throttled progress reporting for whole-file object walks.
+
+use crate::core::error::{DwgError, ErrorKind};
+use crate::core::result::Result;
+
+/// Calls back with `(done, total)` at the start of a walk, about every 1% of
+/// `total` after that, and once more at the end. The callback returns
+/// `false` to stop the walk; the caller then returns what it has so far.
+pub struct Progress<'a> {
+    callback: Option<Box<dyn FnMut(usize, usize) -> bool + 'a>>,
+    next_report: usize,
+    stopped: bool,
+}
+
+impl<'a> Progress<'a> {
+    /// A reporter that never calls back and never stops.
+    pub fn none() -> Self {
+        Self {
+            callback: None,
+            next_report: 0,
+            stopped: false,
+        }
+    }
+
+    pub fn new(callback: impl FnMut(usize, usize) -> bool + 'a) -> Self {
+        Self {
+            callback: Some(Box::new(callback)),
+            next_report: 0,
+            stopped: false,
+        }
+    }
+
+    /// Note that `done` of `total` objects have been processed. Returns
+    /// `false` once the callback has asked to stop.
+    pub fn tick(&mut self, done: usize, total: usize) -> bool {
+        if self.stopped {
+            return false;
+        }
+        let Some(callback) = self.callback.as_mut() else {
+            return true;
+        };
+        if done < self.next_report && done < total {
+            return true;
+        }
+        self.next_report = done + total.div_ceil(100).max(1);
+        if !callback(done, total) {
+            self.stopped = true;
+        }
+        !self.stopped
+    }
+
+    /// [`tick`](Self::tick) for walks that fail rather than return partial
+    /// results: a stop request becomes a `Cancelled` error.
+    pub fn check(&mut self, done: usize, total: usize) -> Result<()> {
+        if self.tick(done, total) {
+            return Ok(());
+        }
+        Err(DwgError::new(
+            ErrorKind::Cancelled,
+            format!("cancelled after {done} of {total} objects"),
+        ))
+    }
+
+    /// Report the end of a walk that was not stopped.
+    pub fn finish(&mut self, total: usize) {
+        if !self.stopped {
+            self.tick(total, total);
+        }
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.stopped
+    }
+}
+
+impl Default for Progress<'_> {
+    fn default() -> Self {
+        Self::none()
+    }
+}
+
+impl std::fmt::Debug for Progress<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Progress")
+            .field("callback", &self.callback.is_some())
+            .field("stopped", &self.stopped)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_about_every_percent_and_stops_on_false() {
+        let mut calls = Vec::new();
+        let mut progress = Progress::new(|done, total| {
+            calls.push((done, total));
+            true
+        });
+        for done in 0..250 {
+            assert!(progress.tick(done, 250));
+        }
+        progress.finish(250);
+        drop(progress);
+        assert_eq!(calls.first(), Some(&(0, 250)));
+        assert_eq!(calls.last(), Some(&(250, 250)));
+        assert_eq!(calls.len(), 85);
+
+        let mut seen = 0;
+        let mut progress = Progress::new(|done, _| {
+            seen = done;
+            done < 3
+        });
+        let stopped_at = (0..10).find(|&done| !progress.tick(done, 10));
+        progress.finish(10);
+        assert!(progress.is_stopped());
+        let err = progress.check(4, 10).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Cancelled);
+        drop(progress);
+        assert_eq!((stopped_at, seen), (Some(3), 3));
+    }
+}
@@ -0,0 +1,195 @@
+//! Uniform-grid index over XY bounding boxes, for "what is inside this
+//! rectangle" queries on decoded entities.
+
+use crate::core::geometry::Bounds2;
+
+/// Upper bound on grid columns (and rows).
+const MAX_GRID_SIDE: usize = 512;
+/// Items covering more cells than this are kept in a list checked by every
+/// query instead of being copied into each cell.
+const MAX_CELLS_PER_ITEM: usize = 64;
+
+#[derive(Debug, Clone)]
+pub struct GridIndex<T> {
+    items: Vec<(T, Bounds2)>,
+    extent: Option<Bounds2>,
+    side: usize,
+    cell_size: (f64, f64),
+    cells: Vec<Vec<usize>>,
+    oversized: Vec<usize>,
+}
+
+impl<T> GridIndex<T> {
+    /// Index `items`; entries with non-finite bounds are dropped.
+    pub fn new(items: impl IntoIterator<Item = (T, Bounds2)>) -> Self {
+        let items: Vec<(T, Bounds2)> = items
+            .into_iter()
+            .filter(|(_, bounds)| {
+                [bounds.min.0, bounds.min.1, bounds.max.0, bounds.max.1]
+                    .iter()
+                    .all(|value| value.is_finite())
+            })
+            .collect();
+        let extent = items
+            .iter()
+            .map(|(_, bounds)| *bounds)
+            .reduce(Bounds2::union);
+        let side = ((items.len() as f64).sqrt().ceil() as usize).clamp(1, MAX_GRID_SIDE);
+        let cell_size = extent
+            .map(|extent| {
+                let size = |span: f64| if span > 0.0 { span / side as f64 } else { 1.0 };
+                (
+                    size(extent.max.0 - extent.min.0),
+                    size(extent.max.1 - extent.min.1),
+                )
+            })
+            .unwrap_or((1.0, 1.0));
+        let mut index = Self {
+            items: Vec::new(),
+            extent,
+            side,
+            cell_size,
+            cells: vec![Vec::new(); side * side],
+            oversized: Vec::new(),
+        };
+        for (position, (_, bounds)) in items.iter().enumerate() {
+            let (columns, rows) = index.cell_range(bounds);
+            if columns.clone().count() * rows.clone().count() > MAX_CELLS_PER_ITEM {
+                index.oversized.push(position);
+                continue;
+            }
+            for row in rows {
+                for column in columns.clone() {
+                    index.cells[row * side + column].push(position);
+                }
+            }
+        }
+        index.items = items;
+        index
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Items whose bounds intersect (or touch) `area`, in insertion order.
+    pub fn query(&self, area: &Bounds2) -> Vec<&T> {
+        let Some(extent) = self.extent else {
+            return Vec::new();
+        };
+        if !extent.intersects(area) {
+            return Vec::new();
+        }
+        let (columns, rows) = self.cell_range(area);
+        let mut candidates = self.oversized.clone();
+        for row in rows {
+            for column in columns.clone() {
+                candidates.extend_from_slice(&self.cells[row * self.side + column]);
+            }
+        }
+        candidates.sort_unstable();
+        candidates.dedup();
+        candidates
+            .into_iter()
+            .map(|position| &self.items[position])
+            .filter(|(_, bounds)| bounds.intersects(area))
+            .map(|(item, _)| item)
+            .collect()
+    }
+
+    /// Column and row ranges of the cells `bounds` covers, clamped to the grid.
+    fn cell_range(
+        &self,
+        bounds: &Bounds2,
+    ) -> (
+        std::ops::RangeInclusive<usize>,
+        std::ops::RangeInclusive<usize>,
+    ) {
+        let origin = self.extent.map(|extent| extent.min).unwrap_or((0.0, 0.0));
+        let last = self.side - 1;
+        let cell = |value: f64, origin: f64, size: f64| -> usize {
+            let position = ((value - origin) / size).floor();
+            if position <= 0.0 {
+                0
+            } else {
+                (position as usize).min(last)
+            }
+        };
+        (
+            cell(bounds.min.0, origin.0, self.cell_size.0)
+                ..=cell(bounds.max.0, origin.0, self.cell_size.0),
+            cell(bounds.min.1, origin.1, self.cell_size.1)
+                ..=cell(bounds.max.1, origin.1, self.cell_size.1),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random boxes (64-bit LCG).
+    fn boxes(count: usize) -> Vec<(usize, Bounds2)> {
+        let mut state = 0x2545_F491_4F6C_DD1Du64;
+        let mut next = move || {
+            state = state
+                .wrapping_mul(6_364_136_223_846_793_005)
+                .wrapping_add(1_442_695_040_888_963_407);
+            (state >> 11) as f64 / (1u64 << 53) as f64
+        };
+        (0..count)
+            .map(|id| {
+                let (x, y) = (next() * 1000.0, next() * 1000.0);
+                let (w, h) = (next() * 30.0, next() * 30.0);
+                (id, Bounds2::new((x, y), (x + w, y + h)))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn matches_brute_force() {
+        let items = boxes(2000);
+        let index = GridIndex::new(items.clone());
+        assert_eq!(index.len(), 2000);
+        for area in [
+            Bounds2::new((100.0, 100.0), (180.0, 140.0)),
+            Bounds2::new((-50.0, -50.0), (5.0, 5.0)),
+            Bounds2::new((500.0, 0.0), (500.0, 1000.0)),
+            Bounds2::new((-10.0, -10.0), (2000.0, 2000.0)),
+        ] {
+            let expected: Vec<usize> = items
+                .iter()
+                .filter(|(_, bounds)| bounds.intersects(&area))
+                .map(|(id, _)| *id)
+                .collect();
+            let found: Vec<usize> = index.query(&area).into_iter().copied().collect();
+            assert_eq!(found, expected, "{area:?}");
+        }
+    }
+
+    #[test]
+    fn large_and_degenerate_items_are_found() {
+        let index = GridIndex::new([
+            ("sheet", Bounds2::new((0.0, 0.0), (100.0, 100.0))),
+            ("point", Bounds2::new((50.0, 50.0), (50.0, 50.0))),
+            (
+                "broken",
+                Bounds2 {
+                    min: (f64::NAN, 0.0),
+                    max: (1.0, 1.0),
+                },
+            ),
+        ]);
+        assert_eq!(index.len(), 2);
+        let area = Bounds2::new((49.0, 49.0), (51.0, 51.0));
+        assert_eq!(index.query(&area), vec![&"sheet", &"point"]);
+        assert!(index
+            .query(&Bounds2::new((200.0, 200.0), (300.0, 300.0)))
+            .is_empty());
+        assert!(GridIndex::<u64>::new([]).query(&area).is_empty());
+    }
+}
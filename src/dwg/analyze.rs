@@ -0,0 +1,273 @@
+//! Non-fatal structural checks over a DWG file.
+//!
+//! Each parser already rejects the problems below as hard errors; `analyze`
+//! runs them independently and collects what went wrong into one report so a
+//! damaged file can be triaged without stopping at the first failure.
+
+use crate::core::config::ParseConfig;
+use crate::core::error::{DwgError, ErrorKind};
+use crate::dwg::decoder::Decoder;
+use crate::objects::ObjectRef;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnomalyKind {
+    /// A stage failed outright; the message carries the parser error.
+    ParseError,
+    SectionSize,
+    ObjectOffset,
+    HandleGap,
+    DuplicateHandle,
+    EmptyClassName,
+    SentinelMismatch,
+}
+
+impl AnomalyKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ParseError => "parse_error",
+            Self::SectionSize => "section_size",
+            Self::ObjectOffset => "object_offset",
+            Self::HandleGap => "handle_gap",
+            Self::DuplicateHandle => "duplicate_handle",
+            Self::EmptyClassName => "empty_class_name",
+            Self::SentinelMismatch => "sentinel_mismatch",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Anomaly {
+    pub kind: AnomalyKind,
+    pub message: String,
+    pub offset: Option<u64>,
+}
+
+impl Anomaly {
+    fn new(kind: AnomalyKind, message: impl Into<String>, offset: Option<u64>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            offset,
+        }
+    }
+
+    fn from_error(stage: &str, err: &DwgError) -> Self {
+        let kind = if err.kind == ErrorKind::SentinelMismatch {
+            AnomalyKind::SentinelMismatch
+        } else {
+            AnomalyKind::ParseError
+        };
+        Self::new(kind, format!("{stage}: {err}"), err.offset)
+    }
+}
+
+/// Like [`analyze`], but also reports files the decoder cannot open at all.
+pub fn analyze_bytes(bytes: &[u8], config: ParseConfig) -> Vec<Anomaly> {
+    match Decoder::new(bytes, config) {
+        Ok(decoder) => analyze(&decoder),
+        Err(err) => vec![Anomaly::from_error("decoder", &err)],
+    }
+}
+
+pub fn analyze(decoder: &Decoder<'_>) -> Vec<Anomaly> {
+    let mut report = Vec::new();
+    if let Err(err) = decoder.ensure_supported() {
+        report.push(Anomaly::from_error("version", &err));
+        return report;
+    }
+
+    match decoder.section_directory() {
+        Ok(directory) if !directory.sentinel_ok => report.push(Anomaly::new(
+            AnomalyKind::SentinelMismatch,
+            "section locator sentinel mismatch",
+            None,
+        )),
+        Ok(_) => {}
+        Err(err) => report.push(Anomaly::from_error("section directory", &err)),
+    }
+
+    let max_section_bytes = decoder.config().max_section_bytes;
+    match decoder.list_sections() {
+        Ok(sections) => {
+            for section in sections.iter().filter(|s| s.size > max_section_bytes) {
+                report.push(Anomaly::new(
+                    AnomalyKind::SectionSize,
+                    format!(
+                        "section {} size {} exceeds limit {}",
+                        section.name, section.size, max_section_bytes
+                    ),
+                    None,
+                ));
+            }
+        }
+        Err(err) => report.push(Anomaly::from_error("sections", &err)),
+    }
+
+    match decoder.dynamic_type_map() {
+        Ok(map) => {
+            let mut codes: Vec<u16> = map
+                .iter()
+                .filter(|(_, name)| name.trim().is_empty())
+                .map(|(code, _)| *code)
+                .collect();
+            codes.sort_unstable();
+            for code in codes {
+                report.push(Anomaly::new(
+                    AnomalyKind::EmptyClassName,
+                    format!("class {code} has an empty DXF name"),
+                    None,
+                ));
+            }
+        }
+        Err(err) => report.push(Anomaly::from_error("classes", &err)),
+    }
+
+    // The offset check needs the map as stored: the object index drops the
+    // entries whose records fail to parse, which includes every entry that
+    // points outside the objects data.
+    match decoder.object_map() {
+        Ok(map) => {
+            match decoder.objects_data_len() {
+                Ok(len) => report.extend(offset_anomalies(&map.objects, len)),
+                Err(err) => report.push(Anomaly::from_error("objects section", &err)),
+            }
+
+            let handles = map.objects.iter().map(|o| o.handle.0).collect();
+            report.extend(handle_anomalies(handles));
+        }
+        Err(err) => report.push(Anomaly::from_error("object map", &err)),
+    }
+
+    report
+}
+
+/// Object map entries that point at or past the end of `len` bytes of
+/// objects data.
+fn offset_anomalies(objects: &[ObjectRef], len: usize) -> Vec<Anomaly> {
+    objects
+        .iter()
+        .filter(|object| object.offset as usize >= len)
+        .map(|object| {
+            Anomaly::new(
+                AnomalyKind::ObjectOffset,
+                format!(
+                    "handle {:#X} offset {:#X} is outside the objects data ({len} bytes)",
+                    object.handle.0, object.offset
+                ),
+                Some(object.offset as u64),
+            )
+        })
+        .collect()
+}
+
+/// Duplicate handles (one anomaly per handle) and gaps in the sorted map.
+fn handle_anomalies(mut handles: Vec<u64>) -> Vec<Anomaly> {
+    let mut report = Vec::new();
+    handles.sort_unstable();
+    for (idx, pair) in handles.windows(2).enumerate() {
+        let (prev, next) = (pair[0], pair[1]);
+        if prev == next {
+            if idx > 0 && handles[idx - 1] == prev {
+                continue;
+            }
+            report.push(Anomaly::new(
+                AnomalyKind::DuplicateHandle,
+                format!("handle {prev:#X} appears more than once in the object map"),
+                None,
+            ));
+        } else if next > prev + 1 {
+            report.push(Anomaly::new(
+                AnomalyKind::HandleGap,
+                format!(
+                    "handles {:#X}..={:#X} missing ({} handles)",
+                    prev + 1,
+                    next - 1,
+                    next - prev - 1
+                ),
+                None,
+            ));
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_r2000_sentinel_mismatch() {
+        let mut bytes = std::fs::read("test_dwg/line_2000.dwg").expect("sample file");
+        let clean = analyze_bytes(&bytes, ParseConfig::default());
+        assert!(clean
+            .iter()
+            .all(|anomaly| anomaly.kind != AnomalyKind::SentinelMismatch));
+
+        // The section locator sentinel follows the 0x15-byte header, the
+        // 9-byte locator records and their CRC.
+        let record_count = u32::from_le_bytes(bytes[0x15..0x19].try_into().unwrap()) as usize;
+        let sentinel_offset = 0x19 + record_count * 9 + 2;
+        bytes[sentinel_offset] ^= 0xFF;
+        let report = analyze_bytes(&bytes, ParseConfig::default());
+        assert!(report
+            .iter()
+            .any(|anomaly| anomaly.kind == AnomalyKind::SentinelMismatch));
+    }
+
+    #[test]
+    fn reports_offsets_outside_the_objects_data() {
+        let objects =
+            [(0x10, 0x00), (0x11, 0x80), (0x12, 0x100)].map(|(handle, offset)| ObjectRef {
+                handle: crate::objects::Handle(handle),
+                offset,
+            });
+        let report = offset_anomalies(&objects, 0x100);
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].kind, AnomalyKind::ObjectOffset);
+        assert_eq!(report[0].offset, Some(0x100));
+        assert!(report[0].message.contains("0x12"));
+
+        // R2004+ indexes drop entries whose records do not parse; the map
+        // the check runs on keeps them.
+        let bytes = std::fs::read("test_dwg/line_2004.dwg").expect("sample file");
+        let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+        let map = decoder.object_map().unwrap();
+        assert!(map.objects.len() >= decoder.build_object_index().unwrap().objects.len());
+    }
+
+    #[test]
+    fn classifies_sentinel_errors_by_kind() {
+        let err = DwgError::new(ErrorKind::SentinelMismatch, "AcDb:Classes marker");
+        assert_eq!(
+            Anomaly::from_error("classes", &err).kind,
+            AnomalyKind::SentinelMismatch
+        );
+        let err = DwgError::new(ErrorKind::Format, "sentinel-like text");
+        assert_eq!(
+            Anomaly::from_error("classes", &err).kind,
+            AnomalyKind::ParseError
+        );
+    }
+
+    #[test]
+    fn reports_duplicate_handles_once_per_handle() {
+        let report = handle_anomalies(vec![0x10, 0x12, 0x11, 0x12, 0x12, 0x15, 0x15]);
+        let kinds: Vec<AnomalyKind> = report.iter().map(|anomaly| anomaly.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                AnomalyKind::DuplicateHandle,
+                AnomalyKind::HandleGap,
+                AnomalyKind::DuplicateHandle,
+            ]
+        );
+        assert!(report[0].message.contains("0x12"));
+        assert!(report[1].message.contains("0x13..=0x14"));
+
+        // The sample's map has none once handle deltas are read unsigned.
+        let bytes = std::fs::read("test_dwg/line_2007.dwg").expect("sample file");
+        assert!(analyze_bytes(&bytes, ParseConfig::default())
+            .iter()
+            .all(|anomaly| anomaly.kind != AnomalyKind::DuplicateHandle));
+    }
+}
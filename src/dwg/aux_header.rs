@@ -0,0 +1,148 @@
+//! `AcDb:AuxHeader`: save counters, version stamps and timestamps.
+//!
+//! Stored as locator record 5 in R2000 files and as a named section from
+//! R2004 on. R14 files have no aux header.
+
+use crate::core::error::{DwgError, ErrorKind};
+use crate::core::result::Result;
+use crate::dwg::version::DwgVersion;
+use crate::io::ByteReader;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuxHeader {
+    /// Format code of the release that last saved the file
+    /// (e.g. 23 = AC1015, 25 = AC1018, 31 = AC1027, 33 = AC1032).
+    pub dwg_version: u16,
+    pub maintenance_version: u32,
+    /// Format and maintenance codes of the release that created the file.
+    pub created_version: (u16, u32),
+    pub save_count: u32,
+    /// TDCREATE / TDUPDATE as `(julian_day, milliseconds_since_midnight)`.
+    pub created: (u32, u32),
+    pub updated: (u32, u32),
+    /// HANDSEED, or `None` when it did not fit (stored as -1).
+    pub handle_seed: Option<u32>,
+}
+
+impl AuxHeader {
+    /// `created` as a Julian date with a day fraction.
+    pub fn created_julian(&self) -> f64 {
+        julian_date(self.created)
+    }
+
+    /// `updated` as a Julian date with a day fraction.
+    pub fn updated_julian(&self) -> f64 {
+        julian_date(self.updated)
+    }
+}
+
+fn julian_date((day, millis): (u32, u32)) -> f64 {
+    f64::from(day) + f64::from(millis) / 86_400_000.0
+}
+
+pub fn parse_aux_header(data: &[u8], version: &DwgVersion) -> Result<AuxHeader> {
+    // R2018 widened the maintenance version fields to RL.
+    let wide_maintenance = matches!(version, DwgVersion::R2018);
+    let mut reader = ByteReader::new(data);
+    let magic = reader.read_bytes(3)?;
+    if magic[1..] != [0x77, 0x01] {
+        return Err(DwgError::new(
+            ErrorKind::Format,
+            format!("invalid AuxHeader signature: {magic:02X?}"),
+        ));
+    }
+    let read_maintenance = |reader: &mut ByteReader<'_>| -> Result<u32> {
+        if wide_maintenance {
+            reader.read_u32_le()
+        } else {
+            reader.read_u16_le().map(u32::from)
+        }
+    };
+
+    let dwg_version = reader.read_u16_le()?;
+    let maintenance_version = read_maintenance(&mut reader)?;
+    let save_count = reader.read_u32_le()?;
+    let _minus_one = reader.read_u32_le()?;
+    let _save_count_low = reader.read_u16_le()?;
+    let _save_count_high = reader.read_u16_le()?;
+    let _zero = reader.read_u32_le()?;
+    let _saved_version = reader.read_u16_le()?;
+    let _saved_maintenance = read_maintenance(&mut reader)?;
+    let created_version = (reader.read_u16_le()?, read_maintenance(&mut reader)?);
+    // Six RS writer constants, then five RL.
+    reader.skip(6 * 2 + 5 * 4)?;
+    let created = (reader.read_u32_le()?, reader.read_u32_le()?);
+    let updated = (reader.read_u32_le()?, reader.read_u32_le()?);
+    let handle_seed = match reader.read_u32_le()? {
+        u32::MAX => None,
+        seed => Some(seed),
+    };
+
+    Ok(AuxHeader {
+        dwg_version,
+        maintenance_version,
+        created_version,
+        save_count,
+        created,
+        updated,
+        handle_seed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(wide: bool) -> Vec<u8> {
+        let maintenance = |out: &mut Vec<u8>, value: u32| {
+            if wide {
+                out.extend_from_slice(&value.to_le_bytes());
+            } else {
+                out.extend_from_slice(&(value as u16).to_le_bytes());
+            }
+        };
+        let mut out = vec![0xFF, 0x77, 0x01];
+        out.extend_from_slice(&33u16.to_le_bytes());
+        maintenance(&mut out, 329);
+        out.extend_from_slice(&158u32.to_le_bytes());
+        out.extend_from_slice(&u32::MAX.to_le_bytes());
+        out.extend_from_slice(&[158, 0, 0, 0, 0, 0, 0, 0]);
+        out.extend_from_slice(&33u16.to_le_bytes());
+        maintenance(&mut out, 329);
+        out.extend_from_slice(&22u16.to_le_bytes());
+        maintenance(&mut out, 46);
+        out.extend_from_slice(&[0; 32]);
+        for value in [2_459_632u32, 37_383_000, 2_461_012, 34_039_000, 0xE64] {
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+        out.extend_from_slice(&[0; 40]);
+        out
+    }
+
+    #[test]
+    fn parses_r2018_wide_maintenance_fields() {
+        let header = parse_aux_header(&sample(true), &DwgVersion::R2018).unwrap();
+        assert_eq!(header.dwg_version, 33);
+        assert_eq!(header.maintenance_version, 329);
+        assert_eq!(header.created_version, (22, 46));
+        assert_eq!(header.save_count, 158);
+        assert_eq!(header.created, (2_459_632, 37_383_000));
+        assert_eq!(header.updated, (2_461_012, 34_039_000));
+        assert_eq!(header.handle_seed, Some(0xE64));
+        assert!((header.updated_julian() - 2_461_012.393_969).abs() < 1e-6);
+    }
+
+    #[test]
+    fn parses_narrow_fields_before_r2018() {
+        let header = parse_aux_header(&sample(false), &DwgVersion::R2013).unwrap();
+        assert_eq!(header.created_version, (22, 46));
+        assert_eq!(header.handle_seed, Some(0xE64));
+    }
+
+    #[test]
+    fn rejects_other_data() {
+        let mut data = sample(false);
+        data[1] = 0;
+        assert!(parse_aux_header(&data, &DwgVersion::R2013).is_err());
+    }
+}
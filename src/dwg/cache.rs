@@ -0,0 +1,379 @@
+//! On-disk cache of the parsed object map and decompressed objects section,
+//! so reopening an unchanged drawing skips both.
+//!
+//! Entries are keyed by the canonical file path plus its modification time
+//! and size; any change to either makes the entry stale and the next open
+//! rewrites it. Entries record state produced with `retain_objects_buffer`
+//! and are only reused by decoders with the same setting and the same
+//! [`ParseConfig`] fields that shape that state.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::core::config::ParseConfig;
+use crate::core::diag::diag_event;
+use crate::core::error::{DwgError, ErrorKind};
+use crate::core::result::Result;
+use crate::dwg::decoder::Decoder;
+use crate::io::ByteReader;
+use crate::objects::{Handle, ObjectRef};
+
+const MAGIC: &[u8; 8] = b"EZDWGC02";
+
+/// Identity of a file on disk and of the configuration it is decoded with.
+/// Two keys are equal only if the file has not been rewritten in between.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CacheKey {
+    pub path: PathBuf,
+    pub mtime_nanos: u128,
+    pub size: u64,
+    /// Hash of the `ParseConfig` fields the object map and objects section
+    /// depend on; see [`config_fingerprint`].
+    pub config_fingerprint: u64,
+}
+
+impl CacheKey {
+    pub fn for_file(path: impl AsRef<Path>, config: &ParseConfig) -> Result<Self> {
+        let path = fs::canonicalize(path.as_ref())?;
+        let metadata = fs::metadata(&path)?;
+        let mtime_nanos = metadata
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_nanos())
+            .unwrap_or(0);
+        Ok(Self {
+            path,
+            mtime_nanos,
+            size: metadata.len(),
+            config_fingerprint: config_fingerprint(config),
+        })
+    }
+
+    /// Entry file name: a stable hash of the path and configuration.
+    /// Collisions are harmless because the full key is stored and compared
+    /// on load.
+    fn file_name(&self) -> String {
+        let hash = fnv1a(self.path.to_string_lossy().bytes());
+        format!("{hash:016x}-{:016x}.ezdwgcache", self.config_fingerprint)
+    }
+}
+
+/// Hash of the fields that change what loading the object map and objects
+/// section produces or rejects. Limits count: state cached under a generous
+/// limit must not let a stricter decoder skip its checks. So does
+/// `scan_recovery`, whose rebuilt index a plain decoder would not produce.
+pub fn config_fingerprint(config: &ParseConfig) -> u64 {
+    let mut fields = Vec::with_capacity(14);
+    fields.push(u8::from(config.strict));
+    fields.push(u8::from(config.scan_recovery));
+    fields.extend(config.max_objects.to_le_bytes());
+    fields.extend(config.max_section_bytes.to_le_bytes());
+    fnv1a(fields)
+}
+
+/// FNV-1a, which unlike `DefaultHasher` is stable across releases.
+fn fnv1a(bytes: impl IntoIterator<Item = u8>) -> u64 {
+    bytes
+        .into_iter()
+        .fold(0xCBF2_9CE4_8422_2325u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01B3)
+        })
+}
+
+/// Decoder state worth keeping between processes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CachedDecode {
+    /// Object map entries in map order.
+    pub objects: Vec<ObjectRef>,
+    /// Decompressed `AcDb:AcDbObjects` section; `None` for R13-R15 files,
+    /// whose records are read in place.
+    pub objects_data: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DecodeCache {
+    dir: PathBuf,
+}
+
+impl DecodeCache {
+    /// Cache stored under `dir`, which is created on first write.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Decoder for `bytes`, the current contents of `path`. A fresh entry
+    /// seeds the decoder; otherwise the state is parsed and written back.
+    /// Cache I/O failures never fail the open.
+    pub fn open<'a>(
+        &self,
+        path: impl AsRef<Path>,
+        bytes: &'a [u8],
+        config: ParseConfig,
+    ) -> Result<Decoder<'a>> {
+        let key = match CacheKey::for_file(path, &config) {
+            // A size mismatch means the file changed after `bytes` was read.
+            Ok(key) if key.size == bytes.len() as u64 && config.retain_objects_buffer => key,
+            _ => return Decoder::new(bytes, config),
+        };
+        if let Some(state) = self.load(&key) {
+            return Decoder::with_cached_state(bytes, config, state);
+        }
+        let decoder = Decoder::new(bytes, config)?;
+        if decoder.ensure_supported().is_ok() {
+            match decoder.cached_state() {
+                Ok(state) => {
+                    if let Err(_err) = self.store(&key, &state) {
+                        diag_event!("cache", "write {}: {_err}", self.entry_path(&key).display());
+                    }
+                }
+                Err(_err) => diag_event!("cache", "skip {}: {_err}", key.path.display()),
+            }
+        }
+        Ok(decoder)
+    }
+
+    /// The entry for `key`, or `None` when it is missing, stale or unreadable.
+    pub fn load(&self, key: &CacheKey) -> Option<CachedDecode> {
+        let data = fs::read(self.entry_path(key)).ok()?;
+        match decode_entry(&data) {
+            Ok((stored, state)) if stored == *key => Some(state),
+            Ok(_) => None,
+            Err(_err) => {
+                diag_event!(
+                    "cache",
+                    "discard {}: {_err}",
+                    self.entry_path(key).display()
+                );
+                None
+            }
+        }
+    }
+
+    /// Write `state` for `key`, replacing any previous entry for the path
+    /// and configuration.
+    pub fn store(&self, key: &CacheKey, state: &CachedDecode) -> Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let path = self.entry_path(key);
+        // Write to a side file first so a concurrent reader never sees a
+        // partial entry.
+        let partial = path.with_extension("partial");
+        let mut file = io::BufWriter::new(fs::File::create(&partial)?);
+        encode_entry(&mut file, key, state)?;
+        file.into_inner().map_err(|err| err.into_error())?;
+        fs::rename(&partial, &path)?;
+        Ok(())
+    }
+
+    fn entry_path(&self, key: &CacheKey) -> PathBuf {
+        self.dir.join(key.file_name())
+    }
+}
+
+fn encode_entry(out: &mut impl Write, key: &CacheKey, state: &CachedDecode) -> io::Result<()> {
+    out.write_all(MAGIC)?;
+    let path = key.path.to_string_lossy();
+    out.write_all(&(path.len() as u64).to_le_bytes())?;
+    out.write_all(path.as_bytes())?;
+    out.write_all(&key.mtime_nanos.to_le_bytes())?;
+    out.write_all(&key.size.to_le_bytes())?;
+    out.write_all(&key.config_fingerprint.to_le_bytes())?;
+    out.write_all(&(state.objects.len() as u64).to_le_bytes())?;
+    for object in &state.objects {
+        out.write_all(&object.handle.0.to_le_bytes())?;
+        out.write_all(&object.offset.to_le_bytes())?;
+    }
+    match &state.objects_data {
+        Some(data) => {
+            out.write_all(&[1])?;
+            out.write_all(&(data.len() as u64).to_le_bytes())?;
+            out.write_all(data)?;
+        }
+        None => out.write_all(&[0])?,
+    }
+    Ok(())
+}
+
+fn decode_entry(data: &[u8]) -> Result<(CacheKey, CachedDecode)> {
+    let mut reader = ByteReader::new(data);
+    if reader.read_bytes(MAGIC.len())? != MAGIC {
+        return Err(DwgError::new(ErrorKind::Format, "not a decode cache entry"));
+    }
+    let path_len = read_len(&mut reader)?;
+    let path = String::from_utf8_lossy(reader.read_bytes(path_len)?).into_owned();
+    let mtime_low = reader.read_u64_le()?;
+    let mtime_high = reader.read_u64_le()?;
+    let size = reader.read_u64_le()?;
+    let config_fingerprint = reader.read_u64_le()?;
+    let key = CacheKey {
+        path: PathBuf::from(path),
+        mtime_nanos: (u128::from(mtime_high) << 64) | u128::from(mtime_low),
+        size,
+        config_fingerprint,
+    };
+
+    let count = read_len(&mut reader)?;
+    // Each entry takes 12 bytes; reject counts the entry cannot hold before
+    // allocating for them.
+    if count > reader.remaining() / 12 {
+        return Err(DwgError::new(ErrorKind::Format, "truncated object map"));
+    }
+    let mut objects = Vec::with_capacity(count);
+    for _ in 0..count {
+        let handle = Handle(reader.read_u64_le()?);
+        let offset = reader.read_u32_le()?;
+        objects.push(ObjectRef { handle, offset });
+    }
+    let objects_data = match reader.read_u8()? {
+        0 => None,
+        _ => {
+            let len = read_len(&mut reader)?;
+            Some(reader.read_bytes(len)?.to_vec())
+        }
+    };
+    Ok((
+        key,
+        CachedDecode {
+            objects,
+            objects_data,
+        },
+    ))
+}
+
+fn read_len(reader: &mut ByteReader<'_>) -> Result<usize> {
+    usize::try_from(reader.read_u64_le()?)
+        .map_err(|_| DwgError::new(ErrorKind::Format, "length out of range"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dwg::decoder::Decoder;
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("ezdwg-cache-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("scratch dir");
+        dir
+    }
+
+    #[test]
+    fn reopening_reuses_entry_until_file_changes() {
+        for name in [
+            "line_2000.dwg",
+            "line_2004.dwg",
+            "line_2007.dwg",
+            "line_2013.dwg",
+        ] {
+            let dir = scratch_dir(name);
+            let drawing = dir.join(name);
+            fs::copy(format!("test_dwg/{name}"), &drawing).expect("copy sample");
+            let cache = DecodeCache::new(dir.join("cache"));
+
+            let bytes = fs::read(&drawing).unwrap();
+            let first = cache
+                .open(&drawing, &bytes, ParseConfig::default())
+                .unwrap();
+            let key = CacheKey::for_file(&drawing, &ParseConfig::default()).unwrap();
+            let state = cache.load(&key).expect("entry written on first open");
+            assert_eq!(state, first.cached_state().unwrap(), "{name}");
+            assert_eq!(state.objects_data.is_none(), name == "line_2000.dwg");
+
+            let reopened = cache
+                .open(&drawing, &bytes, ParseConfig::default())
+                .unwrap();
+            let fresh = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+            let handles = |decoder: &Decoder<'_>| -> Vec<(u64, String)> {
+                decoder
+                    .entities()
+                    .filter_map(|item| item.ok())
+                    .map(|(handle, entity)| (handle.0, entity.type_name().to_string()))
+                    .collect()
+            };
+            assert_eq!(handles(&reopened), handles(&fresh), "{name}");
+
+            // Appending a byte changes the size, so the entry goes stale.
+            let mut grown = bytes.clone();
+            grown.push(0);
+            fs::write(&drawing, &grown).unwrap();
+            assert!(cache
+                .load(&CacheKey::for_file(&drawing, &ParseConfig::default()).unwrap())
+                .is_none());
+            cache
+                .open(&drawing, &grown, ParseConfig::default())
+                .unwrap();
+            assert!(cache
+                .load(&CacheKey::for_file(&drawing, &ParseConfig::default()).unwrap())
+                .is_some());
+
+            let _ = fs::remove_dir_all(&dir);
+        }
+    }
+
+    #[test]
+    fn corrupt_entries_are_ignored() {
+        let dir = scratch_dir("corrupt");
+        let drawing = dir.join("line_2004.dwg");
+        fs::copy("test_dwg/line_2004.dwg", &drawing).expect("copy sample");
+        let cache = DecodeCache::new(dir.join("cache"));
+        let key = CacheKey::for_file(&drawing, &ParseConfig::default()).unwrap();
+        fs::create_dir_all(cache.dir()).unwrap();
+        fs::write(cache.entry_path(&key), b"EZDWGC02\xFF\xFF").unwrap();
+        assert!(cache.load(&key).is_none());
+
+        let bytes = fs::read(&drawing).unwrap();
+        let decoder = cache
+            .open(&drawing, &bytes, ParseConfig::default())
+            .unwrap();
+        assert!(!decoder.build_object_index().unwrap().is_empty());
+        assert!(cache.load(&key).is_some());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn entries_are_keyed_by_config() {
+        let dir = scratch_dir("config");
+        let drawing = dir.join("line_2004.dwg");
+        fs::copy("test_dwg/line_2004.dwg", &drawing).expect("copy sample");
+        let cache = DecodeCache::new(dir.join("cache"));
+        let bytes = fs::read(&drawing).unwrap();
+        cache
+            .open(&drawing, &bytes, ParseConfig::default())
+            .unwrap();
+
+        // Reusing the default entry would skip the object-count check.
+        let limited = ParseConfig::builder().max_objects(1).build();
+        let key = CacheKey::for_file(&drawing, &limited).unwrap();
+        assert!(cache.load(&key).is_none());
+        let decoder = cache.open(&drawing, &bytes, limited).unwrap();
+        assert!(decoder.build_object_index().is_err());
+        assert!(cache
+            .load(&CacheKey::for_file(&drawing, &ParseConfig::default()).unwrap())
+            .is_some());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn scan_recovery_state_is_not_shared() {
+        let dir = scratch_dir("scan");
+        let drawing = dir.join("line_2004.dwg");
+        fs::copy("test_dwg/line_2004.dwg", &drawing).expect("copy sample");
+        let cache = DecodeCache::new(dir.join("cache"));
+        let bytes = fs::read(&drawing).unwrap();
+        let scanning = ParseConfig::builder().scan_recovery(true).build();
+        cache.open(&drawing, &bytes, scanning.clone()).unwrap();
+
+        assert!(cache
+            .load(&CacheKey::for_file(&drawing, &scanning).unwrap())
+            .is_some());
+        assert!(cache
+            .load(&CacheKey::for_file(&drawing, &ParseConfig::default()).unwrap())
+            .is_none());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,265 @@
+use crate::core::config::ParseConfig;
+use crate::core::error::{DwgError, ErrorKind};
+use crate::core::result::Result;
+use crate::dwg::compression::check_dst_size;
+
+/// Decompress one R2004-family (AC1018/AC1024/AC1027/AC1032) LZ77 stream.
+///
+/// `dst_size` is the decompressed size recorded in the page or section
+/// header; the result is always exactly `dst_size` bytes, zero-padded when
+/// the stream ends early. `dst_size` above `config.max_section_bytes` is a
+/// format error.
+pub fn decompress_r18(src: &[u8], dst_size: usize, config: &ParseConfig) -> Result<Vec<u8>> {
+    check_dst_size(dst_size, config)?;
+    let mut dst = vec![0u8; dst_size];
+    let mut dst_idx: usize = 0;
+    let mut cursor = Cursor::new(src);
+
+    let (literal_len, mut opcode1) = read_literal_length(&mut cursor)?;
+    dst_idx = copy_literal(&mut dst, dst_idx, src, &mut cursor, literal_len)?;
+
+    while cursor.pos < src.len() {
+        if opcode1 == 0x00 {
+            opcode1 = cursor.read_u8()?;
+        }
+
+        let (compressed_bytes, compressed_offset, next_literal_len, next_opcode1) = match opcode1 {
+            0x10 => {
+                let comp_bytes = read_long_compression_offset(&mut cursor)? + 9;
+                let (offset, literal_count) = read_two_byte_offset(&mut cursor)?;
+                let offset = offset + 0x3FFF;
+                let (literal_len, next_opcode1) = if literal_count == 0 {
+                    read_literal_length(&mut cursor)?
+                } else {
+                    (literal_count, 0x00)
+                };
+                (comp_bytes, offset, literal_len, next_opcode1)
+            }
+            0x11 => break,
+            0x12..=0x1F => {
+                let comp_bytes = (opcode1 & 0x0F) as usize + 2;
+                let (offset, literal_count) = read_two_byte_offset(&mut cursor)?;
+                let offset = offset + 0x3FFF;
+                let (literal_len, next_opcode1) = if literal_count == 0 {
+                    read_literal_length(&mut cursor)?
+                } else {
+                    (literal_count, 0x00)
+                };
+                (comp_bytes, offset, literal_len, next_opcode1)
+            }
+            0x20 => {
+                let comp_bytes = read_long_compression_offset(&mut cursor)? + 0x21;
+                let (offset, literal_count) = read_two_byte_offset(&mut cursor)?;
+                let (literal_len, next_opcode1) = if literal_count == 0 {
+                    read_literal_length(&mut cursor)?
+                } else {
+                    (literal_count, 0x00)
+                };
+                (comp_bytes, offset, literal_len, next_opcode1)
+            }
+            0x21..=0x3F => {
+                let comp_bytes = (opcode1 - 0x1E) as usize;
+                let (offset, literal_count) = read_two_byte_offset(&mut cursor)?;
+                let (literal_len, next_opcode1) = if literal_count == 0 {
+                    read_literal_length(&mut cursor)?
+                } else {
+                    (literal_count, 0x00)
+                };
+                (comp_bytes, offset, literal_len, next_opcode1)
+            }
+            0x40..=0xFF => {
+                let comp_bytes = ((opcode1 & 0xF0) >> 4) as usize - 1;
+                let opcode2 = cursor.read_u8()? as usize;
+                let offset = (opcode2 << 2) | ((opcode1 as usize & 0x0C) >> 2);
+                if opcode1 & 0x03 != 0 {
+                    let literal_len = (opcode1 & 0x03) as usize;
+                    (comp_bytes, offset, literal_len, 0x00)
+                } else {
+                    let (literal_len, next_opcode1) = read_literal_length(&mut cursor)?;
+                    (comp_bytes, offset, literal_len, next_opcode1)
+                }
+            }
+            _ => {
+                return Err(DwgError::new(
+                    ErrorKind::Format,
+                    "invalid R2004 compression opcode",
+                ))
+            }
+        };
+
+        dst_idx = copy_decompressed(&mut dst, dst_idx, compressed_offset + 1, compressed_bytes)?;
+        dst_idx = copy_literal(&mut dst, dst_idx, src, &mut cursor, next_literal_len)?;
+        opcode1 = next_opcode1;
+        // Back-references only read earlier output, so nothing past
+        // `dst_size` can change the result; stop instead of growing `dst`.
+        if dst_idx >= dst_size {
+            break;
+        }
+    }
+
+    Ok(dst)
+}
+
+struct Cursor<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn read_u8(&mut self) -> Result<u8> {
+        if self.pos >= self.data.len() {
+            return Err(DwgError::new(
+                ErrorKind::Decode,
+                "unexpected end of compressed stream",
+            ));
+        }
+        let value = self.data[self.pos];
+        self.pos += 1;
+        Ok(value)
+    }
+}
+
+fn read_literal_length(cursor: &mut Cursor<'_>) -> Result<(usize, u8)> {
+    let mut opcode1 = 0u8;
+    let mut length = 0usize;
+    let byte = cursor.read_u8()?;
+    if (0x01..=0x0F).contains(&byte) {
+        length = byte as usize + 3;
+    } else if byte & 0xF0 != 0 {
+        opcode1 = byte;
+    } else if byte == 0x00 {
+        length = 0x0F;
+        let mut b = cursor.read_u8()?;
+        while b == 0x00 {
+            length += 0xFF;
+            b = cursor.read_u8()?;
+        }
+        length += b as usize + 3;
+    }
+    Ok((length, opcode1))
+}
+
+fn read_long_compression_offset(cursor: &mut Cursor<'_>) -> Result<usize> {
+    let mut value = 0usize;
+    let mut byte = cursor.read_u8()?;
+    if byte == 0x00 {
+        value = 0xFF;
+        byte = cursor.read_u8()?;
+        while byte == 0x00 {
+            value += 0xFF;
+            byte = cursor.read_u8()?;
+        }
+    }
+    Ok(value + byte as usize)
+}
+
+fn read_two_byte_offset(cursor: &mut Cursor<'_>) -> Result<(usize, usize)> {
+    let byte1 = cursor.read_u8()?;
+    let byte2 = cursor.read_u8()?;
+    let value = (byte1 as usize >> 2) | ((byte2 as usize) << 6);
+    let literal_count = (byte1 & 0x03) as usize;
+    Ok((value, literal_count))
+}
+
+fn copy_literal(
+    dst: &mut [u8],
+    dst_idx: usize,
+    src: &[u8],
+    cursor: &mut Cursor<'_>,
+    length: usize,
+) -> Result<usize> {
+    if length == 0 {
+        return Ok(dst_idx);
+    }
+    let end = cursor.pos.saturating_add(length);
+    if end > src.len() {
+        return Err(DwgError::new(
+            ErrorKind::Decode,
+            "literal run exceeds compressed data",
+        ));
+    }
+    let dst_idx_end = dst_idx.saturating_add(length);
+    let copy_len = dst_idx_end.min(dst.len()).saturating_sub(dst_idx);
+    if copy_len > 0 {
+        dst[dst_idx..dst_idx + copy_len].copy_from_slice(&src[cursor.pos..cursor.pos + copy_len]);
+    }
+    cursor.pos = end;
+    Ok(dst_idx_end)
+}
+
+fn copy_decompressed(
+    dst: &mut [u8],
+    dst_idx: usize,
+    offset: usize,
+    length: usize,
+) -> Result<usize> {
+    if length == 0 {
+        return Ok(dst_idx);
+    }
+
+    // Keep behavior permissive for corrupted blocks, matching pydwg's approach.
+    let dst_idx_end = dst_idx.saturating_add(length);
+    if offset > dst_idx {
+        return Ok(dst_idx_end);
+    }
+
+    for out in dst_idx..dst_idx_end.min(dst.len()) {
+        dst[out] = dst[out - offset];
+    }
+    Ok(dst_idx_end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::decompress_r18;
+    use crate::core::config::ParseConfig;
+    use crate::core::error::ErrorKind;
+
+    fn decompress(src: &[u8], dst_size: usize) -> crate::core::result::Result<Vec<u8>> {
+        decompress_r18(src, dst_size, &ParseConfig::default())
+    }
+
+    #[test]
+    fn decodes_literal_run_and_pads_to_dst_size() {
+        let src = [0x02, b'A', b'B', b'C', b'D', b'E', 0x11];
+        assert_eq!(decompress(&src, 5).unwrap(), b"ABCDE");
+        assert_eq!(decompress(&src, 7).unwrap(), b"ABCDE\0\0");
+    }
+
+    #[test]
+    fn decodes_two_byte_back_reference() {
+        // Literal "ABCD", then opcode 0x5C: 4 bytes from offset 3 (distance 4).
+        let src = [0x01, b'A', b'B', b'C', b'D', 0x5C, 0x00, 0x11];
+        assert_eq!(decompress(&src, 8).unwrap(), b"ABCDABCD");
+    }
+
+    #[test]
+    fn stops_at_dst_size_on_overrun() {
+        // Long back-reference (0x20 with 0xFF extra bytes) past a 4-byte output.
+        let src = [0x01, b'A', b'B', b'C', b'D', 0x20, 0xFF, 0x0C, 0x00, 0x11];
+        assert_eq!(decompress(&src, 4).unwrap(), b"ABCD");
+        assert_eq!(decompress(&src, 6).unwrap(), b"ABCDAB");
+    }
+
+    #[test]
+    fn rejects_dst_size_over_limit() {
+        let config = ParseConfig {
+            max_section_bytes: 4,
+            ..ParseConfig::default()
+        };
+        let src = [0x02, b'A', b'B', b'C', b'D', b'E', 0x11];
+        let err = decompress_r18(&src, 5, &config).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Format);
+        assert_eq!(decompress_r18(&src, 4, &config).unwrap(), b"ABCD");
+    }
+
+    #[test]
+    fn rejects_truncated_literal() {
+        let src = [0x02, b'A', b'B'];
+        assert!(decompress(&src, 5).is_err());
+    }
+}
@@ -0,0 +1,580 @@
+use crate::core::config::ParseConfig;
+use crate::core::diag::{diag_event, diag_warn};
+use crate::core::error::{DwgError, ErrorKind};
+use crate::core::result::Result;
+use crate::dwg::compression::check_dst_size;
+
+/// De-interleave R2007 (AC1021) Reed-Solomon coded data into `block_count`
+/// blocks of `k` data bytes, dropping the parity bytes without correcting
+/// errors. `method` 4 is interleaved (data pages, system pages); 1 is stored
+/// as-is.
+pub fn decode_reed_solomon(
+    src: &[u8],
+    k: usize,
+    block_count: usize,
+    method: u8,
+) -> Result<Vec<u8>> {
+    diag_event!(
+        "rs_decode",
+        "R2007 RS decode: k={k}, blocks={block_count}, method={method}, input={} bytes",
+        src.len()
+    );
+    let decoded = deinterleave_reed_solomon(src, k, block_count, method);
+    match &decoded {
+        Ok(_out) => diag_event!("rs_decode", "R2007 RS decode: {} bytes out", _out.len()),
+        Err(_err) => diag_warn!(
+            "rs_decode",
+            "R2007 RS decode failed (k={k}, blocks={block_count}): {_err}"
+        ),
+    }
+    decoded
+}
+
+fn deinterleave_reed_solomon(
+    src: &[u8],
+    k: usize,
+    block_count: usize,
+    method: u8,
+) -> Result<Vec<u8>> {
+    let output_size = k
+        .checked_mul(block_count)
+        .ok_or_else(|| DwgError::new(ErrorKind::Decode, "RS output size overflow"))?;
+    if output_size == 0 {
+        return Ok(Vec::new());
+    }
+    if src.len() < output_size {
+        return Err(DwgError::new(
+            ErrorKind::Decode,
+            "R2007 RS input is smaller than required output layout",
+        ));
+    }
+
+    match method {
+        4 => {
+            let mut out = vec![0u8; output_size];
+            let mut dst = 0usize;
+            for bc in 0..block_count {
+                for idx in 0..k {
+                    out[dst] = src[block_count * idx + bc];
+                    dst += 1;
+                }
+            }
+            Ok(out)
+        }
+        1 => Ok(src[..output_size].to_vec()),
+        _ => Err(DwgError::not_implemented(
+            "unsupported Reed-Solomon method for R2007",
+        )),
+    }
+}
+
+/// Decompress one R2007 (AC1021) stream into exactly `dst_size` bytes.
+/// `dst_size` above `config.max_section_bytes` is a format error.
+pub fn decompress_r21(src: &[u8], dst_size: usize, config: &ParseConfig) -> Result<Vec<u8>> {
+    check_dst_size(dst_size, config)?;
+    diag_event!(
+        "decompress",
+        "R2007 decompress: {} -> {dst_size} bytes",
+        src.len()
+    );
+    if dst_size == 0 {
+        return Ok(Vec::new());
+    }
+    if src.is_empty() {
+        return Err(DwgError::new(
+            ErrorKind::Decode,
+            "R2007 compressed stream is empty",
+        ));
+    }
+
+    let mut dst = vec![0u8; dst_size];
+    let src_size = src.len();
+
+    let mut src_idx = 0usize;
+    let mut dst_idx = 0usize;
+    let mut length = 0usize;
+
+    let mut opcode = read_u8(src, &mut src_idx)? as usize;
+    if (opcode & 0xF0) == 0x20 {
+        src_idx = src_idx
+            .checked_add(2)
+            .ok_or_else(|| DwgError::new(ErrorKind::Decode, "R2007 opcode index overflow"))?;
+        if src_idx >= src_size {
+            return Err(DwgError::new(
+                ErrorKind::Decode,
+                "R2007 opcode bootstrap exceeds input",
+            ));
+        }
+        length = (src[src_idx] & 0x07) as usize;
+        src_idx += 1;
+    }
+
+    while src_idx < src_size {
+        if length == 0 {
+            (length, src_idx) = read_literal_length(src, src_idx, opcode)?;
+        }
+
+        if dst_idx + length > dst_size {
+            break;
+        }
+
+        copy_compressed_chunk(src, src_idx, length, &mut dst, dst_idx)?;
+        dst_idx += length;
+        src_idx += length;
+
+        if src_idx >= src_size {
+            break;
+        }
+
+        let (next_opcode, next_length, next_src_idx, next_dst_idx) =
+            copy_decompressed_chunks(src, src_idx, &mut dst, dst_idx)?;
+        opcode = next_opcode;
+        length = next_length;
+        src_idx = next_src_idx;
+        dst_idx = next_dst_idx;
+    }
+
+    Ok(dst)
+}
+
+fn read_literal_length(src: &[u8], mut src_idx: usize, opcode: usize) -> Result<(usize, usize)> {
+    let mut length = opcode + 8;
+    if length == 0x17 {
+        let mut n = *src.get(src_idx).ok_or_else(|| {
+            DwgError::new(
+                ErrorKind::Decode,
+                "R2007 literal length read exceeds compressed data",
+            )
+        })? as usize;
+        src_idx += 1;
+        length += n;
+        if n == 0xFF {
+            loop {
+                let lo = *src.get(src_idx).ok_or_else(|| {
+                    DwgError::new(
+                        ErrorKind::Decode,
+                        "R2007 literal extension exceeds compressed data",
+                    )
+                })? as usize;
+                let hi = *src.get(src_idx + 1).ok_or_else(|| {
+                    DwgError::new(
+                        ErrorKind::Decode,
+                        "R2007 literal extension exceeds compressed data",
+                    )
+                })? as usize;
+                src_idx += 2;
+                n = lo | (hi << 8);
+                length += n;
+                if n != 0xFFFF {
+                    break;
+                }
+            }
+        }
+    }
+    Ok((length, src_idx))
+}
+
+fn copy_compressed_chunk(
+    src: &[u8],
+    mut src_idx: usize,
+    mut length: usize,
+    dst: &mut [u8],
+    dst_idx: usize,
+) -> Result<()> {
+    let mut out = dst_idx;
+
+    while length >= 32 {
+        copy_16b(src, src_idx + 16, dst, &mut out)?;
+        copy_16b(src, src_idx, dst, &mut out)?;
+        src_idx += 32;
+        length -= 32;
+    }
+
+    match length {
+        0 => {}
+        1 => copy_1b(src, src_idx, dst, &mut out)?,
+        2 => copy_2b(src, src_idx, dst, &mut out)?,
+        3 => copy_3b(src, src_idx, dst, &mut out)?,
+        4 => copy_4b(src, src_idx, dst, &mut out)?,
+        5 => {
+            copy_1b(src, src_idx + 4, dst, &mut out)?;
+            copy_4b(src, src_idx, dst, &mut out)?;
+        }
+        6 => {
+            copy_1b(src, src_idx + 5, dst, &mut out)?;
+            copy_4b(src, src_idx + 1, dst, &mut out)?;
+            copy_1b(src, src_idx, dst, &mut out)?;
+        }
+        7 => {
+            copy_2b(src, src_idx + 5, dst, &mut out)?;
+            copy_4b(src, src_idx + 1, dst, &mut out)?;
+            copy_1b(src, src_idx, dst, &mut out)?;
+        }
+        8 => {
+            copy_4b(src, src_idx, dst, &mut out)?;
+            copy_4b(src, src_idx + 4, dst, &mut out)?;
+        }
+        9 => {
+            copy_1b(src, src_idx + 8, dst, &mut out)?;
+            copy_8b(src, src_idx, dst, &mut out)?;
+        }
+        10 => {
+            copy_1b(src, src_idx + 9, dst, &mut out)?;
+            copy_8b(src, src_idx + 1, dst, &mut out)?;
+            copy_1b(src, src_idx, dst, &mut out)?;
+        }
+        11 => {
+            copy_2b(src, src_idx + 9, dst, &mut out)?;
+            copy_8b(src, src_idx + 1, dst, &mut out)?;
+            copy_1b(src, src_idx, dst, &mut out)?;
+        }
+        12 => {
+            copy_4b(src, src_idx + 8, dst, &mut out)?;
+            copy_8b(src, src_idx, dst, &mut out)?;
+        }
+        13 => {
+            copy_1b(src, src_idx + 12, dst, &mut out)?;
+            copy_4b(src, src_idx + 8, dst, &mut out)?;
+            copy_8b(src, src_idx, dst, &mut out)?;
+        }
+        14 => {
+            copy_1b(src, src_idx + 13, dst, &mut out)?;
+            copy_4b(src, src_idx + 9, dst, &mut out)?;
+            copy_8b(src, src_idx + 1, dst, &mut out)?;
+            copy_1b(src, src_idx, dst, &mut out)?;
+        }
+        15 => {
+            copy_2b(src, src_idx + 13, dst, &mut out)?;
+            copy_4b(src, src_idx + 9, dst, &mut out)?;
+            copy_8b(src, src_idx + 1, dst, &mut out)?;
+            copy_1b(src, src_idx, dst, &mut out)?;
+        }
+        16 => copy_16b(src, src_idx, dst, &mut out)?,
+        17 => {
+            copy_8b(src, src_idx + 9, dst, &mut out)?;
+            copy_1b(src, src_idx + 8, dst, &mut out)?;
+            copy_8b(src, src_idx, dst, &mut out)?;
+        }
+        18 => {
+            copy_1b(src, src_idx + 17, dst, &mut out)?;
+            copy_16b(src, src_idx + 1, dst, &mut out)?;
+            copy_1b(src, src_idx, dst, &mut out)?;
+        }
+        19 => {
+            copy_3b(src, src_idx + 16, dst, &mut out)?;
+            copy_16b(src, src_idx, dst, &mut out)?;
+        }
+        20 => {
+            copy_4b(src, src_idx + 16, dst, &mut out)?;
+            copy_16b(src, src_idx, dst, &mut out)?;
+        }
+        21 => {
+            copy_1b(src, src_idx + 20, dst, &mut out)?;
+            copy_4b(src, src_idx + 16, dst, &mut out)?;
+            copy_16b(src, src_idx, dst, &mut out)?;
+        }
+        22 => {
+            copy_2b(src, src_idx + 20, dst, &mut out)?;
+            copy_4b(src, src_idx + 16, dst, &mut out)?;
+            copy_16b(src, src_idx, dst, &mut out)?;
+        }
+        23 => {
+            copy_3b(src, src_idx + 20, dst, &mut out)?;
+            copy_4b(src, src_idx + 16, dst, &mut out)?;
+            copy_16b(src, src_idx, dst, &mut out)?;
+        }
+        24 => {
+            copy_8b(src, src_idx + 16, dst, &mut out)?;
+            copy_16b(src, src_idx, dst, &mut out)?;
+        }
+        25 => {
+            copy_8b(src, src_idx + 17, dst, &mut out)?;
+            copy_1b(src, src_idx + 16, dst, &mut out)?;
+            copy_16b(src, src_idx, dst, &mut out)?;
+        }
+        26 => {
+            copy_1b(src, src_idx + 25, dst, &mut out)?;
+            copy_8b(src, src_idx + 17, dst, &mut out)?;
+            copy_1b(src, src_idx + 16, dst, &mut out)?;
+            copy_16b(src, src_idx, dst, &mut out)?;
+        }
+        27 => {
+            copy_2b(src, src_idx + 25, dst, &mut out)?;
+            copy_8b(src, src_idx + 17, dst, &mut out)?;
+            copy_1b(src, src_idx + 16, dst, &mut out)?;
+            copy_16b(src, src_idx, dst, &mut out)?;
+        }
+        28 => {
+            copy_4b(src, src_idx + 24, dst, &mut out)?;
+            copy_8b(src, src_idx + 16, dst, &mut out)?;
+            copy_16b(src, src_idx, dst, &mut out)?;
+        }
+        29 => {
+            copy_1b(src, src_idx + 28, dst, &mut out)?;
+            copy_4b(src, src_idx + 24, dst, &mut out)?;
+            copy_8b(src, src_idx + 16, dst, &mut out)?;
+            copy_16b(src, src_idx, dst, &mut out)?;
+        }
+        30 => {
+            copy_2b(src, src_idx + 28, dst, &mut out)?;
+            copy_4b(src, src_idx + 24, dst, &mut out)?;
+            copy_8b(src, src_idx + 16, dst, &mut out)?;
+            copy_16b(src, src_idx, dst, &mut out)?;
+        }
+        31 => {
+            copy_1b(src, src_idx + 30, dst, &mut out)?;
+            copy_4b(src, src_idx + 26, dst, &mut out)?;
+            copy_8b(src, src_idx + 18, dst, &mut out)?;
+            copy_16b(src, src_idx + 2, dst, &mut out)?;
+            copy_2b(src, src_idx, dst, &mut out)?;
+        }
+        _ => {
+            return Err(DwgError::new(
+                ErrorKind::Decode,
+                "R2007 invalid compressed chunk length",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn copy_decompressed_chunks(
+    src: &[u8],
+    mut src_idx: usize,
+    dst: &mut [u8],
+    mut dst_idx: usize,
+) -> Result<(usize, usize, usize, usize)> {
+    let src_size = src.len();
+    let mut opcode = read_u8(src, &mut src_idx)? as usize;
+    let (mut opcode_next, mut offset, mut length, mut src_idx_next) =
+        read_instructions(src, src_idx, opcode)?;
+    opcode = opcode_next;
+    src_idx = src_idx_next;
+
+    loop {
+        dst_idx = copy_from_output(dst, dst_idx, offset, length)?;
+
+        length = opcode & 0x07;
+        if length != 0 || src_idx >= src_size {
+            break;
+        }
+
+        opcode = read_u8(src, &mut src_idx)? as usize;
+        if (opcode >> 4) == 0 {
+            break;
+        }
+        if (opcode >> 4) == 15 {
+            opcode &= 0x0F;
+        }
+
+        (opcode_next, offset, length, src_idx_next) = read_instructions(src, src_idx, opcode)?;
+        opcode = opcode_next;
+        src_idx = src_idx_next;
+    }
+
+    Ok((opcode, length, src_idx, dst_idx))
+}
+
+fn read_instructions(
+    src: &[u8],
+    mut src_idx: usize,
+    mut opcode: usize,
+) -> Result<(usize, usize, usize, usize)> {
+    let mut length;
+    let offset;
+    match opcode >> 4 {
+        0 => {
+            length = (opcode & 0x0F) + 0x13;
+            offset = read_u8(src, &mut src_idx)? as usize;
+            opcode = read_u8(src, &mut src_idx)? as usize;
+            length += (opcode >> 3) & 0x10;
+            let offset = ((opcode & 0x78) << 5) + 1 + offset;
+            Ok((opcode, offset, length, src_idx))
+        }
+        1 => {
+            length = (opcode & 0x0F) + 0x03;
+            offset = read_u8(src, &mut src_idx)? as usize;
+            opcode = read_u8(src, &mut src_idx)? as usize;
+            let offset = ((opcode & 0xF8) << 5) + 1 + offset;
+            Ok((opcode, offset, length, src_idx))
+        }
+        2 => {
+            let mut offset = read_u8(src, &mut src_idx)? as usize;
+            offset |= ((read_u8(src, &mut src_idx)? as usize) << 8) & 0xFF00;
+            length = opcode & 0x07;
+            if (opcode & 0x08) == 0 {
+                opcode = read_u8(src, &mut src_idx)? as usize;
+                length += opcode & 0xF8;
+            } else {
+                offset += 1;
+                length += (read_u8(src, &mut src_idx)? as usize) << 3;
+                opcode = read_u8(src, &mut src_idx)? as usize;
+                length = (((opcode & 0xF8) << 8) + length) + 0x100;
+            }
+            Ok((opcode, offset, length, src_idx))
+        }
+        _ => {
+            length = opcode >> 4;
+            let mut offset = opcode & 0x0F;
+            opcode = read_u8(src, &mut src_idx)? as usize;
+            offset = ((opcode & 0xF8) << 1) + offset + 1;
+            Ok((opcode, offset, length, src_idx))
+        }
+    }
+}
+
+fn copy_from_output(dst: &mut [u8], dst_idx: usize, offset: usize, length: usize) -> Result<usize> {
+    let src_idx = dst_idx.checked_sub(offset).ok_or_else(|| {
+        DwgError::new(
+            ErrorKind::Decode,
+            "R2007 back-reference offset exceeds decompressed prefix",
+        )
+    })?;
+    let end = dst_idx
+        .checked_add(length)
+        .ok_or_else(|| DwgError::new(ErrorKind::Decode, "R2007 decompressed write overflow"))?;
+    if end > dst.len() {
+        return Err(DwgError::new(
+            ErrorKind::Decode,
+            "R2007 decompressed write exceeds output buffer",
+        ));
+    }
+    for i in 0..length {
+        let src_pos = src_idx + i;
+        let dst_pos = dst_idx + i;
+        if src_pos >= dst.len() {
+            return Err(DwgError::new(
+                ErrorKind::Decode,
+                "R2007 decompressed read exceeds output buffer",
+            ));
+        }
+        dst[dst_pos] = dst[src_pos];
+    }
+    Ok(end)
+}
+
+fn read_u8(src: &[u8], src_idx: &mut usize) -> Result<u8> {
+    let value = *src.get(*src_idx).ok_or_else(|| {
+        DwgError::new(
+            ErrorKind::Decode,
+            "R2007 compressed stream read exceeds buffer",
+        )
+    })?;
+    *src_idx += 1;
+    Ok(value)
+}
+
+fn copy_1b(src: &[u8], src_idx: usize, dst: &mut [u8], dst_idx: &mut usize) -> Result<()> {
+    if src_idx >= src.len() || *dst_idx >= dst.len() {
+        return Err(DwgError::new(
+            ErrorKind::Decode,
+            "R2007 copy_1b out of range",
+        ));
+    }
+    dst[*dst_idx] = src[src_idx];
+    *dst_idx += 1;
+    Ok(())
+}
+
+fn copy_2b(src: &[u8], src_idx: usize, dst: &mut [u8], dst_idx: &mut usize) -> Result<()> {
+    copy_1b(src, src_idx + 1, dst, dst_idx)?;
+    copy_1b(src, src_idx, dst, dst_idx)
+}
+
+fn copy_3b(src: &[u8], src_idx: usize, dst: &mut [u8], dst_idx: &mut usize) -> Result<()> {
+    copy_1b(src, src_idx + 2, dst, dst_idx)?;
+    copy_1b(src, src_idx + 1, dst, dst_idx)?;
+    copy_1b(src, src_idx, dst, dst_idx)
+}
+
+fn copy_4b(src: &[u8], src_idx: usize, dst: &mut [u8], dst_idx: &mut usize) -> Result<()> {
+    copy_bytes_direct(src, src_idx, 4, dst, dst_idx)
+}
+
+fn copy_8b(src: &[u8], src_idx: usize, dst: &mut [u8], dst_idx: &mut usize) -> Result<()> {
+    copy_bytes_direct(src, src_idx, 8, dst, dst_idx)
+}
+
+fn copy_16b(src: &[u8], src_idx: usize, dst: &mut [u8], dst_idx: &mut usize) -> Result<()> {
+    copy_8b(src, src_idx + 8, dst, dst_idx)?;
+    copy_8b(src, src_idx, dst, dst_idx)
+}
+
+fn copy_bytes_direct(
+    src: &[u8],
+    src_idx: usize,
+    length: usize,
+    dst: &mut [u8],
+    dst_idx: &mut usize,
+) -> Result<()> {
+    let src_end = src_idx
+        .checked_add(length)
+        .ok_or_else(|| DwgError::new(ErrorKind::Decode, "R2007 source range overflow"))?;
+    let dst_end = (*dst_idx)
+        .checked_add(length)
+        .ok_or_else(|| DwgError::new(ErrorKind::Decode, "R2007 destination range overflow"))?;
+    if src_end > src.len() || dst_end > dst.len() {
+        return Err(DwgError::new(
+            ErrorKind::Decode,
+            "R2007 direct copy out of range",
+        ));
+    }
+    dst[*dst_idx..dst_end].copy_from_slice(&src[src_idx..src_end]);
+    *dst_idx = dst_end;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_reed_solomon, decompress_r21};
+    use crate::core::config::ParseConfig;
+    use crate::core::error::ErrorKind;
+
+    #[test]
+    fn deinterleaves_method_4_blocks() {
+        // Two blocks of three bytes, stored column-major: a0 b0 a1 b1 a2 b2.
+        let src = [0xA0, 0xB0, 0xA1, 0xB1, 0xA2, 0xB2, 0xFF];
+        assert_eq!(
+            decode_reed_solomon(&src, 3, 2, 4).unwrap(),
+            [0xA0, 0xA1, 0xA2, 0xB0, 0xB1, 0xB2]
+        );
+    }
+
+    #[test]
+    fn copies_method_1_prefix_and_rejects_unknown_method() {
+        let src = [1, 2, 3, 4, 5, 6, 7];
+        assert_eq!(
+            decode_reed_solomon(&src, 3, 2, 1).unwrap(),
+            [1, 2, 3, 4, 5, 6]
+        );
+        let err = decode_reed_solomon(&src, 3, 2, 2).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::NotImplemented);
+        assert!(decode_reed_solomon(&src[..4], 3, 2, 4).is_err());
+    }
+
+    #[test]
+    fn decodes_leading_literal_run() {
+        // Opcode 0 starts an 8-byte literal.
+        let src = [0x00, b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7'];
+        let config = ParseConfig::default();
+        assert_eq!(decompress_r21(&src, 8, &config).unwrap(), b"01234567");
+    }
+
+    #[test]
+    fn handles_empty_input_and_output() {
+        let config = ParseConfig::default();
+        assert!(decompress_r21(&[0x00], 0, &config).unwrap().is_empty());
+        assert!(decompress_r21(&[], 8, &config).is_err());
+    }
+
+    #[test]
+    fn rejects_dst_size_over_limit() {
+        let config = ParseConfig {
+            max_section_bytes: 1024,
+            ..ParseConfig::default()
+        };
+        let err = decompress_r21(&[0x00], 1025, &config).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Format);
+    }
+}
@@ -1,12 +1,28 @@
-use crate::container::{SectionDirectory, SectionSlice};
+use crate::container::{NamedSection, SectionDirectory, SectionInfo, SectionSlice};
 use crate::core::config::ParseConfig;
+use crate::core::diag::diag_event;
 use crate::core::error::{DwgError, ErrorKind};
+use crate::core::geometry::{ArcTessellation, DEFAULT_POINT_EPSILON};
+use crate::core::progress::Progress;
 use crate::core::result::Result;
+use crate::dwg::aux_header::{parse_aux_header, AuxHeader};
+#[cfg(feature = "cache")]
+use crate::dwg::cache::CachedDecode;
+use crate::dwg::entity_dispatch;
+use crate::dwg::measurement::{parse_measurement, Measurement};
+use crate::dwg::preview::{read_thumbnail, Thumbnail};
 use crate::dwg::r2000;
 use crate::dwg::r2004;
 use crate::dwg::r2007;
+use crate::dwg::record_scan;
 use crate::dwg::version::{detect_version, DwgVersion};
-use crate::objects::{ObjectIndex, ObjectRecord};
+use crate::entities::{Entity, HatchEntity};
+use crate::objects::{
+    object_record, object_type_class, object_type_name, Handle, ObjectClass, ObjectIndex,
+    ObjectRecord, ObjectRef,
+};
+use std::borrow::Cow;
+use std::cell::OnceCell;
 use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
@@ -14,6 +30,11 @@ pub struct Decoder<'a> {
     bytes: &'a [u8],
     version: DwgVersion,
     config: ParseConfig,
+    objects_data: OnceCell<Vec<u8>>,
+    object_index: OnceCell<ObjectIndex>,
+    /// Class map, or the error loading it; kept so per-object type lookups
+    /// do not parse the classes section again.
+    dynamic_types: OnceCell<Result<HashMap<u16, String>>>,
 }
 
 impl<'a> Decoder<'a> {
@@ -23,6 +44,43 @@ impl<'a> Decoder<'a> {
             bytes,
             version,
             config,
+            objects_data: OnceCell::new(),
+            object_index: OnceCell::new(),
+            dynamic_types: OnceCell::new(),
+        })
+    }
+
+    /// Decoder for `bytes` that starts from `state`, previously taken with
+    /// [`Decoder::cached_state`] from the same bytes, instead of parsing the
+    /// object map and objects section again.
+    #[cfg(feature = "cache")]
+    pub fn with_cached_state(
+        bytes: &'a [u8],
+        config: ParseConfig,
+        state: CachedDecode,
+    ) -> Result<Self> {
+        let decoder = Self::new(bytes, config)?;
+        let _ = decoder
+            .object_index
+            .set(ObjectIndex::from_objects(state.objects));
+        if let Some(data) = state.objects_data {
+            let _ = decoder.objects_data.set(data);
+        }
+        Ok(decoder)
+    }
+
+    /// Object map and, for R2004+, the decompressed objects section, parsing
+    /// them first if this decoder has not yet.
+    #[cfg(feature = "cache")]
+    pub fn cached_state(&self) -> Result<CachedDecode> {
+        let objects = self.cached_object_index()?.objects.clone();
+        let objects_data = match self.version {
+            DwgVersion::R14 | DwgVersion::R2000 => None,
+            _ => self.retained_objects_data()?.map(<[u8]>::to_vec),
+        };
+        Ok(CachedDecode {
+            objects,
+            objects_data,
         })
     }
 
@@ -30,6 +88,10 @@ impl<'a> Decoder<'a> {
         &self.version
     }
 
+    pub fn config(&self) -> &ParseConfig {
+        &self.config
+    }
+
     pub fn ensure_supported(&self) -> Result<()> {
         match self.version {
             DwgVersion::R14
@@ -39,7 +101,7 @@ impl<'a> Decoder<'a> {
             | DwgVersion::R2010
             | DwgVersion::R2013
             | DwgVersion::R2018 => Ok(()),
-            DwgVersion::Unknown(_) => Err(DwgError::new(
+            DwgVersion::R13 | DwgVersion::Unknown(_) => Err(DwgError::new(
                 ErrorKind::Unsupported,
                 format!("unsupported DWG version: {}", self.version.as_str()),
             )),
@@ -55,7 +117,21 @@ impl<'a> Decoder<'a> {
                 r2004::parse_section_directory(self.bytes, &self.config)
             }
             DwgVersion::R2007 => r2007::parse_section_directory(self.bytes, &self.config),
-            DwgVersion::Unknown(_) => Err(DwgError::new(
+            DwgVersion::R13 | DwgVersion::Unknown(_) => Err(DwgError::new(
+                ErrorKind::Unsupported,
+                format!("unsupported DWG version: {}", self.version.as_str()),
+            )),
+        }
+    }
+
+    pub fn list_sections(&self) -> Result<Vec<SectionInfo>> {
+        match self.version {
+            DwgVersion::R14 | DwgVersion::R2000 => r2000::list_sections(self.bytes, &self.config),
+            DwgVersion::R2004 | DwgVersion::R2010 | DwgVersion::R2013 | DwgVersion::R2018 => {
+                r2004::list_sections(self.bytes, &self.config)
+            }
+            DwgVersion::R2007 => r2007::list_sections(self.bytes, &self.config),
+            DwgVersion::R13 | DwgVersion::Unknown(_) => Err(DwgError::new(
                 ErrorKind::Unsupported,
                 format!("unsupported DWG version: {}", self.version.as_str()),
             )),
@@ -77,44 +153,484 @@ impl<'a> Decoder<'a> {
             DwgVersion::R2007 => {
                 r2007::load_section_by_index(self.bytes, directory, index, &self.config)
             }
-            DwgVersion::Unknown(_) => Err(DwgError::new(
+            DwgVersion::R13 | DwgVersion::Unknown(_) => Err(DwgError::new(
                 ErrorKind::Unsupported,
                 format!("unsupported DWG version: {}", self.version.as_str()),
             )),
         }
     }
 
+    pub fn load_section_by_name(
+        &self,
+        directory: &SectionDirectory,
+        name: &str,
+    ) -> Result<SectionSlice<'a>> {
+        let index = directory.find_by_name(name).ok_or_else(|| {
+            DwgError::new(ErrorKind::Format, format!("section not found: {name}"))
+        })?;
+        self.load_section_by_index(directory, index)
+    }
+
     pub fn build_object_index(&self) -> Result<ObjectIndex> {
+        if let Some(index) = self.object_index.get() {
+            return Ok(index.clone());
+        }
+        // R13-R15 records are read in place from the file, so the object map
+        // is the only per-walk parse left to reuse.
+        if matches!(self.version, DwgVersion::R14 | DwgVersion::R2000)
+            && self.config.retain_objects_buffer
+        {
+            return self.cached_object_index().cloned();
+        }
+        self.parse_object_index()
+    }
+
+    fn parse_object_index(&self) -> Result<ObjectIndex> {
+        if !self.config.scan_recovery || matches!(self.version, DwgVersion::R14 | DwgVersion::R2000)
+        {
+            return self.parse_object_map();
+        }
+        // Only a map that fails to load, or lists records that do not parse,
+        // is worth the byte-by-byte scan.
+        let objects_data = self.objects_data()?;
+        let listed = self.object_map().map(|map| {
+            let total = map.len();
+            let readable: Vec<_> = map
+                .objects
+                .into_iter()
+                .filter(|object| {
+                    object_record::parse_object_record(&objects_data, object.offset).is_ok()
+                })
+                .collect();
+            (readable, total)
+        });
+        let readable = match listed {
+            Ok((readable, total)) if readable.len() == total => {
+                return Ok(ObjectIndex::from_objects(readable));
+            }
+            Ok((readable, _total)) => Some(readable),
+            Err(_) => None,
+        };
+        let scanned = record_scan::scan_object_records(
+            &objects_data,
+            &self.version,
+            self.config.max_section_bytes,
+        );
+        match readable {
+            Some(readable) if readable.len() >= scanned.len() => {
+                Ok(ObjectIndex::from_objects(readable))
+            }
+            _readable => {
+                diag_event!(
+                    "object",
+                    "object map unusable ({} readable objects); scanned {} records instead",
+                    _readable.map_or(0, |readable| readable.len()),
+                    scanned.len()
+                );
+                Ok(ObjectIndex::from_objects(scanned))
+            }
+        }
+    }
+
+    /// The object map as stored in the file. Unlike
+    /// [`build_object_index`](Self::build_object_index), R2004+ entries whose
+    /// records do not parse are kept, and no scan recovery is attempted.
+    pub fn object_map(&self) -> Result<ObjectIndex> {
         match self.version {
             DwgVersion::R14 | DwgVersion::R2000 => {
                 r2000::build_object_index(self.bytes, &self.config)
             }
             DwgVersion::R2004 | DwgVersion::R2010 | DwgVersion::R2013 | DwgVersion::R2018 => {
-                r2004::build_object_index(self.bytes, &self.config)
+                r2004::parse_object_map(self.bytes, &self.config)
             }
-            DwgVersion::R2007 => r2007::build_object_index(self.bytes, &self.config),
-            DwgVersion::Unknown(_) => Err(DwgError::new(
+            DwgVersion::R2007 => r2007::parse_object_map(self.bytes, &self.config),
+            DwgVersion::R13 | DwgVersion::Unknown(_) => Err(DwgError::new(
                 ErrorKind::Unsupported,
                 format!("unsupported DWG version: {}", self.version.as_str()),
             )),
         }
     }
 
-    pub fn parse_object_record(&self, offset: u32) -> Result<ObjectRecord<'a>> {
+    fn parse_object_map(&self) -> Result<ObjectIndex> {
         match self.version {
+            DwgVersion::R14 | DwgVersion::R2000 => {
+                r2000::build_object_index(self.bytes, &self.config)
+            }
+            DwgVersion::R2004 | DwgVersion::R2010 | DwgVersion::R2013 | DwgVersion::R2018 => {
+                match self.retained_objects_data()? {
+                    Some(data) => {
+                        r2004::build_object_index_with_objects(self.bytes, &self.config, data)
+                    }
+                    None => r2004::build_object_index(self.bytes, &self.config),
+                }
+            }
+            DwgVersion::R2007 => match self.retained_objects_data()? {
+                Some(data) => {
+                    r2007::build_object_index_with_objects(self.bytes, &self.config, data)
+                }
+                None => r2007::build_object_index(self.bytes, &self.config),
+            },
+            DwgVersion::R13 | DwgVersion::Unknown(_) => Err(DwgError::new(
+                ErrorKind::Unsupported,
+                format!("unsupported DWG version: {}", self.version.as_str()),
+            )),
+        }
+    }
+
+    pub fn parse_object_record(&self, offset: u32) -> Result<ObjectRecord<'_>> {
+        let record = match self.version {
             DwgVersion::R14 | DwgVersion::R2000 => r2000::parse_object_record(self.bytes, offset),
             DwgVersion::R2004 | DwgVersion::R2010 | DwgVersion::R2013 | DwgVersion::R2018 => {
-                r2004::parse_object_record(self.bytes, offset, &self.config)
+                match self.retained_objects_data()? {
+                    Some(data) => object_record::parse_object_record(data, offset),
+                    None => r2004::parse_object_record(self.bytes, offset, &self.config),
+                }
+            }
+            DwgVersion::R2007 => match self.retained_objects_data()? {
+                Some(data) => object_record::parse_object_record(data, offset),
+                None => r2007::parse_object_record(self.bytes, offset, &self.config),
+            },
+            DwgVersion::R13 | DwgVersion::Unknown(_) => Err(DwgError::new(
+                ErrorKind::Unsupported,
+                format!("unsupported DWG version: {}", self.version.as_str()),
+            )),
+        };
+        record
+            .map(|mut record| {
+                record.strict = self.config.strict;
+                record
+            })
+            .inspect_err(|err| {
+                diag_event!("object", "parse object record at offset {offset:#X}: {err}")
+            })
+    }
+
+    /// Look up `handle` in the object map and decode it as a graphical entity,
+    /// using the decoder that matches the object's type and this file's version.
+    /// Non-entity objects and unhandled entity types yield `UnsupportedEntity`.
+    pub fn decode_entity(&self, handle: Handle) -> Result<Entity> {
+        let index = self.cached_object_index()?;
+        let Some(object) = index.get(handle) else {
+            return Err(DwgError::new(
+                ErrorKind::Resolve,
+                format!("object handle {:#X} not found in object map", handle.0),
+            ));
+        };
+        let first_err = match self.decode_entity_at(object.offset, handle) {
+            Ok(entity) => return Ok(entity),
+            Err(err) => err,
+        };
+        // R2007 object maps can list one handle at several offsets; fall back
+        // to the other entries before giving up.
+        index
+            .objects
+            .iter()
+            .filter(|candidate| candidate.handle == handle && candidate.offset != object.offset)
+            .find_map(|candidate| self.decode_entity_at(candidate.offset, handle).ok())
+            .ok_or(first_err)
+    }
+
+    fn decode_entity_at(&self, offset: u32, handle: Handle) -> Result<Entity> {
+        let record = self.parse_object_record(offset)?;
+        let header = entity_dispatch::parse_object_header_for_version(&record, &self.version)?;
+        let type_name = self.type_name(header.type_code);
+        self.decode_entity_record(&record, &header, handle, &type_name)
+    }
+
+    fn decode_entity_record(
+        &self,
+        record: &ObjectRecord<'_>,
+        header: &entity_dispatch::ObjectHeader,
+        handle: Handle,
+        type_name: &str,
+    ) -> Result<Entity> {
+        let mut reader = record.bit_reader();
+        entity_dispatch::skip_object_type_prefix(&mut reader, &self.version)?;
+        entity_dispatch::decode_entity_for_version(
+            &mut reader,
+            &self.version,
+            header,
+            handle.0,
+            type_name,
+        )
+        .and_then(|entity| self.check_dimension_layout(entity))
+        .map(|mut entity| {
+            if let Entity::Hatch(hatch) = &mut entity {
+                self.flatten_hatch(hatch);
+            }
+            entity
+        })
+        .inspect_err(|err| {
+            diag_event!(
+                "object",
+                "decode {type_name} handle {:#X} at offset {:#X}: {err}",
+                handle.0,
+                record.offset
+            )
+        })
+    }
+
+    /// `entity`, or an error when it is a dimension whose layout had to be
+    /// guessed and [`ParseConfig::strict_dimensions`] is set.
+    pub(crate) fn check_dimension_layout(&self, entity: Entity) -> Result<Entity> {
+        if self.config.strict_dimensions {
+            if let Some(common) = entity.dimension_common() {
+                common.ensure_layout_decoded()?;
+            }
+        }
+        Ok(entity)
+    }
+
+    /// Redraw `hatch`'s paths at [`ParseConfig::arc_tessellation`] and
+    /// [`ParseConfig::point_epsilon`]; decoders flatten at the defaults.
+    pub(crate) fn flatten_hatch(&self, hatch: &mut HatchEntity) {
+        let tessellation = self.config.arc_tessellation();
+        let epsilon = self.config.point_epsilon;
+        if tessellation != ArcTessellation::default() || epsilon != DEFAULT_POINT_EPSILON {
+            hatch.reflatten(tessellation, epsilon);
+        }
+    }
+
+    /// Lazily decode every graphical entity in object-map order. Non-entity
+    /// objects and entity types without a decoder are skipped; records that
+    /// fail to parse are yielded as errors so callers can skip or stop.
+    pub fn entities(&self) -> impl Iterator<Item = Result<(Handle, Entity)>> + '_ {
+        self.entities_filtered(|_| true)
+    }
+
+    /// [`entities`](Self::entities) restricted to objects whose resolved type
+    /// name (`"LINE"`, or the class name of a dynamic type) `keep` accepts;
+    /// rejected records are not decoded.
+    pub fn entities_filtered<'s>(
+        &'s self,
+        keep: impl Fn(&str) -> bool + 's,
+    ) -> impl Iterator<Item = Result<(Handle, Entity)>> + 's {
+        self.entities_with_progress(keep, Progress::none())
+    }
+
+    /// [`entities_filtered`](Self::entities_filtered) reporting objects
+    /// walked to `progress`. When the callback asks to stop, the iteration
+    /// ends with a single `Cancelled` error.
+    pub fn entities_with_progress<'s>(
+        &'s self,
+        keep: impl Fn(&str) -> bool + 's,
+        mut progress: Progress<'s>,
+    ) -> impl Iterator<Item = Result<(Handle, Entity)>> + 's {
+        let (objects, index_error) = match self.cached_object_index() {
+            Ok(index) => (index.objects.as_slice(), None),
+            Err(err) => (&[][..], Some(err)),
+        };
+        let dynamic_types = if objects.is_empty() {
+            HashMap::new()
+        } else {
+            self.dynamic_type_map().unwrap_or_default()
+        };
+        let total = objects.len();
+        let decoded = objects
+            .iter()
+            .enumerate()
+            .map_while(move |(done, object)| {
+                if progress.is_stopped() {
+                    return None;
+                }
+                if let Err(err) = progress.check(done, total) {
+                    return Some(Some(Err(err)));
+                }
+                let item = self.decode_listed_entity(object, &dynamic_types, &keep);
+                if done + 1 == total {
+                    progress.finish(total);
+                }
+                Some(item)
+            })
+            .flatten();
+        index_error.map(Err).into_iter().chain(decoded)
+    }
+
+    /// [`entities`](Self::entities) for the `count` object-map entries from
+    /// `offset` on, so a large drawing can be read a window at a time; only
+    /// those records are parsed. The window is clamped to the map.
+    pub fn entities_in_window(
+        &self,
+        offset: usize,
+        count: usize,
+    ) -> impl Iterator<Item = Result<(Handle, Entity)>> + '_ {
+        let (objects, index_error) = match self.cached_object_index() {
+            Ok(index) => {
+                let start = offset.min(index.objects.len());
+                let end = start.saturating_add(count).min(index.objects.len());
+                (&index.objects[start..end], None)
+            }
+            Err(err) => (&[][..], Some(err)),
+        };
+        let dynamic_types = if objects.is_empty() {
+            HashMap::new()
+        } else {
+            self.dynamic_type_map().unwrap_or_default()
+        };
+        let decoded = objects
+            .iter()
+            .filter_map(move |object| self.decode_listed_entity(object, &dynamic_types, |_| true));
+        index_error.map(Err).into_iter().chain(decoded)
+    }
+
+    /// One object-map entry as an entity, `None` for non-entities, entities
+    /// `keep` rejects and types without a decoder.
+    fn decode_listed_entity(
+        &self,
+        object: &ObjectRef,
+        dynamic_types: &HashMap<u16, String>,
+        keep: impl Fn(&str) -> bool,
+    ) -> Option<Result<(Handle, Entity)>> {
+        let record = match self.parse_object_record(object.offset) {
+            Ok(record) => record,
+            Err(err) => return Some(Err(err)),
+        };
+        let header = match entity_dispatch::parse_object_header_for_version(&record, &self.version)
+        {
+            Ok(header) => header,
+            Err(err) => return Some(Err(err)),
+        };
+        let type_name = match dynamic_types.get(&header.type_code) {
+            Some(name) => name.clone(),
+            None if object_type_class(header.type_code) == ObjectClass::Entity => {
+                object_type_name(header.type_code)
+            }
+            None => return None,
+        };
+        if !keep(&type_name) {
+            return None;
+        }
+        match self.decode_entity_record(&record, &header, object.handle, &type_name) {
+            Ok(entity) => Some(Ok((object.handle, entity))),
+            Err(err) if err.kind == ErrorKind::UnsupportedEntity => None,
+            Err(err) => Some(Err(err)),
+        }
+    }
+
+    fn cached_object_index(&self) -> Result<&ObjectIndex> {
+        if let Some(index) = self.object_index.get() {
+            return Ok(index);
+        }
+        let index = self.parse_object_index()?;
+        Ok(self.object_index.get_or_init(|| index))
+    }
+
+    /// Built-in name for fixed type codes; class-section name for dynamic ones.
+    fn type_name(&self, type_code: u16) -> String {
+        if type_code >= 500 {
+            if let Some(name) = self
+                .cached_dynamic_types()
+                .as_ref()
+                .ok()
+                .and_then(|map| map.get(&type_code).cloned())
+            {
+                return name;
+            }
+        }
+        object_type_name(type_code)
+    }
+
+    /// Size of the buffer object-map offsets point into: the whole file for
+    /// R14/R2000, the decompressed `AcDb:AcDbObjects` section otherwise.
+    pub fn objects_data_len(&self) -> Result<usize> {
+        self.objects_data().map(|data| data.len())
+    }
+
+    /// The buffer object-map offsets point into, borrowed when it is the
+    /// file itself or retained.
+    pub(crate) fn objects_data(&self) -> Result<Cow<'_, [u8]>> {
+        match self.version {
+            DwgVersion::R14 | DwgVersion::R2000 => Ok(Cow::Borrowed(self.bytes)),
+            DwgVersion::R2004 | DwgVersion::R2010 | DwgVersion::R2013 | DwgVersion::R2018 => {
+                match self.retained_objects_data()? {
+                    Some(data) => Ok(Cow::Borrowed(data)),
+                    None => Ok(Cow::Owned(r2004::load_objects_data(
+                        self.bytes,
+                        &self.config,
+                    )?)),
+                }
+            }
+            DwgVersion::R2007 => match self.retained_objects_data()? {
+                Some(data) => Ok(Cow::Borrowed(data)),
+                None => Ok(Cow::Owned(r2007::load_objects_data(
+                    self.bytes,
+                    &self.config,
+                )?)),
+            },
+            DwgVersion::R13 | DwgVersion::Unknown(_) => Err(DwgError::new(
+                ErrorKind::Unsupported,
+                format!("unsupported DWG version: {}", self.version.as_str()),
+            )),
+        }
+    }
+
+    /// Decompressed `AcDb:AcDbObjects` data shared by every record handed
+    /// out by this decoder, or `None` when `retain_objects_buffer` is off.
+    /// Only meaningful for R2004+ containers, whose object section is paged.
+    fn retained_objects_data(&self) -> Result<Option<&[u8]>> {
+        if !self.config.retain_objects_buffer {
+            return Ok(None);
+        }
+        if let Some(data) = self.objects_data.get() {
+            return Ok(Some(data.as_slice()));
+        }
+        let data = match self.version {
+            DwgVersion::R2007 => r2007::load_objects_data(self.bytes, &self.config)?,
+            _ => r2004::load_objects_data(self.bytes, &self.config)?,
+        };
+        Ok(Some(self.objects_data.get_or_init(|| data).as_slice()))
+    }
+
+    /// Stored CRC values of the section directory, classes section and
+    /// object-map blocks as `(section_name, stored_crc)`, without verifying them.
+    pub fn section_crcs(&self) -> Result<Vec<(String, u16)>> {
+        match self.version {
+            DwgVersion::R14 | DwgVersion::R2000 => r2000::section_crcs(self.bytes, &self.config),
+            DwgVersion::R2004 | DwgVersion::R2010 | DwgVersion::R2013 | DwgVersion::R2018 => {
+                r2004::section_crcs(self.bytes, &self.config)
             }
-            DwgVersion::R2007 => r2007::parse_object_record(self.bytes, offset, &self.config),
-            DwgVersion::Unknown(_) => Err(DwgError::new(
+            DwgVersion::R2007 => r2007::section_crcs(self.bytes, &self.config),
+            DwgVersion::R13 | DwgVersion::Unknown(_) => Err(DwgError::new(
                 ErrorKind::Unsupported,
                 format!("unsupported DWG version: {}", self.version.as_str()),
             )),
         }
     }
 
+    /// Save counters, version stamps and timestamps from the aux header
+    /// (R2000+).
+    pub fn aux_header(&self) -> Result<AuxHeader> {
+        let directory = self.section_directory()?;
+        let section = self.load_section_by_name(&directory, NamedSection::AuxHeader.as_str())?;
+        parse_aux_header(section.data.as_ref(), &self.version)
+    }
+
+    /// The MEASUREMENT setting; `None` when the file stores no template
+    /// section.
+    pub fn measurement(&self) -> Result<Option<Measurement>> {
+        let directory = self.section_directory()?;
+        let Some(index) = directory.find_by_name(NamedSection::Template.as_str()) else {
+            return Ok(None);
+        };
+        let section = self.load_section_by_index(&directory, index)?;
+        parse_measurement(section.data.as_ref(), &self.version)
+    }
+
+    /// The thumbnail preview saved with the drawing, if any.
+    pub fn thumbnail(&self) -> Result<Option<Thumbnail>> {
+        read_thumbnail(self.bytes)
+    }
+
+    /// Dynamic type code -> class DXF name, parsed once per decoder.
     pub fn dynamic_type_map(&self) -> Result<HashMap<u16, String>> {
+        self.cached_dynamic_types().clone()
+    }
+
+    fn cached_dynamic_types(&self) -> &Result<HashMap<u16, String>> {
+        self.dynamic_types.get_or_init(|| self.load_dynamic_types())
+    }
+
+    fn load_dynamic_types(&self) -> Result<HashMap<u16, String>> {
         match self.version {
             DwgVersion::R14 | DwgVersion::R2000 => {
                 match r2000::load_dynamic_type_map(self.bytes, &self.config) {
@@ -123,13 +639,312 @@ impl<'a> Decoder<'a> {
                 }
             }
             DwgVersion::R2004 => r2004::load_dynamic_type_map(self.bytes, &self.config),
-            DwgVersion::R2010 => Ok(HashMap::new()),
             DwgVersion::R2007 => r2007::load_dynamic_type_map(self.bytes, &self.config),
-            DwgVersion::R2013 | DwgVersion::R2018 => Ok(HashMap::new()),
-            DwgVersion::Unknown(_) => Err(DwgError::new(
+            DwgVersion::R2010 | DwgVersion::R2013 | DwgVersion::R2018 => {
+                r2004::load_dynamic_type_map_r2010(self.bytes, &self.config, &self.version)
+            }
+            DwgVersion::R13 | DwgVersion::Unknown(_) => Err(DwgError::new(
                 ErrorKind::Unsupported,
                 format!("unsupported DWG version: {}", self.version.as_str()),
             )),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn class_map_is_parsed_once() {
+        let bytes = std::fs::read("test_dwg/acadsharp/sample_AC1032.dwg").expect("sample file");
+        let decoder = Decoder::new(&bytes, ParseConfig::default()).expect("decoder");
+        assert!(decoder.dynamic_types.get().is_none());
+        let map = decoder.dynamic_type_map().expect("class map");
+        let (&code, name) = map.iter().next().expect("a class");
+        assert!(decoder.dynamic_types.get().is_some());
+        assert_eq!(&decoder.type_name(code), name);
+        assert_eq!(decoder.dynamic_type_map().unwrap(), map);
+    }
+
+    #[test]
+    fn r2000_object_index_is_parsed_once() {
+        let entries = |index: &ObjectIndex| -> Vec<(u64, u32)> {
+            index
+                .objects
+                .iter()
+                .map(|object| (object.handle.0, object.offset))
+                .collect()
+        };
+        let bytes = std::fs::read("test_dwg/line_2000.dwg").expect("sample file");
+        let decoder = Decoder::new(&bytes, ParseConfig::default()).expect("decoder");
+        let first = decoder.build_object_index().expect("object index");
+        assert!(decoder.object_index.get().is_some());
+        let second = decoder.build_object_index().expect("object index");
+        assert_eq!(entries(&first), entries(&second));
+
+        let config = ParseConfig {
+            retain_objects_buffer: false,
+            ..ParseConfig::default()
+        };
+        let decoder = Decoder::new(&bytes, config).expect("decoder");
+        assert_eq!(
+            entries(&decoder.build_object_index().unwrap()),
+            entries(&first)
+        );
+        assert!(decoder.object_index.get().is_none());
+    }
+
+    #[test]
+    fn empty_classes_and_object_map_sections_read_as_empty() {
+        // Zero the classes (record 1) and object map (record 2) locator sizes,
+        // as a minimally populated R2000 file stores them. The R2004 and
+        // R2007 samples get the same sections emptied in their section maps.
+        let mut bytes = std::fs::read("test_dwg/line_2000.dwg").expect("sample file");
+        let count = u32::from_le_bytes(bytes[0x15..0x19].try_into().unwrap()) as usize;
+        for record in 0..count {
+            let start = 0x19 + record * 9;
+            if matches!(bytes[start], 1 | 2) {
+                bytes[start + 5..start + 9].fill(0);
+            }
+        }
+        assert_reads_as_empty(&bytes, "line_2000");
+
+        let empty = [NamedSection::Classes, NamedSection::Handles];
+        let bytes = std::fs::read("test_dwg/line_2004.dwg").expect("sample file");
+        let rewritten = r2004::with_empty_sections(&bytes, &[]);
+        assert_eq!(entity_count(&rewritten), 1, "line_2004 rewritten as is");
+        assert_reads_as_empty(&r2004::with_empty_sections(&bytes, &empty), "line_2004");
+        let bytes = std::fs::read("test_dwg/line_2007.dwg").expect("sample file");
+        let rewritten = r2007::with_empty_sections(&bytes, &[]);
+        assert_eq!(entity_count(&rewritten), 1, "line_2007 rewritten as is");
+        assert_reads_as_empty(&r2007::with_empty_sections(&bytes, &empty), "line_2007");
+    }
+
+    fn entity_count(bytes: &[u8]) -> usize {
+        let decoder = Decoder::new(bytes, ParseConfig::default()).expect("decoder");
+        decoder.entities().filter(|item| item.is_ok()).count()
+    }
+
+    fn assert_reads_as_empty(bytes: &[u8], name: &str) {
+        let decoder = Decoder::new(bytes, ParseConfig::default()).expect("decoder");
+        assert!(
+            decoder.dynamic_type_map().expect("class map").is_empty(),
+            "{name}"
+        );
+        let index = decoder.build_object_index().expect("object index");
+        assert!(index.objects.is_empty(), "{name}");
+        assert_eq!(decoder.entities().count(), 0, "{name}");
+    }
+
+    #[test]
+    fn entities_iterates_decoded_entities_with_filter() {
+        for name in ["line_2000.dwg", "line_2004.dwg", "line_2013.dwg"] {
+            let bytes = std::fs::read(format!("test_dwg/{name}")).expect("sample file");
+            let decoder = Decoder::new(&bytes, ParseConfig::default()).expect("decoder");
+            let lines: Vec<(Handle, Entity)> = decoder
+                .entities()
+                .filter_map(|item| item.ok())
+                .filter(|(_, entity)| matches!(entity, Entity::Line(_)))
+                .collect();
+            assert_eq!(lines.len(), 1, "{name}");
+            assert_eq!(lines[0].1.handle(), lines[0].0 .0, "{name}");
+
+            let filtered: Vec<Entity> = decoder
+                .entities_filtered(|type_name| type_name == "LINE")
+                .map(|item| item.expect("line decodes").1)
+                .collect();
+            assert_eq!(filtered.len(), 1, "{name}");
+            assert_eq!(filtered[0].type_name(), "LINE");
+        }
+    }
+
+    #[test]
+    fn entities_with_progress_ends_with_cancelled_error() {
+        let bytes = std::fs::read("test_dwg/line_2004.dwg").expect("sample file");
+        let decoder = Decoder::new(&bytes, ParseConfig::default()).expect("decoder");
+        let total = decoder.build_object_index().unwrap().len();
+
+        let mut reports = 0;
+        let all: Vec<_> = decoder
+            .entities_with_progress(
+                |_| true,
+                Progress::new(|_, _| {
+                    reports += 1;
+                    true
+                }),
+            )
+            .collect();
+        assert_eq!(all.len(), decoder.entities().count());
+        assert!(all.iter().all(|item| item.is_ok()));
+        assert!((2..=total + 1).contains(&reports));
+
+        let items: Vec<_> = decoder
+            .entities_with_progress(|_| true, Progress::new(|done, _| done == 0))
+            .collect();
+        let err = items.last().unwrap().as_ref().unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Cancelled);
+        assert_eq!(
+            items.len(),
+            1,
+            "only the error: the LINE comes late in the map"
+        );
+    }
+
+    #[test]
+    fn decode_entity_finds_single_line_in_samples() {
+        for name in [
+            "line_R14.dwg",
+            "line_2000.dwg",
+            "line_2004.dwg",
+            "line_2007.dwg",
+            "line_2010.dwg",
+            "line_2013.dwg",
+        ] {
+            let bytes = std::fs::read(format!("test_dwg/{name}")).expect("sample file");
+            let decoder = Decoder::new(&bytes, ParseConfig::default()).expect("decoder");
+            let index = decoder.build_object_index().expect("object index");
+            let mut handles: Vec<Handle> =
+                index.objects.iter().map(|object| object.handle).collect();
+            handles.sort_by_key(|handle| handle.0);
+            handles.dedup();
+            let lines: Vec<Entity> = handles
+                .into_iter()
+                .filter_map(|handle| decoder.decode_entity(handle).ok())
+                .filter(|entity| matches!(entity, Entity::Line(_)))
+                .collect();
+            assert_eq!(lines.len(), 1, "{name}");
+            assert_eq!(lines[0].type_name(), "LINE");
+            let Entity::Line(line) = &lines[0] else {
+                unreachable!()
+            };
+            assert_eq!(line.ltype_scale, 1.0, "{name}");
+            if name == "line_R14.dwg" {
+                assert_eq!(line.lineweight, None, "{name}");
+            } else {
+                // 29 is the BYLAYER lineweight code.
+                assert_eq!(line.lineweight, Some(29), "{name}");
+            }
+        }
+    }
+
+    #[test]
+    fn object_index_headers_match_record_headers_in_every_version() {
+        for name in [
+            "line_2000.dwg",
+            "line_2007.dwg",
+            "line_2010.dwg",
+            "line_2013.dwg",
+        ] {
+            let bytes = std::fs::read(format!("test_dwg/{name}")).expect("sample file");
+            let decoder = Decoder::new(&bytes, ParseConfig::default()).expect("decoder");
+            let index = decoder.build_object_index().expect("object index");
+            let objects_data = decoder.objects_data().expect("objects data");
+            for item in index.headers(&objects_data, decoder.version()) {
+                let (object, header) = item.expect("header");
+                let record = decoder.parse_object_record(object.offset).expect("record");
+                let expected = crate::dwg::entity_dispatch::parse_object_header_for_version(
+                    &record,
+                    decoder.version(),
+                )
+                .expect("record header");
+                assert_eq!(header.type_code, expected.type_code, "{name}");
+            }
+        }
+    }
+
+    #[test]
+    fn decode_entity_reports_missing_handle() {
+        let bytes = std::fs::read("test_dwg/line_2000.dwg").expect("sample file");
+        let decoder = Decoder::new(&bytes, ParseConfig::default()).expect("decoder");
+        let err = decoder
+            .decode_entity(Handle(0xFFFF_FFFF))
+            .expect_err("missing handle");
+        assert!(matches!(err.kind, ErrorKind::Resolve));
+    }
+
+    #[test]
+    fn strict_dimensions_rejects_guessed_layouts() {
+        let bytes = std::fs::read("test_dwg/acadsharp/sample_AC1027.dwg").expect("sample file");
+        let lenient = Decoder::new(&bytes, ParseConfig::default()).expect("decoder");
+        let entity = lenient.decode_entity(Handle(0x514)).unwrap();
+        assert_eq!(entity.dimension_common().unwrap().layout_score, 0);
+        let guessed = lenient.decode_entity(Handle(0xC96)).unwrap();
+        assert!(guessed.dimension_common().unwrap().is_layout_guessed());
+
+        let config = ParseConfig {
+            strict_dimensions: true,
+            ..ParseConfig::default()
+        };
+        let strict = Decoder::new(&bytes, config).expect("decoder");
+        assert!(strict.decode_entity(Handle(0x514)).is_ok());
+        let err = strict.decode_entity(Handle(0xC96)).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Decode);
+    }
+
+    /// Invariants over every sample: each object-map entry parses to a
+    /// record storing the same handle, and each decoded entity names an
+    /// indexed handle of the type its record declares.
+    #[test]
+    fn object_map_agrees_with_records_in_every_sample() {
+        let mut paths = Vec::new();
+        for dir in ["test_dwg", "test_dwg/acadsharp"] {
+            for entry in std::fs::read_dir(dir).expect("sample dir") {
+                let path = entry.expect("sample entry").path();
+                if path.extension().is_some_and(|ext| ext == "dwg") {
+                    paths.push(path);
+                }
+            }
+        }
+        assert!(paths.len() > 40);
+        for path in paths {
+            let name = path.display();
+            let bytes = std::fs::read(&path).expect("sample file");
+            let decoder = Decoder::new(&bytes, ParseConfig::default()).expect("decoder");
+            let index = decoder.build_object_index().expect("object index");
+            let mut type_codes = HashMap::new();
+            for object in &index.objects {
+                let record = decoder.parse_object_record(object.offset).unwrap();
+                let header =
+                    entity_dispatch::parse_object_header_for_version(&record, &decoder.version)
+                        .unwrap();
+                let mut reader = record.bit_reader();
+                entity_dispatch::skip_object_type_prefix(&mut reader, &decoder.version).unwrap();
+                if matches!(
+                    decoder.version,
+                    DwgVersion::R2000 | DwgVersion::R2004 | DwgVersion::R2007
+                ) {
+                    let _obj_size = reader.read_rl(crate::bit::Endian::Little).unwrap();
+                }
+                let stored = reader.read_h().unwrap().value;
+                assert_eq!(stored, object.handle.0, "{name}");
+                type_codes.insert(object.handle.0, header.type_code);
+            }
+            for (handle, entity) in decoder.entities().filter_map(|item| item.ok()) {
+                assert_eq!(entity.handle(), handle.0, "{name}");
+                let type_code = type_codes[&handle.0];
+                assert_eq!(
+                    entity.type_name(),
+                    decoder.type_name(type_code),
+                    "{name} {:#X}",
+                    handle.0
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn r2010_plus_class_map_names_custom_classes() {
+        for (name, code, class) in [
+            ("line_2010", 504, "TABLESTYLE"),
+            ("line_2013", 505, "MLEADERSTYLE"),
+            ("acadsharp/sample_AC1027", 526, "MULTILEADER"),
+            ("acadsharp/sample_AC1032", 523, "ACAD_TABLE"),
+        ] {
+            let bytes = std::fs::read(format!("test_dwg/{name}.dwg")).expect("sample file");
+            let decoder = Decoder::new(&bytes, ParseConfig::default()).expect("decoder");
+            let map = decoder.dynamic_type_map().expect("class map");
+            assert_eq!(map.get(&code).map(String::as_str), Some(class), "{name}");
+        }
+    }
+}
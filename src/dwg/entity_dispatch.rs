@@ -0,0 +1,1402 @@
+use crate::bit::{BitReader, Endian};
+use crate::core::error::{DwgError, ErrorKind};
+use crate::core::result::Result;
+use crate::dwg::version;
+use crate::entities;
+use crate::entities::Entity;
+use crate::objects;
+
+pub fn decode_line_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::LineEntity> {
+    match version {
+        version::DwgVersion::R14 => entities::decode_line_r14(reader, object_handle),
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_line_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_line_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_line_r2007(reader),
+        _ => entities::decode_line(reader),
+    }
+}
+
+pub fn decode_point_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::PointEntity> {
+    match version {
+        version::DwgVersion::R14 => entities::decode_point_r14(reader, object_handle),
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_point_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_point_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_point_r2007(reader),
+        _ => entities::decode_point(reader),
+    }
+}
+
+pub fn decode_arc_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::ArcEntity> {
+    match version {
+        version::DwgVersion::R14 => entities::decode_arc_r14(reader, object_handle),
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_arc_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_arc_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_arc_r2007(reader),
+        _ => entities::decode_arc(reader),
+    }
+}
+
+pub fn decode_circle_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::CircleEntity> {
+    match version {
+        version::DwgVersion::R14 => entities::decode_circle_r14(reader, object_handle),
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_circle_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_circle_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_circle_r2007(reader),
+        _ => entities::decode_circle(reader),
+    }
+}
+
+pub fn decode_ellipse_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::EllipseEntity> {
+    match version {
+        version::DwgVersion::R14 => entities::decode_ellipse_r14(reader, object_handle),
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_ellipse_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_ellipse_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_ellipse_r2007(reader),
+        _ => entities::decode_ellipse(reader),
+    }
+}
+
+pub fn decode_spline_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::SplineEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_spline_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_spline_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_spline_r2007(reader),
+        _ => entities::decode_spline(reader),
+    }
+}
+
+pub fn decode_text_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::TextEntity> {
+    match version {
+        version::DwgVersion::R14 => entities::decode_text_r14(reader, object_handle),
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_text_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_text_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_text_r2007(reader),
+        _ => entities::decode_text(reader),
+    }
+}
+
+pub fn decode_insert_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::InsertEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_insert_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_insert_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_insert_r2007(reader),
+        version::DwgVersion::R2004 => entities::decode_insert_r2004(reader),
+        _ => entities::decode_insert(reader),
+    }
+}
+
+pub fn decode_attrib_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::AttribEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_attrib_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_attrib_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_attrib_r2007(reader),
+        _ => entities::decode_attrib(reader),
+    }
+}
+
+pub fn decode_attdef_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::AttribEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_attdef_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_attdef_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_attdef_r2007(reader),
+        _ => entities::decode_attdef(reader),
+    }
+}
+
+pub fn decode_mtext_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::MTextEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_mtext_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_mtext_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_mtext_r2007(reader),
+        version::DwgVersion::R2004 => entities::decode_mtext_r2004(reader),
+        _ => entities::decode_mtext(reader),
+    }
+}
+
+pub fn decode_leader_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::LeaderEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_leader_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_leader_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_leader_r2007(reader),
+        _ => entities::decode_leader(reader),
+    }
+}
+
+pub fn decode_hatch_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::HatchEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_hatch_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_hatch_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_hatch_r2007(reader),
+        version::DwgVersion::R2004 => entities::decode_hatch_r2004(reader),
+        _ => entities::decode_hatch(reader),
+    }
+}
+
+pub fn decode_tolerance_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::ToleranceEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_tolerance_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_tolerance_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_tolerance_r2007(reader),
+        _ => entities::decode_tolerance(reader),
+    }
+}
+
+pub fn decode_mline_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::MLineEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_mline_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_mline_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_mline_r2007(reader),
+        _ => entities::decode_mline(reader),
+    }
+}
+
+pub fn decode_dim_linear_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::DimLinearEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_dim_linear_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_dim_linear_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_dim_linear_r2007(reader),
+        _ => entities::decode_dim_linear(reader),
+    }
+}
+
+pub fn decode_dim_ang3pt_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::DimAng3PtEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_dim_ang3pt_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_dim_ang3pt_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_dim_ang3pt_r2007(reader),
+        _ => entities::decode_dim_ang3pt(reader),
+    }
+}
+
+pub fn decode_dim_ang2ln_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::DimAng2LnEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_dim_ang2ln_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_dim_ang2ln_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_dim_ang2ln_r2007(reader),
+        _ => entities::decode_dim_ang2ln(reader),
+    }
+}
+
+pub fn decode_dim_radius_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::DimRadiusEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_dim_radius_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_dim_radius_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_dim_radius_r2007(reader),
+        _ => entities::decode_dim_radius(reader),
+    }
+}
+
+pub fn decode_dim_diameter_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::DimDiameterEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_dim_diameter_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_dim_diameter_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_dim_diameter_r2007(reader),
+        _ => entities::decode_dim_diameter(reader),
+    }
+}
+
+pub fn decode_lwpolyline_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::LwPolylineEntity> {
+    match version {
+        version::DwgVersion::R14 => {
+            entities::decode_lwpolyline_r14(reader, object_handle, header.type_code)
+        }
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_lwpolyline_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_lwpolyline_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_lwpolyline_r2007(reader),
+        _ => entities::decode_lwpolyline(reader),
+    }
+}
+
+pub fn decode_polyline_2d_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    object_handle: u64,
+) -> Result<entities::Polyline2dEntity> {
+    match version {
+        version::DwgVersion::R14 => entities::decode_polyline_2d_r14(reader, object_handle),
+        _ => entities::decode_polyline_2d(reader),
+    }
+}
+
+pub fn decode_polyline_3d_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::Polyline3dEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_polyline_3d_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_polyline_3d_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_polyline_3d_r2007(reader),
+        _ => entities::decode_polyline_3d(reader),
+    }
+}
+
+pub fn decode_vertex_3d_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::Vertex3dEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_vertex_3d_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_vertex_3d_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_vertex_3d_r2007(reader),
+        _ => entities::decode_vertex_3d(reader),
+    }
+}
+
+pub fn decode_polyline_mesh_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::PolylineMeshEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_polyline_mesh_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_polyline_mesh_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_polyline_mesh_r2007(reader),
+        _ => entities::decode_polyline_mesh(reader),
+    }
+}
+
+pub fn decode_polyline_pface_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::PolylinePFaceEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_polyline_pface_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_polyline_pface_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_polyline_pface_r2007(reader),
+        _ => entities::decode_polyline_pface(reader),
+    }
+}
+
+pub fn decode_vertex_pface_face_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::VertexPFaceFaceEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_vertex_pface_face_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_vertex_pface_face_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_vertex_pface_face_r2007(reader),
+        _ => entities::decode_vertex_pface_face(reader),
+    }
+}
+
+pub fn decode_3dface_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::Face3dEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_3dface_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_3dface_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_3dface_r2007(reader),
+        _ => entities::decode_3dface(reader),
+    }
+}
+
+pub fn decode_solid_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::SolidEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_solid_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_solid_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_solid_r2007(reader),
+        _ => entities::decode_solid(reader),
+    }
+}
+
+pub fn decode_trace_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::TraceEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_trace_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_trace_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_trace_r2007(reader),
+        _ => entities::decode_trace(reader),
+    }
+}
+
+pub fn decode_shape_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::ShapeEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_shape_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_shape_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_shape_r2007(reader),
+        _ => entities::decode_shape(reader),
+    }
+}
+
+pub fn decode_proxy_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::ProxyEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_proxy_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_proxy_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_proxy_r2018(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_proxy_r2007(reader),
+        _ => entities::decode_proxy(reader),
+    }
+}
+
+pub fn decode_image_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::ImageEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_image_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_image_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_image_r2007(reader),
+        _ => entities::decode_image(reader),
+    }
+}
+
+pub fn decode_mleader_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::MLeaderEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_mleader_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_mleader_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_mleader_r2007(reader),
+        _ => entities::decode_mleader(reader),
+    }
+}
+
+/// R2007 tables are not decoded; R2010+ embed their cells as TABLECONTENT.
+pub fn decode_table_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::TableEntity> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_table_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_table_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2004 => entities::decode_table_r2004(reader),
+        version::DwgVersion::R2000 => entities::decode_table(reader),
+        other => Err(DwgError::not_implemented(format!(
+            "ACAD_TABLE decoding is not implemented for {}",
+            other.as_str()
+        ))),
+    }
+}
+
+pub fn decode_image_def_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<entities::ImageDefObject> {
+    match version {
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_image_def_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::decode_image_def_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => entities::decode_image_def_r2007(reader),
+        _ => entities::decode_image_def(reader),
+    }
+}
+
+pub fn decode_appid_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<objects::AppIdObject> {
+    match version {
+        version::DwgVersion::R14 => objects::decode_appid_r14(reader),
+        version::DwgVersion::R2000 => objects::decode_appid_r2000(reader),
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            objects::decode_appid_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            objects::decode_appid_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => objects::decode_appid_r2007(reader),
+        _ => objects::decode_appid(reader),
+    }
+}
+
+pub fn decode_block_header_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<objects::BlockHeaderObject> {
+    match version {
+        version::DwgVersion::R14 => objects::decode_block_header_r14(reader),
+        version::DwgVersion::R2000 => objects::decode_block_header_r2000(reader),
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            objects::decode_block_header_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            objects::decode_block_header_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => objects::decode_block_header_r2007(reader),
+        _ => objects::decode_block_header(reader),
+    }
+}
+
+pub fn decode_vport_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<objects::VportObject> {
+    match version {
+        version::DwgVersion::R14 => objects::decode_vport_r14(reader),
+        version::DwgVersion::R2000 => objects::decode_vport_r2000(reader),
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            objects::decode_vport_r2010(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            objects::decode_vport_r2013(reader, object_data_end_bit, object_handle)
+        }
+        version::DwgVersion::R2007 => objects::decode_vport_r2007(reader),
+        _ => objects::decode_vport(reader),
+    }
+}
+
+pub fn resolve_r2010_object_data_end_bit(header: &ObjectHeader) -> Result<u32> {
+    if header.handle_stream_size_bits.is_none() {
+        return Err(DwgError::new(
+            ErrorKind::Format,
+            "missing R2010 handle stream size",
+        ));
+    }
+    header.object_data_end_bit.ok_or_else(|| {
+        DwgError::new(
+            ErrorKind::Format,
+            "R2010 handle stream exceeds object data size",
+        )
+    })
+}
+
+pub fn resolve_r2010_object_data_end_bit_candidates(header: &ObjectHeader) -> Vec<u32> {
+    let total_bits = header.data_size.saturating_mul(8);
+    let Some(handle_bits) = header.handle_stream_size_bits else {
+        return Vec::new();
+    };
+
+    let bases = [
+        total_bits.saturating_sub(handle_bits),
+        total_bits.saturating_sub(handle_bits.saturating_sub(8)),
+    ];
+    let deltas = [-16i32, -8, 0, 8, 16];
+
+    let mut out = Vec::new();
+    for base in bases {
+        for delta in deltas {
+            let candidate_i64 = i64::from(base) + i64::from(delta);
+            if candidate_i64 < 0 {
+                continue;
+            }
+            let Ok(candidate) = u32::try_from(candidate_i64) else {
+                continue;
+            };
+            if candidate > total_bits {
+                continue;
+            }
+            out.push(candidate);
+        }
+    }
+    out.sort_unstable();
+    out.dedup();
+    out
+}
+
+pub fn skip_object_type_prefix(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+) -> Result<u16> {
+    match version {
+        version::DwgVersion::R2010 | version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let _handle_stream_size_bits = reader.read_umc()?;
+            let type_code = reader.read_ot_r2010()?;
+            if type_code == 0 {
+                return Err(DwgError::new(ErrorKind::Format, "object type code is zero"));
+            }
+            Ok(type_code)
+        }
+        _ => {
+            let type_code = reader.read_bs()?;
+            if type_code == 0 {
+                return Err(DwgError::new(ErrorKind::Format, "object type code is zero"));
+            }
+            Ok(type_code)
+        }
+    }
+}
+
+/// Layout an entity belongs to, from the entity mode in its common header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntityOwner {
+    ModelSpace,
+    /// The active paper-space layout.
+    PaperSpace,
+    /// Owned by the BLOCK_HEADER with this handle: a block definition or a
+    /// paper-space layout other than the active one.
+    Block(u64),
+}
+
+/// Read the common entity header of an entity record positioned after its
+/// type code.
+pub fn parse_common_entity_header_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+) -> Result<entities::common::CommonEntityHeader> {
+    Ok(match version {
+        version::DwgVersion::R14 => entities::common::parse_common_entity_header_r14(reader)?,
+        version::DwgVersion::R2010 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::common::parse_common_entity_header_r2010(reader, object_data_end_bit)?
+        }
+        version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let object_data_end_bit = resolve_r2010_object_data_end_bit(header)?;
+            entities::common::parse_common_entity_header_r2013(reader, object_data_end_bit)?
+        }
+        version::DwgVersion::R2007 => entities::common::parse_common_entity_header_r2007(reader)?,
+        _ => entities::common::parse_common_entity_header(reader)?,
+    })
+}
+
+/// Read just the common entity header (and, for block-owned entities, the
+/// owner handle) of an entity record positioned after its type code.
+pub fn parse_entity_owner_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<EntityOwner> {
+    let mut common = parse_common_entity_header_for_version(reader, version, header)?;
+    // Owner references are relative to the object's own handle, which the
+    // object map records reliably.
+    common.handle = object_handle;
+    match common.entity_mode {
+        0 => {
+            reader.set_bit_pos(common.handle_stream_start_bit());
+            let owner = entities::common::read_handle_reference(reader, common.handle)?;
+            Ok(EntityOwner::Block(owner))
+        }
+        1 => Ok(EntityOwner::PaperSpace),
+        2 => Ok(EntityOwner::ModelSpace),
+        mode => Err(DwgError::new(
+            ErrorKind::Format,
+            format!("invalid entity mode {mode}"),
+        )),
+    }
+}
+
+/// Read just the common entity header and the layer handle of an entity
+/// record positioned after its type code.
+pub fn parse_entity_layer_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+) -> Result<u64> {
+    let mut common = parse_common_entity_header_for_version(reader, version, header)?;
+    common.handle = object_handle;
+    reader.set_bit_pos(common.handle_stream_start_bit());
+    entities::common::parse_common_entity_layer_handle(reader, &common)
+}
+
+/// Name of a symbol table record (BLOCK_HEADER, LAYER, ...) positioned
+/// after its type code. R2007+ keep the name in the string stream, which
+/// this does not read.
+pub fn decode_table_entry_name_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+) -> Result<String> {
+    if !matches!(
+        version,
+        version::DwgVersion::R2010 | version::DwgVersion::R2013 | version::DwgVersion::R2018
+    ) {
+        let _obj_size = reader.read_rl(Endian::Little)?;
+    }
+    let _record_handle = reader.read_h()?;
+    entities::common::skip_eed(reader)?;
+    let _num_reactors = reader.read_bl()?;
+    let _xdic_missing_flag = reader.read_b()?;
+    reader.read_tv()
+}
+
+/// Version-neutral summary of an object header: enough to pick and drive
+/// the per-version entity decoder.
+#[derive(Debug, Clone, Copy)]
+pub struct ObjectHeader {
+    pub data_size: u32,
+    pub type_code: u16,
+    pub handle_stream_size_bits: Option<u32>,
+    /// R2010+: where the data and string streams end, from
+    /// [`ObjectHeaderR2010::object_data_end_bit`](objects::ObjectHeaderR2010::object_data_end_bit).
+    pub object_data_end_bit: Option<u32>,
+}
+
+pub fn parse_object_header_for_version(
+    record: &objects::ObjectRecord<'_>,
+    version: &version::DwgVersion,
+) -> Result<ObjectHeader> {
+    match version {
+        version::DwgVersion::R2010 | version::DwgVersion::R2013 | version::DwgVersion::R2018 => {
+            let header = objects::object_header_r2010::parse_from_record(record)?;
+            Ok(ObjectHeader {
+                data_size: header.data_size,
+                type_code: header.type_code,
+                handle_stream_size_bits: Some(header.handle_stream_size_bits),
+                object_data_end_bit: header.object_data_end_bit(),
+            })
+        }
+        _ => {
+            let header = objects::object_header_r2000::parse_from_record(record)?;
+            Ok(ObjectHeader {
+                data_size: header.data_size,
+                type_code: header.type_code,
+                handle_stream_size_bits: None,
+                object_data_end_bit: None,
+            })
+        }
+    }
+}
+
+/// Entity type names [`decode_entity_for_version`] has a decoder for.
+pub const DECODED_ENTITY_TYPES: &[&str] = &[
+    "TEXT",
+    "ATTRIB",
+    "ATTDEF",
+    "SEQEND",
+    "INSERT",
+    "MINSERT",
+    "VERTEX_2D",
+    "VERTEX_3D",
+    "VERTEX_MESH",
+    "VERTEX_PFACE",
+    "VERTEX_PFACE_FACE",
+    "POLYLINE_2D",
+    "POLYLINE_3D",
+    "ARC",
+    "CIRCLE",
+    "LINE",
+    "DIM_ORDINATE",
+    "DIM_LINEAR",
+    "DIM_ALIGNED",
+    "DIM_ANG3PT",
+    "DIM_ANG2LN",
+    "DIM_RADIUS",
+    "DIM_DIAMETER",
+    "POINT",
+    "3DFACE",
+    "POLYLINE_PFACE",
+    "POLYLINE_MESH",
+    "SOLID",
+    "TRACE",
+    "SHAPE",
+    "ELLIPSE",
+    "SPLINE",
+    "MTEXT",
+    "LEADER",
+    "TOLERANCE",
+    "MLINE",
+    "LWPOLYLINE",
+    "HATCH",
+    "ACAD_PROXY_ENTITY",
+    "IMAGE",
+    "MULTILEADER",
+    "WIPEOUT",
+    "ACAD_TABLE",
+];
+
+/// Decode an entity record whose type prefix has already been consumed,
+/// choosing the decoder from the resolved DWG `type_name`.
+pub fn decode_entity_for_version(
+    reader: &mut BitReader<'_>,
+    version: &version::DwgVersion,
+    header: &ObjectHeader,
+    object_handle: u64,
+    type_name: &str,
+) -> Result<Entity> {
+    let entity = match type_name {
+        "TEXT" => Entity::Text(decode_text_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "ATTRIB" => Entity::Attrib(decode_attrib_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "ATTDEF" => Entity::AttDef(decode_attdef_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "SEQEND" => Entity::Seqend(entities::decode_seqend(reader)?),
+        "INSERT" => Entity::Insert(decode_insert_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "MINSERT" => Entity::MInsert(entities::decode_minsert(reader)?),
+        "VERTEX_2D" => Entity::Vertex2d(entities::decode_vertex_2d(reader)?),
+        "VERTEX_3D" => Entity::Vertex3d(decode_vertex_3d_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "VERTEX_MESH" => Entity::VertexMesh(decode_vertex_3d_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "VERTEX_PFACE" => Entity::VertexPFace(decode_vertex_3d_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "VERTEX_PFACE_FACE" => Entity::VertexPFaceFace(decode_vertex_pface_face_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "POLYLINE_2D" => Entity::Polyline2d(decode_polyline_2d_for_version(
+            reader,
+            version,
+            object_handle,
+        )?),
+        "POLYLINE_3D" => Entity::Polyline3d(decode_polyline_3d_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "ARC" => Entity::Arc(decode_arc_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "CIRCLE" => Entity::Circle(decode_circle_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "LINE" => Entity::Line(decode_line_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "DIM_ORDINATE" => Entity::DimOrdinate(decode_dim_linear_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "DIM_LINEAR" => Entity::DimLinear(decode_dim_linear_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "DIM_ALIGNED" => Entity::DimAligned(decode_dim_linear_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "DIM_ANG3PT" => Entity::DimAng3Pt(decode_dim_ang3pt_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "DIM_ANG2LN" => Entity::DimAng2Ln(decode_dim_ang2ln_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "DIM_RADIUS" => Entity::DimRadius(decode_dim_radius_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "DIM_DIAMETER" => Entity::DimDiameter(decode_dim_diameter_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "POINT" => Entity::Point(decode_point_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "3DFACE" => Entity::Face3d(decode_3dface_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "POLYLINE_PFACE" => Entity::PolylinePFace(decode_polyline_pface_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "POLYLINE_MESH" => Entity::PolylineMesh(decode_polyline_mesh_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "SOLID" => Entity::Solid(decode_solid_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "TRACE" => Entity::Trace(decode_trace_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "SHAPE" => Entity::Shape(decode_shape_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "ELLIPSE" => Entity::Ellipse(decode_ellipse_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "SPLINE" => Entity::Spline(decode_spline_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "MTEXT" => Entity::MText(decode_mtext_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "LEADER" => Entity::Leader(decode_leader_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "TOLERANCE" => Entity::Tolerance(decode_tolerance_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "MLINE" => Entity::MLine(decode_mline_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "LWPOLYLINE" => Entity::LwPolyline(decode_lwpolyline_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "HATCH" => Entity::Hatch(decode_hatch_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "ACAD_PROXY_ENTITY" => Entity::Proxy(decode_proxy_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "IMAGE" => Entity::Image(decode_image_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "MULTILEADER" => Entity::MLeader(decode_mleader_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "WIPEOUT" => Entity::Wipeout(decode_image_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        "ACAD_TABLE" => Entity::Table(decode_table_for_version(
+            reader,
+            version,
+            header,
+            object_handle,
+        )?),
+        other => return Err(DwgError::unsupported_entity(other)),
+    };
+    Ok(entity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::test_support::Bits;
+
+    #[test]
+    fn decoded_entity_types_match_the_dispatch() {
+        let header = ObjectHeader {
+            data_size: 0,
+            type_code: 0,
+            handle_stream_size_bits: None,
+            object_data_end_bit: None,
+        };
+        let decode = |type_name: &str| {
+            let mut reader = BitReader::new(&[]);
+            decode_entity_for_version(
+                &mut reader,
+                &version::DwgVersion::R2000,
+                &header,
+                1,
+                type_name,
+            )
+            .err()
+            .map(|err| err.kind)
+        };
+        for type_name in DECODED_ENTITY_TYPES {
+            assert_ne!(
+                decode(type_name),
+                Some(ErrorKind::UnsupportedEntity),
+                "{type_name}"
+            );
+        }
+        assert_eq!(decode("3DSOLID"), Some(ErrorKind::UnsupportedEntity));
+    }
+
+    #[test]
+    fn dispatches_proxy_entities_with_their_graphics_extents() {
+        // Proxy graphics: total size, one EXTENTS chunk of two RD points.
+        let mut graphics: Vec<u8> = Vec::new();
+        graphics.extend_from_slice(&64u32.to_le_bytes());
+        graphics.extend_from_slice(&1u32.to_le_bytes());
+        graphics.extend_from_slice(&56u32.to_le_bytes());
+        graphics.extend_from_slice(&1u32.to_le_bytes());
+        for value in [1.0f64, 2.0, 0.0, 4.0, 6.0, 0.0] {
+            graphics.extend_from_slice(&value.to_le_bytes());
+        }
+
+        let mut body = Bits::default();
+        // Model space, lineweight BYLAYER.
+        body.common_entity_header_with(0x40, 0b10, &graphics, 29);
+        body.push(0b00, 2); // class id BL, four-byte form
+        body.push(u64::from(500u32.swap_bytes()), 32);
+        body.small(21); // object drawing format
+        body.push(0, 1); // not from DXF
+        let mut handles = Bits::default();
+        handles.handle(5, 0x10); // layer
+        let data = Bits::record(body, handles);
+
+        let header = ObjectHeader {
+            data_size: data.len() as u32,
+            type_code: 0x1F2,
+            handle_stream_size_bits: None,
+            object_data_end_bit: None,
+        };
+        let entity = decode_entity_for_version(
+            &mut BitReader::new(&data),
+            &version::DwgVersion::R2000,
+            &header,
+            0x40,
+            "ACAD_PROXY_ENTITY",
+        )
+        .unwrap();
+        assert_eq!(entity.type_name(), "ACAD_PROXY_ENTITY");
+        let Entity::Proxy(proxy) = entity else {
+            unreachable!()
+        };
+        assert_eq!(proxy.handle, 0x40);
+        assert_eq!(proxy.layer_handle, 0x10);
+        assert_eq!(proxy.lineweight, Some(29));
+        assert_eq!(proxy.class_id, 500);
+        assert_eq!(proxy.object_drawing_format, 21);
+        assert!(!proxy.from_dxf);
+        assert_eq!(proxy.graphics_size, graphics.len());
+        assert_eq!(
+            proxy.graphics_extents,
+            Some(((1.0, 2.0, 0.0), (4.0, 6.0, 0.0)))
+        );
+    }
+}
@@ -3,6 +3,7 @@ use std::io::Read;
 use std::path::Path;
 
 use crate::core::result::Result;
+use crate::dwg::version::{sniff_bytes, FileKind, SNIFF_LEN};
 
 pub fn read_file(path: impl AsRef<Path>) -> Result<Vec<u8>> {
     let mut file = File::open(path.as_ref())?;
@@ -17,3 +18,12 @@ pub fn read_version_tag(path: impl AsRef<Path>) -> Result<[u8; 6]> {
     file.read_exact(&mut tag)?;
     Ok(tag)
 }
+
+/// [`sniff_bytes`] on the start of the file at `path`, reading no more than
+/// it needs.
+pub fn sniff(path: impl AsRef<Path>) -> Result<FileKind> {
+    let file = File::open(path.as_ref())?;
+    let mut head = Vec::with_capacity(SNIFF_LEN);
+    file.take(SNIFF_LEN as u64).read_to_end(&mut head)?;
+    Ok(sniff_bytes(&head))
+}
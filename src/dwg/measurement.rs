@@ -0,0 +1,72 @@
+//! `AcDb:Template`: the MEASUREMENT setting, which tells whether the
+//! drawing was started from imperial or metric defaults.
+//!
+//! Stored as locator record 4 in R13-R2000 files and as a named section from
+//! R2004 on. Both hold an RS template description length, the description
+//! and the RS MEASUREMENT value; R2007+ store the description as UTF-16.
+
+use crate::core::error::{DwgError, ErrorKind};
+use crate::core::result::Result;
+use crate::dwg::version::DwgVersion;
+use crate::io::ByteReader;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Measurement {
+    /// Inch-based linetypes and hatch patterns (`acad.lin`, `acad.pat`).
+    Imperial,
+    /// Millimetre-based ones (`acadiso.lin`, `acadiso.pat`).
+    Metric,
+}
+
+impl Measurement {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Imperial => "imperial",
+            Self::Metric => "metric",
+        }
+    }
+}
+
+/// `None` for an empty section, which some writers leave behind.
+pub fn parse_measurement(data: &[u8], version: &DwgVersion) -> Result<Option<Measurement>> {
+    if data.is_empty() {
+        return Ok(None);
+    }
+    let unit_size = match version {
+        DwgVersion::R2007 | DwgVersion::R2010 | DwgVersion::R2013 | DwgVersion::R2018 => 2,
+        _ => 1,
+    };
+    let mut reader = ByteReader::new(data);
+    let description_len = usize::from(reader.read_u16_le()?);
+    reader.skip(description_len * unit_size)?;
+    match reader.read_u16_le()? {
+        0 => Ok(Some(Measurement::Imperial)),
+        1 => Ok(Some(Measurement::Metric)),
+        other => Err(DwgError::new(
+            ErrorKind::Format,
+            format!("invalid MEASUREMENT value: {other}"),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_the_description_in_version_units() {
+        let r2000 = [3, 0, b'I', b'S', b'O', 1, 0];
+        assert_eq!(
+            parse_measurement(&r2000, &DwgVersion::R2000).unwrap(),
+            Some(Measurement::Metric)
+        );
+        // R2007+: one UTF-16 NUL, then the value and section padding.
+        let r2007 = [1, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(
+            parse_measurement(&r2007, &DwgVersion::R2007).unwrap(),
+            Some(Measurement::Imperial)
+        );
+        assert_eq!(parse_measurement(&[], &DwgVersion::R2004).unwrap(), None);
+        assert!(parse_measurement(&[0, 0, 2, 0], &DwgVersion::R2004).is_err());
+    }
+}
@@ -1,6 +1,15 @@
+pub mod analyze;
+pub mod aux_header;
+#[cfg(feature = "cache")]
+pub mod cache;
+pub mod compression;
 pub mod decoder;
+pub mod entity_dispatch;
 pub mod file_open;
+pub mod measurement;
+pub mod preview;
 pub mod r2000;
 pub mod r2004;
 pub mod r2007;
+pub mod record_scan;
 pub mod version;
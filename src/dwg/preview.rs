@@ -0,0 +1,184 @@
+//! Thumbnail preview saved with the drawing.
+//!
+//! Every release stores an RL file offset at 0x0D pointing at the preview
+//! block: a sentinel, its size, then one `(code, start, size)` entry per
+//! image. Starts are absolute file offsets, also in R2004+ files, where the
+//! block lies in an uncompressed `AcDb:Preview` page.
+
+use crate::core::error::{DwgError, ErrorKind};
+use crate::core::result::Result;
+use crate::io::ByteReader;
+
+const PREVIEW_ADDRESS_OFFSET: usize = 0x0D;
+
+const SENTINEL: [u8; 16] = [
+    0x1F, 0x25, 0x6D, 0x07, 0xD4, 0x36, 0x28, 0x28, 0x9D, 0x57, 0xCA, 0x3F, 0x9D, 0x44, 0x10, 0x2B,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThumbnailFormat {
+    Bmp,
+    Wmf,
+    Png,
+}
+
+impl ThumbnailFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Bmp => "bmp",
+            Self::Wmf => "wmf",
+            Self::Png => "png",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Thumbnail {
+    pub format: ThumbnailFormat,
+    /// A complete image file. DWG stores BMP previews as a bare DIB; the
+    /// BMP file header is added here so the bytes can be written out as is.
+    pub data: Vec<u8>,
+}
+
+/// The preview image of a DWG file, preferring PNG over BMP over WMF when
+/// several are stored. `None` when the file was saved without one.
+pub fn read_thumbnail(bytes: &[u8]) -> Result<Option<Thumbnail>> {
+    let mut reader = ByteReader::new(bytes);
+    reader.seek(PREVIEW_ADDRESS_OFFSET)?;
+    let address = reader.read_u32_le()? as usize;
+    if address == 0 {
+        return Ok(None);
+    }
+    reader.seek(address)?;
+    if reader.read_bytes(SENTINEL.len())? != SENTINEL {
+        return Err(
+            DwgError::new(ErrorKind::SentinelMismatch, "preview sentinel mismatch")
+                .with_offset(address as u64),
+        );
+    }
+    let _size = reader.read_u32_le()?;
+    let count = reader.read_u8()?;
+    let mut best: Option<(ThumbnailFormat, usize, usize)> = None;
+    for _ in 0..count {
+        let code = reader.read_u8()?;
+        let start = reader.read_u32_le()? as usize;
+        let size = reader.read_u32_le()? as usize;
+        // Code 1 is the preview header data, not an image.
+        let format = match code {
+            2 => ThumbnailFormat::Bmp,
+            3 => ThumbnailFormat::Wmf,
+            6 => ThumbnailFormat::Png,
+            _ => continue,
+        };
+        let rank = |format: ThumbnailFormat| match format {
+            ThumbnailFormat::Png => 0,
+            ThumbnailFormat::Bmp => 1,
+            ThumbnailFormat::Wmf => 2,
+        };
+        if size > 0 && best.is_none_or(|(current, _, _)| rank(format) < rank(current)) {
+            best = Some((format, start, size));
+        }
+    }
+    let Some((format, start, size)) = best else {
+        return Ok(None);
+    };
+    let image = start
+        .checked_add(size)
+        .and_then(|end| bytes.get(start..end))
+        .ok_or_else(|| {
+            DwgError::new(ErrorKind::Format, "preview image runs past end of file")
+                .with_offset(start as u64)
+        })?;
+    let data = match format {
+        ThumbnailFormat::Bmp => bmp_file(image)?,
+        _ => image.to_vec(),
+    };
+    Ok(Some(Thumbnail { format, data }))
+}
+
+/// `dib` with a BITMAPFILEHEADER in front. The pixel offset skips the info
+/// header and the palette, which 8-bit and smaller images carry.
+fn bmp_file(dib: &[u8]) -> Result<Vec<u8>> {
+    let mut reader = ByteReader::new(dib);
+    let header_size = reader.read_u32_le()?;
+    reader.seek(14)?;
+    let bit_count = reader.read_u16_le()?;
+    reader.seek(32)?;
+    let colors_used = reader.read_u32_le()?;
+    let palette_len = match (colors_used, bit_count) {
+        (0, 1..=8) => 1u32 << bit_count,
+        (colors, _) => colors,
+    };
+    let pixel_offset = palette_len
+        .checked_mul(4)
+        .and_then(|palette_bytes| palette_bytes.checked_add(header_size))
+        .and_then(|offset| offset.checked_add(14))
+        .ok_or_else(|| DwgError::new(ErrorKind::Format, "preview bitmap palette too large"))?;
+    let file_size = u32::try_from(14 + dib.len())
+        .map_err(|_| DwgError::new(ErrorKind::Format, "preview bitmap too large"))?;
+    let mut file = Vec::with_capacity(14 + dib.len());
+    file.extend_from_slice(b"BM");
+    file.extend_from_slice(&file_size.to_le_bytes());
+    file.extend_from_slice(&[0; 4]);
+    file.extend_from_slice(&pixel_offset.to_le_bytes());
+    file.extend_from_slice(dib);
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_bmp_and_png_previews() {
+        for (name, format) in [
+            ("line_R14.dwg", Some(ThumbnailFormat::Bmp)),
+            ("line_2000.dwg", Some(ThumbnailFormat::Bmp)),
+            ("line_2007.dwg", Some(ThumbnailFormat::Bmp)),
+            ("line_2013.dwg", Some(ThumbnailFormat::Png)),
+            ("text_2004.dwg", None),
+        ] {
+            let bytes = std::fs::read(format!("test_dwg/{name}")).expect("sample file");
+            let thumbnail = read_thumbnail(&bytes).unwrap();
+            assert_eq!(thumbnail.as_ref().map(|thumbnail| thumbnail.format), format);
+            let Some(thumbnail) = thumbnail else {
+                continue;
+            };
+            match thumbnail.format {
+                ThumbnailFormat::Png => assert!(thumbnail.data.starts_with(b"\x89PNG")),
+                _ => {
+                    assert!(thumbnail.data.starts_with(b"BM"), "{name}");
+                    let size = u32::from_le_bytes(thumbnail.data[2..6].try_into().unwrap());
+                    assert_eq!(size as usize, thumbnail.data.len());
+                    // 8-bit sample previews: 40-byte header and 256 colors.
+                    let offset = u32::from_le_bytes(thumbnail.data[10..14].try_into().unwrap());
+                    assert_eq!(offset, 14 + 40 + 256 * 4, "{name}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_a_preview_address_without_sentinel() {
+        let mut bytes = std::fs::read("test_dwg/line_2004.dwg").expect("sample file");
+        bytes[PREVIEW_ADDRESS_OFFSET..PREVIEW_ADDRESS_OFFSET + 4]
+            .copy_from_slice(&0x20u32.to_le_bytes());
+        let err = read_thumbnail(&bytes).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::SentinelMismatch);
+        assert_eq!(err.offset, Some(0x20));
+    }
+
+    #[test]
+    fn rejects_a_bitmap_palette_too_large_to_address() {
+        let mut dib = vec![0; 40];
+        dib[0..4].copy_from_slice(&40u32.to_le_bytes());
+        dib[14..16].copy_from_slice(&8u16.to_le_bytes());
+        dib[32..36].copy_from_slice(&u32::MAX.to_le_bytes());
+        let err = bmp_file(&dib).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Format);
+
+        dib[32..36].copy_from_slice(&0u32.to_le_bytes());
+        let file = bmp_file(&dib).unwrap();
+        assert_eq!(file[10..14], (14u32 + 40 + 256 * 4).to_le_bytes());
+    }
+}
@@ -1,7 +1,7 @@
 use crate::bit::{BitReader, Endian};
 use crate::container::section_directory;
 use crate::container::section_loader;
-use crate::container::SectionKind;
+use crate::container::{SectionInfo, SectionKind, SECTION_UNCOMPRESSED};
 use crate::core::config::ParseConfig;
 use crate::core::error::{DwgError, ErrorKind};
 use crate::core::result::Result;
@@ -30,6 +30,23 @@ pub fn load_section_by_index<'a>(
     section_loader::load_section_by_index(bytes, directory, index, config)
 }
 
+/// R13-R15 sections are stored as single uncompressed, unencrypted blocks.
+pub fn list_sections(bytes: &[u8], config: &ParseConfig) -> Result<Vec<SectionInfo>> {
+    let directory = parse_section_directory(bytes, config)?;
+    Ok(directory
+        .records
+        .into_iter()
+        .map(|record| SectionInfo {
+            name: record.name.clone().unwrap_or_else(|| record.kind().label()),
+            size: record.size as u64,
+            compressed_size: record.size as u64,
+            page_count: 1,
+            encrypted: 0,
+            compression: SECTION_UNCOMPRESSED,
+        })
+        .collect())
+}
+
 pub fn build_object_index(bytes: &[u8], config: &ParseConfig) -> Result<ObjectIndex> {
     objects::build_object_index(bytes, config)
 }
@@ -46,7 +63,7 @@ pub fn load_dynamic_type_map(bytes: &[u8], config: &ParseConfig) -> Result<HashM
         .position(|record| record.kind() == SectionKind::Classes)
         .ok_or_else(|| DwgError::new(ErrorKind::Format, "section not found: AcDb:Classes"))?;
     let section = load_section_by_index(bytes, &directory, classes_index, config)?;
-    let classes = parse_classes_section_r13_r15(&section.data)?;
+    let (classes, _crc) = parse_classes_section_r13_r15(&section.data)?;
 
     let mut map = HashMap::with_capacity(classes.len());
     let has_explicit_codes = classes.iter().any(|entry| entry.class_number >= 500);
@@ -83,19 +100,50 @@ pub fn load_dynamic_type_map(bytes: &[u8], config: &ParseConfig) -> Result<HashM
     Ok(map)
 }
 
+pub fn section_crcs(bytes: &[u8], config: &ParseConfig) -> Result<Vec<(String, u16)>> {
+    let directory = parse_section_directory(bytes, config)?;
+    let mut crcs = vec![("SectionDirectory".to_string(), directory.crc)];
+    for (index, record) in directory.records.iter().enumerate() {
+        let label = record.name.clone().unwrap_or_else(|| record.kind().label());
+        match record.kind() {
+            SectionKind::Classes => {
+                let section = load_section_by_index(bytes, &directory, index, config)?;
+                if let (_, Some(crc)) = parse_classes_section_r13_r15(&section.data)? {
+                    crcs.push((label, crc));
+                }
+            }
+            SectionKind::ObjectMap => {
+                let section = load_section_by_index(bytes, &directory, index, config)?;
+                for (block, crc) in objects::object_map_block_crcs(&section.data)?
+                    .into_iter()
+                    .enumerate()
+                {
+                    crcs.push((format!("{label}[{block}]"), crc));
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(crcs)
+}
+
 #[derive(Debug, Clone)]
 struct ClassEntry {
     class_number: u16,
     dxf_name: String,
 }
 
-fn parse_classes_section_r13_r15(data: &[u8]) -> Result<Vec<ClassEntry>> {
+/// An empty section, as minimal files store, holds no classes and no CRC.
+fn parse_classes_section_r13_r15(data: &[u8]) -> Result<(Vec<ClassEntry>, Option<u16>)> {
+    if data.is_empty() {
+        return Ok((Vec::new(), None));
+    }
     let mut reader = BitReader::new(data);
 
     let sentinel_before = reader.read_rcs(SENTINEL_CLASSES_BEFORE.len())?;
     if sentinel_before.as_slice() != SENTINEL_CLASSES_BEFORE {
         return Err(DwgError::new(
-            ErrorKind::Format,
+            ErrorKind::SentinelMismatch,
             "AcDb:Classes sentinel(before) mismatch",
         ));
     }
@@ -134,9 +182,10 @@ fn parse_classes_section_r13_r15(data: &[u8]) -> Result<Vec<ClassEntry>> {
         }
     }
 
+    let mut crc = None;
     if let Ok(class_data_end_u32) = u32::try_from(class_data_end) {
         reader.set_bit_pos(class_data_end_u32);
-        let _ = reader.read_crc();
+        crc = reader.read_crc().ok();
         if let Ok(sentinel_after) = reader.read_rcs(SENTINEL_CLASSES_AFTER.len()) {
             if sentinel_after.as_slice() != SENTINEL_CLASSES_AFTER {
                 // Keep best-effort parsed classes when trailing markers are not readable.
@@ -144,5 +193,5 @@ fn parse_classes_section_r13_r15(data: &[u8]) -> Result<Vec<ClassEntry>> {
         }
     }
 
-    Ok(classes)
+    Ok((classes, crc))
 }
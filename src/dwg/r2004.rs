@@ -2,15 +2,24 @@ use std::borrow::Cow;
 use std::collections::HashMap;
 
 use crate::bit::{BitReader, Endian};
-use crate::container::{SectionDirectory, SectionLocatorRecord, SectionSlice};
+use crate::container::{
+    NamedSection, SectionDirectory, SectionInfo, SectionLocatorRecord, SectionSlice,
+    SECTION_COMPRESSED,
+};
 use crate::core::config::ParseConfig;
+use crate::core::diag::diag_event;
 use crate::core::error::{DwgError, ErrorKind};
 use crate::core::result::Result;
+use crate::dwg::compression::decompress_r18;
+use crate::dwg::r2007;
+use crate::dwg::version::DwgVersion;
 use crate::io::ByteReader;
-use crate::objects::object_record::parse_object_record_owned;
+use crate::objects::object_record::{self, parse_object_record_owned};
 use crate::objects::{Handle, ObjectIndex, ObjectRecord, ObjectRef};
 
 const HEADER_OFFSET: usize = 0x80;
+/// File header byte holding the maintenance release number.
+const MAINTENANCE_RELEASE_OFFSET: usize = 0x0B;
 const HEADER_SIZE: usize = 0x6c;
 const SECTION_PAGE_MAP_MAGIC: u32 = 0x41630E3B;
 const SECTION_MAP_MAGIC: u32 = 0x4163003B;
@@ -60,6 +69,7 @@ struct SectionEntry {
 #[derive(Debug, Clone)]
 struct SectionPageInfo {
     page_id: u32,
+    data_size: u32,
 }
 
 #[derive(Debug, Clone)]
@@ -73,10 +83,10 @@ struct ClassEntry {
     dxf_name: String,
 }
 
-pub fn parse_section_directory(bytes: &[u8], _config: &ParseConfig) -> Result<SectionDirectory> {
+pub fn parse_section_directory(bytes: &[u8], config: &ParseConfig) -> Result<SectionDirectory> {
     let header = read_header_data(bytes)?;
-    let page_map = read_page_map(bytes, &header)?;
-    let section_map = read_section_map(bytes, &header, &page_map)?;
+    let page_map = read_page_map(bytes, &header, config)?;
+    let section_map = read_section_map(bytes, &header, &page_map, config)?;
 
     let mut page_lookup = HashMap::with_capacity(page_map.len());
     for entry in page_map {
@@ -116,6 +126,23 @@ pub fn parse_section_directory(bytes: &[u8], _config: &ParseConfig) -> Result<Se
     })
 }
 
+pub fn list_sections(bytes: &[u8], config: &ParseConfig) -> Result<Vec<SectionInfo>> {
+    let header = read_header_data(bytes)?;
+    let page_map = read_page_map(bytes, &header, config)?;
+    let section_map = read_section_map(bytes, &header, &page_map, config)?;
+    Ok(section_map
+        .into_iter()
+        .map(|section| SectionInfo {
+            compressed_size: section.pages.iter().map(|page| page.data_size as u64).sum(),
+            page_count: section.pages.len() as u32,
+            encrypted: section.encrypted,
+            compression: section.compressed,
+            size: section.size,
+            name: section.name,
+        })
+        .collect())
+}
+
 pub fn load_section_by_index<'a>(
     bytes: &'a [u8],
     directory: &SectionDirectory,
@@ -123,8 +150,8 @@ pub fn load_section_by_index<'a>(
     config: &ParseConfig,
 ) -> Result<SectionSlice<'a>> {
     let header = read_header_data(bytes)?;
-    let page_map = read_page_map(bytes, &header)?;
-    let section_map = read_section_map(bytes, &header, &page_map)?;
+    let page_map = read_page_map(bytes, &header, config)?;
+    let section_map = read_section_map(bytes, &header, &page_map, config)?;
     let section = section_map
         .get(index)
         .cloned()
@@ -166,13 +193,20 @@ pub fn load_section_by_index<'a>(
 }
 
 pub fn build_object_index(bytes: &[u8], config: &ParseConfig) -> Result<ObjectIndex> {
-    let handles_data = load_named_section_data(bytes, config, "AcDb:Handles")?;
-    let objects_data = load_named_section_data(bytes, config, "AcDb:AcDbObjects")?;
-    let index = parse_object_map_handles(&handles_data, config)?;
+    let objects_data = load_objects_data(bytes, config)?;
+    build_object_index_with_objects(bytes, config, &objects_data)
+}
+
+pub fn build_object_index_with_objects(
+    bytes: &[u8],
+    config: &ParseConfig,
+    objects_data: &[u8],
+) -> Result<ObjectIndex> {
+    let index = parse_object_map(bytes, config)?;
 
     let mut valid_objects = Vec::with_capacity(index.objects.len());
     for object in index.objects {
-        if parse_object_record_owned(&objects_data, object.offset).is_ok() {
+        if object_record::parse_object_record(objects_data, object.offset).is_ok() {
             valid_objects.push(object);
         }
     }
@@ -180,19 +214,63 @@ pub fn build_object_index(bytes: &[u8], config: &ParseConfig) -> Result<ObjectIn
     Ok(ObjectIndex::from_objects(valid_objects))
 }
 
+/// The `AcDb:Handles` object map as stored, including entries whose records
+/// do not parse.
+pub fn parse_object_map(bytes: &[u8], config: &ParseConfig) -> Result<ObjectIndex> {
+    let handles_data = load_named_section_data(bytes, config, NamedSection::Handles)?;
+    parse_object_map_handles(&handles_data, config)
+}
+
+pub fn load_objects_data(bytes: &[u8], config: &ParseConfig) -> Result<Vec<u8>> {
+    load_named_section_data(bytes, config, NamedSection::AcDbObjects)
+}
+
 pub fn parse_object_record<'a>(
     bytes: &'a [u8],
     offset: u32,
     config: &ParseConfig,
 ) -> Result<ObjectRecord<'a>> {
-    let data = load_named_section_data(bytes, config, "AcDb:AcDbObjects")?;
+    let data = load_objects_data(bytes, config)?;
     let record = parse_object_record_owned(&data, offset)?;
     Ok(record)
 }
 
+pub fn section_crcs(bytes: &[u8], config: &ParseConfig) -> Result<Vec<(String, u16)>> {
+    named_section_crcs(
+        |name| load_named_section_data(bytes, config, name),
+        |data| parse_classes_section(data).map(|(_classes, crc)| crc),
+    )
+}
+
+/// The stored CRCs of the `AcDb:Classes` section and each `AcDb:Handles`
+/// block, from sections read through `load`. Shared by the R2004 and R2007
+/// containers, which only differ in how sections load and classes parse.
+pub(crate) fn named_section_crcs(
+    load: impl Fn(NamedSection) -> Result<Vec<u8>>,
+    classes_crc: impl Fn(&[u8]) -> Result<u16>,
+) -> Result<Vec<(String, u16)>> {
+    let mut crcs = Vec::new();
+    // The classes layout changes in later releases; skip its CRC when it does
+    // not parse, and for an empty section, which stores none.
+    let classes_data = load(NamedSection::Classes)?;
+    if !classes_data.is_empty() {
+        if let Ok(crc) = classes_crc(&classes_data) {
+            crcs.push((NamedSection::Classes.as_str().to_string(), crc));
+        }
+    }
+    let handles_data = load(NamedSection::Handles)?;
+    for (block, crc) in crate::objects::object_map_block_crcs(&handles_data)?
+        .into_iter()
+        .enumerate()
+    {
+        crcs.push((format!("{}[{block}]", NamedSection::Handles.as_str()), crc));
+    }
+    Ok(crcs)
+}
+
 pub fn load_dynamic_type_map(bytes: &[u8], config: &ParseConfig) -> Result<HashMap<u16, String>> {
-    let data = load_named_section_data(bytes, config, "AcDb:Classes")?;
-    let classes = parse_classes_section(&data)?;
+    let data = load_named_section_data(bytes, config, NamedSection::Classes)?;
+    let (classes, _crc) = parse_classes_section(&data)?;
     let mut map = HashMap::with_capacity(classes.len());
     for (idx, class) in classes.iter().enumerate() {
         let code = 500usize + idx;
@@ -204,10 +282,27 @@ pub fn load_dynamic_type_map(bytes: &[u8], config: &ParseConfig) -> Result<HashM
     Ok(map)
 }
 
-fn load_named_section_data(bytes: &[u8], config: &ParseConfig, name: &str) -> Result<Vec<u8>> {
+/// R2010+ files keep the R2007 classes layout in the R2004 container.
+pub fn load_dynamic_type_map_r2010(
+    bytes: &[u8],
+    config: &ParseConfig,
+    version: &DwgVersion,
+) -> Result<HashMap<u16, String>> {
+    let data = load_named_section_data(bytes, config, NamedSection::Classes)?;
+    let maintenance_release = bytes.get(MAINTENANCE_RELEASE_OFFSET).copied().unwrap_or(0);
+    let extra_size_field = !matches!(version, DwgVersion::R2010) || maintenance_release > 3;
+    let (classes, _crc) = r2007::parse_classes_section(&data, extra_size_field)?;
+    Ok(r2007::class_type_map(&classes))
+}
+
+fn load_named_section_data(
+    bytes: &[u8],
+    config: &ParseConfig,
+    name: NamedSection,
+) -> Result<Vec<u8>> {
     let header = read_header_data(bytes)?;
-    let page_map = read_page_map(bytes, &header)?;
-    let section_map = read_section_map(bytes, &header, &page_map)?;
+    let page_map = read_page_map(bytes, &header, config)?;
+    let section_map = read_section_map(bytes, &header, &page_map, config)?;
 
     let mut page_lookup = HashMap::with_capacity(page_map.len());
     for entry in page_map {
@@ -218,8 +313,13 @@ fn load_named_section_data(bytes: &[u8], config: &ParseConfig, name: &str) -> Re
 
     let section = section_map
         .iter()
-        .find(|section| section.name == name)
-        .ok_or_else(|| DwgError::new(ErrorKind::Format, format!("section not found: {name}")))?;
+        .find(|section| section.name == name.as_str())
+        .ok_or_else(|| {
+            DwgError::new(
+                ErrorKind::Format,
+                format!("section not found: {}", name.as_str()),
+            )
+        })?;
 
     load_section_data(bytes, section, &page_lookup, config)
 }
@@ -252,7 +352,12 @@ fn read_header_data(bytes: &[u8]) -> Result<HeaderData> {
     })
 }
 
-fn read_system_section(bytes: &[u8], address: u64, expected_signature: u32) -> Result<Vec<u8>> {
+fn read_system_section(
+    bytes: &[u8],
+    address: u64,
+    expected_signature: u32,
+    config: &ParseConfig,
+) -> Result<Vec<u8>> {
     let offset = address as usize;
     if offset + 0x14 > bytes.len() {
         return Err(DwgError::new(
@@ -289,19 +394,23 @@ fn read_system_section(bytes: &[u8], address: u64, expected_signature: u32) -> R
         return Ok(Vec::new());
     }
     match header.compressed_type {
-        0x02 => decompress_r18(data, header.decompressed_size as usize),
+        0x02 => decompress_r18(data, header.decompressed_size as usize, config),
         _ => Err(DwgError::not_implemented(
             "unsupported R2004 system section compression type",
         )),
     }
 }
 
-fn read_page_map(bytes: &[u8], header: &HeaderData) -> Result<Vec<PageMapEntry>> {
+fn read_page_map(
+    bytes: &[u8],
+    header: &HeaderData,
+    config: &ParseConfig,
+) -> Result<Vec<PageMapEntry>> {
     let page_map_addr = header
         .section_page_map_address
         .checked_add(0x100)
         .ok_or_else(|| DwgError::new(ErrorKind::Format, "section page map address overflow"))?;
-    let data = read_system_section(bytes, page_map_addr, SECTION_PAGE_MAP_MAGIC)?;
+    let data = read_system_section(bytes, page_map_addr, SECTION_PAGE_MAP_MAGIC, config)?;
     let mut reader = ByteReader::new(&data);
     let mut page_address: u64 = 0x100;
     let mut entries = Vec::new();
@@ -328,6 +437,7 @@ fn read_page_map(bytes: &[u8], header: &HeaderData) -> Result<Vec<PageMapEntry>>
         entries.push(entry);
     }
 
+    diag_event!("page_map", "R2004 page map: {} entries", entries.len());
     Ok(entries)
 }
 
@@ -335,6 +445,7 @@ fn read_section_map(
     bytes: &[u8],
     header: &HeaderData,
     page_map: &[PageMapEntry],
+    config: &ParseConfig,
 ) -> Result<Vec<SectionEntry>> {
     let section_map_page = page_map
         .iter()
@@ -342,7 +453,7 @@ fn read_section_map(
         .ok_or_else(|| {
             DwgError::new(ErrorKind::Format, "section map page not found in page map")
         })?;
-    let data = read_system_section(bytes, section_map_page.address, SECTION_MAP_MAGIC)?;
+    let data = read_system_section(bytes, section_map_page.address, SECTION_MAP_MAGIC, config)?;
     let mut reader = ByteReader::new(&data);
     if reader.remaining() < 20 {
         return Err(DwgError::new(
@@ -382,9 +493,9 @@ fn read_section_map(
                 ));
             }
             let page_id = reader.read_u32_le()?;
-            let _data_size = reader.read_u32_le()?;
+            let data_size = reader.read_u32_le()?;
             let _start_offset = reader.read_u64_le()?;
-            pages.push(SectionPageInfo { page_id });
+            pages.push(SectionPageInfo { page_id, data_size });
         }
 
         sections.push(SectionEntry {
@@ -406,6 +517,13 @@ fn load_section_data(
     page_map: &HashMap<u32, PageMapEntry>,
     config: &ParseConfig,
 ) -> Result<Vec<u8>> {
+    diag_event!(
+        "section",
+        "load R2004 section {:?}: {} pages, compressed={}",
+        section.name,
+        section.pages.len(),
+        section.compressed
+    );
     if section.encrypted == 1 {
         return Err(DwgError::not_implemented(
             "encrypted R2004 sections are not supported",
@@ -460,8 +578,19 @@ fn load_section_data(
             ));
         }
         let data = &bytes[data_offset..data_end];
-        let decompressed = if section.compressed == 2 {
-            decompress_r18(data, section.max_decompressed_size as usize)?
+        let decompressed = if section.compressed == SECTION_COMPRESSED {
+            decompress_r18(data, section.max_decompressed_size as usize, config).inspect_err(
+                |err| {
+                    diag_event!(
+                        "decompress",
+                        "R2004 page {} of section {:?} at {:#X}: {}",
+                        page.page_id,
+                        section.name,
+                        entry.address,
+                        err
+                    )
+                },
+            )?
         } else {
             data.to_vec()
         };
@@ -513,13 +642,7 @@ fn parse_data_section_header(bytes: &[u8; 32]) -> Result<DataSectionHeader> {
 }
 
 fn record_no_for_name(name: &str) -> u8 {
-    match name {
-        "AcDb:Header" => 0,
-        "AcDb:Classes" => 1,
-        "AcDb:Handles" => 2,
-        "AcDb:Template" => 4,
-        _ => 255,
-    }
+    NamedSection::from_name(name).map_or(255, |section| section.record_no())
 }
 
 fn read_cstring(bytes: &[u8]) -> String {
@@ -538,13 +661,16 @@ fn magic_sequence() -> [u8; HEADER_SIZE] {
     seq
 }
 
-fn parse_classes_section(data: &[u8]) -> Result<Vec<ClassEntry>> {
+fn parse_classes_section(data: &[u8]) -> Result<(Vec<ClassEntry>, u16)> {
+    if data.is_empty() {
+        return Ok((Vec::new(), 0));
+    }
     let mut reader = BitReader::new(data);
 
     let sentinel_before = reader.read_rcs(SENTINEL_CLASSES_BEFORE.len())?;
     if sentinel_before.as_slice() != SENTINEL_CLASSES_BEFORE {
         return Err(DwgError::new(
-            ErrorKind::Format,
+            ErrorKind::SentinelMismatch,
             "AcDb:Classes sentinel(before) mismatch",
         ));
     }
@@ -577,16 +703,16 @@ fn parse_classes_section(data: &[u8]) -> Result<Vec<ClassEntry>> {
         }
     }
 
-    let _crc = reader.read_crc()?;
+    let crc = reader.read_crc()?;
     let sentinel_after = reader.read_rcs(SENTINEL_CLASSES_AFTER.len())?;
     if sentinel_after.as_slice() != SENTINEL_CLASSES_AFTER {
         return Err(DwgError::new(
-            ErrorKind::Format,
+            ErrorKind::SentinelMismatch,
             "AcDb:Classes sentinel(after) mismatch",
         ));
     }
 
-    Ok(classes)
+    Ok((classes, crc))
 }
 
 fn parse_object_map_handles(bytes: &[u8], config: &ParseConfig) -> Result<ObjectIndex> {
@@ -598,7 +724,7 @@ fn parse_object_map_handles(bytes: &[u8], config: &ParseConfig) -> Result<Object
             break;
         }
 
-        let section_size = read_u16_be(&mut reader)? as usize;
+        let section_size = reader.read_u16_be()? as usize;
         if section_size == 2 {
             break;
         }
@@ -621,8 +747,9 @@ fn parse_object_map_handles(bytes: &[u8], config: &ParseConfig) -> Result<Object
         let mut last_offset: i64 = 0;
 
         while (reader.tell() - start) < (section_size as u64 - 2) {
-            last_handle += read_modular_char(&mut reader)?;
-            last_offset += read_modular_char(&mut reader)?;
+            // Handles only ever increase; only the offset delta is signed.
+            last_handle += reader.read_unsigned_modular_char()? as i64;
+            last_offset += reader.read_modular_char()?;
 
             if last_handle < 0 || last_offset < 0 {
                 return Err(DwgError::new(
@@ -655,253 +782,113 @@ fn parse_object_map_handles(bytes: &[u8], config: &ParseConfig) -> Result<Object
         if reader.remaining() < 2 {
             break;
         }
-        let _crc = read_u16_be(&mut reader)?;
+        let _crc = reader.read_u16_be()?;
     }
 
     Ok(ObjectIndex::from_objects(objects))
 }
 
-fn read_u16_be(reader: &mut ByteReader<'_>) -> Result<u16> {
-    let hi = reader.read_u8()? as u16;
-    let lo = reader.read_u8()? as u16;
-    Ok((hi << 8) | lo)
-}
-
-fn read_modular_char(reader: &mut ByteReader<'_>) -> Result<i64> {
-    let mut value: i64 = 0;
-    let mut shift = 0;
-
-    for _ in 0..4 {
-        let mut byte = reader.read_u8()?;
-        if (byte & 0x80) == 0 {
-            let negative = (byte & 0x40) != 0;
-            if negative {
-                byte &= 0xBF;
-            }
-            value |= (byte as i64) << shift;
-            if negative {
-                value = -value;
-            }
-            return Ok(value);
-        }
-        byte &= 0x7F;
-        value |= (byte as i64) << shift;
-        shift += 7;
-    }
-
-    Ok(value)
-}
-
-fn decompress_r18(src: &[u8], dst_size: usize) -> Result<Vec<u8>> {
-    let mut dst = vec![0u8; dst_size];
-    let mut dst_idx: usize = 0;
-    let mut cursor = Cursor::new(src);
-
-    let (literal_len, mut opcode1) = read_literal_length(&mut cursor)?;
-    dst_idx = copy_literal(&mut dst, dst_idx, src, &mut cursor, literal_len)?;
-
-    while cursor.pos < src.len() {
-        if opcode1 == 0x00 {
-            opcode1 = cursor.read_u8()?;
-        }
-
-        let (compressed_bytes, compressed_offset, next_literal_len, next_opcode1) = match opcode1 {
-            0x10 => {
-                let comp_bytes = read_long_compression_offset(&mut cursor)? + 9;
-                let (offset, literal_count) = read_two_byte_offset(&mut cursor)?;
-                let offset = offset + 0x3FFF;
-                let (literal_len, next_opcode1) = if literal_count == 0 {
-                    read_literal_length(&mut cursor)?
-                } else {
-                    (literal_count, 0x00)
-                };
-                (comp_bytes, offset, literal_len, next_opcode1)
-            }
-            0x11 => break,
-            0x12..=0x1F => {
-                let comp_bytes = (opcode1 & 0x0F) as usize + 2;
-                let (offset, literal_count) = read_two_byte_offset(&mut cursor)?;
-                let offset = offset + 0x3FFF;
-                let (literal_len, next_opcode1) = if literal_count == 0 {
-                    read_literal_length(&mut cursor)?
-                } else {
-                    (literal_count, 0x00)
-                };
-                (comp_bytes, offset, literal_len, next_opcode1)
-            }
-            0x20 => {
-                let comp_bytes = read_long_compression_offset(&mut cursor)? + 0x21;
-                let (offset, literal_count) = read_two_byte_offset(&mut cursor)?;
-                let (literal_len, next_opcode1) = if literal_count == 0 {
-                    read_literal_length(&mut cursor)?
-                } else {
-                    (literal_count, 0x00)
-                };
-                (comp_bytes, offset, literal_len, next_opcode1)
-            }
-            0x21..=0x3F => {
-                let comp_bytes = (opcode1 - 0x1E) as usize;
-                let (offset, literal_count) = read_two_byte_offset(&mut cursor)?;
-                let (literal_len, next_opcode1) = if literal_count == 0 {
-                    read_literal_length(&mut cursor)?
-                } else {
-                    (literal_count, 0x00)
-                };
-                (comp_bytes, offset, literal_len, next_opcode1)
-            }
-            0x40..=0xFF => {
-                let comp_bytes = ((opcode1 & 0xF0) >> 4) as usize - 1;
-                let opcode2 = cursor.read_u8()? as usize;
-                let offset = (opcode2 << 2) | ((opcode1 as usize & 0x0C) >> 2);
-                if opcode1 & 0x03 != 0 {
-                    let literal_len = (opcode1 & 0x03) as usize;
-                    (comp_bytes, offset, literal_len, 0x00)
-                } else {
-                    let (literal_len, next_opcode1) = read_literal_length(&mut cursor)?;
-                    (comp_bytes, offset, literal_len, next_opcode1)
+/// `bytes` with the `names` sections emptied in place of their pages, for
+/// tests. The rewritten section map, stored as one literal run, and a page
+/// map pointing at it are appended; the old section map page becomes a gap.
+#[cfg(test)]
+pub(crate) fn with_empty_sections(bytes: &[u8], names: &[NamedSection]) -> Vec<u8> {
+    fn system_section(signature: u32, data: &[u8]) -> Vec<u8> {
+        // A literal run of `data.len()` bytes, then the end opcode.
+        let mut stream = match data.len() - 3 {
+            len @ 1..=0x0F => vec![len as u8],
+            len => {
+                let mut rest = len - 0x0F;
+                let mut stream = vec![0];
+                while rest > 0xFF {
+                    stream.push(0);
+                    rest -= 0xFF;
                 }
-            }
-            _ => {
-                return Err(DwgError::new(
-                    ErrorKind::Format,
-                    "invalid R2004 compression opcode",
-                ))
+                stream.push(rest as u8);
+                stream
             }
         };
-
-        dst_idx = copy_decompressed(&mut dst, dst_idx, compressed_offset + 1, compressed_bytes)?;
-        dst_idx = copy_literal(&mut dst, dst_idx, src, &mut cursor, next_literal_len)?;
-        opcode1 = next_opcode1;
-    }
-
-    if dst.len() > dst_size {
-        dst.truncate(dst_size);
-    } else if dst.len() < dst_size {
-        dst.resize(dst_size, 0);
-    }
-
-    Ok(dst)
-}
-
-struct Cursor<'a> {
-    data: &'a [u8],
-    pos: usize,
-}
-
-impl<'a> Cursor<'a> {
-    fn new(data: &'a [u8]) -> Self {
-        Self { data, pos: 0 }
-    }
-
-    fn read_u8(&mut self) -> Result<u8> {
-        if self.pos >= self.data.len() {
-            return Err(DwgError::new(
-                ErrorKind::Decode,
-                "unexpected end of compressed stream",
-            ));
+        stream.extend_from_slice(data);
+        stream.push(0x11);
+        let mut section = Vec::new();
+        for field in [signature, data.len() as u32, stream.len() as u32, 2, 0] {
+            section.extend_from_slice(&field.to_le_bytes());
         }
-        let value = self.data[self.pos];
-        self.pos += 1;
-        Ok(value)
+        section.extend(stream);
+        section
     }
-}
 
-fn read_literal_length(cursor: &mut Cursor<'_>) -> Result<(usize, u8)> {
-    let mut opcode1 = 0u8;
-    let mut length = 0usize;
-    let byte = cursor.read_u8()?;
-    if (0x01..=0x0F).contains(&byte) {
-        length = byte as usize + 3;
-    } else if byte & 0xF0 != 0 {
-        opcode1 = byte;
-    } else if byte == 0x00 {
-        length = 0x0F;
-        let mut b = cursor.read_u8()?;
-        while b == 0x00 {
-            length += 0xFF;
-            b = cursor.read_u8()?;
+    let config = ParseConfig::default();
+    let header = read_header_data(bytes).unwrap();
+    let page_map_address = header.section_page_map_address + 0x100;
+    let page_map =
+        read_system_section(bytes, page_map_address, SECTION_PAGE_MAP_MAGIC, &config).unwrap();
+    let section_map_address = read_page_map(bytes, &header, &config)
+        .unwrap()
+        .into_iter()
+        .find(|entry| entry.id == header.section_map_id as i32)
+        .unwrap()
+        .address;
+    let mut section_map =
+        read_system_section(bytes, section_map_address, SECTION_MAP_MAGIC, &config).unwrap();
+
+    // Entries: size, page count, max decompressed size, ..., name, then 16
+    // bytes per page. A zero page size leaves the section empty.
+    let mut pos = 20;
+    while pos + 96 <= section_map.len() {
+        let page_count =
+            u32::from_le_bytes(section_map[pos + 8..pos + 12].try_into().unwrap()) as usize;
+        let name = read_cstring(&section_map[pos + 32..pos + 96]);
+        if names.iter().any(|section| section.as_str() == name) {
+            section_map[pos..pos + 8].fill(0);
+            section_map[pos + 12..pos + 16].fill(0);
         }
-        length += b as usize + 3;
+        pos += 96 + page_count * 16;
     }
-    Ok((length, opcode1))
-}
 
-fn read_long_compression_offset(cursor: &mut Cursor<'_>) -> Result<usize> {
-    let mut value = 0usize;
-    let mut byte = cursor.read_u8()?;
-    if byte == 0x00 {
-        value = 0xFF;
-        byte = cursor.read_u8()?;
-        while byte == 0x00 {
-            value += 0xFF;
-            byte = cursor.read_u8()?;
+    let mut entries = Vec::new();
+    let mut end = 0x100u64;
+    let mut reader = ByteReader::new(&page_map);
+    while reader.remaining() >= 8 {
+        let id = reader.read_i32_le().unwrap();
+        let size = reader.read_u32_le().unwrap();
+        end += u64::from(size);
+        let gap = if id == header.section_map_id as i32 {
+            entries.extend_from_slice(&(-id).to_le_bytes());
+            true
+        } else {
+            entries.extend_from_slice(&id.to_le_bytes());
+            id < 0
+        };
+        entries.extend_from_slice(&size.to_le_bytes());
+        if gap {
+            let skipped = if id < 0 {
+                reader.read_bytes(16).unwrap().to_vec()
+            } else {
+                vec![0; 16]
+            };
+            entries.extend(skipped);
         }
     }
-    Ok(value + byte as usize)
-}
-
-fn read_two_byte_offset(cursor: &mut Cursor<'_>) -> Result<(usize, usize)> {
-    let byte1 = cursor.read_u8()?;
-    let byte2 = cursor.read_u8()?;
-    let value = (byte1 as usize >> 2) | ((byte2 as usize) << 6);
-    let literal_count = (byte1 & 0x03) as usize;
-    Ok((value, literal_count))
-}
-
-fn copy_literal(
-    dst: &mut Vec<u8>,
-    dst_idx: usize,
-    src: &[u8],
-    cursor: &mut Cursor<'_>,
-    length: usize,
-) -> Result<usize> {
-    if length == 0 {
-        return Ok(dst_idx);
-    }
-    let end = cursor.pos + length;
-    if end > src.len() {
-        return Err(DwgError::new(
-            ErrorKind::Decode,
-            "literal run exceeds compressed data",
-        ));
-    }
-    ensure_len(dst, dst_idx + length);
-    dst[dst_idx..dst_idx + length].copy_from_slice(&src[cursor.pos..end]);
-    cursor.pos = end;
-    Ok(dst_idx + length)
-}
-
-fn copy_decompressed(
-    dst: &mut Vec<u8>,
-    dst_idx: usize,
-    offset: usize,
-    length: usize,
-) -> Result<usize> {
-    if length == 0 {
-        return Ok(dst_idx);
-    }
+    let section_map = system_section(SECTION_MAP_MAGIC, &section_map);
+    entries.extend_from_slice(&(header.section_map_id as i32).to_le_bytes());
+    entries.extend_from_slice(&(section_map.len() as u32).to_le_bytes());
 
-    // Keep behavior permissive for corrupted blocks, matching pydwg's approach.
-    if offset > dst_idx {
-        ensure_len(dst, dst_idx + length);
-        return Ok(dst_idx + length);
-    }
+    let mut patched = bytes.to_vec();
+    patched.resize(patched.len().max(end as usize), 0);
+    patched.truncate(end as usize);
+    patched.extend(section_map);
+    let new_page_map_address = patched.len() as u64;
+    patched.extend(system_section(SECTION_PAGE_MAP_MAGIC, &entries));
 
-    ensure_len(dst, dst_idx + length);
-    let mut out = dst_idx;
-    for _ in 0..length {
-        let src_idx = out - offset;
-        let byte = dst[src_idx];
-        dst[out] = byte;
-        out += 1;
-    }
-    Ok(out)
-}
-
-fn ensure_len(dst: &mut Vec<u8>, len: usize) {
-    if dst.len() < len {
-        dst.resize(len, 0);
+    let magic = magic_sequence();
+    let header_bytes = &mut patched[HEADER_OFFSET..HEADER_OFFSET + HEADER_SIZE];
+    let field = &mut header_bytes[0x54..0x5C];
+    let plain: Vec<u8> = (new_page_map_address - 0x100).to_le_bytes().to_vec();
+    for (idx, byte) in field.iter_mut().enumerate() {
+        *byte = plain[idx] ^ magic[0x54 + idx];
     }
+    patched
 }
 
 #[cfg(test)]
@@ -917,7 +904,15 @@ mod tests {
         assert!(directory
             .records
             .iter()
-            .any(|record| record.name.as_deref() == Some("AcDb:Handles")));
+            .any(|record| record.name.as_deref() == Some(NamedSection::Handles.as_str())));
+    }
+
+    #[test]
+    fn empty_classes_and_handles_sections_parse_as_empty() {
+        let (classes, _crc) = parse_classes_section(&[]).expect("classes");
+        assert!(classes.is_empty());
+        let index = parse_object_map_handles(&[], &ParseConfig::default()).expect("handles");
+        assert!(index.objects.is_empty());
     }
 
     #[test]
@@ -937,6 +932,101 @@ mod tests {
         assert!(record.size > 0);
     }
 
+    #[test]
+    fn decoder_retained_objects_buffer_matches_per_record_loads() {
+        let bytes = std::fs::read("test_dwg/line_2004.dwg").expect("sample file");
+        let config = ParseConfig::default();
+        let decoder = crate::dwg::decoder::Decoder::new(&bytes, config.clone()).expect("decoder");
+        let index = decoder.build_object_index().expect("object index");
+        assert_eq!(index.objects.len(), 199);
+
+        for object in &index.objects {
+            let retained = decoder
+                .parse_object_record(object.offset)
+                .expect("retained record");
+            let loaded =
+                parse_object_record(&bytes, object.offset, &config).expect("object record");
+            assert!(matches!(retained.body, Cow::Borrowed(_)));
+            assert_eq!(retained.size, loaded.size);
+            assert_eq!(retained.body.as_ref(), loaded.body.as_ref());
+        }
+    }
+
+    #[test]
+    fn scan_recovery_salvages_entities_past_a_corrupt_handles_section() {
+        let mut bytes = std::fs::read("test_dwg/line_2004.dwg").expect("sample file");
+        let config = ParseConfig::default();
+        let mut expected: Vec<_> = crate::dwg::decoder::Decoder::new(&bytes, config.clone())
+            .expect("decoder")
+            .entities()
+            .map(|entity| entity.expect("entity").0)
+            .collect();
+
+        let header = read_header_data(&bytes).expect("header data");
+        let page_map = read_page_map(&bytes, &header, &config).expect("page map");
+        let section_map =
+            read_section_map(&bytes, &header, &page_map, &config).expect("section map");
+        let handles = section_map
+            .iter()
+            .find(|section| section.name == NamedSection::Handles.as_str())
+            .expect("handles section");
+        for page in &handles.pages {
+            let entry = page_map
+                .iter()
+                .find(|entry| entry.id == page.page_id as i32)
+                .expect("page");
+            let start = entry.address as usize;
+            bytes[start..start + 32].fill(0);
+        }
+
+        let decoder = crate::dwg::decoder::Decoder::new(&bytes, config.clone()).expect("decoder");
+        assert!(decoder.build_object_index().is_err());
+        let recovering = ParseConfig {
+            scan_recovery: true,
+            ..config
+        };
+        let decoder = crate::dwg::decoder::Decoder::new(&bytes, recovering).expect("decoder");
+        let mut recovered: Vec<_> = decoder
+            .entities()
+            .map(|entity| entity.expect("entity").0)
+            .collect();
+        recovered.sort_by_key(|handle| handle.0);
+        expected.sort_by_key(|handle| handle.0);
+        assert!(!recovered.is_empty());
+        assert_eq!(recovered, expected);
+    }
+
+    #[test]
+    fn object_index_headers_iterate_lazily_over_objects_buffer() {
+        let bytes = std::fs::read("test_dwg/line_2004.dwg").expect("sample file");
+        let config = ParseConfig::default();
+        let objects_data = load_objects_data(&bytes, &config).expect("objects data");
+        let index =
+            build_object_index_with_objects(&bytes, &config, &objects_data).expect("object index");
+
+        let headers: Vec<_> = index
+            .headers(&objects_data, &DwgVersion::R2004)
+            .collect::<Result<_>>()
+            .expect("headers");
+        assert_eq!(headers.len(), index.len());
+        assert_eq!(
+            headers
+                .iter()
+                .filter(|(_object, header)| header.type_code == 0x13)
+                .count(),
+            1
+        );
+        let (first_object, first_header) = headers[0];
+        assert_eq!(first_object.offset, first_header.offset);
+        assert_eq!(
+            index
+                .headers(&objects_data, &DwgVersion::R2004)
+                .take(3)
+                .count(),
+            3
+        );
+    }
+
     #[test]
     fn parses_object_headers_from_records() {
         let bytes = std::fs::read("test_dwg/line_2004.dwg").expect("sample file");
@@ -1,10 +1,16 @@
 use std::collections::HashMap;
 
 use crate::bit::{BitReader, Endian};
-use crate::container::{SectionDirectory, SectionLocatorRecord, SectionSlice};
+use crate::container::{
+    NamedSection, SectionDirectory, SectionInfo, SectionLocatorRecord, SectionSlice,
+    SECTION_COMPRESSED, SECTION_UNCOMPRESSED,
+};
 use crate::core::config::ParseConfig;
+use crate::core::diag::diag_event;
 use crate::core::error::{DwgError, ErrorKind};
 use crate::core::result::Result;
+use crate::dwg::compression::{decode_reed_solomon, decompress_r21};
+use crate::entities::common::string_stream_reader;
 use crate::io::ByteReader;
 use crate::objects::{Handle, ObjectIndex, ObjectRecord, ObjectRef};
 
@@ -29,7 +35,8 @@ const SENTINEL_CLASSES_AFTER: [u8; 16] = [
 ];
 
 #[derive(Debug, Clone)]
-struct ClassEntry {
+pub(crate) struct ClassEntry {
+    class_number: u16,
     dxf_name: String,
 }
 
@@ -75,8 +82,8 @@ struct ContainerMetadata {
     sections: Vec<SectionEntry>,
 }
 
-pub fn parse_section_directory(bytes: &[u8], _config: &ParseConfig) -> Result<SectionDirectory> {
-    let metadata = parse_container_metadata(bytes)?;
+pub fn parse_section_directory(bytes: &[u8], config: &ParseConfig) -> Result<SectionDirectory> {
+    let metadata = parse_container_metadata(bytes, config)?;
     let mut records = Vec::with_capacity(metadata.sections.len());
 
     for section in metadata.sections {
@@ -109,13 +116,38 @@ pub fn parse_section_directory(bytes: &[u8], _config: &ParseConfig) -> Result<Se
     })
 }
 
+pub fn list_sections(bytes: &[u8], config: &ParseConfig) -> Result<Vec<SectionInfo>> {
+    let metadata = parse_container_metadata(bytes, config)?;
+    Ok(metadata
+        .sections
+        .into_iter()
+        .map(|section| SectionInfo {
+            compressed_size: section.pages.iter().map(|page| page.size_compressed).sum(),
+            page_count: section.pages.len() as u32,
+            // Encrypted sections are rejected while reading the section map.
+            encrypted: 0,
+            compression: if section
+                .pages
+                .iter()
+                .any(|page| page.size_compressed < page.size_uncompressed)
+            {
+                SECTION_COMPRESSED
+            } else {
+                SECTION_UNCOMPRESSED
+            },
+            size: section.size,
+            name: section.name,
+        })
+        .collect())
+}
+
 pub fn load_section_by_index<'a>(
     bytes: &'a [u8],
     directory: &SectionDirectory,
     index: usize,
     config: &ParseConfig,
 ) -> Result<SectionSlice<'a>> {
-    let metadata = parse_container_metadata(bytes)?;
+    let metadata = parse_container_metadata(bytes, config)?;
     let section = metadata
         .sections
         .get(index)
@@ -150,15 +182,20 @@ pub fn load_section_by_index<'a>(
 }
 
 pub fn build_object_index(bytes: &[u8], config: &ParseConfig) -> Result<ObjectIndex> {
-    let handles_data = load_named_section_data(bytes, config, "AcDb:Handles")?;
-    let objects_data = load_named_section_data(bytes, config, "AcDb:AcDbObjects")?;
-    let index = parse_object_map_handles(&handles_data, config)?;
+    let objects_data = load_objects_data(bytes, config)?;
+    build_object_index_with_objects(bytes, config, &objects_data)
+}
+
+pub fn build_object_index_with_objects(
+    bytes: &[u8],
+    config: &ParseConfig,
+    objects_data: &[u8],
+) -> Result<ObjectIndex> {
+    let index = parse_object_map(bytes, config)?;
 
     let mut valid_objects = Vec::with_capacity(index.objects.len());
     for object in index.objects {
-        if crate::objects::object_record::parse_object_record_owned(&objects_data, object.offset)
-            .is_ok()
-        {
+        if crate::objects::object_record::parse_object_record(objects_data, object.offset).is_ok() {
             valid_objects.push(object);
         }
     }
@@ -166,45 +203,62 @@ pub fn build_object_index(bytes: &[u8], config: &ParseConfig) -> Result<ObjectIn
     Ok(ObjectIndex::from_objects(valid_objects))
 }
 
+/// The `AcDb:Handles` object map as stored, including entries whose records
+/// do not parse.
+pub fn parse_object_map(bytes: &[u8], config: &ParseConfig) -> Result<ObjectIndex> {
+    let handles_data = load_named_section_data(bytes, config, NamedSection::Handles)?;
+    parse_object_map_handles(&handles_data, config)
+}
+
+pub fn load_objects_data(bytes: &[u8], config: &ParseConfig) -> Result<Vec<u8>> {
+    load_named_section_data(bytes, config, NamedSection::AcDbObjects)
+}
+
 pub fn parse_object_record<'a>(
     bytes: &'a [u8],
     offset: u32,
     config: &ParseConfig,
 ) -> Result<ObjectRecord<'a>> {
-    let data = load_named_section_data(bytes, config, "AcDb:AcDbObjects")?;
+    let data = load_objects_data(bytes, config)?;
     crate::objects::object_record::parse_object_record_owned(&data, offset)
 }
 
+pub fn section_crcs(bytes: &[u8], config: &ParseConfig) -> Result<Vec<(String, u16)>> {
+    crate::dwg::r2004::named_section_crcs(
+        |name| load_named_section_data(bytes, config, name),
+        |data| parse_classes_section(data, false).map(|(_classes, crc)| crc),
+    )
+}
+
 pub fn load_dynamic_type_map(bytes: &[u8], config: &ParseConfig) -> Result<HashMap<u16, String>> {
-    let data = load_named_section_data(bytes, config, "AcDb:Classes")?;
-    let classes = parse_classes_section(&data)?;
-    let mut map = HashMap::with_capacity(classes.len());
-    for (idx, class) in classes.iter().enumerate() {
-        let code = 500usize + idx;
-        if code > u16::MAX as usize {
-            break;
-        }
-        if !class.dxf_name.is_empty() {
-            map.insert(code as u16, class.dxf_name.to_ascii_uppercase());
-        }
-    }
-    Ok(map)
+    let data = load_named_section_data(bytes, config, NamedSection::Classes)?;
+    let (classes, _crc) = parse_classes_section(&data, false)?;
+    Ok(class_type_map(&classes))
 }
 
-fn parse_container_metadata(bytes: &[u8]) -> Result<ContainerMetadata> {
-    let header = read_header_data(bytes)?;
-    let page_map = read_page_map(bytes, &header)?;
-    let sections = read_section_map(bytes, &header, &page_map)?;
+fn parse_container_metadata(bytes: &[u8], config: &ParseConfig) -> Result<ContainerMetadata> {
+    let header = read_header_data(bytes, config)?;
+    let page_map = read_page_map(bytes, &header, config)?;
+    let sections = read_section_map(bytes, &header, &page_map, config)?;
     Ok(ContainerMetadata { page_map, sections })
 }
 
-fn load_named_section_data(bytes: &[u8], config: &ParseConfig, name: &str) -> Result<Vec<u8>> {
-    let metadata = parse_container_metadata(bytes)?;
+fn load_named_section_data(
+    bytes: &[u8],
+    config: &ParseConfig,
+    name: NamedSection,
+) -> Result<Vec<u8>> {
+    let metadata = parse_container_metadata(bytes, config)?;
     let section = metadata
         .sections
         .iter()
-        .find(|section| section.name == name)
-        .ok_or_else(|| DwgError::new(ErrorKind::Format, format!("section not found: {name}")))?;
+        .find(|section| section.name == name.as_str())
+        .ok_or_else(|| {
+            DwgError::new(
+                ErrorKind::Format,
+                format!("section not found: {}", name.as_str()),
+            )
+        })?;
     load_section_data(bytes, section, &metadata.page_map, config)
 }
 
@@ -224,6 +278,13 @@ fn load_section_data(
         ));
     }
 
+    diag_event!(
+        "section",
+        "load R2007 section {:?}: {} pages, encoded={}",
+        section.name,
+        section.pages.len(),
+        section.encoded
+    );
     let total_size = to_usize(section.size, "R2007 section size")?;
     let mut output = vec![0u8; total_size];
     if total_size == 0 {
@@ -242,7 +303,18 @@ fn load_section_data(
             section.encoded,
             page.size_compressed,
             page.size_uncompressed,
-        )?;
+            config,
+        )
+        .inspect_err(|err| {
+            diag_event!(
+                "section",
+                "R2007 page {} of section {:?} at {:#X}: {}",
+                page.id,
+                section.name,
+                entry.address,
+                err
+            )
+        })?;
 
         let start = to_usize(page.offset, "R2007 section page offset")?;
         if start >= output.len() {
@@ -261,6 +333,7 @@ fn read_data_page(
     encoded: u64,
     size_compressed: u64,
     size_uncompressed: u64,
+    config: &ParseConfig,
 ) -> Result<Vec<u8>> {
     const DATA_PAGE_RS_DATA_SIZE: u64 = 251;
 
@@ -305,7 +378,7 @@ fn read_data_page(
                 "R2007 compressed data page exceeds decoded buffer",
             ));
         }
-        decompress_r21(&decoded[..compressed_size], uncompressed_size)
+        decompress_r21(&decoded[..compressed_size], uncompressed_size, config)
     } else {
         let size = to_usize(size_uncompressed, "R2007 data page size")?;
         if size > decoded.len() {
@@ -318,40 +391,56 @@ fn read_data_page(
     }
 }
 
-fn parse_classes_section(data: &[u8]) -> Result<Vec<ClassEntry>> {
+/// R2010+ files keep this layout inside the R2004 container; from R2013, and
+/// for R2010 maintenance releases above 3, an RL follows the size field.
+/// The names live in a string stream at the end of the class data.
+pub(crate) fn parse_classes_section(
+    data: &[u8],
+    extra_size_field: bool,
+) -> Result<(Vec<ClassEntry>, u16)> {
+    if data.is_empty() {
+        return Ok((Vec::new(), 0));
+    }
     let mut reader = BitReader::new(data);
 
     let sentinel_before = reader.read_rcs(SENTINEL_CLASSES_BEFORE.len())?;
     if sentinel_before.as_slice() != SENTINEL_CLASSES_BEFORE {
         return Err(DwgError::new(
-            ErrorKind::Format,
+            ErrorKind::SentinelMismatch,
             "AcDb:Classes sentinel(before) mismatch",
         ));
     }
 
     let size = reader.read_rl(Endian::Little)? as usize;
+    if extra_size_field {
+        let _high_size = reader.read_rl(Endian::Little)?;
+    }
+    // The bit size counts from its own position.
+    let base_bits = reader.tell_bits() as u32;
     let end_bit = reader.read_rl(Endian::Little)?;
     let max_class_number = reader.read_bs()?;
     let _zero0 = reader.read_rc()?;
     let _zero1 = reader.read_rc()?;
     let _bit_flag = reader.read_b()?;
 
-    let (saved_byte, saved_bit) = reader.get_pos();
-    let string_stream_present = if end_bit > 0 {
-        let base_offset_bits = 20u32 * 8;
-        reader.set_bit_pos(base_offset_bits + end_bit - 1);
-        let flag = reader.read_b()? != 0;
-        reader.set_pos(saved_byte, saved_bit);
-        flag
-    } else {
-        false
+    let data_end_bit = base_bits + end_bit;
+    let mut strings = match end_bit {
+        0 => None,
+        _ => string_stream_reader(&reader, data_end_bit)?,
     };
 
     let mut classes = Vec::new();
     while reader.get_pos().0 <= size {
         let class_number = reader.read_bs()?;
         let _proxy_flags = reader.read_bs()?;
-        let dxf_name = String::new();
+        let dxf_name = match strings.as_mut() {
+            Some(strings) => {
+                let _app_name = strings.read_tu()?;
+                let _cpp_name = strings.read_tu()?;
+                strings.read_tu()?
+            }
+            None => String::new(),
+        };
         let _was_a_zombie = reader.read_b()?;
         let _item_class_id = reader.read_bs()?;
         let _number_of_objects = reader.read_bl()?;
@@ -360,42 +449,39 @@ fn parse_classes_section(data: &[u8]) -> Result<Vec<ClassEntry>> {
         let _unknown0 = reader.read_bl()?;
         let _unknown1 = reader.read_bl()?;
 
-        classes.push(ClassEntry { dxf_name });
+        classes.push(ClassEntry {
+            class_number,
+            dxf_name,
+        });
 
         if class_number == max_class_number {
             break;
         }
     }
 
-    if string_stream_present {
-        for class in &mut classes {
-            let _app_name = read_tu(&mut reader)?;
-            let _cpp_name = read_tu(&mut reader)?;
-            class.dxf_name = read_tu(&mut reader)?;
-        }
-        let base_offset_bits = 20u32 * 8;
-        reader.set_bit_pos(base_offset_bits + end_bit);
+    if end_bit > 0 {
+        reader.set_bit_pos(data_end_bit);
     }
-
-    let _crc = reader.read_crc()?;
+    let crc = reader.read_crc()?;
     let sentinel_after = reader.read_rcs(SENTINEL_CLASSES_AFTER.len())?;
     if sentinel_after.as_slice() != SENTINEL_CLASSES_AFTER {
         return Err(DwgError::new(
-            ErrorKind::Format,
+            ErrorKind::SentinelMismatch,
             "AcDb:Classes sentinel(after) mismatch",
         ));
     }
 
-    Ok(classes)
+    Ok((classes, crc))
 }
 
-fn read_tu(reader: &mut BitReader<'_>) -> Result<String> {
-    let length = reader.read_bs()? as usize;
-    let mut units = Vec::with_capacity(length);
-    for _ in 0..length {
-        units.push(reader.read_rs(Endian::Little)?);
-    }
-    Ok(String::from_utf16_lossy(&units))
+/// Type code to upper-case DXF class name; classes without a name are left
+/// out.
+pub(crate) fn class_type_map(classes: &[ClassEntry]) -> HashMap<u16, String> {
+    classes
+        .iter()
+        .filter(|class| !class.dxf_name.is_empty())
+        .map(|class| (class.class_number, class.dxf_name.to_ascii_uppercase()))
+        .collect()
 }
 
 fn parse_object_map_handles(bytes: &[u8], config: &ParseConfig) -> Result<ObjectIndex> {
@@ -407,7 +493,7 @@ fn parse_object_map_handles(bytes: &[u8], config: &ParseConfig) -> Result<Object
             break;
         }
 
-        let section_size = read_u16_be(&mut reader)? as usize;
+        let section_size = reader.read_u16_be()? as usize;
         if section_size == 2 {
             break;
         }
@@ -430,8 +516,9 @@ fn parse_object_map_handles(bytes: &[u8], config: &ParseConfig) -> Result<Object
         let mut last_offset: i64 = 0;
 
         while (reader.tell() - start) < (section_size as u64 - 2) {
-            last_handle += read_modular_char(&mut reader)?;
-            last_offset += read_modular_char(&mut reader)?;
+            // Handles only ever increase; only the offset delta is signed.
+            last_handle += reader.read_unsigned_modular_char()? as i64;
+            last_offset += reader.read_modular_char()?;
 
             if last_handle < 0 || last_offset < 0 {
                 return Err(DwgError::new(
@@ -464,44 +551,13 @@ fn parse_object_map_handles(bytes: &[u8], config: &ParseConfig) -> Result<Object
         if reader.remaining() < 2 {
             break;
         }
-        let _crc = read_u16_be(&mut reader)?;
+        let _crc = reader.read_u16_be()?;
     }
 
     Ok(ObjectIndex::from_objects(objects))
 }
 
-fn read_u16_be(reader: &mut ByteReader<'_>) -> Result<u16> {
-    let hi = reader.read_u8()? as u16;
-    let lo = reader.read_u8()? as u16;
-    Ok((hi << 8) | lo)
-}
-
-fn read_modular_char(reader: &mut ByteReader<'_>) -> Result<i64> {
-    let mut value: i64 = 0;
-    let mut shift = 0;
-
-    for _ in 0..4 {
-        let mut byte = reader.read_u8()?;
-        if (byte & 0x80) == 0 {
-            let negative = (byte & 0x40) != 0;
-            if negative {
-                byte &= 0xBF;
-            }
-            value |= (byte as i64) << shift;
-            if negative {
-                value = -value;
-            }
-            return Ok(value);
-        }
-        byte &= 0x7F;
-        value |= (byte as i64) << shift;
-        shift += 7;
-    }
-
-    Ok(value)
-}
-
-fn read_header_data(bytes: &[u8]) -> Result<HeaderData> {
+fn read_header_data(bytes: &[u8], config: &ParseConfig) -> Result<HeaderData> {
     if bytes.len() < SECOND_HEADER_OFFSET + SECOND_HEADER_RS_SIZE {
         return Err(DwgError::new(
             ErrorKind::Format,
@@ -551,6 +607,7 @@ fn read_header_data(bytes: &[u8]) -> Result<HeaderData> {
         decompress_r21(
             &decoded[SECOND_HEADER_PAYLOAD_OFFSET..end],
             SECOND_HEADER_BODY_SIZE,
+            config,
         )?
     } else {
         return Err(DwgError::new(
@@ -585,7 +642,11 @@ fn read_header_data(bytes: &[u8]) -> Result<HeaderData> {
     })
 }
 
-fn read_page_map(bytes: &[u8], header: &HeaderData) -> Result<Vec<PageMapEntry>> {
+fn read_page_map(
+    bytes: &[u8],
+    header: &HeaderData,
+    config: &ParseConfig,
+) -> Result<Vec<PageMapEntry>> {
     let address = STREAM_BASE_OFFSET
         .checked_add(header.pages_map_offset)
         .ok_or_else(|| DwgError::new(ErrorKind::Format, "R2007 page map address overflow"))?;
@@ -595,6 +656,7 @@ fn read_page_map(bytes: &[u8], header: &HeaderData) -> Result<Vec<PageMapEntry>>
         header.pages_map_size_compressed,
         header.pages_map_size_uncompressed,
         header.pages_map_correction_factor,
+        config,
     )?;
 
     let mut reader = ByteReader::new(&data);
@@ -629,6 +691,7 @@ fn read_page_map(bytes: &[u8], header: &HeaderData) -> Result<Vec<PageMapEntry>>
             "R2007 page map has no entries",
         ));
     }
+    diag_event!("page_map", "R2007 page map: {} entries", entries.len());
     Ok(entries)
 }
 
@@ -636,6 +699,7 @@ fn read_section_map(
     bytes: &[u8],
     header: &HeaderData,
     page_map: &[PageMapEntry],
+    config: &ParseConfig,
 ) -> Result<Vec<SectionEntry>> {
     let section_map_page = page_map
         .iter()
@@ -648,6 +712,7 @@ fn read_section_map(
         header.sections_map_size_compressed,
         header.sections_map_size_uncompressed,
         header.sections_map_correction_factor,
+        config,
     )?;
 
     let mut reader = ByteReader::new(&data);
@@ -734,6 +799,7 @@ fn read_system_page(
     size_compressed: u64,
     size_uncompressed: u64,
     correction_factor: u64,
+    config: &ParseConfig,
 ) -> Result<Vec<u8>> {
     let compressed_padded = align_up(size_compressed, SYSTEM_PAGE_CRC_BLOCK_SIZE)?;
     let rs_pre_encoded_size = compressed_padded
@@ -771,7 +837,7 @@ fn read_system_page(
                 "R2007 compressed system page data out of range",
             ));
         }
-        decompress_r21(&decoded[..compressed_size], uncompressed_size)
+        decompress_r21(&decoded[..compressed_size], uncompressed_size, config)
     } else {
         let size = to_usize(size_uncompressed, "R2007 page size")?;
         if size > decoded.len() {
@@ -784,488 +850,6 @@ fn read_system_page(
     }
 }
 
-fn decode_reed_solomon(src: &[u8], k: usize, block_count: usize, method: u8) -> Result<Vec<u8>> {
-    let output_size = k
-        .checked_mul(block_count)
-        .ok_or_else(|| DwgError::new(ErrorKind::Decode, "RS output size overflow"))?;
-    if output_size == 0 {
-        return Ok(Vec::new());
-    }
-    if src.len() < output_size {
-        return Err(DwgError::new(
-            ErrorKind::Decode,
-            "R2007 RS input is smaller than required output layout",
-        ));
-    }
-
-    match method {
-        4 => {
-            let mut out = vec![0u8; output_size];
-            let mut dst = 0usize;
-            for bc in 0..block_count {
-                for idx in 0..k {
-                    out[dst] = src[block_count * idx + bc];
-                    dst += 1;
-                }
-            }
-            Ok(out)
-        }
-        1 => Ok(src[..output_size].to_vec()),
-        _ => Err(DwgError::not_implemented(
-            "unsupported Reed-Solomon method for R2007",
-        )),
-    }
-}
-
-fn decompress_r21(src: &[u8], dst_size: usize) -> Result<Vec<u8>> {
-    if dst_size == 0 {
-        return Ok(Vec::new());
-    }
-    if src.is_empty() {
-        return Err(DwgError::new(
-            ErrorKind::Decode,
-            "R2007 compressed stream is empty",
-        ));
-    }
-
-    let mut dst = vec![0u8; dst_size];
-    let src_size = src.len();
-
-    let mut src_idx = 0usize;
-    let mut dst_idx = 0usize;
-    let mut length = 0usize;
-
-    let mut opcode = read_u8(src, &mut src_idx)? as usize;
-    if (opcode & 0xF0) == 0x20 {
-        src_idx = src_idx
-            .checked_add(2)
-            .ok_or_else(|| DwgError::new(ErrorKind::Decode, "R2007 opcode index overflow"))?;
-        if src_idx >= src_size {
-            return Err(DwgError::new(
-                ErrorKind::Decode,
-                "R2007 opcode bootstrap exceeds input",
-            ));
-        }
-        length = (src[src_idx] & 0x07) as usize;
-        src_idx += 1;
-    }
-
-    while src_idx < src_size {
-        if length == 0 {
-            (length, src_idx) = read_literal_length(src, src_idx, opcode)?;
-        }
-
-        if dst_idx + length > dst_size {
-            break;
-        }
-
-        copy_compressed_chunk(src, src_idx, length, &mut dst, dst_idx)?;
-        dst_idx += length;
-        src_idx += length;
-
-        if src_idx >= src_size {
-            break;
-        }
-
-        let (next_opcode, next_length, next_src_idx, next_dst_idx) =
-            copy_decompressed_chunks(src, src_idx, &mut dst, dst_idx)?;
-        opcode = next_opcode;
-        length = next_length;
-        src_idx = next_src_idx;
-        dst_idx = next_dst_idx;
-    }
-
-    Ok(dst)
-}
-
-fn read_literal_length(src: &[u8], mut src_idx: usize, opcode: usize) -> Result<(usize, usize)> {
-    let mut length = opcode + 8;
-    if length == 0x17 {
-        let mut n = *src.get(src_idx).ok_or_else(|| {
-            DwgError::new(
-                ErrorKind::Decode,
-                "R2007 literal length read exceeds compressed data",
-            )
-        })? as usize;
-        src_idx += 1;
-        length += n;
-        if n == 0xFF {
-            loop {
-                let lo = *src.get(src_idx).ok_or_else(|| {
-                    DwgError::new(
-                        ErrorKind::Decode,
-                        "R2007 literal extension exceeds compressed data",
-                    )
-                })? as usize;
-                let hi = *src.get(src_idx + 1).ok_or_else(|| {
-                    DwgError::new(
-                        ErrorKind::Decode,
-                        "R2007 literal extension exceeds compressed data",
-                    )
-                })? as usize;
-                src_idx += 2;
-                n = lo | (hi << 8);
-                length += n;
-                if n != 0xFFFF {
-                    break;
-                }
-            }
-        }
-    }
-    Ok((length, src_idx))
-}
-
-fn copy_compressed_chunk(
-    src: &[u8],
-    mut src_idx: usize,
-    mut length: usize,
-    dst: &mut [u8],
-    dst_idx: usize,
-) -> Result<()> {
-    let mut out = dst_idx;
-
-    while length >= 32 {
-        copy_16b(src, src_idx + 16, dst, &mut out)?;
-        copy_16b(src, src_idx, dst, &mut out)?;
-        src_idx += 32;
-        length -= 32;
-    }
-
-    match length {
-        0 => {}
-        1 => copy_1b(src, src_idx, dst, &mut out)?,
-        2 => copy_2b(src, src_idx, dst, &mut out)?,
-        3 => copy_3b(src, src_idx, dst, &mut out)?,
-        4 => copy_4b(src, src_idx, dst, &mut out)?,
-        5 => {
-            copy_1b(src, src_idx + 4, dst, &mut out)?;
-            copy_4b(src, src_idx, dst, &mut out)?;
-        }
-        6 => {
-            copy_1b(src, src_idx + 5, dst, &mut out)?;
-            copy_4b(src, src_idx + 1, dst, &mut out)?;
-            copy_1b(src, src_idx, dst, &mut out)?;
-        }
-        7 => {
-            copy_2b(src, src_idx + 5, dst, &mut out)?;
-            copy_4b(src, src_idx + 1, dst, &mut out)?;
-            copy_1b(src, src_idx, dst, &mut out)?;
-        }
-        8 => {
-            copy_4b(src, src_idx, dst, &mut out)?;
-            copy_4b(src, src_idx + 4, dst, &mut out)?;
-        }
-        9 => {
-            copy_1b(src, src_idx + 8, dst, &mut out)?;
-            copy_8b(src, src_idx, dst, &mut out)?;
-        }
-        10 => {
-            copy_1b(src, src_idx + 9, dst, &mut out)?;
-            copy_8b(src, src_idx + 1, dst, &mut out)?;
-            copy_1b(src, src_idx, dst, &mut out)?;
-        }
-        11 => {
-            copy_2b(src, src_idx + 9, dst, &mut out)?;
-            copy_8b(src, src_idx + 1, dst, &mut out)?;
-            copy_1b(src, src_idx, dst, &mut out)?;
-        }
-        12 => {
-            copy_4b(src, src_idx + 8, dst, &mut out)?;
-            copy_8b(src, src_idx, dst, &mut out)?;
-        }
-        13 => {
-            copy_1b(src, src_idx + 12, dst, &mut out)?;
-            copy_4b(src, src_idx + 8, dst, &mut out)?;
-            copy_8b(src, src_idx, dst, &mut out)?;
-        }
-        14 => {
-            copy_1b(src, src_idx + 13, dst, &mut out)?;
-            copy_4b(src, src_idx + 9, dst, &mut out)?;
-            copy_8b(src, src_idx + 1, dst, &mut out)?;
-            copy_1b(src, src_idx, dst, &mut out)?;
-        }
-        15 => {
-            copy_2b(src, src_idx + 13, dst, &mut out)?;
-            copy_4b(src, src_idx + 9, dst, &mut out)?;
-            copy_8b(src, src_idx + 1, dst, &mut out)?;
-            copy_1b(src, src_idx, dst, &mut out)?;
-        }
-        16 => copy_16b(src, src_idx, dst, &mut out)?,
-        17 => {
-            copy_8b(src, src_idx + 9, dst, &mut out)?;
-            copy_1b(src, src_idx + 8, dst, &mut out)?;
-            copy_8b(src, src_idx, dst, &mut out)?;
-        }
-        18 => {
-            copy_1b(src, src_idx + 17, dst, &mut out)?;
-            copy_16b(src, src_idx + 1, dst, &mut out)?;
-            copy_1b(src, src_idx, dst, &mut out)?;
-        }
-        19 => {
-            copy_3b(src, src_idx + 16, dst, &mut out)?;
-            copy_16b(src, src_idx, dst, &mut out)?;
-        }
-        20 => {
-            copy_4b(src, src_idx + 16, dst, &mut out)?;
-            copy_16b(src, src_idx, dst, &mut out)?;
-        }
-        21 => {
-            copy_1b(src, src_idx + 20, dst, &mut out)?;
-            copy_4b(src, src_idx + 16, dst, &mut out)?;
-            copy_16b(src, src_idx, dst, &mut out)?;
-        }
-        22 => {
-            copy_2b(src, src_idx + 20, dst, &mut out)?;
-            copy_4b(src, src_idx + 16, dst, &mut out)?;
-            copy_16b(src, src_idx, dst, &mut out)?;
-        }
-        23 => {
-            copy_3b(src, src_idx + 20, dst, &mut out)?;
-            copy_4b(src, src_idx + 16, dst, &mut out)?;
-            copy_16b(src, src_idx, dst, &mut out)?;
-        }
-        24 => {
-            copy_8b(src, src_idx + 16, dst, &mut out)?;
-            copy_16b(src, src_idx, dst, &mut out)?;
-        }
-        25 => {
-            copy_8b(src, src_idx + 17, dst, &mut out)?;
-            copy_1b(src, src_idx + 16, dst, &mut out)?;
-            copy_16b(src, src_idx, dst, &mut out)?;
-        }
-        26 => {
-            copy_1b(src, src_idx + 25, dst, &mut out)?;
-            copy_8b(src, src_idx + 17, dst, &mut out)?;
-            copy_1b(src, src_idx + 16, dst, &mut out)?;
-            copy_16b(src, src_idx, dst, &mut out)?;
-        }
-        27 => {
-            copy_2b(src, src_idx + 25, dst, &mut out)?;
-            copy_8b(src, src_idx + 17, dst, &mut out)?;
-            copy_1b(src, src_idx + 16, dst, &mut out)?;
-            copy_16b(src, src_idx, dst, &mut out)?;
-        }
-        28 => {
-            copy_4b(src, src_idx + 24, dst, &mut out)?;
-            copy_8b(src, src_idx + 16, dst, &mut out)?;
-            copy_16b(src, src_idx, dst, &mut out)?;
-        }
-        29 => {
-            copy_1b(src, src_idx + 28, dst, &mut out)?;
-            copy_4b(src, src_idx + 24, dst, &mut out)?;
-            copy_8b(src, src_idx + 16, dst, &mut out)?;
-            copy_16b(src, src_idx, dst, &mut out)?;
-        }
-        30 => {
-            copy_2b(src, src_idx + 28, dst, &mut out)?;
-            copy_4b(src, src_idx + 24, dst, &mut out)?;
-            copy_8b(src, src_idx + 16, dst, &mut out)?;
-            copy_16b(src, src_idx, dst, &mut out)?;
-        }
-        31 => {
-            copy_1b(src, src_idx + 30, dst, &mut out)?;
-            copy_4b(src, src_idx + 26, dst, &mut out)?;
-            copy_8b(src, src_idx + 18, dst, &mut out)?;
-            copy_16b(src, src_idx + 2, dst, &mut out)?;
-            copy_2b(src, src_idx, dst, &mut out)?;
-        }
-        _ => {
-            return Err(DwgError::new(
-                ErrorKind::Decode,
-                "R2007 invalid compressed chunk length",
-            ));
-        }
-    }
-
-    Ok(())
-}
-
-fn copy_decompressed_chunks(
-    src: &[u8],
-    mut src_idx: usize,
-    dst: &mut [u8],
-    mut dst_idx: usize,
-) -> Result<(usize, usize, usize, usize)> {
-    let src_size = src.len();
-    let mut opcode = read_u8(src, &mut src_idx)? as usize;
-    let (mut opcode_next, mut offset, mut length, mut src_idx_next) =
-        read_instructions(src, src_idx, opcode)?;
-    opcode = opcode_next;
-    src_idx = src_idx_next;
-
-    loop {
-        dst_idx = copy_from_output(dst, dst_idx, offset, length)?;
-
-        length = opcode & 0x07;
-        if length != 0 || src_idx >= src_size {
-            break;
-        }
-
-        opcode = read_u8(src, &mut src_idx)? as usize;
-        if (opcode >> 4) == 0 {
-            break;
-        }
-        if (opcode >> 4) == 15 {
-            opcode &= 0x0F;
-        }
-
-        (opcode_next, offset, length, src_idx_next) = read_instructions(src, src_idx, opcode)?;
-        opcode = opcode_next;
-        src_idx = src_idx_next;
-    }
-
-    Ok((opcode, length, src_idx, dst_idx))
-}
-
-fn read_instructions(
-    src: &[u8],
-    mut src_idx: usize,
-    mut opcode: usize,
-) -> Result<(usize, usize, usize, usize)> {
-    let mut length;
-    let offset;
-    match opcode >> 4 {
-        0 => {
-            length = (opcode & 0x0F) + 0x13;
-            offset = read_u8(src, &mut src_idx)? as usize;
-            opcode = read_u8(src, &mut src_idx)? as usize;
-            length += (opcode >> 3) & 0x10;
-            let offset = ((opcode & 0x78) << 5) + 1 + offset;
-            Ok((opcode, offset, length, src_idx))
-        }
-        1 => {
-            length = (opcode & 0x0F) + 0x03;
-            offset = read_u8(src, &mut src_idx)? as usize;
-            opcode = read_u8(src, &mut src_idx)? as usize;
-            let offset = ((opcode & 0xF8) << 5) + 1 + offset;
-            Ok((opcode, offset, length, src_idx))
-        }
-        2 => {
-            let mut offset = read_u8(src, &mut src_idx)? as usize;
-            offset |= ((read_u8(src, &mut src_idx)? as usize) << 8) & 0xFF00;
-            length = opcode & 0x07;
-            if (opcode & 0x08) == 0 {
-                opcode = read_u8(src, &mut src_idx)? as usize;
-                length += opcode & 0xF8;
-            } else {
-                offset += 1;
-                length += (read_u8(src, &mut src_idx)? as usize) << 3;
-                opcode = read_u8(src, &mut src_idx)? as usize;
-                length = (((opcode & 0xF8) << 8) + length) + 0x100;
-            }
-            Ok((opcode, offset, length, src_idx))
-        }
-        _ => {
-            length = opcode >> 4;
-            let mut offset = opcode & 0x0F;
-            opcode = read_u8(src, &mut src_idx)? as usize;
-            offset = ((opcode & 0xF8) << 1) + offset + 1;
-            Ok((opcode, offset, length, src_idx))
-        }
-    }
-}
-
-fn copy_from_output(dst: &mut [u8], dst_idx: usize, offset: usize, length: usize) -> Result<usize> {
-    let src_idx = dst_idx.checked_sub(offset).ok_or_else(|| {
-        DwgError::new(
-            ErrorKind::Decode,
-            "R2007 back-reference offset exceeds decompressed prefix",
-        )
-    })?;
-    let end = dst_idx
-        .checked_add(length)
-        .ok_or_else(|| DwgError::new(ErrorKind::Decode, "R2007 decompressed write overflow"))?;
-    if end > dst.len() {
-        return Err(DwgError::new(
-            ErrorKind::Decode,
-            "R2007 decompressed write exceeds output buffer",
-        ));
-    }
-    for i in 0..length {
-        let src_pos = src_idx + i;
-        let dst_pos = dst_idx + i;
-        if src_pos >= dst.len() {
-            return Err(DwgError::new(
-                ErrorKind::Decode,
-                "R2007 decompressed read exceeds output buffer",
-            ));
-        }
-        dst[dst_pos] = dst[src_pos];
-    }
-    Ok(end)
-}
-
-fn read_u8(src: &[u8], src_idx: &mut usize) -> Result<u8> {
-    let value = *src.get(*src_idx).ok_or_else(|| {
-        DwgError::new(
-            ErrorKind::Decode,
-            "R2007 compressed stream read exceeds buffer",
-        )
-    })?;
-    *src_idx += 1;
-    Ok(value)
-}
-
-fn copy_1b(src: &[u8], src_idx: usize, dst: &mut [u8], dst_idx: &mut usize) -> Result<()> {
-    if src_idx >= src.len() || *dst_idx >= dst.len() {
-        return Err(DwgError::new(
-            ErrorKind::Decode,
-            "R2007 copy_1b out of range",
-        ));
-    }
-    dst[*dst_idx] = src[src_idx];
-    *dst_idx += 1;
-    Ok(())
-}
-
-fn copy_2b(src: &[u8], src_idx: usize, dst: &mut [u8], dst_idx: &mut usize) -> Result<()> {
-    copy_1b(src, src_idx + 1, dst, dst_idx)?;
-    copy_1b(src, src_idx, dst, dst_idx)
-}
-
-fn copy_3b(src: &[u8], src_idx: usize, dst: &mut [u8], dst_idx: &mut usize) -> Result<()> {
-    copy_1b(src, src_idx + 2, dst, dst_idx)?;
-    copy_1b(src, src_idx + 1, dst, dst_idx)?;
-    copy_1b(src, src_idx, dst, dst_idx)
-}
-
-fn copy_4b(src: &[u8], src_idx: usize, dst: &mut [u8], dst_idx: &mut usize) -> Result<()> {
-    copy_bytes_direct(src, src_idx, 4, dst, dst_idx)
-}
-
-fn copy_8b(src: &[u8], src_idx: usize, dst: &mut [u8], dst_idx: &mut usize) -> Result<()> {
-    copy_bytes_direct(src, src_idx, 8, dst, dst_idx)
-}
-
-fn copy_16b(src: &[u8], src_idx: usize, dst: &mut [u8], dst_idx: &mut usize) -> Result<()> {
-    copy_8b(src, src_idx + 8, dst, dst_idx)?;
-    copy_8b(src, src_idx, dst, dst_idx)
-}
-
-fn copy_bytes_direct(
-    src: &[u8],
-    src_idx: usize,
-    length: usize,
-    dst: &mut [u8],
-    dst_idx: &mut usize,
-) -> Result<()> {
-    let src_end = src_idx
-        .checked_add(length)
-        .ok_or_else(|| DwgError::new(ErrorKind::Decode, "R2007 source range overflow"))?;
-    let dst_end = (*dst_idx)
-        .checked_add(length)
-        .ok_or_else(|| DwgError::new(ErrorKind::Decode, "R2007 destination range overflow"))?;
-    if src_end > src.len() || dst_end > dst.len() {
-        return Err(DwgError::new(
-            ErrorKind::Decode,
-            "R2007 direct copy out of range",
-        ));
-    }
-    dst[*dst_idx..dst_end].copy_from_slice(&src[src_idx..src_end]);
-    *dst_idx = dst_end;
-    Ok(())
-}
-
 fn decode_utf16_string(bytes: &[u8]) -> Result<String> {
     if !bytes.len().is_multiple_of(2) {
         return Err(DwgError::new(
@@ -1282,13 +866,7 @@ fn decode_utf16_string(bytes: &[u8]) -> Result<String> {
 }
 
 fn record_no_for_name(name: &str) -> u8 {
-    match name {
-        "AcDb:Header" | "AcDb:Headers" => 0,
-        "AcDb:Classes" => 1,
-        "AcDb:Handles" => 2,
-        "AcDb:Template" => 4,
-        _ => 255,
-    }
+    NamedSection::from_name(name).map_or(255, |section| section.record_no())
 }
 
 fn align_up(value: u64, align: u64) -> Result<u64> {
@@ -1316,6 +894,134 @@ fn to_usize(value: u64, label: &str) -> Result<usize> {
         .map_err(|_| DwgError::new(ErrorKind::Format, format!("{label} exceeds usize range")))
 }
 
+/// `bytes` with the `names` sections emptied, for tests. The rewritten
+/// section map and a page map pointing at it are appended as uncompressed
+/// system pages, and the second header is stored uncompressed to locate them.
+#[cfg(test)]
+pub(crate) fn with_empty_sections(bytes: &[u8], names: &[NamedSection]) -> Vec<u8> {
+    // Interleave `data` into RS codewords with zero parity, which the reader
+    // does not check.
+    fn encode(data: &[u8], block_count: usize) -> Vec<u8> {
+        let k = SYSTEM_PAGE_RS_DATA_SIZE as usize;
+        let mut out = vec![0u8; block_count * SYSTEM_PAGE_RS_CODEWORD_SIZE as usize];
+        for (pos, byte) in data.iter().enumerate() {
+            out[block_count * (pos % k) + pos / k] = *byte;
+        }
+        out
+    }
+    fn system_page(data: &[u8]) -> Vec<u8> {
+        let padded = align_up(data.len() as u64, SYSTEM_PAGE_CRC_BLOCK_SIZE).unwrap();
+        let block_count = div_ceil(padded, SYSTEM_PAGE_RS_DATA_SIZE) as usize;
+        let mut page = encode(data, block_count);
+        page.resize(
+            align_up(page.len() as u64, SYSTEM_PAGE_ALIGN_SIZE).unwrap() as usize,
+            0,
+        );
+        page
+    }
+
+    let config = ParseConfig::default();
+    let header = read_header_data(bytes, &config).unwrap();
+    let page_map = read_page_map(bytes, &header, &config).unwrap();
+    let section_map_page = page_map
+        .iter()
+        .find(|entry| entry.id == header.sections_map_id as i64)
+        .unwrap();
+    let mut section_map = read_system_page(
+        bytes,
+        section_map_page.address,
+        header.sections_map_size_compressed,
+        header.sections_map_size_uncompressed,
+        header.sections_map_correction_factor,
+        &config,
+    )
+    .unwrap();
+
+    // Eight u64 fields (size first, name length fifth, page count last),
+    // the UTF-16 name, then the page infos. A zero size reads as empty.
+    let mut pos = 0;
+    while pos + SECTION_ENTRY_SIZE <= section_map.len() {
+        let field = |idx: usize| {
+            u64::from_le_bytes(
+                section_map[pos + idx * 8..pos + idx * 8 + 8]
+                    .try_into()
+                    .unwrap(),
+            ) as usize
+        };
+        let (name_length, page_count) = (field(4), field(7));
+        if name_length == 0 {
+            break;
+        }
+        let name_start = pos + SECTION_ENTRY_SIZE;
+        let name = decode_utf16_string(&section_map[name_start..name_start + name_length]).unwrap();
+        if names.iter().any(|section| section.as_str() == name) {
+            section_map[pos..pos + 8].fill(0);
+        }
+        pos = name_start + name_length + page_count * SECTION_PAGE_INFO_SIZE;
+    }
+
+    // Retire the old section map page and list the new one after the others.
+    let unused_id = page_map.iter().map(|entry| entry.id).max().unwrap() + 1;
+    let section_map_page = system_page(&section_map);
+    let mut entries = Vec::new();
+    for entry in &page_map {
+        let id = if entry.id == header.sections_map_id as i64 {
+            unused_id
+        } else {
+            entry.id
+        };
+        entries.extend_from_slice(&entry.size.to_le_bytes());
+        entries.extend_from_slice(&id.to_le_bytes());
+    }
+    entries.extend_from_slice(&(section_map_page.len() as u64).to_le_bytes());
+    entries.extend_from_slice(&header.sections_map_id.to_le_bytes());
+    entries.extend_from_slice(&[0; 16]);
+    let end = page_map
+        .last()
+        .map(|entry| entry.address + entry.size)
+        .unwrap() as usize;
+
+    let mut patched = bytes.to_vec();
+    patched.resize(end, 0);
+    patched.extend(section_map_page);
+    let page_map_offset = patched.len() as u64 - STREAM_BASE_OFFSET;
+    patched.extend(system_page(&entries));
+
+    // Rewrite the second header with an uncompressed body.
+    let encoded = &bytes[SECOND_HEADER_OFFSET..SECOND_HEADER_OFFSET + SECOND_HEADER_RS_SIZE];
+    let decoded = decode_reed_solomon(encoded, 239, 3, 4).unwrap();
+    let compressed_size = i32::from_le_bytes(decoded[0x18..0x1C].try_into().unwrap());
+    let payload = &decoded[SECOND_HEADER_PAYLOAD_OFFSET..];
+    let mut body = if compressed_size < 0 {
+        payload[..SECOND_HEADER_BODY_SIZE].to_vec()
+    } else {
+        decompress_r21(
+            &payload[..compressed_size as usize],
+            SECOND_HEADER_BODY_SIZE,
+            &config,
+        )
+        .unwrap()
+    };
+    for (field, value) in [
+        (3, 1),
+        (7, page_map_offset),
+        (10, entries.len() as u64),
+        (11, entries.len() as u64),
+        (22, section_map.len() as u64),
+        (25, section_map.len() as u64),
+        (27, 1),
+    ] {
+        body[field * 8..field * 8 + 8].copy_from_slice(&value.to_le_bytes());
+    }
+    let mut head = decoded[..SECOND_HEADER_PAYLOAD_OFFSET].to_vec();
+    head[0x18..0x1C].copy_from_slice(&(-(SECOND_HEADER_BODY_SIZE as i32)).to_le_bytes());
+    head.extend(body);
+    let encoded_head = encode(&head, 3);
+    patched[SECOND_HEADER_OFFSET..SECOND_HEADER_OFFSET + encoded_head.len()]
+        .copy_from_slice(&encoded_head);
+    patched
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1346,17 +1052,17 @@ mod tests {
         assert!(dir
             .records
             .iter()
-            .any(|record| record.name.as_deref() == Some("AcDb:Classes")));
+            .any(|record| record.name.as_deref() == Some(NamedSection::Classes.as_str())));
         assert!(dir
             .records
             .iter()
-            .any(|record| record.name.as_deref() == Some("AcDb:Handles")));
+            .any(|record| record.name.as_deref() == Some(NamedSection::Handles.as_str())));
         assert!(dir
             .records
             .iter()
-            .any(|record| record.name.as_deref() == Some("AcDb:AcDbObjects")));
+            .any(|record| record.name.as_deref() == Some(NamedSection::AcDbObjects.as_str())));
         assert!(dir.records.iter().any(|record| {
-            record.name.as_deref() == Some("AcDb:AcDbObjects")
+            record.name.as_deref() == Some(NamedSection::AcDbObjects.as_str())
                 && record.offset > 0
                 && record.size > 0
         }));
@@ -1370,7 +1076,7 @@ mod tests {
         let index = dir
             .records
             .iter()
-            .position(|record| record.name.as_deref() == Some("AcDb:Handles"))
+            .position(|record| record.name.as_deref() == Some(NamedSection::Handles.as_str()))
             .expect("handles section");
         let section = load_section_by_index(&bytes, &dir, index, &Default::default())
             .expect("handles section data");
@@ -0,0 +1,152 @@
+//! Object index recovery for R2004+ files whose object map is unreadable.
+//!
+//! Records in `AcDb:AcDbObjects` follow each other: an MS size, for R2010+
+//! an MC handle stream size the MS does not count, the data, and a CRC over
+//! everything before it. A position whose data starts with a type code and
+//! an own handle, and whose CRC matches, is taken as a record, and the scan
+//! resumes after it; elsewhere it moves on by one byte. Sizes past the data
+//! or the section limit are rejected before any CRC is computed, so garbage
+//! costs little more than its length to step over.
+
+use crate::bit::{BitReader, Endian};
+use crate::core::crc::{compute_crc, CRC_SEED};
+use crate::dwg::entity_dispatch::skip_object_type_prefix;
+use crate::dwg::version::DwgVersion;
+use crate::objects::{object_record, Handle, ObjectRef};
+
+/// Every record found in `objects_data`, in offset order, ignoring claimed
+/// sizes above `max_record_bytes`.
+pub fn scan_object_records(
+    objects_data: &[u8],
+    version: &DwgVersion,
+    max_record_bytes: u64,
+) -> Vec<ObjectRef> {
+    let mut objects = Vec::new();
+    let mut offset = 0;
+    while offset < objects_data.len() {
+        match record_at(objects_data, offset, version, max_record_bytes) {
+            Some((handle, end)) => {
+                objects.push(ObjectRef {
+                    handle: Handle(handle),
+                    offset: offset as u32,
+                });
+                offset = end;
+            }
+            None => offset += 1,
+        }
+    }
+    objects
+}
+
+/// Handle of the record at `offset` and the offset just past its CRC.
+fn record_at(
+    data: &[u8],
+    offset: usize,
+    version: &DwgVersion,
+    max_record_bytes: u64,
+) -> Option<(u64, usize)> {
+    let r2010_plus = matches!(
+        version,
+        DwgVersion::R2010 | DwgVersion::R2013 | DwgVersion::R2018
+    );
+    let mut reader = BitReader::new(data);
+    reader.set_pos(offset, 0);
+    let size = reader.read_ms().ok()?;
+    if size == 0 || u64::from(size) > max_record_bytes {
+        return None;
+    }
+    if r2010_plus {
+        reader.read_umc().ok()?;
+    }
+    let (data_start, _) = reader.get_pos();
+    let crc_start = data_start.checked_add(size as usize)?;
+    let stored_crc = data.get(crc_start..crc_start.checked_add(2)?)?;
+
+    let record = object_record::parse_object_record(data, u32::try_from(offset).ok()?).ok()?;
+    let mut reader = record.bit_reader();
+    skip_object_type_prefix(&mut reader, version).ok()?;
+    if !r2010_plus {
+        let _obj_size = reader.read_rl(Endian::Little).ok()?;
+    }
+    let handle = reader.read_h().ok()?;
+    if handle.code != 0 || handle.value == 0 {
+        return None;
+    }
+
+    if compute_crc(&data[offset..crc_start], CRC_SEED).to_le_bytes() != stored_crc {
+        return None;
+    }
+    Some((handle.value, crc_start + 2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::ParseConfig;
+    use crate::dwg::decoder::Decoder;
+
+    #[test]
+    fn scan_finds_the_records_the_object_map_lists() {
+        for name in [
+            "line_2004",
+            "line_2007",
+            "line_2013",
+            "acadsharp/sample_AC1032",
+        ] {
+            let bytes = std::fs::read(format!("test_dwg/{name}.dwg")).expect("sample file");
+            let decoder = Decoder::new(&bytes, ParseConfig::default()).expect("decoder");
+            let index = decoder.build_object_index().expect("object index");
+            let data = decoder.objects_data().expect("objects data");
+            let scanned = scan_object_records(&data, decoder.version(), u64::MAX);
+            let mut listed: Vec<_> = index
+                .objects
+                .iter()
+                .map(|object| (object.offset, object.handle.0))
+                .collect();
+            listed.sort();
+            let found: Vec<_> = scanned
+                .iter()
+                .map(|object| (object.offset, object.handle.0))
+                .collect();
+            assert_eq!(found, listed, "{name}");
+        }
+    }
+
+    #[test]
+    fn scan_steps_over_garbage_between_records() {
+        let bytes = std::fs::read("test_dwg/line_2004.dwg").expect("sample file");
+        let decoder = Decoder::new(&bytes, ParseConfig::default()).expect("decoder");
+        let data = decoder.objects_data().expect("objects data");
+        let records = scan_object_records(&data, decoder.version(), u64::MAX);
+        let first = records[0].offset as usize;
+        let second = records[1].offset as usize;
+        let third = records[2].offset as usize;
+
+        // A deterministic noise gap, with a large MS size at its start.
+        let mut state = 0x2545_F491_u32;
+        let mut garbage = vec![0xFF, 0xFF, 0xFF, 0x7F];
+        garbage.extend((0..4096).map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        }));
+        let mut patched = data[first..second].to_vec();
+        patched.extend(&garbage);
+        patched.extend(&data[second..third]);
+
+        let found: Vec<_> = scan_object_records(&patched, decoder.version(), u64::MAX)
+            .iter()
+            .map(|object| (object.offset as usize, object.handle))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (0, records[0].handle),
+                (second - first + garbage.len(), records[1].handle),
+            ]
+        );
+        // A limit below the record sizes finds nothing.
+        assert!(scan_object_records(&patched, decoder.version(), 1).is_empty());
+    }
+}
@@ -3,6 +3,8 @@ use crate::core::result::Result;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum DwgVersion {
+    /// Recognized from its tag; not decoded.
+    R13,
     R14,
     R2000,
     R2004,
@@ -13,9 +15,40 @@ pub enum DwgVersion {
     Unknown(String),
 }
 
+/// Release tags in the first six bytes of a file, oldest first.
+const TAGS: [(&[u8; 6], DwgVersion); 8] = [
+    (b"AC1012", DwgVersion::R13),
+    (b"AC1014", DwgVersion::R14),
+    (b"AC1015", DwgVersion::R2000),
+    (b"AC1018", DwgVersion::R2004),
+    (b"AC1021", DwgVersion::R2007),
+    (b"AC1024", DwgVersion::R2010),
+    (b"AC1027", DwgVersion::R2013),
+    (b"AC1032", DwgVersion::R2018),
+];
+
 impl DwgVersion {
+    /// The release a tag names; `Unknown` keeps unrecognized tags verbatim.
+    pub fn from_tag(tag: &[u8; 6]) -> Self {
+        TAGS.iter()
+            .find(|(known, _)| *known == tag)
+            .map(|(_, version)| version.clone())
+            .unwrap_or_else(|| Self::Unknown(String::from_utf8_lossy(tag).into_owned()))
+    }
+
+    /// Inverse of [`from_tag`](Self::from_tag). An `Unknown` tag that is not
+    /// six bytes long is cut off or padded with NULs.
+    pub fn as_tag(&self) -> [u8; 6] {
+        let mut tag = [0u8; 6];
+        let text = self.as_str().as_bytes();
+        let len = text.len().min(6);
+        tag[..len].copy_from_slice(&text[..len]);
+        tag
+    }
+
     pub fn as_str(&self) -> &str {
         match self {
+            Self::R13 => "AC1012",
             Self::R14 => "AC1014",
             Self::R2000 => "AC1015",
             Self::R2004 => "AC1018",
@@ -28,6 +61,64 @@ impl DwgVersion {
     }
 }
 
+/// What a file is, judged from its first bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileKind {
+    /// A DWG drawing; `Unknown` for releases this crate does not read.
+    Dwg(DwgVersion),
+    DxfAscii,
+    DxfBinary,
+    Dwf,
+    /// Not a recognized CAD file.
+    Other,
+}
+
+impl FileKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Dwg(_) => "dwg",
+            Self::DxfAscii => "dxf",
+            Self::DxfBinary => "dxf_binary",
+            Self::Dwf => "dwf",
+            Self::Other => "other",
+        }
+    }
+}
+
+/// Bytes a caller should pass to [`sniff_bytes`]; fewer still work for
+/// everything but binary DXF, whose sentinel is 22 bytes long.
+pub const SNIFF_LEN: usize = 32;
+
+/// Classify a file from its first bytes. DWG files start with an `AC`
+/// release tag, binary DXF with `AutoCAD Binary DXF`, DWF with `(DWF V`.
+/// ASCII DXF starts, after optional whitespace, with the group code `0` or
+/// the comment code `999` on a line of its own.
+pub fn sniff_bytes(head: &[u8]) -> FileKind {
+    if head.starts_with(b"AutoCAD Binary DXF") {
+        return FileKind::DxfBinary;
+    }
+    if head.starts_with(b"(DWF V") {
+        return FileKind::Dwf;
+    }
+    if let Some(tag) = head.first_chunk::<6>() {
+        if tag.starts_with(b"AC") && tag.iter().all(|byte| byte.is_ascii_graphic()) {
+            return FileKind::Dwg(DwgVersion::from_tag(tag));
+        }
+    }
+    let text = head.trim_ascii_start();
+    let code_len = text
+        .iter()
+        .position(|byte| !byte.is_ascii_digit())
+        .unwrap_or(text.len());
+    let (code, rest) = text.split_at(code_len);
+    if matches!(code, b"0" | b"999") && matches!(rest.first(), Some(b'\r' | b'\n')) {
+        return FileKind::DxfAscii;
+    }
+    FileKind::Other
+}
+
+/// DWG release of `bytes`. Files [`sniff_bytes`] recognizes as something
+/// else fail with [`ErrorKind::NotDwg`] naming what they are.
 pub fn detect_version(bytes: &[u8]) -> Result<DwgVersion> {
     if bytes.len() < 6 {
         return Err(DwgError::new(
@@ -35,23 +126,27 @@ pub fn detect_version(bytes: &[u8]) -> Result<DwgVersion> {
             "file too small to contain DWG version",
         ));
     }
-    let tag = std::str::from_utf8(&bytes[..6]).unwrap_or("");
-    let version = match tag {
-        "AC1014" => DwgVersion::R14,
-        "AC1015" => DwgVersion::R2000,
-        "AC1018" => DwgVersion::R2004,
-        "AC1021" => DwgVersion::R2007,
-        "AC1024" => DwgVersion::R2010,
-        "AC1027" => DwgVersion::R2013,
-        "AC1032" => DwgVersion::R2018,
-        other => DwgVersion::Unknown(other.to_string()),
-    };
-    Ok(version)
+    match sniff_bytes(bytes) {
+        FileKind::Dwg(version) => Ok(version),
+        FileKind::DxfAscii | FileKind::DxfBinary => Err(DwgError::new(
+            ErrorKind::NotDwg,
+            "this is a DXF file, not a DWG",
+        )),
+        FileKind::Dwf => Err(DwgError::new(
+            ErrorKind::NotDwg,
+            "this is a DWF file, not a DWG",
+        )),
+        FileKind::Other => Err(DwgError::new(
+            ErrorKind::NotDwg,
+            "not a DWG file: no AC version tag",
+        )),
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{detect_version, DwgVersion};
+    use super::{detect_version, sniff_bytes, DwgVersion, FileKind};
+    use crate::core::error::ErrorKind;
 
     #[test]
     fn detects_known_versions() {
@@ -63,4 +158,48 @@ mod tests {
         assert_eq!(detect_version(b"AC1027xxxx").unwrap(), DwgVersion::R2013);
         assert_eq!(detect_version(b"AC1032xxxx").unwrap(), DwgVersion::R2018);
     }
+
+    #[test]
+    fn tags_round_trip() {
+        for tag in [
+            b"AC1012", b"AC1014", b"AC1015", b"AC1018", b"AC1021", b"AC1024", b"AC1027", b"AC1032",
+        ] {
+            let version = DwgVersion::from_tag(tag);
+            assert!(!matches!(version, DwgVersion::Unknown(_)), "{version:?}");
+            assert_eq!(&version.as_tag(), tag);
+        }
+        assert_eq!(DwgVersion::from_tag(b"AC1012"), DwgVersion::R13);
+        let unknown = DwgVersion::from_tag(b"AC1009");
+        assert_eq!(unknown, DwgVersion::Unknown("AC1009".to_string()));
+        assert_eq!(&unknown.as_tag(), b"AC1009");
+        assert_eq!(
+            &DwgVersion::Unknown("AC1".to_string()).as_tag(),
+            b"AC1\0\0\0"
+        );
+    }
+
+    #[test]
+    fn sniffs_dxf_dwf_and_other_files() {
+        assert_eq!(
+            sniff_bytes(b"AC1009\0\0\0"),
+            FileKind::Dwg(DwgVersion::Unknown("AC1009".to_string()))
+        );
+        assert_eq!(sniff_bytes(b"  0\r\nSECTION\r\n"), FileKind::DxfAscii);
+        assert_eq!(sniff_bytes(b"999\ncomment\n0\nSECTION"), FileKind::DxfAscii);
+        assert_eq!(
+            sniff_bytes(b"AutoCAD Binary DXF\r\n\x1a\0"),
+            FileKind::DxfBinary
+        );
+        assert_eq!(sniff_bytes(b"(DWF V06.00)"), FileKind::Dwf);
+        assert_eq!(sniff_bytes(b"%PDF-1.7"), FileKind::Other);
+        assert_eq!(sniff_bytes(b"10\n0.0"), FileKind::Other);
+
+        let err = detect_version(b"  0\nSECTION").unwrap_err();
+        assert_eq!(err.kind, ErrorKind::NotDwg);
+        assert!(err.message.contains("DXF"));
+        assert_eq!(
+            detect_version(b"PK\x03\x04rest").unwrap_err().kind,
+            ErrorKind::NotDwg
+        );
+    }
 }
@@ -4,10 +4,15 @@ use crate::core::result::Result;
 use crate::entities::common::{
     parse_common_entity_handles, parse_common_entity_header, parse_common_entity_header_r14,
     parse_common_entity_header_r2007, parse_common_entity_header_r2010,
-    parse_common_entity_header_r2013, parse_common_entity_layer_handle, CommonEntityHeader,
+    parse_common_entity_header_r2013, parse_common_entity_layer_handle, seek_handle_stream,
+    CommonEntityHeader,
 };
+use std::f64::consts::TAU;
 use std::sync::atomic::{AtomicU32, Ordering};
 
+type Point3 = (f64, f64, f64);
+type ArcBody = (Point3, f64, Point3, f64, f64);
+
 static R14_ARC_PREFERRED_DELTA: AtomicU32 = AtomicU32::new(64);
 
 #[derive(Debug, Clone)]
@@ -15,13 +20,44 @@ pub struct ArcEntity {
     pub handle: u64,
     pub color_index: Option<u16>,
     pub true_color: Option<u32>,
+    pub transparency: Option<u32>,
+    pub ltype_scale: f64,
+    pub lineweight: Option<i8>,
     pub layer_handle: u64,
     pub center: (f64, f64, f64),
     pub radius: f64,
+    pub extrusion: (f64, f64, f64),
     pub angle_start: f64,
     pub angle_end: f64,
 }
 
+impl ArcEntity {
+    /// DWG arcs always run counter-clockwise from `angle_start` to
+    /// `angle_end` about the extrusion (OCS Z) axis.
+    pub fn is_ccw(&self) -> bool {
+        true
+    }
+
+    /// `(angle_start, angle_end)` wrapped into `[0, 2*pi)`.
+    pub fn normalized_angles(&self) -> (f64, f64) {
+        (
+            self.angle_start.rem_euclid(TAU),
+            self.angle_end.rem_euclid(TAU),
+        )
+    }
+
+    /// Counter-clockwise sweep in `(0, 2*pi]`; equal start and end angles
+    /// are a full turn, matching how AutoCAD draws them.
+    pub fn sweep_angle(&self) -> f64 {
+        let sweep = (self.angle_end - self.angle_start).rem_euclid(TAU);
+        if sweep == 0.0 {
+            TAU
+        } else {
+            sweep
+        }
+    }
+}
+
 pub fn decode_arc(reader: &mut BitReader<'_>) -> Result<ArcEntity> {
     let header = parse_common_entity_header(reader)?;
     decode_arc_with_header(reader, header, false, false)
@@ -61,7 +97,7 @@ pub fn decode_arc_r14(reader: &mut BitReader<'_>, object_handle: u64) -> Result<
 
 pub fn decode_arc_r2007(reader: &mut BitReader<'_>) -> Result<ArcEntity> {
     let header = parse_common_entity_header_r2007(reader)?;
-    decode_arc_with_header(reader, header, true, true)
+    decode_arc_with_header(reader, header, true, false)
 }
 
 pub fn decode_arc_r2010(
@@ -93,11 +129,11 @@ fn decode_arc_with_header(
     let center = reader.read_3bd()?;
     let radius = reader.read_bd()?;
     let _thickness = reader.read_bt()?;
-    let _extrusion = reader.read_be()?;
+    let extrusion = reader.read_be()?;
     let angle_start = reader.read_bd()?;
     let angle_end = reader.read_bd()?;
     // Handles are stored in the handle stream at obj_size bit offset.
-    reader.set_bit_pos(header.obj_size);
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
     let layer_handle = match if r2007_layer_only {
         parse_common_entity_layer_handle(reader, &header)
     } else {
@@ -120,9 +156,13 @@ fn decode_arc_with_header(
         handle: header.handle,
         color_index: header.color.index,
         true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
         layer_handle,
         center,
         radius,
+        extrusion,
         angle_start,
         angle_end,
     })
@@ -169,9 +209,7 @@ fn decode_arc_r14_fallback(reader: &mut BitReader<'_>, object_handle: u64) -> Re
     ))
 }
 
-fn parse_arc_body_no_common(
-    reader: &mut BitReader<'_>,
-) -> Result<((f64, f64, f64), f64, (f64, f64, f64), f64, f64)> {
+fn parse_arc_body_no_common(reader: &mut BitReader<'_>) -> Result<ArcBody> {
     let center = reader.read_3bd()?;
     let radius = reader.read_bd()?;
     let _thickness = reader.read_bt()?;
@@ -181,9 +219,7 @@ fn parse_arc_body_no_common(
     Ok((center, radius, extrusion, angle_start, angle_end))
 }
 
-fn parse_arc_body_no_common_r14(
-    reader: &mut BitReader<'_>,
-) -> Result<((f64, f64, f64), f64, (f64, f64, f64), f64, f64)> {
+fn parse_arc_body_no_common_r14(reader: &mut BitReader<'_>) -> Result<ArcBody> {
     let center = reader.read_3bd()?;
     let radius = reader.read_bd()?;
     let _thickness = reader.read_bd()?;
@@ -230,9 +266,13 @@ fn consider_arc_r14_delta(
             handle: object_handle,
             color_index: None,
             true_color: None,
+            transparency: None,
+            ltype_scale: 1.0,
+            lineweight: None,
             layer_handle: 0,
             center,
             radius,
+            extrusion,
             angle_start,
             angle_end,
         };
@@ -343,10 +383,49 @@ fn is_high_confidence_arc_candidate(
     }
 
     let span = (angle_end - angle_start).abs();
-    if span < 1.0e-3 || span > 7.0 {
+    if !(1.0e-3..=7.0).contains(&span) {
         return false;
     }
 
     let extrusion_err = extrusion.0.abs() + extrusion.1.abs() + (extrusion.2 - 1.0).abs();
     extrusion_err < 1.0e-6
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::{FRAC_PI_2, PI};
+
+    fn arc(angle_start: f64, angle_end: f64) -> ArcEntity {
+        ArcEntity {
+            handle: 1,
+            color_index: None,
+            true_color: None,
+            transparency: None,
+            ltype_scale: 1.0,
+            lineweight: None,
+            layer_handle: 0,
+            center: (0.0, 0.0, 0.0),
+            radius: 1.0,
+            extrusion: (0.0, 0.0, 1.0),
+            angle_start,
+            angle_end,
+        }
+    }
+
+    #[test]
+    fn sweep_wraps_through_zero() {
+        let entity = arc(3.0 * FRAC_PI_2, FRAC_PI_2);
+        assert!(entity.is_ccw());
+        assert!((entity.sweep_angle() - PI).abs() < 1e-12);
+        assert!((arc(FRAC_PI_2, 3.0 * FRAC_PI_2).sweep_angle() - PI).abs() < 1e-12);
+        assert_eq!(arc(1.0, 1.0).sweep_angle(), TAU);
+    }
+
+    #[test]
+    fn normalizes_out_of_range_angles() {
+        let (start, end) = arc(-FRAC_PI_2, 5.0 * FRAC_PI_2).normalized_angles();
+        assert!((start - 3.0 * FRAC_PI_2).abs() < 1e-12);
+        assert!((end - FRAC_PI_2).abs() < 1e-12);
+    }
+}
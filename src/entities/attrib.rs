@@ -4,14 +4,19 @@ use crate::core::result::Result;
 use crate::entities::common::{
     parse_common_entity_handles, parse_common_entity_header, parse_common_entity_header_r2007,
     parse_common_entity_header_r2010, parse_common_entity_header_r2013,
-    parse_common_entity_layer_handle, read_handle_reference, CommonEntityHeader,
+    parse_common_entity_layer_handle, read_handle_reference, seek_handle_stream,
+    CommonEntityHeader,
 };
+use crate::entities::entity::DecodeMethod;
 
 #[derive(Debug, Clone)]
 pub struct AttribEntity {
     pub handle: u64,
     pub color_index: Option<u16>,
     pub true_color: Option<u32>,
+    pub transparency: Option<u32>,
+    pub ltype_scale: f64,
+    pub lineweight: Option<i8>,
     pub layer_handle: u64,
     pub text: String,
     pub insertion: (f64, f64, f64),
@@ -30,6 +35,9 @@ pub struct AttribEntity {
     pub flags: u8,
     pub lock_position: bool,
     pub prompt: Option<String>,
+    /// `Fallback` when the tag fields follow a version byte, `Guessed` when
+    /// neither layout read them and they are left empty.
+    pub decode_method: DecodeMethod,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -77,7 +85,7 @@ pub fn decode_attdef(reader: &mut BitReader<'_>) -> Result<AttribEntity> {
 
 pub fn decode_attdef_r2007(reader: &mut BitReader<'_>) -> Result<AttribEntity> {
     let header = parse_common_entity_header_r2007(reader)?;
-    decode_attrib_like_with_header(reader, header, true, true)
+    decode_attrib_like_with_header(reader, header, false, true)
 }
 
 pub fn decode_attdef_r2010(
@@ -118,8 +126,7 @@ fn decode_attrib_like_with_header(
     let insertion_y = reader.read_rd(Endian::Little)?;
 
     let alignment = if (data_flags & 0x02) == 0 {
-        let align_x = reader.read_dd(insertion_x)?;
-        let align_y = reader.read_dd(insertion_y)?;
+        let (align_x, align_y) = reader.read_2dd((insertion_x, insertion_y))?;
         Some((align_x, align_y, elevation))
     } else {
         None
@@ -170,11 +177,15 @@ fn decode_attrib_like_with_header(
 
     let tail_start = reader.get_pos();
     let mut tail = AttribTailData::default();
-    for with_version_prefix in [false, true] {
+    let mut decode_method = DecodeMethod::Guessed;
+    for (method, with_version_prefix) in
+        [(DecodeMethod::Exact, false), (DecodeMethod::Fallback, true)]
+    {
         reader.set_pos(tail_start.0, tail_start.1);
         match parse_attrib_tail_data(reader, is_attdef, with_version_prefix) {
             Ok(parsed) => {
                 tail = parsed;
+                decode_method = method;
                 break;
             }
             Err(err)
@@ -187,7 +198,7 @@ fn decode_attrib_like_with_header(
     }
 
     // Handles are stored in the handle stream at obj_size bit offset.
-    reader.set_bit_pos(header.obj_size);
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
     let handles_pos = reader.get_pos();
     let (layer_handle, style_handle) = match parse_common_entity_handles(reader, &header) {
         Ok(common_handles) => (
@@ -212,6 +223,9 @@ fn decode_attrib_like_with_header(
         handle: header.handle,
         color_index: header.color.index,
         true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
         layer_handle,
         text,
         insertion: (insertion_x, insertion_y, elevation),
@@ -230,6 +244,7 @@ fn decode_attrib_like_with_header(
         flags: tail.flags,
         lock_position: tail.lock_position,
         prompt: tail.prompt,
+        decode_method,
     })
 }
 
@@ -4,10 +4,14 @@ use crate::core::result::Result;
 use crate::entities::common::{
     parse_common_entity_handles, parse_common_entity_header, parse_common_entity_header_r14,
     parse_common_entity_header_r2007, parse_common_entity_header_r2010,
-    parse_common_entity_header_r2013, parse_common_entity_layer_handle, CommonEntityHeader,
+    parse_common_entity_header_r2013, parse_common_entity_layer_handle, seek_handle_stream,
+    CommonEntityHeader,
 };
 use std::sync::atomic::{AtomicU32, Ordering};
 
+type Point3 = (f64, f64, f64);
+type CircleBody = (Point3, f64, f64, Point3);
+
 static R14_CIRCLE_PREFERRED_DELTA: AtomicU32 = AtomicU32::new(64);
 
 #[derive(Debug, Clone)]
@@ -15,9 +19,14 @@ pub struct CircleEntity {
     pub handle: u64,
     pub color_index: Option<u16>,
     pub true_color: Option<u32>,
+    pub transparency: Option<u32>,
+    pub ltype_scale: f64,
+    pub lineweight: Option<i8>,
     pub layer_handle: u64,
     pub center: (f64, f64, f64),
     pub radius: f64,
+    pub thickness: f64,
+    pub extrusion: (f64, f64, f64),
 }
 
 pub fn decode_circle(reader: &mut BitReader<'_>) -> Result<CircleEntity> {
@@ -59,7 +68,7 @@ pub fn decode_circle_r14(reader: &mut BitReader<'_>, object_handle: u64) -> Resu
 
 pub fn decode_circle_r2007(reader: &mut BitReader<'_>) -> Result<CircleEntity> {
     let header = parse_common_entity_header_r2007(reader)?;
-    decode_circle_with_header(reader, header, true, true)
+    decode_circle_with_header(reader, header, true, false)
 }
 
 pub fn decode_circle_r2010(
@@ -90,10 +99,10 @@ fn decode_circle_with_header(
 ) -> Result<CircleEntity> {
     let center = reader.read_3bd()?;
     let radius = reader.read_bd()?;
-    let _thickness = reader.read_bt()?;
-    let _extrusion = reader.read_be()?;
+    let thickness = reader.read_bt()?;
+    let extrusion = reader.read_be()?;
     // Handles are stored in the handle stream at obj_size bit offset.
-    reader.set_bit_pos(header.obj_size);
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
     let layer_handle = match if r2007_layer_only {
         parse_common_entity_layer_handle(reader, &header)
     } else {
@@ -116,9 +125,14 @@ fn decode_circle_with_header(
         handle: header.handle,
         color_index: header.color.index,
         true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
         layer_handle,
         center,
         radius,
+        thickness,
+        extrusion,
     })
 }
 
@@ -166,24 +180,20 @@ fn decode_circle_r14_fallback(
     ))
 }
 
-fn parse_circle_body_no_common(
-    reader: &mut BitReader<'_>,
-) -> Result<((f64, f64, f64), f64, (f64, f64, f64))> {
+fn parse_circle_body_no_common(reader: &mut BitReader<'_>) -> Result<CircleBody> {
     let center = reader.read_3bd()?;
     let radius = reader.read_bd()?;
-    let _thickness = reader.read_bt()?;
+    let thickness = reader.read_bt()?;
     let extrusion = reader.read_be()?;
-    Ok((center, radius, extrusion))
+    Ok((center, radius, thickness, extrusion))
 }
 
-fn parse_circle_body_no_common_r14(
-    reader: &mut BitReader<'_>,
-) -> Result<((f64, f64, f64), f64, (f64, f64, f64))> {
+fn parse_circle_body_no_common_r14(reader: &mut BitReader<'_>) -> Result<CircleBody> {
     let center = reader.read_3bd()?;
     let radius = reader.read_bd()?;
-    let _thickness = reader.read_bd()?;
+    let thickness = reader.read_bd()?;
     let extrusion = reader.read_3bd()?;
-    Ok((center, radius, extrusion))
+    Ok((center, radius, thickness, extrusion))
 }
 
 fn consider_circle_r14_delta(
@@ -206,7 +216,7 @@ fn consider_circle_r14_delta(
         } else {
             parse_circle_body_no_common_r14(&mut probe)
         };
-        let Ok((center, radius, extrusion)) = parsed else {
+        let Ok((center, radius, thickness, extrusion)) = parsed else {
             continue;
         };
         let Some(mut score) = score_circle_candidate(delta, center, radius, extrusion) else {
@@ -221,9 +231,14 @@ fn consider_circle_r14_delta(
             handle: object_handle,
             color_index: None,
             true_color: None,
+            transparency: None,
+            ltype_scale: 1.0,
+            lineweight: None,
             layer_handle: 0,
             center,
             radius,
+            thickness,
+            extrusion,
         };
 
         if is_high_confidence_circle_candidate(delta, center, radius, extrusion, score) {
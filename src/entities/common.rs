@@ -1,11 +1,25 @@
+//! The common prefixes of entity and object records and their handles.
+//!
+//! Both headers keep the handle stream position in `obj_size`: R2000-R2007
+//! store it as the RL object size, counted from the start of the body
+//! (object type included), and R2007 places its string stream just before
+//! it. R2010+ headers take it from the record's handle stream size.
+
+use std::ops::Range;
+
 use crate::bit::HandleRef;
 use crate::bit::{BitReader, Endian};
+use crate::core::diag::diag_warn;
+use crate::core::error::{DwgError, ErrorKind};
 use crate::core::result::Result;
 
 #[derive(Debug, Clone, Copy, Default)]
 pub struct CommonEntityColor {
     pub index: Option<u16>,
     pub true_color: Option<u32>,
+    /// Raw entity transparency (alpha type in the high byte), present when
+    /// the color flags carry 0x2000. R2004+ only.
+    pub transparency: Option<u32>,
 }
 
 #[derive(Debug, Clone)]
@@ -13,6 +27,11 @@ pub struct CommonEntityHeader {
     pub obj_size: u32,
     pub handle: u64,
     pub color: CommonEntityColor,
+    pub ltype_scale: f64,
+    /// Raw DWG lineweight byte: 0-23 index the standard lineweight table,
+    /// 29/30/31 are BYLAYER/BYBLOCK/DEFAULT. `None` for R13/R14, which do not
+    /// store one.
+    pub lineweight: Option<i8>,
     pub entity_mode: u8,
     pub num_of_reactors: u32,
     pub xdic_missing_flag: u8,
@@ -24,6 +43,23 @@ pub struct CommonEntityHeader {
     pub has_face_visual_style: bool,
     pub has_edge_visual_style: bool,
     pub has_legacy_entity_links: bool,
+    /// Position and byte size of the embedded proxy graphics, when present.
+    pub graphic: Option<GraphicData>,
+    /// Extended data blocks as `(APPID handle, raw bytes)`, in file order.
+    pub xdata: Vec<(u64, Vec<u8>)>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct GraphicData {
+    pub start: (usize, u8),
+    pub size: usize,
+}
+
+impl CommonEntityHeader {
+    /// Bit offset of the handle stream within the object body.
+    pub fn handle_stream_start_bit(&self) -> u32 {
+        self.obj_size
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -37,6 +73,100 @@ pub struct CommonEntityHandles {
     pub material: Option<u64>,
 }
 
+/// Common prefix of a non-entity object (dictionary, table record, IMAGEDEF,
+/// ...), read right after the object type.
+#[derive(Debug, Clone)]
+pub struct CommonObjectHeader {
+    pub obj_size: u32,
+    pub handle: u64,
+    pub num_of_reactors: u32,
+    pub xdic_missing_flag: u8,
+    pub has_ds_binary_data: bool,
+}
+
+impl CommonObjectHeader {
+    /// Bit offset of the handle stream within the object body.
+    pub fn handle_stream_start_bit(&self) -> u32 {
+        self.obj_size
+    }
+}
+
+pub fn parse_common_object_header(reader: &mut BitReader<'_>) -> Result<CommonObjectHeader> {
+    parse_common_object_header_impl(reader, false, None)
+}
+
+/// R13-R15 objects have no XDictionary-missing flag: the XDictionary handle
+/// is always stored.
+pub fn parse_common_object_header_r2000(reader: &mut BitReader<'_>) -> Result<CommonObjectHeader> {
+    let obj_size = reader.read_rl(Endian::Little)?;
+    let handle = reader.read_h()?.value;
+    skip_eed(reader)?;
+    let num_of_reactors = reader.read_bl()?;
+    Ok(CommonObjectHeader {
+        obj_size,
+        handle,
+        num_of_reactors,
+        xdic_missing_flag: 0,
+        has_ds_binary_data: false,
+    })
+}
+
+/// R13/R14 store the data size after the extended data rather than first.
+pub fn parse_common_object_header_r14(reader: &mut BitReader<'_>) -> Result<CommonObjectHeader> {
+    let handle = reader.read_h()?.value;
+    skip_eed(reader)?;
+    let obj_size = reader.read_rl(Endian::Little)?;
+    let num_of_reactors = reader.read_bl()?;
+    Ok(CommonObjectHeader {
+        obj_size,
+        handle,
+        num_of_reactors,
+        xdic_missing_flag: 0,
+        has_ds_binary_data: false,
+    })
+}
+
+pub fn parse_common_object_header_r2010(
+    reader: &mut BitReader<'_>,
+    object_data_end_bit: u32,
+) -> Result<CommonObjectHeader> {
+    parse_common_object_header_impl(reader, false, Some(object_data_end_bit))
+}
+
+pub fn parse_common_object_header_r2013(
+    reader: &mut BitReader<'_>,
+    object_data_end_bit: u32,
+) -> Result<CommonObjectHeader> {
+    parse_common_object_header_impl(reader, true, Some(object_data_end_bit))
+}
+
+fn parse_common_object_header_impl(
+    reader: &mut BitReader<'_>,
+    r2013_plus: bool,
+    object_data_end_bit: Option<u32>,
+) -> Result<CommonObjectHeader> {
+    let obj_size = match object_data_end_bit {
+        Some(bits) => bits,
+        None => reader.read_rl(Endian::Little)?,
+    };
+    let handle = reader.read_h()?.value;
+    skip_eed(reader)?;
+    let num_of_reactors = reader.read_bl()?;
+    let xdic_missing_flag = reader.read_b()?;
+    let has_ds_binary_data = if r2013_plus {
+        reader.read_b()? != 0
+    } else {
+        false
+    };
+    Ok(CommonObjectHeader {
+        obj_size,
+        handle,
+        num_of_reactors,
+        xdic_missing_flag,
+        has_ds_binary_data,
+    })
+}
+
 pub fn parse_common_entity_header(reader: &mut BitReader<'_>) -> Result<CommonEntityHeader> {
     parse_common_entity_header_impl(reader, false, false, false, None)
 }
@@ -90,29 +220,24 @@ fn parse_common_entity_header_impl(
         None => reader.read_rl(Endian::Little)?,
     };
     let handle = reader.read_h()?.value;
-
-    let mut ext_size = reader.read_bs()?;
-    if ext_size > 0 {
-        let mut size = ext_size;
-        while size > 0 {
-            let _app_handle = reader.read_h()?;
-            for _ in 0..size {
-                let _ = reader.read_rc()?;
-            }
-            ext_size = reader.read_bs()?;
-            size = ext_size;
-        }
-    }
+    let xdata = read_eed(reader)?;
 
     let graphic_present_flag = reader.read_b()?;
-    if graphic_present_flag == 1 {
+    let graphic = if graphic_present_flag == 1 {
         let graphic_size = if r2010_plus {
             reader.read_bll()? as usize
         } else {
             reader.read_rl(Endian::Little)? as usize
         };
+        let start = reader.get_pos();
         let _ = reader.read_rcs(graphic_size)?;
-    }
+        Some(GraphicData {
+            start,
+            size: graphic_size,
+        })
+    } else {
+        None
+    };
 
     let entity_mode = reader.read_bb()?;
     let num_of_reactors = reader.read_bl()?;
@@ -137,14 +262,14 @@ fn parse_common_entity_header_impl(
                 let _name = reader.read_tv()?;
             }
             if flags & 0x2000 != 0 {
-                let _transparency = reader.read_bl()?;
+                color.transparency = Some(reader.read_bl()?);
             }
         }
     } else {
         let _color_unknown = reader.read_b()?;
     }
 
-    let _ltype_scale = reader.read_bd()?;
+    let ltype_scale = reader.read_bd()?;
     let ltype_flags = reader.read_bb()?;
     let plotstyle_flags = reader.read_bb()?;
     let material_flags = if with_material_and_shadow {
@@ -165,12 +290,14 @@ fn parse_common_entity_header_impl(
     };
 
     let _invisibility = reader.read_bs()?;
-    let _line_weight = reader.read_rc()?;
+    let lineweight = Some(reader.read_rc()? as i8);
 
     Ok(CommonEntityHeader {
         obj_size,
         handle,
         color,
+        ltype_scale,
+        lineweight,
         entity_mode,
         num_of_reactors,
         xdic_missing_flag,
@@ -182,6 +309,8 @@ fn parse_common_entity_header_impl(
         has_face_visual_style,
         has_edge_visual_style,
         has_legacy_entity_links: false,
+        graphic,
+        xdata,
     })
 }
 
@@ -190,13 +319,20 @@ fn parse_common_entity_header_r14_impl(
     with_ds_binary_flag: bool,
 ) -> Result<CommonEntityHeader> {
     let handle = reader.read_h()?.value;
-    skip_eed(reader)?;
+    let xdata = read_eed(reader)?;
 
     let graphic_present_flag = reader.read_b()?;
-    if graphic_present_flag == 1 {
+    let graphic = if graphic_present_flag == 1 {
         let graphic_size = reader.read_rl(Endian::Little)? as usize;
+        let start = reader.get_pos();
         let _ = reader.read_rcs(graphic_size)?;
-    }
+        Some(GraphicData {
+            start,
+            size: graphic_size,
+        })
+    } else {
+        None
+    };
 
     let obj_size = reader.read_rl(Endian::Little)?;
     let entity_mode = reader.read_bb()?;
@@ -211,7 +347,7 @@ fn parse_common_entity_header_r14_impl(
     let is_bylayer_ltype = reader.read_b()? != 0;
     let no_links = reader.read_b()?;
     let color = read_common_entity_color_cmc(reader)?;
-    let _ltype_scale = reader.read_bd()?;
+    let ltype_scale = reader.read_bd()?;
     let _invisibility = reader.read_bs()?;
     let _line_weight = reader.read_rc()?;
 
@@ -221,6 +357,8 @@ fn parse_common_entity_header_r14_impl(
         obj_size,
         handle,
         color,
+        ltype_scale,
+        lineweight: None,
         entity_mode,
         num_of_reactors,
         xdic_missing_flag,
@@ -232,6 +370,8 @@ fn parse_common_entity_header_r14_impl(
         has_face_visual_style: false,
         has_edge_visual_style: false,
         has_legacy_entity_links: no_links == 0,
+        graphic,
+        xdata,
     })
 }
 
@@ -260,10 +400,11 @@ fn read_common_entity_color_cmc(reader: &mut BitReader<'_>) -> Result<CommonEnti
     Ok(CommonEntityColor {
         index: Some(color_index),
         true_color,
+        transparency: None,
     })
 }
 
-fn skip_eed(reader: &mut BitReader<'_>) -> Result<()> {
+pub(crate) fn skip_eed(reader: &mut BitReader<'_>) -> Result<()> {
     let mut ext_size = reader.read_bs()?;
     while ext_size > 0 {
         let _app_handle = reader.read_h()?;
@@ -275,6 +416,100 @@ fn skip_eed(reader: &mut BitReader<'_>) -> Result<()> {
     Ok(())
 }
 
+/// Extended data: `[BS size][H app][size bytes]...` terminated by a zero
+/// size.
+pub(crate) fn read_eed(reader: &mut BitReader<'_>) -> Result<Vec<(u64, Vec<u8>)>> {
+    let mut blocks = Vec::new();
+    let mut ext_size = reader.read_bs()?;
+    while ext_size > 0 {
+        let app_handle = reader.read_h()?.value;
+        blocks.push((app_handle, reader.read_rcs(ext_size as usize)?));
+        ext_size = reader.read_bs()?;
+    }
+    Ok(blocks)
+}
+
+/// Reader over the string stream that R2007+ objects keep at the end of
+/// their data, just before `data_end_bit`: `[strings][RS size (+RS high
+/// bits)][B present]`. `None` when the object stores no strings.
+pub fn string_stream_reader<'a>(
+    reader: &BitReader<'a>,
+    data_end_bit: u32,
+) -> Result<Option<BitReader<'a>>> {
+    let Some(range) = string_stream_range(reader, data_end_bit)? else {
+        return Ok(None);
+    };
+    let mut stream = reader.clone();
+    stream.set_bit_pos(range.start);
+    Ok(Some(stream))
+}
+
+/// Bits holding the strings of [`string_stream_reader`], excluding the
+/// trailing size and flag.
+pub fn string_stream_range(
+    reader: &BitReader<'_>,
+    data_end_bit: u32,
+) -> Result<Option<Range<u32>>> {
+    let truncated = || DwgError::new(ErrorKind::Format, "string stream exceeds object data");
+    let mut stream = reader.clone();
+    let Some(flag_bit) = data_end_bit.checked_sub(1) else {
+        return Ok(None);
+    };
+    stream.set_bit_pos(flag_bit);
+    if stream.read_b()? == 0 {
+        return Ok(None);
+    }
+    let mut size_bit = flag_bit.checked_sub(16).ok_or_else(truncated)?;
+    stream.set_bit_pos(size_bit);
+    let mut size_bits = u32::from(stream.read_rs(Endian::Little)?);
+    if size_bits & 0x8000 != 0 {
+        size_bit = size_bit.checked_sub(16).ok_or_else(truncated)?;
+        stream.set_bit_pos(size_bit);
+        let high = u32::from(stream.read_rs(Endian::Little)?);
+        size_bits = (size_bits & 0x7FFF) | (high << 15);
+    }
+    let start_bit = size_bit.checked_sub(size_bits).ok_or_else(truncated)?;
+    Ok(Some(start_bit..size_bit))
+}
+
+/// Padding allowed between the end of the data body and the handle stream.
+const HANDLE_STREAM_SLACK_BITS: u64 = 8;
+
+/// Jump to the handle stream at `start_bit` once the data body has been read
+/// and return the drift in bits (positive when the body ran past it).
+///
+/// A body that ran past `start_bit`, or stopped more than a byte short of it,
+/// means either the field layout was misread or `obj_size` is off; in the
+/// latter case the handles read next (typically the layer) are bogus. A body
+/// that stopped short is only warned about: R2007+ keep the string stream in
+/// the gap, and some decoders read those strings inline or leave trailing
+/// data unread while their handles still resolve. A body that overran is an
+/// error on [strict](BitReader::is_strict) readers.
+#[must_use = "an overrun on a strict reader is an error"]
+pub fn seek_handle_stream(reader: &mut BitReader<'_>, start_bit: u32) -> Result<i64> {
+    let data_end = reader.tell_bits();
+    let start = u64::from(start_bit);
+    let drift = data_end as i64 - start as i64;
+    if data_end > start {
+        let message = format!(
+            "entity data overran handle stream by {} bits (handle stream at {start})",
+            data_end - start
+        );
+        if reader.is_strict() {
+            return Err(DwgError::new(ErrorKind::Format, message));
+        }
+        diag_warn!("entity", "{message}");
+    } else if start - data_end > HANDLE_STREAM_SLACK_BITS {
+        diag_warn!(
+            "entity",
+            "entity data stopped {} bits before handle stream at {start}",
+            start - data_end
+        );
+    }
+    reader.set_bit_pos(start_bit);
+    Ok(drift)
+}
+
 pub fn parse_common_entity_handles(
     reader: &mut BitReader<'_>,
     header: &CommonEntityHeader,
@@ -285,7 +520,11 @@ pub fn parse_common_entity_handles(
         None
     };
 
-    let mut reactors = Vec::with_capacity(header.num_of_reactors as usize);
+    // Every handle reference takes at least one byte; don't trust the count
+    // for the allocation size.
+    let reactor_capacity =
+        (header.num_of_reactors as usize).min((reader.remaining_bits() / 8) as usize);
+    let mut reactors = Vec::with_capacity(reactor_capacity);
     for _ in 0..header.num_of_reactors {
         reactors.push(read_handle_reference(reader, header.handle)?);
     }
@@ -367,14 +606,100 @@ pub fn parse_common_entity_layer_handle(
 }
 
 pub fn read_handle_reference(reader: &mut BitReader<'_>, base_handle: u64) -> Result<u64> {
-    let HandleRef { code, value, .. } = reader.read_h()?;
+    let offset = reader.get_pos().0 as u64;
+    let handle = reader.read_h()?;
+    resolve_handle_reference(handle, base_handle).map_err(|err| err.with_offset(offset))
+}
+
+/// Resolve `handle` against `base_handle`. Offset codes that step outside the
+/// `u64` range are malformed and fail instead of clamping to handle 0, which
+/// would silently point layer/owner references at the wrong object.
+pub fn resolve_handle_reference(handle: HandleRef, base_handle: u64) -> Result<u64> {
+    let HandleRef { code, value, .. } = handle;
     let absolute = match code {
-        0x06 => base_handle + 1,
-        0x08 => base_handle.saturating_sub(1),
-        0x0A => base_handle.saturating_add(value),
-        0x0C => base_handle.saturating_sub(value),
-        0x02..=0x05 => value,
-        _ => value,
+        0x06 => base_handle.checked_add(1),
+        0x08 => base_handle.checked_sub(1),
+        0x0A => base_handle.checked_add(value),
+        0x0C => base_handle.checked_sub(value),
+        _ => Some(value),
     };
-    Ok(absolute)
+    absolute.ok_or_else(|| {
+        DwgError::new(
+            ErrorKind::Format,
+            format!(
+                "relative handle code {code:#X} (offset {value:#X}) out of range for base handle {base_handle:#X}"
+            ),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(bytes: &[u8], base_handle: u64) -> Result<u64> {
+        let mut reader = BitReader::new(bytes);
+        read_handle_reference(&mut reader, base_handle)
+    }
+
+    #[test]
+    fn seek_handle_stream_reports_drift() {
+        let bytes = [0u8; 8];
+        let mut reader = BitReader::new(&bytes);
+        reader.set_bit_pos(27);
+        assert_eq!(seek_handle_stream(&mut reader, 32).unwrap(), -5);
+        assert_eq!(reader.tell_bits(), 32);
+
+        reader.set_bit_pos(40);
+        assert_eq!(seek_handle_stream(&mut reader, 32).unwrap(), 8);
+        assert_eq!(reader.tell_bits(), 32);
+    }
+
+    #[test]
+    fn seek_handle_stream_rejects_overrun_when_strict() {
+        let bytes = [0u8; 8];
+        let mut reader = BitReader::new(&bytes);
+        reader.set_strict(true);
+        reader.set_bit_pos(27);
+        assert_eq!(seek_handle_stream(&mut reader, 32).unwrap(), -5);
+
+        reader.set_bit_pos(40);
+        let err = seek_handle_stream(&mut reader, 32).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Format);
+        assert_eq!(reader.tell_bits(), 40);
+    }
+
+    #[test]
+    fn absolute_codes_return_value() {
+        for code in 0x02u8..=0x05 {
+            assert_eq!(
+                read(&[(code << 4) | 0x02, 0x12, 0x34], 0x99).unwrap(),
+                0x1234
+            );
+        }
+        assert_eq!(read(&[0x50], 0x99).unwrap(), 0);
+    }
+
+    #[test]
+    fn relative_codes_offset_base_handle() {
+        assert_eq!(read(&[0x60], 0x40).unwrap(), 0x41);
+        assert_eq!(read(&[0x80], 0x40).unwrap(), 0x3F);
+        assert_eq!(read(&[0xA1, 0x05], 0x40).unwrap(), 0x45);
+        assert_eq!(read(&[0xC1, 0x05], 0x40).unwrap(), 0x3B);
+        assert_eq!(read(&[0xC1, 0x40], 0x40).unwrap(), 0);
+    }
+
+    #[test]
+    fn out_of_range_relative_handles_are_errors() {
+        for (bytes, base) in [
+            (&[0x80][..], 0),
+            (&[0xC1, 0x41][..], 0x40),
+            (&[0x60][..], u64::MAX),
+            (&[0xA1, 0x01][..], u64::MAX),
+        ] {
+            let err = read(bytes, base).unwrap_err();
+            assert_eq!(err.kind, ErrorKind::Format);
+            assert_eq!(err.offset, Some(0));
+        }
+    }
 }
@@ -0,0 +1,381 @@
+use std::f64::consts::TAU;
+
+use crate::bit::{BitReader, Endian};
+use crate::core::result::Result;
+use crate::entities::common::{
+    parse_common_entity_header, parse_common_entity_header_r2007, parse_common_entity_header_r2010,
+    parse_common_entity_header_r2013,
+};
+use crate::entities::dim_linear::{
+    decode_dimension_r2010_plus_with_header, decode_dimension_with_header, point_score,
+    DimensionCommonData, DimensionTail,
+};
+
+type Point3 = (f64, f64, f64);
+
+/// Angular dimension through a vertex and one point on each leg.
+#[derive(Debug, Clone)]
+pub struct DimAng3PtEntity {
+    pub common: DimensionCommonData,
+    /// A point on the dimension arc.
+    pub point10: Point3,
+    /// End points of the two extension lines.
+    pub point13: Point3,
+    pub point14: Point3,
+    /// Vertex of the angle.
+    pub point15: Point3,
+}
+
+/// Angular dimension between two lines.
+#[derive(Debug, Clone)]
+pub struct DimAng2LnEntity {
+    pub common: DimensionCommonData,
+    /// A point on the dimension arc (OCS XY).
+    pub point16: (f64, f64),
+    /// First line, from `point13` to `point14`.
+    pub point13: Point3,
+    pub point14: Point3,
+    /// Second line, from `point15` to `point10`.
+    pub point15: Point3,
+    pub point10: Point3,
+}
+
+impl DimAng3PtEntity {
+    /// Angle in radians at `point15` between the rays through `point13` and
+    /// `point14`, measured on the side of the dimension arc; more than π
+    /// when the arc sits outside the rays.
+    pub fn measured_angle(&self) -> f64 {
+        let first = sub(self.point13, self.point15);
+        let second = sub(self.point14, self.point15);
+        let angle = angle_between(first, second);
+        match sector_signs(first, second, sub(self.point10, self.point15)) {
+            Some((true, true)) | None => angle,
+            Some(_) => TAU - angle,
+        }
+    }
+}
+
+impl DimAng2LnEntity {
+    /// Angle in radians between the two lines, taken in the one of the four
+    /// sectors they form that holds `point16`. Parallel lines measure zero.
+    pub fn measured_angle(&self) -> f64 {
+        let first = sub(self.point14, self.point13);
+        let second = sub(self.point10, self.point15);
+        let Some(vertex) = intersect(self.point13, first, self.point15, second) else {
+            return 0.0;
+        };
+        let arc = (self.point16.0 - vertex.0, self.point16.1 - vertex.1, 0.0);
+        let (first, second) = match sector_signs(first, second, arc) {
+            Some((first_forward, second_forward)) => (
+                if first_forward { first } else { neg(first) },
+                if second_forward { second } else { neg(second) },
+            ),
+            None => (first, second),
+        };
+        angle_between(first, second)
+    }
+}
+
+struct Ang3PtTail {
+    point10: Point3,
+    point13: Point3,
+    point14: Point3,
+    point15: Point3,
+}
+
+impl DimensionTail for Ang3PtTail {
+    const NAME: &'static str = "DIM_ANG3PT";
+
+    fn read(reader: &mut BitReader<'_>) -> Result<Self> {
+        Ok(Self {
+            point10: reader.read_3bd()?,
+            point13: reader.read_3bd()?,
+            point14: reader.read_3bd()?,
+            point15: reader.read_3bd()?,
+        })
+    }
+
+    fn score(&self) -> u64 {
+        [self.point10, self.point13, self.point14, self.point15]
+            .into_iter()
+            .map(point_score)
+            .fold(0u64, u64::saturating_add)
+    }
+}
+
+struct Ang2LnTail {
+    point16: (f64, f64),
+    point13: Point3,
+    point14: Point3,
+    point15: Point3,
+    point10: Point3,
+}
+
+impl DimensionTail for Ang2LnTail {
+    const NAME: &'static str = "DIM_ANG2LN";
+
+    fn read(reader: &mut BitReader<'_>) -> Result<Self> {
+        Ok(Self {
+            point16: (
+                reader.read_rd(Endian::Little)?,
+                reader.read_rd(Endian::Little)?,
+            ),
+            point13: reader.read_3bd()?,
+            point14: reader.read_3bd()?,
+            point15: reader.read_3bd()?,
+            point10: reader.read_3bd()?,
+        })
+    }
+
+    fn score(&self) -> u64 {
+        [
+            (self.point16.0, self.point16.1, 0.0),
+            self.point13,
+            self.point14,
+            self.point15,
+            self.point10,
+        ]
+        .into_iter()
+        .map(point_score)
+        .fold(0u64, u64::saturating_add)
+    }
+}
+
+impl DimAng3PtEntity {
+    fn from_parts((common, tail): (DimensionCommonData, Ang3PtTail)) -> Self {
+        Self {
+            common,
+            point10: tail.point10,
+            point13: tail.point13,
+            point14: tail.point14,
+            point15: tail.point15,
+        }
+    }
+}
+
+impl DimAng2LnEntity {
+    fn from_parts((common, tail): (DimensionCommonData, Ang2LnTail)) -> Self {
+        Self {
+            common,
+            point16: tail.point16,
+            point13: tail.point13,
+            point14: tail.point14,
+            point15: tail.point15,
+            point10: tail.point10,
+        }
+    }
+}
+
+pub fn decode_dim_ang3pt(reader: &mut BitReader<'_>) -> Result<DimAng3PtEntity> {
+    let header = parse_common_entity_header(reader)?;
+    decode_dimension_with_header(reader, header, false).map(DimAng3PtEntity::from_parts)
+}
+
+pub fn decode_dim_ang3pt_r2007(reader: &mut BitReader<'_>) -> Result<DimAng3PtEntity> {
+    let header = parse_common_entity_header_r2007(reader)?;
+    decode_dimension_with_header(reader, header, true).map(DimAng3PtEntity::from_parts)
+}
+
+pub fn decode_dim_ang3pt_r2010(
+    reader: &mut BitReader<'_>,
+    object_data_end_bit: u32,
+    object_handle: u64,
+) -> Result<DimAng3PtEntity> {
+    let mut header = parse_common_entity_header_r2010(reader, object_data_end_bit)?;
+    header.handle = object_handle;
+    decode_dimension_r2010_plus_with_header(reader, header, true).map(DimAng3PtEntity::from_parts)
+}
+
+pub fn decode_dim_ang3pt_r2013(
+    reader: &mut BitReader<'_>,
+    object_data_end_bit: u32,
+    object_handle: u64,
+) -> Result<DimAng3PtEntity> {
+    let mut header = parse_common_entity_header_r2013(reader, object_data_end_bit)?;
+    header.handle = object_handle;
+    decode_dimension_r2010_plus_with_header(reader, header, true).map(DimAng3PtEntity::from_parts)
+}
+
+pub fn decode_dim_ang2ln(reader: &mut BitReader<'_>) -> Result<DimAng2LnEntity> {
+    let header = parse_common_entity_header(reader)?;
+    decode_dimension_with_header(reader, header, false).map(DimAng2LnEntity::from_parts)
+}
+
+pub fn decode_dim_ang2ln_r2007(reader: &mut BitReader<'_>) -> Result<DimAng2LnEntity> {
+    let header = parse_common_entity_header_r2007(reader)?;
+    decode_dimension_with_header(reader, header, true).map(DimAng2LnEntity::from_parts)
+}
+
+pub fn decode_dim_ang2ln_r2010(
+    reader: &mut BitReader<'_>,
+    object_data_end_bit: u32,
+    object_handle: u64,
+) -> Result<DimAng2LnEntity> {
+    let mut header = parse_common_entity_header_r2010(reader, object_data_end_bit)?;
+    header.handle = object_handle;
+    decode_dimension_r2010_plus_with_header(reader, header, true).map(DimAng2LnEntity::from_parts)
+}
+
+pub fn decode_dim_ang2ln_r2013(
+    reader: &mut BitReader<'_>,
+    object_data_end_bit: u32,
+    object_handle: u64,
+) -> Result<DimAng2LnEntity> {
+    let mut header = parse_common_entity_header_r2013(reader, object_data_end_bit)?;
+    header.handle = object_handle;
+    decode_dimension_r2010_plus_with_header(reader, header, true).map(DimAng2LnEntity::from_parts)
+}
+
+fn sub(a: Point3, b: Point3) -> Point3 {
+    (a.0 - b.0, a.1 - b.1, a.2 - b.2)
+}
+
+fn neg(a: Point3) -> Point3 {
+    (-a.0, -a.1, -a.2)
+}
+
+fn cross_2d(a: Point3, b: Point3) -> f64 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+/// Unsigned angle between two vectors, in `[0, π]`.
+fn angle_between(a: Point3, b: Point3) -> f64 {
+    let cross = (
+        a.1 * b.2 - a.2 * b.1,
+        a.2 * b.0 - a.0 * b.2,
+        a.0 * b.1 - a.1 * b.0,
+    );
+    let cross_len = (cross.0 * cross.0 + cross.1 * cross.1 + cross.2 * cross.2).sqrt();
+    cross_len.atan2(a.0 * b.0 + a.1 * b.1 + a.2 * b.2)
+}
+
+/// Whether `point` lies on the forward side of `first` and of `second`,
+/// from its coordinates in their (XY) basis; `None` when they are parallel.
+fn sector_signs(first: Point3, second: Point3, point: Point3) -> Option<(bool, bool)> {
+    let det = cross_2d(first, second);
+    if det.abs() <= f64::EPSILON * (1.0 + first.0.abs() + first.1.abs()) {
+        return None;
+    }
+    let along_first = cross_2d(point, second) / det;
+    let along_second = cross_2d(first, point) / det;
+    Some((along_first >= 0.0, along_second >= 0.0))
+}
+
+/// XY intersection of two lines given by a point and a direction.
+fn intersect(a: Point3, a_dir: Point3, b: Point3, b_dir: Point3) -> Option<(f64, f64)> {
+    let det = cross_2d(a_dir, b_dir);
+    if det.abs() <= f64::EPSILON * (1.0 + a_dir.0.abs() + a_dir.1.abs()) {
+        return None;
+    }
+    let t = cross_2d(sub(b, a), b_dir) / det;
+    Some((a.0 + a_dir.0 * t, a.1 + a_dir.1 * t))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::{DecodeMethod, Entity};
+    use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+    fn common() -> DimensionCommonData {
+        DimensionCommonData {
+            handle: 1,
+            color_index: None,
+            true_color: None,
+            transparency: None,
+            ltype_scale: 1.0,
+            lineweight: None,
+            layer_handle: 0,
+            extrusion: (0.0, 0.0, 1.0),
+            text_midpoint: (0.0, 0.0, 0.0),
+            elevation: 0.0,
+            dim_flags: 0,
+            user_text: String::new(),
+            text_rotation: 0.0,
+            horizontal_direction: 0.0,
+            insert_scale: (1.0, 1.0, 1.0),
+            insert_rotation: 0.0,
+            attachment_point: None,
+            line_spacing_style: None,
+            line_spacing_factor: None,
+            actual_measurement: None,
+            insert_point: None,
+            dimstyle_handle: None,
+            anonymous_block_handle: None,
+            layout_score: 0,
+            decode_method: DecodeMethod::Exact,
+        }
+    }
+
+    #[test]
+    fn formatted_text_substitutes_the_measurement() {
+        let with_text = |user_text: &str, measurement: Option<f64>| DimensionCommonData {
+            user_text: user_text.to_string(),
+            actual_measurement: measurement,
+            ..common()
+        };
+        assert_eq!(
+            with_text("", Some(12.345)).formatted_text(2).as_deref(),
+            Some("12.35")
+        );
+        assert_eq!(
+            with_text("<> TYP", Some(12.0)).formatted_text(1).as_deref(),
+            Some("12.0 TYP")
+        );
+        assert_eq!(
+            with_text("", Some(-0.0001)).formatted_text(2).as_deref(),
+            Some("0.00")
+        );
+        assert_eq!(
+            with_text("SEE DETAIL", None).formatted_text(2).as_deref(),
+            Some("SEE DETAIL")
+        );
+        assert_eq!(
+            with_text(" ", Some(1.0)).formatted_text(2).as_deref(),
+            Some("")
+        );
+        assert_eq!(with_text("<>", None).formatted_text(2), None);
+
+        let angle = Entity::DimAng3Pt(DimAng3PtEntity {
+            common: with_text("<> MIN", Some(FRAC_PI_4)),
+            point10: (1.0, 1.0, 0.0),
+            point13: (1.0, 0.0, 0.0),
+            point14: (0.0, 1.0, 0.0),
+            point15: (0.0, 0.0, 0.0),
+        });
+        assert_eq!(angle.dimension_text(1).as_deref(), Some("45.0° MIN"));
+    }
+
+    #[test]
+    fn three_point_angle_follows_the_arc_side() {
+        let mut dim = DimAng3PtEntity {
+            common: common(),
+            point10: (1.0, 1.0, 0.0),
+            point13: (2.0, 0.0, 0.0),
+            point14: (0.0, 3.0, 0.0),
+            point15: (0.0, 0.0, 0.0),
+        };
+        assert!((dim.measured_angle() - FRAC_PI_2).abs() < 1e-12);
+        dim.point10 = (-1.0, -1.0, 0.0);
+        assert!((dim.measured_angle() - 3.0 * FRAC_PI_2).abs() < 1e-12);
+    }
+
+    #[test]
+    fn two_line_angle_uses_sector_of_arc_point() {
+        // The X axis and the diagonal through the origin.
+        let mut dim = DimAng2LnEntity {
+            common: common(),
+            point16: (3.0, 1.0),
+            point13: (-1.0, 0.0, 0.0),
+            point14: (1.0, 0.0, 0.0),
+            point15: (-1.0, -1.0, 0.0),
+            point10: (-2.0, -2.0, 0.0),
+        };
+        assert!((dim.measured_angle() - FRAC_PI_4).abs() < 1e-12);
+        dim.point16 = (-1.0, 3.0);
+        assert!((dim.measured_angle() - 3.0 * FRAC_PI_4).abs() < 1e-12);
+        dim.point15 = (0.0, 1.0, 0.0);
+        dim.point10 = (5.0, 1.0, 0.0);
+        assert_eq!(dim.measured_angle(), 0.0);
+    }
+}
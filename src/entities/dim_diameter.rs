@@ -4,11 +4,12 @@ use crate::core::result::Result;
 use crate::entities::common::{
     parse_common_entity_handles, parse_common_entity_header_r2010,
     parse_common_entity_header_r2013, parse_common_entity_layer_handle, read_handle_reference,
-    CommonEntityHeader,
+    seek_handle_stream, CommonEntityHeader,
 };
 use crate::entities::dim_linear::{
     decode_dim_linear, decode_dim_linear_r2007, DimLinearEntity, DimensionCommonData,
 };
+use crate::entities::entity::DecodeMethod;
 
 pub type DimDiameterEntity = DimLinearEntity;
 
@@ -115,7 +116,8 @@ fn decode_dim_diameter_r2010_plus_with_header(
         }
     }
 
-    if let Some((_, entity)) = best {
+    if let Some((score, mut entity)) = best {
+        entity.common.set_layout_score(score);
         return Ok(entity);
     }
 
@@ -172,7 +174,7 @@ fn decode_r2010_plus_variant(
     let point10 = reader.read_3bd()?;
     let _leader_length = reader.read_bd()?;
 
-    reader.set_bit_pos(header.obj_size);
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
     let handles_pos = reader.get_pos();
     let (dimstyle_handle, anonymous_block_handle, layer_handle) = match (
         read_handle_reference(reader, header.handle),
@@ -200,6 +202,9 @@ fn decode_r2010_plus_variant(
         handle: header.handle,
         color_index: header.color.index,
         true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
         layer_handle,
         extrusion,
         text_midpoint: (text_mid_x, text_mid_y, elevation),
@@ -217,6 +222,8 @@ fn decode_r2010_plus_variant(
         insert_point,
         dimstyle_handle,
         anonymous_block_handle,
+        layout_score: 0,
+        decode_method: DecodeMethod::Exact,
     };
 
     Ok(DimLinearEntity {
@@ -4,14 +4,19 @@ use crate::core::result::Result;
 use crate::entities::common::{
     parse_common_entity_handles, parse_common_entity_header, parse_common_entity_header_r2007,
     parse_common_entity_header_r2010, parse_common_entity_header_r2013,
-    parse_common_entity_layer_handle, read_handle_reference, CommonEntityHeader,
+    parse_common_entity_layer_handle, read_handle_reference, seek_handle_stream,
+    CommonEntityHeader,
 };
+use crate::entities::entity::DecodeMethod;
 
 #[derive(Debug, Clone)]
 pub struct DimensionCommonData {
     pub handle: u64,
     pub color_index: Option<u16>,
     pub true_color: Option<u32>,
+    pub transparency: Option<u32>,
+    pub ltype_scale: f64,
+    pub lineweight: Option<i8>,
     pub layer_handle: u64,
     pub extrusion: (f64, f64, f64),
     pub text_midpoint: (f64, f64, f64),
@@ -29,6 +34,90 @@ pub struct DimensionCommonData {
     pub insert_point: Option<(f64, f64, f64)>,
     pub dimstyle_handle: Option<u64>,
     pub anonymous_block_handle: Option<u64>,
+    /// Plausibility penalty of the layout variant the record was read with;
+    /// 0 when every value looked sane. See [`GUESSED_LAYOUT_SCORE`].
+    pub layout_score: u64,
+    /// `Exact` for a score of 0, `Guessed` from [`GUESSED_LAYOUT_SCORE`] up.
+    /// Several variants can read a record cleanly, so a clean read is not
+    /// tied to any one of them.
+    pub decode_method: DecodeMethod,
+}
+
+/// Layout scores at or above this come from values no drawing would hold
+/// (non-finite or astronomically large coordinates, out-of-range enums), so
+/// the chosen variant is a best guess rather than a clean decode.
+pub const GUESSED_LAYOUT_SCORE: u64 = 1_000;
+
+impl DimensionCommonData {
+    /// Whether no layout variant decoded cleanly and the fields come from
+    /// the least implausible one.
+    pub fn is_layout_guessed(&self) -> bool {
+        self.layout_score >= GUESSED_LAYOUT_SCORE
+    }
+
+    /// Record the score of the layout variant the fields were read with.
+    pub(crate) fn set_layout_score(&mut self, score: u64) {
+        self.layout_score = score;
+        self.decode_method = if score >= GUESSED_LAYOUT_SCORE {
+            DecodeMethod::Guessed
+        } else if score > 0 {
+            DecodeMethod::Fallback
+        } else {
+            DecodeMethod::Exact
+        };
+    }
+
+    /// The text the dimension displays, with the measurement rounded to
+    /// `decimal_places`: `user_text` with every `<>` replaced by it, the
+    /// measurement alone when there is no user text, and nothing for a
+    /// single space, which suppresses the text. `None` when the text needs a
+    /// measurement the record does not store. Angular dimensions store
+    /// radians; [`Entity::dimension_text`](crate::entities::Entity::dimension_text)
+    /// shows those in degrees.
+    pub fn formatted_text(&self, decimal_places: usize) -> Option<String> {
+        self.text_with(|value| format_measurement(value, decimal_places))
+    }
+
+    /// `user_text` resolved as in [`formatted_text`](Self::formatted_text),
+    /// with `format` writing the measured value.
+    pub(crate) fn text_with(&self, format: impl Fn(f64) -> String) -> Option<String> {
+        match self.user_text.as_str() {
+            " " => Some(String::new()),
+            "" => self.actual_measurement.map(format),
+            text if text.contains("<>") => {
+                let value = format(self.actual_measurement?);
+                Some(text.replace("<>", &value))
+            }
+            text => Some(text.to_string()),
+        }
+    }
+
+    /// A `Decode` error for guessed layouts, for callers that would rather
+    /// fail than use them.
+    pub fn ensure_layout_decoded(&self) -> Result<()> {
+        if !self.is_layout_guessed() {
+            return Ok(());
+        }
+        Err(DwgError::new(
+            ErrorKind::Decode,
+            format!(
+                "dimension handle {:#X} matches no layout cleanly (score {})",
+                self.handle, self.layout_score
+            ),
+        ))
+    }
+}
+
+/// `value` with `decimal_places` digits, dropping the sign of values that
+/// round to zero.
+pub(crate) fn format_measurement(value: f64, decimal_places: usize) -> String {
+    let text = format!("{value:.decimal_places$}");
+    match text.strip_prefix('-') {
+        Some(unsigned) if unsigned.bytes().all(|byte| matches!(byte, b'0' | b'.')) => {
+            unsigned.to_string()
+        }
+        _ => text,
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -51,14 +140,71 @@ struct DimLinearVariant {
     style_before_common: bool,
 }
 
+/// Fields a dimension type stores after the ones all dimensions share.
+pub(crate) trait DimensionTail: Sized {
+    /// Entity name used in decode errors.
+    const NAME: &'static str;
+
+    fn read(reader: &mut BitReader<'_>) -> Result<Self>;
+
+    /// Plausibility penalty of the fields, added to the shared fields' one
+    /// when choosing between layout variants.
+    fn score(&self) -> u64;
+}
+
+struct LinearTail {
+    point13: (f64, f64, f64),
+    point14: (f64, f64, f64),
+    point10: (f64, f64, f64),
+    ext_line_rotation: f64,
+    dim_rotation: f64,
+}
+
+impl DimensionTail for LinearTail {
+    const NAME: &'static str = "DIM_LINEAR";
+
+    fn read(reader: &mut BitReader<'_>) -> Result<Self> {
+        Ok(Self {
+            point13: reader.read_3bd()?,
+            point14: reader.read_3bd()?,
+            point10: reader.read_3bd()?,
+            ext_line_rotation: reader.read_bd()?,
+            dim_rotation: reader.read_bd()?,
+        })
+    }
+
+    fn score(&self) -> u64 {
+        [self.point10, self.point13, self.point14]
+            .into_iter()
+            .map(point_score)
+            .chain([self.ext_line_rotation, self.dim_rotation].map(angle_score))
+            .fold(0u64, u64::saturating_add)
+    }
+}
+
+impl DimLinearEntity {
+    fn from_parts(common: DimensionCommonData, tail: LinearTail) -> Self {
+        Self {
+            common,
+            point13: tail.point13,
+            point14: tail.point14,
+            point10: tail.point10,
+            ext_line_rotation: tail.ext_line_rotation,
+            dim_rotation: tail.dim_rotation,
+        }
+    }
+}
+
 pub fn decode_dim_linear(reader: &mut BitReader<'_>) -> Result<DimLinearEntity> {
     let header = parse_common_entity_header(reader)?;
-    decode_dim_linear_with_header(reader, header, false)
+    let (common, tail) = decode_dimension_with_header(reader, header, false)?;
+    Ok(DimLinearEntity::from_parts(common, tail))
 }
 
 pub fn decode_dim_linear_r2007(reader: &mut BitReader<'_>) -> Result<DimLinearEntity> {
     let header = parse_common_entity_header_r2007(reader)?;
-    decode_dim_linear_with_header(reader, header, true)
+    let (common, tail) = decode_dimension_with_header(reader, header, true)?;
+    Ok(DimLinearEntity::from_parts(common, tail))
 }
 
 pub fn decode_dim_linear_r2010(
@@ -68,7 +214,8 @@ pub fn decode_dim_linear_r2010(
 ) -> Result<DimLinearEntity> {
     let mut header = parse_common_entity_header_r2010(reader, object_data_end_bit)?;
     header.handle = object_handle;
-    decode_dim_linear_r2010_plus_with_header(reader, header, true)
+    let (common, tail) = decode_dimension_r2010_plus_with_header(reader, header, true)?;
+    Ok(DimLinearEntity::from_parts(common, tail))
 }
 
 pub fn decode_dim_linear_r2013(
@@ -78,14 +225,17 @@ pub fn decode_dim_linear_r2013(
 ) -> Result<DimLinearEntity> {
     let mut header = parse_common_entity_header_r2013(reader, object_data_end_bit)?;
     header.handle = object_handle;
-    decode_dim_linear_r2010_plus_with_header(reader, header, true)
+    let (common, tail) = decode_dimension_r2010_plus_with_header(reader, header, true)?;
+    Ok(DimLinearEntity::from_parts(common, tail))
 }
 
-fn decode_dim_linear_r2010_plus_with_header(
+/// Shared fields and `T` of an R2010+ dimension, trying each known layout
+/// variant and keeping the most plausible result.
+pub(crate) fn decode_dimension_r2010_plus_with_header<T: DimensionTail>(
     reader: &mut BitReader<'_>,
     header: CommonEntityHeader,
     allow_handle_decode_failure: bool,
-) -> Result<DimLinearEntity> {
+) -> Result<(DimensionCommonData, T)> {
     let data_pos = reader.get_pos();
     let variants = [
         R2010PlusVariant {
@@ -130,14 +280,18 @@ fn decode_dim_linear_r2010_plus_with_header(
         },
     ];
 
-    let mut best: Option<(u64, DimLinearEntity)> = None;
+    let mut best: Option<(u64, (DimensionCommonData, T))> = None;
     let mut last_error: Option<DwgError> = None;
     for parse_variant in variants {
         reader.set_pos(data_pos.0, data_pos.1);
-        match decode_r2010_plus_variant(reader, &header, parse_variant, allow_handle_decode_failure)
-        {
+        match decode_r2010_plus_variant::<T>(
+            reader,
+            &header,
+            parse_variant,
+            allow_handle_decode_failure,
+        ) {
             Ok(entity) => {
-                let score = plausibility_score(&entity);
+                let score = plausibility_score(&entity.0, entity.1.score());
                 match &best {
                     Some((best_score, _)) if score >= *best_score => {}
                     _ => best = Some((score, entity)),
@@ -147,14 +301,15 @@ fn decode_dim_linear_r2010_plus_with_header(
         }
     }
 
-    if let Some((_, entity)) = best {
-        return Ok(entity);
+    if let Some((score, (mut common, tail))) = best {
+        common.set_layout_score(score);
+        return Ok((common, tail));
     }
 
     Err(last_error.unwrap_or_else(|| {
         DwgError::new(
             ErrorKind::Decode,
-            "failed to decode R2010+ DIM_LINEAR with all variants",
+            format!("failed to decode R2010+ {} with all variants", T::NAME),
         )
     }))
 }
@@ -166,12 +321,12 @@ struct R2010PlusVariant {
     extrusion_is_be: bool,
 }
 
-fn decode_r2010_plus_variant(
+fn decode_r2010_plus_variant<T: DimensionTail>(
     reader: &mut BitReader<'_>,
     header: &CommonEntityHeader,
     parse_variant: R2010PlusVariant,
     allow_handle_decode_failure: bool,
-) -> Result<DimLinearEntity> {
+) -> Result<(DimensionCommonData, T)> {
     if parse_variant.has_dimension_version {
         let _dimension_version = reader.read_rc()?;
     }
@@ -206,13 +361,9 @@ fn decode_r2010_plus_variant(
     let point12_y = reader.read_rd(Endian::Little)?;
     let insert_point = Some((point12_x, point12_y, elevation));
 
-    let point13 = reader.read_3bd()?;
-    let point14 = reader.read_3bd()?;
-    let point10 = reader.read_3bd()?;
-    let ext_line_rotation = reader.read_bd()?;
-    let dim_rotation = reader.read_bd()?;
+    let tail = T::read(reader)?;
 
-    reader.set_bit_pos(header.obj_size);
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
     let handles_pos = reader.get_pos();
     let (dimstyle_handle, anonymous_block_handle, layer_handle) = match (
         read_handle_reference(reader, header.handle),
@@ -231,7 +382,7 @@ fn decode_r2010_plus_variant(
             reader.set_pos(handles_pos.0, handles_pos.1);
             return Err(DwgError::new(
                 ErrorKind::Decode,
-                "failed to decode DIM_LINEAR handles",
+                format!("failed to decode {} handles", T::NAME),
             ));
         }
     };
@@ -240,6 +391,9 @@ fn decode_r2010_plus_variant(
         handle: header.handle,
         color_index: header.color.index,
         true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
         layer_handle,
         extrusion,
         text_midpoint: (text_mid_x, text_mid_y, elevation),
@@ -257,23 +411,20 @@ fn decode_r2010_plus_variant(
         insert_point,
         dimstyle_handle,
         anonymous_block_handle,
+        layout_score: 0,
+        decode_method: DecodeMethod::Exact,
     };
 
-    Ok(DimLinearEntity {
-        common,
-        point13,
-        point14,
-        point10,
-        ext_line_rotation,
-        dim_rotation,
-    })
+    Ok((common, tail))
 }
 
-fn decode_dim_linear_with_header(
+/// Shared fields and `T` of an R2000-R2007 dimension, trying each known
+/// layout variant and keeping the most plausible result.
+pub(crate) fn decode_dimension_with_header<T: DimensionTail>(
     reader: &mut BitReader<'_>,
     header: CommonEntityHeader,
     allow_handle_decode_failure: bool,
-) -> Result<DimLinearEntity> {
+) -> Result<(DimensionCommonData, T)> {
     let data_pos = reader.get_pos();
 
     let variants = [
@@ -291,13 +442,13 @@ fn decode_dim_linear_with_header(
         variant(false, false, false, false, false, false),
     ];
 
-    let mut best: Option<(u64, DimLinearEntity)> = None;
+    let mut best: Option<(u64, (DimensionCommonData, T))> = None;
     let mut last_error: Option<DwgError> = None;
     for parse_variant in variants {
         reader.set_pos(data_pos.0, data_pos.1);
-        match decode_variant(reader, &header, parse_variant, allow_handle_decode_failure) {
+        match decode_variant::<T>(reader, &header, parse_variant, allow_handle_decode_failure) {
             Ok(entity) => {
-                let score = plausibility_score(&entity);
+                let score = plausibility_score(&entity.0, entity.1.score());
                 match &best {
                     Some((best_score, _)) if score >= *best_score => {}
                     _ => best = Some((score, entity)),
@@ -307,20 +458,22 @@ fn decode_dim_linear_with_header(
         }
     }
 
-    if let Some((_, entity)) = best {
-        return Ok(entity);
+    if let Some((score, (mut common, tail))) = best {
+        common.set_layout_score(score);
+        return Ok((common, tail));
     }
 
-    Err(last_error
-        .unwrap_or_else(|| DwgError::new(ErrorKind::Decode, "failed to decode DIM_LINEAR")))
+    Err(last_error.unwrap_or_else(|| {
+        DwgError::new(ErrorKind::Decode, format!("failed to decode {}", T::NAME))
+    }))
 }
 
-fn decode_variant(
+fn decode_variant<T: DimensionTail>(
     reader: &mut BitReader<'_>,
     header: &CommonEntityHeader,
     parse_variant: DimLinearVariant,
     allow_handle_decode_failure: bool,
-) -> Result<DimLinearEntity> {
+) -> Result<(DimensionCommonData, T)> {
     let extrusion = reader.read_3bd()?;
     let text_mid_x = reader.read_rd(Endian::Little)?;
     let text_mid_y = reader.read_rd(Endian::Little)?;
@@ -357,6 +510,12 @@ fn decode_variant(
     }
 
     let insert_point = if parse_variant.has_point12 {
+        if !reader.has_bits(128) {
+            return Err(DwgError::new(
+                ErrorKind::Decode,
+                format!("{} point 12 does not fit in the record", T::NAME),
+            ));
+        }
         let x = reader.read_rd(Endian::Little)?;
         let y = reader.read_rd(Endian::Little)?;
         Some((x, y, elevation))
@@ -364,14 +523,10 @@ fn decode_variant(
         None
     };
 
-    let point13 = reader.read_3bd()?;
-    let point14 = reader.read_3bd()?;
-    let point10 = reader.read_3bd()?;
-    let ext_line_rotation = reader.read_bd()?;
-    let dim_rotation = reader.read_bd()?;
+    let tail = T::read(reader)?;
 
     // Handles are stored in the handle stream at obj_size bit offset.
-    reader.set_bit_pos(header.obj_size);
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
     let handles_pos = reader.get_pos();
     let (dimstyle_handle, anonymous_block_handle, layer_handle) = if allow_handle_decode_failure {
         let layer = parse_common_entity_layer_handle(reader, header).unwrap_or(0);
@@ -399,6 +554,9 @@ fn decode_variant(
         handle: header.handle,
         color_index: header.color.index,
         true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
         layer_handle,
         extrusion,
         text_midpoint: (text_mid_x, text_mid_y, elevation),
@@ -416,16 +574,11 @@ fn decode_variant(
         insert_point,
         dimstyle_handle,
         anonymous_block_handle,
+        layout_score: 0,
+        decode_method: DecodeMethod::Exact,
     };
 
-    Ok(DimLinearEntity {
-        common,
-        point13,
-        point14,
-        point10,
-        ext_line_rotation,
-        dim_rotation,
-    })
+    Ok((common, tail))
 }
 
 const fn variant(
@@ -446,18 +599,8 @@ const fn variant(
     }
 }
 
-fn plausibility_score(entity: &DimLinearEntity) -> u64 {
-    let mut score = 0u64;
-    let common = &entity.common;
-
-    for pt in [
-        entity.point10,
-        entity.point13,
-        entity.point14,
-        common.text_midpoint,
-    ] {
-        score = score.saturating_add(point_score(pt));
-    }
+fn plausibility_score(common: &DimensionCommonData, tail_score: u64) -> u64 {
+    let mut score = tail_score.saturating_add(point_score(common.text_midpoint));
     if let Some(insert_point) = common.insert_point {
         score = score.saturating_add(point_score(insert_point));
     }
@@ -469,8 +612,6 @@ fn plausibility_score(entity: &DimLinearEntity) -> u64 {
     for angle in [
         common.text_rotation,
         common.horizontal_direction,
-        entity.ext_line_rotation,
-        entity.dim_rotation,
         common.insert_rotation,
     ] {
         score = score.saturating_add(angle_score(angle));
@@ -536,7 +677,7 @@ fn scale_score(scale: (f64, f64, f64)) -> u64 {
     score
 }
 
-fn point_score(point: (f64, f64, f64)) -> u64 {
+pub(crate) fn point_score(point: (f64, f64, f64)) -> u64 {
     value_score(point.0)
         .saturating_add(value_score(point.1))
         .saturating_add(value_score(point.2))
@@ -0,0 +1,220 @@
+//! Typed values of an extended data (XDATA) block.
+//!
+//! Each group is one RC group code (DXF code minus 1000) followed by its
+//! value. Handles are stored as 8 big-endian bytes; everything else is
+//! little-endian.
+
+use crate::core::error::{DwgError, ErrorKind};
+use crate::core::result::Result;
+use crate::io::ByteReader;
+
+type Point3 = (f64, f64, f64);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum XDataValue {
+    /// 1000
+    String(String),
+    /// 1002 `{`
+    ListStart,
+    /// 1002 `}`
+    ListEnd,
+    /// 1003: LAYER record handle.
+    Layer(u64),
+    /// 1004
+    Binary(Vec<u8>),
+    /// 1005
+    Handle(u64),
+    /// 1010
+    Point(Point3),
+    /// 1011
+    WorldPosition(Point3),
+    /// 1012
+    WorldDisplacement(Point3),
+    /// 1013
+    WorldDirection(Point3),
+    /// 1040
+    Real(f64),
+    /// 1041
+    Distance(f64),
+    /// 1042
+    ScaleFactor(f64),
+    /// 1070
+    Short(i16),
+    /// 1071
+    Long(i32),
+}
+
+impl XDataValue {
+    /// DXF group code of the value.
+    pub fn group_code(&self) -> u16 {
+        match self {
+            Self::String(_) => 1000,
+            Self::ListStart | Self::ListEnd => 1002,
+            Self::Layer(_) => 1003,
+            Self::Binary(_) => 1004,
+            Self::Handle(_) => 1005,
+            Self::Point(_) => 1010,
+            Self::WorldPosition(_) => 1011,
+            Self::WorldDisplacement(_) => 1012,
+            Self::WorldDirection(_) => 1013,
+            Self::Real(_) => 1040,
+            Self::Distance(_) => 1041,
+            Self::ScaleFactor(_) => 1042,
+            Self::Short(_) => 1070,
+            Self::Long(_) => 1071,
+        }
+    }
+}
+
+/// Parse one application's XDATA bytes. `unicode` selects the R2007+
+/// string layout (RS length, UTF-16 units) over the older one (RC length,
+/// RS codepage, bytes). Truncated and unknown groups are errors.
+pub fn parse_eed(data: &[u8], unicode: bool) -> Result<Vec<XDataValue>> {
+    let mut values = Vec::new();
+    parse_eed_into(data, unicode, &mut values)?;
+    Ok(values)
+}
+
+/// Like [`parse_eed`], but stops at the first malformed group and returns
+/// the values before it.
+pub fn parse_eed_tolerant(data: &[u8], unicode: bool) -> Vec<XDataValue> {
+    let mut values = Vec::new();
+    let _ = parse_eed_into(data, unicode, &mut values);
+    values
+}
+
+fn parse_eed_into(data: &[u8], unicode: bool, values: &mut Vec<XDataValue>) -> Result<()> {
+    let mut reader = ByteReader::new(data);
+    while reader.remaining() > 0 {
+        let start = reader.tell();
+        let code = reader.read_u8()?;
+        let value = read_value(&mut reader, code, unicode).map_err(|err| {
+            DwgError::new(
+                err.kind,
+                format!("EED group {}: {}", 1000 + u16::from(code), err.message),
+            )
+            .with_offset(start)
+        })?;
+        values.push(value);
+    }
+    Ok(())
+}
+
+fn read_value(reader: &mut ByteReader<'_>, code: u8, unicode: bool) -> Result<XDataValue> {
+    Ok(match code {
+        0 => XDataValue::String(read_string(reader, unicode)?),
+        2 => match reader.read_u8()? {
+            0 => XDataValue::ListStart,
+            1 => XDataValue::ListEnd,
+            other => {
+                return Err(DwgError::new(
+                    ErrorKind::Format,
+                    format!("invalid control string {other}"),
+                ))
+            }
+        },
+        3 => XDataValue::Layer(reader.read_u64_be()?),
+        4 => {
+            let size = usize::from(reader.read_u8()?);
+            XDataValue::Binary(reader.read_bytes(size)?.to_vec())
+        }
+        5 => XDataValue::Handle(reader.read_u64_be()?),
+        10 => XDataValue::Point(read_point(reader)?),
+        11 => XDataValue::WorldPosition(read_point(reader)?),
+        12 => XDataValue::WorldDisplacement(read_point(reader)?),
+        13 => XDataValue::WorldDirection(read_point(reader)?),
+        40 => XDataValue::Real(reader.read_f64_le()?),
+        41 => XDataValue::Distance(reader.read_f64_le()?),
+        42 => XDataValue::ScaleFactor(reader.read_f64_le()?),
+        70 => XDataValue::Short(reader.read_u16_le()? as i16),
+        71 => XDataValue::Long(reader.read_i32_le()?),
+        _ => return Err(DwgError::new(ErrorKind::Format, "unknown group code")),
+    })
+}
+
+fn read_string(reader: &mut ByteReader<'_>, unicode: bool) -> Result<String> {
+    if unicode {
+        let length = usize::from(reader.read_u16_le()?);
+        let bytes = reader.read_bytes(length * 2)?;
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        Ok(String::from_utf16_lossy(&units))
+    } else {
+        let length = usize::from(reader.read_u8()?);
+        let _codepage = reader.read_u16_le()?;
+        Ok(String::from_utf8_lossy(reader.read_bytes(length)?).into_owned())
+    }
+}
+
+fn read_point(reader: &mut ByteReader<'_>) -> Result<Point3> {
+    Ok((
+        reader.read_f64_le()?,
+        reader.read_f64_le()?,
+        reader.read_f64_le()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_unicode_groups() {
+        let mut data = vec![0x00, 0x02, 0x00, b'h', 0x00, b'i', 0x00, 0x02, 0x00];
+        data.extend([0x46, 0x0C, 0x02, 0x47, 0xFE, 0x19, 0x00, 0x00]);
+        data.extend([0x05, 0, 0, 0, 0, 0, 0, 0x02, 0x59]);
+        data.push(0x0A);
+        for value in [1.0f64, 2.0, 3.0] {
+            data.extend(value.to_le_bytes());
+        }
+        data.push(0x28);
+        data.extend(0.5f64.to_le_bytes());
+        data.extend([0x02, 0x01]);
+
+        assert_eq!(
+            parse_eed(&data, true).unwrap(),
+            vec![
+                XDataValue::String("hi".to_string()),
+                XDataValue::ListStart,
+                XDataValue::Short(524),
+                XDataValue::Long(6654),
+                XDataValue::Handle(0x259),
+                XDataValue::Point((1.0, 2.0, 3.0)),
+                XDataValue::Real(0.5),
+                XDataValue::ListEnd,
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_codepage_strings_before_r2007() {
+        let data = [
+            0x00, 0x04, 0x1E, 0x00, b'A', b'C', b'A', b'D', 0x04, 0x02, 0xAB, 0xCD,
+        ];
+        assert_eq!(
+            parse_eed(&data, false).unwrap(),
+            vec![
+                XDataValue::String("ACAD".to_string()),
+                XDataValue::Binary(vec![0xAB, 0xCD]),
+            ]
+        );
+    }
+
+    #[test]
+    fn tolerant_mode_keeps_groups_before_a_malformed_one() {
+        let data = [0x46, 0x01, 0x00, 0x63, 0x46, 0x02, 0x00];
+        let err = parse_eed(&data, true).unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Format);
+        assert_eq!(err.offset, Some(3));
+        assert_eq!(parse_eed_tolerant(&data, true), vec![XDataValue::Short(1)]);
+
+        let truncated = [0x46, 0x01, 0x00, 0x28, 0x00, 0x00];
+        assert!(parse_eed(&truncated, true).is_err());
+        assert_eq!(
+            parse_eed_tolerant(&truncated, true),
+            vec![XDataValue::Short(1)]
+        );
+    }
+}
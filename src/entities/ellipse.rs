@@ -4,10 +4,14 @@ use crate::core::result::Result;
 use crate::entities::common::{
     parse_common_entity_handles, parse_common_entity_header, parse_common_entity_header_r14,
     parse_common_entity_header_r2007, parse_common_entity_header_r2010,
-    parse_common_entity_header_r2013, parse_common_entity_layer_handle, CommonEntityHeader,
+    parse_common_entity_header_r2013, parse_common_entity_layer_handle, seek_handle_stream,
+    CommonEntityHeader,
 };
 use std::sync::atomic::{AtomicU32, Ordering};
 
+type Point3 = (f64, f64, f64);
+type EllipseBody = (Point3, Point3, Point3, f64, f64, f64);
+
 static R14_ELLIPSE_PREFERRED_DELTA: AtomicU32 = AtomicU32::new(64);
 
 #[derive(Debug, Clone)]
@@ -15,6 +19,9 @@ pub struct EllipseEntity {
     pub handle: u64,
     pub color_index: Option<u16>,
     pub true_color: Option<u32>,
+    pub transparency: Option<u32>,
+    pub ltype_scale: f64,
+    pub lineweight: Option<i8>,
     pub layer_handle: u64,
     pub center: (f64, f64, f64),
     pub major_axis: (f64, f64, f64),
@@ -70,7 +77,7 @@ pub fn decode_ellipse_r14(reader: &mut BitReader<'_>, object_handle: u64) -> Res
 
 pub fn decode_ellipse_r2007(reader: &mut BitReader<'_>) -> Result<EllipseEntity> {
     let header = parse_common_entity_header_r2007(reader)?;
-    decode_ellipse_with_header(reader, header, true, true)
+    decode_ellipse_with_header(reader, header, true, false)
 }
 
 pub fn decode_ellipse_r2010(
@@ -116,6 +123,9 @@ fn decode_ellipse_with_header(
         handle: header.handle,
         color_index: header.color.index,
         true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
         layer_handle,
         center,
         major_axis,
@@ -133,7 +143,7 @@ fn decode_ellipse_layer_handle(
     r2007_layer_only: bool,
 ) -> Result<u64> {
     // Handles are stored in the handle stream at obj_size bit offset.
-    reader.set_bit_pos(header.obj_size);
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
     let layer_handle = match if r2007_layer_only {
         parse_common_entity_layer_handle(reader, header)
     } else {
@@ -163,7 +173,7 @@ fn decode_ellipse_r14_fallback(
     if header.handle == 0 {
         header.handle = object_handle;
     }
-    let target_end = header.obj_size as u64;
+    let target_end = header.handle_stream_start_bit() as u64;
     if target_end <= base_bit {
         return Err(DwgError::new(
             ErrorKind::Format,
@@ -209,16 +219,7 @@ fn decode_ellipse_r14_fallback(
     ))
 }
 
-fn parse_ellipse_body_no_common(
-    reader: &mut BitReader<'_>,
-) -> Result<(
-    (f64, f64, f64),
-    (f64, f64, f64),
-    (f64, f64, f64),
-    f64,
-    f64,
-    f64,
-)> {
+fn parse_ellipse_body_no_common(reader: &mut BitReader<'_>) -> Result<EllipseBody> {
     let center = reader.read_3bd()?;
     let major_axis = reader.read_3bd()?;
     let extrusion = reader.read_3bd()?;
@@ -259,7 +260,7 @@ fn consider_ellipse_r14_delta(
     if probe.tell_bits() != target_end {
         return None;
     }
-    let Some(score) = score_ellipse_candidate(
+    let score = score_ellipse_candidate(
         delta,
         center,
         major_axis,
@@ -267,9 +268,7 @@ fn consider_ellipse_r14_delta(
         axis_ratio,
         start_angle,
         end_angle,
-    ) else {
-        return None;
-    };
+    )?;
     if !is_high_confidence_ellipse_candidate(
         center,
         major_axis,
@@ -283,14 +282,15 @@ fn consider_ellipse_r14_delta(
     }
 
     let mut layer_reader = reader.clone();
-    let layer_handle = match decode_ellipse_layer_handle(&mut layer_reader, header, true, false) {
-        Ok(layer) => layer,
-        Err(_) => 0,
-    };
+    let layer_handle =
+        decode_ellipse_layer_handle(&mut layer_reader, header, true, false).unwrap_or_default();
     Some(EllipseEntity {
         handle: header.handle,
         color_index: header.color.index,
         true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
         layer_handle,
         center,
         major_axis,
@@ -339,14 +339,15 @@ fn update_best_ellipse_candidate(
     };
 
     let mut layer_reader = reader.clone();
-    let layer_handle = match decode_ellipse_layer_handle(&mut layer_reader, header, true, false) {
-        Ok(layer) => layer,
-        Err(_) => 0,
-    };
+    let layer_handle =
+        decode_ellipse_layer_handle(&mut layer_reader, header, true, false).unwrap_or_default();
     let candidate = EllipseEntity {
         handle: header.handle,
         color_index: header.color.index,
         true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
         layer_handle,
         center,
         major_axis,
@@ -398,7 +399,7 @@ fn score_ellipse_candidate(
     }
     let extrusion_norm =
         (extrusion.0 * extrusion.0 + extrusion.1 * extrusion.1 + extrusion.2 * extrusion.2).sqrt();
-    if !extrusion_norm.is_finite() || extrusion_norm < 1.0e-9 || extrusion_norm > 1.0e3 {
+    if !extrusion_norm.is_finite() || !(1.0e-9..=1.0e3).contains(&extrusion_norm) {
         return None;
     }
     if axis_ratio <= 0.0 {
@@ -0,0 +1,346 @@
+use std::f64::consts::TAU;
+
+use crate::entities::dim_linear::format_measurement;
+use crate::entities::{
+    ArcEntity, AttribEntity, CircleEntity, DimAng2LnEntity, DimAng3PtEntity, DimLinearEntity,
+    DimensionCommonData, EllipseEntity, Face3dEntity, HatchEntity, ImageEntity, InsertEntity,
+    LeaderEntity, LineEntity, LwPolylineEntity, MInsertEntity, MLeaderEntity, MLineEntity,
+    MTextEntity, PointEntity, Polyline2dEntity, Polyline3dEntity, PolylineMeshEntity,
+    PolylinePFaceEntity, ProxyEntity, SeqendEntity, ShapeEntity, SolidEntity, SplineEntity,
+    TableEntity, TextEntity, ToleranceEntity, TraceEntity, Vertex2dEntity, Vertex3dEntity,
+    VertexPFaceFaceEntity,
+};
+
+/// How a decoder that tries more than one layout arrived at its result.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DecodeMethod {
+    /// The preferred layout read cleanly.
+    #[default]
+    Exact,
+    /// An alternative layout was needed, or the chosen one holds values
+    /// that are unusual but not impossible.
+    Fallback,
+    /// No layout read cleanly: the fields come from the least implausible
+    /// attempt, or were left at their defaults.
+    Guessed,
+}
+
+impl DecodeMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Exact => "exact",
+            Self::Fallback => "fallback",
+            Self::Guessed => "guessed",
+        }
+    }
+}
+
+/// Coarse shape of an entity's geometry, for consumers that treat every
+/// curve or every text alike.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeometryKind {
+    Point,
+    OpenCurve,
+    /// A curve that ends where it starts, or a boundary such as a HATCH.
+    ClosedCurve,
+    /// Filled or surface geometry: SOLID, TRACE, 3DFACE, meshes, WIPEOUT.
+    Region,
+    Text,
+    /// Block references, dimensions, tables and anything else made of
+    /// other geometry, plus the vertices and SEQENDs of polylines.
+    Other,
+}
+
+impl GeometryKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Point => "point",
+            Self::OpenCurve => "open_curve",
+            Self::ClosedCurve => "closed_curve",
+            Self::Region => "region",
+            Self::Text => "text",
+            Self::Other => "other",
+        }
+    }
+}
+
+/// A decoded graphical entity, tagged by its DWG object type.
+#[derive(Debug, Clone)]
+pub enum Entity {
+    Text(TextEntity),
+    Attrib(AttribEntity),
+    AttDef(AttribEntity),
+    Seqend(SeqendEntity),
+    Insert(InsertEntity),
+    MInsert(MInsertEntity),
+    Vertex2d(Vertex2dEntity),
+    Vertex3d(Vertex3dEntity),
+    VertexMesh(Vertex3dEntity),
+    VertexPFace(Vertex3dEntity),
+    VertexPFaceFace(VertexPFaceFaceEntity),
+    Polyline2d(Polyline2dEntity),
+    Polyline3d(Polyline3dEntity),
+    Arc(ArcEntity),
+    Circle(CircleEntity),
+    Line(LineEntity),
+    DimOrdinate(DimLinearEntity),
+    DimLinear(DimLinearEntity),
+    DimAligned(DimLinearEntity),
+    DimAng3Pt(DimAng3PtEntity),
+    DimAng2Ln(DimAng2LnEntity),
+    DimRadius(DimLinearEntity),
+    DimDiameter(DimLinearEntity),
+    Point(PointEntity),
+    Face3d(Face3dEntity),
+    PolylinePFace(PolylinePFaceEntity),
+    PolylineMesh(PolylineMeshEntity),
+    Solid(SolidEntity),
+    Trace(TraceEntity),
+    Shape(ShapeEntity),
+    Ellipse(EllipseEntity),
+    Spline(SplineEntity),
+    MText(MTextEntity),
+    Leader(LeaderEntity),
+    Tolerance(ToleranceEntity),
+    MLine(MLineEntity),
+    LwPolyline(LwPolylineEntity),
+    Hatch(HatchEntity),
+    Proxy(ProxyEntity),
+    Image(ImageEntity),
+    MLeader(MLeaderEntity),
+    /// WIPEOUT stores the IMAGE layout; its clip boundary is the mask.
+    Wipeout(ImageEntity),
+    Table(TableEntity),
+}
+
+impl Entity {
+    pub fn handle(&self) -> u64 {
+        match self {
+            Self::Text(entity) => entity.handle,
+            Self::Attrib(entity) | Self::AttDef(entity) => entity.handle,
+            Self::Seqend(entity) => entity.handle,
+            Self::Insert(entity) => entity.handle,
+            Self::MInsert(entity) => entity.handle,
+            Self::Vertex2d(entity) => entity.handle,
+            Self::Vertex3d(entity) | Self::VertexMesh(entity) | Self::VertexPFace(entity) => {
+                entity.handle
+            }
+            Self::VertexPFaceFace(entity) => entity.handle,
+            Self::Polyline2d(entity) => entity.handle,
+            Self::Polyline3d(entity) => entity.handle,
+            Self::Arc(entity) => entity.handle,
+            Self::Circle(entity) => entity.handle,
+            Self::Line(entity) => entity.handle,
+            Self::DimOrdinate(entity)
+            | Self::DimLinear(entity)
+            | Self::DimAligned(entity)
+            | Self::DimRadius(entity)
+            | Self::DimDiameter(entity) => entity.common.handle,
+            Self::DimAng3Pt(entity) => entity.common.handle,
+            Self::DimAng2Ln(entity) => entity.common.handle,
+            Self::Point(entity) => entity.handle,
+            Self::Face3d(entity) => entity.handle,
+            Self::PolylinePFace(entity) => entity.handle,
+            Self::PolylineMesh(entity) => entity.handle,
+            Self::Solid(entity) => entity.handle,
+            Self::Trace(entity) => entity.handle,
+            Self::Shape(entity) => entity.handle,
+            Self::Ellipse(entity) => entity.handle,
+            Self::Spline(entity) => entity.handle,
+            Self::MText(entity) => entity.handle,
+            Self::Leader(entity) => entity.handle,
+            Self::Tolerance(entity) => entity.handle,
+            Self::MLine(entity) => entity.handle,
+            Self::LwPolyline(entity) => entity.handle,
+            Self::Hatch(entity) => entity.handle,
+            Self::Proxy(entity) => entity.handle,
+            Self::Image(entity) => entity.handle,
+            Self::MLeader(entity) => entity.handle,
+            Self::Wipeout(entity) => entity.handle,
+            Self::Table(entity) => entity.handle,
+        }
+    }
+
+    /// Fields shared by every dimension type; `None` for other entities.
+    pub fn dimension_common(&self) -> Option<&DimensionCommonData> {
+        match self {
+            Self::DimOrdinate(entity)
+            | Self::DimLinear(entity)
+            | Self::DimAligned(entity)
+            | Self::DimRadius(entity)
+            | Self::DimDiameter(entity) => Some(&entity.common),
+            Self::DimAng3Pt(entity) => Some(&entity.common),
+            Self::DimAng2Ln(entity) => Some(&entity.common),
+            _ => None,
+        }
+    }
+
+    /// What a dimension displays, as
+    /// [`DimensionCommonData::formatted_text`], with angular measurements
+    /// in degrees followed by `°`. `None` for other entities.
+    pub fn dimension_text(&self, decimal_places: usize) -> Option<String> {
+        let degrees = |radians: f64| {
+            format!(
+                "{}°",
+                format_measurement(radians.to_degrees(), decimal_places)
+            )
+        };
+        match self {
+            Self::DimAng3Pt(entity) => entity.common.text_with(degrees),
+            Self::DimAng2Ln(entity) => entity.common.text_with(degrees),
+            _ => self.dimension_common()?.formatted_text(decimal_places),
+        }
+    }
+
+    /// [`DecodeMethod::Exact`] for every type decoded with a single layout;
+    /// dimensions, ATTRIB/ATTDEF and MTEXT report how their layout was
+    /// chosen.
+    pub fn decode_method(&self) -> DecodeMethod {
+        match self {
+            Self::Attrib(entity) | Self::AttDef(entity) => entity.decode_method,
+            Self::MText(entity) => entity.decode_method,
+            _ => self
+                .dimension_common()
+                .map_or(DecodeMethod::Exact, |common| common.decode_method),
+        }
+    }
+
+    /// Closed curves are told apart by their closed flag; an ELLIPSE is
+    /// closed when it sweeps a full turn.
+    pub fn geometry_kind(&self) -> GeometryKind {
+        let curve = |closed: bool| {
+            if closed {
+                GeometryKind::ClosedCurve
+            } else {
+                GeometryKind::OpenCurve
+            }
+        };
+        match self {
+            Self::Point(_) => GeometryKind::Point,
+            Self::Line(_) | Self::Arc(_) | Self::Leader(_) => GeometryKind::OpenCurve,
+            Self::Circle(_) | Self::Hatch(_) => GeometryKind::ClosedCurve,
+            Self::LwPolyline(entity) => curve(entity.flags & 0x200 != 0),
+            Self::Polyline2d(entity) => curve(entity.flags_info.closed),
+            Self::Polyline3d(entity) => curve(entity.flags_70_bits & 0x01 != 0),
+            Self::Spline(entity) => curve(entity.closed),
+            // MLINE flags: 0x02 closed.
+            Self::MLine(entity) => curve(entity.open_closed & 0x02 != 0),
+            Self::Ellipse(entity) => {
+                let sweep = (entity.end_angle - entity.start_angle).rem_euclid(TAU);
+                curve(sweep < 1e-9 || TAU - sweep < 1e-9)
+            }
+            Self::Solid(_)
+            | Self::Trace(_)
+            | Self::Face3d(_)
+            | Self::PolylinePFace(_)
+            | Self::PolylineMesh(_)
+            | Self::Wipeout(_) => GeometryKind::Region,
+            Self::Text(_)
+            | Self::Attrib(_)
+            | Self::AttDef(_)
+            | Self::MText(_)
+            | Self::Tolerance(_) => GeometryKind::Text,
+            Self::Seqend(_)
+            | Self::Insert(_)
+            | Self::MInsert(_)
+            | Self::Vertex2d(_)
+            | Self::Vertex3d(_)
+            | Self::VertexMesh(_)
+            | Self::VertexPFace(_)
+            | Self::VertexPFaceFace(_)
+            | Self::DimOrdinate(_)
+            | Self::DimLinear(_)
+            | Self::DimAligned(_)
+            | Self::DimAng3Pt(_)
+            | Self::DimAng2Ln(_)
+            | Self::DimRadius(_)
+            | Self::DimDiameter(_)
+            | Self::Shape(_)
+            | Self::Proxy(_)
+            | Self::Image(_)
+            | Self::MLeader(_)
+            | Self::Table(_) => GeometryKind::Other,
+        }
+    }
+
+    /// DWG type name, matching `object_type_name` for built-in types.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Text(_) => "TEXT",
+            Self::Attrib(_) => "ATTRIB",
+            Self::AttDef(_) => "ATTDEF",
+            Self::Seqend(_) => "SEQEND",
+            Self::Insert(_) => "INSERT",
+            Self::MInsert(_) => "MINSERT",
+            Self::Vertex2d(_) => "VERTEX_2D",
+            Self::Vertex3d(_) => "VERTEX_3D",
+            Self::VertexMesh(_) => "VERTEX_MESH",
+            Self::VertexPFace(_) => "VERTEX_PFACE",
+            Self::VertexPFaceFace(_) => "VERTEX_PFACE_FACE",
+            Self::Polyline2d(_) => "POLYLINE_2D",
+            Self::Polyline3d(_) => "POLYLINE_3D",
+            Self::Arc(_) => "ARC",
+            Self::Circle(_) => "CIRCLE",
+            Self::Line(_) => "LINE",
+            Self::DimOrdinate(_) => "DIM_ORDINATE",
+            Self::DimLinear(_) => "DIM_LINEAR",
+            Self::DimAligned(_) => "DIM_ALIGNED",
+            Self::DimAng3Pt(_) => "DIM_ANG3PT",
+            Self::DimAng2Ln(_) => "DIM_ANG2LN",
+            Self::DimRadius(_) => "DIM_RADIUS",
+            Self::DimDiameter(_) => "DIM_DIAMETER",
+            Self::Point(_) => "POINT",
+            Self::Face3d(_) => "3DFACE",
+            Self::PolylinePFace(_) => "POLYLINE_PFACE",
+            Self::PolylineMesh(_) => "POLYLINE_MESH",
+            Self::Solid(_) => "SOLID",
+            Self::Trace(_) => "TRACE",
+            Self::Shape(_) => "SHAPE",
+            Self::Ellipse(_) => "ELLIPSE",
+            Self::Spline(_) => "SPLINE",
+            Self::MText(_) => "MTEXT",
+            Self::Leader(_) => "LEADER",
+            Self::Tolerance(_) => "TOLERANCE",
+            Self::MLine(_) => "MLINE",
+            Self::LwPolyline(_) => "LWPOLYLINE",
+            Self::Hatch(_) => "HATCH",
+            Self::Proxy(_) => "ACAD_PROXY_ENTITY",
+            Self::Image(_) => "IMAGE",
+            Self::MLeader(_) => "MULTILEADER",
+            Self::Wipeout(_) => "WIPEOUT",
+            Self::Table(_) => "ACAD_TABLE",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::ParseConfig;
+    use crate::dwg::decoder::Decoder;
+
+    #[test]
+    fn classifies_sample_geometry() {
+        for (name, type_name, kind) in [
+            ("line_2004.dwg", "LINE", GeometryKind::OpenCurve),
+            ("arc_2004.dwg", "ARC", GeometryKind::OpenCurve),
+            ("circle_2004.dwg", "CIRCLE", GeometryKind::ClosedCurve),
+            ("ellipse_2004.dwg", "ELLIPSE", GeometryKind::ClosedCurve),
+            ("point2d_2004.dwg", "POINT", GeometryKind::Point),
+            ("text_2004.dwg", "TEXT", GeometryKind::Text),
+            ("insert_2004.dwg", "INSERT", GeometryKind::Other),
+        ] {
+            let bytes = std::fs::read(format!("test_dwg/{name}")).expect("sample file");
+            let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+            let kinds: Vec<_> = decoder
+                .entities_filtered(|entity| entity == type_name)
+                .map(|item| item.unwrap().1.geometry_kind())
+                .collect();
+            assert!(!kinds.is_empty(), "{name}");
+            assert!(
+                kinds.iter().all(|found| *found == kind),
+                "{name}: {kinds:?}"
+            );
+        }
+    }
+}
@@ -4,7 +4,7 @@ use crate::core::result::Result;
 use crate::entities::common::{
     parse_common_entity_handles, parse_common_entity_header, parse_common_entity_header_r2007,
     parse_common_entity_header_r2010, parse_common_entity_header_r2013,
-    parse_common_entity_layer_handle, CommonEntityHeader,
+    parse_common_entity_layer_handle, seek_handle_stream, CommonEntityHeader,
 };
 
 #[derive(Debug, Clone)]
@@ -12,6 +12,9 @@ pub struct Face3dEntity {
     pub handle: u64,
     pub color_index: Option<u16>,
     pub true_color: Option<u32>,
+    pub transparency: Option<u32>,
+    pub ltype_scale: f64,
+    pub lineweight: Option<i8>,
     pub layer_handle: u64,
     pub p1: (f64, f64, f64),
     pub p2: (f64, f64, f64),
@@ -27,7 +30,7 @@ pub fn decode_3dface(reader: &mut BitReader<'_>) -> Result<Face3dEntity> {
 
 pub fn decode_3dface_r2007(reader: &mut BitReader<'_>) -> Result<Face3dEntity> {
     let header = parse_common_entity_header_r2007(reader)?;
-    decode_3dface_with_header(reader, header, true, true)
+    decode_3dface_with_header(reader, header, true, false)
 }
 
 pub fn decode_3dface_r2010(
@@ -79,7 +82,7 @@ fn decode_3dface_with_header(
     };
 
     // Handles are stored in the handle stream at obj_size bit offset.
-    reader.set_bit_pos(header.obj_size);
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
     let layer_handle = match if r2007_layer_only {
         parse_common_entity_layer_handle(reader, &header)
     } else {
@@ -102,6 +105,9 @@ fn decode_3dface_with_header(
         handle: header.handle,
         color_index: header.color.index,
         true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
         layer_handle,
         p1,
         p2,
@@ -1,16 +1,145 @@
 use crate::bit::{BitReader, Endian};
 use crate::core::error::{DwgError, ErrorKind};
+use crate::core::geometry::{
+    append_segment_points, circular_arc_points, elliptical_arc_points, points_equal_2d,
+    polyline_with_bulges_points, ArcTessellation, DEFAULT_POINT_EPSILON,
+};
 use crate::core::result::Result;
 use crate::entities::common::{
     parse_common_entity_handles, parse_common_entity_header, parse_common_entity_header_r2007,
     parse_common_entity_header_r2010, parse_common_entity_header_r2013,
-    parse_common_entity_layer_handle, CommonEntityHeader,
+    parse_common_entity_layer_handle, seek_handle_stream, CommonEntityHeader,
 };
 
 #[derive(Debug, Clone)]
 pub struct HatchPath {
     pub closed: bool,
+    /// `boundary` flattened at [`ParseConfig::arc_tessellation`](crate::core::config::ParseConfig::arc_tessellation)
+    /// and closed within [`ParseConfig::point_epsilon`](crate::core::config::ParseConfig::point_epsilon).
     pub points: Vec<(f64, f64)>,
+    pub boundary: HatchBoundary,
+}
+
+/// Exact geometry of a hatch boundary path.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HatchBoundary {
+    Edges(Vec<HatchEdge>),
+    /// Vertices with one bulge each, or no bulges when every segment is
+    /// straight.
+    Polyline {
+        vertices: Vec<(f64, f64)>,
+        bulges: Vec<f64>,
+    },
+}
+
+/// One edge of an edge-defined boundary path, in OCS coordinates.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HatchEdge {
+    Line {
+        start: (f64, f64),
+        end: (f64, f64),
+    },
+    CircularArc {
+        center: (f64, f64),
+        radius: f64,
+        start_angle: f64,
+        end_angle: f64,
+        is_ccw: bool,
+    },
+    EllipticalArc {
+        center: (f64, f64),
+        /// Major axis endpoint relative to `center`.
+        major_endpoint: (f64, f64),
+        ratio: f64,
+        start_angle: f64,
+        end_angle: f64,
+        is_ccw: bool,
+    },
+}
+
+impl HatchPath {
+    fn new(closed: bool, boundary: HatchBoundary) -> Self {
+        let mut path = Self {
+            closed,
+            points: Vec::new(),
+            boundary,
+        };
+        path.points = path.flatten(ArcTessellation::default(), DEFAULT_POINT_EPSILON);
+        path
+    }
+
+    /// Boundary points with arcs split as `tessellation` says, repeating the
+    /// first point at the end of closed paths unless the last one is within
+    /// `point_epsilon` of it. An edge starting within `point_epsilon` of the
+    /// previous edge's end continues from that point.
+    pub fn flatten(&self, tessellation: ArcTessellation, point_epsilon: f64) -> Vec<(f64, f64)> {
+        let mut points = match &self.boundary {
+            HatchBoundary::Edges(edges) => {
+                let mut points = Vec::new();
+                for edge in edges {
+                    let segment = match *edge {
+                        HatchEdge::Line { start, end } => vec![start, end],
+                        HatchEdge::CircularArc {
+                            center,
+                            radius,
+                            start_angle,
+                            end_angle,
+                            is_ccw,
+                        } => circular_arc_points(
+                            center,
+                            radius,
+                            start_angle,
+                            end_angle,
+                            is_ccw,
+                            tessellation,
+                        ),
+                        HatchEdge::EllipticalArc {
+                            center,
+                            major_endpoint,
+                            ratio,
+                            start_angle,
+                            end_angle,
+                            is_ccw,
+                        } => elliptical_arc_points(
+                            center,
+                            major_endpoint,
+                            ratio,
+                            start_angle,
+                            end_angle,
+                            is_ccw,
+                            tessellation,
+                        ),
+                    };
+                    append_segment_points(&mut points, &segment, point_epsilon);
+                }
+                points
+            }
+            HatchBoundary::Polyline { vertices, bulges } if !bulges.is_empty() => {
+                polyline_with_bulges_points(
+                    vertices,
+                    bulges,
+                    self.closed,
+                    tessellation,
+                    point_epsilon,
+                )
+            }
+            HatchBoundary::Polyline { vertices, .. } => vertices.clone(),
+        };
+        if self.closed {
+            close_path_if_needed(&mut points, point_epsilon);
+        }
+        points
+    }
+}
+
+impl HatchEntity {
+    /// Re-flatten every path's `points` with `tessellation`, see
+    /// [`HatchPath::flatten`].
+    pub fn reflatten(&mut self, tessellation: ArcTessellation, point_epsilon: f64) {
+        for path in &mut self.paths {
+            path.points = path.flatten(tessellation, point_epsilon);
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -18,6 +147,9 @@ pub struct HatchEntity {
     pub handle: u64,
     pub color_index: Option<u16>,
     pub true_color: Option<u32>,
+    pub transparency: Option<u32>,
+    pub ltype_scale: f64,
+    pub lineweight: Option<i8>,
     pub layer_handle: u64,
     pub name: String,
     pub solid_fill: bool,
@@ -39,7 +171,7 @@ pub fn decode_hatch_r2004(reader: &mut BitReader<'_>) -> Result<HatchEntity> {
 
 pub fn decode_hatch_r2007(reader: &mut BitReader<'_>) -> Result<HatchEntity> {
     let header = parse_common_entity_header_r2007(reader)?;
-    decode_hatch_with_header(reader, header, true, true, true)
+    decode_hatch_with_header(reader, header, true, false, true)
 }
 
 pub fn decode_hatch_r2010(
@@ -88,44 +220,33 @@ fn decode_hatch_with_header(
         any_path_uses_pixel_size |= (path_flag & 0x04) != 0;
 
         if (path_flag & 0x02) == 0 {
-            let num_segments = bounded_count(reader.read_bl()?, "hatch edge path segments")?;
-            let mut path_points: Vec<(f64, f64)> = Vec::new();
+            // A segment is at least its type byte and one RD point.
+            let raw_count = reader.read_bl()?;
+            let num_segments =
+                fitting_count(reader, raw_count, 8 + 128, "hatch edge path segments")?;
+            let mut edges = Vec::with_capacity(num_segments);
             for _ in 0..num_segments {
                 let segment_type = reader.read_rc()?;
-                match segment_type {
-                    1 => {
-                        let start = read_point2rd(reader)?;
-                        let end = read_point2rd(reader)?;
-                        append_segment_points(&mut path_points, &[start, end]);
-                    }
-                    2 => {
-                        let center = read_point2rd(reader)?;
-                        let radius = reader.read_bd()?;
-                        let start_angle = reader.read_bd()?;
-                        let end_angle = reader.read_bd()?;
-                        let is_ccw = reader.read_b()? != 0;
-                        let segment =
-                            circular_arc_points(center, radius, start_angle, end_angle, is_ccw, 64);
-                        append_segment_points(&mut path_points, &segment);
-                    }
-                    3 => {
-                        let center = read_point2rd(reader)?;
-                        let major_endpoint = read_point2rd(reader)?;
-                        let ratio = reader.read_bd()?;
-                        let start_angle = reader.read_bd()?;
-                        let end_angle = reader.read_bd()?;
-                        let is_ccw = reader.read_b()? != 0;
-                        let segment = elliptical_arc_points(
-                            center,
-                            major_endpoint,
-                            ratio,
-                            start_angle,
-                            end_angle,
-                            is_ccw,
-                            96,
-                        );
-                        append_segment_points(&mut path_points, &segment);
-                    }
+                edges.push(match segment_type {
+                    1 => HatchEdge::Line {
+                        start: read_point2rd(reader)?,
+                        end: read_point2rd(reader)?,
+                    },
+                    2 => HatchEdge::CircularArc {
+                        center: read_point2rd(reader)?,
+                        radius: reader.read_bd()?,
+                        start_angle: reader.read_bd()?,
+                        end_angle: reader.read_bd()?,
+                        is_ccw: reader.read_b()? != 0,
+                    },
+                    3 => HatchEdge::EllipticalArc {
+                        center: read_point2rd(reader)?,
+                        major_endpoint: read_point2rd(reader)?,
+                        ratio: reader.read_bd()?,
+                        start_angle: reader.read_bd()?,
+                        end_angle: reader.read_bd()?,
+                        is_ccw: reader.read_b()? != 0,
+                    },
                     4 => {
                         return Err(DwgError::new(
                             ErrorKind::NotImplemented,
@@ -138,20 +259,17 @@ fn decode_hatch_with_header(
                             format!("unsupported HATCH edge segment type: {segment_type}"),
                         ));
                     }
-                }
+                });
             }
             let _num_boundary_obj_handles = reader.read_bl()?;
-            close_path_if_needed(&mut path_points);
-            paths.push(HatchPath {
-                closed: true,
-                points: path_points,
-            });
+            paths.push(HatchPath::new(true, HatchBoundary::Edges(edges)));
             continue;
         }
 
         let bulges_present = reader.read_b()? != 0;
         let closed = reader.read_b()? != 0;
-        let num_vertices = bounded_count(reader.read_bl()?, "hatch polyline vertices")?;
+        let raw_count = reader.read_bl()?;
+        let num_vertices = fitting_count(reader, raw_count, 128, "hatch polyline vertices")?;
         let mut vertices: Vec<(f64, f64)> = Vec::with_capacity(num_vertices);
         let mut bulges: Vec<f64> = Vec::with_capacity(num_vertices);
         for _ in 0..num_vertices {
@@ -161,16 +279,10 @@ fn decode_hatch_with_header(
             }
         }
         let _num_boundary_obj_handles = reader.read_bl()?;
-
-        let mut points = if bulges_present {
-            polyline_with_bulges_points(&vertices, &bulges, closed, 64)
-        } else {
-            vertices
-        };
-        if closed {
-            close_path_if_needed(&mut points);
-        }
-        paths.push(HatchPath { closed, points });
+        paths.push(HatchPath::new(
+            closed,
+            HatchBoundary::Polyline { vertices, bulges },
+        ));
     }
 
     if let Err(err) = skip_hatch_definition_payload(reader, solid_fill, any_path_uses_pixel_size) {
@@ -183,7 +295,7 @@ fn decode_hatch_with_header(
     }
 
     // Handles are stored in the handle stream at obj_size bit offset.
-    reader.set_bit_pos(header.obj_size);
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
     let layer_handle = match if r2007_layer_only {
         parse_common_entity_layer_handle(reader, &header)
     } else {
@@ -206,6 +318,9 @@ fn decode_hatch_with_header(
         handle: header.handle,
         color_index: header.color.index,
         true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
         layer_handle,
         name,
         solid_fill,
@@ -261,7 +376,8 @@ fn skip_hatch_definition_payload(
 
     let num_seed_points = if any_path_uses_pixel_size {
         let _pixel_size = reader.read_bd()?;
-        bounded_count(reader.read_bl()?, "hatch seed points")?
+        let raw_count = reader.read_bl()?;
+        fitting_count(reader, raw_count, 128, "hatch seed points")?
     } else {
         0usize
     };
@@ -278,196 +394,132 @@ fn read_point2rd(reader: &mut BitReader<'_>) -> Result<(f64, f64)> {
     ))
 }
 
-fn append_segment_points(points: &mut Vec<(f64, f64)>, segment: &[(f64, f64)]) {
-    if segment.is_empty() {
-        return;
-    }
-    if points.is_empty() {
-        points.extend_from_slice(segment);
-        return;
-    }
-    let mut start = 0usize;
-    if points_equal_2d(*points.last().unwrap(), segment[0]) {
-        start = 1;
-    }
-    points.extend_from_slice(&segment[start..]);
-}
-
-fn close_path_if_needed(points: &mut Vec<(f64, f64)>) {
+fn close_path_if_needed(points: &mut Vec<(f64, f64)>, point_epsilon: f64) {
     if points.len() <= 1 {
         return;
     }
     let first = points[0];
     let last = *points.last().unwrap();
-    if !points_equal_2d(first, last) {
+    if !points_equal_2d(first, last, point_epsilon) {
         points.push(first);
     }
 }
 
-fn circular_arc_points(
-    center: (f64, f64),
-    radius: f64,
-    start_angle: f64,
-    end_angle: f64,
-    is_ccw: bool,
-    arc_segments: usize,
-) -> Vec<(f64, f64)> {
-    if radius.abs() <= 1.0e-12 {
-        return vec![];
-    }
-    let sweep = normalized_sweep(start_angle, end_angle, is_ccw);
-    let segs = ((sweep.abs() / std::f64::consts::TAU) * (arc_segments.max(8) as f64)).ceil();
-    let segments = segs.max(2.0) as usize;
-    let mut out = Vec::with_capacity(segments + 1);
-    for i in 0..=segments {
-        let t = (i as f64) / (segments as f64);
-        let angle = start_angle + sweep * t;
-        out.push((
-            center.0 + radius * angle.cos(),
-            center.1 + radius * angle.sin(),
+fn bounded_count(raw: u32, label: &str) -> Result<usize> {
+    let count = raw as usize;
+    if count > 1_000_000 {
+        return Err(DwgError::new(
+            ErrorKind::Format,
+            format!("{} count is too large: {}", label, count),
         ));
     }
-    out
-}
-
-fn elliptical_arc_points(
-    center: (f64, f64),
-    major_endpoint: (f64, f64),
-    ratio: f64,
-    start_angle: f64,
-    end_angle: f64,
-    is_ccw: bool,
-    arc_segments: usize,
-) -> Vec<(f64, f64)> {
-    let mx = major_endpoint.0;
-    let my = major_endpoint.1;
-    if mx.abs() <= 1.0e-12 && my.abs() <= 1.0e-12 {
-        return vec![];
-    }
-    let vx = -my * ratio;
-    let vy = mx * ratio;
-    let sweep = normalized_sweep(start_angle, end_angle, is_ccw);
-    let segs = ((sweep.abs() / std::f64::consts::TAU) * (arc_segments.max(16) as f64)).ceil();
-    let segments = segs.max(4.0) as usize;
-    let mut out = Vec::with_capacity(segments + 1);
-    for i in 0..=segments {
-        let t = (i as f64) / (segments as f64);
-        let angle = start_angle + sweep * t;
-        let c = angle.cos();
-        let s = angle.sin();
-        out.push((center.0 + mx * c + vx * s, center.1 + my * c + vy * s));
-    }
-    out
+    Ok(count)
 }
 
-fn polyline_with_bulges_points(
-    points: &[(f64, f64)],
-    bulges: &[f64],
-    closed: bool,
-    arc_segments: usize,
-) -> Vec<(f64, f64)> {
-    if points.len() <= 1 {
-        return points.to_vec();
-    }
-    let mut bulge_values = vec![0.0f64; points.len()];
-    for (idx, bulge) in bulges.iter().enumerate().take(points.len()) {
-        bulge_values[idx] = *bulge;
-    }
-
-    let seg_count = if closed {
-        points.len()
-    } else {
-        points.len().saturating_sub(1)
-    };
-    let mut out: Vec<(f64, f64)> = Vec::new();
-    for idx in 0..seg_count {
-        let start = points[idx];
-        let end = points[(idx + 1) % points.len()];
-        let bulge = bulge_values[idx];
-        let segment = bulge_segment_points(start, end, bulge, arc_segments);
-        append_segment_points(&mut out, &segment);
+/// [`bounded_count`] for items of at least `min_bits` each, rejecting counts
+/// whose items could not fit in the rest of the record.
+fn fitting_count(reader: &BitReader<'_>, raw: u32, min_bits: u64, label: &str) -> Result<usize> {
+    let count = bounded_count(raw, label)?;
+    if !reader.has_bits(count as u64 * min_bits) {
+        return Err(DwgError::new(
+            ErrorKind::Format,
+            format!(
+                "{label} count {count} does not fit in the remaining {} bits",
+                reader.remaining_bits()
+            ),
+        ));
     }
-    out
+    Ok(count)
 }
 
-fn bulge_segment_points(
-    start: (f64, f64),
-    end: (f64, f64),
-    bulge: f64,
-    arc_segments: usize,
-) -> Vec<(f64, f64)> {
-    if bulge.abs() <= 1.0e-12 {
-        return vec![start, end];
-    }
-
-    let dx = end.0 - start.0;
-    let dy = end.1 - start.1;
-    let chord = (dx * dx + dy * dy).sqrt();
-    if chord <= 1.0e-12 {
-        return vec![start, end];
-    }
-
-    let theta = 4.0 * bulge.atan();
-    if theta.abs() <= 1.0e-12 {
-        return vec![start, end];
-    }
-
-    let normal = (-dy / chord, dx / chord);
-    let center_offset = chord * (1.0 - bulge * bulge) / (4.0 * bulge);
-    let mid = ((start.0 + end.0) * 0.5, (start.1 + end.1) * 0.5);
-    let center = (
-        mid.0 + normal.0 * center_offset,
-        mid.1 + normal.1 * center_offset,
-    );
-    let radius = ((start.0 - center.0).powi(2) + (start.1 - center.1).powi(2)).sqrt();
-    if radius <= 1.0e-12 {
-        return vec![start, end];
-    }
-
-    let start_angle = (start.1 - center.1).atan2(start.0 - center.0);
-    let segs = ((theta.abs() / std::f64::consts::TAU) * (arc_segments.max(8) as f64)).ceil();
-    let segments = segs.max(2.0) as usize;
-    let mut out = Vec::with_capacity(segments + 1);
-    for i in 0..=segments {
-        let t = (i as f64) / (segments as f64);
-        let angle = start_angle + theta * t;
-        out.push((
-            center.0 + radius * angle.cos(),
-            center.1 + radius * angle.sin(),
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_reflatten_at_the_requested_arc_segments() {
+        // A half-disc: the diameter, then a counter-clockwise semicircle back.
+        let edges = vec![
+            HatchEdge::Line {
+                start: (-1.0, 0.0),
+                end: (1.0, 0.0),
+            },
+            HatchEdge::CircularArc {
+                center: (0.0, 0.0),
+                radius: 1.0,
+                start_angle: 0.0,
+                end_angle: std::f64::consts::PI,
+                is_ccw: true,
+            },
+        ];
+        let mut hatch = HatchEntity {
+            handle: 1,
+            color_index: None,
+            true_color: None,
+            transparency: None,
+            ltype_scale: 1.0,
+            lineweight: None,
+            layer_handle: 0,
+            name: "SOLID".to_string(),
+            solid_fill: true,
+            associative: false,
+            elevation: 0.0,
+            extrusion: (0.0, 0.0, 1.0),
+            paths: vec![HatchPath::new(true, HatchBoundary::Edges(edges))],
+        };
+        // Two diameter ends plus 32 chords of the default 64 per turn; the
+        // arc ends where the path starts.
+        assert_eq!(hatch.paths[0].points.len(), 34);
+        hatch.reflatten(ArcTessellation::Segments(16), DEFAULT_POINT_EPSILON);
+        let points = &hatch.paths[0].points;
+        assert_eq!(points.len(), 10);
+        assert!(points_equal_2d(
+            points[0],
+            points[points.len() - 1],
+            DEFAULT_POINT_EPSILON
         ));
+        assert!(points
+            .iter()
+            .all(|(x, y)| *y >= -1e-12 && x.hypot(*y) <= 1.0 + 1e-12));
     }
-    if let Some(first) = out.first_mut() {
-        *first = start;
-    }
-    if let Some(last) = out.last_mut() {
-        *last = end;
-    }
-    out
-}
 
-fn normalized_sweep(start_angle: f64, end_angle: f64, is_ccw: bool) -> f64 {
-    let mut sweep = end_angle - start_angle;
-    if is_ccw {
-        if sweep < 0.0 {
-            sweep += std::f64::consts::TAU;
-        }
-    } else if sweep > 0.0 {
-        sweep -= std::f64::consts::TAU;
+    #[test]
+    fn point_epsilon_decides_whether_a_path_is_closed_again() {
+        let path = HatchPath::new(
+            true,
+            HatchBoundary::Polyline {
+                vertices: vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (1e-4, 0.0)],
+                bulges: Vec::new(),
+            },
+        );
+        assert_eq!(path.points.len(), 5);
+        assert_eq!(path.points[4], (0.0, 0.0));
+        assert_eq!(path.flatten(ArcTessellation::default(), 1e-3).len(), 4);
+
+        // The same tolerance decides whether adjacent edges share a point.
+        let path = HatchPath::new(
+            false,
+            HatchBoundary::Edges(vec![
+                HatchEdge::Line {
+                    start: (0.0, 0.0),
+                    end: (1.0, 0.0),
+                },
+                HatchEdge::Line {
+                    start: (1.0, 1e-4),
+                    end: (1.0, 1.0),
+                },
+            ]),
+        );
+        assert_eq!(path.points.len(), 4);
+        assert_eq!(path.flatten(ArcTessellation::default(), 1e-3).len(), 3);
     }
-    sweep
-}
 
-fn points_equal_2d(a: (f64, f64), b: (f64, f64)) -> bool {
-    (a.0 - b.0).abs() <= 1.0e-9 && (a.1 - b.1).abs() <= 1.0e-9
-}
-
-fn bounded_count(raw: u32, label: &str) -> Result<usize> {
-    let count = raw as usize;
-    if count > 1_000_000 {
-        return Err(DwgError::new(
-            ErrorKind::Format,
-            format!("{} count is too large: {}", label, count),
-        ));
+    #[test]
+    fn counts_that_cannot_fit_the_record_are_rejected() {
+        let data = [0u8; 32];
+        let reader = BitReader::new(&data);
+        assert_eq!(fitting_count(&reader, 2, 128, "points").unwrap(), 2);
+        let err = fitting_count(&reader, 3, 128, "points").unwrap_err();
+        assert_eq!(err.kind, ErrorKind::Format);
     }
-    Ok(count)
 }
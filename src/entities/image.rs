@@ -0,0 +1,439 @@
+use crate::bit::{BitReader, Endian};
+use crate::core::error::{DwgError, ErrorKind};
+use crate::core::result::Result;
+use crate::entities::common::{
+    parse_common_entity_handles, parse_common_entity_header, parse_common_entity_header_r2007,
+    parse_common_entity_header_r2010, parse_common_entity_header_r2013,
+    parse_common_entity_layer_handle, parse_common_object_header, parse_common_object_header_r2010,
+    parse_common_object_header_r2013, read_handle_reference, seek_handle_stream,
+    CommonEntityHeader, CommonObjectHeader,
+};
+
+type Point3 = (f64, f64, f64);
+
+const MAX_CLIP_VERTICES: usize = 100_000;
+
+/// IMAGE: a raster underlay placed in the drawing. The pixel data lives in
+/// the external file named by the referenced IMAGEDEF.
+#[derive(Debug, Clone)]
+pub struct ImageEntity {
+    pub handle: u64,
+    pub color_index: Option<u16>,
+    pub true_color: Option<u32>,
+    pub transparency: Option<u32>,
+    pub ltype_scale: f64,
+    pub lineweight: Option<i8>,
+    pub layer_handle: u64,
+    pub class_version: u32,
+    /// WCS position of the lower-left corner of the lower-left pixel.
+    pub insertion: Point3,
+    /// WCS vector along one pixel in the image's U (column) direction.
+    pub u_vector: Point3,
+    /// WCS vector along one pixel in the image's V (row) direction.
+    pub v_vector: Point3,
+    /// Image size in pixels, `(width, height)`.
+    pub size: (f64, f64),
+    /// 1 show image, 2 show when not aligned with the screen, 4 use the clip
+    /// boundary, 8 transparency on.
+    pub display_flags: u16,
+    pub clipping: bool,
+    pub brightness: u8,
+    pub contrast: u8,
+    pub fade: u8,
+    /// R2010+: the clip boundary hides the inside instead of the outside.
+    pub clip_inverted: bool,
+    /// 1 rectangular (two opposite corners), 2 polygonal.
+    pub clip_boundary_type: u16,
+    /// Clip boundary in pixel coordinates.
+    pub clip_vertices: Vec<(f64, f64)>,
+    pub imagedef_handle: Option<u64>,
+    pub imagedef_reactor_handle: Option<u64>,
+}
+
+impl ImageEntity {
+    /// Clip boundary (or the full image frame when not clipping) as a closed
+    /// WCS polygon. Boundary vertices are pixel coordinates measured from the
+    /// top-left pixel centre, so V runs downwards.
+    pub fn boundary_wcs(&self) -> Vec<Point3> {
+        let (width, height) = self.size;
+        let pixels = match self.clip_vertices.as_slice() {
+            [a, b] if self.clipping || self.clip_boundary_type == 1 => {
+                vec![*a, (b.0, a.1), *b, (a.0, b.1)]
+            }
+            vertices if self.clipping && vertices.len() > 2 => vertices.to_vec(),
+            _ => vec![
+                (-0.5, height - 0.5),
+                (width - 0.5, height - 0.5),
+                (width - 0.5, -0.5),
+                (-0.5, -0.5),
+            ],
+        };
+        let (u, v) = (self.u_vector, self.v_vector);
+        let origin = (
+            self.insertion.0 + (u.0 - v.0) * 0.5,
+            self.insertion.1 + (u.1 - v.1) * 0.5,
+            self.insertion.2 + (u.2 - v.2) * 0.5,
+        );
+        let mut polygon: Vec<Point3> = pixels
+            .into_iter()
+            .map(|(x, y)| {
+                let y = height - y;
+                (
+                    origin.0 + u.0 * x + v.0 * y,
+                    origin.1 + u.1 * x + v.1 * y,
+                    origin.2 + u.2 * x + v.2 * y,
+                )
+            })
+            .collect();
+        if polygon.len() > 1 && polygon.first() == polygon.last() {
+            polygon.pop();
+        }
+        polygon
+    }
+}
+
+/// IMAGEDEF: the external raster file an IMAGE refers to.
+#[derive(Debug, Clone)]
+pub struct ImageDefObject {
+    pub handle: u64,
+    pub class_version: u32,
+    /// Image size in pixels, `(width, height)`.
+    pub image_size: (f64, f64),
+    /// Path as saved in the drawing. `None` for R2007+, where strings are kept
+    /// in the object's string stream.
+    pub file_path: Option<String>,
+    pub is_loaded: bool,
+    /// 0 none, 2 centimeters, 5 inches.
+    pub resolution_units: u8,
+    /// Size of one pixel in drawing units, `(width, height)`.
+    pub pixel_size: (f64, f64),
+}
+
+pub fn decode_image(reader: &mut BitReader<'_>) -> Result<ImageEntity> {
+    let header = parse_common_entity_header(reader)?;
+    decode_image_with_header(reader, header, false, false)
+}
+
+pub fn decode_image_r2007(reader: &mut BitReader<'_>) -> Result<ImageEntity> {
+    let header = parse_common_entity_header_r2007(reader)?;
+    decode_image_with_header(reader, header, true, false)
+}
+
+pub fn decode_image_r2010(
+    reader: &mut BitReader<'_>,
+    object_data_end_bit: u32,
+    object_handle: u64,
+) -> Result<ImageEntity> {
+    let mut header = parse_common_entity_header_r2010(reader, object_data_end_bit)?;
+    header.handle = object_handle;
+    decode_image_with_header(reader, header, true, true)
+}
+
+pub fn decode_image_r2013(
+    reader: &mut BitReader<'_>,
+    object_data_end_bit: u32,
+    object_handle: u64,
+) -> Result<ImageEntity> {
+    let mut header = parse_common_entity_header_r2013(reader, object_data_end_bit)?;
+    header.handle = object_handle;
+    decode_image_with_header(reader, header, true, true)
+}
+
+fn decode_image_with_header(
+    reader: &mut BitReader<'_>,
+    header: CommonEntityHeader,
+    allow_handle_decode_failure: bool,
+    r2010_plus: bool,
+) -> Result<ImageEntity> {
+    let class_version = reader.read_bl()?;
+    let insertion = reader.read_3bd()?;
+    let u_vector = reader.read_3bd()?;
+    let v_vector = reader.read_3bd()?;
+    let size = (
+        reader.read_rd(Endian::Little)?,
+        reader.read_rd(Endian::Little)?,
+    );
+    let display_flags = reader.read_bs()?;
+    let clipping = reader.read_b()? != 0;
+    let brightness = reader.read_rc()?;
+    let contrast = reader.read_rc()?;
+    let fade = reader.read_rc()?;
+    let clip_inverted = if r2010_plus {
+        reader.read_b()? != 0
+    } else {
+        false
+    };
+    let clip_boundary_type = reader.read_bs()?;
+    let clip_vertex_count = if clip_boundary_type == 1 {
+        2
+    } else {
+        reader.read_bl()? as usize
+    };
+    if clip_vertex_count > MAX_CLIP_VERTICES {
+        return Err(DwgError::new(
+            ErrorKind::Format,
+            format!(
+                "invalid IMAGE clip vertex count: {} (max {})",
+                clip_vertex_count, MAX_CLIP_VERTICES
+            ),
+        ));
+    }
+    let mut clip_vertices = Vec::with_capacity(clip_vertex_count);
+    for _ in 0..clip_vertex_count {
+        clip_vertices.push((
+            reader.read_rd(Endian::Little)?,
+            reader.read_rd(Endian::Little)?,
+        ));
+    }
+
+    // Handles are stored in the handle stream at obj_size bit offset.
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
+    let handles_pos = reader.get_pos();
+    let (layer_handle, imagedef_handle, imagedef_reactor_handle) =
+        match parse_common_entity_handles(reader, &header) {
+            Ok(common_handles) => {
+                let imagedef = read_handle_reference(reader, header.handle).ok();
+                let reactor =
+                    imagedef.and_then(|_| read_handle_reference(reader, header.handle).ok());
+                (common_handles.layer, imagedef, reactor)
+            }
+            Err(err)
+                if allow_handle_decode_failure
+                    && matches!(
+                        err.kind,
+                        ErrorKind::Format | ErrorKind::Decode | ErrorKind::Io
+                    ) =>
+            {
+                reader.set_pos(handles_pos.0, handles_pos.1);
+                let layer = parse_common_entity_layer_handle(reader, &header).unwrap_or(0);
+                (layer, None, None)
+            }
+            Err(err) => return Err(err),
+        };
+
+    Ok(ImageEntity {
+        handle: header.handle,
+        color_index: header.color.index,
+        true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
+        layer_handle,
+        class_version,
+        insertion,
+        u_vector,
+        v_vector,
+        size,
+        display_flags,
+        clipping,
+        brightness,
+        contrast,
+        fade,
+        clip_inverted,
+        clip_boundary_type,
+        clip_vertices,
+        imagedef_handle,
+        imagedef_reactor_handle,
+    })
+}
+
+pub fn decode_image_def(reader: &mut BitReader<'_>) -> Result<ImageDefObject> {
+    let header = parse_common_object_header(reader)?;
+    decode_image_def_with_header(reader, header, true)
+}
+
+pub fn decode_image_def_r2007(reader: &mut BitReader<'_>) -> Result<ImageDefObject> {
+    let header = parse_common_object_header(reader)?;
+    decode_image_def_with_header(reader, header, false)
+}
+
+pub fn decode_image_def_r2010(
+    reader: &mut BitReader<'_>,
+    object_data_end_bit: u32,
+    object_handle: u64,
+) -> Result<ImageDefObject> {
+    let mut header = parse_common_object_header_r2010(reader, object_data_end_bit)?;
+    header.handle = object_handle;
+    decode_image_def_with_header(reader, header, false)
+}
+
+pub fn decode_image_def_r2013(
+    reader: &mut BitReader<'_>,
+    object_data_end_bit: u32,
+    object_handle: u64,
+) -> Result<ImageDefObject> {
+    let mut header = parse_common_object_header_r2013(reader, object_data_end_bit)?;
+    header.handle = object_handle;
+    decode_image_def_with_header(reader, header, false)
+}
+
+fn decode_image_def_with_header(
+    reader: &mut BitReader<'_>,
+    header: CommonObjectHeader,
+    inline_strings: bool,
+) -> Result<ImageDefObject> {
+    let class_version = reader.read_bl()?;
+    let image_size = (
+        reader.read_rd(Endian::Little)?,
+        reader.read_rd(Endian::Little)?,
+    );
+    let file_path = if inline_strings {
+        Some(reader.read_tv()?)
+    } else {
+        None
+    };
+    let is_loaded = reader.read_b()? != 0;
+    let resolution_units = reader.read_rc()?;
+    let pixel_size = (
+        reader.read_rd(Endian::Little)?,
+        reader.read_rd(Endian::Little)?,
+    );
+
+    Ok(ImageDefObject {
+        handle: header.handle,
+        class_version,
+        image_size,
+        file_path,
+        is_loaded,
+        resolution_units,
+        pixel_size,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::test_support::Bits;
+
+    #[test]
+    fn decodes_r2000_image_placement_and_clip() {
+        let mut body = Bits::default();
+        body.common_entity_header(0x30, 0b00);
+        body.push(0b10, 2); // class version BL = 0
+        for value in [10.0, 20.0, 0.0, 0.5, 0.0, 0.0, 0.0, 0.5, 0.0] {
+            body.bd(value); // insertion, U and V vectors
+        }
+        body.rd(640.0);
+        body.rd(480.0);
+        body.small(0x07);
+        body.push(1, 1); // clipping on
+        body.rc(50);
+        body.rc(60);
+        body.rc(0);
+        body.small(1); // rectangular clip: two corners, no count
+        for value in [-0.5, -0.5, 639.5, 479.5] {
+            body.rd(value);
+        }
+
+        let mut handles = Bits::default();
+        handles.handle(4, 0x1F); // owner: model space
+        handles.handle(5, 0x10); // layer
+        handles.handle(5, 0x40); // IMAGEDEF
+        handles.handle(3, 0x41); // IMAGEDEF_REACTOR
+
+        let record = Bits::record(body, handles);
+        let image = decode_image(&mut BitReader::new(&record)).unwrap();
+        assert_eq!(image.handle, 0x30);
+        assert_eq!(image.insertion, (10.0, 20.0, 0.0));
+        assert_eq!(image.u_vector, (0.5, 0.0, 0.0));
+        assert_eq!(image.v_vector, (0.0, 0.5, 0.0));
+        assert_eq!(image.size, (640.0, 480.0));
+        assert_eq!(image.display_flags, 0x07);
+        assert!(image.clipping && !image.clip_inverted);
+        assert_eq!((image.brightness, image.contrast, image.fade), (50, 60, 0));
+        assert_eq!(image.clip_vertices, vec![(-0.5, -0.5), (639.5, 479.5)]);
+        assert_eq!(image.layer_handle, 0x10);
+        assert_eq!(image.imagedef_handle, Some(0x40));
+        assert_eq!(image.imagedef_reactor_handle, Some(0x41));
+    }
+
+    #[test]
+    fn decodes_image_def_path_only_when_inline() {
+        let build = |inline_path: bool| {
+            let mut body = Bits::default();
+            body.common_object_header(0x40);
+            body.push(1, 1); // no xdictionary
+            body.push(0b10, 2); // class version BL = 0
+            body.rd(640.0);
+            body.rd(480.0);
+            if inline_path {
+                let path = b"C:\\scans\\site.tif";
+                body.small(path.len() as u8);
+                for byte in path {
+                    body.rc(*byte);
+                }
+            }
+            body.push(1, 1); // loaded
+            body.rc(2); // centimeters
+            body.rd(0.01);
+            body.rd(0.02);
+            Bits::record(body, Bits::default())
+        };
+
+        let definition = decode_image_def(&mut BitReader::new(&build(true))).unwrap();
+        assert_eq!(definition.handle, 0x40);
+        assert_eq!(definition.image_size, (640.0, 480.0));
+        assert_eq!(definition.file_path.as_deref(), Some("C:\\scans\\site.tif"));
+        assert!(definition.is_loaded);
+        assert_eq!(definition.resolution_units, 2);
+        assert_eq!(definition.pixel_size, (0.01, 0.02));
+
+        let definition = decode_image_def_r2007(&mut BitReader::new(&build(false))).unwrap();
+        assert_eq!(definition.file_path, None);
+        assert_eq!(definition.pixel_size, (0.01, 0.02));
+    }
+
+    fn unit_image(clipping: bool, clip_vertices: Vec<(f64, f64)>) -> ImageEntity {
+        ImageEntity {
+            handle: 0x30,
+            color_index: None,
+            true_color: None,
+            transparency: None,
+            ltype_scale: 1.0,
+            lineweight: None,
+            layer_handle: 0,
+            class_version: 0,
+            insertion: (-0.5, -0.5, 0.0),
+            u_vector: (4.0, 0.0, 0.0),
+            v_vector: (0.0, 2.0, 0.0),
+            size: (1.0, 1.0),
+            display_flags: 0x07,
+            clipping,
+            brightness: 50,
+            contrast: 50,
+            fade: 0,
+            clip_inverted: false,
+            clip_boundary_type: if clip_vertices.len() == 2 { 1 } else { 2 },
+            clip_vertices,
+            imagedef_handle: None,
+            imagedef_reactor_handle: None,
+        }
+    }
+
+    #[test]
+    fn boundary_maps_pixel_clip_to_world() {
+        // WIPEOUT-style unit image stretched to 4 x 2 with its lower-left
+        // corner at (-0.5, -0.5).
+        let full = unit_image(false, Vec::new()).boundary_wcs();
+        assert_eq!(
+            full,
+            vec![
+                (-0.5, -0.5, 0.0),
+                (3.5, -0.5, 0.0),
+                (3.5, 1.5, 0.0),
+                (-0.5, 1.5, 0.0)
+            ]
+        );
+
+        // Polygon clip in pixel space, closed by repeating the first vertex.
+        let triangle = unit_image(
+            true,
+            vec![(-0.5, 0.5), (0.5, 0.5), (0.0, -0.5), (-0.5, 0.5)],
+        )
+        .boundary_wcs();
+        assert_eq!(
+            triangle,
+            vec![(-0.5, -0.5, 0.0), (3.5, -0.5, 0.0), (1.5, 1.5, 0.0)]
+        );
+    }
+}
@@ -1,6 +1,11 @@
 use crate::bit::{BitReader, Endian};
+use crate::core::geometry::insert_transform;
 use crate::core::result::Result;
-use crate::entities::common::parse_common_entity_header;
+use crate::entities::common::{
+    parse_common_entity_handles, parse_common_entity_header, parse_common_entity_header_r2007,
+    parse_common_entity_header_r2010, parse_common_entity_header_r2013, read_handle_reference,
+    seek_handle_stream, CommonEntityHeader,
+};
 
 #[derive(Debug, Clone)]
 pub struct InsertEntity {
@@ -8,11 +13,66 @@ pub struct InsertEntity {
     pub position: (f64, f64, f64),
     pub scale: (f64, f64, f64),
     pub rotation: f64,
+    pub extrusion: (f64, f64, f64),
+    pub has_attribs: bool,
+    pub block_header_handle: Option<u64>,
+    /// Every owned ATTRIB; only R2004+ records list them all.
+    pub attrib_handles: Vec<u64>,
+    /// Ends of the ATTRIB chain, as R13-R2000 records store it.
+    pub first_attrib_handle: Option<u64>,
+    pub last_attrib_handle: Option<u64>,
+    pub seqend_handle: Option<u64>,
 }
 
 pub fn decode_insert(reader: &mut BitReader<'_>) -> Result<InsertEntity> {
     let header = parse_common_entity_header(reader)?;
+    decode_insert_with_header(reader, header, AttribLayout::Chain)
+}
+
+pub fn decode_insert_r2004(reader: &mut BitReader<'_>) -> Result<InsertEntity> {
+    let header = parse_common_entity_header(reader)?;
+    decode_insert_with_header(reader, header, AttribLayout::Owned)
+}
+
+pub fn decode_insert_r2007(reader: &mut BitReader<'_>) -> Result<InsertEntity> {
+    let header = parse_common_entity_header_r2007(reader)?;
+    decode_insert_with_header(reader, header, AttribLayout::Owned)
+}
+
+pub fn decode_insert_r2010(
+    reader: &mut BitReader<'_>,
+    object_data_end_bit: u32,
+    object_handle: u64,
+) -> Result<InsertEntity> {
+    let mut header = parse_common_entity_header_r2010(reader, object_data_end_bit)?;
+    header.handle = object_handle;
+    decode_insert_with_header(reader, header, AttribLayout::Owned)
+}
 
+pub fn decode_insert_r2013(
+    reader: &mut BitReader<'_>,
+    object_data_end_bit: u32,
+    object_handle: u64,
+) -> Result<InsertEntity> {
+    let mut header = parse_common_entity_header_r2013(reader, object_data_end_bit)?;
+    header.handle = object_handle;
+    decode_insert_with_header(reader, header, AttribLayout::Owned)
+}
+
+/// How the handle stream references the ATTRIBs of an INSERT.
+#[derive(Clone, Copy)]
+enum AttribLayout {
+    /// R13-R2000: first and last ATTRIB handles.
+    Chain,
+    /// R2004+: an owned count in the data stream and one handle per ATTRIB.
+    Owned,
+}
+
+fn decode_insert_with_header(
+    reader: &mut BitReader<'_>,
+    header: CommonEntityHeader,
+    layout: AttribLayout,
+) -> Result<InsertEntity> {
     let position = reader.read_3bd()?;
     let data_flags = reader.read_bb()?;
 
@@ -36,16 +96,133 @@ pub fn decode_insert(reader: &mut BitReader<'_>) -> Result<InsertEntity> {
     };
 
     let rotation = reader.read_bd()?;
-    let _extrusion = reader.read_3bd()?;
-    let has_attribs = reader.read_b()?;
-    if has_attribs == 1 {
-        let _owned_obj_count = reader.read_bl()?;
-    }
+    let extrusion = reader.read_3bd()?;
+    let has_attribs = reader.read_b()? == 1;
+    let owned_obj_count = match layout {
+        AttribLayout::Owned if has_attribs => Some(reader.read_bl()? as usize),
+        _ => None,
+    };
+
+    // The handle stream is optional for callers that only need geometry, so
+    // unreadable handles leave the references empty instead of failing the
+    // INSERT. Entity data overrunning the stream still fails under strict.
+    // The block reference comes first and is kept when only the attribute
+    // list after it is unreadable.
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
+    let block_header_handle = parse_common_entity_handles(reader, &header)
+        .and_then(|_| read_handle_reference(reader, header.handle))
+        .ok();
+    let mut first_attrib_handle = None;
+    let mut last_attrib_handle = None;
+    let (attrib_handles, seqend_handle) = match (block_header_handle, layout) {
+        (Some(_), _) if !has_attribs => (Vec::new(), None),
+        (Some(_), AttribLayout::Chain) => (|| -> Result<Option<u64>> {
+            first_attrib_handle = Some(read_handle_reference(reader, header.handle)?);
+            last_attrib_handle = Some(read_handle_reference(reader, header.handle)?);
+            Ok(Some(read_handle_reference(reader, header.handle)?))
+        })()
+        .map(|seqend| (Vec::new(), seqend))
+        .unwrap_or_default(),
+        (Some(_), AttribLayout::Owned) => (|| -> Result<(Vec<u64>, Option<u64>)> {
+            let count = owned_obj_count.unwrap_or_default();
+            let capacity = count.min((reader.remaining_bits() / 8) as usize);
+            let mut attribs = Vec::with_capacity(capacity);
+            for _ in 0..count {
+                attribs.push(read_handle_reference(reader, header.handle)?);
+            }
+            let seqend = read_handle_reference(reader, header.handle)?;
+            Ok((attribs, Some(seqend)))
+        })()
+        .unwrap_or_default(),
+        (None, _) => (Vec::new(), None),
+    };
 
     Ok(InsertEntity {
         handle: header.handle,
         position,
         scale: (x_scale, y_scale, z_scale),
         rotation,
+        extrusion,
+        has_attribs,
+        block_header_handle,
+        attrib_handles,
+        first_attrib_handle,
+        last_attrib_handle,
+        seqend_handle,
     })
 }
+
+impl InsertEntity {
+    /// Block-to-world matrix (row-major) for a block whose base point is
+    /// `base_point`, see [`insert_transform`].
+    pub fn transform(&self, base_point: (f64, f64, f64)) -> [f64; 16] {
+        insert_transform(
+            self.position,
+            self.scale,
+            self.rotation,
+            self.extrusion,
+            base_point,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::test_support::Bits;
+
+    fn insert_body(owned_count: Option<u8>) -> Bits {
+        let mut body = Bits::default();
+        body.common_entity_header(0x40, 0b10);
+        body.point((100.0, 50.0, 0.0));
+        body.push(0b11, 2); // unit scale
+        body.bd(0.5); // rotation
+        body.point((0.0, 0.0, 1.0));
+        body.push(1, 1); // has attribs
+        if let Some(count) = owned_count {
+            body.small(count);
+        }
+        body
+    }
+
+    #[test]
+    fn reads_the_attrib_chain_before_r2004() {
+        let mut handles = Bits::default();
+        handles.handle(5, 0x10); // layer
+        handles.handle(5, 0x20); // block header
+        handles.handle(4, 0x41); // first attrib
+        handles.handle(4, 0x43); // last attrib
+        handles.handle(3, 0x44); // seqend
+        let data = Bits::record(insert_body(None), handles);
+
+        let insert = decode_insert(&mut BitReader::new(&data)).unwrap();
+        assert_eq!(insert.handle, 0x40);
+        assert_eq!(insert.position, (100.0, 50.0, 0.0));
+        assert_eq!(insert.scale, (1.0, 1.0, 1.0));
+        assert_eq!(insert.rotation, 0.5);
+        assert!(insert.has_attribs);
+        assert_eq!(insert.block_header_handle, Some(0x20));
+        assert_eq!(insert.first_attrib_handle, Some(0x41));
+        assert_eq!(insert.last_attrib_handle, Some(0x43));
+        assert!(insert.attrib_handles.is_empty());
+        assert_eq!(insert.seqend_handle, Some(0x44));
+    }
+
+    #[test]
+    fn reads_owned_attribs_from_r2004() {
+        let mut handles = Bits::default();
+        handles.handle(5, 0x10); // layer
+        handles.handle(5, 0x20); // block header
+        handles.handle(4, 0x41);
+        handles.handle(4, 0x42);
+        handles.handle(3, 0x44); // seqend
+        let data = Bits::record(insert_body(Some(2)), handles);
+
+        let insert = decode_insert_r2004(&mut BitReader::new(&data)).unwrap();
+        assert_eq!(insert.position, (100.0, 50.0, 0.0));
+        assert_eq!(insert.block_header_handle, Some(0x20));
+        assert_eq!(insert.attrib_handles, vec![0x41, 0x42]);
+        assert_eq!(insert.first_attrib_handle, None);
+        assert_eq!(insert.seqend_handle, Some(0x44));
+    }
+}
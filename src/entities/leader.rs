@@ -4,7 +4,7 @@ use crate::core::result::Result;
 use crate::entities::common::{
     parse_common_entity_handles, parse_common_entity_header, parse_common_entity_header_r2007,
     parse_common_entity_header_r2010, parse_common_entity_header_r2013,
-    parse_common_entity_layer_handle, CommonEntityHeader,
+    parse_common_entity_layer_handle, seek_handle_stream, CommonEntityHeader,
 };
 
 #[derive(Debug, Clone)]
@@ -12,6 +12,9 @@ pub struct LeaderEntity {
     pub handle: u64,
     pub color_index: Option<u16>,
     pub true_color: Option<u32>,
+    pub transparency: Option<u32>,
+    pub ltype_scale: f64,
+    pub lineweight: Option<i8>,
     pub layer_handle: u64,
     pub annotation_type: u16,
     pub path_type: u16,
@@ -25,7 +28,7 @@ pub fn decode_leader(reader: &mut BitReader<'_>) -> Result<LeaderEntity> {
 
 pub fn decode_leader_r2007(reader: &mut BitReader<'_>) -> Result<LeaderEntity> {
     let header = parse_common_entity_header_r2007(reader)?;
-    decode_leader_with_header(reader, header, true, true)
+    decode_leader_with_header(reader, header, true, false)
 }
 
 pub fn decode_leader_r2010(
@@ -75,7 +78,7 @@ fn decode_leader_with_header(
     }
 
     // Handles are stored in the handle stream at obj_size bit offset.
-    reader.set_bit_pos(header.obj_size);
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
     let layer_handle = match if r2007_layer_only {
         parse_common_entity_layer_handle(reader, &header)
     } else {
@@ -98,6 +101,9 @@ fn decode_leader_with_header(
         handle: header.handle,
         color_index: header.color.index,
         true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
         layer_handle,
         annotation_type,
         path_type,
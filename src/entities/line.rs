@@ -4,10 +4,15 @@ use crate::core::result::Result;
 use crate::entities::common::{
     parse_common_entity_handles, parse_common_entity_header, parse_common_entity_header_r14,
     parse_common_entity_header_r2007, parse_common_entity_header_r2010,
-    parse_common_entity_header_r2013, parse_common_entity_layer_handle, CommonEntityHeader,
+    parse_common_entity_header_r2013, parse_common_entity_layer_handle, seek_handle_stream,
+    CommonEntityHeader,
 };
 use std::sync::atomic::{AtomicU32, Ordering};
 
+type Point3 = (f64, f64, f64);
+type LineBody = (Point3, Point3, Point3);
+type LineDebugCandidate = (u64, u64, Point3, Point3, Point3);
+
 static R14_LINE_PREFERRED_DELTA: AtomicU32 = AtomicU32::new(64);
 
 #[derive(Debug, Clone)]
@@ -15,6 +20,9 @@ pub struct LineEntity {
     pub handle: u64,
     pub color_index: Option<u16>,
     pub true_color: Option<u32>,
+    pub transparency: Option<u32>,
+    pub ltype_scale: f64,
+    pub lineweight: Option<i8>,
     pub layer_handle: u64,
     pub start: (f64, f64, f64),
     pub end: (f64, f64, f64),
@@ -72,7 +80,7 @@ pub fn decode_line_r14(reader: &mut BitReader<'_>, object_handle: u64) -> Result
 
 pub fn decode_line_r2007(reader: &mut BitReader<'_>) -> Result<LineEntity> {
     let header = parse_common_entity_header_r2007(reader)?;
-    decode_line_with_header(reader, header, true, true)
+    decode_line_with_header(reader, header, true, false)
 }
 
 fn decode_line_r14_fallback(reader: &mut BitReader<'_>, object_handle: u64) -> Result<LineEntity> {
@@ -81,8 +89,7 @@ fn decode_line_r14_fallback(reader: &mut BitReader<'_>, object_handle: u64) -> R
     let debug_enabled = std::env::var("EZDWG_DEBUG_R14_LINE")
         .ok()
         .is_some_and(|value| value != "0");
-    let mut debug_candidates: Vec<(u64, u64, (f64, f64, f64), (f64, f64, f64), (f64, f64, f64))> =
-        Vec::new();
+    let mut debug_candidates: Vec<LineDebugCandidate> = Vec::new();
 
     let preferred_delta = R14_LINE_PREFERRED_DELTA.load(Ordering::Relaxed) as i64;
     let preferred_start = preferred_delta.saturating_sub(6).max(0) as u64;
@@ -125,6 +132,9 @@ fn decode_line_r14_fallback(reader: &mut BitReader<'_>, object_handle: u64) -> R
                 handle: object_handle,
                 color_index: None,
                 true_color: None,
+                transparency: None,
+                ltype_scale: 1.0,
+                lineweight: None,
                 layer_handle: 0,
                 start,
                 end,
@@ -191,9 +201,7 @@ fn decode_line_r14_fallback(reader: &mut BitReader<'_>, object_handle: u64) -> R
     ))
 }
 
-fn parse_line_body_no_common(
-    reader: &mut BitReader<'_>,
-) -> Result<((f64, f64, f64), (f64, f64, f64), (f64, f64, f64))> {
+fn parse_line_body_no_common(reader: &mut BitReader<'_>) -> Result<LineBody> {
     let z_is_zero = reader.read_b()?;
     let x_start = reader.read_rd(Endian::Little)?;
     let x_end = reader.read_dd(x_start)?;
@@ -218,9 +226,7 @@ fn parse_line_body_no_common(
     ))
 }
 
-fn parse_line_body_no_common_r14_alt(
-    reader: &mut BitReader<'_>,
-) -> Result<((f64, f64, f64), (f64, f64, f64), (f64, f64, f64))> {
+fn parse_line_body_no_common_r14_alt(reader: &mut BitReader<'_>) -> Result<LineBody> {
     let x_start = reader.read_rd(Endian::Little)?;
     let x_end = reader.read_dd(x_start)?;
     let y_start = reader.read_rd(Endian::Little)?;
@@ -237,9 +243,7 @@ fn parse_line_body_no_common_r14_alt(
     ))
 }
 
-fn parse_line_body_no_common_r14_3bd(
-    reader: &mut BitReader<'_>,
-) -> Result<((f64, f64, f64), (f64, f64, f64), (f64, f64, f64))> {
+fn parse_line_body_no_common_r14_3bd(reader: &mut BitReader<'_>) -> Result<LineBody> {
     let start = reader.read_3bd()?;
     let end = reader.read_3bd()?;
     let _thickness = reader.read_bt()?;
@@ -275,7 +279,7 @@ fn score_line_candidate(
 
     let ex_norm =
         (extrusion.0 * extrusion.0 + extrusion.1 * extrusion.1 + extrusion.2 * extrusion.2).sqrt();
-    if !ex_norm.is_finite() || ex_norm < 1.0e-9 || ex_norm > 1.0e3 {
+    if !ex_norm.is_finite() || !(1.0e-9..=1.0e3).contains(&ex_norm) {
         return None;
     }
 
@@ -415,6 +419,9 @@ fn decode_line_with_header(
         handle: header.handle,
         color_index: header.color.index,
         true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
         layer_handle,
         start: (x_start, y_start, z_start),
         end: (x_end, y_end, z_end),
@@ -442,6 +449,9 @@ fn decode_line_with_header_r14(
         handle: header.handle,
         color_index: header.color.index,
         true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
         layer_handle,
         start,
         end,
@@ -455,7 +465,7 @@ fn decode_layer_handle_with_common_header(
     r2007_layer_only: bool,
 ) -> Result<u64> {
     // Handles are stored in the handle stream at obj_size bit offset.
-    reader.set_bit_pos(header.obj_size);
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
     let layer_handle = match if r2007_layer_only {
         parse_common_entity_layer_handle(reader, header)
     } else {
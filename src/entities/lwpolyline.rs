@@ -4,8 +4,8 @@ use crate::core::result::Result;
 use crate::entities::common::{
     parse_common_entity_handles, parse_common_entity_header, parse_common_entity_header_r14,
     parse_common_entity_header_r2007, parse_common_entity_header_r2010,
-    parse_common_entity_header_r2013, parse_common_entity_layer_handle, CommonEntityColor,
-    CommonEntityHeader,
+    parse_common_entity_header_r2013, parse_common_entity_layer_handle, read_eed,
+    seek_handle_stream, CommonEntityColor, CommonEntityHeader,
 };
 
 #[derive(Debug, Clone)]
@@ -13,12 +13,17 @@ pub struct LwPolylineEntity {
     pub handle: u64,
     pub color_index: Option<u16>,
     pub true_color: Option<u32>,
+    pub transparency: Option<u32>,
+    pub ltype_scale: f64,
+    pub lineweight: Option<i8>,
     pub layer_handle: u64,
     pub flags: u16,
     pub vertices: Vec<(f64, f64)>,
     pub const_width: Option<f64>,
     pub bulges: Vec<f64>,
     pub widths: Vec<(f64, f64)>,
+    pub elevation: f64,
+    pub extrusion: (f64, f64, f64),
 }
 
 const MAX_LWPOLYLINE_ITEMS: usize = 1_000_000;
@@ -169,7 +174,7 @@ pub fn decode_lwpolyline_r14(
 
 pub fn decode_lwpolyline_r2007(reader: &mut BitReader<'_>) -> Result<LwPolylineEntity> {
     let header = parse_common_entity_header_r2007(reader)?;
-    decode_lwpolyline_with_header(reader, header, true, true, false)
+    decode_lwpolyline_with_header(reader, header, true, false, false)
 }
 
 pub fn decode_lwpolyline_r2010(
@@ -211,12 +216,17 @@ fn decode_lwpolyline_with_header(
         handle: header.handle,
         color_index: header.color.index,
         true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
         layer_handle,
         flags: body.flags,
         vertices: body.vertices,
         const_width: body.const_width,
         bulges: body.bulges,
         widths: body.widths,
+        elevation: body.elevation,
+        extrusion: body.extrusion,
     })
 }
 
@@ -253,6 +263,8 @@ struct LwPolylineBody {
     const_width: Option<f64>,
     bulges: Vec<f64>,
     widths: Vec<(f64, f64)>,
+    elevation: f64,
+    extrusion: (f64, f64, f64),
 }
 
 fn decode_lwpolyline_body(
@@ -265,15 +277,19 @@ fn decode_lwpolyline_body(
     } else {
         None
     };
-    if (flags & 0x08) != 0 {
-        let _elevation = reader.read_bd()?;
-    }
+    let elevation = if (flags & 0x08) != 0 {
+        reader.read_bd()?
+    } else {
+        0.0
+    };
     if (flags & 0x02) != 0 {
         let _thickness = reader.read_bd()?;
     }
-    if (flags & 0x01) != 0 {
-        let _normal = reader.read_3bd()?;
-    }
+    let extrusion = if (flags & 0x01) != 0 {
+        reader.read_3bd()?
+    } else {
+        (0.0, 0.0, 1.0)
+    };
 
     let num_verts = reader.read_bl()? as usize;
     validate_lwpolyline_count("vertex count", num_verts)?;
@@ -309,9 +325,8 @@ fn decode_lwpolyline_body(
             let y0 = reader.read_rd(Endian::Little)?;
             vertices.push((x0, y0));
             for _ in 1..num_verts {
-                let x = reader.read_dd(vertices.last().unwrap().0)?;
-                let y = reader.read_dd(vertices.last().unwrap().1)?;
-                vertices.push((x, y));
+                let vertex = reader.read_2dd(*vertices.last().unwrap())?;
+                vertices.push(vertex);
             }
         }
     }
@@ -354,6 +369,8 @@ fn decode_lwpolyline_body(
         const_width,
         bulges,
         widths,
+        elevation,
+        extrusion,
     })
 }
 
@@ -364,7 +381,7 @@ fn decode_lwpolyline_layer_handle(
     r2007_layer_only: bool,
 ) -> Result<u64> {
     // Handles are stored in the handle stream at obj_size bit offset.
-    reader.set_bit_pos(header.obj_size);
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
     let layer_handle = match if r2007_layer_only {
         parse_common_entity_layer_handle(reader, header)
     } else {
@@ -393,7 +410,7 @@ fn parse_r14_lwpolyline_compact_header(
     if handle == 0 {
         handle = object_handle;
     }
-    skip_eed(reader)?;
+    let xdata = read_eed(reader)?;
 
     let graphic_present_flag = reader.read_b()?;
     if graphic_present_flag == 1 {
@@ -415,13 +432,15 @@ fn parse_r14_lwpolyline_compact_header(
     }
 
     let _color_unknown = reader.read_b()?;
-    let _ltype_scale = reader.read_bd()?;
+    let ltype_scale = reader.read_bd()?;
     let _invisibility = reader.read_bs()?;
 
     Ok(CommonEntityHeader {
         obj_size,
         handle,
         color: CommonEntityColor::default(),
+        ltype_scale,
+        lineweight: None,
         entity_mode,
         num_of_reactors,
         xdic_missing_flag,
@@ -433,21 +452,11 @@ fn parse_r14_lwpolyline_compact_header(
         has_face_visual_style: false,
         has_edge_visual_style: false,
         has_legacy_entity_links: false,
+        graphic: None,
+        xdata,
     })
 }
 
-fn skip_eed(reader: &mut BitReader<'_>) -> Result<()> {
-    let mut ext_size = reader.read_bs()?;
-    while ext_size > 0 {
-        let _app_handle = reader.read_h()?;
-        for _ in 0..ext_size {
-            let _ = reader.read_rc()?;
-        }
-        ext_size = reader.read_bs()?;
-    }
-    Ok(())
-}
-
 fn decode_lwpolyline_r14_scan_by_obj_size(
     reader: &mut BitReader<'_>,
     object_handle: u64,
@@ -458,7 +467,7 @@ fn decode_lwpolyline_r14_scan_by_obj_size(
     if header.handle == 0 {
         header.handle = object_handle;
     }
-    let target_end = header.obj_size as u64;
+    let target_end = header.handle_stream_start_bit() as u64;
     if target_end <= base_bit {
         return Err(DwgError::new(
             ErrorKind::Format,
@@ -510,12 +519,17 @@ fn decode_lwpolyline_r14_scan_by_obj_size(
         handle: header.handle,
         color_index: header.color.index,
         true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
         layer_handle,
         flags: body.flags,
         vertices: body.vertices,
         const_width: body.const_width,
         bulges: body.bulges,
         widths: body.widths,
+        elevation: body.elevation,
+        extrusion: body.extrusion,
     })
 }
 
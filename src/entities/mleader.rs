@@ -0,0 +1,402 @@
+use crate::bit::BitReader;
+use crate::core::error::{DwgError, ErrorKind};
+use crate::core::result::Result;
+use crate::entities::common::{
+    parse_common_entity_handles, parse_common_entity_header, parse_common_entity_header_r2007,
+    parse_common_entity_header_r2010, parse_common_entity_header_r2013,
+    parse_common_entity_layer_handle, seek_handle_stream, string_stream_reader, CommonEntityHeader,
+};
+
+type Point3 = (f64, f64, f64);
+
+const MAX_MLEADER_ITEMS: usize = 100_000;
+
+/// MULTILEADER: leader lines sharing one annotation (MTEXT or a block).
+/// Decoding stops after the annotation context; style overrides are skipped.
+#[derive(Debug, Clone)]
+pub struct MLeaderEntity {
+    pub handle: u64,
+    pub color_index: Option<u16>,
+    pub true_color: Option<u32>,
+    pub transparency: Option<u32>,
+    pub ltype_scale: f64,
+    pub lineweight: Option<i8>,
+    pub layer_handle: u64,
+    /// R2010+ only.
+    pub class_version: Option<u16>,
+    pub leaders: Vec<MLeaderRoot>,
+    pub scale: f64,
+    pub content_base: Point3,
+    pub text_height: f64,
+    pub arrow_size: f64,
+    pub landing_gap: f64,
+    pub content: MLeaderContent,
+}
+
+/// One leader root: the lines attached at one side of the content.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MLeaderRoot {
+    /// Where the leader lines meet the landing (last leader line point).
+    pub landing_point: Option<Point3>,
+    /// Unit direction of the dogleg, from the landing point.
+    pub dogleg_vector: Option<Point3>,
+    pub dogleg_length: f64,
+    /// Vertices of each leader line, from the arrow head towards the landing.
+    pub lines: Vec<Vec<Point3>>,
+    /// R2010+: 0 horizontal, 1 vertical attachment.
+    pub attach_direction: Option<u16>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MLeaderContent {
+    None,
+    /// `text` is the raw MTEXT string (with format codes); `None` if the
+    /// object carried no string data.
+    Text {
+        text: Option<String>,
+        location: Point3,
+        direction: Point3,
+        rotation: f64,
+        width: f64,
+        height: f64,
+    },
+    Block {
+        location: Point3,
+        scale: Point3,
+        rotation: f64,
+    },
+}
+
+pub fn decode_mleader(reader: &mut BitReader<'_>) -> Result<MLeaderEntity> {
+    let header = parse_common_entity_header(reader)?;
+    let strings = TextSource::Inline;
+    decode_mleader_with_header(reader, header, strings, false)
+}
+
+pub fn decode_mleader_r2007(reader: &mut BitReader<'_>) -> Result<MLeaderEntity> {
+    let header = parse_common_entity_header_r2007(reader)?;
+    let strings = TextSource::stream(reader, header.obj_size)?;
+    decode_mleader_with_header(reader, header, strings, false)
+}
+
+pub fn decode_mleader_r2010(
+    reader: &mut BitReader<'_>,
+    object_data_end_bit: u32,
+    object_handle: u64,
+) -> Result<MLeaderEntity> {
+    let mut header = parse_common_entity_header_r2010(reader, object_data_end_bit)?;
+    header.handle = object_handle;
+    let strings = TextSource::stream(reader, object_data_end_bit)?;
+    decode_mleader_with_header(reader, header, strings, true)
+}
+
+pub fn decode_mleader_r2013(
+    reader: &mut BitReader<'_>,
+    object_data_end_bit: u32,
+    object_handle: u64,
+) -> Result<MLeaderEntity> {
+    let mut header = parse_common_entity_header_r2013(reader, object_data_end_bit)?;
+    header.handle = object_handle;
+    let strings = TextSource::stream(reader, object_data_end_bit)?;
+    decode_mleader_with_header(reader, header, strings, true)
+}
+
+/// Where T fields are read from: inline in the data (pre-R2007) or from the
+/// object's string stream.
+enum TextSource<'a> {
+    Inline,
+    Stream(Option<BitReader<'a>>),
+}
+
+impl<'a> TextSource<'a> {
+    fn stream(reader: &BitReader<'a>, data_end_bit: u32) -> Result<Self> {
+        Ok(Self::Stream(string_stream_reader(reader, data_end_bit)?))
+    }
+
+    fn read(&mut self, data: &mut BitReader<'_>) -> Result<Option<String>> {
+        match self {
+            Self::Inline => data.read_tv().map(Some),
+            Self::Stream(Some(stream)) => stream.read_tu().map(Some),
+            Self::Stream(None) => Ok(None),
+        }
+    }
+}
+
+fn decode_mleader_with_header(
+    reader: &mut BitReader<'_>,
+    header: CommonEntityHeader,
+    mut strings: TextSource<'_>,
+    r2010_plus: bool,
+) -> Result<MLeaderEntity> {
+    let class_version = if r2010_plus {
+        Some(reader.read_bs()?)
+    } else {
+        None
+    };
+
+    let root_count = read_count(reader, "leader root count")?;
+    let mut leaders = Vec::with_capacity(root_count.min(64));
+    for _ in 0..root_count {
+        leaders.push(read_leader_root(reader, &mut strings, r2010_plus)?);
+    }
+
+    let scale = reader.read_bd()?;
+    let content_base = reader.read_3bd()?;
+    let text_height = reader.read_bd()?;
+    let arrow_size = reader.read_bd()?;
+    let landing_gap = reader.read_bd()?;
+    let _text_left_attachment = reader.read_bs()?;
+    let _text_right_attachment = reader.read_bs()?;
+    let _text_angle_type = reader.read_bs()?;
+    let _text_alignment = reader.read_bs()?;
+
+    let content = if reader.read_b()? != 0 {
+        let text = strings.read(reader)?;
+        let _normal = reader.read_3bd()?;
+        let location = reader.read_3bd()?;
+        let direction = reader.read_3bd()?;
+        let rotation = reader.read_bd()?;
+        let width = reader.read_bd()?;
+        let height = reader.read_bd()?;
+        MLeaderContent::Text {
+            text,
+            location,
+            direction,
+            rotation,
+            width,
+            height,
+        }
+    } else if reader.read_b()? != 0 {
+        let _normal = reader.read_3bd()?;
+        let location = reader.read_3bd()?;
+        let scale = reader.read_3bd()?;
+        let rotation = reader.read_bd()?;
+        MLeaderContent::Block {
+            location,
+            scale,
+            rotation,
+        }
+    } else {
+        MLeaderContent::None
+    };
+
+    // Handles are stored in the handle stream at obj_size bit offset.
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
+    let handles_pos = reader.get_pos();
+    let layer_handle = match parse_common_entity_handles(reader, &header) {
+        Ok(common_handles) => common_handles.layer,
+        Err(err)
+            if matches!(
+                err.kind,
+                ErrorKind::Format | ErrorKind::Decode | ErrorKind::Io
+            ) =>
+        {
+            reader.set_pos(handles_pos.0, handles_pos.1);
+            parse_common_entity_layer_handle(reader, &header).unwrap_or(0)
+        }
+        Err(err) => return Err(err),
+    };
+
+    Ok(MLeaderEntity {
+        handle: header.handle,
+        color_index: header.color.index,
+        true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
+        layer_handle,
+        class_version,
+        leaders,
+        scale,
+        content_base,
+        text_height,
+        arrow_size,
+        landing_gap,
+        content,
+    })
+}
+
+fn read_leader_root(
+    reader: &mut BitReader<'_>,
+    strings: &mut TextSource<'_>,
+    r2010_plus: bool,
+) -> Result<MLeaderRoot> {
+    let has_landing_point = reader.read_b()? != 0;
+    let has_dogleg = reader.read_b()? != 0;
+    let landing_point = if has_landing_point {
+        Some(reader.read_3bd()?)
+    } else {
+        None
+    };
+    let dogleg_vector = if has_dogleg {
+        Some(reader.read_3bd()?)
+    } else {
+        None
+    };
+    let break_count = read_count(reader, "leader break count")?;
+    for _ in 0..break_count {
+        let _start = reader.read_3bd()?;
+        let _end = reader.read_3bd()?;
+    }
+    let _branch_index = reader.read_bl()?;
+    let dogleg_length = reader.read_bd()?;
+
+    let line_count = read_count(reader, "leader line count")?;
+    let mut lines = Vec::with_capacity(line_count.min(64));
+    for _ in 0..line_count {
+        let point_count = read_count(reader, "leader point count")?;
+        let mut points = Vec::with_capacity(point_count.min(1024));
+        for _ in 0..point_count {
+            points.push(reader.read_3bd()?);
+        }
+        let segment_break_count = read_count(reader, "leader segment break count")?;
+        for _ in 0..segment_break_count {
+            let _segment_index = reader.read_bl()?;
+            let _start = reader.read_3bd()?;
+            let _end = reader.read_3bd()?;
+        }
+        let _line_index = reader.read_bl()?;
+        if r2010_plus {
+            let _leader_type = reader.read_bs()?;
+            skip_cmc(reader, strings)?;
+            let _lineweight = reader.read_bl()?;
+            let _arrow_size = reader.read_bd()?;
+            let _override_flags = reader.read_bl()?;
+        }
+        lines.push(points);
+    }
+
+    let attach_direction = if r2010_plus {
+        Some(reader.read_bs()?)
+    } else {
+        None
+    };
+    Ok(MLeaderRoot {
+        landing_point,
+        dogleg_vector,
+        dogleg_length,
+        lines,
+        attach_direction,
+    })
+}
+
+/// R2004+ CMC color: BS index, BL RGB, RC name flags, then the names.
+fn skip_cmc(reader: &mut BitReader<'_>, strings: &mut TextSource<'_>) -> Result<()> {
+    let _index = reader.read_bs()?;
+    let _rgb = reader.read_bl()?;
+    let flags = reader.read_rc()?;
+    if flags & 0x01 != 0 {
+        let _color_name = strings.read(reader)?;
+    }
+    if flags & 0x02 != 0 {
+        let _book_name = strings.read(reader)?;
+    }
+    Ok(())
+}
+
+fn read_count(reader: &mut BitReader<'_>, label: &str) -> Result<usize> {
+    let count = reader.read_bl()? as usize;
+    if count > MAX_MLEADER_ITEMS {
+        return Err(DwgError::new(
+            ErrorKind::Format,
+            format!(
+                "invalid MULTILEADER {}: {} (max {})",
+                label, count, MAX_MLEADER_ITEMS
+            ),
+        ));
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::test_support::Bits;
+
+    /// R2007 MULTILEADER with one leader line and MTEXT content; the label
+    /// lives in the string stream at the end of the data.
+    fn mleader_record(label: &str) -> Vec<u8> {
+        let mut body = Bits::default();
+        body.common_entity_header_r2007(0x50, 0b00);
+
+        body.small(1); // one leader root
+        body.push(1, 1); // landing point present
+        body.push(1, 1); // dogleg present
+        body.point((10.0, 5.0, 0.0));
+        body.point((1.0, 0.0, 0.0));
+        body.push(0b10, 2); // no breaks
+        body.push(0b10, 2); // branch index 0
+        body.bd(2.5); // dogleg length
+        body.small(1); // one leader line
+        body.small(2);
+        body.point((0.0, 0.0, 0.0));
+        body.point((10.0, 5.0, 0.0));
+        body.push(0b10, 2); // no segment breaks
+        body.push(0b10, 2); // line index 0
+
+        body.bd(1.0); // overall scale
+        body.point((12.5, 5.0, 0.0));
+        body.bd(0.18);
+        body.bd(0.18);
+        body.bd(0.09);
+        for _ in 0..4 {
+            body.push(0b10, 2); // attachment / alignment BS = 0
+        }
+        body.push(1, 1); // text content
+        body.point((0.0, 0.0, 1.0));
+        body.point((12.6, 5.2, 0.0));
+        body.point((1.0, 0.0, 0.0));
+        body.bd(0.0);
+        body.bd(4.0);
+        body.bd(0.5);
+
+        // String stream: the label, its bit size, then the present flag.
+        let mut strings = Bits::default();
+        strings.push(0b00, 2);
+        strings.rs(label.encode_utf16().count() as u16);
+        for unit in label.encode_utf16() {
+            strings.rs(unit);
+        }
+        let string_bits = strings.0.len() as u16;
+        body.0.extend(strings.0);
+        body.rs(string_bits);
+        body.push(1, 1);
+
+        let mut handles = Bits::default();
+        handles.handle(4, 0x1F); // owner: model space
+        handles.handle(5, 0x10); // layer
+        Bits::record(body, handles)
+    }
+
+    #[test]
+    fn decodes_r2007_leader_and_text_from_string_stream() {
+        let record = mleader_record("Valve \\PV-101");
+        let mleader = decode_mleader_r2007(&mut BitReader::new(&record)).unwrap();
+        assert_eq!(mleader.handle, 0x50);
+        assert_eq!(mleader.layer_handle, 0x10);
+        assert_eq!(mleader.class_version, None);
+        assert_eq!(
+            mleader.leaders,
+            vec![MLeaderRoot {
+                landing_point: Some((10.0, 5.0, 0.0)),
+                dogleg_vector: Some((1.0, 0.0, 0.0)),
+                dogleg_length: 2.5,
+                lines: vec![vec![(0.0, 0.0, 0.0), (10.0, 5.0, 0.0)]],
+                attach_direction: None,
+            }]
+        );
+        assert_eq!(mleader.content_base, (12.5, 5.0, 0.0));
+        assert_eq!(mleader.text_height, 0.18);
+        assert_eq!(
+            mleader.content,
+            MLeaderContent::Text {
+                text: Some("Valve \\PV-101".to_string()),
+                location: (12.6, 5.2, 0.0),
+                direction: (1.0, 0.0, 0.0),
+                rotation: 0.0,
+                width: 4.0,
+                height: 0.5,
+            }
+        );
+    }
+}
@@ -2,28 +2,38 @@ pub mod arc;
 pub mod attrib;
 pub mod circle;
 pub mod common;
+pub mod dim_angular;
 pub mod dim_diameter;
 pub mod dim_linear;
 pub mod dim_radius;
+pub mod eed;
 pub mod ellipse;
+pub mod entity;
 pub mod face3d;
 pub mod hatch;
+pub mod image;
 pub mod insert;
 pub mod leader;
 pub mod line;
 pub mod lwpolyline;
 pub mod minsert;
+pub mod mleader;
 pub mod mline;
 pub mod mtext;
+pub mod mtext_format;
 pub mod point;
 pub mod polyline_2d;
 pub mod polyline_3d;
 pub mod polyline_mesh;
 pub mod polyline_pface;
+pub mod proxy;
 pub mod seqend;
 pub mod shape;
 pub mod solid;
 pub mod spline;
+pub mod table;
+#[cfg(test)]
+pub(crate) mod test_support;
 pub mod text;
 pub mod tolerance;
 pub mod trace;
@@ -42,30 +52,45 @@ pub use circle::{
     decode_circle, decode_circle_r14, decode_circle_r2007, decode_circle_r2010,
     decode_circle_r2013, CircleEntity,
 };
+pub use dim_angular::{
+    decode_dim_ang2ln, decode_dim_ang2ln_r2007, decode_dim_ang2ln_r2010, decode_dim_ang2ln_r2013,
+    decode_dim_ang3pt, decode_dim_ang3pt_r2007, decode_dim_ang3pt_r2010, decode_dim_ang3pt_r2013,
+    DimAng2LnEntity, DimAng3PtEntity,
+};
 pub use dim_diameter::{
     decode_dim_diameter, decode_dim_diameter_r2007, decode_dim_diameter_r2010,
     decode_dim_diameter_r2013, DimDiameterEntity,
 };
 pub use dim_linear::{
     decode_dim_linear, decode_dim_linear_r2007, decode_dim_linear_r2010, decode_dim_linear_r2013,
-    DimLinearEntity, DimensionCommonData,
+    DimLinearEntity, DimensionCommonData, GUESSED_LAYOUT_SCORE,
 };
 pub use dim_radius::{
     decode_dim_radius, decode_dim_radius_r2007, decode_dim_radius_r2010, decode_dim_radius_r2013,
     DimRadiusEntity,
 };
+pub use eed::{parse_eed, parse_eed_tolerant, XDataValue};
 pub use ellipse::{
     decode_ellipse, decode_ellipse_r14, decode_ellipse_r2007, decode_ellipse_r2010,
     decode_ellipse_r2013, EllipseEntity,
 };
+pub use entity::{DecodeMethod, Entity, GeometryKind};
 pub use face3d::{
     decode_3dface, decode_3dface_r2007, decode_3dface_r2010, decode_3dface_r2013, Face3dEntity,
 };
 pub use hatch::{
     decode_hatch, decode_hatch_r2004, decode_hatch_r2007, decode_hatch_r2010, decode_hatch_r2013,
-    HatchEntity, HatchPath,
+    HatchBoundary, HatchEdge, HatchEntity, HatchPath,
+};
+pub use image::{
+    decode_image, decode_image_def, decode_image_def_r2007, decode_image_def_r2010,
+    decode_image_def_r2013, decode_image_r2007, decode_image_r2010, decode_image_r2013,
+    ImageDefObject, ImageEntity,
+};
+pub use insert::{
+    decode_insert, decode_insert_r2004, decode_insert_r2007, decode_insert_r2010,
+    decode_insert_r2013, InsertEntity,
 };
-pub use insert::{decode_insert, InsertEntity};
 pub use leader::{
     decode_leader, decode_leader_r2007, decode_leader_r2010, decode_leader_r2013, LeaderEntity,
 };
@@ -78,6 +103,10 @@ pub use lwpolyline::{
     decode_lwpolyline_r2013, LwPolylineEntity,
 };
 pub use minsert::{decode_minsert, MInsertEntity};
+pub use mleader::{
+    decode_mleader, decode_mleader_r2007, decode_mleader_r2010, decode_mleader_r2013,
+    MLeaderContent, MLeaderEntity, MLeaderRoot,
+};
 pub use mline::{
     decode_mline, decode_mline_r2007, decode_mline_r2010, decode_mline_r2013, MLineEntity,
     MLineVertex,
@@ -86,6 +115,7 @@ pub use mtext::{
     decode_mtext, decode_mtext_r2004, decode_mtext_r2007, decode_mtext_r2010, decode_mtext_r2013,
     MTextEntity,
 };
+pub use mtext_format::mtext_to_plain;
 pub use point::{
     decode_point, decode_point_r14, decode_point_r2007, decode_point_r2010, decode_point_r2013,
     PointEntity,
@@ -106,6 +136,10 @@ pub use polyline_pface::{
     decode_polyline_pface, decode_polyline_pface_r2007, decode_polyline_pface_r2010,
     decode_polyline_pface_r2013, PolylinePFaceEntity,
 };
+pub use proxy::{
+    decode_proxy, decode_proxy_r2007, decode_proxy_r2010, decode_proxy_r2013, decode_proxy_r2018,
+    ProxyEntity,
+};
 pub use seqend::{decode_seqend, SeqendEntity};
 pub use shape::{
     decode_shape, decode_shape_r2007, decode_shape_r2010, decode_shape_r2013, ShapeEntity,
@@ -115,7 +149,11 @@ pub use solid::{
 };
 pub use spline::{
     catmull_rom_spline, decode_spline, decode_spline_r2007, decode_spline_r2010,
-    decode_spline_r2013, SplineEntity,
+    decode_spline_r2013, sample_spline, SplineEntity,
+};
+pub use table::{
+    decode_table, decode_table_r2004, decode_table_r2010, decode_table_r2013, TableCell,
+    TableEntity,
 };
 pub use text::{
     decode_text, decode_text_r14, decode_text_r2007, decode_text_r2010, decode_text_r2013,
@@ -128,7 +166,7 @@ pub use tolerance::{
 pub use trace::{
     decode_trace, decode_trace_r2007, decode_trace_r2010, decode_trace_r2013, TraceEntity,
 };
-pub use vertex_2d::{decode_vertex_2d, Vertex2dEntity};
+pub use vertex_2d::{decode_vertex_2d, Vertex2dEntity, Vertex2dFlags};
 pub use vertex_3d::{
     decode_vertex_3d, decode_vertex_3d_r2007, decode_vertex_3d_r2010, decode_vertex_3d_r2013,
     Vertex3dEntity,
@@ -4,14 +4,18 @@ use crate::core::result::Result;
 use crate::entities::common::{
     parse_common_entity_handles, parse_common_entity_header, parse_common_entity_header_r2007,
     parse_common_entity_header_r2010, parse_common_entity_header_r2013,
-    parse_common_entity_layer_handle, CommonEntityHeader,
+    parse_common_entity_layer_handle, seek_handle_stream, CommonEntityHeader,
 };
+use crate::entities::entity::DecodeMethod;
 
 #[derive(Debug, Clone)]
 pub struct MTextEntity {
     pub handle: u64,
     pub color_index: Option<u16>,
     pub true_color: Option<u32>,
+    pub transparency: Option<u32>,
+    pub ltype_scale: f64,
+    pub lineweight: Option<i8>,
     pub layer_handle: u64,
     pub text: String,
     pub insertion: (f64, f64, f64),
@@ -26,6 +30,9 @@ pub struct MTextEntity {
     pub background_color_index: Option<u16>,
     pub background_true_color: Option<u32>,
     pub background_transparency: Option<u32>,
+    /// `Guessed` when the background flags announce a fill that could not
+    /// be read; the background fields are then `None`.
+    pub decode_method: DecodeMethod,
 }
 
 pub fn decode_mtext(reader: &mut BitReader<'_>) -> Result<MTextEntity> {
@@ -40,7 +47,7 @@ pub fn decode_mtext_r2004(reader: &mut BitReader<'_>) -> Result<MTextEntity> {
 
 pub fn decode_mtext_r2007(reader: &mut BitReader<'_>) -> Result<MTextEntity> {
     let header = parse_common_entity_header_r2007(reader)?;
-    decode_mtext_with_header(reader, header, true, true)
+    decode_mtext_with_header(reader, header, false, true)
 }
 
 pub fn decode_mtext_r2010(
@@ -88,9 +95,12 @@ fn decode_mtext_with_header(
     let mut background_color_index = None;
     let mut background_true_color = None;
     let mut background_transparency = None;
+    let mut decode_method = DecodeMethod::Exact;
     if has_background_data {
         background_flags = reader.read_bl()?;
-        if (background_flags & 0x01) != 0 || (background_flags & 0x10) != 0 {
+        // Smallest possible payload: BD + BS + BL (2 bits each), RC, BL.
+        if ((background_flags & 0x01) != 0 || (background_flags & 0x10) != 0) && reader.has_bits(16)
+        {
             let parse_start = reader.get_pos();
             let parsed_background = (|| -> Result<(f64, u16, Option<u32>, u32)> {
                 let scale_factor = reader.read_bd()?;
@@ -126,6 +136,7 @@ fn decode_mtext_with_header(
                     ) =>
                 {
                     reader.set_pos(parse_start.0, parse_start.1);
+                    decode_method = DecodeMethod::Guessed;
                 }
                 Err(err) => return Err(err),
             }
@@ -133,7 +144,7 @@ fn decode_mtext_with_header(
     }
 
     // Handles are stored in the handle stream at obj_size bit offset.
-    reader.set_bit_pos(header.obj_size);
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
     let handles_pos = reader.get_pos();
     let layer_handle = match parse_common_entity_handles(reader, &header) {
         Ok(common_handles) => common_handles.layer,
@@ -154,6 +165,9 @@ fn decode_mtext_with_header(
         handle: header.handle,
         color_index: header.color.index,
         true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
         layer_handle,
         text,
         insertion,
@@ -168,6 +182,7 @@ fn decode_mtext_with_header(
         background_color_index,
         background_true_color,
         background_transparency,
+        decode_method,
     })
 }
 
@@ -0,0 +1,99 @@
+//! Plain-text rendering of MTEXT format codes.
+
+/// Strip MTEXT inline formatting: grouping braces, style toggles (`\L`,
+/// `\O`, `\K`) and `;`-terminated directives (`\f`, `\H`, `\C`, `\A`, ...)
+/// are removed, `\P` and `\X` become newlines, `\~` a space, `\U+XXXX` its
+/// character and stacked fractions (`\S1#2;`) `1/2`. `\\`, `\{` and `\}`
+/// unescape to the literal character.
+pub fn mtext_to_plain(raw: &str) -> String {
+    let chars: Vec<char> = raw.chars().collect();
+    let mut out = String::with_capacity(raw.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let ch = chars[i];
+        if ch == '{' || ch == '}' {
+            i += 1;
+            continue;
+        }
+        if ch != '\\' {
+            out.push(ch);
+            i += 1;
+            continue;
+        }
+        let Some(&code) = chars.get(i + 1) else {
+            out.push('\\');
+            break;
+        };
+        i += 2;
+        match code {
+            '\\' | '{' | '}' => out.push(code),
+            'P' | 'X' => out.push('\n'),
+            '~' => out.push(' '),
+            'L' | 'l' | 'O' | 'o' | 'K' | 'k' => {}
+            'U' | 'u' => match unicode_escape(&chars[i..]) {
+                Some(decoded) => {
+                    out.push(decoded);
+                    i += 5;
+                }
+                None => out.push(code),
+            },
+            'S' => {
+                while i < chars.len() && chars[i] != ';' {
+                    out.push(match chars[i] {
+                        '#' | '^' => '/',
+                        other => other,
+                    });
+                    i += 1;
+                }
+                i += 1;
+            }
+            'A' | 'C' | 'c' | 'F' | 'f' | 'H' | 'h' | 'Q' | 'q' | 'T' | 't' | 'W' | 'w' | 'p' => {
+                while i < chars.len() && chars[i] != ';' {
+                    i += 1;
+                }
+                i += 1;
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// `+XXXX` following `\U`.
+fn unicode_escape(rest: &[char]) -> Option<char> {
+    if rest.len() < 5 || rest[0] != '+' {
+        return None;
+    }
+    let digits: String = rest[1..5].iter().collect();
+    u32::from_str_radix(&digits, 16)
+        .ok()
+        .and_then(char::from_u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strips_directives_and_groups() {
+        assert_eq!(
+            mtext_to_plain(r"{\fArial|b1|i0|c0|p34;\C1;\H2.5x;Title}\PBody"),
+            "Title\nBody"
+        );
+        assert_eq!(
+            mtext_to_plain(r"\A1;\LUnder\l and \Oover\o"),
+            "Under and over"
+        );
+        assert_eq!(mtext_to_plain(r"\Q15;\W0.8;\T1.1;narrow"), "narrow");
+    }
+
+    #[test]
+    fn unescapes_literals_and_special_codes() {
+        assert_eq!(mtext_to_plain(r"a\{b\}c\\d"), r"a{b}c\d");
+        assert_eq!(mtext_to_plain(r"1\~2"), "1 2");
+        assert_eq!(mtext_to_plain(r"\U+00B0C \S1#2; \S3^4;"), "\u{B0}C 1/2 3/4");
+        assert_eq!(mtext_to_plain(r"\U+ZZ"), "U+ZZ");
+        assert_eq!(mtext_to_plain("trailing\\"), "trailing\\");
+        assert_eq!(mtext_to_plain(""), "");
+    }
+}
@@ -4,10 +4,15 @@ use crate::core::result::Result;
 use crate::entities::common::{
     parse_common_entity_handles, parse_common_entity_header, parse_common_entity_header_r14,
     parse_common_entity_header_r2007, parse_common_entity_header_r2010,
-    parse_common_entity_header_r2013, parse_common_entity_layer_handle, CommonEntityHeader,
+    parse_common_entity_header_r2013, parse_common_entity_layer_handle, seek_handle_stream,
+    CommonEntityHeader,
 };
 use std::sync::atomic::{AtomicU32, Ordering};
 
+type Point3 = (f64, f64, f64);
+type PointBody = (Point3, f64, Point3, f64);
+type PointDebugCandidate = (u64, u64, Point3, Point3, f64);
+
 static R14_POINT_PREFERRED_DELTA: AtomicU32 = AtomicU32::new(64);
 
 #[derive(Debug, Clone)]
@@ -15,8 +20,13 @@ pub struct PointEntity {
     pub handle: u64,
     pub color_index: Option<u16>,
     pub true_color: Option<u32>,
+    pub transparency: Option<u32>,
+    pub ltype_scale: f64,
+    pub lineweight: Option<i8>,
     pub layer_handle: u64,
     pub location: (f64, f64, f64),
+    pub thickness: f64,
+    pub extrusion: (f64, f64, f64),
     pub x_axis_angle: f64,
 }
 
@@ -62,7 +72,7 @@ fn decode_point_r14_fallback(
     let debug_enabled = std::env::var("EZDWG_DEBUG_R14_POINT")
         .ok()
         .is_some_and(|value| value != "0");
-    let mut debug_candidates: Vec<(u64, u64, (f64, f64, f64), (f64, f64, f64), f64)> = Vec::new();
+    let mut debug_candidates: Vec<PointDebugCandidate> = Vec::new();
 
     let preferred_delta = R14_POINT_PREFERRED_DELTA.load(Ordering::Relaxed) as i64;
     let preferred_start = preferred_delta.saturating_sub(8).max(0) as u64;
@@ -141,7 +151,7 @@ fn decode_point_r14_fallback(
 
 pub fn decode_point_r2007(reader: &mut BitReader<'_>) -> Result<PointEntity> {
     let header = parse_common_entity_header_r2007(reader)?;
-    decode_point_with_header(reader, header, true, true)
+    decode_point_with_header(reader, header, true, false)
 }
 
 pub fn decode_point_r2010(
@@ -171,11 +181,11 @@ fn decode_point_with_header(
     r2007_layer_only: bool,
 ) -> Result<PointEntity> {
     let location = reader.read_3bd()?;
-    let _thickness = reader.read_bt()?;
-    let _extrusion = reader.read_be()?;
+    let thickness = reader.read_bt()?;
+    let extrusion = reader.read_be()?;
     let x_axis_angle = reader.read_bd()?;
     // Handles are stored in the handle stream at obj_size bit offset.
-    reader.set_bit_pos(header.obj_size);
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
     let layer_handle = match if r2007_layer_only {
         parse_common_entity_layer_handle(reader, &header)
     } else {
@@ -198,20 +208,23 @@ fn decode_point_with_header(
         handle: header.handle,
         color_index: header.color.index,
         true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
         layer_handle,
         location,
+        thickness,
+        extrusion,
         x_axis_angle,
     })
 }
 
-fn parse_point_body_no_common(
-    reader: &mut BitReader<'_>,
-) -> Result<((f64, f64, f64), (f64, f64, f64), f64)> {
+fn parse_point_body_no_common(reader: &mut BitReader<'_>) -> Result<PointBody> {
     let location = reader.read_3bd()?;
-    let _thickness = reader.read_bt()?;
+    let thickness = reader.read_bt()?;
     let extrusion = reader.read_be()?;
     let x_axis_angle = reader.read_bd()?;
-    Ok((location, extrusion, x_axis_angle))
+    Ok((location, thickness, extrusion, x_axis_angle))
 }
 
 fn consider_point_r14_delta(
@@ -220,7 +233,7 @@ fn consider_point_r14_delta(
     delta: u64,
     object_handle: u64,
     best: &mut Option<(u64, u64, PointEntity)>,
-    debug_candidates: Option<&mut Vec<(u64, u64, (f64, f64, f64), (f64, f64, f64), f64)>>,
+    debug_candidates: Option<&mut Vec<PointDebugCandidate>>,
 ) -> Option<PointEntity> {
     let target = base_bit.saturating_add(delta);
     let Ok(target_u32) = u32::try_from(target) else {
@@ -228,12 +241,11 @@ fn consider_point_r14_delta(
     };
     let mut probe = reader.clone();
     probe.set_bit_pos(target_u32);
-    let Ok((location, extrusion, x_axis_angle)) = parse_point_body_no_common(&mut probe) else {
-        return None;
-    };
-    let Some(score) = score_point_candidate(delta, location, extrusion, x_axis_angle) else {
+    let Ok((location, thickness, extrusion, x_axis_angle)) = parse_point_body_no_common(&mut probe)
+    else {
         return None;
     };
+    let score = score_point_candidate(delta, location, extrusion, x_axis_angle)?;
     if let Some(list) = debug_candidates {
         list.push((score, delta, location, extrusion, x_axis_angle));
     }
@@ -242,8 +254,13 @@ fn consider_point_r14_delta(
         handle: object_handle,
         color_index: None,
         true_color: None,
+        transparency: None,
+        ltype_scale: 1.0,
+        lineweight: None,
         layer_handle: 0,
         location,
+        thickness,
+        extrusion,
         x_axis_angle,
     };
 
@@ -287,7 +304,7 @@ fn score_point_candidate(
 
     let ex_norm =
         (extrusion.0 * extrusion.0 + extrusion.1 * extrusion.1 + extrusion.2 * extrusion.2).sqrt();
-    if !ex_norm.is_finite() || ex_norm < 1.0e-9 || ex_norm > 1.0e3 {
+    if !ex_norm.is_finite() || !(1.0e-9..=1.0e3).contains(&ex_norm) {
         return None;
     }
 
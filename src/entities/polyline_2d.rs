@@ -2,7 +2,7 @@ use crate::bit::BitReader;
 use crate::core::result::Result;
 use crate::entities::common::{
     parse_common_entity_handles, parse_common_entity_header, parse_common_entity_header_r14,
-    read_handle_reference, CommonEntityHeader,
+    read_handle_reference, seek_handle_stream, CommonEntityHeader,
 };
 
 #[derive(Debug, Clone, Copy)]
@@ -74,6 +74,9 @@ pub struct Polyline2dEntity {
     pub width_end: f64,
     pub thickness: f64,
     pub elevation: f64,
+    /// Normal of the polyline's OCS; the vertices and `elevation` of a
+    /// plain 2D polyline are in that system.
+    pub extrusion: (f64, f64, f64),
     pub owned_handles: Vec<u64>,
 }
 
@@ -105,13 +108,14 @@ fn decode_polyline_2d_with_header(
     let width_end = reader.read_bd()?;
     let thickness = reader.read_bt()?;
     let elevation = reader.read_bd()?;
-    let _extrusion = reader.read_be()?;
+    let extrusion = reader.read_be()?;
     let owned_obj_count = reader.read_bl()? as usize;
     // Handles are stored in the handle stream at obj_size bit offset.
-    reader.set_bit_pos(header.obj_size);
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
     let _common_handles = parse_common_entity_handles(reader, &header)?;
 
-    let mut owned_handles = Vec::with_capacity(owned_obj_count);
+    let mut owned_handles =
+        Vec::with_capacity(owned_obj_count.min((reader.remaining_bits() / 8) as usize));
     for _ in 0..owned_obj_count {
         owned_handles.push(read_handle_reference(reader, header.handle)?);
     }
@@ -126,6 +130,40 @@ fn decode_polyline_2d_with_header(
         width_end,
         thickness,
         elevation,
+        extrusion,
         owned_handles,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::test_support::Bits;
+
+    #[test]
+    fn keeps_a_tilted_extrusion() {
+        let mut body = Bits::default();
+        body.common_entity_header(0x30, 0b10);
+        body.push(0b10, 2); // flags BS = 0
+        body.push(0b10, 2); // curve type BS = 0
+        body.bd(0.0); // start width
+        body.bd(0.0); // end width
+        body.push(1, 1); // thickness BT = 0
+        body.bd(2.5); // elevation
+        body.push(0, 1); // extrusion BE: explicit 3BD
+        body.bd(1.0);
+        body.bd(0.0);
+        body.bd(0.0);
+        body.push(0b10, 2); // owned objects BL = 0
+
+        let mut handles = Bits::default();
+        handles.handle(5, 0x10); // layer
+        let data = Bits::record(body, handles);
+
+        let polyline = decode_polyline_2d(&mut BitReader::new(&data)).unwrap();
+        assert_eq!(polyline.handle, 0x30);
+        assert_eq!(polyline.elevation, 2.5);
+        assert_eq!(polyline.extrusion, (1.0, 0.0, 0.0));
+        assert!(polyline.owned_handles.is_empty());
+    }
+}
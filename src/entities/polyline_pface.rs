@@ -4,7 +4,8 @@ use crate::core::result::Result;
 use crate::entities::common::{
     parse_common_entity_handles, parse_common_entity_header, parse_common_entity_header_r2007,
     parse_common_entity_header_r2010, parse_common_entity_header_r2013,
-    parse_common_entity_layer_handle, read_handle_reference, CommonEntityHeader,
+    parse_common_entity_layer_handle, read_handle_reference, seek_handle_stream,
+    CommonEntityHeader,
 };
 
 #[derive(Debug, Clone)]
@@ -12,6 +13,9 @@ pub struct PolylinePFaceEntity {
     pub handle: u64,
     pub color_index: Option<u16>,
     pub true_color: Option<u32>,
+    pub transparency: Option<u32>,
+    pub ltype_scale: f64,
+    pub lineweight: Option<i8>,
     pub layer_handle: u64,
     pub num_vertices: u16,
     pub num_faces: u16,
@@ -58,7 +62,7 @@ fn decode_polyline_pface_with_header(
     let owned_obj_count = reader.read_bl()? as usize;
 
     // Handles are stored in the handle stream at obj_size bit offset.
-    reader.set_bit_pos(header.obj_size);
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
     let handles_pos = reader.get_pos();
     let (layer_handle, owned_handles) = match parse_common_entity_handles(reader, &header) {
         Ok(common_handles) => {
@@ -88,6 +92,9 @@ fn decode_polyline_pface_with_header(
         handle: header.handle,
         color_index: header.color.index,
         true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
         layer_handle,
         num_vertices,
         num_faces,
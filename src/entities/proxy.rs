@@ -0,0 +1,224 @@
+use crate::bit::BitReader;
+use crate::core::error::ErrorKind;
+use crate::core::result::Result;
+use crate::entities::common::{
+    parse_common_entity_handles, parse_common_entity_header, parse_common_entity_header_r2007,
+    parse_common_entity_header_r2010, parse_common_entity_header_r2013,
+    parse_common_entity_layer_handle, seek_handle_stream, CommonEntityHeader,
+};
+
+type Point3 = (f64, f64, f64);
+
+/// Proxy-graphics chunk type holding the entity's bounding box.
+const GRAPHICS_EXTENTS: u32 = 1;
+
+/// ACAD_PROXY_ENTITY: an entity saved by an ARX application that is not
+/// loaded. Only what identifies it is decoded; the custom data is skipped.
+#[derive(Debug, Clone)]
+pub struct ProxyEntity {
+    pub handle: u64,
+    pub color_index: Option<u16>,
+    pub true_color: Option<u32>,
+    pub transparency: Option<u32>,
+    pub ltype_scale: f64,
+    pub lineweight: Option<i8>,
+    pub layer_handle: u64,
+    /// Class number of the original object (500+, see the class section).
+    pub class_id: u32,
+    /// DXF name of the original class, filled in from the class map by callers
+    /// that have one.
+    pub class_name: Option<String>,
+    /// Packed DWG version (low 16 bits) and maintenance version (high 16 bits)
+    /// the proxy data was written with.
+    pub object_drawing_format: u32,
+    /// The proxy data was converted from DXF rather than written as DWG.
+    pub from_dxf: bool,
+    /// Byte size of the embedded proxy graphics; 0 when none are stored.
+    pub graphics_size: usize,
+    /// `(min, max)` from the proxy graphics EXTENTS chunk, when present.
+    pub graphics_extents: Option<(Point3, Point3)>,
+}
+
+pub fn decode_proxy(reader: &mut BitReader<'_>) -> Result<ProxyEntity> {
+    let header = parse_common_entity_header(reader)?;
+    decode_proxy_with_header(reader, header, false, false)
+}
+
+pub fn decode_proxy_r2007(reader: &mut BitReader<'_>) -> Result<ProxyEntity> {
+    let header = parse_common_entity_header_r2007(reader)?;
+    decode_proxy_with_header(reader, header, true, false)
+}
+
+pub fn decode_proxy_r2010(
+    reader: &mut BitReader<'_>,
+    object_data_end_bit: u32,
+    object_handle: u64,
+) -> Result<ProxyEntity> {
+    let mut header = parse_common_entity_header_r2010(reader, object_data_end_bit)?;
+    header.handle = object_handle;
+    decode_proxy_with_header(reader, header, true, false)
+}
+
+pub fn decode_proxy_r2013(
+    reader: &mut BitReader<'_>,
+    object_data_end_bit: u32,
+    object_handle: u64,
+) -> Result<ProxyEntity> {
+    let mut header = parse_common_entity_header_r2013(reader, object_data_end_bit)?;
+    header.handle = object_handle;
+    decode_proxy_with_header(reader, header, true, false)
+}
+
+pub fn decode_proxy_r2018(
+    reader: &mut BitReader<'_>,
+    object_data_end_bit: u32,
+    object_handle: u64,
+) -> Result<ProxyEntity> {
+    let mut header = parse_common_entity_header_r2013(reader, object_data_end_bit)?;
+    header.handle = object_handle;
+    decode_proxy_with_header(reader, header, true, true)
+}
+
+fn decode_proxy_with_header(
+    reader: &mut BitReader<'_>,
+    header: CommonEntityHeader,
+    allow_handle_decode_failure: bool,
+    r2018_plus: bool,
+) -> Result<ProxyEntity> {
+    let class_id = reader.read_bl()?;
+    let object_drawing_format = if r2018_plus {
+        let version = reader.read_bl()?;
+        let maintenance_version = reader.read_bl()?;
+        (version & 0xFFFF) | (maintenance_version << 16)
+    } else {
+        reader.read_bl()?
+    };
+    let from_dxf = reader.read_b()? != 0;
+
+    // Handles are stored in the handle stream at obj_size bit offset.
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
+    let handles_pos = reader.get_pos();
+    let layer_handle = match parse_common_entity_handles(reader, &header) {
+        Ok(common_handles) => common_handles.layer,
+        Err(err)
+            if allow_handle_decode_failure
+                && matches!(
+                    err.kind,
+                    ErrorKind::Format | ErrorKind::Decode | ErrorKind::Io
+                ) =>
+        {
+            reader.set_pos(handles_pos.0, handles_pos.1);
+            parse_common_entity_layer_handle(reader, &header).unwrap_or(0)
+        }
+        Err(err) => return Err(err),
+    };
+
+    let (graphics_size, graphics_extents) = match header.graphic {
+        Some(graphic) => {
+            reader.set_pos(graphic.start.0, graphic.start.1);
+            let data = reader.read_rcs(graphic.size)?;
+            (graphic.size, proxy_graphics_extents(&data))
+        }
+        None => (0, None),
+    };
+
+    Ok(ProxyEntity {
+        handle: header.handle,
+        color_index: header.color.index,
+        true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
+        layer_handle,
+        class_id,
+        class_name: None,
+        object_drawing_format,
+        from_dxf,
+        graphics_size,
+        graphics_extents,
+    })
+}
+
+/// Scan proxy graphics (`RL total size, RL chunk count`, then chunks of
+/// `RL size, RL type, data`) for the EXTENTS chunk.
+pub fn proxy_graphics_extents(data: &[u8]) -> Option<(Point3, Point3)> {
+    let read_u32 = |pos: usize| -> Option<u32> {
+        let bytes = data.get(pos..pos + 4)?;
+        Some(u32::from_le_bytes(bytes.try_into().ok()?))
+    };
+    let read_point = |pos: usize| -> Option<Point3> {
+        let coord = |index: usize| -> Option<f64> {
+            let at = pos + index * 8;
+            let bytes = data.get(at..at + 8)?;
+            Some(f64::from_le_bytes(bytes.try_into().ok()?))
+        };
+        Some((coord(0)?, coord(1)?, coord(2)?))
+    };
+
+    let chunk_count = read_u32(4)?;
+    let mut pos = 8usize;
+    for _ in 0..chunk_count {
+        let size = read_u32(pos)? as usize;
+        let kind = read_u32(pos + 4)?;
+        if kind == GRAPHICS_EXTENTS {
+            return Some((read_point(pos + 8)?, read_point(pos + 32)?));
+        }
+        // A chunk never shrinks below its own size/type words.
+        if size < 8 {
+            return None;
+        }
+        pos = pos.checked_add(size)?;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::proxy_graphics_extents;
+
+    fn chunk(kind: u32, payload: &[u8]) -> Vec<u8> {
+        let mut out = ((payload.len() + 8) as u32).to_le_bytes().to_vec();
+        out.extend_from_slice(&kind.to_le_bytes());
+        out.extend_from_slice(payload);
+        out
+    }
+
+    fn graphics(chunks: &[Vec<u8>]) -> Vec<u8> {
+        let body: Vec<u8> = chunks.concat();
+        let mut out = ((body.len() + 8) as u32).to_le_bytes().to_vec();
+        out.extend_from_slice(&(chunks.len() as u32).to_le_bytes());
+        out.extend(body);
+        out
+    }
+
+    #[test]
+    fn finds_extents_after_other_chunks() {
+        let extents: Vec<u8> = [1.0f64, 2.0, 0.0, 4.0, 6.0, 1.5]
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect();
+        // A CIRCLE chunk (type 2) precedes the extents.
+        let data = graphics(&[chunk(2, &[0u8; 36]), chunk(1, &extents)]);
+        assert_eq!(
+            proxy_graphics_extents(&data),
+            Some(((1.0, 2.0, 0.0), (4.0, 6.0, 1.5)))
+        );
+    }
+
+    #[test]
+    fn missing_or_truncated_extents_yield_none() {
+        assert_eq!(
+            proxy_graphics_extents(&graphics(&[chunk(2, &[0; 36])])),
+            None
+        );
+        assert_eq!(
+            proxy_graphics_extents(&graphics(&[chunk(1, &[0; 20])])),
+            None
+        );
+        assert_eq!(
+            proxy_graphics_extents(&graphics(&[chunk(2, &[])[..4].to_vec()])),
+            None
+        );
+        assert_eq!(proxy_graphics_extents(&[]), None);
+    }
+}
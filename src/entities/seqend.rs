@@ -1,15 +1,25 @@
 use crate::bit::BitReader;
 use crate::core::result::Result;
-use crate::entities::common::parse_common_entity_header;
+use crate::entities::common::{
+    parse_common_entity_handles, parse_common_entity_header, seek_handle_stream,
+};
 
 #[derive(Debug, Clone)]
 pub struct SeqendEntity {
     pub handle: u64,
+    pub owner_handle: Option<u64>,
 }
 
 pub fn decode_seqend(reader: &mut BitReader<'_>) -> Result<SeqendEntity> {
     let header = parse_common_entity_header(reader)?;
+    // SEQEND has no body; the owner reference is the only useful payload, so a
+    // damaged handle stream leaves it unset instead of failing the record.
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
+    let owner_handle = parse_common_entity_handles(reader, &header)
+        .ok()
+        .and_then(|handles| handles.owner_ref);
     Ok(SeqendEntity {
         handle: header.handle,
+        owner_handle,
     })
 }
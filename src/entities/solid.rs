@@ -4,7 +4,7 @@ use crate::core::result::Result;
 use crate::entities::common::{
     parse_common_entity_handles, parse_common_entity_header, parse_common_entity_header_r2007,
     parse_common_entity_header_r2010, parse_common_entity_header_r2013,
-    parse_common_entity_layer_handle, CommonEntityHeader,
+    parse_common_entity_layer_handle, seek_handle_stream, CommonEntityHeader,
 };
 
 #[derive(Debug, Clone)]
@@ -12,6 +12,9 @@ pub struct SolidEntity {
     pub handle: u64,
     pub color_index: Option<u16>,
     pub true_color: Option<u32>,
+    pub transparency: Option<u32>,
+    pub ltype_scale: f64,
+    pub lineweight: Option<i8>,
     pub layer_handle: u64,
     pub p1: (f64, f64, f64),
     pub p2: (f64, f64, f64),
@@ -28,7 +31,7 @@ pub fn decode_solid(reader: &mut BitReader<'_>) -> Result<SolidEntity> {
 
 pub fn decode_solid_r2007(reader: &mut BitReader<'_>) -> Result<SolidEntity> {
     let header = parse_common_entity_header_r2007(reader)?;
-    decode_solid_with_header(reader, header, true, true)
+    decode_solid_with_header(reader, header, true, false)
 }
 
 pub fn decode_solid_r2010(
@@ -73,7 +76,7 @@ fn decode_solid_with_header(
     let p4 = (c4.0, c4.1, elevation);
 
     // Handles are stored in the handle stream at obj_size bit offset.
-    reader.set_bit_pos(header.obj_size);
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
     let layer_handle = match if r2007_layer_only {
         parse_common_entity_layer_handle(reader, &header)
     } else {
@@ -96,6 +99,9 @@ fn decode_solid_with_header(
         handle: header.handle,
         color_index: header.color.index,
         true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
         layer_handle,
         p1,
         p2,
@@ -1,10 +1,11 @@
 use crate::bit::BitReader;
 use crate::core::error::{DwgError, ErrorKind};
+use crate::core::geometry::points_equal_3d;
 use crate::core::result::Result;
 use crate::entities::common::{
     parse_common_entity_handles, parse_common_entity_header, parse_common_entity_header_r2007,
     parse_common_entity_header_r2010, parse_common_entity_header_r2013,
-    parse_common_entity_layer_handle, CommonEntityHeader,
+    parse_common_entity_layer_handle, seek_handle_stream, CommonEntityHeader,
 };
 
 type Point3 = (f64, f64, f64);
@@ -15,6 +16,9 @@ pub struct SplineEntity {
     pub handle: u64,
     pub color_index: Option<u16>,
     pub true_color: Option<u32>,
+    pub transparency: Option<u32>,
+    pub ltype_scale: f64,
+    pub lineweight: Option<i8>,
     pub layer_handle: u64,
     pub scenario: u32,
     pub spline_flags1: Option<u32>,
@@ -67,7 +71,7 @@ pub fn decode_spline(reader: &mut BitReader<'_>) -> Result<SplineEntity> {
 
 pub fn decode_spline_r2007(reader: &mut BitReader<'_>) -> Result<SplineEntity> {
     let header = parse_common_entity_header_r2007(reader)?;
-    decode_spline_with_header(reader, header, true, true, false)
+    decode_spline_with_header(reader, header, true, false, false)
 }
 
 pub fn decode_spline_r2010(
@@ -156,7 +160,7 @@ fn decode_spline_with_header(
     };
 
     // Handles are stored in the handle stream at obj_size bit offset.
-    reader.set_bit_pos(header.obj_size);
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
     let layer_handle = match if r2007_layer_only {
         parse_common_entity_layer_handle(reader, &header)
     } else {
@@ -179,6 +183,9 @@ fn decode_spline_with_header(
         handle: header.handle,
         color_index: header.color.index,
         true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
         layer_handle,
         scenario: data.scenario,
         spline_flags1: data.spline_flags1,
@@ -319,10 +326,97 @@ fn bounded_count(raw: u32, label: &str) -> Result<usize> {
     Ok(count)
 }
 
+/// Evaluate a SPLINE into a polyline: control-point splines through the
+/// (rational) B-spline basis with `samples_per_segment` points per non-empty
+/// knot span, fit-point splines through a Catmull-Rom curve over the fit
+/// points (see [`catmull_rom_spline`] for `point_epsilon`). Falls back to
+/// the raw control points when the knot vector does not match the control
+/// point count.
+pub fn sample_spline(
+    entity: &SplineEntity,
+    samples_per_segment: usize,
+    point_epsilon: f64,
+) -> Result<Vec<Point3>> {
+    let samples = samples_per_segment.max(1);
+    if entity.control_points.is_empty() && entity.fit_points.len() >= 2 {
+        return catmull_rom_spline(&entity.fit_points, entity.closed, samples, point_epsilon);
+    }
+
+    let degree = entity.degree as usize;
+    let count = entity.control_points.len();
+    if degree == 0 || count <= degree || entity.knots.len() != count + degree + 1 {
+        return Ok(entity.control_points.clone());
+    }
+    let weights = if entity.rational && entity.weights.len() == count {
+        Some(entity.weights.as_slice())
+    } else {
+        None
+    };
+
+    let mut out = Vec::new();
+    let mut last_span = None;
+    for span in degree..count {
+        let start = entity.knots[span];
+        let end = entity.knots[span + 1];
+        if end <= start {
+            continue;
+        }
+        for step in 0..samples {
+            let u = start + (end - start) * (step as f64 / samples as f64);
+            out.push(de_boor(entity, weights, span, u));
+        }
+        last_span = Some(span);
+    }
+    match last_span {
+        Some(span) => out.push(de_boor(entity, weights, span, entity.knots[span + 1])),
+        None => return Ok(entity.control_points.clone()),
+    }
+    Ok(out)
+}
+
+fn de_boor(entity: &SplineEntity, weights: Option<&[f64]>, span: usize, u: f64) -> Point3 {
+    let degree = entity.degree as usize;
+    let knots = &entity.knots;
+    // Homogeneous coordinates so rational splines use the same recurrence.
+    let mut d: Vec<[f64; 4]> = (0..=degree)
+        .map(|j| {
+            let index = j + span - degree;
+            let (x, y, z) = entity.control_points[index];
+            let w = weights.map_or(1.0, |weights| weights[index]);
+            [x * w, y * w, z * w, w]
+        })
+        .collect();
+    for r in 1..=degree {
+        for j in (r..=degree).rev() {
+            let left = knots[j + span - degree];
+            let right = knots[j + 1 + span - r];
+            let alpha = if right > left {
+                (u - left) / (right - left)
+            } else {
+                0.0
+            };
+            let prev = d[j - 1];
+            for (value, prev) in d[j].iter_mut().zip(prev) {
+                *value = (1.0 - alpha) * prev + alpha * *value;
+            }
+        }
+    }
+    let [x, y, z, w] = d[degree];
+    if w.abs() < 1e-12 {
+        (x, y, z)
+    } else {
+        (x / w, y / w, z / w)
+    }
+}
+
+/// Centripetal Catmull-Rom curve through `points`. Closed curves end on
+/// their first point, which is appended unless the last one already lies
+/// within `point_epsilon` of it.
 pub fn catmull_rom_spline(
     points: &[Point3],
     closed: bool,
     segments_per_span: usize,
+    point_epsilon: f64,
 ) -> Result<Vec<Point3>> {
     if points.len() < 2 {
         return Ok(points.to_vec());
@@ -373,7 +467,7 @@ pub fn catmull_rom_spline(
     if closed && !out.is_empty() {
         let first = out[0];
         let last = *out.last().unwrap();
-        if !points_equal(first, last) {
+        if !points_equal_3d(first, last, point_epsilon) {
             out.push(first);
         }
     }
@@ -419,7 +513,112 @@ fn lerp(p0: Point3, p1: Point3, t0: f64, t1: f64, t: f64) -> Point3 {
     )
 }
 
-fn points_equal(a: Point3, b: Point3) -> bool {
-    const EPS: f64 = 1e-9;
-    (a.0 - b.0).abs() < EPS && (a.1 - b.1).abs() < EPS && (a.2 - b.2).abs() < EPS
+#[cfg(test)]
+mod tests {
+    use super::{sample_spline, SplineEntity};
+    use crate::core::geometry::DEFAULT_POINT_EPSILON;
+
+    fn spline(degree: u32, knots: Vec<f64>, control_points: Vec<(f64, f64, f64)>) -> SplineEntity {
+        SplineEntity {
+            handle: 1,
+            color_index: None,
+            true_color: None,
+            transparency: None,
+            ltype_scale: 1.0,
+            lineweight: None,
+            layer_handle: 0,
+            scenario: 1,
+            spline_flags1: None,
+            knot_parameter: None,
+            degree,
+            rational: false,
+            closed: false,
+            periodic: false,
+            fit_tolerance: None,
+            knot_tolerance: None,
+            ctrl_tolerance: None,
+            start_tangent: None,
+            end_tangent: None,
+            knots,
+            control_points,
+            weights: Vec::new(),
+            fit_points: Vec::new(),
+        }
+    }
+
+    fn assert_close(a: (f64, f64, f64), b: (f64, f64, f64)) {
+        assert!(
+            (a.0 - b.0).abs() < 1e-9 && (a.1 - b.1).abs() < 1e-9 && (a.2 - b.2).abs() < 1e-9,
+            "{a:?} != {b:?}"
+        );
+    }
+
+    #[test]
+    fn samples_clamped_cubic_as_bezier() {
+        let entity = spline(
+            3,
+            vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0],
+            vec![
+                (0.0, 0.0, 0.0),
+                (1.0, 2.0, 0.0),
+                (3.0, 2.0, 0.0),
+                (4.0, 0.0, 0.0),
+            ],
+        );
+        let points = sample_spline(&entity, 4, DEFAULT_POINT_EPSILON).unwrap();
+        assert_eq!(points.len(), 5);
+        assert_close(points[0], (0.0, 0.0, 0.0));
+        assert_close(points[2], (2.0, 1.5, 0.0));
+        assert_close(points[4], (4.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn samples_rational_quarter_circle() {
+        let mut entity = spline(
+            2,
+            vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
+            vec![(1.0, 0.0, 0.0), (1.0, 1.0, 0.0), (0.0, 1.0, 0.0)],
+        );
+        entity.rational = true;
+        entity.weights = vec![1.0, std::f64::consts::FRAC_1_SQRT_2, 1.0];
+        for (x, y, _) in sample_spline(&entity, 8, DEFAULT_POINT_EPSILON).unwrap() {
+            assert!((x.hypot(y) - 1.0).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn skips_repeated_knots_and_falls_back_on_bad_knot_vectors() {
+        let control_points = vec![
+            (0.0, 0.0, 0.0),
+            (1.0, 1.0, 0.0),
+            (2.0, 0.0, 0.0),
+            (3.0, 1.0, 0.0),
+        ];
+        // Two linear spans with a doubled interior knot (one empty span).
+        let entity = spline(
+            1,
+            vec![0.0, 0.0, 1.0, 1.0, 2.0, 2.0],
+            control_points.clone(),
+        );
+        let points = sample_spline(&entity, 2, DEFAULT_POINT_EPSILON).unwrap();
+        assert_eq!(points.len(), 5);
+        assert_close(points[4], (3.0, 1.0, 0.0));
+
+        let entity = spline(3, vec![0.0, 1.0], control_points.clone());
+        assert_eq!(
+            sample_spline(&entity, 4, DEFAULT_POINT_EPSILON).unwrap(),
+            control_points
+        );
+    }
+
+    #[test]
+    fn samples_fit_points_through_each_point() {
+        let mut entity = spline(3, Vec::new(), Vec::new());
+        entity.fit_points = vec![(0.0, 0.0, 0.0), (1.0, 1.0, 0.0), (2.0, 0.0, 0.0)];
+        let points = sample_spline(&entity, 4, DEFAULT_POINT_EPSILON).unwrap();
+        assert_eq!(points.len(), 9);
+        assert_close(points[0], (0.0, 0.0, 0.0));
+        assert_close(points[4], (1.0, 1.0, 0.0));
+        assert_close(points[8], (2.0, 0.0, 0.0));
+    }
 }
@@ -0,0 +1,723 @@
+use crate::bit::{BitReader, Endian};
+use crate::core::error::{DwgError, ErrorKind};
+use crate::core::result::Result;
+use crate::entities::common::{
+    parse_common_entity_handles, parse_common_entity_header, parse_common_entity_header_r2010,
+    parse_common_entity_header_r2013, parse_common_entity_layer_handle, seek_handle_stream,
+    string_stream_reader, CommonEntityHeader,
+};
+
+type Point3 = (f64, f64, f64);
+
+const MAX_TABLE_CELLS: usize = 1_000_000;
+const MAX_TABLE_ITEMS: usize = 100_000;
+
+/// ACAD_TABLE: an anonymous-block reference plus the cell grid. Up to R2004
+/// the grid follows the insert data directly; R2010+ embed a TABLECONTENT.
+/// Per-table style overrides are not decoded.
+#[derive(Debug, Clone)]
+pub struct TableEntity {
+    pub handle: u64,
+    pub color_index: Option<u16>,
+    pub true_color: Option<u32>,
+    pub transparency: Option<u32>,
+    pub ltype_scale: f64,
+    pub lineweight: Option<i8>,
+    pub layer_handle: u64,
+    pub insertion: Point3,
+    pub scale: Point3,
+    pub rotation: f64,
+    pub extrusion: Point3,
+    /// Direction of the table rows (the table's X axis) in WCS.
+    pub horizontal_direction: Point3,
+    pub row_heights: Vec<f64>,
+    pub column_widths: Vec<f64>,
+    /// Row-major, `row_heights.len() * column_widths.len()` cells.
+    pub cells: Vec<TableCell>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableCell {
+    /// 1 text, 2 block.
+    pub cell_type: u16,
+    pub flags: u8,
+    pub merged: bool,
+    pub rotation: f64,
+    /// Raw MTEXT string of a text cell.
+    pub text: Option<String>,
+}
+
+impl TableEntity {
+    pub fn row_count(&self) -> usize {
+        self.row_heights.len()
+    }
+
+    pub fn column_count(&self) -> usize {
+        self.column_widths.len()
+    }
+
+    /// Cell strings as rows of columns; block and empty cells give "".
+    pub fn text_grid(&self) -> Vec<Vec<String>> {
+        let columns = self.column_count().max(1);
+        self.cells
+            .chunks(columns)
+            .map(|row| {
+                row.iter()
+                    .map(|cell| cell.text.clone().unwrap_or_default())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+pub fn decode_table(reader: &mut BitReader<'_>) -> Result<TableEntity> {
+    let header = parse_common_entity_header(reader)?;
+    decode_table_with_header(reader, header, false)
+}
+
+pub fn decode_table_r2004(reader: &mut BitReader<'_>) -> Result<TableEntity> {
+    let header = parse_common_entity_header(reader)?;
+    decode_table_with_header(reader, header, true)
+}
+
+pub fn decode_table_r2010(
+    reader: &mut BitReader<'_>,
+    object_data_end_bit: u32,
+    object_handle: u64,
+) -> Result<TableEntity> {
+    let mut header = parse_common_entity_header_r2010(reader, object_data_end_bit)?;
+    header.handle = object_handle;
+    let mut strings = string_stream_reader(reader, object_data_end_bit)?;
+    decode_table_content_with_header(reader, header, &mut strings, false)
+}
+
+pub fn decode_table_r2013(
+    reader: &mut BitReader<'_>,
+    object_data_end_bit: u32,
+    object_handle: u64,
+) -> Result<TableEntity> {
+    let mut header = parse_common_entity_header_r2013(reader, object_data_end_bit)?;
+    header.handle = object_handle;
+    let mut strings = string_stream_reader(reader, object_data_end_bit)?;
+    decode_table_content_with_header(reader, header, &mut strings, true)
+}
+
+struct InsertPlacement {
+    insertion: Point3,
+    scale: Point3,
+    rotation: f64,
+    extrusion: Point3,
+}
+
+/// The INSERT fields every ACAD_TABLE starts with (R2004+ ATTRIB layout).
+fn read_insert_placement(reader: &mut BitReader<'_>, r2004_plus: bool) -> Result<InsertPlacement> {
+    let insertion = reader.read_3bd()?;
+    let scale = match reader.read_bb()? {
+        0x03 => (1.0, 1.0, 1.0),
+        0x01 => {
+            let y = reader.read_dd(1.0)?;
+            let z = reader.read_dd(1.0)?;
+            (1.0, y, z)
+        }
+        0x02 => {
+            let x = reader.read_rd(Endian::Little)?;
+            (x, x, x)
+        }
+        _ => {
+            let x = reader.read_rd(Endian::Little)?;
+            let y = reader.read_dd(x)?;
+            let z = reader.read_dd(x)?;
+            (x, y, z)
+        }
+    };
+    let rotation = reader.read_bd()?;
+    let extrusion = reader.read_3bd()?;
+    let has_attribs = reader.read_b()? != 0;
+    if r2004_plus && has_attribs {
+        let _owned_count = reader.read_bl()?;
+    }
+    Ok(InsertPlacement {
+        insertion,
+        scale,
+        rotation,
+        extrusion,
+    })
+}
+
+fn decode_table_with_header(
+    reader: &mut BitReader<'_>,
+    header: CommonEntityHeader,
+    r2004_plus: bool,
+) -> Result<TableEntity> {
+    let InsertPlacement {
+        insertion,
+        scale,
+        rotation,
+        extrusion,
+    } = read_insert_placement(reader, r2004_plus)?;
+
+    let _table_value_flags = reader.read_bs()?;
+    let horizontal_direction = reader.read_3bd()?;
+    let column_count = reader.read_bl()? as usize;
+    let row_count = reader.read_bl()? as usize;
+    let cell_count = column_count
+        .checked_mul(row_count)
+        .filter(|count| *count <= MAX_TABLE_CELLS)
+        .ok_or_else(|| {
+            DwgError::new(
+                ErrorKind::Format,
+                format!(
+                    "invalid ACAD_TABLE size: {} x {} (max {} cells)",
+                    row_count, column_count, MAX_TABLE_CELLS
+                ),
+            )
+        })?;
+    let mut column_widths = Vec::with_capacity(column_count);
+    for _ in 0..column_count {
+        column_widths.push(reader.read_bd()?);
+    }
+    let mut row_heights = Vec::with_capacity(row_count);
+    for _ in 0..row_count {
+        row_heights.push(reader.read_bd()?);
+    }
+    let mut cells = Vec::with_capacity(cell_count.min((reader.remaining_bits() / 16) as usize));
+    for _ in 0..cell_count {
+        cells.push(read_cell(reader)?);
+    }
+
+    let layer_handle = read_layer_handle(reader, &header)?;
+
+    Ok(TableEntity {
+        handle: header.handle,
+        color_index: header.color.index,
+        true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
+        layer_handle,
+        insertion,
+        scale,
+        rotation,
+        extrusion,
+        horizontal_direction,
+        row_heights,
+        column_widths,
+        cells,
+    })
+}
+
+fn read_layer_handle(reader: &mut BitReader<'_>, header: &CommonEntityHeader) -> Result<u64> {
+    // Handles are stored in the handle stream at obj_size bit offset.
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
+    let handles_pos = reader.get_pos();
+    match parse_common_entity_handles(reader, header) {
+        Ok(common_handles) => Ok(common_handles.layer),
+        Err(err)
+            if matches!(
+                err.kind,
+                ErrorKind::Format | ErrorKind::Decode | ErrorKind::Io
+            ) =>
+        {
+            reader.set_pos(handles_pos.0, handles_pos.1);
+            Ok(parse_common_entity_layer_handle(reader, header).unwrap_or(0))
+        }
+        Err(err) => Err(err),
+    }
+}
+
+fn read_cell(reader: &mut BitReader<'_>) -> Result<TableCell> {
+    let cell_type = reader.read_bs()?;
+    let flags = reader.read_rc()?;
+    let merged = reader.read_b()? != 0;
+    let _autofit = reader.read_b()?;
+    let _merged_width = reader.read_bl()?;
+    let _merged_height = reader.read_bl()?;
+    let rotation = reader.read_bd()?;
+
+    let mut text = None;
+    match cell_type {
+        1 => text = Some(reader.read_tv()?),
+        2 => {
+            let _block_scale = reader.read_bd()?;
+            if reader.read_b()? != 0 {
+                let _attdef_count = reader.read_bs()?;
+                let _attdef_index = reader.read_bs()?;
+                let _attdef_text = reader.read_tv()?;
+            }
+        }
+        _ => {}
+    }
+    if matches!(cell_type, 1 | 2) && reader.read_b()? != 0 {
+        skip_cell_overrides(reader)?;
+    }
+
+    Ok(TableCell {
+        cell_type,
+        flags,
+        merged,
+        rotation,
+        text,
+    })
+}
+
+/// Per-cell overrides selected by the BL override mask.
+fn skip_cell_overrides(reader: &mut BitReader<'_>) -> Result<()> {
+    let overrides = reader.read_bl()?;
+    let _virtual_edge = reader.read_rc()?;
+    if overrides & 0x01 != 0 {
+        let _alignment = reader.read_rs(Endian::Little)?;
+    }
+    if overrides & 0x02 != 0 {
+        let _background_fill_none = reader.read_b()?;
+    }
+    if overrides & 0x04 != 0 {
+        skip_cmc(reader)?;
+    }
+    if overrides & 0x08 != 0 {
+        skip_cmc(reader)?;
+    }
+    // 0x10 (text style) is a handle in the handle stream.
+    if overrides & 0x20 != 0 {
+        let _text_height = reader.read_bd()?;
+    }
+    // Top, right, bottom, left borders: color, lineweight, visibility.
+    for edge in 0..4 {
+        if overrides & (0x40 << edge) != 0 {
+            skip_cmc(reader)?;
+        }
+        if overrides & (0x400 << edge) != 0 {
+            let _lineweight = reader.read_bs()?;
+        }
+        if overrides & (0x4000 << edge) != 0 {
+            let _visibility = reader.read_bs()?;
+        }
+    }
+    Ok(())
+}
+
+/// R2004 CMC color: BS index, BL RGB, RC name flags, then the names.
+fn skip_cmc(reader: &mut BitReader<'_>) -> Result<()> {
+    let _index = reader.read_bs()?;
+    let _rgb = reader.read_bl()?;
+    let flags = reader.read_rc()?;
+    if flags & 0x01 != 0 {
+        let _color_name = reader.read_tv()?;
+    }
+    if flags & 0x02 != 0 {
+        let _book_name = reader.read_tv()?;
+    }
+    Ok(())
+}
+
+fn decode_table_content_with_header(
+    reader: &mut BitReader<'_>,
+    header: CommonEntityHeader,
+    strings: &mut Option<BitReader<'_>>,
+    r2013_plus: bool,
+) -> Result<TableEntity> {
+    let InsertPlacement {
+        insertion,
+        scale,
+        rotation,
+        extrusion,
+    } = read_insert_placement(reader, true)?;
+    let _unknown_rc = reader.read_rc()?;
+    let _unknown_bl = reader.read_bl()?;
+    if r2013_plus {
+        let _unknown = reader.read_bl()?;
+    } else {
+        let _unknown = reader.read_b()?;
+    }
+
+    let content = read_table_content(reader, strings)?;
+    let layer_handle = read_layer_handle(reader, &header)?;
+
+    Ok(TableEntity {
+        handle: header.handle,
+        color_index: header.color.index,
+        true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
+        layer_handle,
+        insertion,
+        scale,
+        rotation,
+        extrusion,
+        // R2010+ no longer store the direction; rows run along the rotated X axis.
+        horizontal_direction: (rotation.cos(), rotation.sin(), 0.0),
+        row_heights: content.row_heights,
+        column_widths: content.column_widths,
+        cells: content.cells,
+    })
+}
+
+struct TableContent {
+    row_heights: Vec<f64>,
+    column_widths: Vec<f64>,
+    cells: Vec<TableCell>,
+}
+
+/// R2010+ TABLECONTENT: columns, rows of cells, then the merged ranges.
+/// Strings come from the object's string stream.
+fn read_table_content(
+    reader: &mut BitReader<'_>,
+    strings: &mut Option<BitReader<'_>>,
+) -> Result<TableContent> {
+    let _name = read_text(strings)?;
+    let _description = read_text(strings)?;
+
+    let column_count = read_count(reader, "column count")?;
+    let mut column_widths = Vec::with_capacity(column_count);
+    for _ in 0..column_count {
+        let _name = read_text(strings)?;
+        let _custom_data = reader.read_bl()?;
+        skip_custom_data_items(reader, strings)?;
+        skip_cell_style(reader, strings)?;
+        let _style_id = reader.read_bl()?;
+        column_widths.push(reader.read_bd()?);
+    }
+
+    let row_count = read_count(reader, "row count")?;
+    let mut row_heights = Vec::with_capacity(row_count);
+    let mut cells = Vec::new();
+    for _ in 0..row_count {
+        let cell_count = read_count(reader, "cell count")?;
+        if cell_count != column_count || cells.len() + cell_count > MAX_TABLE_CELLS {
+            return Err(DwgError::new(
+                ErrorKind::Format,
+                format!(
+                    "invalid ACAD_TABLE row: {} cells for {} columns",
+                    cell_count, column_count
+                ),
+            ));
+        }
+        for _ in 0..cell_count {
+            cells.push(read_content_cell(reader, strings)?);
+        }
+        let _custom_data = reader.read_bl()?;
+        skip_custom_data_items(reader, strings)?;
+        skip_cell_style(reader, strings)?;
+        let _style_id = reader.read_bl()?;
+        row_heights.push(reader.read_bd()?);
+    }
+
+    // Field references are handles; only their count is in the data stream.
+    let _field_ref_count = reader.read_bl()?;
+    skip_cell_style(reader, strings)?;
+    let merged_count = read_count(reader, "merged range count")?;
+    for _ in 0..merged_count {
+        let top = reader.read_bl()? as usize;
+        let left = reader.read_bl()? as usize;
+        let bottom = reader.read_bl()? as usize;
+        let right = reader.read_bl()? as usize;
+        for row in top..=bottom.min(row_count.saturating_sub(1)) {
+            for column in left..=right.min(column_count.saturating_sub(1)) {
+                cells[row * column_count + column].merged = true;
+            }
+        }
+    }
+
+    Ok(TableContent {
+        row_heights,
+        column_widths,
+        cells,
+    })
+}
+
+fn read_content_cell(
+    reader: &mut BitReader<'_>,
+    strings: &mut Option<BitReader<'_>>,
+) -> Result<TableCell> {
+    let flags = reader.read_bl()?;
+    let _tooltip = read_text(strings)?;
+    let _custom_data = reader.read_bl()?;
+    skip_custom_data_items(reader, strings)?;
+    if reader.read_bl()? != 0 {
+        // Linked data: the data link handle, then the linked range.
+        let _row_count = reader.read_bl()?;
+        let _column_count = reader.read_bl()?;
+        let _unknown = reader.read_bl()?;
+    }
+
+    let mut cell_type = 1;
+    let mut rotation = 0.0;
+    let mut text = None;
+    for _ in 0..read_count(reader, "cell content count")? {
+        // 1 value, 2 field, 4 block; fields and blocks are handles.
+        let content_type = reader.read_bl()?;
+        match content_type {
+            1 => text = read_value(reader, strings)?.or(text),
+            4 => cell_type = 2,
+            _ => {}
+        }
+        for _ in 0..read_count(reader, "cell attribute count")? {
+            let _value = read_text(strings)?;
+            let _index = reader.read_bl()?;
+        }
+        if reader.read_bs()? != 0 {
+            rotation = read_content_format(reader, strings)?;
+        }
+    }
+
+    skip_cell_style(reader, strings)?;
+    let _style_id = reader.read_bl()?;
+    if reader.read_bl()? != 0 {
+        let _geometry_flags = reader.read_bl()?;
+        let _width_with_gap = reader.read_bd()?;
+        let _height_with_gap = reader.read_bd()?;
+        for _ in 0..read_count(reader, "cell geometry count")? {
+            let _distance_top_left = reader.read_3bd()?;
+            let _distance_center = reader.read_3bd()?;
+            let _content_width = reader.read_bd()?;
+            let _content_height = reader.read_bd()?;
+            let _width = reader.read_bd()?;
+            let _height = reader.read_bd()?;
+            let _unknown = reader.read_bl()?;
+        }
+    }
+
+    Ok(TableCell {
+        cell_type,
+        flags: flags as u8,
+        merged: false,
+        rotation,
+        text,
+    })
+}
+
+/// A cell value. String values return their text, other types the display
+/// string when one is stored.
+fn read_value(
+    reader: &mut BitReader<'_>,
+    strings: &mut Option<BitReader<'_>>,
+) -> Result<Option<String>> {
+    let flags = reader.read_bl()?;
+    let data_type = reader.read_bl()?;
+    let mut text = None;
+    if flags & 0x01 == 0 {
+        match data_type {
+            0 | 1 => {
+                let _value = reader.read_bl()?;
+            }
+            2 => {
+                let _value = reader.read_bd()?;
+            }
+            // String (UTF-16), date, 2D and 3D point: a byte count, then the bytes.
+            4 | 8 | 16 | 32 => {
+                let size = read_count(reader, "value size")?;
+                let bytes = reader.read_rcs(size)?;
+                if data_type == 4 {
+                    let units: Vec<u16> = bytes
+                        .chunks_exact(2)
+                        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                        .collect();
+                    let value = String::from_utf16_lossy(&units);
+                    text = Some(value.trim_end_matches('\0').to_string());
+                }
+            }
+            // Object id: a handle.
+            64 => {}
+            other => {
+                return Err(DwgError::new(
+                    ErrorKind::Format,
+                    format!("unsupported ACAD_TABLE value type: {}", other),
+                ))
+            }
+        }
+    }
+    let _unit_type = reader.read_bl()?;
+    let _format = read_text(strings)?;
+    let display = read_text(strings)?;
+    Ok(text.or(Some(display).filter(|value| !value.is_empty())))
+}
+
+/// Returns the content rotation.
+fn read_content_format(
+    reader: &mut BitReader<'_>,
+    strings: &mut Option<BitReader<'_>>,
+) -> Result<f64> {
+    let _override_flags = reader.read_bl()?;
+    let _property_flags = reader.read_bl()?;
+    let _data_type = reader.read_bl()?;
+    let _unit_type = reader.read_bl()?;
+    let _format = read_text(strings)?;
+    let rotation = reader.read_bd()?;
+    let _block_scale = reader.read_bd()?;
+    let _alignment = reader.read_bl()?;
+    skip_cmc_r2007(reader, strings)?;
+    // The text style is a handle.
+    let _text_height = reader.read_bd()?;
+    Ok(rotation)
+}
+
+fn skip_cell_style(reader: &mut BitReader<'_>, strings: &mut Option<BitReader<'_>>) -> Result<()> {
+    let _style_type = reader.read_bl()?;
+    if reader.read_bs()? == 0 {
+        return Ok(());
+    }
+    let _override_flags = reader.read_bl()?;
+    let _merge_flags = reader.read_bl()?;
+    skip_cmc_r2007(reader, strings)?;
+    let _content_layout = reader.read_bl()?;
+    read_content_format(reader, strings)?;
+    if reader.read_bs()? & 0x01 != 0 {
+        // Vertical, horizontal, bottom, right margins and the two spacings.
+        for _ in 0..6 {
+            let _margin = reader.read_bd()?;
+        }
+    }
+    for _ in 0..read_count(reader, "cell border count")? {
+        if reader.read_bl()? == 0 {
+            continue;
+        }
+        let _override_flags = reader.read_bl()?;
+        let _border_type = reader.read_bl()?;
+        skip_cmc_r2007(reader, strings)?;
+        let _lineweight = reader.read_bl()?;
+        // The linetype is a handle.
+        let _visibility = reader.read_bl()?;
+        let _double_line_spacing = reader.read_bd()?;
+    }
+    Ok(())
+}
+
+fn skip_custom_data_items(
+    reader: &mut BitReader<'_>,
+    strings: &mut Option<BitReader<'_>>,
+) -> Result<()> {
+    for _ in 0..read_count(reader, "custom data count")? {
+        let _name = read_text(strings)?;
+        read_value(reader, strings)?;
+    }
+    Ok(())
+}
+
+/// R2007+ CMC: as [`skip_cmc`], with the names in the string stream.
+fn skip_cmc_r2007(reader: &mut BitReader<'_>, strings: &mut Option<BitReader<'_>>) -> Result<()> {
+    let _index = reader.read_bs()?;
+    let _rgb = reader.read_bl()?;
+    let flags = reader.read_rc()?;
+    if flags & 0x01 != 0 {
+        let _color_name = read_text(strings)?;
+    }
+    if flags & 0x02 != 0 {
+        let _book_name = read_text(strings)?;
+    }
+    Ok(())
+}
+
+fn read_text(strings: &mut Option<BitReader<'_>>) -> Result<String> {
+    match strings {
+        Some(stream) => stream.read_tu(),
+        None => Ok(String::new()),
+    }
+}
+
+fn read_count(reader: &mut BitReader<'_>, label: &str) -> Result<usize> {
+    let count = reader.read_bl()? as usize;
+    if count > MAX_TABLE_ITEMS {
+        return Err(DwgError::new(
+            ErrorKind::Format,
+            format!(
+                "invalid ACAD_TABLE {}: {} (max {})",
+                label, count, MAX_TABLE_ITEMS
+            ),
+        ));
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::test_support::Bits;
+
+    fn cell(body: &mut Bits, cell_type: u8, text: Option<&str>, overrides: Option<u8>) {
+        body.small(cell_type);
+        body.rc(0); // flags
+        body.push(0, 1); // not merged
+        body.push(0, 1); // no autofit
+        body.push(0b10, 2); // merged width BL = 0
+        body.push(0b10, 2); // merged height BL = 0
+        body.bd(0.0); // rotation
+        match cell_type {
+            1 => body.tv(text.unwrap_or_default()),
+            2 => {
+                body.bd(1.0); // block scale
+                body.push(0, 1); // no attribute definitions
+            }
+            _ => {}
+        }
+        if matches!(cell_type, 1 | 2) {
+            match overrides {
+                Some(mask) => {
+                    body.push(1, 1);
+                    body.small(mask);
+                    body.rc(0); // virtual edge
+                    if mask & 0x04 != 0 {
+                        body.small(5); // background color index
+                        body.push(0b10, 2); // RGB BL = 0
+                        body.rc(0); // no names
+                    }
+                    if mask & 0x20 != 0 {
+                        body.bd(2.5);
+                    }
+                }
+                None => body.push(0, 1),
+            }
+        }
+    }
+
+    #[test]
+    fn decodes_r2004_cell_text_grid() {
+        let mut body = Bits::default();
+        body.common_entity_header(0x60, 0b00);
+
+        for value in [5.0, 7.0, 0.0] {
+            body.bd(value);
+        }
+        body.push(0b11, 2); // unit scale
+        body.bd(0.0); // rotation
+        for value in [0.0, 0.0, 1.0] {
+            body.bd(value);
+        }
+        body.push(0, 1); // no attributes
+        body.push(0b10, 2); // table value flags
+        for value in [1.0, 0.0, 0.0] {
+            body.bd(value);
+        }
+        body.small(2); // columns
+        body.small(2); // rows
+        body.bd(30.0);
+        body.bd(12.5);
+        body.bd(8.0);
+        body.bd(6.0);
+        cell(&mut body, 1, Some("Item"), None);
+        cell(&mut body, 1, Some("Qty"), Some(0x24));
+        cell(&mut body, 1, Some("Bolt M8"), None);
+        cell(&mut body, 2, None, None);
+
+        let mut handles = Bits::default();
+        handles.handle(4, 0x1F); // owner: model space
+        handles.handle(5, 0x10); // layer
+        let bytes = Bits::record(body, handles);
+
+        let table = decode_table_r2004(&mut BitReader::new(&bytes)).unwrap();
+        assert_eq!(table.handle, 0x60);
+        assert_eq!(table.layer_handle, 0x10);
+        assert_eq!(table.insertion, (5.0, 7.0, 0.0));
+        assert_eq!(table.scale, (1.0, 1.0, 1.0));
+        assert_eq!((table.row_count(), table.column_count()), (2, 2));
+        assert_eq!(table.column_widths, vec![30.0, 12.5]);
+        assert_eq!(table.row_heights, vec![8.0, 6.0]);
+        assert_eq!(
+            table.text_grid(),
+            vec![
+                vec!["Item".to_string(), "Qty".to_string()],
+                vec!["Bolt M8".to_string(), String::new()],
+            ]
+        );
+        assert_eq!(table.cells[3].cell_type, 2);
+    }
+}
@@ -5,7 +5,7 @@ use crate::entities::common::{
     parse_common_entity_handles, parse_common_entity_header, parse_common_entity_header_r14,
     parse_common_entity_header_r2007, parse_common_entity_header_r2010,
     parse_common_entity_header_r2013, parse_common_entity_layer_handle, read_handle_reference,
-    CommonEntityHeader,
+    seek_handle_stream, CommonEntityHeader,
 };
 
 #[derive(Debug, Clone)]
@@ -13,6 +13,9 @@ pub struct TextEntity {
     pub handle: u64,
     pub color_index: Option<u16>,
     pub true_color: Option<u32>,
+    pub transparency: Option<u32>,
+    pub ltype_scale: f64,
+    pub lineweight: Option<i8>,
     pub layer_handle: u64,
     pub text: String,
     pub insertion: (f64, f64, f64),
@@ -84,8 +87,7 @@ fn decode_text_with_header(
     let insertion_y = reader.read_rd(Endian::Little)?;
 
     let alignment = if (data_flags & 0x02) == 0 {
-        let align_x = reader.read_dd(insertion_x)?;
-        let align_y = reader.read_dd(insertion_y)?;
+        let (align_x, align_y) = reader.read_2dd((insertion_x, insertion_y))?;
         Some((align_x, align_y, elevation))
     } else {
         None
@@ -141,6 +143,9 @@ fn decode_text_with_header(
         handle: header.handle,
         color_index: header.color.index,
         true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
         layer_handle,
         text,
         insertion: (insertion_x, insertion_y, elevation),
@@ -186,6 +191,9 @@ fn decode_text_with_header_r14(
         handle: header.handle,
         color_index: header.color.index,
         true_color: header.color.true_color,
+        transparency: header.color.transparency,
+        ltype_scale: header.ltype_scale,
+        lineweight: header.lineweight,
         layer_handle,
         text,
         insertion: (insertion_x, insertion_y, elevation),
@@ -209,7 +217,7 @@ fn decode_text_handles(
     allow_handle_decode_failure: bool,
 ) -> Result<(u64, Option<u64>)> {
     // Handles are stored in the handle stream at obj_size bit offset.
-    reader.set_bit_pos(header.obj_size);
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
     let handles_pos = reader.get_pos();
     match parse_common_entity_handles(reader, header) {
         Ok(common_handles) => Ok((
@@ -1,24 +1,54 @@
-use crate::bit::{BitReader, Endian};
+use crate::bit::BitReader;
 use crate::core::result::Result;
-use crate::entities::common::{parse_common_entity_handles, parse_common_entity_header};
+use crate::entities::common::{
+    parse_common_entity_handles, parse_common_entity_header, seek_handle_stream,
+};
+
+/// VERTEX (2D) flag bits (DXF group 70).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Vertex2dFlags {
+    /// Extra vertex inserted by curve fitting.
+    pub curve_fit_extra: bool,
+    /// `tangent_dir` holds a curve-fit tangent; otherwise it is a default 0.
+    pub has_tangent: bool,
+    /// Vertex generated by spline fitting.
+    pub spline_vertex: bool,
+    /// Spline frame control point rather than a point on the curve.
+    pub spline_frame_control: bool,
+}
+
+impl Vertex2dFlags {
+    pub fn from_flags(flags: u16) -> Self {
+        Self {
+            curve_fit_extra: flags & 0x01 != 0,
+            has_tangent: flags & 0x02 != 0,
+            spline_vertex: flags & 0x08 != 0,
+            spline_frame_control: flags & 0x10 != 0,
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct Vertex2dEntity {
     pub handle: u64,
     pub flags: u16,
+    pub flags_info: Vertex2dFlags,
     pub position: (f64, f64, f64),
     pub start_width: f64,
     pub end_width: f64,
     pub bulge: f64,
     pub tangent_dir: f64,
+    pub owner_handle: Option<u64>,
 }
 
 pub fn decode_vertex_2d(reader: &mut BitReader<'_>) -> Result<Vertex2dEntity> {
     let header = parse_common_entity_header(reader)?;
-    // Flags are NOT bit-pair-coded in the DWG spec for VERTEX(2D).
-    let flags = reader.read_rs(Endian::Little)?;
+    // Flags are a single raw byte, NOT bit-pair-coded, for VERTEX(2D).
+    let flags = u16::from(reader.read_rc()?);
     let position = reader.read_3bd()?;
 
+    // A negative start width means both widths equal |start| and the end
+    // width is not stored. Bulge and tangent are always present.
     let mut start_width = reader.read_bd()?;
     let end_width = if start_width < 0.0 {
         start_width = -start_width;
@@ -30,15 +60,68 @@ pub fn decode_vertex_2d(reader: &mut BitReader<'_>) -> Result<Vertex2dEntity> {
     let bulge = reader.read_bd()?;
     let tangent_dir = reader.read_bd()?;
 
-    let _handles = parse_common_entity_handles(reader, &header)?;
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
+    let handles = parse_common_entity_handles(reader, &header)?;
 
     Ok(Vertex2dEntity {
         handle: header.handle,
         flags,
+        flags_info: Vertex2dFlags::from_flags(flags),
         position,
         start_width,
         end_width,
         bulge,
         tangent_dir,
+        owner_handle: handles.owner_ref,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::test_support::Bits;
+
+    /// R2000 VERTEX (2D) record: common header, body, then the handle stream
+    /// (owner and layer).
+    fn vertex_record(flags: u8, start_width: f64, end_width: f64, bulge: f64) -> Vec<u8> {
+        let mut body = Bits::default();
+        body.common_entity_header(0x21, 0b00);
+        body.rc(flags);
+        body.bd(2.5);
+        body.bd(0.0);
+        body.bd(0.0);
+        body.bd(start_width);
+        if start_width >= 0.0 {
+            body.bd(end_width);
+        }
+        body.bd(bulge);
+        body.bd(if flags & 0x02 != 0 { 0.75 } else { 0.0 });
+
+        let mut handles = Bits::default();
+        handles.handle(4, 0x20); // owner: POLYLINE
+        handles.handle(5, 0x10); // layer
+        Bits::record(body, handles)
+    }
+
+    #[test]
+    fn decodes_mixed_arc_and_line_vertices() {
+        let arc = vertex_record(0x02, 0.0, 0.5, 0.4142);
+        let vertex = decode_vertex_2d(&mut BitReader::new(&arc)).unwrap();
+        assert_eq!(vertex.flags, 0x02);
+        assert!(vertex.flags_info.has_tangent);
+        assert_eq!(vertex.position, (2.5, 0.0, 0.0));
+        assert_eq!((vertex.start_width, vertex.end_width), (0.0, 0.5));
+        assert_eq!(vertex.bulge, 0.4142);
+        assert_eq!(vertex.tangent_dir, 0.75);
+        assert_eq!(vertex.owner_handle, Some(0x20));
+
+        // Negative start width: both widths are |start|, end width omitted.
+        let line = vertex_record(0x00, -0.25, 0.0, 0.0);
+        let vertex = decode_vertex_2d(&mut BitReader::new(&line)).unwrap();
+        assert_eq!(vertex.flags_info, Vertex2dFlags::from_flags(0));
+        assert_eq!((vertex.start_width, vertex.end_width), (0.25, 0.25));
+        assert_eq!(vertex.bulge, 0.0);
+        assert_eq!(vertex.tangent_dir, 0.0);
+        assert_eq!(vertex.owner_handle, Some(0x20));
+    }
+}
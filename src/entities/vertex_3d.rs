@@ -4,7 +4,7 @@ use crate::core::result::Result;
 use crate::entities::common::{
     parse_common_entity_handles, parse_common_entity_header, parse_common_entity_header_r2007,
     parse_common_entity_header_r2010, parse_common_entity_header_r2013,
-    parse_common_entity_layer_handle, CommonEntityHeader,
+    parse_common_entity_layer_handle, seek_handle_stream, CommonEntityHeader,
 };
 
 #[derive(Debug, Clone)]
@@ -21,7 +21,7 @@ pub fn decode_vertex_3d(reader: &mut BitReader<'_>) -> Result<Vertex3dEntity> {
 
 pub fn decode_vertex_3d_r2007(reader: &mut BitReader<'_>) -> Result<Vertex3dEntity> {
     let header = parse_common_entity_header_r2007(reader)?;
-    decode_vertex_3d_with_header(reader, header, true, true)
+    decode_vertex_3d_with_header(reader, header, true, false)
 }
 
 pub fn decode_vertex_3d_r2010(
@@ -53,7 +53,7 @@ fn decode_vertex_3d_with_header(
     let flags = reader.read_rc()?;
     let position = reader.read_3bd()?;
     // Handles are stored in the handle stream at obj_size bit offset.
-    reader.set_bit_pos(header.obj_size);
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
     if let Err(err) = if r2007_layer_only {
         parse_common_entity_layer_handle(reader, &header).map(|_| ())
     } else {
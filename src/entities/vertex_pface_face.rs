@@ -4,7 +4,7 @@ use crate::core::result::Result;
 use crate::entities::common::{
     parse_common_entity_handles, parse_common_entity_header, parse_common_entity_header_r2007,
     parse_common_entity_header_r2010, parse_common_entity_header_r2013,
-    parse_common_entity_layer_handle, CommonEntityHeader,
+    parse_common_entity_layer_handle, seek_handle_stream, CommonEntityHeader,
 };
 
 #[derive(Debug, Clone)]
@@ -23,7 +23,7 @@ pub fn decode_vertex_pface_face(reader: &mut BitReader<'_>) -> Result<VertexPFac
 
 pub fn decode_vertex_pface_face_r2007(reader: &mut BitReader<'_>) -> Result<VertexPFaceFaceEntity> {
     let header = parse_common_entity_header_r2007(reader)?;
-    decode_vertex_pface_face_with_header(reader, header, true, true)
+    decode_vertex_pface_face_with_header(reader, header, true, false)
 }
 
 pub fn decode_vertex_pface_face_r2010(
@@ -58,7 +58,7 @@ fn decode_vertex_pface_face_with_header(
     let index4 = reader.read_bs()?;
 
     // Handles are stored in the handle stream at obj_size bit offset.
-    reader.set_bit_pos(header.obj_size);
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
     if let Err(err) = if r2007_layer_only {
         parse_common_entity_layer_handle(reader, &header).map(|_| ())
     } else {
@@ -1,56 +1,106 @@
-def detect_version(path: str) -> str: ...
-def list_section_locators(path: str) -> list[tuple[str, int, int]]: ...
-def read_section_bytes(path: str, index: int) -> bytes: ...
-def list_object_map_entries(path: str, limit: int | None = ...) -> list[tuple[int, int]]: ...
-def list_object_headers(path: str, limit: int | None = ...) -> list[tuple[int, int, int, int]]: ...
-def list_object_headers_with_type(path: str, limit: int | None = ...) -> list[tuple[int, int, int, int, str, str]]: ...
-def list_object_headers_by_type(path: str, type_codes: list[int], limit: int | None = ...) -> list[tuple[int, int, int, int, str, str]]: ...
-def read_object_records_by_type(path: str, type_codes: list[int], limit: int | None = ...) -> list[tuple[int, int, int, int, bytes]]: ...
-def decode_entity_styles(path: str, limit: int | None = ...) -> list[tuple[int, int | None, int | None, int]]: ...
-def decode_layer_colors(path: str, limit: int | None = ...) -> list[tuple[int, int, int | None]]: ...
-def decode_line_entities(path: str, limit: int | None = ...) -> list[tuple[int, float, float, float, float, float, float]]: ...
-def decode_point_entities(path: str, limit: int | None = ...) -> list[tuple[int, float, float, float, float]]: ...
-def decode_3dface_entities(path: str, limit: int | None = ...) -> list[tuple[int, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], int]]: ...
-def decode_arc_entities(path: str, limit: int | None = ...) -> list[tuple[int, float, float, float, float, float, float]]: ...
-def decode_circle_entities(path: str, limit: int | None = ...) -> list[tuple[int, float, float, float, float]]: ...
-def decode_line_arc_circle_entities(path: str, limit: int | None = ...) -> tuple[list[tuple[int, float, float, float, float, float, float]], list[tuple[int, float, float, float, float, float, float]], list[tuple[int, float, float, float, float]]]: ...
-def decode_ellipse_entities(path: str, limit: int | None = ...) -> list[tuple[int, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], float, float, float]]: ...
-def decode_spline_entities(path: str, limit: int | None = ...) -> list[tuple[int, tuple[int, int, bool, bool, bool], tuple[float | None, float | None, float | None], list[float], list[tuple[float, float, float]], list[float], list[tuple[float, float, float]]]]: ...
-def decode_dimension_entities(path: str, limit: int | None = ...) -> list[tuple[str, tuple[int, str, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float] | None, tuple[tuple[float, float, float], tuple[float, float, float]], tuple[float, float, float, float], tuple[int, float | None, int | None, int | None, float | None, float], tuple[int | None, int | None]]]]: ...
-def decode_dim_diameter_entities(path: str, limit: int | None = ...) -> list[tuple[int, str, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float] | None, tuple[tuple[float, float, float], tuple[float, float, float]], tuple[float, float, float, float], tuple[int, float | None, int | None, int | None, float | None, float], tuple[int | None, int | None]]]: ...
-def decode_dim_linear_entities(path: str, limit: int | None = ...) -> list[tuple[int, str, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float] | None, tuple[tuple[float, float, float], tuple[float, float, float]], tuple[float, float, float, float], tuple[int, float | None, int | None, int | None, float | None, float], tuple[int | None, int | None]]]: ...
-def decode_dim_ordinate_entities(path: str, limit: int | None = ...) -> list[tuple[int, str, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float] | None, tuple[tuple[float, float, float], tuple[float, float, float]], tuple[float, float, float, float], tuple[int, float | None, int | None, int | None, float | None, float], tuple[int | None, int | None]]]: ...
-def decode_dim_aligned_entities(path: str, limit: int | None = ...) -> list[tuple[int, str, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float] | None, tuple[tuple[float, float, float], tuple[float, float, float]], tuple[float, float, float, float], tuple[int, float | None, int | None, int | None, float | None, float], tuple[int | None, int | None]]]: ...
-def decode_dim_ang3pt_entities(path: str, limit: int | None = ...) -> list[tuple[int, str, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float] | None, tuple[tuple[float, float, float], tuple[float, float, float]], tuple[float, float, float, float], tuple[int, float | None, int | None, int | None, float | None, float], tuple[int | None, int | None]]]: ...
-def decode_dim_ang2ln_entities(path: str, limit: int | None = ...) -> list[tuple[int, str, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float] | None, tuple[tuple[float, float, float], tuple[float, float, float]], tuple[float, float, float, float], tuple[int, float | None, int | None, int | None, float | None, float], tuple[int | None, int | None]]]: ...
-def decode_dim_radius_entities(path: str, limit: int | None = ...) -> list[tuple[int, str, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float] | None, tuple[tuple[float, float, float], tuple[float, float, float]], tuple[float, float, float, float], tuple[int, float | None, int | None, int | None, float | None, float], tuple[int | None, int | None]]]: ...
-def decode_text_entities(path: str, limit: int | None = ...) -> list[tuple[int, str, tuple[float, float, float], tuple[float, float, float] | None, tuple[float, float, float], tuple[float, float, float, float, float], tuple[int, int, int], int | None]]: ...
-def decode_attrib_entities(path: str, limit: int | None = ...) -> list[tuple[int, str, str | None, str | None, tuple[float, float, float], tuple[float, float, float] | None, tuple[float, float, float], tuple[float, float, float, float, float], tuple[int, int, int], int, bool, int | None]]: ...
-def decode_attdef_entities(path: str, limit: int | None = ...) -> list[tuple[int, str, str | None, str | None, tuple[float, float, float], tuple[float, float, float] | None, tuple[float, float, float], tuple[float, float, float, float, float], tuple[int, int, int], int, bool, int | None]]: ...
-def decode_mtext_entities(path: str, limit: int | None = ...) -> list[tuple[int, str, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], float, float, int, int, tuple[int, float | None, int | None, int | None, int | None]]]: ...
-def decode_leader_entities(path: str, limit: int | None = ...) -> list[tuple[int, int, int, list[tuple[float, float, float]]]]: ...
-def decode_hatch_entities(path: str, limit: int | None = ...) -> list[tuple[int, str, bool, bool, float, tuple[float, float, float], list[tuple[bool, list[tuple[float, float]]]]]]: ...
-def decode_tolerance_entities(path: str, limit: int | None = ...) -> list[tuple[int, str, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], float, float, int | None]]: ...
-def decode_mline_entities(path: str, limit: int | None = ...) -> list[tuple[int, float, int, tuple[float, float, float], tuple[float, float, float], int, int, list[tuple[tuple[float, float, float], tuple[float, float, float], tuple[float, float, float]]], int | None]]: ...
-def decode_insert_entities(path: str, limit: int | None = ...) -> list[tuple[int, float, float, float, float, float, float, float]]: ...
-def decode_minsert_entities(path: str, limit: int | None = ...) -> list[tuple[int, float, float, float, float, float, float, float, int, int, float, float]]: ...
-def decode_polyline_3d_entities(path: str, limit: int | None = ...) -> list[tuple[int, int, int]]: ...
-def decode_polyline_3d_with_vertices(path: str, limit: int | None = ...) -> list[tuple[int, int, bool, list[tuple[float, float, float]]]]: ...
-def decode_polyline_mesh_entities(path: str, limit: int | None = ...) -> list[tuple[int, int, int, int, int, int, int]]: ...
-def decode_polyline_mesh_with_vertices(path: str, limit: int | None = ...) -> list[tuple[int, int, int, int, bool, list[tuple[float, float, float]]]]: ...
-def decode_polyline_pface_entities(path: str, limit: int | None = ...) -> list[tuple[int, int, int]]: ...
-def decode_polyline_pface_with_faces(path: str, limit: int | None = ...) -> list[tuple[int, int, int, list[tuple[float, float, float]], list[tuple[int, int, int, int]]]]: ...
-def decode_solid_entities(path: str, limit: int | None = ...) -> list[tuple[int, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], float, tuple[float, float, float]]]: ...
-def decode_trace_entities(path: str, limit: int | None = ...) -> list[tuple[int, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], float, tuple[float, float, float]]]: ...
-def decode_shape_entities(path: str, limit: int | None = ...) -> list[tuple[int, tuple[float, float, float], float, float, float, float, float, int, tuple[float, float, float], int | None]]: ...
-def decode_polyline_2d_entities(path: str, limit: int | None = ...) -> list[tuple[int, int, int, float, float, float, float]]: ...
-def decode_polyline_2d_entities_interpreted(path: str, limit: int | None = ...) -> list[tuple[int, int, int, str, bool, bool, bool, bool, bool, bool, bool, bool]]: ...
-def decode_lwpolyline_entities(path: str, limit: int | None = ...) -> list[tuple[int, int, list[tuple[float, float]], list[float], list[tuple[float, float]], float | None]]: ...
-def decode_polyline_2d_with_vertices(path: str, limit: int | None = ...) -> list[tuple[int, int, list[tuple[float, float, float]]]]: ...
-def decode_polyline_2d_with_vertices_interpolated(path: str, segments_per_span: int = ..., limit: int | None = ...) -> list[tuple[int, int, bool, list[tuple[float, float, float]]]]: ...
-def decode_vertex_2d_entities(path: str, limit: int | None = ...) -> list[tuple[int, int, float, float, float, float, float, float, float]]: ...
-def decode_vertex_3d_entities(path: str, limit: int | None = ...) -> list[tuple[int, int, float, float, float]]: ...
-def decode_vertex_mesh_entities(path: str, limit: int | None = ...) -> list[tuple[int, int, float, float, float]]: ...
-def decode_vertex_pface_entities(path: str, limit: int | None = ...) -> list[tuple[int, int, float, float, float]]: ...
-def decode_vertex_pface_face_entities(path: str, limit: int | None = ...) -> list[tuple[int, int, int, int, int]]: ...
-def decode_polyline_2d_with_vertex_data(path: str, limit: int | None = ...) -> list[tuple[int, int, list[tuple[float, float, float, float, float, float, float, int]]]]: ...
+import os
+from typing import IO, Callable, Union
+
+class ConfiguredSource: ...
+
+DwgSource = Union[str, os.PathLike[str], bytes, bytearray, IO[bytes], ConfiguredSource]
+
+class UnsupportedEntityError(ValueError): ...
+class Cancelled(Exception): ...
+
+def detect_version(path: DwgSource) -> str: ...
+def sniff(path: DwgSource) -> tuple[str, str | None]: ...
+def set_decode_cache_dir(dir: str | os.PathLike[str] | None = None) -> None: ...
+def configure(path: DwgSource, preset: str | None = None, config: dict[str, bool | int | float | str | None] | None = None) -> ConfiguredSource: ...
+def list_section_locators(path: DwgSource) -> list[tuple[str, int, int]]: ...
+def list_sections_detailed(path: DwgSource) -> list[tuple[str, int, int, int, int, int]]: ...
+def read_section_bytes(path: DwgSource, index: int) -> bytes: ...
+def read_section_bytes_by_name(path: DwgSource, name: str) -> bytes: ...
+def section_crcs(path: DwgSource) -> list[tuple[str, int]]: ...
+def aux_header(path: DwgSource) -> tuple[int, int, tuple[int, int], int, float, float, int | None]: ...
+def measurement_units(path: DwgSource) -> str | None: ...
+def read_thumbnail(path: DwgSource) -> tuple[str, bytes] | None: ...
+def compute_crc(data: bytes, seed: int = ...) -> int: ...
+def analyze(path: DwgSource) -> list[tuple[str, str, int | None]]: ...
+def list_object_map_entries(path: DwgSource, limit: int | None = ...) -> list[tuple[int, int]]: ...
+def list_object_headers(path: DwgSource, limit: int | None = ...) -> list[tuple[int, int, int, int]]: ...
+def list_object_headers_with_type(path: DwgSource, limit: int | None = ...) -> list[tuple[int, int, int, int, str, str]]: ...
+def list_object_headers_by_type(path: DwgSource, type_codes: list[int], limit: int | None = ...) -> list[tuple[int, int, int, int, str, str]]: ...
+def object_type_histogram(path: DwgSource) -> list[tuple[int, str, int]]: ...
+def read_object_records_by_type(path: DwgSource, type_codes: list[int], limit: int | None = ...) -> list[tuple[int, int, int, int, bytes]]: ...
+def read_object_records_by_handles(path: DwgSource, handles: list[int]) -> list[tuple[int, int, int, int, bytes]]: ...
+def decode_entity_styles(path: DwgSource, limit: int | None = ...) -> list[tuple[int, int | None, int | None, int | None, int]]: ...
+def decode_entity_line_styles(path: DwgSource, limit: int | None = ...) -> list[tuple[int, float, int | None]]: ...
+def decode_layer_colors(path: DwgSource, limit: int | None = ...) -> list[tuple[int, int, int | None]]: ...
+def decode_layer_states(path: DwgSource, limit: int | None = ...) -> list[tuple[int, bool, bool, bool, bool]]: ...
+def decode_line_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, float, float, float, float, float, float]]: ...
+def merge_lines_to_polylines(path: DwgSource, tolerance: float | None = ...) -> list[tuple[list[int], bool, list[tuple[float, float, float]]]]: ...
+def aci_to_rgb(index: int) -> tuple[int, int, int]: ...
+def entity_bounds(path: DwgSource) -> list[tuple[int, tuple[float, float], tuple[float, float]]]: ...
+def spatial_query(path: DwgSource, min: tuple[float, float], max: tuple[float, float]) -> list[int]: ...
+def object_summaries(path: DwgSource, skip_hidden_layers: bool = ...) -> list[tuple[int, str, str | None, tuple[tuple[float, float], tuple[float, float]] | None]]: ...
+def list_inexact_decodes(path: DwgSource) -> list[tuple[int, str, str]]: ...
+def decode_entities_paginated(path: DwgSource, offset: int, count: int) -> list[tuple[int, str, str]]: ...
+def decode_point_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, float, float, float, float, float]]: ...
+def decode_3dface_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], int]]: ...
+def decode_arc_entities(path: DwgSource, limit: int | None = ..., wcs: bool = ...) -> list[tuple[int, float, float, float, float, float, float]]: ...
+def decode_arc_sweeps(path: DwgSource, limit: int | None = ..., wcs: bool = ...) -> list[tuple[int, float, float, float, float, float, float, float, tuple[float, float, float]]]: ...
+def decode_circle_entities(path: DwgSource, limit: int | None = ..., wcs: bool = ...) -> list[tuple[int, float, float, float, float, float, tuple[float, float, float]]]: ...
+def decode_line_arc_circle_entities(path: DwgSource, limit: int | None = ..., wcs: bool = ...) -> tuple[list[tuple[int, float, float, float, float, float, float]], list[tuple[int, float, float, float, float, float, float]], list[tuple[int, float, float, float, float, float, tuple[float, float, float]]]]: ...
+def decode_ellipse_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], float, float, float]]: ...
+def decode_spline_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, tuple[int, int, bool, bool, bool], tuple[float | None, float | None, float | None], list[float], list[tuple[float, float, float]], list[float], list[tuple[float, float, float]]]]: ...
+def decode_spline_points(path: DwgSource, samples_per_segment: int = ..., include_raw: bool = ..., limit: int | None = ...) -> list[tuple[int, int, bool, list[tuple[float, float, float]], list[tuple[float, float, float]] | None, list[float] | None]]: ...
+def decode_dimension_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[str, tuple[int, str, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float] | None, tuple[tuple[float, float, float], tuple[float, float, float]], tuple[float, float, float, float], tuple[int, float | None, int | None, int | None, float | None, float], tuple[int | None, int | None]]]]: ...
+def decode_dim_diameter_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, str, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float] | None, tuple[tuple[float, float, float], tuple[float, float, float]], tuple[float, float, float, float], tuple[int, float | None, int | None, int | None, float | None, float], tuple[int | None, int | None]]]: ...
+def decode_dim_linear_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, str, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float] | None, tuple[tuple[float, float, float], tuple[float, float, float]], tuple[float, float, float, float], tuple[int, float | None, int | None, int | None, float | None, float], tuple[int | None, int | None]]]: ...
+def decode_dim_ordinate_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, str, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float] | None, tuple[tuple[float, float, float], tuple[float, float, float]], tuple[float, float, float, float], tuple[int, float | None, int | None, int | None, float | None, float], tuple[int | None, int | None]]]: ...
+def decode_dim_aligned_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, str, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float] | None, tuple[tuple[float, float, float], tuple[float, float, float]], tuple[float, float, float, float], tuple[int, float | None, int | None, int | None, float | None, float], tuple[int | None, int | None]]]: ...
+def decode_dim_ang3pt_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, str, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float] | None, tuple[tuple[float, float, float], tuple[float, float, float]], tuple[float, float, float, float], tuple[int, float | None, int | None, int | None, float | None, float], tuple[int | None, int | None]]]: ...
+def decode_dim_ang2ln_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, str, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float] | None, tuple[tuple[float, float, float], tuple[float, float, float]], tuple[float, float, float, float], tuple[int, float | None, int | None, int | None, float | None, float], tuple[int | None, int | None]]]: ...
+def decode_dim_radius_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, str, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float] | None, tuple[tuple[float, float, float], tuple[float, float, float]], tuple[float, float, float, float], tuple[int, float | None, int | None, int | None, float | None, float], tuple[int | None, int | None]]]: ...
+def decode_text_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, str, tuple[float, float, float], tuple[float, float, float] | None, tuple[float, float, float], tuple[float, float, float, float, float], tuple[int, int, int], int | None]]: ...
+def decode_attrib_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, str, str | None, str | None, tuple[float, float, float], tuple[float, float, float] | None, tuple[float, float, float], tuple[float, float, float, float, float], tuple[int, int, int], int, bool, int | None]]: ...
+def decode_attdef_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, str, str | None, str | None, tuple[float, float, float], tuple[float, float, float] | None, tuple[float, float, float], tuple[float, float, float, float, float], tuple[int, int, int], int, bool, int | None]]: ...
+def decode_mtext_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, str, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], float, float, int, int, tuple[int, float | None, int | None, int | None, int | None]]]: ...
+def decode_leader_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, int, int, list[tuple[float, float, float]]]]: ...
+def decode_hatch_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, str, bool, bool, float, tuple[float, float, float], list[tuple[bool, list[tuple[float, float]]]]]]: ...
+def decode_tolerance_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, str, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], float, float, int | None]]: ...
+def decode_mline_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, float, int, tuple[float, float, float], tuple[float, float, float], int, int, list[tuple[tuple[float, float, float], tuple[float, float, float], tuple[float, float, float]]], int | None]]: ...
+def decode_insert_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, float, float, float, float, float, float, float]]: ...
+def decode_insert_entities_with_block(path: DwgSource, limit: int | None = ...) -> list[tuple[int, tuple[float, float, float], tuple[float, float, float], float, int | None, str | None]]: ...
+def decode_inserts_with_attribs(path: DwgSource, limit: int | None = ...) -> list[tuple[int, float, float, float, float, float, float, float, list[tuple[int, str | None, str]]]]: ...
+def insert_transforms(path: DwgSource, limit: int | None = ...) -> list[tuple[int, int | None, str | None, list[float]]]: ...
+def decode_minsert_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, float, float, float, float, float, float, float, int, int, float, float]]: ...
+def decode_polyline_3d_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, int, int]]: ...
+def decode_polyline_3d_with_vertices(path: DwgSource, limit: int | None = ...) -> list[tuple[int, int, bool, list[tuple[float, float, float]]]]: ...
+def decode_polyline_mesh_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, int, int, int, int, int, int]]: ...
+def decode_polyline_mesh_with_vertices(path: DwgSource, limit: int | None = ...) -> list[tuple[int, int, int, int, bool, list[tuple[float, float, float]]]]: ...
+def decode_polyline_pface_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, int, int]]: ...
+def decode_polyline_pface_with_faces(path: DwgSource, limit: int | None = ...) -> list[tuple[int, int, int, list[tuple[float, float, float]], list[tuple[int, int, int, int]]]]: ...
+def decode_solid_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], float, tuple[float, float, float]]]: ...
+def decode_trace_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], float, tuple[float, float, float]]]: ...
+def decode_shape_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, tuple[float, float, float], float, float, float, float, float, int, tuple[float, float, float], int | None]]: ...
+def decode_proxy_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, int, str | None, bool, int, tuple[tuple[float, float, float], tuple[float, float, float]] | None]]: ...
+def list_images(path: DwgSource) -> list[tuple[int, tuple[float, float, float], tuple[float, float, float], tuple[float, float, float], tuple[float, float], int, list[tuple[float, float]], tuple[int, str | None, tuple[float, float], tuple[float, float], int] | None]]: ...
+def list_vports(path: DwgSource) -> list[tuple[int, str | None, tuple[float, float], tuple[float, float], tuple[float, float], float, float, tuple[float, float, float], tuple[float, float, float], tuple[bool, tuple[float, float]], tuple[bool, tuple[float, float], tuple[float, float], float]]]: ...
+def list_appids(path: DwgSource) -> list[tuple[int, str | None]]: ...
+def list_blocks(path: DwgSource) -> list[tuple[int, str | None, bool, int, tuple[float, float, float]]]: ...
+def drawing_stats(path: DwgSource) -> tuple[str, int, int, int, int, list[tuple[str, int, int, int, int, int]], int, int, list[tuple[str, int]]]: ...
+def modelspace_entity_handles(path: DwgSource) -> list[int]: ...
+def list_xrefs(path: DwgSource) -> list[tuple[int, str | None, str | None, str]]: ...
+def decode_entity_xdata(path: DwgSource, limit: int | None = ..., progress: Callable[[int, int], object] | None = ...) -> list[tuple[int, str, list[tuple[int, list[tuple[int, str | bytes | int | float | tuple[float, float, float]]]]]]]: ...
+def decode_multileaders(path: DwgSource, limit: int | None = ...) -> list[tuple[int, str, str | None, tuple[float, float, float] | None, list[tuple[tuple[float, float, float] | None, tuple[float, float, float] | None, float, list[list[tuple[float, float, float]]]]]]]: ...
+def decode_wipeouts(path: DwgSource, limit: int | None = ...) -> list[tuple[int, int, bool, list[tuple[float, float, float]]]]: ...
+def decode_tables(path: DwgSource, limit: int | None = ...) -> list[tuple[int, tuple[float, float, float], int, int, list[float], list[float], list[list[str]]]]: ...
+def extract_all_text(path: DwgSource, strip_formatting: bool = ..., progress: Callable[[int, int], object] | None = ..., skip_hidden_layers: bool = ...) -> list[tuple[int, str, str | None, str, tuple[float, float, float]]]: ...
+def decode_polyline_2d_entities(path: DwgSource, limit: int | None = ..., include_extrusion: bool = ...) -> list[tuple[int, int, int, float, float, float, float, tuple[float, float, float] | None]]: ...
+def decode_polyline_2d_entities_interpreted(path: DwgSource, limit: int | None = ...) -> list[tuple[int, int, int, str, bool, bool, bool, bool, bool, bool, bool, bool]]: ...
+def decode_lwpolyline_entities(path: DwgSource, limit: int | None = ..., wcs: bool = ...) -> list[tuple[int, int, list[tuple[float, float]] | list[tuple[float, float, float]], list[float], list[tuple[float, float]], float | None]]: ...
+def decode_polyline_2d_with_vertices(path: DwgSource, limit: int | None = ..., wcs: bool = ...) -> list[tuple[int, int, list[tuple[float, float, float]]]]: ...
+def decode_polyline_2d_with_vertices_interpolated(path: DwgSource, segments_per_span: int | None = ..., limit: int | None = ..., wcs: bool = ...) -> list[tuple[int, int, bool, list[tuple[float, float, float]]]]: ...
+def decode_vertex_2d_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, int, float, float, float, float, float, float, float]]: ...
+def decode_vertex_3d_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, int, float, float, float]]: ...
+def decode_vertex_mesh_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, int, float, float, float]]: ...
+def decode_vertex_pface_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, int, float, float, float]]: ...
+def decode_vertex_pface_face_entities(path: DwgSource, limit: int | None = ...) -> list[tuple[int, int, int, int, int]]: ...
+def decode_polyline_2d_with_vertex_data(path: DwgSource, limit: int | None = ...) -> list[tuple[int, int, list[tuple[float, float, float, float, float, float, float, int]]]]: ...
@@ -3,7 +3,7 @@ from __future__ import annotations
 import math
 from dataclasses import dataclass
 from pathlib import Path
-from typing import Any, Iterable
+from typing import Any, Callable, Iterable
 
 from .document import Document, Layout, read
 from .entity import Entity
@@ -26,6 +26,7 @@ def to_dxf(
     types: str | Iterable[str] | None = None,
     dxf_version: str = "R2010",
     strict: bool = False,
+    progress: Callable[[int, int], Any] | None = None,
 ) -> ConvertResult:
     ezdxf = _require_ezdxf()
     source_path, layout = _resolve_layout(source)
@@ -37,12 +38,25 @@ def to_dxf(
     written = 0
     skipped_by_type: dict[str, int] = {}
 
-    for entity in layout.query(types):
+    entities: Iterable[Entity] = layout.query(types)
+    if progress is not None:
+        # The total is needed up front, so decode everything before writing.
+        entities = list(entities)
+        entity_count = len(entities)
+        step = max(math.ceil(entity_count / 100), 1)
+
+    for index, entity in enumerate(entities):
+        # Returning False stops the export; what was converted so far is saved.
+        if progress is not None and index % step == 0 and progress(index, entity_count) is False:
+            break
         total += 1
         if _write_entity_to_modelspace(modelspace, entity):
             written += 1
             continue
         skipped_by_type[entity.dxftype] = skipped_by_type.get(entity.dxftype, 0) + 1
+    else:
+        if progress is not None:
+            progress(entity_count, entity_count)
 
     skipped = total - written
     if strict and skipped > 0:
@@ -5,7 +5,7 @@ import math
 import re
 from functools import lru_cache
 from dataclasses import dataclass
-from typing import Iterable, Iterator
+from typing import Any, Iterable, Iterator
 
 from . import raw
 from .entity import Entity
@@ -51,11 +51,23 @@ TYPE_ALIASES = {
 _BULK_PRIMITIVE_TYPES = {"LINE", "ARC", "CIRCLE"}
 
 
-def read(path: str) -> "Document":
+def read(
+    path: str,
+    cache_dir: str | None = None,
+    preset: str | None = None,
+    config: dict[str, Any] | None = None,
+) -> "Document":
+    # `cache_dir` turns on the process-wide decode cache (`cache` feature);
+    # `preset` and `config` pick the `ParseConfig` this document decodes with.
+    if cache_dir is not None:
+        raw.set_decode_cache_dir(cache_dir)
+    source = None
+    if preset is not None or config is not None:
+        source = raw.configure(path, preset, config)
     version = raw.detect_version(path)
     if version not in SUPPORTED_VERSIONS:
         raise ValueError(f"unsupported DWG version: {version}")
-    return Document(path=path, version=version)
+    return Document(path=path, version=version, decode_source=source)
 
 
 @dataclass(frozen=True)
@@ -64,12 +76,17 @@ class Document:
     version: str
     decode_path: str | None = None
     decode_version: str | None = None
+    # What raw calls decode: `decode_path`, or it bound to the document's
+    # `ParseConfig` by `raw.configure`.
+    decode_source: str | raw.ConfiguredSource | None = None
 
     def __post_init__(self) -> None:
         if self.decode_path is None:
             object.__setattr__(self, "decode_path", self.path)
         if self.decode_version is None:
             object.__setattr__(self, "decode_version", self.version)
+        if self.decode_source is None:
+            object.__setattr__(self, "decode_source", self.decode_path)
 
     def modelspace(self) -> "Layout":
         return Layout(self, "MODELSPACE")
@@ -98,10 +115,10 @@ class Layout:
         return self.query(types)
 
     def query(self, types: str | Iterable[str] | None = None) -> Iterator[Entity]:
-        type_set = _normalize_types(types, self.doc.decode_path)
+        type_set = _normalize_types(types, self.doc.decode_source)
         bulk_rows = None
         if sum(1 for dxftype in type_set if dxftype in _BULK_PRIMITIVE_TYPES) >= 2:
-            bulk_rows = _line_arc_circle_rows(self.doc.decode_path)
+            bulk_rows = _line_arc_circle_rows(self.doc.decode_source)
         for dxftype in type_set:
             yield from self._iter_type(dxftype, bulk_rows=bulk_rows)
 
@@ -126,9 +143,9 @@ class Layout:
         ]
         | None = None,
     ) -> Iterator[Entity]:
-        decode_path = self.doc.decode_path
-        entity_style_map = _entity_style_map(decode_path)
-        layer_color_map = _layer_color_map(decode_path)
+        source = self.doc.decode_source
+        entity_style_map = _entity_style_map(source)
+        layer_color_map = _layer_color_map(source)
         layer_color_overrides = _layer_color_overrides(
             self.doc.decode_version, entity_style_map, layer_color_map
         )
@@ -136,7 +153,7 @@ class Layout:
             if bulk_rows is not None:
                 line_rows = bulk_rows[0]
             else:
-                line_rows = list(raw.decode_line_entities(decode_path))
+                line_rows = list(raw.decode_line_entities(source))
             line_supplementary_handles = _line_supplementary_handles(
                 line_rows, entity_style_map, layer_color_overrides
             )
@@ -163,7 +180,7 @@ class Layout:
             return
 
         if dxftype == "ARC":
-            arc_rows = bulk_rows[1] if bulk_rows is not None else raw.decode_arc_entities(decode_path)
+            arc_rows = bulk_rows[1] if bulk_rows is not None else raw.decode_arc_entities(source)
             for handle, cx, cy, cz, radius, start_angle, end_angle in arc_rows:
                 start_deg = math.degrees(start_angle)
                 end_deg = math.degrees(end_angle)
@@ -194,7 +211,7 @@ class Layout:
                 bulges,
                 widths,
                 const_width,
-            ) in raw.decode_lwpolyline_entities(decode_path):
+            ) in raw.decode_lwpolyline_entities(source):
                 points3d = [(x, y, 0.0) for x, y in points]
                 bulges_list = list(bulges)
                 if len(bulges_list) < len(points3d):
@@ -232,7 +249,7 @@ class Layout:
 
         if dxftype == "POLYLINE_3D":
             for handle, flags_70_bits, closed, points in raw.decode_polyline_3d_with_vertices(
-                decode_path
+                source
             ):
                 yield Entity(
                     dxftype="POLYLINE_3D",
@@ -260,7 +277,7 @@ class Layout:
                 n_vertex_count,
                 closed,
                 points,
-            ) in raw.decode_polyline_mesh_with_vertices(decode_path):
+            ) in raw.decode_polyline_mesh_with_vertices(source):
                 yield Entity(
                     dxftype="POLYLINE_MESH",
                     handle=handle,
@@ -288,7 +305,7 @@ class Layout:
                 num_faces,
                 vertices,
                 faces,
-            ) in raw.decode_polyline_pface_with_faces(decode_path):
+            ) in raw.decode_polyline_pface_with_faces(source):
                 yield Entity(
                     dxftype="POLYLINE_PFACE",
                     handle=handle,
@@ -310,7 +327,7 @@ class Layout:
 
         if dxftype == "3DFACE":
             for handle, p1, p2, p3, p4, invisible_edge_flags in raw.decode_3dface_entities(
-                decode_path
+                source
             ):
                 yield Entity(
                     dxftype="3DFACE",
@@ -331,7 +348,7 @@ class Layout:
 
         if dxftype == "SOLID":
             for handle, p1, p2, p3, p4, thickness, extrusion in raw.decode_solid_entities(
-                decode_path
+                source
             ):
                 yield Entity(
                     dxftype="SOLID",
@@ -353,7 +370,7 @@ class Layout:
 
         if dxftype == "TRACE":
             for handle, p1, p2, p3, p4, thickness, extrusion in raw.decode_trace_entities(
-                decode_path
+                source
             ):
                 yield Entity(
                     dxftype="TRACE",
@@ -385,7 +402,7 @@ class Layout:
                 shape_no,
                 extrusion,
                 shapefile_handle,
-            ) in raw.decode_shape_entities(decode_path):
+            ) in raw.decode_shape_entities(source):
                 yield Entity(
                     dxftype="SHAPE",
                     handle=handle,
@@ -411,7 +428,9 @@ class Layout:
             return
 
         if dxftype == "POINT":
-            for handle, x, y, z, angle in raw.decode_point_entities(decode_path):
+            for handle, x, y, z, thickness, angle in raw.decode_point_entities(
+                source
+            ):
                 yield Entity(
                     dxftype="POINT",
                     handle=handle,
@@ -419,6 +438,7 @@ class Layout:
                         handle,
                         {
                             "location": (x, y, z),
+                            "thickness": thickness,
                             "x_axis_angle": angle,
                         },
                         entity_style_map,
@@ -433,16 +453,18 @@ class Layout:
             if bulk_rows is not None:
                 circle_rows = bulk_rows[2]
             else:
-                circle_rows = list(raw.decode_circle_entities(decode_path))
+                circle_rows = list(raw.decode_circle_entities(source))
             circle_supplementary_handles = _circle_supplementary_handles(
                 circle_rows, entity_style_map, layer_color_overrides
             )
-            for handle, cx, cy, cz, radius in circle_rows:
+            for handle, cx, cy, cz, radius, thickness, extrusion in circle_rows:
                 dxf = _attach_entity_color(
                     handle,
                     {
                         "center": (cx, cy, cz),
                         "radius": radius,
+                        "thickness": thickness,
+                        "extrusion": extrusion,
                     },
                     entity_style_map,
                     layer_color_map,
@@ -468,7 +490,7 @@ class Layout:
                 axis_ratio,
                 start_angle,
                 end_angle,
-            ) in raw.decode_ellipse_entities(decode_path):
+            ) in raw.decode_ellipse_entities(source):
                 yield Entity(
                     dxftype="ELLIPSE",
                     handle=handle,
@@ -499,7 +521,7 @@ class Layout:
                 control_points,
                 weights,
                 fit_points,
-            ) in raw.decode_spline_entities(decode_path):
+            ) in raw.decode_spline_entities(source):
                 scenario, degree, rational, closed, periodic = flags_data
                 fit_tolerance, knot_tolerance, ctrl_tolerance = tolerance_data
                 points = list(fit_points if len(fit_points) >= 2 else control_points)
@@ -543,7 +565,7 @@ class Layout:
                 metrics,
                 align_flags,
                 style_handle,
-            ) in raw.decode_text_entities(decode_path):
+            ) in raw.decode_text_entities(source):
                 thickness, oblique_angle, height, rotation, width_factor = metrics
                 generation, horizontal_alignment, vertical_alignment = align_flags
                 yield Entity(
@@ -588,7 +610,7 @@ class Layout:
                 attrib_flags,
                 lock_position,
                 style_handle,
-            ) in raw.decode_attrib_entities(decode_path):
+            ) in raw.decode_attrib_entities(source):
                 thickness, oblique_angle, height, rotation, width_factor = metrics
                 generation, horizontal_alignment, vertical_alignment = align_flags
                 yield Entity(
@@ -637,7 +659,7 @@ class Layout:
                 attrib_flags,
                 lock_position,
                 style_handle,
-            ) in raw.decode_attdef_entities(decode_path):
+            ) in raw.decode_attdef_entities(source):
                 thickness, oblique_angle, height, rotation, width_factor = metrics
                 generation, horizontal_alignment, vertical_alignment = align_flags
                 yield Entity(
@@ -684,7 +706,7 @@ class Layout:
                 attachment,
                 drawing_dir,
                 background_data,
-            ) in raw.decode_mtext_entities(decode_path):
+            ) in raw.decode_mtext_entities(source):
                 (
                     background_flags,
                     background_scale_factor,
@@ -726,7 +748,7 @@ class Layout:
 
         if dxftype == "LEADER":
             for handle, annotation_type, path_type, points in raw.decode_leader_entities(
-                decode_path
+                source
             ):
                 points_list = list(points)
                 yield Entity(
@@ -756,7 +778,7 @@ class Layout:
                 elevation,
                 extrusion,
                 path_rows,
-            ) in raw.decode_hatch_entities(decode_path):
+            ) in raw.decode_hatch_entities(source):
                 paths = []
                 for closed, points in path_rows:
                     path_points = [(x, y, elevation) for x, y in points]
@@ -799,7 +821,7 @@ class Layout:
                 height,
                 dimgap,
                 dimstyle_handle,
-            ) in raw.decode_tolerance_entities(decode_path):
+            ) in raw.decode_tolerance_entities(source):
                 rotation = math.degrees(math.atan2(x_direction[1], x_direction[0]))
                 yield Entity(
                     dxftype="TOLERANCE",
@@ -835,7 +857,7 @@ class Layout:
                 lines_in_style,
                 vertices,
                 mlinestyle_handle,
-            ) in raw.decode_mline_entities(decode_path):
+            ) in raw.decode_mline_entities(source):
                 vertices_list = list(vertices)
                 points = [vertex[0] for vertex in vertices_list if len(vertex) >= 1]
                 vertex_directions = [vertex[1] for vertex in vertices_list if len(vertex) >= 2]
@@ -882,7 +904,7 @@ class Layout:
                 num_rows,
                 column_spacing,
                 row_spacing,
-            ) in raw.decode_minsert_entities(decode_path):
+            ) in raw.decode_minsert_entities(source):
                 yield Entity(
                     dxftype="MINSERT",
                     handle=handle,
@@ -912,7 +934,7 @@ class Layout:
             used_bulk_decoder = False
 
             try:
-                for dimtype, row in raw.decode_dimension_entities(decode_path):
+                for dimtype, row in raw.decode_dimension_entities(source):
                     dimension_rows.append((str(dimtype).upper(), row))
                 used_bulk_decoder = True
             except Exception:
@@ -920,7 +942,7 @@ class Layout:
 
             def _append_rows(dimtype: str, decode_fn) -> None:
                 try:
-                    rows = decode_fn(decode_path)
+                    rows = decode_fn(source)
                 except Exception:
                     rows = []
                 for row in rows:
@@ -1002,7 +1024,7 @@ class Layout:
                 )
             return
 
-        raise ValueError(
+        raise raw.UnsupportedEntityError(
             f"unsupported entity type: {dxftype}. "
             "Supported types: LINE, LWPOLYLINE, POLYLINE_3D, POLYLINE_MESH, POLYLINE_PFACE, 3DFACE, SOLID, TRACE, SHAPE, ARC, CIRCLE, ELLIPSE, SPLINE, POINT, TEXT, ATTRIB, ATTDEF, MTEXT, LEADER, HATCH, TOLERANCE, MLINE, MINSERT, DIMENSION"
         )
@@ -1116,7 +1138,7 @@ def _build_dimension_common_dxf(
 
 
 @lru_cache(maxsize=16)
-def _present_supported_types(path: str | None) -> tuple[str, ...]:
+def _present_supported_types(path: str | raw.ConfiguredSource | None) -> tuple[str, ...]:
     if not path:
         return tuple(SUPPORTED_ENTITY_TYPES)
     try:
@@ -1149,7 +1171,9 @@ def _canonical_entity_type_name(raw_name: object) -> str | None:
     return None
 
 
-def _normalize_types(types: str | Iterable[str] | None, path: str | None = None) -> list[str]:
+def _normalize_types(
+    types: str | Iterable[str] | None, path: str | raw.ConfiguredSource | None = None
+) -> list[str]:
     default_types = list(_present_supported_types(path))
     if types is None:
         return default_types
@@ -1193,11 +1217,11 @@ def _normalize_types(types: str | Iterable[str] | None, path: str | None = None)
 
 @lru_cache(maxsize=16)
 def _line_arc_circle_rows(
-    path: str,
+    path: str | raw.ConfiguredSource,
 ) -> tuple[
     list[tuple[int, float, float, float, float, float, float]],
     list[tuple[int, float, float, float, float, float, float]],
-    list[tuple[int, float, float, float, float]],
+    list[tuple[int, float, float, float, float, float, tuple[float, float, float]]],
 ]:
     try:
         line_rows, arc_rows, circle_rows = raw.decode_line_arc_circle_entities(path)
@@ -1211,18 +1235,22 @@ def _line_arc_circle_rows(
 
 
 @lru_cache(maxsize=16)
-def _entity_style_map(path: str) -> dict[int, tuple[int | None, int | None, int]]:
+def _entity_style_map(
+    path: str | raw.ConfiguredSource,
+) -> dict[int, tuple[int | None, int | None, int, int | None]]:
     try:
         return {
-            handle: (index, true_color, layer_handle)
-            for handle, index, true_color, layer_handle in raw.decode_entity_styles(path)
+            handle: (index, true_color, layer_handle, transparency)
+            for handle, index, true_color, transparency, layer_handle in raw.decode_entity_styles(
+                path
+            )
         }
     except Exception:
         return {}
 
 
 @lru_cache(maxsize=16)
-def _layer_color_map(path: str) -> dict[int, tuple[int, int | None]]:
+def _layer_color_map(path: str | raw.ConfiguredSource) -> dict[int, tuple[int, int | None]]:
     try:
         return {
             handle: (index, true_color)
@@ -1234,14 +1262,15 @@ def _layer_color_map(path: str) -> dict[int, tuple[int, int | None]]:
 
 def _layer_color_overrides(
     version: str,
-    entity_style_map: dict[int, tuple[int | None, int | None, int]],
+    entity_style_map: dict[int, tuple[int | None, int | None, int, int | None]],
     layer_color_map: dict[int, tuple[int, int | None]],
 ) -> dict[int, tuple[int, int | None]]:
     if version not in {"AC1024", "AC1027", "AC1032"}:
         return {}
 
     usage: dict[int, int] = {}
-    for _, _, layer_handle in entity_style_map.values():
+    for style in entity_style_map.values():
+        layer_handle = style[2]
         usage[layer_handle] = usage.get(layer_handle, 0) + 1
     if not usage:
         return {}
@@ -1285,7 +1314,7 @@ def _layer_color_overrides(
 def _attach_entity_color(
     handle: int,
     dxf: dict,
-    entity_style_map: dict[int, tuple[int | None, int | None, int]],
+    entity_style_map: dict[int, tuple[int | None, int | None, int, int | None]],
     layer_color_map: dict[int, tuple[int, int | None]],
     layer_color_overrides: dict[int, tuple[int, int | None]] | None = None,
     dxftype: str | None = None,
@@ -1293,12 +1322,15 @@ def _attach_entity_color(
     index = None
     true_color = None
     layer_handle = None
+    transparency = None
     resolved_index = None
     resolved_true_color = None
 
     style = entity_style_map.get(handle)
     if style is not None:
-        index, true_color, layer_handle = style
+        index, true_color, layer_handle = style[:3]
+        if len(style) > 3:
+            transparency = style[3]
         resolved_index = index
         resolved_true_color = true_color
         if index in (None, 0, 256, 257) and true_color is None:
@@ -1331,6 +1363,7 @@ def _attach_entity_color(
     dxf["color_index"] = index
     dxf["true_color"] = true_color
     dxf["layer_handle"] = layer_handle
+    dxf["transparency"] = transparency
     dxf["resolved_color_index"] = resolved_index
     dxf["resolved_true_color"] = resolved_true_color
     return dxf
@@ -1338,7 +1371,7 @@ def _attach_entity_color(
 
 def _line_supplementary_handles(
     line_rows: list[tuple[int, float, float, float, float, float, float]],
-    entity_style_map: dict[int, tuple[int | None, int | None, int]],
+    entity_style_map: dict[int, tuple[int | None, int | None, int, int | None]],
     layer_color_overrides: dict[int, tuple[int, int | None]] | None,
 ) -> set[int]:
     if layer_color_overrides is None:
@@ -1394,8 +1427,8 @@ def _line_supplementary_handles(
 
 
 def _circle_supplementary_handles(
-    circle_rows: list[tuple[int, float, float, float, float]],
-    entity_style_map: dict[int, tuple[int | None, int | None, int]],
+    circle_rows: list[tuple[int, float, float, float, float, float, tuple[float, float, float]]],
+    entity_style_map: dict[int, tuple[int | None, int | None, int, int | None]],
     layer_color_overrides: dict[int, tuple[int, int | None]] | None,
 ) -> set[int]:
     if layer_color_overrides is None:
@@ -1408,7 +1441,7 @@ def _circle_supplementary_handles(
         return (round(x, 6), round(y, 6), round(z, 6))
 
     by_center: dict[tuple[float, float, float], list[tuple[int, float]]] = {}
-    for handle, cx, cy, cz, radius in circle_rows:
+    for handle, cx, cy, cz, radius, _thickness, _extrusion in circle_rows:
         style = entity_style_map.get(handle)
         if style is None or style[2] != source_layer:
             continue
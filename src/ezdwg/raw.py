@@ -1,7 +1,13 @@
 from ._core import (
+    Cancelled,
+    ConfiguredSource,
+    UnsupportedEntityError,
     decode_entity_styles,
+    decode_entity_line_styles,
     decode_layer_colors,
+    decode_layer_states,
     decode_arc_entities,
+    decode_arc_sweeps,
     decode_line_arc_circle_entities,
     decode_3dface_entities,
     decode_attrib_entities,
@@ -9,6 +15,7 @@ from ._core import (
     decode_circle_entities,
     decode_ellipse_entities,
     decode_spline_entities,
+    decode_spline_points,
     decode_dimension_entities,
     decode_dim_diameter_entities,
     decode_dim_ordinate_entities,
@@ -18,7 +25,17 @@ from ._core import (
     decode_dim_linear_entities,
     decode_dim_radius_entities,
     decode_insert_entities,
+    decode_insert_entities_with_block,
+    decode_inserts_with_attribs,
+    insert_transforms,
     decode_line_entities,
+    merge_lines_to_polylines,
+    aci_to_rgb,
+    entity_bounds,
+    spatial_query,
+    object_summaries,
+    list_inexact_decodes,
+    decode_entities_paginated,
     decode_lwpolyline_entities,
     decode_minsert_entities,
     decode_mtext_entities,
@@ -36,6 +53,19 @@ from ._core import (
     decode_solid_entities,
     decode_trace_entities,
     decode_shape_entities,
+    decode_proxy_entities,
+    list_images,
+    list_vports,
+    list_appids,
+    list_blocks,
+    drawing_stats,
+    modelspace_entity_handles,
+    list_xrefs,
+    decode_entity_xdata,
+    decode_multileaders,
+    decode_wipeouts,
+    decode_tables,
+    extract_all_text,
     decode_polyline_2d_entities,
     decode_polyline_2d_entities_interpreted,
     decode_polyline_2d_with_vertex_data,
@@ -47,37 +77,77 @@ from ._core import (
     decode_vertex_pface_entities,
     decode_vertex_pface_face_entities,
     decode_vertex_2d_entities,
+    analyze,
+    compute_crc,
     detect_version,
+    sniff,
     list_object_headers,
     list_object_headers_by_type,
     list_object_headers_with_type,
     list_object_map_entries,
     list_section_locators,
+    list_sections_detailed,
+    object_type_histogram,
     read_object_records_by_type,
+    read_object_records_by_handles,
     read_section_bytes,
+    read_section_bytes_by_name,
+    section_crcs,
+    aux_header,
+    measurement_units,
+    read_thumbnail,
+    set_decode_cache_dir,
+    configure,
 )
 
 __all__ = [
+    "Cancelled",
+    "ConfiguredSource",
+    "UnsupportedEntityError",
     "detect_version",
+    "sniff",
+    "set_decode_cache_dir",
+    "configure",
     "list_section_locators",
+    "list_sections_detailed",
     "read_section_bytes",
+    "read_section_bytes_by_name",
+    "section_crcs",
+    "aux_header",
+    "measurement_units",
+    "read_thumbnail",
+    "compute_crc",
+    "analyze",
     "list_object_map_entries",
     "list_object_headers",
     "list_object_headers_by_type",
     "list_object_headers_with_type",
+    "object_type_histogram",
     "read_object_records_by_type",
+    "read_object_records_by_handles",
     "decode_entity_styles",
+    "decode_entity_line_styles",
     "decode_layer_colors",
+    "decode_layer_states",
     "decode_line_entities",
+    "merge_lines_to_polylines",
+    "aci_to_rgb",
+    "entity_bounds",
+    "spatial_query",
+    "object_summaries",
+    "list_inexact_decodes",
+    "decode_entities_paginated",
     "decode_point_entities",
     "decode_3dface_entities",
     "decode_arc_entities",
+    "decode_arc_sweeps",
     "decode_line_arc_circle_entities",
     "decode_attrib_entities",
     "decode_attdef_entities",
     "decode_circle_entities",
     "decode_ellipse_entities",
     "decode_spline_entities",
+    "decode_spline_points",
     "decode_dimension_entities",
     "decode_dim_diameter_entities",
     "decode_dim_ordinate_entities",
@@ -93,6 +163,9 @@ __all__ = [
     "decode_tolerance_entities",
     "decode_mline_entities",
     "decode_insert_entities",
+    "decode_insert_entities_with_block",
+    "decode_inserts_with_attribs",
+    "insert_transforms",
     "decode_minsert_entities",
     "decode_lwpolyline_entities",
     "decode_polyline_3d_entities",
@@ -104,6 +177,19 @@ __all__ = [
     "decode_solid_entities",
     "decode_trace_entities",
     "decode_shape_entities",
+    "decode_proxy_entities",
+    "list_images",
+    "list_vports",
+    "list_appids",
+    "list_blocks",
+    "drawing_stats",
+    "modelspace_entity_handles",
+    "list_xrefs",
+    "decode_entity_xdata",
+    "decode_multileaders",
+    "decode_wipeouts",
+    "decode_tables",
+    "extract_all_text",
     "decode_polyline_2d_entities",
     "decode_polyline_2d_entities_interpreted",
     "decode_polyline_2d_with_vertices",
@@ -0,0 +1,2225 @@
+//! Plain-Rust entity queries, one per `raw.decode_*_entities` function.
+//!
+//! Each function walks the object index of an open [`Decoder`] and decodes
+//! every entity of one type into its struct from [`crate::entities`]. Error
+//! handling matches the Python API: on the versions where
+//! [`is_best_effort_compat_version`] holds, records that fail to parse are
+//! skipped; elsewhere the first failure is returned.
+//!
+//! ```no_run
+//! use _core::core::config::ParseConfig;
+//! use _core::dwg::decoder::Decoder;
+//! use _core::high_level;
+//!
+//! let bytes = std::fs::read("drawing.dwg")?;
+//! let decoder = Decoder::new(&bytes, ParseConfig::default())?;
+//! for line in high_level::decode_line_entities(&decoder, None)? {
+//!     println!("{:?} -> {:?}", line.start, line.end);
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::collections::HashMap;
+
+use crate::bit::BitReader;
+use crate::container::SectionInfo;
+use crate::core::config::SpaceFilter;
+use crate::core::diag::diag_event;
+use crate::core::error::{DwgError, ErrorKind};
+use crate::core::geometry::{self, Bounds2};
+use crate::core::progress::Progress;
+use crate::core::result::Result;
+use crate::core::spatial::GridIndex;
+use crate::dwg::decoder::Decoder;
+use crate::dwg::entity_dispatch::{
+    decode_appid_for_version, decode_arc_for_version, decode_block_header_for_version,
+    decode_circle_for_version, decode_ellipse_for_version, decode_entity_for_version,
+    decode_image_def_for_version, decode_image_for_version, decode_insert_for_version,
+    decode_line_for_version, decode_lwpolyline_for_version, decode_mleader_for_version,
+    decode_mtext_for_version, decode_point_for_version, decode_proxy_for_version,
+    decode_spline_for_version, decode_table_entry_name_for_version, decode_table_for_version,
+    decode_text_for_version, decode_vport_for_version, parse_common_entity_header_for_version,
+    parse_entity_layer_for_version, parse_entity_owner_for_version,
+    parse_object_header_for_version, skip_object_type_prefix, EntityOwner, ObjectHeader,
+    DECODED_ENTITY_TYPES,
+};
+use crate::dwg::version::DwgVersion;
+use crate::entities::eed::parse_eed_tolerant;
+use crate::entities::{
+    decode_seqend, ArcEntity, CircleEntity, DecodeMethod, DimensionCommonData, EllipseEntity,
+    Entity, ImageDefObject, ImageEntity, InsertEntity, LineEntity, LwPolylineEntity, MLeaderEntity,
+    MTextEntity, PointEntity, ProxyEntity, SeqendEntity, SplineEntity, TableEntity, TextEntity,
+    XDataValue,
+};
+use crate::objects::{
+    decode_layer_color_record, decode_layer_state_record, object_type_class, object_type_name,
+    parse_object_record, AppIdObject, BlockEntities, BlockHeaderObject, Handle, LayerState,
+    ObjectClass, ObjectIndex, ObjectRecord, ObjectRef, VportObject,
+};
+
+pub fn decode_line_entities(
+    decoder: &Decoder<'_>,
+    limit: Option<usize>,
+) -> Result<Vec<LineEntity>> {
+    collect_entities(decoder, 0x13, "LINE", limit, decode_line_for_version)
+}
+
+pub fn decode_point_entities(
+    decoder: &Decoder<'_>,
+    limit: Option<usize>,
+) -> Result<Vec<PointEntity>> {
+    collect_entities(
+        decoder,
+        0x1B,
+        "POINT",
+        limit,
+        |reader, version, header, handle| {
+            decode_point_for_version(reader, version, header, handle).inspect_err(|err| {
+                if std::env::var("EZDWG_DEBUG_POINT_DECODE")
+                    .ok()
+                    .is_some_and(|value| value != "0")
+                {
+                    eprintln!(
+                        "[point-decode] handle={} type=0x{:X} error={}",
+                        handle, header.type_code, err
+                    );
+                }
+            })
+        },
+    )
+}
+
+pub fn decode_arc_entities(decoder: &Decoder<'_>, limit: Option<usize>) -> Result<Vec<ArcEntity>> {
+    collect_entities(decoder, 0x11, "ARC", limit, decode_arc_for_version)
+}
+
+pub fn decode_circle_entities(
+    decoder: &Decoder<'_>,
+    limit: Option<usize>,
+) -> Result<Vec<CircleEntity>> {
+    collect_entities(decoder, 0x12, "CIRCLE", limit, decode_circle_for_version)
+}
+
+pub fn decode_ellipse_entities(
+    decoder: &Decoder<'_>,
+    limit: Option<usize>,
+) -> Result<Vec<EllipseEntity>> {
+    collect_entities(decoder, 0x23, "ELLIPSE", limit, decode_ellipse_for_version)
+}
+
+pub fn decode_spline_entities(
+    decoder: &Decoder<'_>,
+    limit: Option<usize>,
+) -> Result<Vec<SplineEntity>> {
+    collect_entities(decoder, 0x24, "SPLINE", limit, decode_spline_for_version)
+}
+
+pub fn decode_text_entities(
+    decoder: &Decoder<'_>,
+    limit: Option<usize>,
+) -> Result<Vec<TextEntity>> {
+    collect_entities(decoder, 0x01, "TEXT", limit, decode_text_for_version)
+}
+
+pub fn decode_mtext_entities(
+    decoder: &Decoder<'_>,
+    limit: Option<usize>,
+) -> Result<Vec<MTextEntity>> {
+    collect_entities(decoder, 0x2C, "MTEXT", limit, decode_mtext_for_version)
+}
+
+pub fn decode_lwpolyline_entities(
+    decoder: &Decoder<'_>,
+    limit: Option<usize>,
+) -> Result<Vec<LwPolylineEntity>> {
+    collect_entities(
+        decoder,
+        0x4D,
+        "LWPOLYLINE",
+        limit,
+        |reader, version, header, handle| {
+            decode_lwpolyline_for_version(reader, version, header, handle).inspect_err(|err| {
+                if std::env::var("EZDWG_DEBUG_LWPOLYLINE")
+                    .ok()
+                    .is_some_and(|value| value != "0")
+                {
+                    eprintln!(
+                        "[lwpolyline] skip handle={} type=0x{:X} err={}",
+                        handle, header.type_code, err
+                    );
+                }
+            })
+        },
+    )
+}
+
+pub fn decode_insert_entities(
+    decoder: &Decoder<'_>,
+    limit: Option<usize>,
+) -> Result<Vec<InsertEntity>> {
+    collect_entities(decoder, 0x07, "INSERT", limit, decode_insert_for_version)
+}
+
+/// INSERT entities with the name of the block each one inserts, read from
+/// its BLOCK_HEADER record; `None` when that record is missing or unnamed.
+pub fn decode_insert_entities_with_block(
+    decoder: &Decoder<'_>,
+    limit: Option<usize>,
+) -> Result<Vec<(InsertEntity, Option<String>)>> {
+    let names: HashMap<u64, String> = list_block_headers(decoder)?
+        .into_iter()
+        .filter_map(|header| Some((header.handle, header.name?)))
+        .collect();
+    Ok(decode_insert_entities(decoder, limit)?
+        .into_iter()
+        .map(|insert| {
+            let name = insert
+                .block_header_handle
+                .and_then(|handle| names.get(&handle).cloned());
+            (insert, name)
+        })
+        .collect())
+}
+
+/// ACAD_PROXY_ENTITY records, with `class_name` resolved from the class map.
+pub fn decode_proxy_entities(
+    decoder: &Decoder<'_>,
+    limit: Option<usize>,
+) -> Result<Vec<ProxyEntity>> {
+    let mut entities = collect_entities(
+        decoder,
+        0x1F2,
+        "ACAD_PROXY_ENTITY",
+        limit,
+        decode_proxy_for_version,
+    )?;
+    let best_effort = is_best_effort_compat_version(decoder);
+    let dynamic_types = load_dynamic_types(decoder, best_effort)?;
+    for entity in &mut entities {
+        entity.class_name = u16::try_from(entity.class_id)
+            .ok()
+            .and_then(|class_id| dynamic_types.get(&class_id).cloned());
+    }
+    Ok(entities)
+}
+
+pub fn decode_image_entities(
+    decoder: &Decoder<'_>,
+    limit: Option<usize>,
+) -> Result<Vec<ImageEntity>> {
+    collect_class_entities(decoder, "IMAGE", limit, decode_image_for_version)
+}
+
+pub fn decode_multileaders(
+    decoder: &Decoder<'_>,
+    limit: Option<usize>,
+) -> Result<Vec<MLeaderEntity>> {
+    collect_class_entities(decoder, "MULTILEADER", limit, decode_mleader_for_version)
+}
+
+/// WIPEOUT masks; they share the IMAGE record layout, see
+/// [`ImageEntity::boundary_wcs`] for the mask polygon.
+pub fn decode_wipeouts(decoder: &Decoder<'_>, limit: Option<usize>) -> Result<Vec<ImageEntity>> {
+    collect_class_entities(decoder, "WIPEOUT", limit, decode_image_for_version)
+}
+
+/// ACAD_TABLE entities with their cell text; see
+/// [`decode_table_for_version`] for the supported releases.
+pub fn decode_tables(decoder: &Decoder<'_>, limit: Option<usize>) -> Result<Vec<TableEntity>> {
+    collect_class_entities(decoder, "ACAD_TABLE", limit, decode_table_for_version)
+}
+
+pub fn decode_image_defs(decoder: &Decoder<'_>) -> Result<Vec<ImageDefObject>> {
+    collect_objects(
+        decoder,
+        None,
+        "IMAGEDEF",
+        None,
+        SpaceFilter::All,
+        decode_image_def_for_version,
+    )
+}
+
+/// VPORT table records; the one named `*ACTIVE` is the initial view.
+pub fn list_vports(decoder: &Decoder<'_>) -> Result<Vec<VportObject>> {
+    collect_objects(
+        decoder,
+        Some(0x41),
+        "VPORT",
+        None,
+        SpaceFilter::All,
+        decode_vport_for_version,
+    )
+}
+
+/// APPID table records: the applications XDATA can be keyed by.
+pub fn list_appids(decoder: &Decoder<'_>) -> Result<Vec<AppIdObject>> {
+    collect_objects(
+        decoder,
+        Some(0x43),
+        "APPID",
+        None,
+        SpaceFilter::All,
+        decode_appid_for_version,
+    )
+}
+
+/// BLOCK_HEADER table records, including the model and paper space layouts.
+pub fn list_block_headers(decoder: &Decoder<'_>) -> Result<Vec<BlockHeaderObject>> {
+    collect_objects(
+        decoder,
+        Some(0x31),
+        "BLOCK_HEADER",
+        None,
+        SpaceFilter::All,
+        decode_block_header_for_version,
+    )
+}
+
+/// Block headers that attach an external drawing, with its saved path.
+pub fn list_xrefs(decoder: &Decoder<'_>) -> Result<Vec<BlockHeaderObject>> {
+    let mut headers = list_block_headers(decoder)?;
+    headers.retain(|header| header.is_xref);
+    Ok(headers)
+}
+
+/// A block definition and the number of entities it owns directly.
+#[derive(Debug, Clone)]
+pub struct BlockSummary {
+    pub header: BlockHeaderObject,
+    /// Entities owned by the block itself, not counting its BLOCK/ENDBLK
+    /// markers or the vertices and attributes owned by those entities.
+    pub entity_count: usize,
+}
+
+/// Every block definition with its entity count. R2004+ headers record the
+/// count; for older files it is found by resolving each entity's owner, and
+/// model and paper space entities are counted against the headers named
+/// `*Model_Space` and `*Paper_Space`.
+pub fn list_blocks(decoder: &Decoder<'_>) -> Result<Vec<BlockSummary>> {
+    let headers = list_block_headers(decoder)?;
+    let mut counts: HashMap<u64, usize> = HashMap::new();
+    let (mut model_count, mut paper_count) = (0, 0);
+    if headers
+        .iter()
+        .any(|header| header.owned_object_count.is_none())
+    {
+        let best_effort = is_best_effort_compat_version(decoder);
+        let dynamic_types = load_dynamic_types(decoder, best_effort)?;
+        let index = decoder.build_object_index()?;
+        for obj in index.objects.iter() {
+            let Some((record, header)) = parse_record_and_header(decoder, obj.offset, best_effort)?
+            else {
+                continue;
+            };
+            if !is_block_member_type(header.type_code, &dynamic_types) {
+                continue;
+            }
+            let mut reader = record.bit_reader();
+            let owner = skip_object_type_prefix(&mut reader, decoder.version()).and_then(|_| {
+                parse_entity_owner_for_version(
+                    &mut reader,
+                    decoder.version(),
+                    &header,
+                    obj.handle.0,
+                )
+            });
+            match owner {
+                Ok(EntityOwner::Block(owner)) => *counts.entry(owner).or_default() += 1,
+                Ok(EntityOwner::ModelSpace) => model_count += 1,
+                Ok(EntityOwner::PaperSpace) => paper_count += 1,
+                Err(err) if best_effort || is_recoverable_decode_error(&err) => {
+                    diag_event!(
+                        "entity",
+                        "owner unresolved handle={:#X}: {err}",
+                        obj.handle.0
+                    );
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+    Ok(headers
+        .into_iter()
+        .map(|header| {
+            let entity_count = match (header.owned_object_count, header.name.as_deref()) {
+                (Some(count), _) => count as usize,
+                (None, Some(name)) if name.eq_ignore_ascii_case("*Model_Space") => model_count,
+                (None, Some(name)) if name.eq_ignore_ascii_case("*Paper_Space") => paper_count,
+                (None, _) => counts.get(&header.handle).copied().unwrap_or(0),
+            };
+            BlockSummary {
+                header,
+                entity_count,
+            }
+        })
+        .collect())
+}
+
+/// Handles of the entities `*Model_Space` owns, read from its block header
+/// without decoding any entity. R13-R2000 headers only name the first and
+/// last entity; of the objects between them in handle order, those with a
+/// model-space entity mode are returned.
+pub fn modelspace_entity_handles(decoder: &Decoder<'_>) -> Result<Vec<u64>> {
+    let model_space = list_block_headers(decoder)?
+        .into_iter()
+        .find(|header| {
+            header
+                .name
+                .as_deref()
+                .is_some_and(|name| name.eq_ignore_ascii_case("*Model_Space"))
+        })
+        .ok_or_else(|| DwgError::new(ErrorKind::Resolve, "no *Model_Space block header"))?;
+    match model_space.entities {
+        Some(BlockEntities::Owned(handles)) => Ok(handles),
+        Some(BlockEntities::Chain { first: 0, .. }) => Ok(Vec::new()),
+        Some(BlockEntities::Chain { first, last }) => {
+            modelspace_chain_handles(decoder, first, last)
+        }
+        None => Err(DwgError::new(
+            ErrorKind::Decode,
+            format!(
+                "*Model_Space block header {:#X} has no readable entity handles",
+                model_space.handle
+            ),
+        )),
+    }
+}
+
+fn modelspace_chain_handles(decoder: &Decoder<'_>, first: u64, last: u64) -> Result<Vec<u64>> {
+    let best_effort = is_best_effort_compat_version(decoder);
+    let dynamic_types = load_dynamic_types(decoder, best_effort)?;
+    let index = decoder.build_object_index()?;
+    let mut chain: Vec<&ObjectRef> = index
+        .objects
+        .iter()
+        .filter(|obj| (first..=last).contains(&obj.handle.0))
+        .collect();
+    chain.sort_by_key(|obj| obj.handle.0);
+    let mut handles = Vec::new();
+    for obj in chain {
+        let Some((record, header)) = parse_record_and_header(decoder, obj.offset, best_effort)?
+        else {
+            continue;
+        };
+        if !is_block_member_type(header.type_code, &dynamic_types) {
+            continue;
+        }
+        let mut reader = record.bit_reader();
+        let owner = skip_object_type_prefix(&mut reader, decoder.version()).and_then(|_| {
+            parse_entity_owner_for_version(&mut reader, decoder.version(), &header, obj.handle.0)
+        });
+        match owner {
+            Ok(EntityOwner::ModelSpace) => handles.push(obj.handle.0),
+            Ok(_) => {}
+            Err(err) if best_effort || is_recoverable_decode_error(&err) => {
+                diag_event!(
+                    "entity",
+                    "owner unresolved handle={:#X}: {err}",
+                    obj.handle.0
+                );
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(handles)
+}
+
+/// Entity types a block lists as its own: not its BLOCK/ENDBLK markers.
+fn is_block_member_type(type_code: u16, dynamic_types: &HashMap<u16, String>) -> bool {
+    match dynamic_types.get(&type_code) {
+        Some(name) => is_dynamic_entity_name(name),
+        None => {
+            object_type_class(type_code) == ObjectClass::Entity && !matches!(type_code, 0x04 | 0x05)
+        }
+    }
+}
+
+/// IMAGE entities paired with the IMAGEDEF each one references, if found.
+pub fn list_images(decoder: &Decoder<'_>) -> Result<Vec<(ImageEntity, Option<ImageDefObject>)>> {
+    let images = decode_image_entities(decoder, None)?;
+    if images.is_empty() {
+        return Ok(Vec::new());
+    }
+    let definitions: HashMap<u64, ImageDefObject> = decode_image_defs(decoder)?
+        .into_iter()
+        .map(|definition| (definition.handle, definition))
+        .collect();
+    Ok(images
+        .into_iter()
+        .map(|image| {
+            let definition = image
+                .imagedef_handle
+                .and_then(|handle| definitions.get(&handle).cloned());
+            (image, definition)
+        })
+        .collect())
+}
+
+/// World XY bounding boxes of LINE, POINT, ARC, CIRCLE, ELLIPSE, LWPOLYLINE
+/// and SPLINE entities. Ellipses use the full ellipse and splines their
+/// control (or fit) points, so those boxes can be larger than the curve.
+pub fn entity_bounds(decoder: &Decoder<'_>) -> Result<Vec<(u64, Bounds2)>> {
+    let xy = |point: (f64, f64, f64)| (point.0, point.1);
+    let mut result = Vec::new();
+    for line in decode_line_entities(decoder, None)? {
+        result.push((line.handle, Bounds2::new(xy(line.start), xy(line.end))));
+    }
+    for point in decode_point_entities(decoder, None)? {
+        let location = xy(point.location);
+        result.push((point.handle, Bounds2::new(location, location)));
+    }
+    for arc in decode_arc_entities(decoder, None)? {
+        let local =
+            geometry::arc_bounds_2d(xy(arc.center), arc.radius, arc.angle_start, arc.angle_end);
+        result.push((
+            arc.handle,
+            geometry::ocs_bounds_to_wcs(arc.extrusion, local, arc.center.2),
+        ));
+    }
+    for circle in decode_circle_entities(decoder, None)? {
+        let (x, y) = xy(circle.center);
+        let local = Bounds2::new(
+            (x - circle.radius, y - circle.radius),
+            (x + circle.radius, y + circle.radius),
+        );
+        result.push((
+            circle.handle,
+            geometry::ocs_bounds_to_wcs(circle.extrusion, local, circle.center.2),
+        ));
+    }
+    for ellipse in decode_ellipse_entities(decoder, None)? {
+        result.push((
+            ellipse.handle,
+            geometry::ellipse_bounds(
+                ellipse.center,
+                ellipse.major_axis,
+                ellipse.extrusion,
+                ellipse.axis_ratio,
+            ),
+        ));
+    }
+    for polyline in decode_lwpolyline_entities(decoder, None)? {
+        let vertices = &polyline.vertices;
+        let closed = polyline.flags & 0x200 != 0;
+        let segment_count = match vertices.len() {
+            0 | 1 => 0,
+            count if closed => count,
+            count => count - 1,
+        };
+        let local = (0..segment_count)
+            .map(|index| {
+                let bulge = polyline.bulges.get(index).copied().unwrap_or(0.0);
+                geometry::bulge_bounds_2d(
+                    vertices[index],
+                    vertices[(index + 1) % vertices.len()],
+                    bulge,
+                )
+            })
+            .reduce(Bounds2::union)
+            .or_else(|| Bounds2::from_points(vertices.iter().copied()));
+        if let Some(local) = local {
+            result.push((
+                polyline.handle,
+                geometry::ocs_bounds_to_wcs(polyline.extrusion, local, polyline.elevation),
+            ));
+        }
+    }
+    for spline in decode_spline_entities(decoder, None)? {
+        let points = if spline.control_points.is_empty() {
+            &spline.fit_points
+        } else {
+            &spline.control_points
+        };
+        if let Some(bounds) = Bounds2::from_points(points.iter().copied().map(xy)) {
+            result.push((spline.handle, bounds));
+        }
+    }
+    Ok(result)
+}
+
+/// Handles, in ascending order, of the [`entity_bounds`] entities whose box
+/// intersects the rectangle spanned by `min` and `max`.
+pub fn spatial_query(decoder: &Decoder<'_>, min: (f64, f64), max: (f64, f64)) -> Result<Vec<u64>> {
+    let index = GridIndex::new(entity_bounds(decoder)?);
+    let mut handles: Vec<u64> = index
+        .query(&Bounds2::new(min, max))
+        .into_iter()
+        .copied()
+        .collect();
+    handles.sort_unstable();
+    Ok(handles)
+}
+
+/// One row of a drawing's object table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectSummary {
+    pub handle: u64,
+    pub type_name: String,
+    /// Entity LAYER record handle; `None` for non-graphical objects.
+    pub layer_handle: Option<u64>,
+    /// Entity layer name; `None` for non-graphical objects and where
+    /// [`collect_table_entry_names`] cannot resolve names (R2007+).
+    pub layer_name: Option<String>,
+    /// World XY box, for the entity types [`entity_bounds`] covers.
+    pub bounds: Option<Bounds2>,
+}
+
+/// Every object in object-index order with its type, layer and bounding
+/// box, for table and tree views of a drawing.
+pub fn object_summaries(decoder: &Decoder<'_>) -> Result<Vec<ObjectSummary>> {
+    let best_effort = is_best_effort_compat_version(decoder);
+    let dynamic_types = load_dynamic_types(decoder, best_effort)?;
+    let index = decoder.build_object_index()?;
+    let layer_names = collect_table_entry_names(
+        decoder,
+        &index.objects,
+        &dynamic_types,
+        best_effort,
+        (0x33, "LAYER"),
+    )?;
+    let bounds: HashMap<u64, Bounds2> = entity_bounds(decoder)?.into_iter().collect();
+    let mut result = Vec::with_capacity(index.objects.len());
+    for obj in index.objects.iter() {
+        let Some((record, header)) = parse_record_and_header(decoder, obj.offset, best_effort)?
+        else {
+            continue;
+        };
+        let (type_name, is_entity) = match dynamic_types.get(&header.type_code) {
+            Some(name) => (name.clone(), is_dynamic_entity_name(name)),
+            None => (
+                object_type_name(header.type_code),
+                object_type_class(header.type_code) == ObjectClass::Entity,
+            ),
+        };
+        let mut layer_handle = None;
+        if is_entity {
+            let mut reader = record.bit_reader();
+            let layer = skip_object_type_prefix(&mut reader, decoder.version()).and_then(|_| {
+                parse_entity_layer_for_version(
+                    &mut reader,
+                    decoder.version(),
+                    &header,
+                    obj.handle.0,
+                )
+            });
+            match layer {
+                Ok(layer) => layer_handle = Some(layer),
+                Err(err) if best_effort || is_recoverable_decode_error(&err) => {
+                    diag_event!(
+                        "entity",
+                        "no layer for {type_name} handle={:#X}: {err}",
+                        obj.handle.0
+                    );
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        result.push(ObjectSummary {
+            handle: obj.handle.0,
+            type_name,
+            layer_handle,
+            layer_name: layer_handle.and_then(|layer| layer_names.get(&layer).cloned()),
+            bounds: bounds.get(&obj.handle.0).copied(),
+        });
+    }
+    Ok(result)
+}
+
+/// Linetype scale and lineweight from an entity's common header.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EntityLineStyle {
+    pub handle: u64,
+    pub ltype_scale: f64,
+    /// Raw lineweight code; `None` for R13/R14, which do not store one.
+    pub lineweight: Option<i8>,
+}
+
+/// The common-header linetype scale and lineweight of every entity in the
+/// configured space, in object-index order.
+pub fn entity_line_styles(
+    decoder: &Decoder<'_>,
+    limit: Option<usize>,
+) -> Result<Vec<EntityLineStyle>> {
+    let best_effort = is_best_effort_compat_version(decoder);
+    let dynamic_types = load_dynamic_types(decoder, best_effort)?;
+    let index = decoder.build_object_index()?;
+    let objects = scope_objects(decoder, index.objects)?;
+    let mut result = Vec::new();
+    for obj in objects {
+        if limit.is_some_and(|limit| result.len() >= limit) {
+            break;
+        }
+        let Some((record, header)) = parse_record_and_header(decoder, obj.offset, best_effort)?
+        else {
+            continue;
+        };
+        let is_entity = match dynamic_types.get(&header.type_code) {
+            Some(name) => is_dynamic_entity_name(name),
+            None => object_type_class(header.type_code) == ObjectClass::Entity,
+        };
+        if !is_entity {
+            continue;
+        }
+        let mut reader = record.bit_reader();
+        let common = skip_object_type_prefix(&mut reader, decoder.version()).and_then(|_| {
+            parse_common_entity_header_for_version(&mut reader, decoder.version(), &header)
+        });
+        match common {
+            Ok(common) => result.push(EntityLineStyle {
+                handle: obj.handle.0,
+                ltype_scale: common.ltype_scale,
+                lineweight: common.lineweight,
+            }),
+            Err(err) if best_effort || is_recoverable_decode_error(&err) => {
+                diag_event!("entity", "skip handle={:#X}: {err}", obj.handle.0);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(result)
+}
+
+/// A LAYER table record with its name, color and state.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayerRecord {
+    pub handle: u64,
+    /// `None` when the name could not be read.
+    pub name: Option<String>,
+    /// Color as the record stores it; R2004+ records often keep an indexed
+    /// color as index 0 with the index in `true_color`.
+    pub color_index: u16,
+    pub true_color: Option<u32>,
+    /// `None` when the flags could not be read.
+    pub state: Option<LayerState>,
+}
+
+/// Every LAYER record whose color decodes, in object-index order.
+pub fn layer_records(decoder: &Decoder<'_>) -> Result<Vec<LayerRecord>> {
+    let best_effort = is_best_effort_compat_version(decoder);
+    let dynamic_types = load_dynamic_types(decoder, best_effort)?;
+    let index = decoder.build_object_index()?;
+    let mut result = Vec::new();
+    for obj in index.objects.iter() {
+        let Some((record, header)) = parse_record_and_header(decoder, obj.offset, best_effort)?
+        else {
+            continue;
+        };
+        if !matches_type_name(header.type_code, 0x33, "LAYER", &dynamic_types) {
+            continue;
+        }
+        let mut reader = record.bit_reader();
+        let decoded = skip_object_type_prefix(&mut reader, decoder.version())
+            .and_then(|_| decode_layer_color_record(&mut reader, decoder.version(), obj.handle.0));
+        let (handle, color_index, true_color) = match decoded {
+            Ok(decoded) => decoded,
+            Err(err) if best_effort || is_recoverable_decode_error(&err) => continue,
+            Err(err) => return Err(err),
+        };
+        let mut reader = record.bit_reader();
+        let state = skip_object_type_prefix(&mut reader, decoder.version())
+            .and_then(|_| decode_layer_state_record(&mut reader, decoder.version()))
+            .map(|(_, state, _)| state)
+            .ok();
+        // Every symbol table record starts with its entry name, which the
+        // APPID decoder reads for any of them, R2007+ string streams included.
+        let mut reader = record.bit_reader();
+        let name = skip_object_type_prefix(&mut reader, decoder.version())
+            .and_then(|_| {
+                decode_appid_for_version(&mut reader, decoder.version(), &header, obj.handle.0)
+            })
+            .ok()
+            .and_then(|appid| appid.name);
+        result.push(LayerRecord {
+            handle,
+            name,
+            color_index,
+            true_color,
+            state,
+        });
+    }
+    Ok(result)
+}
+
+/// Whole-file counts for dashboards over drawing archives.
+#[derive(Debug, Clone)]
+pub struct DrawingStats {
+    pub version: DwgVersion,
+    /// Objects in the object map, graphical or not.
+    pub object_count: usize,
+    /// Graphical entities, vertices, attributes and block markers included.
+    pub entity_count: usize,
+    pub layer_count: usize,
+    /// BLOCK_HEADER records, `*Model_Space` and `*Paper_Space` included.
+    pub block_count: usize,
+    /// Container sections; R13-R15 files store every one uncompressed.
+    pub sections: Vec<SectionInfo>,
+    pub section_bytes: u64,
+    pub compressed_section_bytes: u64,
+    /// Entity types present that no decoder reads, with their counts, most
+    /// frequent first. BLOCK and ENDBLK markers are not listed.
+    pub unsupported_types: Vec<(String, usize)>,
+}
+
+/// Object, entity, layer and block counts, the section table and the entity
+/// types left undecoded, from one walk of the object map that reads only
+/// record headers.
+pub fn drawing_stats(decoder: &Decoder<'_>) -> Result<DrawingStats> {
+    let best_effort = is_best_effort_compat_version(decoder);
+    let sections = decoder.list_sections()?;
+    let dynamic_types = load_dynamic_types(decoder, best_effort)?;
+    let index = decoder.build_object_index()?;
+    let objects_data = decoder.objects_data()?;
+    let (mut entity_count, mut layer_count, mut block_count) = (0, 0, 0);
+    let mut unsupported: HashMap<String, usize> = HashMap::new();
+    for item in index.headers(&objects_data, decoder.version()) {
+        let header = match item {
+            Ok((_, header)) => header,
+            Err(_) if best_effort => continue,
+            Err(err) => return Err(err),
+        };
+        let (type_name, is_entity) = match dynamic_types.get(&header.type_code) {
+            Some(name) => (name.clone(), is_dynamic_entity_name(name)),
+            None => (
+                object_type_name(header.type_code),
+                object_type_class(header.type_code) == ObjectClass::Entity,
+            ),
+        };
+        if is_entity {
+            entity_count += 1;
+            // BLOCK and ENDBLK only bracket a block's entities.
+            let is_marker = matches!(type_name.as_str(), "BLOCK" | "ENDBLK");
+            if !is_marker && !DECODED_ENTITY_TYPES.contains(&type_name.as_str()) {
+                *unsupported.entry(type_name).or_default() += 1;
+            }
+        } else if type_name == "LAYER" {
+            layer_count += 1;
+        } else if type_name == "BLOCK_HEADER" {
+            block_count += 1;
+        }
+    }
+    let mut unsupported_types: Vec<(String, usize)> = unsupported.into_iter().collect();
+    unsupported_types.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    Ok(DrawingStats {
+        version: decoder.version().clone(),
+        object_count: index.objects.len(),
+        entity_count,
+        layer_count,
+        block_count,
+        section_bytes: sections.iter().map(|section| section.size).sum(),
+        compressed_section_bytes: sections.iter().map(|section| section.compressed_size).sum(),
+        sections,
+        unsupported_types,
+    })
+}
+
+/// Every entity in `index` decoded straight from `buffer`, an objects
+/// section taken out beforehand (`AcDb:AcDbObjects` for R2004+; for R13-R15,
+/// whose records sit in place, the whole file). Types numbered by the classes
+/// section are skipped, as only that section names them, and so are records
+/// that fail to decode.
+pub fn decode_entities_from_objects_buffer(
+    buffer: &[u8],
+    index: &ObjectIndex,
+    version: &DwgVersion,
+) -> Result<Vec<(Handle, Entity)>> {
+    if matches!(version, DwgVersion::R13 | DwgVersion::Unknown(_)) {
+        return Err(DwgError::new(
+            ErrorKind::Unsupported,
+            format!("unsupported DWG version: {}", version.as_str()),
+        ));
+    }
+    let mut result = Vec::new();
+    for obj in index.objects.iter() {
+        let decoded = parse_object_record(buffer, obj.offset).and_then(|record| {
+            let header = parse_object_header_for_version(&record, version)?;
+            if object_type_class(header.type_code) != ObjectClass::Entity {
+                return Ok(None);
+            }
+            let mut reader = record.bit_reader();
+            skip_object_type_prefix(&mut reader, version)?;
+            let type_name = object_type_name(header.type_code);
+            decode_entity_for_version(&mut reader, version, &header, obj.handle.0, &type_name)
+                .map(Some)
+        });
+        match decoded {
+            Ok(Some(entity)) => result.push((obj.handle, entity)),
+            Ok(None) => {}
+            Err(err) if is_recoverable_decode_error(&err) => {
+                diag_event!("entity", "skip handle={:#X}: {err}", obj.handle.0);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(result)
+}
+
+/// Entities among the `count` object-map entries from `offset` on, for
+/// viewers that load a large drawing a page at a time; the
+/// [`ObjectIndex::len`] of [`Decoder::build_object_index`] bounds the
+/// pages. Records that fail to decode are skipped, so a page can hold fewer
+/// than `count` entities, or none.
+pub fn decode_entities_paginated(
+    decoder: &Decoder<'_>,
+    offset: usize,
+    count: usize,
+) -> Result<Vec<(Handle, Entity)>> {
+    let best_effort = is_best_effort_compat_version(decoder);
+    let mut result = Vec::new();
+    for item in decoder.entities_in_window(offset, count) {
+        match item {
+            Ok(entity) => result.push(entity),
+            Err(err) if best_effort || is_recoverable_decode_error(&err) => {
+                diag_event!("entity", "skip paged record: {err}");
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(result)
+}
+
+/// Handle, type name and [`DecodeMethod`] of every dimension, ATTRIB,
+/// ATTDEF and MTEXT whose layout was not read exactly, in object-map order,
+/// so batch jobs can flag those decodes for review. Records that fail to
+/// decode, including dimensions rejected by `strict_dimensions`, are left
+/// out.
+pub fn inexact_decodes(decoder: &Decoder<'_>) -> Result<Vec<(u64, &'static str, DecodeMethod)>> {
+    let best_effort = is_best_effort_compat_version(decoder);
+    let mut result = Vec::new();
+    for item in decoder.entities_filtered(|name| {
+        matches!(name, "MTEXT" | "ATTRIB" | "ATTDEF") || name.starts_with("DIM_")
+    }) {
+        let entity = match item {
+            Ok((_, entity)) => entity,
+            Err(err) if best_effort || is_recoverable_decode_error(&err) => continue,
+            Err(err) => return Err(err),
+        };
+        let method = entity.decode_method();
+        if method != DecodeMethod::Exact {
+            result.push((entity.handle(), entity.type_name(), method));
+        }
+    }
+    Ok(result)
+}
+
+/// Extended data attached to one entity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EntityXData {
+    pub handle: u64,
+    pub type_name: String,
+    /// `(APPID handle, values)` per registered application.
+    pub xdata: Vec<(u64, Vec<XDataValue>)>,
+}
+
+/// Entities carrying XDATA, in object-index order. Entities without any
+/// are left out. A block's values end at its first malformed group.
+pub fn decode_entity_xdata(
+    decoder: &Decoder<'_>,
+    limit: Option<usize>,
+) -> Result<Vec<EntityXData>> {
+    decode_entity_xdata_with_progress(decoder, limit, &mut Progress::none())
+}
+
+/// [`decode_entity_xdata`] reporting objects walked to `progress`; a stop
+/// request returns the entities found so far.
+pub fn decode_entity_xdata_with_progress(
+    decoder: &Decoder<'_>,
+    limit: Option<usize>,
+    progress: &mut Progress<'_>,
+) -> Result<Vec<EntityXData>> {
+    let best_effort = is_best_effort_compat_version(decoder);
+    let dynamic_types = load_dynamic_types(decoder, best_effort)?;
+    let index = decoder.build_object_index()?;
+    let filter = decoder.config().space_filter;
+    let mut scope = SpaceScope::new(decoder, filter, &index.objects, &dynamic_types)?;
+    let unicode = !matches!(
+        decoder.version(),
+        DwgVersion::R14 | DwgVersion::R2000 | DwgVersion::R2004
+    );
+    let mut result = Vec::new();
+    let total = index.objects.len();
+    for (done, obj) in index.objects.iter().enumerate() {
+        if !progress.tick(done, total) {
+            break;
+        }
+        let Some((record, header)) = parse_record_and_header(decoder, obj.offset, best_effort)?
+        else {
+            continue;
+        };
+        let type_name = match dynamic_types.get(&header.type_code) {
+            Some(name) if is_dynamic_entity_name(name) => name.clone(),
+            Some(_) => continue,
+            None if object_type_class(header.type_code) == ObjectClass::Entity => {
+                object_type_name(header.type_code)
+            }
+            None => continue,
+        };
+        let mut reader = record.bit_reader();
+        let decoded = skip_object_type_prefix(&mut reader, decoder.version()).and_then(|_| {
+            if scope
+                .as_mut()
+                .is_some_and(|scope| !scope.keeps(&reader, &header, obj.handle.0))
+            {
+                return Ok(None);
+            }
+            parse_common_entity_header_for_version(&mut reader, decoder.version(), &header)
+                .map(Some)
+        });
+        let common = match decoded {
+            Ok(Some(common)) => common,
+            Ok(None) => continue,
+            Err(err) if best_effort => {
+                diag_event!(
+                    "entity",
+                    "skip {type_name} handle={:#X}: {err}",
+                    obj.handle.0
+                );
+                continue;
+            }
+            Err(err) => return Err(err),
+        };
+        if common.xdata.is_empty() {
+            continue;
+        }
+        let xdata = common
+            .xdata
+            .iter()
+            .map(|(app_handle, data)| (*app_handle, parse_eed_tolerant(data, unicode)))
+            .collect();
+        result.push(EntityXData {
+            handle: obj.handle.0,
+            type_name,
+            xdata,
+        });
+        if limit.is_some_and(|limit| result.len() >= limit) {
+            break;
+        }
+    }
+    progress.finish(total);
+    Ok(result)
+}
+
+fn dimension_text(common: DimensionCommonData) -> (u64, (f64, f64, f64), Vec<Option<String>>) {
+    (
+        common.layer_handle,
+        common.text_midpoint,
+        vec![Some(common.user_text)],
+    )
+}
+
+/// Class-section entity types that share the common entity header.
+fn is_dynamic_entity_name(name: &str) -> bool {
+    matches!(
+        name,
+        "LWPOLYLINE" | "HATCH" | "IMAGE" | "MULTILEADER" | "WIPEOUT" | "ACAD_TABLE"
+    )
+}
+
+/// One string carried by a text-bearing entity.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextItem {
+    pub handle: u64,
+    pub type_name: &'static str,
+    pub layer_handle: u64,
+    /// Raw value; MTEXT and dimension text keep their format codes.
+    pub text: String,
+    pub insertion: (f64, f64, f64),
+}
+
+/// Every string of TEXT, MTEXT, ATTRIB, ATTDEF (tag, then prompt) and
+/// dimension user text, in object-index order. Empty strings are dropped.
+pub fn extract_all_text(decoder: &Decoder<'_>) -> Result<Vec<TextItem>> {
+    extract_all_text_with_progress(decoder, &mut Progress::none())
+}
+
+/// [`extract_all_text`] reporting objects walked to `progress`; a stop
+/// request returns the strings found so far.
+pub fn extract_all_text_with_progress(
+    decoder: &Decoder<'_>,
+    progress: &mut Progress<'_>,
+) -> Result<Vec<TextItem>> {
+    let best_effort = is_best_effort_compat_version(decoder);
+    let dynamic_types = load_dynamic_types(decoder, best_effort)?;
+    let index = decoder.build_object_index()?;
+    let filter = decoder.config().space_filter;
+    let mut scope = SpaceScope::new(decoder, filter, &index.objects, &dynamic_types)?;
+    let mut result = Vec::new();
+    let total = index.objects.len();
+    for (done, obj) in index.objects.iter().enumerate() {
+        if !progress.tick(done, total) {
+            break;
+        }
+        let Some((record, header)) = parse_record_and_header(decoder, obj.offset, best_effort)?
+        else {
+            continue;
+        };
+        let type_name = dynamic_types
+            .get(&header.type_code)
+            .cloned()
+            .unwrap_or_else(|| crate::objects::object_type_name(header.type_code));
+        if !matches!(type_name.as_str(), "TEXT" | "MTEXT" | "ATTRIB" | "ATTDEF")
+            && !type_name.starts_with("DIM_")
+        {
+            continue;
+        }
+        let mut reader = record.bit_reader();
+        let decoded = skip_object_type_prefix(&mut reader, decoder.version()).and_then(|_| {
+            if scope
+                .as_mut()
+                .is_some_and(|scope| !scope.keeps(&reader, &header, obj.handle.0))
+            {
+                return Ok(None);
+            }
+            decode_entity_for_version(
+                &mut reader,
+                decoder.version(),
+                &header,
+                obj.handle.0,
+                &type_name,
+            )
+            .and_then(|entity| decoder.check_dimension_layout(entity))
+            .map(Some)
+        });
+        let entity = match decoded {
+            Ok(Some(entity)) => entity,
+            Ok(None) => continue,
+            Err(err) if best_effort => {
+                diag_event!(
+                    "entity",
+                    "skip {type_name} handle={:#X}: {err}",
+                    obj.handle.0
+                );
+                continue;
+            }
+            Err(err) => return Err(err),
+        };
+        let type_name = entity.type_name();
+        let (layer_handle, insertion, texts) = match entity {
+            Entity::Text(text) => (text.layer_handle, text.insertion, vec![Some(text.text)]),
+            Entity::MText(mtext) => (mtext.layer_handle, mtext.insertion, vec![Some(mtext.text)]),
+            Entity::Attrib(attrib) => (
+                attrib.layer_handle,
+                attrib.insertion,
+                vec![Some(attrib.text)],
+            ),
+            Entity::AttDef(attdef) => (
+                attdef.layer_handle,
+                attdef.insertion,
+                vec![attdef.tag, attdef.prompt],
+            ),
+            Entity::DimOrdinate(dim)
+            | Entity::DimLinear(dim)
+            | Entity::DimAligned(dim)
+            | Entity::DimRadius(dim)
+            | Entity::DimDiameter(dim) => dimension_text(dim.common),
+            Entity::DimAng3Pt(dim) => dimension_text(dim.common),
+            Entity::DimAng2Ln(dim) => dimension_text(dim.common),
+            _ => continue,
+        };
+        for text in texts.into_iter().flatten() {
+            if text.is_empty() {
+                continue;
+            }
+            result.push(TextItem {
+                handle: obj.handle.0,
+                type_name,
+                layer_handle,
+                text,
+                insertion,
+            });
+        }
+    }
+    progress.finish(total);
+    Ok(result)
+}
+
+/// Decode every object whose type is `builtin_code`, or a class registered
+/// under `name`, stopping once `limit` entities have been collected.
+fn collect_entities<T>(
+    decoder: &Decoder<'_>,
+    builtin_code: u16,
+    name: &str,
+    limit: Option<usize>,
+    decode: impl Fn(&mut BitReader<'_>, &DwgVersion, &ObjectHeader, u64) -> Result<T>,
+) -> Result<Vec<T>> {
+    let filter = decoder.config().space_filter;
+    collect_objects(decoder, Some(builtin_code), name, limit, filter, decode)
+}
+
+/// [`collect_entities`] for entity types that only exist as registered
+/// classes (IMAGE, MULTILEADER, ...) and have no fixed type code.
+fn collect_class_entities<T>(
+    decoder: &Decoder<'_>,
+    name: &str,
+    limit: Option<usize>,
+    decode: impl Fn(&mut BitReader<'_>, &DwgVersion, &ObjectHeader, u64) -> Result<T>,
+) -> Result<Vec<T>> {
+    let filter = decoder.config().space_filter;
+    collect_objects(decoder, None, name, limit, filter, decode)
+}
+
+fn collect_objects<T>(
+    decoder: &Decoder<'_>,
+    builtin_code: Option<u16>,
+    name: &str,
+    limit: Option<usize>,
+    filter: SpaceFilter,
+    decode: impl Fn(&mut BitReader<'_>, &DwgVersion, &ObjectHeader, u64) -> Result<T>,
+) -> Result<Vec<T>> {
+    let best_effort = is_best_effort_compat_version(decoder);
+    let dynamic_types = load_dynamic_types(decoder, best_effort)?;
+    if builtin_code.is_none() && !dynamic_types.values().any(|class| class == name) {
+        return Ok(Vec::new());
+    }
+    let index = decoder.build_object_index()?;
+    let mut scope = SpaceScope::new(decoder, filter, &index.objects, &dynamic_types)?;
+    let mut result = Vec::new();
+    for obj in index.objects.iter() {
+        let Some((record, header)) = parse_record_and_header(decoder, obj.offset, best_effort)?
+        else {
+            continue;
+        };
+        let matches = match builtin_code {
+            Some(code) => matches_type_name(header.type_code, code, name, &dynamic_types),
+            None => dynamic_types
+                .get(&header.type_code)
+                .is_some_and(|class| class == name),
+        };
+        if !matches {
+            continue;
+        }
+        let mut reader = record.bit_reader();
+        if let Err(err) = skip_object_type_prefix(&mut reader, decoder.version()) {
+            if best_effort {
+                continue;
+            }
+            return Err(err);
+        }
+        if let Some(scope) = scope.as_mut() {
+            if !scope.keeps(&reader, &header, obj.handle.0) {
+                continue;
+            }
+        }
+        let entity = match decode(&mut reader, decoder.version(), &header, obj.handle.0) {
+            Ok(entity) => entity,
+            Err(err) if best_effort => {
+                diag_event!("entity", "skip {name} handle={:#X}: {err}", obj.handle.0);
+                continue;
+            }
+            Err(err) => return Err(err),
+        };
+        result.push(entity);
+        if let Some(limit) = limit {
+            if result.len() >= limit {
+                break;
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// `objects` without the entities the decoder's [`SpaceFilter`] excludes,
+/// for walks that match types themselves instead of going through
+/// [`collect_entities`]. Non-entity objects and records that do not parse
+/// are kept for the walk to handle.
+pub fn scope_objects(decoder: &Decoder<'_>, objects: Vec<ObjectRef>) -> Result<Vec<ObjectRef>> {
+    let filter = decoder.config().space_filter;
+    if filter == SpaceFilter::All {
+        return Ok(objects);
+    }
+    let best_effort = is_best_effort_compat_version(decoder);
+    let dynamic_types = load_dynamic_types(decoder, best_effort)?;
+    let Some(mut scope) = SpaceScope::new(decoder, filter, &objects, &dynamic_types)? else {
+        return Ok(objects);
+    };
+    let mut kept = Vec::with_capacity(objects.len());
+    for obj in objects {
+        let entity = match parse_record_and_header(decoder, obj.offset, true)? {
+            Some((record, header)) => {
+                let is_entity = match dynamic_types.get(&header.type_code) {
+                    Some(name) => is_dynamic_entity_name(name),
+                    None => object_type_class(header.type_code) == ObjectClass::Entity,
+                };
+                let mut reader = record.bit_reader();
+                (is_entity && skip_object_type_prefix(&mut reader, decoder.version()).is_ok())
+                    .then(|| scope.keeps(&reader, &header, obj.handle.0))
+            }
+            None => None,
+        };
+        if entity != Some(false) {
+            kept.push(obj);
+        }
+    }
+    Ok(kept)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Layout {
+    Model,
+    Paper,
+    BlockDefinition,
+}
+
+/// Applies a [`SpaceFilter`] by following each entity's owner chain
+/// (ATTRIB -> INSERT, VERTEX -> POLYLINE, ...) up to model space, a
+/// paper-space layout or a block definition.
+struct SpaceScope<'d, 'a> {
+    decoder: &'d Decoder<'a>,
+    filter: SpaceFilter,
+    offsets: HashMap<u64, u32>,
+    block_names: HashMap<u64, String>,
+    owners: HashMap<u64, Option<Layout>>,
+}
+
+impl<'d, 'a> SpaceScope<'d, 'a> {
+    /// `None` when `filter` keeps everything, so no owners are resolved.
+    fn new(
+        decoder: &'d Decoder<'a>,
+        filter: SpaceFilter,
+        objects: &[ObjectRef],
+        dynamic_types: &HashMap<u16, String>,
+    ) -> Result<Option<Self>> {
+        if filter == SpaceFilter::All {
+            return Ok(None);
+        }
+        let best_effort = is_best_effort_compat_version(decoder);
+        let block_names = collect_table_entry_names(
+            decoder,
+            objects,
+            dynamic_types,
+            best_effort,
+            (0x31, "BLOCK_HEADER"),
+        )?;
+        Ok(Some(Self {
+            decoder,
+            filter,
+            offsets: objects
+                .iter()
+                .map(|object| (object.handle.0, object.offset))
+                .collect(),
+            block_names,
+            owners: HashMap::new(),
+        }))
+    }
+
+    /// `reader` is positioned just after the entity's type code.
+    fn keeps(&mut self, reader: &BitReader<'_>, header: &ObjectHeader, handle: u64) -> bool {
+        let layout = self.layout_of_entity(reader.clone(), header, handle, 0);
+        match self.filter {
+            SpaceFilter::All => true,
+            SpaceFilter::ModelOnly => layout == Some(Layout::Model),
+            SpaceFilter::PaperOnly => layout == Some(Layout::Paper),
+        }
+    }
+
+    fn layout_of_entity(
+        &mut self,
+        mut reader: BitReader<'_>,
+        header: &ObjectHeader,
+        handle: u64,
+        depth: u32,
+    ) -> Option<Layout> {
+        let version = self.decoder.version();
+        match parse_entity_owner_for_version(&mut reader, version, header, handle) {
+            Ok(EntityOwner::ModelSpace) => Some(Layout::Model),
+            Ok(EntityOwner::PaperSpace) => Some(Layout::Paper),
+            Ok(EntityOwner::Block(owner)) => self.layout_of_owner(owner, depth + 1),
+            Err(err) => {
+                diag_event!("entity", "entity owner unresolved: {err}");
+                None
+            }
+        }
+    }
+
+    fn layout_of_owner(&mut self, handle: u64, depth: u32) -> Option<Layout> {
+        if let Some(layout) = self.owners.get(&handle) {
+            return *layout;
+        }
+        let layout = if let Some(name) = self.block_names.get(&handle) {
+            let name = name.to_ascii_uppercase();
+            Some(if name == "*MODEL_SPACE" {
+                Layout::Model
+            } else if name.starts_with("*PAPER_SPACE") {
+                Layout::Paper
+            } else {
+                Layout::BlockDefinition
+            })
+        } else if depth > self.decoder.config().max_recursion {
+            None
+        } else {
+            self.layout_of_owner_record(handle, depth)
+        };
+        self.owners.insert(handle, layout);
+        layout
+    }
+
+    fn layout_of_owner_record(&mut self, handle: u64, depth: u32) -> Option<Layout> {
+        let offset = *self.offsets.get(&handle)?;
+        let decoder = self.decoder;
+        let (record, header) = parse_record_and_header(decoder, offset, true).ok()??;
+        match header.type_code {
+            // A block header whose name could not be read (R2007+).
+            0x31 => return Some(Layout::BlockDefinition),
+            // INSERT/MINSERT own their ATTRIBs, polylines their vertices.
+            0x07 | 0x08 | 0x0F | 0x10 | 0x1D | 0x1E => {}
+            _ => return None,
+        }
+        let mut reader = record.bit_reader();
+        skip_object_type_prefix(&mut reader, decoder.version()).ok()?;
+        self.layout_of_entity(reader, &header, handle, depth)
+    }
+}
+
+/// Versions whose sample corpus still contains records the decoders cannot
+/// handle; queries skip those records instead of failing the whole file.
+pub fn is_best_effort_compat_version(decoder: &Decoder<'_>) -> bool {
+    matches!(
+        decoder.version(),
+        DwgVersion::R14
+            | DwgVersion::R2000
+            | DwgVersion::R2010
+            | DwgVersion::R2013
+            | DwgVersion::R2018
+    )
+}
+
+pub(crate) fn parse_record_and_header<'a>(
+    decoder: &'a Decoder<'_>,
+    offset: u32,
+    best_effort: bool,
+) -> Result<Option<(ObjectRecord<'a>, ObjectHeader)>> {
+    let record = match decoder.parse_object_record(offset) {
+        Ok(record) => record,
+        Err(_) if best_effort => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    let header = match parse_object_header_for_version(&record, decoder.version()) {
+        Ok(header) => header,
+        Err(err) if best_effort => {
+            diag_event!("object", "parse object header at offset {offset:#X}: {err}");
+            return Ok(None);
+        }
+        Err(err) => return Err(err),
+    };
+    Ok(Some((record, header)))
+}
+
+pub(crate) fn load_dynamic_types(
+    decoder: &Decoder<'_>,
+    best_effort: bool,
+) -> Result<HashMap<u16, String>> {
+    match decoder.dynamic_type_map() {
+        Ok(map) => Ok(map),
+        Err(_) if best_effort => Ok(HashMap::new()),
+        Err(err) => Err(err),
+    }
+}
+
+/// Decode failures that mean "this record is not what we hoped", as opposed
+/// to a broken file.
+pub(crate) fn is_recoverable_decode_error(err: &DwgError) -> bool {
+    matches!(
+        err.kind,
+        ErrorKind::NotImplemented
+            | ErrorKind::UnsupportedEntity
+            | ErrorKind::Decode
+            | ErrorKind::Format
+    )
+}
+
+/// Symbol table record handle -> entry name for one table (BLOCK_HEADER,
+/// LAYER, ...). Only versions that keep the entry name inline (R2004 and
+/// older) are resolved; R2007+ move it to the string stream.
+pub(crate) fn collect_table_entry_names(
+    decoder: &Decoder<'_>,
+    objects: &[ObjectRef],
+    dynamic_types: &HashMap<u16, String>,
+    best_effort: bool,
+    (type_code, type_name): (u16, &str),
+) -> Result<HashMap<u64, String>> {
+    let mut names = HashMap::new();
+    if matches!(
+        decoder.version(),
+        DwgVersion::R2007 | DwgVersion::R2010 | DwgVersion::R2013 | DwgVersion::R2018
+    ) {
+        return Ok(names);
+    }
+    for obj in objects.iter() {
+        let Some((record, header)) = parse_record_and_header(decoder, obj.offset, best_effort)?
+        else {
+            continue;
+        };
+        if !matches_type_name(header.type_code, type_code, type_name, dynamic_types) {
+            continue;
+        }
+        let mut reader = record.bit_reader();
+        let decoded = skip_object_type_prefix(&mut reader, decoder.version())
+            .and_then(|_| decode_table_entry_name_for_version(&mut reader, decoder.version()));
+        match decoded {
+            Ok(name) => {
+                names.insert(obj.handle.0, name);
+            }
+            Err(err) if best_effort || is_recoverable_decode_error(&err) => continue,
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(names)
+}
+
+pub(crate) fn matches_type_name(
+    type_code: u16,
+    builtin_code: u16,
+    builtin_name: &str,
+    dynamic_types: &HashMap<u16, String>,
+) -> bool {
+    if type_code == builtin_code {
+        return true;
+    }
+    dynamic_types
+        .get(&type_code)
+        .map(|name| name == builtin_name)
+        .unwrap_or(false)
+}
+
+/// Object map state for gathering each owner's children (an insert's
+/// attributes, a polyline's vertices) during one walk over `sorted`.
+pub struct ChildWalk<'w, 'a> {
+    pub decoder: &'w Decoder<'a>,
+    /// The object map in file offset order.
+    pub sorted: &'w [ObjectRef],
+    pub dynamic_types: &'w HashMap<u16, String>,
+    /// Child handles by the owner their records name, for owners that do not
+    /// list their children.
+    pub child_owners: &'w HashMap<u64, Vec<u64>>,
+    pub best_effort: bool,
+}
+
+/// What [`ChildWalk::owned_children`] makes of a child-type record that
+/// follows the owner in the object map.
+pub enum AdjacentChild<T> {
+    Take(T),
+    /// Part of the group but not usable; the walk goes on past it.
+    Skip,
+    /// Names another owner; the group ends before it.
+    Stop,
+}
+
+impl ChildWalk<'_, '_> {
+    /// The children of `owner` and the index of `sorted` to resume the object
+    /// walk from.
+    ///
+    /// Children come from `owned_handles` when the owner lists them, else from
+    /// the records naming `owner` in `child_owners`, both resolved by `lookup`.
+    /// Without either, the records after `start_index` are read while
+    /// `is_child` accepts their type, up to and including a SEQEND that closes
+    /// the group; `decode_adjacent` gets each past its type prefix.
+    pub fn owned_children<T>(
+        &self,
+        owner: u64,
+        owned_handles: &[u64],
+        start_index: usize,
+        lookup: impl Fn(u64) -> Option<T>,
+        is_child: impl Fn(u16) -> bool,
+        mut decode_adjacent: impl FnMut(
+            &mut BitReader<'_>,
+            &ObjectHeader,
+            u64,
+        ) -> Result<AdjacentChild<T>>,
+    ) -> Result<(Vec<T>, usize)> {
+        // Without an owned-handle list, prefer children that name the owner as
+        // theirs; the object map order is not guaranteed to keep them adjacent.
+        let listed = if owned_handles.is_empty() {
+            self.child_owners.get(&owner).map(Vec::as_slice)
+        } else {
+            Some(owned_handles)
+        };
+        if let Some(handles) = listed {
+            let children = handles
+                .iter()
+                .filter_map(|&handle| lookup(handle))
+                .collect();
+            return Ok((children, start_index + 1));
+        }
+
+        let version = self.decoder.version();
+        let mut children = Vec::new();
+        let mut next_i = start_index + 1;
+        while next_i < self.sorted.len() {
+            let next = self.sorted[next_i];
+            let Some((next_record, next_header)) =
+                parse_record_and_header(self.decoder, next.offset, self.best_effort)?
+            else {
+                next_i += 1;
+                continue;
+            };
+            let is_seqend =
+                matches_type_name(next_header.type_code, 0x06, "SEQEND", self.dynamic_types);
+            if !is_seqend && !is_child(next_header.type_code) {
+                break;
+            }
+            let mut next_reader = next_record.bit_reader();
+            if let Err(err) = skip_object_type_prefix(&mut next_reader, version) {
+                if self.best_effort {
+                    next_i += 1;
+                    continue;
+                }
+                return Err(err);
+            }
+            if is_seqend {
+                match decode_seqend(&mut next_reader) {
+                    Ok(seqend) if !seqend_closes_group(&seqend, owner) => {}
+                    Err(err) if !self.best_effort => return Err(err),
+                    _ => next_i += 1,
+                }
+                break;
+            }
+            match decode_adjacent(&mut next_reader, &next_header, next.handle.0) {
+                Ok(AdjacentChild::Take(child)) => children.push(child),
+                Ok(AdjacentChild::Skip) => {}
+                Ok(AdjacentChild::Stop) => break,
+                Err(_) if self.best_effort => {}
+                Err(err) => return Err(err),
+            }
+            next_i += 1;
+        }
+
+        Ok((children, next_i))
+    }
+}
+
+/// A SEQEND closes a sequential group unless it names a different owner.
+fn seqend_closes_group(seqend: &SeqendEntity, owner_handle: u64) -> bool {
+    seqend
+        .owner_handle
+        .is_none_or(|owner| owner == owner_handle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::ParseConfig;
+    use crate::entities::test_support::Bits;
+    use crate::objects::Handle;
+
+    fn open(name: &str) -> Vec<u8> {
+        std::fs::read(format!("test_dwg/{name}")).expect("sample file")
+    }
+
+    #[test]
+    fn seqend_only_closes_its_own_group() {
+        let owned = SeqendEntity {
+            handle: 0x30,
+            owner_handle: Some(0x10),
+        };
+        let unknown = SeqendEntity {
+            handle: 0x31,
+            owner_handle: None,
+        };
+
+        assert!(seqend_closes_group(&owned, 0x10));
+        assert!(!seqend_closes_group(&owned, 0x20));
+        assert!(seqend_closes_group(&unknown, 0x20));
+    }
+
+    #[test]
+    fn decodes_lines_across_versions() {
+        for name in [
+            "line_R14.dwg",
+            "line_2000.dwg",
+            "line_2007.dwg",
+            "line_2013.dwg",
+        ] {
+            let bytes = open(name);
+            let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+            let lines = decode_line_entities(&decoder, None).unwrap();
+            assert!(!lines.is_empty(), "{name}");
+            assert!(lines.iter().all(|line| line.handle != 0), "{name}");
+        }
+    }
+
+    #[test]
+    fn lists_active_vport_across_versions() {
+        for name in [
+            "line_R14.dwg",
+            "line_2000.dwg",
+            "line_2004.dwg",
+            "line_2007.dwg",
+            "line_2010.dwg",
+            "line_2013.dwg",
+        ] {
+            let bytes = open(name);
+            let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+            let vports = list_vports(&decoder).unwrap();
+            let active = vports
+                .iter()
+                .find(|vport| {
+                    vport
+                        .name
+                        .as_deref()
+                        .is_some_and(|name| name.eq_ignore_ascii_case("*ACTIVE"))
+                })
+                .unwrap_or_else(|| panic!("{name}: no *ACTIVE vport"));
+            assert!(
+                (active.view_height - 288.0653526970954).abs() < 1e-9,
+                "{name}"
+            );
+            assert!(
+                (active.aspect_ratio - 2.033190578158458).abs() < 1e-9,
+                "{name}"
+            );
+            assert!((active.view_center.0 - 292.836).abs() < 1e-3, "{name}");
+            assert_eq!(active.view_direction, (0.0, 0.0, 1.0), "{name}");
+            assert_eq!(active.upper_right, (1.0, 1.0), "{name}");
+            assert_eq!(active.grid_spacing, (10.0, 10.0), "{name}");
+            assert_eq!(active.snap_spacing, (10.0, 10.0), "{name}");
+        }
+    }
+
+    #[test]
+    fn lists_acad_appid_across_versions() {
+        for name in [
+            "line_R14.dwg",
+            "line_2000.dwg",
+            "line_2004.dwg",
+            "line_2007.dwg",
+            "line_2010.dwg",
+            "line_2013.dwg",
+        ] {
+            let bytes = open(name);
+            let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+            let appids = list_appids(&decoder).unwrap();
+            assert!(
+                appids
+                    .iter()
+                    .any(|appid| appid.handle != 0 && appid.name.as_deref() == Some("ACAD")),
+                "{name}: {appids:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn entity_xdata_references_registered_appids() {
+        let bytes = open("acadsharp/BLOCKPOINTPARAMETER.dwg");
+        let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+        let rows = decode_entity_xdata(&decoder, None).unwrap();
+        assert!(!rows.is_empty());
+        let appids: Vec<u64> = list_appids(&decoder)
+            .unwrap()
+            .iter()
+            .map(|appid| appid.handle)
+            .collect();
+        for row in &rows {
+            assert!(!row.xdata.is_empty());
+            for (app_handle, values) in &row.xdata {
+                assert!(appids.contains(app_handle), "{row:?}");
+                // AcDbBlockRepETag: (1070, 1071, 1005 self handle).
+                assert_eq!(values.len(), 3, "{row:?}");
+                assert_eq!(values[2], XDataValue::Handle(row.handle), "{row:?}");
+            }
+        }
+        assert_eq!(decode_entity_xdata(&decoder, Some(1)).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn limit_caps_the_result() {
+        let bytes = open("insert_2004.dwg");
+        let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+        let inserts = decode_insert_entities(&decoder, None).unwrap();
+        assert!(!inserts.is_empty());
+        assert_eq!(decode_insert_entities(&decoder, Some(1)).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn spatial_query_selects_by_bounds() {
+        let bytes = open("line_2000.dwg");
+        let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+        let bounds = entity_bounds(&decoder).unwrap();
+        let lines = decode_line_entities(&decoder, None).unwrap();
+        assert_eq!(bounds.len(), lines.len());
+        let (handle, line_bounds) = bounds[0];
+
+        let center = (
+            (line_bounds.min.0 + line_bounds.max.0) / 2.0,
+            (line_bounds.min.1 + line_bounds.max.1) / 2.0,
+        );
+        assert_eq!(
+            spatial_query(&decoder, center, center).unwrap(),
+            vec![handle]
+        );
+        let outside = (line_bounds.max.0 + 1.0, line_bounds.max.1 + 1.0);
+        assert!(
+            spatial_query(&decoder, outside, (outside.0 + 5.0, outside.1 + 5.0))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn extract_all_text_covers_text_and_mtext() {
+        fn texts_of(decoder: &Decoder<'_>, type_name: &str) -> Vec<String> {
+            extract_all_text(decoder)
+                .unwrap()
+                .into_iter()
+                .filter(|item| item.type_name == type_name)
+                .map(|item| item.text)
+                .collect()
+        }
+
+        let bytes = open("text_2000.dwg");
+        let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+        let texts: Vec<String> = decode_text_entities(&decoder, None)
+            .unwrap()
+            .into_iter()
+            .map(|text| text.text)
+            .filter(|text| !text.is_empty())
+            .collect();
+        assert!(!texts.is_empty());
+        assert_eq!(texts_of(&decoder, "TEXT"), texts);
+
+        let bytes = open("mtext_2004.dwg");
+        let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+        let mtexts: Vec<String> = decode_mtext_entities(&decoder, None)
+            .unwrap()
+            .into_iter()
+            .map(|mtext| mtext.text)
+            .filter(|text| !text.is_empty())
+            .collect();
+        assert!(!mtexts.is_empty());
+        assert_eq!(texts_of(&decoder, "MTEXT"), mtexts);
+    }
+
+    #[test]
+    fn object_summaries_cover_the_whole_index() {
+        let bytes = open("line_2004.dwg");
+        let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+        let summaries = object_summaries(&decoder).unwrap();
+        assert_eq!(
+            summaries.len(),
+            decoder.build_object_index().unwrap().objects.len()
+        );
+        let line = summaries
+            .iter()
+            .find(|summary| summary.type_name == "LINE")
+            .unwrap();
+        assert!(line.layer_handle.is_some());
+        assert_eq!(line.layer_name.as_deref(), Some("0"));
+        let (_, bounds) = entity_bounds(&decoder)
+            .unwrap()
+            .into_iter()
+            .find(|(handle, _)| *handle == line.handle)
+            .unwrap();
+        assert_eq!(line.bounds, Some(bounds));
+        let layer = summaries
+            .iter()
+            .find(|summary| summary.type_name == "LAYER")
+            .unwrap();
+        assert_eq!(
+            (layer.layer_handle, layer.layer_name.as_ref(), layer.bounds),
+            (None, None, None)
+        );
+    }
+
+    #[test]
+    fn list_blocks_counts_owned_entities() {
+        let bytes = open("insert_2004.dwg");
+        let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+        let blocks = list_blocks(&decoder).unwrap();
+        let block = blocks
+            .iter()
+            .find(|block| block.header.name.as_deref() == Some("BLK1"))
+            .unwrap();
+        assert_eq!(block.entity_count, 1);
+        assert!(!block.header.is_xref && !block.header.is_anonymous);
+
+        // R2000 headers carry no count, so owners are resolved instead.
+        let bytes = open("line_2000.dwg");
+        let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+        let counts: Vec<(Option<String>, usize)> = list_blocks(&decoder)
+            .unwrap()
+            .into_iter()
+            .map(|block| (block.header.name, block.entity_count))
+            .collect();
+        assert!(counts.contains(&(Some("*Model_Space".to_string()), 1)));
+        assert_eq!(counts.iter().map(|(_, count)| count).sum::<usize>(), 1);
+    }
+
+    #[test]
+    fn modelspace_entity_handles_skip_owned_records() {
+        // The block's LINE and the polyline's vertices are not listed.
+        for (name, expected) in [
+            ("insert_2004.dwg", vec![0x2C]),
+            ("polyline2d_line_R14.dwg", vec![0x7C]),
+            ("line_2000.dwg", vec![0x83]),
+        ] {
+            let bytes = open(name);
+            let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+            assert_eq!(
+                modelspace_entity_handles(&decoder).unwrap(),
+                expected,
+                "{name}"
+            );
+        }
+
+        let bytes = open("acadsharp/sample_AC1032.dwg");
+        let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+        let handles = modelspace_entity_handles(&decoder).unwrap();
+        let model_space = list_blocks(&decoder)
+            .unwrap()
+            .into_iter()
+            .find(|block| block.header.name.as_deref() == Some("*Model_Space"))
+            .unwrap();
+        assert_eq!(handles.len(), model_space.entity_count);
+        let index = decoder.build_object_index().unwrap();
+        assert!(handles
+            .iter()
+            .all(|&handle| index.get(Handle(handle)).is_some()));
+    }
+
+    #[test]
+    fn angular_dimensions_measure_their_stored_value() {
+        for name in ["acadsharp/sample_AC1027.dwg", "acadsharp/sample_AC1032.dwg"] {
+            let bytes = open(name);
+            let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+            let mut measured = Vec::new();
+            for item in decoder.entities_filtered(|name| name.starts_with("DIM_ANG")) {
+                let (angle, stored) = match item.unwrap().1 {
+                    Entity::DimAng3Pt(dim) => (dim.measured_angle(), dim.common.actual_measurement),
+                    Entity::DimAng2Ln(dim) => (dim.measured_angle(), dim.common.actual_measurement),
+                    other => panic!("unexpected {}", other.type_name()),
+                };
+                assert!((angle - stored.unwrap()).abs() < 1e-9, "{name}");
+                measured.push(angle);
+            }
+            assert_eq!(measured.len(), 2, "{name}");
+        }
+    }
+
+    #[test]
+    fn layer_records_carry_names_colors_and_states() {
+        for name in ["line_2000.dwg", "line_2013.dwg"] {
+            let bytes = open(name);
+            let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+            let layers = layer_records(&decoder).unwrap();
+            assert_eq!(layers.len(), 1, "{name}");
+            assert_eq!(layers[0].name.as_deref(), Some("0"), "{name}");
+        }
+        let bytes = open("acadsharp/sample_AC1032.dwg");
+        let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+        let layers = layer_records(&decoder).unwrap();
+        let layer = |name: &str| {
+            layers
+                .iter()
+                .find(|layer| layer.name.as_deref() == Some(name))
+                .unwrap_or_else(|| panic!("no layer {name}"))
+        };
+        let color = layer("Layer_color_80");
+        assert_eq!((color.color_index, color.true_color), (0, Some(80)));
+        assert!(!layer("Layer_Off").state.unwrap().on);
+        assert!(layer("Layer_Freeze").state.unwrap().frozen);
+        assert!(layer("Layer_Lock").state.unwrap().locked);
+    }
+
+    #[test]
+    fn drawing_stats_count_objects_tables_and_undecoded_types() {
+        let bytes = open("line_2000.dwg");
+        let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+        let stats = drawing_stats(&decoder).unwrap();
+        assert_eq!(stats.version, DwgVersion::R2000);
+        assert_eq!((stats.entity_count, stats.layer_count), (7, 1));
+        assert_eq!(stats.block_count, 3);
+        assert_eq!(stats.section_bytes, stats.compressed_section_bytes);
+        assert!(stats.unsupported_types.is_empty());
+
+        let bytes = open("acadsharp/sample_AC1032.dwg");
+        let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+        let stats = drawing_stats(&decoder).unwrap();
+        assert_eq!(
+            stats.object_count,
+            decoder.build_object_index().unwrap().objects.len()
+        );
+        assert_eq!(stats.layer_count, layer_records(&decoder).unwrap().len());
+        assert_eq!(stats.block_count, list_blocks(&decoder).unwrap().len());
+        assert!(stats.compressed_section_bytes < stats.section_bytes);
+        assert_eq!(stats.unsupported_types[0], ("VIEWPORT".to_string(), 6));
+        assert!(stats
+            .unsupported_types
+            .contains(&("3DSOLID".to_string(), 2)));
+    }
+
+    #[test]
+    fn objects_buffer_decodes_like_the_decoder() {
+        for name in [
+            "line_2000.dwg",
+            "line_2004.dwg",
+            "line_2007.dwg",
+            "line_2013.dwg",
+        ] {
+            let bytes = open(name);
+            let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+            let index = decoder.build_object_index().unwrap();
+            let buffer = match decoder.version() {
+                DwgVersion::R14 | DwgVersion::R2000 => bytes.clone(),
+                _ => {
+                    let directory = decoder.section_directory().unwrap();
+                    let section = decoder
+                        .load_section_by_name(&directory, "AcDb:AcDbObjects")
+                        .unwrap();
+                    section.data.into_owned()
+                }
+            };
+            let decoded =
+                decode_entities_from_objects_buffer(&buffer, &index, decoder.version()).unwrap();
+            let expected: Vec<(Handle, &'static str)> = decoder
+                .entities()
+                .filter_map(|item| item.ok())
+                .map(|(handle, entity)| (handle, entity.type_name()))
+                .collect();
+            let actual: Vec<(Handle, &'static str)> = decoded
+                .iter()
+                .map(|(handle, entity)| (*handle, entity.type_name()))
+                .collect();
+            assert_eq!(actual, expected, "{name}");
+            assert!(actual.iter().any(|(_, type_name)| *type_name == "LINE"));
+        }
+    }
+
+    #[test]
+    fn pages_cover_the_entities_of_a_full_walk() {
+        for name in ["line_2004.dwg", "acadsharp/sample_AC1032.dwg"] {
+            let bytes = open(name);
+            let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+            let expected: Vec<Handle> = decoder
+                .entities()
+                .filter_map(|item| item.ok())
+                .map(|(handle, _)| handle)
+                .collect();
+            let total = decoder.build_object_index().unwrap().len();
+            let mut paged = Vec::new();
+            for offset in (0..total).step_by(50) {
+                let page = decode_entities_paginated(&decoder, offset, 50).unwrap();
+                paged.extend(page.into_iter().map(|(handle, _)| handle));
+            }
+            assert_eq!(paged, expected, "{name}");
+            assert!(decode_entities_paginated(&decoder, total, 50)
+                .unwrap()
+                .is_empty());
+        }
+    }
+
+    #[test]
+    fn inserts_carry_the_name_of_their_block() {
+        let bytes = open("acadsharp/sample_AC1032.dwg");
+        let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+        let inserts = decode_insert_entities_with_block(&decoder, None).unwrap();
+        let name_of = |handle: u64| {
+            inserts
+                .iter()
+                .find(|(insert, _)| insert.handle == handle)
+                .and_then(|(_, name)| name.as_deref())
+        };
+        assert_eq!(name_of(0x704), Some("MyBlock"));
+        // Its attribute list does not read; the block is named even so.
+        assert_eq!(name_of(0x79C), Some("my_block_v2"));
+        assert_eq!(name_of(0xC9E), Some("_BoxBlank"));
+    }
+
+    #[test]
+    fn inexact_decodes_report_how_layouts_were_chosen() {
+        let bytes = open("acadsharp/sample_AC1027.dwg");
+        let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+        let rows = inexact_decodes(&decoder).unwrap();
+        assert!(rows.contains(&(0xC96, "DIM_LINEAR", DecodeMethod::Guessed)));
+        for (handle, _, method) in &rows {
+            let entity = decoder.decode_entity(Handle(*handle)).unwrap();
+            assert_eq!(entity.decode_method(), *method);
+        }
+        // Clean dimension reads are not listed.
+        assert!(!rows.iter().any(|(handle, _, _)| *handle == 0x514));
+
+        let bytes = open("mtext_2004.dwg");
+        let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+        assert!(inexact_decodes(&decoder).unwrap().is_empty());
+    }
+
+    #[test]
+    fn text_walk_reports_progress_and_stops_on_request() {
+        let bytes = open("text_2000.dwg");
+        let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+        let total = decoder.build_object_index().unwrap().len();
+        let mut calls = Vec::new();
+        let items = extract_all_text_with_progress(
+            &decoder,
+            &mut Progress::new(|done, total| {
+                calls.push((done, total));
+                true
+            }),
+        )
+        .unwrap();
+        assert_eq!(items, extract_all_text(&decoder).unwrap());
+        assert_eq!(calls.first(), Some(&(0, total)));
+        assert_eq!(calls.last(), Some(&(total, total)));
+        assert!(calls.len() <= 102);
+
+        let stopped =
+            extract_all_text_with_progress(&decoder, &mut Progress::new(|_, _| false)).unwrap();
+        assert!(stopped.is_empty());
+    }
+
+    #[test]
+    fn space_filter_separates_model_space_from_block_contents() {
+        let with_filter = |name: &str, space_filter: SpaceFilter| {
+            let bytes = open(name);
+            let config = ParseConfig {
+                space_filter,
+                ..ParseConfig::default()
+            };
+            let decoder = Decoder::new(&bytes, config).unwrap();
+            let lines = decode_line_entities(&decoder, None).unwrap().len();
+            let inserts = decode_insert_entities(&decoder, None).unwrap().len();
+            (lines, inserts)
+        };
+
+        // The INSERT sits in model space; its block's LINE does not.
+        let all = with_filter("insert_2004.dwg", SpaceFilter::All);
+        let model = with_filter("insert_2004.dwg", SpaceFilter::ModelOnly);
+        assert_eq!(model, (all.0 - 1, all.1));
+        assert_eq!(
+            with_filter("insert_2004.dwg", SpaceFilter::PaperOnly),
+            (0, 0)
+        );
+        assert_eq!(
+            with_filter("line_2013.dwg", SpaceFilter::ModelOnly),
+            with_filter("line_2013.dwg", SpaceFilter::All)
+        );
+    }
+
+    #[test]
+    fn r2010_streams_locate_block_names() {
+        use crate::objects::object_header_r2010;
+
+        for name in ["acadsharp/sample_AC1027.dwg", "acadsharp/sample_AC1032.dwg"] {
+            let bytes = open(name);
+            let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+            for object in decoder.build_object_index().unwrap().objects {
+                let record = decoder.parse_object_record(object.offset).unwrap();
+                let header = object_header_r2010::parse_from_record(&record).unwrap();
+                let streams = header.streams(&record).unwrap();
+                assert!(
+                    streams.data.end <= streams.handles.start,
+                    "{name} {:#x}",
+                    object.handle.0
+                );
+                if let Some(strings) = &streams.strings {
+                    assert!(
+                        streams.data.end == strings.start && strings.end < streams.handles.start
+                    );
+                }
+            }
+            let names: Vec<_> = list_block_headers(&decoder)
+                .unwrap()
+                .into_iter()
+                .map(|header| header.name)
+                .collect();
+            assert!(names.iter().all(Option::is_some), "{name}: {names:?}");
+            for expected in ["*Model_Space", "*Paper_Space"] {
+                assert!(names.contains(&Some(expected.to_string())), "{name}");
+            }
+        }
+    }
+
+    #[test]
+    fn r2007_handle_streams_start_at_the_object_size() {
+        use crate::entities::common::parse_common_entity_handles;
+
+        for name in [
+            "line_2007.dwg",
+            "arc_2007.dwg",
+            "ellipse_2007.dwg",
+            "polyline2d_line_2007.dwg",
+        ] {
+            let bytes = open(name);
+            let decoder = Decoder::new(&bytes, ParseConfig::default()).unwrap();
+            let dynamic_types = load_dynamic_types(&decoder, false).unwrap();
+            let mut entities = 0;
+            for object in decoder.build_object_index().unwrap().objects {
+                let (record, header) = parse_record_and_header(&decoder, object.offset, false)
+                    .unwrap()
+                    .unwrap();
+                if dynamic_types.contains_key(&header.type_code)
+                    || object_type_class(header.type_code) != ObjectClass::Entity
+                {
+                    continue;
+                }
+                let mut reader = record.bit_reader();
+                skip_object_type_prefix(&mut reader, decoder.version()).unwrap();
+                let mut common =
+                    parse_common_entity_header_for_version(&mut reader, decoder.version(), &header)
+                        .unwrap();
+                common.handle = object.handle.0;
+                // The string stream, when present, ends exactly there.
+                assert!(reader.tell_bits() < u64::from(common.obj_size), "{name}");
+                reader.set_bit_pos(common.handle_stream_start_bit());
+                let handles = parse_common_entity_handles(&mut reader, &common).unwrap();
+                assert_ne!(handles.layer, 0, "{name}");
+                // Nothing but padding is left after the common handles.
+                let body_bits = record.body.len() as u64 * 8;
+                assert!(body_bits - reader.tell_bits() < 8, "{name}");
+                entities += 1;
+            }
+            assert!(entities >= 5, "{name}");
+        }
+
+        // No sample carries MTEXT or ATTDEF, so hand-built records stand in:
+        // their data stops short of the string stream, and the owner, layer
+        // and text style handles follow it at the object size.
+        let handles = || {
+            let mut handles = Bits::default();
+            handles.handle(4, 0x1F); // owner
+            handles.handle(5, 0x10); // layer
+            handles.handle(5, 0x11); // text style
+            handles
+        };
+        let string_stream = |body: &mut Bits| body.push(0x5A5A, 24);
+
+        let mut body = Bits::default();
+        body.common_entity_header_r2007(0x40, 0b00);
+        for point in [(1.0, 2.0, 0.0), (0.0, 0.0, 1.0), (1.0, 0.0, 0.0)] {
+            body.point(point); // insertion, extrusion, x axis
+        }
+        body.bd(10.0); // rect width
+        body.bd(2.5); // text height
+        body.small(1); // attachment
+        body.small(1); // drawing direction
+        body.bd(2.5); // extents height
+        body.bd(8.0); // extents width
+        body.tv("");
+        body.small(1); // line spacing style
+        body.bd(1.0); // line spacing factor
+        body.push(0, 1);
+        body.push(0b10, 2); // background flags BL = 0
+        string_stream(&mut body);
+        let data = Bits::record(body, handles());
+        let mtext = crate::entities::decode_mtext_r2007(&mut BitReader::new(&data)).unwrap();
+        assert_eq!(mtext.layer_handle, 0x10);
+
+        let mut body = Bits::default();
+        body.common_entity_header_r2007(0x41, 0b00);
+        body.rc(0xFF); // data flags: every optional field defaulted
+        body.rd(3.0);
+        body.rd(4.0);
+        body.push(1, 1); // default extrusion
+        body.push(1, 1); // zero thickness
+        body.rd(2.0); // height
+        body.tv("");
+        body.tv("TAG");
+        body.push(0b10, 2); // field length BS = 0
+        body.rc(0); // flags
+        body.push(0, 1); // lock position
+        body.tv("");
+        string_stream(&mut body);
+        let data = Bits::record(body, handles());
+        let attdef = crate::entities::decode_attdef_r2007(&mut BitReader::new(&data)).unwrap();
+        assert_eq!(attdef.layer_handle, 0x10);
+        assert_eq!(attdef.style_handle, Some(0x11));
+    }
+}
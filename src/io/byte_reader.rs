@@ -72,6 +72,23 @@ impl<'a> ByteReader<'a> {
         ]))
     }
 
+    pub fn read_u16_be(&mut self) -> Result<u16> {
+        let bytes = self.read_bytes(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    pub fn read_u32_be(&mut self) -> Result<u32> {
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    pub fn read_u64_be(&mut self) -> Result<u64> {
+        let bytes = self.read_bytes(8)?;
+        Ok(u64::from_be_bytes([
+            bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7],
+        ]))
+    }
+
     pub fn read_i32_le(&mut self) -> Result<i32> {
         let bytes = self.read_bytes(4)?;
         Ok(i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
@@ -84,6 +101,76 @@ impl<'a> ByteReader<'a> {
         ]))
     }
 
+    pub fn read_f32_le(&mut self) -> Result<f32> {
+        let bytes = self.read_bytes(4)?;
+        Ok(f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    pub fn read_f32_be(&mut self) -> Result<f32> {
+        let bytes = self.read_bytes(4)?;
+        Ok(f32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Modular char (MC): little-endian 7-bit groups with a 0x80 continuation
+    /// flag; bit 0x40 of the final byte carries the sign.
+    pub fn read_modular_char(&mut self) -> Result<i64> {
+        let mut value: i64 = 0;
+        let mut shift = 0;
+
+        for _ in 0..4 {
+            let mut byte = self.read_u8()?;
+            if (byte & 0x80) == 0 {
+                let negative = (byte & 0x40) != 0;
+                if negative {
+                    byte &= 0xBF;
+                }
+                value |= (byte as i64) << shift;
+                if negative {
+                    value = -value;
+                }
+                return Ok(value);
+            }
+            byte &= 0x7F;
+            value |= (byte as i64) << shift;
+            shift += 7;
+        }
+        Ok(value)
+    }
+
+    /// Unsigned modular char: the MC layout without the sign bit, so the
+    /// final byte contributes all 7 bits.
+    pub fn read_unsigned_modular_char(&mut self) -> Result<u64> {
+        let mut value: u64 = 0;
+        let mut shift = 0;
+
+        for _ in 0..4 {
+            let byte = self.read_u8()?;
+            value |= u64::from(byte & 0x7F) << shift;
+            if (byte & 0x80) == 0 {
+                return Ok(value);
+            }
+            shift += 7;
+        }
+        Ok(value)
+    }
+
+    /// Modular short (MS): little-endian 15-bit words with a 0x8000
+    /// continuation flag.
+    pub fn read_modular_short(&mut self) -> Result<u32> {
+        let mut value: u32 = 0;
+        let mut shift = 0;
+
+        for _ in 0..2 {
+            let word = self.read_u16_le()?;
+            value |= ((word & 0x7FFF) as u32) << shift;
+            if (word & 0x8000) == 0 {
+                return Ok(value);
+            }
+            shift += 15;
+        }
+        Ok(value)
+    }
+
     pub fn read_bytes(&mut self, n: usize) -> Result<&'a [u8]> {
         self.require(n)?;
         let start = self.pos;
@@ -123,3 +210,96 @@ impl<'a> ByteReader<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::ByteReader;
+
+    #[test]
+    fn reads_big_endian_integers() {
+        let data = [
+            0x12, 0x34, 0x01, 0x02, 0x03, 0x04, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08,
+        ];
+        let mut reader = ByteReader::new(&data);
+        assert_eq!(reader.read_u16_be().unwrap(), 0x1234);
+        assert_eq!(reader.read_u32_be().unwrap(), 0x0102_0304);
+        assert_eq!(reader.read_u64_be().unwrap(), 0x0102_0304_0506_0708);
+        assert!(reader.read_u16_be().is_err());
+    }
+
+    #[test]
+    fn reads_f32_in_both_byte_orders() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&1.5f32.to_le_bytes());
+        data.extend_from_slice(&(-0.25f32).to_be_bytes());
+        let mut reader = ByteReader::new(&data);
+        assert_eq!(reader.read_f32_le().unwrap(), 1.5);
+        assert_eq!(reader.read_f32_be().unwrap(), -0.25);
+        assert!(reader.read_f32_le().is_err());
+        assert_eq!(reader.remaining(), 0);
+    }
+
+    #[test]
+    fn f32_read_reports_eof_offset() {
+        let data = [0x00, 0x00, 0x80];
+        let err = ByteReader::new(&data).read_f32_le().unwrap_err();
+        assert_eq!(err.offset, Some(0));
+    }
+
+    #[test]
+    fn reads_single_byte_modular_chars() {
+        let data = [0x05, 0x45, 0x3F];
+        let mut reader = ByteReader::new(&data);
+        assert_eq!(reader.read_modular_char().unwrap(), 5);
+        assert_eq!(reader.read_modular_char().unwrap(), -5);
+        assert_eq!(reader.read_modular_char().unwrap(), 63);
+    }
+
+    #[test]
+    fn reads_multi_byte_modular_chars() {
+        // 100 = 0b1100100: the low 7 bits go in a continuation byte, only then
+        // does the shift advance for the terminating byte.
+        let data = [
+            0xE4, 0x00, // 100
+            0xC8, 0x41, // -200
+            0x80, 0x80, 0x01, // 0x4000
+            0xFF, 0xFF, 0xFF, 0x3F, // 0x7FF_FFFF
+        ];
+        let mut reader = ByteReader::new(&data);
+        assert_eq!(reader.read_modular_char().unwrap(), 100);
+        assert_eq!(reader.read_modular_char().unwrap(), -200);
+        assert_eq!(reader.read_modular_char().unwrap(), 0x4000);
+        assert_eq!(reader.read_modular_char().unwrap(), 0x7FF_FFFF);
+        assert_eq!(reader.remaining(), 0);
+    }
+
+    #[test]
+    fn unsigned_modular_chars_keep_the_sign_bit() {
+        let data = [0x45, 0xE4, 0x01];
+        let mut reader = ByteReader::new(&data);
+        assert_eq!(reader.read_unsigned_modular_char().unwrap(), 0x45);
+        assert_eq!(reader.read_unsigned_modular_char().unwrap(), 100 + (1 << 7));
+        assert!(reader.read_unsigned_modular_char().is_err());
+    }
+
+    #[test]
+    fn modular_char_reports_truncated_input() {
+        let data = [0x80, 0x80];
+        assert!(ByteReader::new(&data).read_modular_char().is_err());
+    }
+
+    #[test]
+    fn reads_modular_short() {
+        let data = [0x34, 0x12, 0xFF, 0xFF, 0x01, 0x00];
+        let mut reader = ByteReader::new(&data);
+        assert_eq!(reader.read_modular_short().unwrap(), 0x1234);
+        assert_eq!(reader.read_modular_short().unwrap(), 0x7FFF | (1 << 15));
+    }
+
+    #[test]
+    fn big_and_little_endian_reads_mirror_each_other() {
+        let data = [0xAB, 0xCD];
+        assert_eq!(ByteReader::new(&data).read_u16_be().unwrap(), 0xABCD);
+        assert_eq!(ByteReader::new(&data).read_u16_le().unwrap(), 0xCDAB);
+    }
+}
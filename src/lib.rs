@@ -6,6 +6,7 @@ pub mod container;
 pub mod core;
 pub mod dwg;
 pub mod entities;
+pub mod high_level;
 pub mod io;
 pub mod objects;
 
@@ -14,5 +15,7 @@ pub mod objects;
 /// import the module.
 #[pymodule]
 fn _core(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    #[cfg(feature = "tracing")]
+    core::diag::init_stderr_logger();
     api::register(m)
 }
@@ -0,0 +1,81 @@
+//! APPID symbol table records: the applications registered to attach
+//! extended data (XDATA) to entities and objects.
+
+use crate::bit::BitReader;
+use crate::core::result::Result;
+use crate::entities::common::{
+    parse_common_object_header, parse_common_object_header_r14, parse_common_object_header_r2000,
+    parse_common_object_header_r2010, parse_common_object_header_r2013, string_stream_reader,
+    CommonObjectHeader,
+};
+
+#[derive(Debug, Clone)]
+pub struct AppIdObject {
+    /// The handle XDATA application references point at.
+    pub handle: u64,
+    /// Registered application name (`ACAD`, ...); `None` when an R2007+
+    /// record stores no string stream.
+    pub name: Option<String>,
+}
+
+pub fn decode_appid_r14(reader: &mut BitReader<'_>) -> Result<AppIdObject> {
+    let header = parse_common_object_header_r14(reader)?;
+    decode_appid_with_header(reader, header, None)
+}
+
+pub fn decode_appid_r2000(reader: &mut BitReader<'_>) -> Result<AppIdObject> {
+    let header = parse_common_object_header_r2000(reader)?;
+    decode_appid_with_header(reader, header, None)
+}
+
+pub fn decode_appid(reader: &mut BitReader<'_>) -> Result<AppIdObject> {
+    let header = parse_common_object_header(reader)?;
+    decode_appid_with_header(reader, header, None)
+}
+
+pub fn decode_appid_r2007(reader: &mut BitReader<'_>) -> Result<AppIdObject> {
+    let header = parse_common_object_header(reader)?;
+    let strings = string_stream_reader(reader, header.obj_size)?;
+    decode_appid_with_header(reader, header, Some(strings))
+}
+
+pub fn decode_appid_r2010(
+    reader: &mut BitReader<'_>,
+    object_data_end_bit: u32,
+    object_handle: u64,
+) -> Result<AppIdObject> {
+    let mut header = parse_common_object_header_r2010(reader, object_data_end_bit)?;
+    header.handle = object_handle;
+    let strings = string_stream_reader(reader, object_data_end_bit)?;
+    decode_appid_with_header(reader, header, Some(strings))
+}
+
+pub fn decode_appid_r2013(
+    reader: &mut BitReader<'_>,
+    object_data_end_bit: u32,
+    object_handle: u64,
+) -> Result<AppIdObject> {
+    let mut header = parse_common_object_header_r2013(reader, object_data_end_bit)?;
+    header.handle = object_handle;
+    let strings = string_stream_reader(reader, object_data_end_bit)?;
+    decode_appid_with_header(reader, header, Some(strings))
+}
+
+/// `strings` is `None` when the name is stored inline (before R2007), and
+/// `Some(stream)` for the object's string stream otherwise. The flags that
+/// follow the name are not needed and left unread.
+fn decode_appid_with_header(
+    reader: &mut BitReader<'_>,
+    header: CommonObjectHeader,
+    strings: Option<Option<BitReader<'_>>>,
+) -> Result<AppIdObject> {
+    let name = match strings {
+        None => Some(reader.read_tv()?),
+        Some(Some(mut stream)) => Some(stream.read_tu()?),
+        Some(None) => None,
+    };
+    Ok(AppIdObject {
+        handle: header.handle,
+        name,
+    })
+}
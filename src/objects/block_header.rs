@@ -0,0 +1,272 @@
+//! BLOCK_HEADER symbol table records: one per block definition, including
+//! the `*Model_Space` and `*Paper_Space` layouts.
+
+use crate::bit::BitReader;
+use crate::core::diag::diag_event;
+use crate::core::result::Result;
+use crate::dwg::version::DwgVersion;
+use crate::entities::common::{
+    parse_common_object_header, parse_common_object_header_r14, parse_common_object_header_r2000,
+    parse_common_object_header_r2010, parse_common_object_header_r2013, read_handle_reference,
+    seek_handle_stream, string_stream_reader, CommonObjectHeader,
+};
+
+#[derive(Debug, Clone)]
+pub struct BlockHeaderObject {
+    /// The handle the block's entities name as their owner.
+    pub handle: u64,
+    /// Block name; `None` when an R2007+ record stores no string stream.
+    pub name: Option<String>,
+    pub is_anonymous: bool,
+    pub has_attributes: bool,
+    /// Set for external references (xrefs) and their overlays.
+    pub is_xref: bool,
+    pub is_xref_overlay: bool,
+    /// Whether the xref file was found and loaded when the drawing was
+    /// saved (DXF flag 32). Not stored before R2000.
+    pub is_xref_resolved: Option<bool>,
+    /// Insertion base point in block coordinates.
+    pub base_point: (f64, f64, f64),
+    /// Path of the referenced drawing, as saved; `None` for ordinary blocks.
+    pub xref_path: Option<String>,
+    /// Number of owned entities recorded in R2004+ files.
+    pub owned_object_count: Option<u32>,
+    /// The block's entities as its handle stream lists them; `None` when
+    /// the stream could not be read, and for R13-R2000 xrefs, which store
+    /// no entity chain.
+    pub entities: Option<BlockEntities>,
+}
+
+/// How a block header names the entities it owns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockEntities {
+    /// R13-R2000: the first and last entity of the block, which follow each
+    /// other in handle order.
+    Chain { first: u64, last: u64 },
+    /// R2004+: every owned entity, in drawing order.
+    Owned(Vec<u64>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum XrefStatus {
+    Resolved,
+    Unresolved,
+    /// Overlays are not followed when the referencing drawing is itself
+    /// attached elsewhere, whether or not they resolved.
+    Overlay,
+}
+
+impl XrefStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Resolved => "resolved",
+            Self::Unresolved => "unresolved",
+            Self::Overlay => "overlay",
+        }
+    }
+}
+
+impl BlockHeaderObject {
+    /// `None` for blocks that are not xrefs. R14 files, which do not record
+    /// the resolved state, report non-overlay xrefs as unresolved.
+    pub fn xref_status(&self) -> Option<XrefStatus> {
+        if !self.is_xref {
+            return None;
+        }
+        Some(if self.is_xref_overlay {
+            XrefStatus::Overlay
+        } else if self.is_xref_resolved == Some(true) {
+            XrefStatus::Resolved
+        } else {
+            XrefStatus::Unresolved
+        })
+    }
+}
+
+pub fn decode_block_header_r14(reader: &mut BitReader<'_>) -> Result<BlockHeaderObject> {
+    let header = parse_common_object_header_r14(reader)?;
+    decode_block_header_with_header(reader, &header, None, &DwgVersion::R14)
+}
+
+pub fn decode_block_header_r2000(reader: &mut BitReader<'_>) -> Result<BlockHeaderObject> {
+    let header = parse_common_object_header_r2000(reader)?;
+    decode_block_header_with_header(reader, &header, None, &DwgVersion::R2000)
+}
+
+pub fn decode_block_header(reader: &mut BitReader<'_>) -> Result<BlockHeaderObject> {
+    let header = parse_common_object_header(reader)?;
+    decode_block_header_with_header(reader, &header, None, &DwgVersion::R2004)
+}
+
+pub fn decode_block_header_r2007(reader: &mut BitReader<'_>) -> Result<BlockHeaderObject> {
+    let header = parse_common_object_header(reader)?;
+    let strings = string_stream_reader(reader, header.obj_size)?;
+    decode_block_header_with_header(reader, &header, Some(strings), &DwgVersion::R2007)
+}
+
+pub fn decode_block_header_r2010(
+    reader: &mut BitReader<'_>,
+    object_data_end_bit: u32,
+    object_handle: u64,
+) -> Result<BlockHeaderObject> {
+    let mut header = parse_common_object_header_r2010(reader, object_data_end_bit)?;
+    header.handle = object_handle;
+    let strings = string_stream_reader(reader, object_data_end_bit)?;
+    decode_block_header_with_header(reader, &header, Some(strings), &DwgVersion::R2010)
+}
+
+pub fn decode_block_header_r2013(
+    reader: &mut BitReader<'_>,
+    object_data_end_bit: u32,
+    object_handle: u64,
+) -> Result<BlockHeaderObject> {
+    let mut header = parse_common_object_header_r2013(reader, object_data_end_bit)?;
+    header.handle = object_handle;
+    let strings = string_stream_reader(reader, object_data_end_bit)?;
+    decode_block_header_with_header(reader, &header, Some(strings), &DwgVersion::R2013)
+}
+
+/// `strings` is `None` when the name is stored inline (before R2007), and
+/// `Some(stream)` for the object's string stream otherwise. The data after
+/// the xref path (inserts, description, preview) is skipped by seeking to
+/// the handle stream.
+fn decode_block_header_with_header(
+    reader: &mut BitReader<'_>,
+    header: &CommonObjectHeader,
+    strings: Option<Option<BitReader<'_>>>,
+    version: &DwgVersion,
+) -> Result<BlockHeaderObject> {
+    let r2007_plus = strings.is_some();
+    let mut strings = strings.flatten();
+    let name = match strings.as_mut() {
+        None if !r2007_plus => Some(reader.read_tv()?),
+        Some(stream) => Some(stream.read_tu()?),
+        None => None,
+    };
+    let _flag_64 = reader.read_b()?;
+    // R2007+ drop the xref index; only the two flag bits remain.
+    if !r2007_plus {
+        let _xref_index_plus_one = reader.read_bs()?;
+    }
+    let _xdep = reader.read_b()?;
+    let is_anonymous = reader.read_b()? != 0;
+    let has_attributes = reader.read_b()? != 0;
+    let is_xref = reader.read_b()? != 0;
+    let is_xref_overlay = reader.read_b()? != 0;
+    let is_xref_resolved = if matches!(version, DwgVersion::R14) {
+        None
+    } else {
+        Some(reader.read_b()? != 0)
+    };
+    let owned_object_count = if matches!(version, DwgVersion::R14 | DwgVersion::R2000) {
+        None
+    } else {
+        Some(reader.read_bl()?)
+    };
+    let base_point = reader.read_3bd()?;
+    let xref_path = match strings.as_mut() {
+        None if !r2007_plus => Some(reader.read_tv()?),
+        Some(stream) => Some(stream.read_tu()?),
+        None => None,
+    }
+    .filter(|path| !path.is_empty());
+    let handle = header.handle;
+    let entities = if is_xref && matches!(version, DwgVersion::R14 | DwgVersion::R2000) {
+        None
+    } else {
+        read_block_entities(reader, header, owned_object_count)
+            .inspect_err(|_err| diag_event!("object", "block {handle:#X} entities unread: {_err}"))
+            .ok()
+    };
+    Ok(BlockHeaderObject {
+        handle,
+        name,
+        is_anonymous,
+        has_attributes,
+        is_xref,
+        is_xref_overlay,
+        is_xref_resolved,
+        base_point,
+        xref_path,
+        owned_object_count,
+        entities,
+    })
+}
+
+/// Handle stream of a block header: the BLOCK_CONTROL owner, reactors,
+/// XDictionary and xref block, the BLOCK entity, then the entity chain or
+/// list. ENDBLK, the INSERTs and the layout that follow are left unread.
+fn read_block_entities(
+    reader: &mut BitReader<'_>,
+    header: &CommonObjectHeader,
+    owned_object_count: Option<u32>,
+) -> Result<BlockEntities> {
+    let handle = header.handle;
+    seek_handle_stream(reader, header.handle_stream_start_bit())?;
+    let _block_control = read_handle_reference(reader, handle)?;
+    for _ in 0..header.num_of_reactors {
+        let _reactor = read_handle_reference(reader, handle)?;
+    }
+    if header.xdic_missing_flag == 0 {
+        let _xdictionary = read_handle_reference(reader, handle)?;
+    }
+    // NULL unless the block is xref-dependent.
+    let _xref_block = read_handle_reference(reader, handle)?;
+    let _block_entity = read_handle_reference(reader, handle)?;
+    let Some(count) = owned_object_count else {
+        let first = read_handle_reference(reader, handle)?;
+        let last = read_handle_reference(reader, handle)?;
+        return Ok(BlockEntities::Chain { first, last });
+    };
+    // Every handle reference takes at least one byte; don't trust the count
+    // for the allocation size.
+    let capacity = (count as usize).min((reader.remaining_bits() / 8) as usize);
+    let mut owned = Vec::with_capacity(capacity);
+    for _ in 0..count {
+        owned.push(read_handle_reference(reader, handle)?);
+    }
+    Ok(BlockEntities::Owned(owned))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::test_support::Bits;
+
+    #[test]
+    fn decodes_r2000_xref_path_and_status() {
+        let mut bits = Bits::default();
+        bits.push(0, 32); // object size RL
+        bits.common_object_header(0x30);
+        bits.tv("SITE");
+        bits.push(1, 1); // 64-flag
+        bits.push(0b10, 2); // xref index BS = 0
+        bits.push(0, 1); // not xref-dependent
+        bits.push(0b0010, 4); // anonymous, attributes, xref, overlay
+        bits.push(1, 1); // resolved
+        bits.push(0b01, 2); // base point: 1.0, 0.0, 0.0
+        bits.push(0b10, 2);
+        bits.push(0b10, 2);
+        bits.tv("C:\\site.dwg");
+        let data = bits.bytes();
+
+        let block = decode_block_header_r2000(&mut BitReader::new(&data)).unwrap();
+        assert_eq!(block.handle, 0x30);
+        assert_eq!(block.name.as_deref(), Some("SITE"));
+        assert_eq!(block.base_point, (1.0, 0.0, 0.0));
+        assert_eq!(block.xref_path.as_deref(), Some("C:\\site.dwg"));
+        assert_eq!(block.xref_status(), Some(XrefStatus::Resolved));
+        assert_eq!(block.owned_object_count, None);
+
+        let overlay = BlockHeaderObject {
+            is_xref_overlay: true,
+            ..block.clone()
+        };
+        assert_eq!(overlay.xref_status(), Some(XrefStatus::Overlay));
+        let plain = BlockHeaderObject {
+            is_xref: false,
+            ..block
+        };
+        assert_eq!(plain.xref_status(), None);
+    }
+}
@@ -0,0 +1,277 @@
+//! LAYER symbol table records, read for their color and visibility state.
+
+use crate::bit::{BitReader, Endian};
+use crate::core::result::Result;
+use crate::dwg::version::DwgVersion;
+use crate::entities::common::skip_eed;
+
+/// `(handle, color index, true color)` of a LAYER record positioned after
+/// its type code; `expected_handle` stands in when the record stores none.
+pub fn decode_layer_color_record(
+    reader: &mut BitReader<'_>,
+    version: &DwgVersion,
+    expected_handle: u64,
+) -> Result<(u64, u16, Option<u32>)> {
+    if matches!(version, DwgVersion::R14 | DwgVersion::R2000) {
+        // A bare BS color index, negative when the layer is off.
+        let (record_handle, _, color_index) = decode_layer_state_record(reader, version)?;
+        let handle = if record_handle != 0 {
+            record_handle
+        } else {
+            expected_handle
+        };
+        return Ok((handle, color_index.unsigned_abs(), None));
+    }
+    let record_handle = read_layer_record_prefix(reader, version)?;
+    let style_start = reader.get_pos();
+    let variants = [
+        LayerColorParseVariant {
+            pre_flag_bits: 0,
+            post_flag_bits: 0,
+            pre_values_bits: 0,
+        },
+        LayerColorParseVariant {
+            pre_flag_bits: 2,
+            post_flag_bits: 0,
+            pre_values_bits: 0,
+        },
+        LayerColorParseVariant {
+            pre_flag_bits: 0,
+            post_flag_bits: 2,
+            pre_values_bits: 0,
+        },
+        LayerColorParseVariant {
+            pre_flag_bits: 0,
+            post_flag_bits: 0,
+            pre_values_bits: 2,
+        },
+        LayerColorParseVariant {
+            pre_flag_bits: 2,
+            post_flag_bits: 2,
+            pre_values_bits: 0,
+        },
+        LayerColorParseVariant {
+            pre_flag_bits: 2,
+            post_flag_bits: 0,
+            pre_values_bits: 2,
+        },
+        LayerColorParseVariant {
+            pre_flag_bits: 0,
+            post_flag_bits: 2,
+            pre_values_bits: 2,
+        },
+        LayerColorParseVariant {
+            pre_flag_bits: 2,
+            post_flag_bits: 2,
+            pre_values_bits: 2,
+        },
+    ];
+
+    let mut best: Option<(u64, (u16, Option<u32>))> = None;
+    for variant in variants {
+        reader.set_pos(style_start.0, style_start.1);
+        let Ok((color_index, true_color, color_byte)) = decode_layer_color_cmc(reader, variant)
+        else {
+            continue;
+        };
+        let score = layer_color_candidate_score(color_index, true_color, color_byte);
+        match &best {
+            Some((best_score, _)) if score >= *best_score => {}
+            _ => best = Some((score, (color_index, true_color))),
+        }
+    }
+
+    if let Some((_, (color_index, true_color))) = best {
+        let handle = if record_handle != 0 {
+            record_handle
+        } else {
+            expected_handle
+        };
+        return Ok((handle, color_index, true_color));
+    }
+
+    // Last resort: parse in the simplest form to keep progress.
+    reader.set_pos(style_start.0, style_start.1);
+    let (color_index, true_color, _) = decode_layer_color_cmc(reader, variants[0])?;
+    let handle = if record_handle != 0 {
+        record_handle
+    } else {
+        expected_handle
+    };
+    Ok((handle, color_index, true_color))
+}
+
+/// Read a LAYER record up to its flags (after the entry name) and return
+/// the handle it stores.
+fn read_layer_record_prefix(reader: &mut BitReader<'_>, version: &DwgVersion) -> Result<u64> {
+    // R2010+/R2013 objects start with handle directly after OT prefix.
+    // Older versions keep ObjSize (RL) before handle.
+    if !matches!(
+        version,
+        DwgVersion::R2010 | DwgVersion::R2013 | DwgVersion::R2018
+    ) {
+        let _obj_size = reader.read_rl(Endian::Little)?;
+    }
+    let record_handle = reader.read_h()?.value;
+    skip_eed(reader)?;
+
+    let _num_reactors = reader.read_bl()?;
+    if !matches!(version, DwgVersion::R14 | DwgVersion::R2000) {
+        let _xdic_missing_flag = reader.read_b()?;
+    }
+    if matches!(version, DwgVersion::R2013 | DwgVersion::R2018) {
+        let _has_ds_binary_data = reader.read_b()?;
+    }
+    // R2007+ store the entry name in the string stream. The data stream
+    // directly continues with layer state flags and color data.
+    if !matches!(
+        version,
+        DwgVersion::R2007 | DwgVersion::R2010 | DwgVersion::R2013 | DwgVersion::R2018
+    ) {
+        let _entry_name = reader.read_tv()?;
+    }
+    Ok(record_handle)
+}
+
+/// Visibility and edit state of a LAYER record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LayerState {
+    pub on: bool,
+    pub frozen: bool,
+    pub locked: bool,
+    pub plottable: bool,
+}
+
+impl LayerState {
+    pub fn is_hidden(&self) -> bool {
+        self.frozen || !self.on
+    }
+}
+
+/// R2000+ pack the states into the flags `BS` (DXF 70/290): 1 frozen,
+/// 2 off, 8 locked, 16 plotted. A layer is also off when its color index
+/// is negative, the only off marker before R2000. Also returns the raw
+/// color index.
+pub fn decode_layer_state_record(
+    reader: &mut BitReader<'_>,
+    version: &DwgVersion,
+) -> Result<(u64, LayerState, i16)> {
+    let record_handle = read_layer_record_prefix(reader, version)?;
+    let _flag_64 = reader.read_b()?;
+    // R2007+ drop the xref index; only the two flag bits remain.
+    if !matches!(
+        version,
+        DwgVersion::R2007 | DwgVersion::R2010 | DwgVersion::R2013 | DwgVersion::R2018
+    ) {
+        let _xref_index_plus_one = reader.read_bs()?;
+    }
+    let _xdep = reader.read_b()?;
+    let mut state = if matches!(version, DwgVersion::R14) {
+        let frozen = reader.read_b()? != 0;
+        let on = reader.read_b()? != 0;
+        let _frozen_in_new_viewports = reader.read_b()?;
+        let locked = reader.read_b()? != 0;
+        LayerState {
+            on,
+            frozen,
+            locked,
+            plottable: true,
+        }
+    } else {
+        let values = reader.read_bs()?;
+        LayerState {
+            on: values & 0x02 == 0,
+            frozen: values & 0x01 != 0,
+            locked: values & 0x08 != 0,
+            plottable: values & 0x10 != 0,
+        }
+    };
+    // R2004+ follow the index with the true color, which is not needed here.
+    let color_index = reader.read_bss()?;
+    if color_index < 0 {
+        state.on = false;
+    }
+    Ok((record_handle, state, color_index))
+}
+
+/// Bit layouts tried for the R2004+ flags ahead of the layer color, whose
+/// exact form varies between writers.
+#[derive(Clone, Copy)]
+struct LayerColorParseVariant {
+    pre_flag_bits: u8,
+    post_flag_bits: u8,
+    pre_values_bits: u8,
+}
+
+fn decode_layer_color_cmc(
+    reader: &mut BitReader<'_>,
+    variant: LayerColorParseVariant,
+) -> Result<(u16, Option<u32>, u8)> {
+    if variant.pre_flag_bits > 0 {
+        let _unknown = reader.read_bits_msb(variant.pre_flag_bits)?;
+    }
+    let _flag_64 = reader.read_b()?;
+    if variant.post_flag_bits > 0 {
+        let _unknown = reader.read_bits_msb(variant.post_flag_bits)?;
+    }
+    let _xref_index_plus_one = reader.read_bs()?;
+    let _xdep = reader.read_b()?;
+    let _frozen = reader.read_b()?;
+    let _on = reader.read_b()?;
+    let _frozen_new = reader.read_b()?;
+    let _locked = reader.read_b()?;
+    if variant.pre_values_bits > 0 {
+        let _unknown = reader.read_bits_msb(variant.pre_values_bits)?;
+    }
+    let _values = reader.read_bs()?;
+
+    let color_index = reader.read_bs()?;
+    let color_rgb = reader.read_bl()?;
+    let color_byte = reader.read_rc()?;
+    if (color_byte & 0x01) != 0 {
+        let _color_name = reader.read_tv()?;
+    }
+    if (color_byte & 0x02) != 0 {
+        let _book_name = reader.read_tv()?;
+    }
+
+    let true_color = if color_rgb == 0 || (color_rgb >> 24) == 0 {
+        // Keep only true 24-bit payload with marker byte present.
+        // If high byte is zero, treat as unset to prefer indexed color.
+        None
+    } else {
+        let rgb = color_rgb & 0x00FF_FFFF;
+        if rgb == 0 {
+            None
+        } else {
+            Some(rgb)
+        }
+    };
+    Ok((color_index, true_color, color_byte))
+}
+
+fn layer_color_candidate_score(color_index: u16, true_color: Option<u32>, color_byte: u8) -> u64 {
+    let mut score = 0u64;
+
+    if color_index <= 257 {
+        score += 0;
+    } else if color_index <= 4096 {
+        score += 1_000;
+    } else {
+        score += 100_000;
+    }
+
+    if color_byte <= 3 {
+        score += 0;
+    } else {
+        score += 10_000;
+    }
+
+    if let Some(rgb) = true_color {
+        if rgb == 0 || rgb > 0x00FF_FFFF {
+            score += 10_000;
+        }
+    }
+
+    score
+}
@@ -1,17 +1,39 @@
+pub mod appid;
+pub mod block_header;
 pub mod handle;
+pub mod layer;
 pub mod object_header_r2000;
 pub mod object_header_r2010;
 pub mod object_locator;
 pub mod object_record;
 pub mod object_ref;
 pub mod object_type;
+pub mod vport;
 
+pub use appid::{
+    decode_appid, decode_appid_r14, decode_appid_r2000, decode_appid_r2007, decode_appid_r2010,
+    decode_appid_r2013, AppIdObject,
+};
+pub use block_header::{
+    decode_block_header, decode_block_header_r14, decode_block_header_r2000,
+    decode_block_header_r2007, decode_block_header_r2010, decode_block_header_r2013, BlockEntities,
+    BlockHeaderObject, XrefStatus,
+};
 pub use handle::Handle;
+pub use layer::{decode_layer_color_record, decode_layer_state_record, LayerState};
 pub use object_header_r2000::{parse_at as parse_object_header_r2000, ObjectHeaderR2000};
-pub use object_header_r2010::{parse_at as parse_object_header_r2010, ObjectHeaderR2010};
-pub use object_locator::{build_object_index, build_object_index_from_directory, ObjectIndex};
+pub use object_header_r2010::{
+    parse_at as parse_object_header_r2010, ObjectHeaderR2010, ObjectStreamsR2010,
+};
+pub use object_locator::{
+    build_object_index, build_object_index_from_directory, object_map_block_crcs, ObjectIndex,
+};
 pub use object_record::{parse_object_record, ObjectRecord};
 pub use object_ref::ObjectRef;
 pub use object_type::{
     object_type_class, object_type_info, object_type_name, ObjectClass, ObjectTypeInfo,
 };
+pub use vport::{
+    decode_vport, decode_vport_r14, decode_vport_r2000, decode_vport_r2007, decode_vport_r2010,
+    decode_vport_r2013, VportObject,
+};
@@ -1,6 +1,9 @@
+use std::ops::Range;
+
 use crate::bit::BitReader;
 use crate::core::error::{DwgError, ErrorKind};
 use crate::core::result::Result;
+use crate::entities::common::string_stream_range;
 use crate::objects::object_record::{parse_object_record, ObjectRecord};
 use crate::objects::ObjectRef;
 
@@ -11,13 +14,74 @@ pub struct ObjectHeaderR2010 {
     pub body_start: usize,
     pub body_bit_pos: u8,
     pub handle_stream_size_bits: u32,
+    /// Bytes taken by the handle stream size (UMC) at the start of the body.
+    pub handle_stream_size_len: u8,
+    /// Bit offset just past the object type, where the object data begins.
+    pub data_start_bit: u32,
     pub type_code: u16,
 }
 
+/// Bit ranges of the three streams an R2010+ object record is split into,
+/// measured from the start of the record body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectStreamsR2010 {
+    /// Fixed fields, from just past the object type to the string stream
+    /// (or to the handle stream when there is none).
+    pub data: Range<u32>,
+    /// Text fields; `None` when the string-stream flag is clear.
+    pub strings: Option<Range<u32>>,
+    pub handles: Range<u32>,
+}
+
 impl ObjectHeaderR2010 {
     pub fn body_bit_pos(&self) -> u8 {
         self.body_bit_pos
     }
+
+    /// Bit offset of the handle stream, measured from the start of the object data.
+    pub fn handle_stream_start_bit(&self) -> Option<u32> {
+        self.data_size
+            .checked_mul(8)?
+            .checked_sub(self.handle_stream_size_bits)
+    }
+
+    /// Bit offset, from the start of the record body, where the data and
+    /// string streams end and the handle stream begins. The object size
+    /// counts from just past the handle stream size, so its length is added
+    /// back.
+    pub fn object_data_end_bit(&self) -> Option<u32> {
+        self.handle_stream_start_bit()?
+            .checked_add(u32::from(self.handle_stream_size_len) * 8)
+    }
+
+    /// Split `record` (the record this header was parsed from) into its
+    /// data, string and handle streams.
+    pub fn streams(&self, record: &ObjectRecord<'_>) -> Result<ObjectStreamsR2010> {
+        let data_end = self.object_data_end_bit().ok_or_else(|| {
+            DwgError::new(
+                ErrorKind::Format,
+                "R2010 handle stream exceeds object data size",
+            )
+        })?;
+        let body_bits = u32::try_from(record.body.len() * 8).unwrap_or(u32::MAX);
+        if data_end < self.data_start_bit || data_end > body_bits {
+            return Err(DwgError::new(
+                ErrorKind::Format,
+                format!("R2010 object data end bit {data_end} outside record body"),
+            ));
+        }
+        let strings = string_stream_range(&record.bit_reader(), data_end)?
+            .filter(|range| range.start >= self.data_start_bit);
+        let data_stop = strings.as_ref().map_or(data_end, |range| range.start);
+        let handles_end = data_end
+            .saturating_add(self.handle_stream_size_bits)
+            .min(body_bits);
+        Ok(ObjectStreamsR2010 {
+            data: self.data_start_bit..data_stop,
+            strings,
+            handles: data_end..handles_end,
+        })
+    }
 }
 
 pub fn parse_at(bytes: &[u8], offset: u32) -> Result<ObjectHeaderR2010> {
@@ -34,6 +98,7 @@ pub fn parse_from_record(record: &ObjectRecord<'_>) -> Result<ObjectHeaderR2010>
     reader.set_pos(0, record.body_bit_pos);
 
     let handle_stream_size_bits = reader.read_umc()?;
+    let handle_stream_size_len = ((reader.tell_bits() - u64::from(record.body_bit_pos)) / 8) as u8;
     let type_code = reader.read_ot_r2010()?;
     if type_code == 0 {
         return Err(DwgError::new(ErrorKind::Format, "object type code is zero"));
@@ -45,6 +110,8 @@ pub fn parse_from_record(record: &ObjectRecord<'_>) -> Result<ObjectHeaderR2010>
         body_start: record.body_start,
         body_bit_pos: record.body_bit_pos,
         handle_stream_size_bits,
+        handle_stream_size_len,
+        data_start_bit: reader.tell_bits() as u32,
         type_code,
     })
 }
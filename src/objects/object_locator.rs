@@ -5,7 +5,11 @@ use crate::container::section_loader;
 use crate::core::config::ParseConfig;
 use crate::core::error::{DwgError, ErrorKind};
 use crate::core::result::Result;
+use crate::dwg::version::DwgVersion;
 use crate::io::ByteReader;
+use crate::objects::object_header_r2000::{self, ObjectHeaderR2000};
+use crate::objects::object_header_r2010;
+use crate::objects::object_record::parse_object_record;
 use crate::objects::{Handle, ObjectRef};
 
 #[derive(Debug, Clone)]
@@ -27,6 +31,37 @@ impl ObjectIndex {
         self.objects.is_empty()
     }
 
+    /// Lazily parse the header of every object, in index order. `objects_buffer`
+    /// is the file bytes for R13-R15 and the decompressed `AcDb:AcDbObjects`
+    /// section for R2004+. R2010+ headers are read with their own layout and
+    /// reported in the same shape.
+    pub fn headers<'a>(
+        &'a self,
+        objects_buffer: &'a [u8],
+        version: &DwgVersion,
+    ) -> impl Iterator<Item = Result<(ObjectRef, ObjectHeaderR2000)>> + 'a {
+        let r2010_plus = matches!(
+            version,
+            DwgVersion::R2010 | DwgVersion::R2013 | DwgVersion::R2018
+        );
+        self.objects.iter().map(move |object| {
+            let header = if r2010_plus {
+                parse_object_record(objects_buffer, object.offset)
+                    .and_then(|record| object_header_r2010::parse_from_record(&record))
+                    .map(|header| ObjectHeaderR2000 {
+                        offset: header.offset,
+                        data_size: header.data_size,
+                        body_start: header.body_start,
+                        body_bit_pos: header.body_bit_pos,
+                        type_code: header.type_code,
+                    })
+            } else {
+                object_header_r2000::parse_for_object(objects_buffer, *object)
+            };
+            header.map(|header| (*object, header))
+        })
+    }
+
     pub fn from_objects(objects: Vec<ObjectRef>) -> Self {
         let mut by_handle = HashMap::with_capacity(objects.len());
         for (idx, obj) in objects.iter().enumerate() {
@@ -72,7 +107,7 @@ fn parse_object_map(bytes: &[u8], _config: &ParseConfig) -> Result<ObjectIndex>
         if reader.remaining() < 2 {
             break;
         }
-        let section_size = read_u16_be(&mut reader)? as usize;
+        let section_size = reader.read_u16_be()? as usize;
         if section_size == 2 {
             break;
         }
@@ -95,8 +130,9 @@ fn parse_object_map(bytes: &[u8], _config: &ParseConfig) -> Result<ObjectIndex>
         let mut last_offset: i64 = 0;
 
         while (reader.tell() - start) < (section_size as u64 - 2) {
-            let delta_handle = read_modular_char(&mut reader)?;
-            let delta_offset = read_modular_char(&mut reader)?;
+            // Handles only ever increase; only the offset delta is signed.
+            let delta_handle = reader.read_unsigned_modular_char()? as i64;
+            let delta_offset = reader.read_modular_char()?;
             last_handle = last_handle.checked_add(delta_handle).ok_or_else(|| {
                 DwgError::new(ErrorKind::Format, "object map handle overflow")
                     .with_offset(reader.tell())
@@ -129,38 +165,35 @@ fn parse_object_map(bytes: &[u8], _config: &ParseConfig) -> Result<ObjectIndex>
         if reader.remaining() < 2 {
             break;
         }
-        let _crc = read_u16_be(&mut reader)?;
+        let _crc = reader.read_u16_be()?;
     }
 
     Ok(ObjectIndex::from_objects(objects))
 }
 
-fn read_u16_be(reader: &mut ByteReader<'_>) -> Result<u16> {
-    let hi = reader.read_u8()? as u16;
-    let lo = reader.read_u8()? as u16;
-    Ok((hi << 8) | lo)
-}
-
-fn read_modular_char(reader: &mut ByteReader<'_>) -> Result<i64> {
-    let mut value: i64 = 0;
-    let mut shift = 0;
-
-    for _ in 0..4 {
-        let mut byte = reader.read_u8()?;
-        if (byte & 0x80) == 0 {
-            let negative = (byte & 0x40) != 0;
-            if negative {
-                byte &= 0xBF;
-            }
-            value |= (byte as i64) << shift;
-            if negative {
-                value = -value;
-            }
-            return Ok(value);
+/// Stored (big-endian) CRC of each object-map block, in block order.
+pub fn object_map_block_crcs(bytes: &[u8]) -> Result<Vec<u16>> {
+    let mut reader = ByteReader::new(bytes);
+    let mut crcs = Vec::new();
+    loop {
+        if reader.remaining() < 2 {
+            break;
+        }
+        let section_size = reader.read_u16_be()? as usize;
+        if section_size == 2 {
+            break;
+        }
+        if section_size < 2 {
+            return Err(DwgError::new(
+                ErrorKind::Format,
+                format!("invalid object map block size {section_size}"),
+            ));
+        }
+        reader.skip(section_size - 2)?;
+        if reader.remaining() < 2 {
+            break;
         }
-        byte &= 0x7F;
-        value |= (byte as i64) << shift;
-        shift += 7;
+        crcs.push(reader.read_u16_be()?);
     }
-    Ok(value)
+    Ok(crcs)
 }
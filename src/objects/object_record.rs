@@ -11,6 +11,8 @@ pub struct ObjectRecord<'a> {
     pub body_bit_pos: u8,
     pub body: Cow<'a, [u8]>,
     pub raw: Cow<'a, [u8]>,
+    /// Readers over this record are [strict](BitReader::is_strict).
+    pub strict: bool,
 }
 
 impl<'a> ObjectRecord<'a> {
@@ -29,6 +31,7 @@ impl<'a> ObjectRecord<'a> {
     pub fn bit_reader(&self) -> BitReader<'_> {
         let mut reader = BitReader::new(self.body.as_ref());
         reader.set_pos(0, self.body_bit_pos);
+        reader.set_strict(self.strict);
         reader
     }
 }
@@ -76,6 +79,7 @@ pub fn parse_object_record<'a>(bytes: &'a [u8], offset: u32) -> Result<ObjectRec
         body_bit_pos,
         body: Cow::Borrowed(body),
         raw: Cow::Borrowed(raw),
+        strict: false,
     })
 }
 
@@ -88,5 +92,6 @@ pub fn parse_object_record_owned(bytes: &[u8], offset: u32) -> Result<ObjectReco
         body_bit_pos: record.body_bit_pos,
         body: Cow::Owned(record.body.as_ref().to_vec()),
         raw: Cow::Owned(record.raw.as_ref().to_vec()),
+        strict: record.strict,
     })
 }
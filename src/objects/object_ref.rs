@@ -1,6 +1,6 @@
 use crate::objects::Handle;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ObjectRef {
     pub handle: Handle,
     pub offset: u32,
@@ -0,0 +1,194 @@
+//! VPORT symbol table records: saved tiled-viewport configurations. The
+//! `*ACTIVE` record is the view the drawing opens with.
+
+use crate::bit::{BitReader, Endian};
+use crate::core::result::Result;
+use crate::entities::common::{
+    parse_common_object_header, parse_common_object_header_r14, parse_common_object_header_r2000,
+    parse_common_object_header_r2010, parse_common_object_header_r2013, string_stream_reader,
+    CommonObjectHeader,
+};
+
+type Point2 = (f64, f64);
+type Point3 = (f64, f64, f64);
+
+#[derive(Debug, Clone)]
+pub struct VportObject {
+    pub handle: u64,
+    /// Entry name (`*ACTIVE` for the current configuration); `None` when an
+    /// R2007+ record stores no string stream.
+    pub name: Option<String>,
+    /// Viewport extents on screen, as fractions of the drawing window.
+    pub lower_left: Point2,
+    pub upper_right: Point2,
+    /// View center and height in display coordinates.
+    pub view_center: Point2,
+    pub view_height: f64,
+    /// View width over view height.
+    pub aspect_ratio: f64,
+    pub view_target: Point3,
+    /// Direction from the target towards the camera.
+    pub view_direction: Point3,
+    pub view_twist: f64,
+    pub lens_length: f64,
+    pub grid_on: bool,
+    pub grid_spacing: Point2,
+    pub snap_on: bool,
+    pub snap_base: Point2,
+    pub snap_spacing: Point2,
+    pub snap_rotation: f64,
+}
+
+/// Fields each release added to the record.
+#[derive(Debug, Clone, Copy)]
+struct VportLayout {
+    r2000_plus: bool,
+    r2007_plus: bool,
+}
+
+pub fn decode_vport_r14(reader: &mut BitReader<'_>) -> Result<VportObject> {
+    let header = parse_common_object_header_r14(reader)?;
+    let layout = VportLayout {
+        r2000_plus: false,
+        r2007_plus: false,
+    };
+    decode_vport_with_header(reader, header, None, layout)
+}
+
+pub fn decode_vport_r2000(reader: &mut BitReader<'_>) -> Result<VportObject> {
+    let header = parse_common_object_header_r2000(reader)?;
+    decode_vport_with_header(reader, header, None, R2000_LAYOUT)
+}
+
+pub fn decode_vport(reader: &mut BitReader<'_>) -> Result<VportObject> {
+    let header = parse_common_object_header(reader)?;
+    decode_vport_with_header(reader, header, None, R2000_LAYOUT)
+}
+
+pub fn decode_vport_r2007(reader: &mut BitReader<'_>) -> Result<VportObject> {
+    let header = parse_common_object_header(reader)?;
+    let strings = string_stream_reader(reader, header.obj_size)?;
+    decode_vport_with_header(reader, header, Some(strings), R2007_LAYOUT)
+}
+
+pub fn decode_vport_r2010(
+    reader: &mut BitReader<'_>,
+    object_data_end_bit: u32,
+    object_handle: u64,
+) -> Result<VportObject> {
+    let mut header = parse_common_object_header_r2010(reader, object_data_end_bit)?;
+    header.handle = object_handle;
+    let strings = string_stream_reader(reader, object_data_end_bit)?;
+    decode_vport_with_header(reader, header, Some(strings), R2007_LAYOUT)
+}
+
+pub fn decode_vport_r2013(
+    reader: &mut BitReader<'_>,
+    object_data_end_bit: u32,
+    object_handle: u64,
+) -> Result<VportObject> {
+    let mut header = parse_common_object_header_r2013(reader, object_data_end_bit)?;
+    header.handle = object_handle;
+    let strings = string_stream_reader(reader, object_data_end_bit)?;
+    decode_vport_with_header(reader, header, Some(strings), R2007_LAYOUT)
+}
+
+const R2000_LAYOUT: VportLayout = VportLayout {
+    r2000_plus: true,
+    r2007_plus: false,
+};
+
+const R2007_LAYOUT: VportLayout = VportLayout {
+    r2000_plus: true,
+    r2007_plus: true,
+};
+
+/// `strings` is `None` when names are stored inline (before R2007), and
+/// `Some(stream)` for the object's string stream otherwise.
+fn decode_vport_with_header(
+    reader: &mut BitReader<'_>,
+    header: CommonObjectHeader,
+    strings: Option<Option<BitReader<'_>>>,
+    layout: VportLayout,
+) -> Result<VportObject> {
+    let name = match strings {
+        None => Some(reader.read_tv()?),
+        Some(Some(mut stream)) => Some(stream.read_tu()?),
+        Some(None) => None,
+    };
+    let _flag_64 = reader.read_b()?;
+    // R2007 dropped the xref index from table records.
+    if !layout.r2007_plus {
+        let _xref_index = reader.read_bs()?;
+    }
+    let _xref_dependent = reader.read_b()?;
+
+    let view_height = reader.read_bd()?;
+    let view_width = reader.read_bd()?;
+    let view_center = read_2rd(reader)?;
+    let view_target = reader.read_3bd()?;
+    let view_direction = reader.read_3bd()?;
+    let view_twist = reader.read_bd()?;
+    let lens_length = reader.read_bd()?;
+    let _front_clip = reader.read_bd()?;
+    let _back_clip = reader.read_bd()?;
+    let _view_mode = reader.read_bits_msb(4)?;
+    if layout.r2000_plus {
+        let _render_mode = reader.read_rc()?;
+    }
+    if layout.r2007_plus {
+        let _use_default_lights = reader.read_b()?;
+        let _default_lighting_type = reader.read_rc()?;
+        let _brightness = reader.read_bd()?;
+        let _contrast = reader.read_bd()?;
+        // Ambient color CMC; its names, if any, live in the string stream.
+        let _index = reader.read_bs()?;
+        let _rgb = reader.read_bl()?;
+        let _name_flags = reader.read_rc()?;
+    }
+    let lower_left = read_2rd(reader)?;
+    let upper_right = read_2rd(reader)?;
+    let _ucs_follow = reader.read_b()?;
+    let _circle_zoom = reader.read_bs()?;
+    let _fast_zoom = reader.read_b()?;
+    let _ucs_icon = reader.read_bb()?;
+    let grid_on = reader.read_b()? != 0;
+    let grid_spacing = read_2rd(reader)?;
+    let snap_on = reader.read_b()? != 0;
+    let _snap_style = reader.read_b()?;
+    let _snap_iso_pair = reader.read_bs()?;
+    let snap_rotation = reader.read_bd()?;
+    let snap_base = read_2rd(reader)?;
+    let snap_spacing = read_2rd(reader)?;
+
+    Ok(VportObject {
+        handle: header.handle,
+        name,
+        lower_left,
+        upper_right,
+        view_center,
+        view_height,
+        aspect_ratio: if view_height != 0.0 {
+            view_width / view_height
+        } else {
+            0.0
+        },
+        view_target,
+        view_direction,
+        view_twist,
+        lens_length,
+        grid_on,
+        grid_spacing,
+        snap_on,
+        snap_base,
+        snap_spacing,
+        snap_rotation,
+    })
+}
+
+fn read_2rd(reader: &mut BitReader<'_>) -> Result<Point2> {
+    Ok((
+        reader.read_rd(Endian::Little)?,
+        reader.read_rd(Endian::Little)?,
+    ))
+}
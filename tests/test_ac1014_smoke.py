@@ -79,6 +79,17 @@ def test_ac1014_arc_decode_smoke() -> None:
     assert abs(a1 - math.pi) < 1.0e-6
 
 
+def test_ac1014_arc_sweeps_extend_arc_rows() -> None:
+    arc_rows = raw.decode_arc_entities(str(R14_ARC_SAMPLE), limit=16)
+    sweep_rows = raw.decode_arc_sweeps(str(R14_ARC_SAMPLE), limit=16)
+    assert [row[:5] for row in sweep_rows] == [row[:5] for row in arc_rows]
+
+    _handle, _cx, _cy, _cz, _r, a0, a1, sweep, extrusion = sweep_rows[0]
+    assert 0.0 <= a0 < math.tau and 0.0 <= a1 < math.tau
+    assert abs(sweep - math.pi) < 1.0e-6
+    assert extrusion == (0.0, 0.0, 1.0)
+
+
 def test_ac1014_circle_decode_smoke() -> None:
     assert R14_CIRCLE_SAMPLE.exists(), f"missing sample: {R14_CIRCLE_SAMPLE}"
     assert raw.detect_version(str(R14_CIRCLE_SAMPLE)) == "AC1014"
@@ -87,14 +98,14 @@ def test_ac1014_circle_decode_smoke() -> None:
     assert len(circle_rows) >= 1
     for row in circle_rows:
         assert row[0] > 0
-        for value in row[1:]:
+        for value in (*row[1:6], *row[6]):
             assert math.isfinite(value)
 
     doc = ezdwg.read(str(R14_CIRCLE_SAMPLE))
     circles = list(doc.modelspace().query("CIRCLE"))
     assert len(circles) == len(circle_rows)
     assert len(circles) >= 1
-    handle, cx, cy, cz, r = circle_rows[0]
+    handle, cx, cy, cz, r, _thickness, _extrusion = circle_rows[0]
     assert handle > 0
     assert abs(cx - 50.0) < 1.0e-6
     assert abs(cy - 50.0) < 1.0e-6
@@ -151,8 +162,9 @@ def test_ac1014_point2d_decode_smoke() -> None:
     points = list(doc.modelspace().query("POINT"))
     assert len(points) == len(point_rows)
     assert len(points) >= 1
-    handle, x, y, z, x_axis_angle = point_rows[0]
+    handle, x, y, z, thickness, x_axis_angle = point_rows[0]
     assert handle > 0
+    assert thickness == 0.0
     assert abs(x - 50.0) < 1.0e-6
     assert abs(y - 50.0) < 1.0e-6
     assert abs(z) < 1.0e-6
@@ -174,7 +186,7 @@ def test_ac1014_point3d_decode_smoke() -> None:
     points = list(doc.modelspace().query("POINT"))
     assert len(points) == len(point_rows)
     assert len(points) >= 1
-    handle, x, y, z, x_axis_angle = point_rows[0]
+    handle, x, y, z, _thickness, x_axis_angle = point_rows[0]
     assert handle > 0
     # Keep this tolerant: the R14 sample keeps Y/Z at 50 and angle at zero.
     assert abs(y - 50.0) < 1.0e-6
@@ -3,6 +3,8 @@ from __future__ import annotations
 import math
 from pathlib import Path
 
+import pytest
+
 import ezdwg
 from ezdwg import raw
 
@@ -28,10 +30,12 @@ def _assert_finite_arc_rows(
             assert math.isfinite(value)
 
 
-def _assert_finite_circle_rows(rows: list[tuple[int, float, float, float, float]]) -> None:
+def _assert_finite_circle_rows(
+    rows: list[tuple[int, float, float, float, float, float, tuple[float, float, float]]],
+) -> None:
     for row in rows:
         assert row[0] > 0
-        for value in row[1:]:
+        for value in (*row[1:6], *row[6]):
             assert math.isfinite(value)
 
 
@@ -86,3 +90,67 @@ def test_ac1032_large_bulk_decode_smoke() -> None:
     _assert_finite_rows(line_rows)
     _assert_finite_arc_rows(arc_rows)
     _assert_finite_circle_rows(circle_rows)
+
+
+def test_ac1032_entity_styles_surface_transparency() -> None:
+    rows = raw.decode_entity_styles(str(LARGE_AC1032))
+    transparencies = [row[3] for row in rows if row[3] is not None]
+    assert transparencies
+    assert all(0 <= value <= 0xFFFFFFFF for value in transparencies)
+
+    doc = ezdwg.read(str(LARGE_AC1032))
+    by_handle = {row[0]: row[3] for row in rows}
+    entity = next(e for e in doc.modelspace().query() if e.handle in by_handle)
+    assert entity.dxf["transparency"] == by_handle[entity.handle]
+
+
+def test_ac1032_spline_points_are_sampled() -> None:
+    raw_rows = {row[0]: row for row in raw.decode_spline_entities(str(LARGE_AC1032))}
+    rows = raw.decode_spline_points(str(LARGE_AC1032), samples_per_segment=8, include_raw=True)
+    assert {row[0] for row in rows} == set(raw_rows)
+
+    for handle, degree, closed, points, control_points, knots in rows:
+        _, flags, _, raw_knots, raw_control, _, raw_fit = raw_rows[handle]
+        assert degree == flags[1]
+        assert closed == flags[3]
+        assert control_points == raw_control
+        assert knots == raw_knots
+        assert len(points) > len(raw_control or raw_fit)
+        anchors = raw_control or raw_fit
+        assert points[0] == pytest.approx(anchors[0])
+        assert points[-1] == pytest.approx(anchors[-1])
+
+    assert all(row[4] is None and row[5] is None for row in raw.decode_spline_points(str(LARGE_AC1032)))
+
+
+def test_ac1032_wcs_flag_keeps_world_z_geometry() -> None:
+    for decode in (raw.decode_arc_entities, raw.decode_circle_entities):
+        ocs_rows = decode(str(LARGE_AC1032), limit=32)
+        wcs_rows = decode(str(LARGE_AC1032), limit=32, wcs=True)
+        assert ocs_rows
+        assert wcs_rows == ocs_rows
+
+    ocs_rows = raw.decode_lwpolyline_entities(str(LARGE_AC1032), limit=32)
+    wcs_rows = raw.decode_lwpolyline_entities(str(LARGE_AC1032), limit=32, wcs=True)
+    assert ocs_rows
+    for ocs_row, wcs_row in zip(ocs_rows, wcs_rows, strict=True):
+        assert wcs_row[:2] == ocs_row[:2] and wcs_row[3:] == ocs_row[3:]
+        # World-Z polylines keep their OCS XY and sit at one elevation.
+        assert [point[:2] for point in wcs_row[2]] == ocs_row[2]
+        assert len({point[2] for point in wcs_row[2]}) <= 1
+
+
+def test_ac1032_space_filter_applies_to_raw_walks() -> None:
+    path = str(LARGE_AC1032)
+    model = raw.configure(path, config={"space_filter": "model"})
+    paper = raw.configure(path, config={"space_filter": "paper"})
+
+    model_lines = {row[0] for row in raw.decode_line_entities(model)}
+    walked_lines, _, _ = raw.decode_line_arc_circle_entities(model)
+    assert {row[0] for row in walked_lines} == model_lines
+
+    all_inserts = {row[0] for row in raw.decode_insert_entities(path)}
+    model_inserts = {row[0] for row in raw.decode_insert_entities(model)}
+    assert model_inserts and model_inserts < all_inserts
+    assert {row[0] for row in raw.decode_inserts_with_attribs(model)} == model_inserts
+    assert raw.decode_insert_entities(paper) == []
@@ -101,9 +101,9 @@ def test_line_supplementary_handles_detects_unique_long_segments() -> None:
 
 def test_circle_supplementary_handles_detects_center_circle() -> None:
     circle_rows = [
-        (10, 0.0, 0.0, 0.0, 3.0),
-        (11, 0.0, 0.0, 0.0, 8.0),
-        (12, 10.0, 0.0, 0.0, 2.0),
+        (10, 0.0, 0.0, 0.0, 3.0, 0.0, (0.0, 0.0, 1.0)),
+        (11, 0.0, 0.0, 0.0, 8.0, 0.0, (0.0, 0.0, 1.0)),
+        (12, 10.0, 0.0, 0.0, 2.0, 0.0, (0.0, 0.0, 1.0)),
     ]
     entity_style_map = {
         10: (None, None, 896),
@@ -2,6 +2,8 @@ from __future__ import annotations
 
 import math
 
+import pytest
+
 import ezdwg.document as document_module
 
 
@@ -86,7 +88,7 @@ def test_query_uses_bulk_line_arc_circle_decoder(monkeypatch) -> None:
         lambda _path: (
             [(1, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0)],
             [(2, 0.0, 0.0, 0.0, 1.0, 0.0, math.pi / 2.0)],
-            [(3, 2.0, 2.0, 0.0, 0.5)],
+            [(3, 2.0, 2.0, 0.0, 0.5, 0.0, (0.0, 0.0, 1.0))],
         ),
     )
     monkeypatch.setattr(
@@ -135,3 +137,13 @@ def test_query_single_type_does_not_force_bulk_line_arc_circle_decoder(monkeypat
     doc = document_module.Document(path="dummy.dwg", version="AC1018")
     entities = list(doc.modelspace().query("LINE"))
     assert [entity.dxftype for entity in entities] == ["LINE"]
+
+
+def test_iter_type_raises_unsupported_entity_error(monkeypatch) -> None:
+    monkeypatch.setattr(document_module, "_entity_style_map", lambda _path: {})
+    monkeypatch.setattr(document_module, "_layer_color_map", lambda _path: {})
+
+    doc = document_module.Document(path="dummy.dwg", version="AC1018")
+    with pytest.raises(document_module.raw.UnsupportedEntityError, match="unsupported entity type"):
+        list(doc.modelspace()._iter_type("WIPEOUT"))
+    assert issubclass(document_module.raw.UnsupportedEntityError, ValueError)
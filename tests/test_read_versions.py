@@ -1,5 +1,6 @@
 from __future__ import annotations
 
+import math
 from pathlib import Path
 
 import pytest
@@ -136,3 +137,721 @@ def test_read_ac1014_sample_smoke() -> None:
 
     rows = ezdwg.raw.list_object_headers_with_type(str(path), limit=20)
     assert len(rows) == 20
+
+
+def test_raw_errors_carry_kind_and_offset(tmp_path: Path) -> None:
+    source = (ROOT / "test_dwg/line_2000.dwg").read_bytes()
+    path = tmp_path / "truncated.dwg"
+    path.write_bytes(source[:30])
+
+    with pytest.raises(OSError) as excinfo:
+        ezdwg.raw.list_object_headers(str(path))
+
+    assert excinfo.value.kind == "io"
+    assert excinfo.value.offset == 30
+    assert "unexpected EOF" in excinfo.value.detail
+
+
+def test_raw_errors_without_offset_report_none(tmp_path: Path) -> None:
+    source = (ROOT / "test_dwg/line_2000.dwg").read_bytes()
+    path = tmp_path / "truncated.dwg"
+    path.write_bytes(source[:400])
+
+    with pytest.raises(ValueError) as excinfo:
+        ezdwg.raw.list_object_headers(str(path))
+
+    assert excinfo.value.kind == "format"
+    assert excinfo.value.offset is None
+
+
+def test_decode_inserts_with_attribs_matches_plain_inserts() -> None:
+    path = str(ROOT / "test_dwg/insert_2004.dwg")
+    plain = ezdwg.raw.decode_insert_entities(path)
+    with_attribs = ezdwg.raw.decode_inserts_with_attribs(path)
+
+    assert [row[:8] for row in with_attribs] == [tuple(row) for row in plain]
+    for row in with_attribs:
+        for attrib_handle, tag, text in row[8]:
+            assert isinstance(attrib_handle, int)
+            assert tag is None or isinstance(tag, str)
+            assert isinstance(text, str)
+
+
+def test_spatial_query_matches_entity_bounds() -> None:
+    path = str(ROOT / "test_dwg/arc_2004.dwg")
+    bounds = ezdwg.raw.entity_bounds(path)
+    assert bounds
+    for handle, (xmin, ymin), (xmax, ymax) in bounds:
+        assert xmin <= xmax and ymin <= ymax
+        hits = ezdwg.raw.spatial_query(path, (xmin, ymin), (xmax, ymax))
+        assert handle in hits
+
+    far = max(abs(v) for _, lo, hi in bounds for v in (*lo, *hi)) * 10 + 100
+    assert ezdwg.raw.spatial_query(path, (far, far), (far + 1, far + 1)) == []
+
+
+def test_object_summaries_combine_type_layer_and_bounds() -> None:
+    path = str(ROOT / "test_dwg/arc_2004.dwg")
+    rows = ezdwg.raw.object_summaries(path)
+    assert len(rows) == len(ezdwg.raw.list_object_map_entries(path))
+    bounds = {handle: (lo, hi) for handle, lo, hi in ezdwg.raw.entity_bounds(path)}
+    arcs = [row for row in rows if row[1] == "ARC"]
+    assert arcs
+    for handle, _, layer_name, bbox in arcs:
+        assert layer_name == "0"
+        assert bbox == bounds[handle]
+    assert all(bbox is None for _, type_name, _, bbox in rows if type_name == "LAYER")
+
+
+def test_decode_layer_states_reads_visibility_flags() -> None:
+    path = str(ROOT / "test_dwg/acadsharp/sample_AC1027.dwg")
+    rows = ezdwg.raw.decode_layer_states(path)
+    assert [row[0] for row in rows] == [row[0] for row in ezdwg.raw.decode_layer_colors(path)]
+    assert sum(not on for _, on, _, _, _ in rows) == 1
+    assert sum(frozen for _, _, frozen, _, _ in rows) == 1
+    assert sum(locked for _, _, _, locked, _ in rows) == 1
+    assert sum(not plottable for *_, plottable in rows) == 2
+
+    for name in ["line_2000.dwg", "line_2004.dwg", "line_2007.dwg", "line_2013.dwg"]:
+        default_layer = ezdwg.raw.decode_layer_states(str(ROOT / "test_dwg" / name))
+        assert [row[1:] for row in default_layer] == [(True, False, False, True)]
+
+    assert ezdwg.raw.object_summaries(path, skip_hidden_layers=True) == ezdwg.raw.object_summaries(
+        path
+    )
+
+
+@pytest.mark.parametrize("name", ["line_R14.dwg", "line_2000.dwg", "line_2007.dwg", "line_2013.dwg"])
+def test_decode_entity_line_styles_reports_lineweight_from_r2000(name: str) -> None:
+    path = str(ROOT / "test_dwg" / name)
+    (line,) = ezdwg.raw.decode_line_entities(path)
+    styles = {handle: (scale, weight) for handle, scale, weight in ezdwg.raw.decode_entity_line_styles(path)}
+
+    if name == "line_R14.dwg":
+        assert styles[line[0]][1] is None
+    else:
+        # 29 is the BYLAYER lineweight code.
+        assert styles[line[0]] == (1.0, 29)
+
+
+@pytest.mark.parametrize("name", ["line_R14.dwg", "line_2000.dwg", "line_2004.dwg", "line_2013.dwg"])
+def test_list_blocks_puts_the_line_in_model_space(name: str) -> None:
+    rows = ezdwg.raw.list_blocks(str(ROOT / "test_dwg" / name))
+    counts = {block_name.lower(): count for _, block_name, _, count, _ in rows}
+    assert counts["*model_space"] == 1
+    assert all(not is_xref and base == (0.0, 0.0, 0.0) for _, _, is_xref, _, base in rows)
+
+
+@pytest.mark.parametrize("name", ["line_R14.dwg", "line_2000.dwg", "line_2007.dwg", "line_2013.dwg"])
+def test_modelspace_entity_handles_finds_the_line(name: str) -> None:
+    path = str(ROOT / "test_dwg" / name)
+    (line,) = ezdwg.raw.decode_line_entities(path)
+    assert ezdwg.raw.modelspace_entity_handles(path) == [line[0]]
+
+
+@pytest.mark.parametrize("name", ["line_2000.dwg", "line_2007.dwg", "line_2013.dwg"])
+def test_read_object_records_by_handles_matches_by_type(name: str) -> None:
+    path = str(ROOT / "test_dwg" / name)
+    by_type = ezdwg.raw.read_object_records_by_type(path, [0x13, 0x31])
+    handles = [row[0] for row in reversed(by_type)]
+    assert ezdwg.raw.read_object_records_by_handles(path, handles + [0xFFFFFF]) == list(
+        reversed(by_type)
+    )
+    assert ezdwg.raw.read_object_records_by_handles(path, []) == []
+
+
+@pytest.mark.parametrize("name", ["insert_2004.dwg", "acadsharp/sample_AC1027.dwg"])
+def test_list_xrefs_is_empty_without_external_references(name: str) -> None:
+    path = str(ROOT / "test_dwg" / name)
+    assert not any(is_xref for _, _, is_xref, _, _ in ezdwg.raw.list_blocks(path))
+    assert ezdwg.raw.list_xrefs(path) == []
+
+
+def test_decode_entities_paginated_pages_through_the_object_map() -> None:
+    path = str(ROOT / "test_dwg/acadsharp/sample_AC1032.dwg")
+    total = len(ezdwg.raw.list_object_map_entries(path))
+    pages = [
+        ezdwg.raw.decode_entities_paginated(path, offset, 100) for offset in range(0, total, 100)
+    ]
+    rows = [row for page in pages for row in page]
+    handles = [handle for handle, _, _ in rows]
+
+    assert len(handles) == len(set(handles))
+    assert ("LINE", "open_curve") in {(type_name, kind) for _, type_name, kind in rows}
+    assert ezdwg.raw.decode_entities_paginated(path, total, 100) == []
+
+
+def test_list_inexact_decodes_flags_heuristic_layouts() -> None:
+    path = str(ROOT / "test_dwg/acadsharp/sample_AC1027.dwg")
+    rows = ezdwg.raw.list_inexact_decodes(path)
+    assert {method for _, _, method in rows} <= {"fallback", "guessed"}
+    assert (0xC96, "DIM_LINEAR", "guessed") in rows
+    assert all(
+        type_name in {"MTEXT", "ATTRIB", "ATTDEF"} or type_name.startswith("DIM_")
+        for _, type_name, _ in rows
+    )
+    assert ezdwg.raw.list_inexact_decodes(str(ROOT / "test_dwg/mtext_2004.dwg")) == []
+
+
+def test_sniff_routes_dwg_and_dxf_files() -> None:
+    assert ezdwg.raw.sniff(str(ROOT / "test_dwg/line_2007.dwg")) == ("dwg", "AC1021")
+    assert ezdwg.raw.sniff(str(ROOT / "test_dwg/line_2007.dxf")) == ("dxf", None)
+    assert ezdwg.raw.sniff(b"AutoCAD Binary DXF\r\n\x1a\x00") == ("dxf_binary", None)
+    assert ezdwg.raw.sniff(b"%PDF-1.7") == ("other", None)
+
+    with pytest.raises(ValueError, match="DXF file, not a DWG") as excinfo:
+        ezdwg.raw.detect_version(str(ROOT / "test_dwg/line_2007.dxf"))
+    assert excinfo.value.kind == "not_dwg"
+
+
+@pytest.mark.parametrize(
+    ("name", "image_format", "magic"),
+    [
+        ("line_R14.dwg", "bmp", b"BM"),
+        ("line_2007.dwg", "bmp", b"BM"),
+        ("line_2013.dwg", "png", b"\x89PNG"),
+        ("acadsharp/sample_AC1032.dwg", "png", b"\x89PNG"),
+    ],
+)
+def test_read_thumbnail_returns_a_complete_image(name: str, image_format: str, magic: bytes) -> None:
+    thumbnail = ezdwg.raw.read_thumbnail(str(ROOT / "test_dwg" / name))
+    assert thumbnail is not None
+    fmt, data = thumbnail
+    assert fmt == image_format
+    assert isinstance(data, bytes) and data.startswith(magic)
+
+
+def test_read_thumbnail_is_none_without_preview() -> None:
+    assert ezdwg.raw.read_thumbnail(str(ROOT / "test_dwg/text_2004.dwg")) is None
+
+
+@pytest.mark.parametrize("name", ["sample_AC1027.dwg", "sample_AC1032.dwg"])
+def test_list_images_decodes_r2013_image_with_its_definition(name: str) -> None:
+    path = str(ROOT / "test_dwg" / "acadsharp" / name)
+    (image,) = ezdwg.raw.list_images(path)
+    handle, insertion, u_vector, v_vector, size, display_flags, clip, definition = image
+
+    assert handle == 0x8C6
+    assert insertion == pytest.approx((0.0, 15.0, 0.0))
+    assert u_vector == pytest.approx((0.25 / 24.0, 0.0, 0.0))
+    assert v_vector == pytest.approx((0.0, 0.25 / 24.0, 0.0))
+    assert size == (994.0, 965.0)
+    assert display_flags == 7
+    assert clip == []
+    def_handle, _file_path, image_size, pixel_size, resolution_units = definition
+    assert def_handle == 0x8C4
+    assert image_size == size
+    assert pixel_size == pytest.approx((25.4 / 96.0, 25.4 / 96.0))
+    assert resolution_units == 5
+
+
+@pytest.mark.parametrize("name", ["sample_AC1027.dwg", "sample_AC1032.dwg"])
+def test_decode_wipeouts_returns_the_world_mask_polygon(name: str) -> None:
+    path = str(ROOT / "test_dwg" / "acadsharp" / name)
+    ((handle, display_flags, clipping, boundary),) = ezdwg.raw.decode_wipeouts(path)
+
+    assert handle == 0x44D
+    assert display_flags == 7
+    assert clipping is True
+    assert boundary == pytest.approx(
+        [
+            (271.9211288808192, 3.988123351850959, 0.0),
+            (298.1133448313244, 4.23977177951258, 0.0),
+            (295.72078612087466, 26.51068675323006, 0.0),
+            (272.6766747285269, 35.69586627792364, 0.0),
+        ]
+    )
+
+
+@pytest.mark.parametrize("name", ["sample_AC1027.dwg", "sample_AC1032.dwg"])
+def test_decode_multileaders_reads_r2013_leaders_and_text(name: str) -> None:
+    path = str(ROOT / "test_dwg" / "acadsharp" / name)
+    rows = ezdwg.raw.decode_multileaders(path)
+
+    assert len(rows) == 15
+    assert all(content_type == "mtext" for _h, content_type, _t, _l, _r in rows)
+    handle, _content_type, text, location, leaders = rows[0]
+    assert handle == 0x7B8
+    assert text == "MLeader text, hello!"
+    assert location == pytest.approx((1083.7710451657636, 23.347047419901852, 0.0))
+    ((landing_point, dogleg_vector, dogleg_length, lines),) = leaders
+    assert landing_point == pytest.approx((1083.3210451657637, 23.239243872835004, 0.0))
+    assert dogleg_vector == pytest.approx((1.0, 0.0, 0.0))
+    assert dogleg_length == pytest.approx(0.36)
+    assert lines == [[pytest.approx((1050.2415627732776, 8.17554702864777, 0.0))]]
+    assert {text for _h, _c, text, _l, _r in rows} == {
+        "MLeader text, hello!",
+        "MULTILEADER TEST",
+        "MULTILEADER\\PTEST\\P123",
+    }
+
+
+def test_list_images_is_empty_without_image_class() -> None:
+    path = str(ROOT / "test_dwg/line_2000.dwg")
+    assert ezdwg.raw.list_images(path) == []
+
+
+@pytest.mark.parametrize("name", ["line_R14.dwg", "line_2004.dwg", "line_2013.dwg"])
+def test_list_vports_returns_active_view(name: str) -> None:
+    rows = ezdwg.raw.list_vports(str(ROOT / "test_dwg" / name))
+    active = next(row for row in rows if row[1] and row[1].upper() == "*ACTIVE")
+    handle, _, lower_left, upper_right, center, height, aspect, _, direction, grid, snap = active
+
+    assert handle > 0
+    assert lower_left == (0.0, 0.0)
+    assert upper_right == (1.0, 1.0)
+    assert center == pytest.approx((292.836, 148.5), abs=1.0e-3)
+    assert height == pytest.approx(288.0653526970954)
+    assert aspect == pytest.approx(2.033190578158458)
+    assert direction == (0.0, 0.0, 1.0)
+    assert grid[1] == (10.0, 10.0)
+    assert snap[2] == (10.0, 10.0)
+
+
+@pytest.mark.parametrize("name", ["line_R14.dwg", "line_2004.dwg", "line_2013.dwg"])
+def test_list_appids_includes_acad(name: str) -> None:
+    rows = ezdwg.raw.list_appids(str(ROOT / "test_dwg" / name))
+    handles = [handle for handle, _ in rows]
+
+    assert "ACAD" in {app_name for _, app_name in rows}
+    assert all(handle > 0 for handle in handles)
+    assert len(set(handles)) == len(handles)
+
+
+def test_decode_entity_xdata_keys_blocks_by_appid() -> None:
+    path = str(ROOT / "test_dwg/acadsharp/BLOCKPOINTPARAMETER.dwg")
+    appids = {handle for handle, _ in ezdwg.raw.list_appids(path)}
+    rows = ezdwg.raw.decode_entity_xdata(path)
+
+    assert rows
+    for handle, type_name, blocks in rows:
+        assert handle > 0
+        assert isinstance(type_name, str)
+        assert blocks
+        for app_handle, values in blocks:
+            assert app_handle in appids
+            assert [code for code, _ in values] == [1070, 1071, 1005]
+            assert values[2][1] == handle
+    assert len(ezdwg.raw.decode_entity_xdata(path, limit=1)) == 1
+    assert ezdwg.raw.decode_entity_xdata(str(ROOT / "test_dwg/line_2000.dwg")) == []
+
+
+def test_decode_entity_xdata_types_each_group() -> None:
+    path = str(ROOT / "test_dwg/acadsharp/sample_AC1027.dwg")
+    groups = [
+        (code, value)
+        for _, _, blocks in ezdwg.raw.decode_entity_xdata(path)
+        for _, group in blocks
+        for code, value in group
+    ]
+    values = dict(groups)
+
+    assert "hello this is my string" in {value for code, value in groups if code == 1000}
+    assert {value for code, value in groups if code == 1002} == {"{", "}"}
+    assert isinstance(values[1005], int)
+    assert isinstance(values[1070], int)
+    assert isinstance(values[1071], int)
+    assert isinstance(values[1040], float)
+    for code in (1010, 1011, 1012, 1013):
+        assert len(values[code]) == 3
+
+
+def test_decode_cache_reopens_unchanged_file(tmp_path: Path) -> None:
+    ezdwg.raw.set_decode_cache_dir(None)
+    sample = tmp_path / "line_2004.dwg"
+    sample.write_bytes((ROOT / "test_dwg" / "line_2004.dwg").read_bytes())
+    expected = [(e.dxftype, e.handle) for e in ezdwg.read(str(sample)).modelspace().query()]
+    try:
+        doc = ezdwg.read(str(sample), cache_dir=str(tmp_path / "cache"))
+    except NotImplementedError:
+        pytest.skip("built without the cache feature")
+    try:
+        assert [(e.dxftype, e.handle) for e in doc.modelspace().query()] == expected
+        assert len(list((tmp_path / "cache").glob("*.ezdwgcache"))) == 1
+        doc = ezdwg.read(str(sample))
+        assert [(e.dxftype, e.handle) for e in doc.modelspace().query()] == expected
+    finally:
+        ezdwg.raw.set_decode_cache_dir(None)
+
+
+def test_parse_config_presets_apply_to_the_document() -> None:
+    path = str(ROOT / "test_dwg" / "line_2004.dwg")
+    expected = [(e.dxftype, e.handle) for e in ezdwg.read(path).modelspace().query()]
+    for preset in ("strict", "tolerant", "fast_preview"):
+        doc = ezdwg.read(path, preset=preset)
+        assert [(e.dxftype, e.handle) for e in doc.modelspace().query()] == expected
+    with pytest.raises(ValueError, match="unknown parse config preset"):
+        ezdwg.raw.configure(path, "lenient")
+
+
+def test_parse_config_dict_sets_limits() -> None:
+    path = str(ROOT / "test_dwg" / "line_2004.dwg")
+    doc = ezdwg.read(path, config={"max_objects": 1})
+    with pytest.raises(ValueError, match="object count exceeds limit 1"):
+        list(doc.modelspace().query())
+    # The limit stays with that document; later reads and raw calls decode as before.
+    assert list(ezdwg.read(path).modelspace().query())
+    assert ezdwg.raw.decode_line_entities(path)
+    with pytest.raises(ValueError, match="object count exceeds limit 1"):
+        ezdwg.raw.decode_line_entities(doc.decode_source)
+    ezdwg.read(path, preset="strict", config={"max_objects": 1_000_000})
+    with pytest.raises(ValueError, match="unknown parse config field"):
+        ezdwg.raw.configure(path, config={"tolerant": True})
+
+
+def test_parse_config_dict_accepts_every_field() -> None:
+    path = str(ROOT / "test_dwg" / "line_2004.dwg")
+    source = ezdwg.raw.configure(
+        path,
+        config={
+            "strict": False,
+            "max_recursion": 64,
+            "max_objects": 1_000_000,
+            "max_section_bytes": 1 << 30,
+            "vertex_owner_fallback": True,
+            "retain_objects_buffer": True,
+            "point_epsilon": 1e-6,
+            "space_filter": "model",
+            "strict_dimensions": False,
+            "scan_recovery": False,
+            "arc_segments": 32,
+            "arc_max_sagitta": None,
+        },
+    )
+    assert ezdwg.raw.decode_line_entities(source) == ezdwg.raw.decode_line_entities(path)
+    with pytest.raises(ValueError, match="unknown space filter"):
+        ezdwg.raw.configure(path, config={"space_filter": "layout"})
+
+
+def test_extract_all_text_matches_text_decoder() -> None:
+    path = str(ROOT / "test_dwg/text_2000.dwg")
+    texts = [row[1] for row in ezdwg.raw.decode_text_entities(path) if row[1]]
+    rows = ezdwg.raw.extract_all_text(path)
+
+    assert [row[3] for row in rows if row[1] == "TEXT"] == texts
+    for handle, type_name, layer_name, text, insertion in rows:
+        assert isinstance(handle, int)
+        assert layer_name is None or isinstance(layer_name, str)
+        assert len(insertion) == 3
+
+
+def test_extract_all_text_strips_mtext_codes() -> None:
+    path = str(ROOT / "test_dwg/mtext_2004.dwg")
+    raw_rows = ezdwg.raw.extract_all_text(path, strip_formatting=False)
+    plain_rows = ezdwg.raw.extract_all_text(path)
+
+    assert [row[0] for row in raw_rows] == [row[0] for row in plain_rows]
+    for row in plain_rows:
+        assert "\\P" not in row[3]
+
+
+def test_extract_all_text_reports_progress_and_stops() -> None:
+    path = str(ROOT / "test_dwg/text_2000.dwg")
+    calls = []
+    rows = ezdwg.raw.extract_all_text(path, progress=lambda done, total: calls.append((done, total)))
+    total = len(ezdwg.raw.list_object_map_entries(path))
+
+    assert rows == ezdwg.raw.extract_all_text(path)
+    assert calls[0] == (0, total)
+    assert calls[-1] == (total, total)
+    assert ezdwg.raw.extract_all_text(path, progress=lambda done, total: False) == []
+
+    def fail(done: int, total: int) -> None:
+        raise RuntimeError("stop")
+
+    with pytest.raises(RuntimeError, match="stop"):
+        ezdwg.raw.decode_entity_xdata(path, progress=fail)
+
+    def cancel(done: int, total: int) -> None:
+        raise ezdwg.raw.Cancelled("closed by user")
+
+    with pytest.raises(ezdwg.raw.Cancelled):
+        ezdwg.raw.extract_all_text(path, progress=cancel)
+
+
+def test_aci_to_rgb_uses_standard_palette() -> None:
+    assert ezdwg.raw.aci_to_rgb(1) == (255, 0, 0)
+    assert ezdwg.raw.aci_to_rgb(11) == (255, 127, 127)
+    assert ezdwg.raw.aci_to_rgb(250) == (51, 51, 51)
+    assert ezdwg.raw.aci_to_rgb(256) == ezdwg.raw.aci_to_rgb(7)
+
+
+@pytest.mark.parametrize("name", ["line_2000", "line_2004", "line_2007", "line_2013"])
+def test_aux_header_reports_saves_and_timestamps(name: str) -> None:
+    path = str(ROOT / f"test_dwg/{name}.dwg")
+    version, _, created_version, saves, created, updated, _ = ezdwg.raw.aux_header(path)
+
+    assert version == created_version[0]
+    assert saves >= 1
+    assert 2440587.5 < created <= updated
+
+
+@pytest.mark.parametrize(
+    ("name", "units"),
+    [
+        ("line_R14", "metric"),
+        ("line_2000", "metric"),
+        ("line_2004", "metric"),
+        ("line_2007", "metric"),
+        ("line_2013", "metric"),
+        ("acadsharp/sample_AC1032", "imperial"),
+        ("insert_2004", None),
+    ],
+)
+def test_measurement_units_reads_the_template_section(name: str, units: str | None) -> None:
+    assert ezdwg.raw.measurement_units(str(ROOT / f"test_dwg/{name}.dwg")) == units
+
+
+@pytest.mark.parametrize("version", ["2004", "2007", "2010", "2013"])
+def test_decode_point_entities_rows(version: str) -> None:
+    for sample in ("point2d", "point3d"):
+        rows = ezdwg.raw.decode_point_entities(str(ROOT / "test_dwg" / f"{sample}_{version}.dwg"))
+        assert rows, sample
+        for handle, x, y, z, thickness, x_axis_angle in rows:
+            assert handle > 0
+            assert all(math.isfinite(value) for value in (x, y, z, x_axis_angle))
+            assert thickness == 0.0
+
+
+@pytest.mark.parametrize("version", ["2004", "2007", "2010", "2013"])
+def test_decode_circle_and_ellipse_rows(version: str) -> None:
+    circles = ezdwg.raw.decode_circle_entities(str(ROOT / "test_dwg" / f"circle_{version}.dwg"))
+    assert circles
+    for handle, cx, cy, cz, radius, thickness, extrusion in circles:
+        assert handle > 0
+        assert radius > 0.0
+        assert thickness == 0.0
+        assert extrusion == pytest.approx((0.0, 0.0, 1.0))
+    ellipses = ezdwg.raw.decode_ellipse_entities(str(ROOT / "test_dwg" / f"ellipse_{version}.dwg"))
+    assert ellipses
+    for _handle, _center, major_axis, extrusion, axis_ratio, _start, _end in ellipses:
+        assert math.hypot(*major_axis) > 0.0
+        assert 0.0 < axis_ratio <= 1.0
+        assert extrusion == pytest.approx((0.0, 0.0, 1.0))
+
+
+def test_decode_tables_is_empty_without_table_class() -> None:
+    path = str(ROOT / "test_dwg/insert_2004.dwg")
+    assert ezdwg.raw.decode_tables(path) == []
+
+
+@pytest.mark.parametrize("name", ["sample_AC1027.dwg", "sample_AC1032.dwg"])
+def test_decode_tables_reads_r2013_table_content(name: str) -> None:
+    path = str(ROOT / "test_dwg" / "acadsharp" / name)
+    rows = ezdwg.raw.decode_tables(path)
+
+    assert [row[0] for row in rows] == [0x528, 0xA35]
+    handle, insertion, row_count, column_count, row_heights, column_widths, cells = rows[0]
+    assert insertion == pytest.approx((774.865723661128, 48.538786038098586, 0.0))
+    assert (row_count, column_count) == (7, 3)
+    assert len(row_heights) == 7
+    assert row_heights[0] == pytest.approx(7.491035367212169)
+    assert column_widths == pytest.approx([34.19820493727295] * 3)
+    assert cells[0] == ["Table sample", "", ""]
+    assert cells[1] == ["Text", "Text", "Text"]
+    assert cells[6] == ["cell text", "cell text", "cell text"]
+
+    _handle, _insertion, row_count, column_count, _heights, _widths, cells = rows[1]
+    assert (row_count, column_count) == (4, 5)
+    assert cells[0][0] == "Hello this is a title"
+    assert cells[1][:3] == ["A2 cell", "This is my content", "cell format 1"]
+    # Non-string values fall back to their formatted display string.
+    assert cells[2][:2] == ["0.500000", "60"]
+    assert cells[3][:2] == ["35.00%", "$100.00"]
+
+
+def test_decode_insert_entities_with_block_names_the_inserted_block() -> None:
+    path = str(ROOT / "test_dwg/insert_2004.dwg")
+    plain = ezdwg.raw.decode_insert_entities(path)
+    rows = ezdwg.raw.decode_insert_entities_with_block(path)
+
+    assert len(rows) == len(plain) == 1
+    handle, position, scale, rotation, block_handle, block_name = rows[0]
+    assert (handle, *position, *scale, rotation) == plain[0]
+    assert block_handle is not None
+    assert block_name == "BLK1"
+
+
+def test_insert_transforms_compose_plain_insert_fields() -> None:
+    path = str(ROOT / "test_dwg/insert_2004.dwg")
+    plain = ezdwg.raw.decode_insert_entities(path)
+    rows = ezdwg.raw.insert_transforms(path)
+
+    assert [row[0] for row in rows] == [row[0] for row in plain]
+    for (handle, block_handle, block_name, matrix), insert in zip(rows, plain):
+        _, x, y, z, sx, sy, sz, rotation = insert
+        assert block_handle is not None
+        assert block_name == "BLK1"
+        assert len(matrix) == 16
+        assert matrix[3] == pytest.approx(x)
+        assert matrix[7] == pytest.approx(y)
+        assert matrix[11] == pytest.approx(z)
+        assert matrix[0] == pytest.approx(sx * math.cos(rotation))
+        assert matrix[4] == pytest.approx(sx * math.sin(rotation))
+        assert matrix[1] == pytest.approx(-sy * math.sin(rotation))
+        assert matrix[10] == pytest.approx(sz)
+        assert matrix[12:] == [0.0, 0.0, 0.0, 1.0]
+
+
+def test_merge_lines_to_polylines_covers_every_line() -> None:
+    path = str(ROOT / "test_dwg/line_2000.dwg")
+    lines = ezdwg.raw.decode_line_entities(path)
+    chains = ezdwg.raw.merge_lines_to_polylines(path)
+
+    assert sorted(h for handles, _, _ in chains for h in handles) == sorted(
+        row[0] for row in lines
+    )
+    for handles, closed, points in chains:
+        assert len(points) == len(handles) + 1
+        assert closed == (points[0] == points[-1])
+    with pytest.raises(ValueError):
+        ezdwg.raw.merge_lines_to_polylines(path, tolerance=0.0)
+
+
+@pytest.mark.parametrize(
+    ("relative_path", "handles_label"),
+    [
+        ("test_dwg/line_2000.dwg", "ObjectMap[0]"),
+        ("test_dwg/line_2004.dwg", "AcDb:Handles[0]"),
+        ("test_dwg/line_2007.dwg", "AcDb:Handles[0]"),
+        ("test_dwg/line_2013.dwg", "AcDb:Handles[0]"),
+    ],
+)
+def test_section_crcs_report_stored_values(relative_path: str, handles_label: str) -> None:
+    crcs = dict(ezdwg.raw.section_crcs(str(ROOT / relative_path)))
+
+    assert handles_label in crcs
+    assert all(0 <= value <= 0xFFFF for value in crcs.values())
+
+
+def test_compute_crc_matches_stored_object_map_crc() -> None:
+    data = (ROOT / "test_dwg/line_2000.dwg").read_bytes()
+    path = str(ROOT / "test_dwg/line_2000.dwg")
+    offset = next(
+        offset
+        for name, offset, _size in ezdwg.raw.list_section_locators(path)
+        if name == "ObjectMap"
+    )
+    block_size = int.from_bytes(data[offset : offset + 2], "big")
+    stored = dict(ezdwg.raw.section_crcs(path))["ObjectMap[0]"]
+
+    assert ezdwg.raw.compute_crc(data[offset : offset + block_size], 0xC0C1) == stored
+    assert ezdwg.raw.compute_crc(b"123456789") == 0xBB3D
+
+
+@pytest.mark.parametrize(
+    "relative_path",
+    [
+        "test_dwg/line_2000.dwg",
+        "test_dwg/line_2004.dwg",
+        "test_dwg/line_2007.dwg",
+    ],
+)
+def test_read_section_bytes_by_name_matches_index_lookup(relative_path: str) -> None:
+    path = str(ROOT / relative_path)
+    names = [name for name, _offset, _size in ezdwg.raw.list_section_locators(path)]
+    handles_name = "ObjectMap" if "ObjectMap" in names else "AcDb:Handles"
+    expected = ezdwg.raw.read_section_bytes(path, names.index(handles_name))
+
+    assert ezdwg.raw.read_section_bytes_by_name(path, "AcDb:Handles") == expected
+    assert ezdwg.raw.read_section_bytes_by_name(path, handles_name) == expected
+    with pytest.raises(ValueError, match="section not found"):
+        ezdwg.raw.read_section_bytes_by_name(path, "AcDb:NoSuchSection")
+
+
+def test_list_sections_detailed_reports_r2004_container_metadata() -> None:
+    path = str(ROOT / "test_dwg/line_2004.dwg")
+    sections = {row[0]: row for row in ezdwg.raw.list_sections_detailed(path)}
+    locators = {name: size for name, _offset, size in ezdwg.raw.list_section_locators(path)}
+
+    _name, size, compressed_size, page_count, encrypted, compression = sections[
+        "AcDb:AcDbObjects"
+    ]
+    assert size == locators["AcDb:AcDbObjects"]
+    assert page_count >= 1
+    assert 0 < compressed_size < size
+    assert encrypted == 0
+    assert compression == 2
+
+
+def test_list_sections_detailed_reports_r2000_sections_as_uncompressed() -> None:
+    path = str(ROOT / "test_dwg/line_2000.dwg")
+    rows = ezdwg.raw.list_sections_detailed(path)
+    locators = ezdwg.raw.list_section_locators(path)
+
+    assert [(row[0], row[1]) for row in rows] == [(name, size) for name, _offset, size in locators]
+    assert all(row[1] == row[2] and row[3] == 1 and row[5] == 1 for row in rows)
+
+
+def test_list_sections_detailed_reports_r2007_compression_like_r2004() -> None:
+    path = str(ROOT / "test_dwg/line_2007.dwg")
+    sections = {row[0]: row for row in ezdwg.raw.list_sections_detailed(path)}
+
+    assert sections["AcDb:AcDbObjects"][5] == 2
+    assert sections["AcDb:Preview"][5] == 1
+    assert all(row[5] == (2 if row[2] < row[1] else 1) for row in sections.values())
+
+
+def test_object_type_histogram_counts_match_object_headers() -> None:
+    path = str(ROOT / "test_dwg/line_2004.dwg")
+    histogram = ezdwg.raw.object_type_histogram(path)
+    headers = ezdwg.raw.list_object_headers_with_type(path)
+
+    assert sum(count for _code, _name, count in histogram) == len(headers)
+    counts = [count for _code, _name, count in histogram]
+    assert counts == sorted(counts, reverse=True)
+    by_name = {name: count for _code, name, count in histogram}
+    assert by_name["LINE"] == 1
+
+
+def test_drawing_stats_sum_up_objects_tables_and_sections() -> None:
+    path = str(ROOT / "test_dwg/line_2004.dwg")
+    (
+        version,
+        object_count,
+        entity_count,
+        layer_count,
+        block_count,
+        sections,
+        section_bytes,
+        compressed_section_bytes,
+        unsupported_types,
+    ) = ezdwg.raw.drawing_stats(path)
+
+    assert version == "AC1018"
+    assert object_count == len(ezdwg.raw.list_object_map_entries(path))
+    assert (entity_count, layer_count, block_count) == (7, 1, 3)
+    assert sections == ezdwg.raw.list_sections_detailed(path)
+    assert section_bytes == sum(row[1] for row in sections)
+    assert compressed_section_bytes == sum(row[2] for row in sections)
+    assert unsupported_types == []
+
+
+def test_analyze_reports_structural_anomalies_without_raising() -> None:
+    clean = ezdwg.raw.analyze(str(ROOT / "test_dwg/line_2004.dwg"))
+    assert {kind for kind, _message, _offset in clean} <= {"handle_gap"}
+
+    data = (ROOT / "test_dwg/line_2004.dwg").read_bytes()
+    truncated = ezdwg.raw.analyze(data[: len(data) // 2])
+    assert any(kind == "parse_error" for kind, _message, _offset in truncated)
+
+
+def test_raw_api_accepts_bytes_and_file_like_sources() -> None:
+    import io
+
+    path = ROOT / "test_dwg/line_2000.dwg"
+    data = path.read_bytes()
+    expected = ezdwg.raw.decode_line_entities(str(path))
+
+    assert ezdwg.raw.decode_line_entities(path) == expected
+    assert ezdwg.raw.decode_line_entities(data) == expected
+    assert ezdwg.raw.decode_line_entities(bytearray(data)) == expected
+    assert ezdwg.raw.decode_line_entities(io.BytesIO(data)) == expected
+    assert ezdwg.raw.detect_version(io.BytesIO(data)) == "AC1015"
+
+
+def test_raw_api_rejects_text_mode_file_like() -> None:
+    import io
+
+    with pytest.raises(TypeError, match="binary mode"):
+        ezdwg.raw.detect_version(io.StringIO("AC1015"))